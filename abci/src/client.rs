@@ -56,9 +56,11 @@ macro_rules! perform {
     ($self:expr, $type:ident, $req:expr) => {
         match $self.perform(request::Value::$type($req))? {
             response::Value::$type(r) => Ok(r),
-            r => {
-                Err(Error::unexpected_server_response_type(stringify!($type).to_string(), r).into())
-            }
+            r => Err(Error::unexpected_server_response_type(
+                stringify!($type).to_string(),
+                Box::new(r),
+            )
+            .into()),
         }
     };
 }
@@ -26,7 +26,7 @@ define_error! {
         UnexpectedServerResponseType
             {
                 expected: String,
-                got: Value,
+                got: Box<Value>,
             }
             | e | {
                 format_args!("unexpected server response type: expected {0}, but got {1:?}",
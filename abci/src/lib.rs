@@ -10,6 +10,8 @@
 //! by a full Tendermint node.
 //!
 //! ```rust
+//! # #[cfg(all(feature = "client", feature = "kvstore-app"))]
+//! # fn main() {
 //! use tendermint_abci::{KeyValueStoreApp, ServerBuilder, ClientBuilder};
 //! use tendermint_proto::abci::{RequestEcho, RequestDeliverTx, RequestQuery};
 //!
@@ -36,7 +38,7 @@
 //! // Deliver a transaction and then commit the transaction
 //! client
 //!     .deliver_tx(RequestDeliverTx {
-//!         tx: "test-key=test-value".as_bytes().to_owned(),
+//!         tx: "test-key=test-value".into(),
 //!     })
 //!     .unwrap();
 //! client.commit().unwrap();
@@ -51,6 +53,9 @@
 //!     })
 //!     .unwrap();
 //! assert_eq!(res.value, "test-value".as_bytes().to_owned());
+//! # }
+//! # #[cfg(not(all(feature = "client", feature = "kvstore-app")))]
+//! # fn main() {}
 //! ```
 
 mod application;
@@ -11,7 +11,9 @@
 
 mod utils;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::time::Duration;
 use tendermint::Time;
 use tendermint_light_client::components::verifier::{ProdVerifier, Verifier};
@@ -27,15 +29,46 @@ use wasm_bindgen::JsValue;
 static ALLOC: wee_alloc::WeeAlloc<'_> = wee_alloc::WeeAlloc::INIT;
 
 /// Check whether a given untrusted block can be trusted.
+///
+/// The outcome of the verification is returned as either `{ "Ok": <verdict> }`
+/// or `{ "Err": <error> }`. An exception is only thrown if the outcome itself
+/// cannot be converted into a JavaScript value.
 #[wasm_bindgen]
-pub fn verify(untrusted: &JsValue, trusted: &JsValue, options: &JsValue, now: &JsValue) -> JsValue {
+pub fn verify(
+    untrusted: &JsValue,
+    trusted: &JsValue,
+    options: &JsValue,
+    now: &JsValue,
+) -> Result<JsValue, JsValue> {
     let result = deserialize_params(untrusted, trusted, options, now).map(
         |(untrusted, trusted, options, now)| {
             let verifier = ProdVerifier::default();
             verifier.verify(&untrusted, &trusted, &options, now)
         },
     );
-    JsValue::from_serde(&result).unwrap()
+    to_js(&result)
+}
+
+/// Convert a value into a JavaScript value, going through its JSON representation.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// Convert the given parameter from a JavaScript value, going through its JSON
+/// representation.
+fn from_js<T: DeserializeOwned>(value: &JsValue, param: &str) -> Result<T, Error> {
+    let serialization = |msg: String| Error::Serialization {
+        param: param.to_string(),
+        msg,
+    };
+
+    let json = js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|json| JsValue::from(json).as_string())
+        .ok_or_else(|| serialization("not a JSON value".to_string()))?;
+
+    serde_json::from_str(&json).map_err(|e| serialization(e.to_string()))
 }
 
 fn deserialize_params(
@@ -44,28 +77,10 @@ fn deserialize_params(
     options: &JsValue,
     now: &JsValue,
 ) -> Result<(LightBlock, LightBlock, Options, Time), Error> {
-    let untrusted = untrusted.into_serde().map_err(|e| Error::Serialization {
-        param: "untrusted".to_string(),
-        msg: e.to_string(),
-    })?;
-
-    let trusted = trusted.into_serde().map_err(|e| Error::Serialization {
-        param: "trusted".to_string(),
-        msg: e.to_string(),
-    })?;
-
-    let options = options
-        .into_serde::<JsOptions>()
-        .map(Into::into)
-        .map_err(|e| Error::Serialization {
-            param: "options".to_string(),
-            msg: e.to_string(),
-        })?;
-
-    let now = now.into_serde().map_err(|e| Error::Serialization {
-        param: "now".to_string(),
-        msg: e.to_string(),
-    })?;
+    let untrusted = from_js(untrusted, "untrusted")?;
+    let trusted = from_js(trusted, "trusted")?;
+    let options = from_js::<JsOptions>(options, "options").and_then(Options::try_from)?;
+    let now = from_js(now, "now")?;
 
     Ok((untrusted, trusted, options, now))
 }
@@ -77,6 +92,10 @@ pub enum Error {
     /// A serialization/deserialization error occurred.
     #[serde(rename = "serialization")]
     Serialization { param: String, msg: String },
+
+    /// The verification options are not valid.
+    #[serde(rename = "invalid_options")]
+    InvalidOptions { msg: String },
 }
 
 // Simplified options supplied from JavaScript.
@@ -85,15 +104,25 @@ pub struct JsOptions {
     pub trust_threshold: (u64, u64),
     pub trusting_period: u64,
     pub clock_drift: u64,
+    /// The maximum amount by which headers may be ahead of the local clock,
+    /// in seconds, if different from the clock drift
+    #[serde(default)]
+    pub forward_clock_drift: Option<u64>,
 }
 
-impl From<JsOptions> for Options {
-    fn from(o: JsOptions) -> Self {
+impl TryFrom<JsOptions> for Options {
+    type Error = Error;
+
+    fn try_from(o: JsOptions) -> Result<Self, Self::Error> {
         let (num, den) = o.trust_threshold;
-        Self {
-            trust_threshold: TrustThreshold::new(num, den).unwrap(),
+        Ok(Self {
+            trust_threshold: TrustThreshold::new(num, den)
+                .map_err(|e| Error::InvalidOptions { msg: e.to_string() })?,
             trusting_period: Duration::from_secs(o.trusting_period),
             clock_drift: Duration::from_secs(o.clock_drift),
-        }
+            max_bisection_steps: None,
+            trust_threshold_overrides: Vec::new(),
+            forward_clock_drift: o.forward_clock_drift.map(Duration::from_secs),
+        })
     }
 }
@@ -3,6 +3,8 @@
 #![cfg(target_arch = "wasm32")]
 
 extern crate wasm_bindgen_test;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use tendermint::Time;
 use tendermint_light_client::components::verifier::Verdict;
 use tendermint_light_client::types::LightBlock;
@@ -161,11 +163,10 @@ fn successful_verification() {
     let (untrusted_block, trusted_block) = test_blocks();
     let options = test_options();
     // Choose a "now" value within the trusting period
-    let now =
-        JsValue::from_serde(&Time::parse_from_rfc3339("1970-01-07T00:00:00Z").unwrap()).unwrap();
-    let js_result = verify(&untrusted_block, &trusted_block, &options, &now);
+    let now = to_js(&Time::parse_from_rfc3339("1970-01-07T00:00:00Z").unwrap()).unwrap();
+    let js_result = verify(&untrusted_block, &trusted_block, &options, &now).unwrap();
     console_log!("js_result = {:?}", js_result);
-    let verdict = JsValue::into_serde::<Result<Verdict, Error>>(&js_result)
+    let verdict = from_js::<Result<Verdict, Error>>(&js_result)
         .unwrap()
         .unwrap();
     assert_eq!(verdict, Verdict::Success);
@@ -176,13 +177,12 @@ fn failed_verification_outside_trusting_period() {
     let (untrusted_block, trusted_block) = test_blocks();
     let options = test_options();
     // Choose a "now" value outside the trusting period
-    let now =
-        JsValue::from_serde(&Time::parse_from_rfc3339("1970-01-16T00:00:00Z").unwrap()).unwrap();
-    let js_result = verify(&untrusted_block, &trusted_block, &options, &now);
+    let now = to_js(&Time::parse_from_rfc3339("1970-01-16T00:00:00Z").unwrap()).unwrap();
+    let js_result = verify(&untrusted_block, &trusted_block, &options, &now).unwrap();
     console_log!("js_result = {:?}", js_result);
     // The result is Ok because we successfully obtained a verdict, even if the
     // verdict isn't Verdict::Success.
-    let verdict = JsValue::into_serde::<Result<Verdict, Error>>(&js_result)
+    let verdict = from_js::<Result<Verdict, Error>>(&js_result)
         .unwrap()
         .unwrap();
     match verdict {
@@ -191,19 +191,82 @@ fn failed_verification_outside_trusting_period() {
     }
 }
 
+#[wasm_bindgen_test]
+fn failed_verification_with_invalid_options() {
+    let (untrusted_block, trusted_block) = test_blocks();
+    let options = to_js(&JsOptions {
+        trust_threshold: (2, 1),
+        trusting_period: 1209600,
+        clock_drift: 5,
+        forward_clock_drift: None,
+    })
+    .unwrap();
+    let now = to_js(&Time::parse_from_rfc3339("1970-01-07T00:00:00Z").unwrap()).unwrap();
+    let js_result = verify(&untrusted_block, &trusted_block, &options, &now).unwrap();
+    let result = from_js::<Result<Verdict, Error>>(&js_result).unwrap();
+    match result {
+        Err(Error::InvalidOptions { .. }) => {}
+        _ => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[wasm_bindgen_test]
+fn failed_verification_with_invalid_block() {
+    let (_, trusted_block) = test_blocks();
+    let options = test_options();
+    let now = to_js(&Time::parse_from_rfc3339("1970-01-07T00:00:00Z").unwrap()).unwrap();
+    let js_result = verify(&JsValue::from_str("block"), &trusted_block, &options, &now).unwrap();
+    let result = from_js::<Result<Verdict, Error>>(&js_result).unwrap();
+    match result {
+        Err(Error::Serialization { param, .. }) => assert_eq!(param, "untrusted"),
+        _ => panic!("unexpected result: {:?}", result),
+    }
+}
+
+#[wasm_bindgen_test]
+fn failed_verification_ahead_of_forward_clock_drift() {
+    let (untrusted_block, trusted_block) = test_blocks();
+    let options = to_js(&JsOptions {
+        trust_threshold: (1, 3),
+        trusting_period: 1209600,
+        clock_drift: 5,
+        forward_clock_drift: Some(0),
+    })
+    .unwrap();
+    // The untrusted block is at 1970-01-01T00:00:04Z
+    let now = to_js(&Time::parse_from_rfc3339("1970-01-01T00:00:03Z").unwrap()).unwrap();
+    let js_result = verify(&untrusted_block, &trusted_block, &options, &now).unwrap();
+    let verdict = from_js::<Result<Verdict, Error>>(&js_result)
+        .unwrap()
+        .unwrap();
+    match verdict {
+        Verdict::Invalid(_) => {}
+        _ => panic!("unexpected verdict: {:?}", verdict),
+    }
+}
+
 fn test_blocks() -> (JsValue, JsValue) {
     let untrusted_block =
-        JsValue::from_serde(&serde_json::from_str::<LightBlock>(UNTRUSTED_BLOCK).unwrap()).unwrap();
-    let trusted_block =
-        JsValue::from_serde(&serde_json::from_str::<LightBlock>(TRUSTED_BLOCK).unwrap()).unwrap();
+        to_js(&serde_json::from_str::<LightBlock>(UNTRUSTED_BLOCK).unwrap()).unwrap();
+    let trusted_block = to_js(&serde_json::from_str::<LightBlock>(TRUSTED_BLOCK).unwrap()).unwrap();
     (untrusted_block, trusted_block)
 }
 
 fn test_options() -> JsValue {
-    JsValue::from_serde(&JsOptions {
+    to_js(&JsOptions {
         trust_threshold: (1, 3),
         trusting_period: 1209600, // 2 weeks
         clock_drift: 5,           // 5 seconds
+        forward_clock_drift: None,
     })
     .unwrap()
 }
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    js_sys::JSON::parse(&serde_json::to_string(value).unwrap())
+}
+
+fn from_js<T: DeserializeOwned>(value: &JsValue) -> Result<T, serde_json::Error> {
+    let json = js_sys::JSON::stringify(value).unwrap();
+    serde_json::from_str(&String::from(json))
+}
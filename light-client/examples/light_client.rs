@@ -90,6 +90,9 @@ fn make_instance(
         trust_threshold: TrustThreshold::default(),
         trusting_period: Duration::from_secs(36000),
         clock_drift: Duration::from_secs(1),
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let builder =
@@ -128,8 +131,8 @@ fn sync_cmd(opts: SyncOpts) -> Result<(), Box<dyn std::error::Error>> {
 
     loop {
         match handle.verify_to_highest() {
-            Ok(light_block) => {
-                println!("[info] synced to block {}", light_block.height());
+            Ok(verified) => {
+                println!("[info] synced to block {}", verified.light_block().height());
             }
             Err(err) => {
                 println!("[error] sync failed: {}", err);
@@ -5,6 +5,7 @@ use tendermint::block::Height;
 use tendermint::Hash;
 
 use crate::components::io::IoError;
+use crate::errors::Error as LightClientError;
 use crate::predicates::errors::VerificationError;
 
 define_error! {
@@ -37,6 +38,10 @@ define_error! {
             [ VerificationError ]
             | _ | { "invalid light block" },
 
+        InvalidOptions
+            [ LightClientError ]
+            | _ | { "invalid options" },
+
         NoTrustedStateInStore
             | _ | { "no trusted state in store" },
 
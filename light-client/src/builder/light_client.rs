@@ -169,12 +169,14 @@ impl LightClientBuilder<NoTrustedState> {
         let header = &light_block.signed_header.header;
         let now = self.clock.now();
 
+        self.options.validate().map_err(Error::invalid_options)?;
+
         self.predicates
             .is_within_trust_period(header, self.options.trusting_period, now)
             .map_err(Error::invalid_light_block)?;
 
         self.predicates
-            .is_header_from_past(header, self.options.clock_drift, now)
+            .is_header_from_past(header, self.options.max_forward_drift(), now)
             .map_err(Error::invalid_light_block)?;
 
         self.predicates
@@ -198,7 +200,8 @@ impl LightClientBuilder<HasTrustedState> {
             verification_trace: VerificationTrace::new(),
         };
 
-        let light_client = LightClient::from_boxed(
+        // The options were validated along with the trusted state
+        let light_client = LightClient::from_validated_boxed(
             self.peer_id,
             self.options,
             self.clock,
@@ -21,6 +21,8 @@ pub struct SupervisorBuilder<State> {
     instances: PeerListBuilder<Instance>,
     addresses: PeerListBuilder<tendermint_rpc::Url>,
     evidence_reporting_timeout: Option<Duration>,
+    detection_interval: Option<Duration>,
+    skip_fork_detection: bool,
     #[allow(dead_code)]
     state: State,
 }
@@ -32,6 +34,8 @@ impl<Current> SupervisorBuilder<Current> {
             instances: self.instances,
             addresses: self.addresses,
             evidence_reporting_timeout: self.evidence_reporting_timeout,
+            detection_interval: self.detection_interval,
+            skip_fork_detection: self.skip_fork_detection,
             state,
         }
     }
@@ -41,6 +45,13 @@ impl<Current> SupervisorBuilder<Current> {
         self.evidence_reporting_timeout = timeout;
         self
     }
+
+    /// Set the interval at which to periodically re-run fork detection
+    /// on the latest trusted block, when the supervisor is otherwise idle
+    pub fn detection_interval(mut self, interval: Option<Duration>) -> Self {
+        self.detection_interval = interval;
+        self
+    }
 }
 
 impl Default for SupervisorBuilder<Init> {
@@ -56,6 +67,8 @@ impl SupervisorBuilder<Init> {
             instances: PeerListBuilder::default(),
             addresses: PeerListBuilder::default(),
             evidence_reporting_timeout: None,
+            detection_interval: None,
+            skip_fork_detection: false,
             state: Init,
         }
     }
@@ -105,6 +118,19 @@ impl SupervisorBuilder<HasPrimary> {
 
         Ok(self.with_state(Done))
     }
+
+    /// Build the supervisor without any witness, trusting the blocks of the primary
+    /// without performing fork detection.
+    ///
+    /// **This is unsafe** and only meant for development and test setups with a single
+    /// full node. See [`Supervisor::danger_skip_fork_detection`] for more details.
+    ///
+    /// When the supervisor is constructed from [`SupervisorBuilder::inner`], the caller
+    /// is responsible for calling [`Supervisor::danger_skip_fork_detection`] itself.
+    pub fn danger_skip_fork_detection(mut self) -> SupervisorBuilder<Done> {
+        self.skip_fork_detection = true;
+        self.with_state(Done)
+    }
 }
 
 impl SupervisorBuilder<Done> {
@@ -113,13 +139,22 @@ impl SupervisorBuilder<Done> {
     #[cfg(feature = "rpc-client")]
     pub fn build_prod(self) -> Supervisor {
         let timeout = self.evidence_reporting_timeout;
+        let detection_interval = self.detection_interval;
+        let skip_fork_detection = self.skip_fork_detection;
         let (instances, addresses) = self.inner();
 
-        Supervisor::new(
+        let supervisor = Supervisor::new(
             instances,
             ProdForkDetector::default(),
             ProdEvidenceReporter::new(addresses.into_values(), timeout),
         )
+        .with_detection_interval(detection_interval);
+
+        if skip_fork_detection {
+            supervisor.danger_skip_fork_detection()
+        } else {
+            supervisor
+        }
     }
 
     /// Get the underlying list of instances and addresses.
@@ -8,6 +8,12 @@ pub trait Clock: Send + Sync {
     fn now(&self) -> Time;
 }
 
+impl<T: Clock + ?Sized> Clock for Box<T> {
+    fn now(&self) -> Time {
+        (**self).now()
+    }
+}
+
 /// Provides the current wall clock time.
 #[derive(Copy, Clone)]
 pub struct SystemClock;
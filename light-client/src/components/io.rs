@@ -1,6 +1,7 @@
 //! Provides an interface and a default implementation of the `Io` component
 
 use flex_error::{define_error, TraceError};
+use std::convert::TryInto;
 use std::time::Duration;
 
 #[cfg(feature = "rpc-client")]
@@ -16,6 +17,18 @@ type TimeoutError = flex_error::DisplayOnly<tokio::time::error::Elapsed>;
 #[cfg(not(feature = "tokio"))]
 type TimeoutError = flex_error::NoSource;
 
+#[cfg(feature = "grpc-client")]
+type GrpcStatusError = flex_error::DisplayOnly<tonic::Status>;
+
+#[cfg(not(feature = "grpc-client"))]
+type GrpcStatusError = flex_error::NoSource;
+
+#[cfg(feature = "grpc-client")]
+type GrpcTransportError = flex_error::DisplayOnly<tonic::transport::Error>;
+
+#[cfg(not(feature = "grpc-client"))]
+type GrpcTransportError = flex_error::NoSource;
+
 /// Type for selecting either a specific height or the latest one
 pub enum AtHeight {
     /// A specific height
@@ -41,6 +54,17 @@ define_error! {
             [ rpc::Error ]
             | _ | { "rpc error" },
 
+        HeightPruned
+            {
+                height: Height,
+                lowest_height: Height,
+            }
+            [ rpc::Error ]
+            | e | {
+                format_args!("height {0} is not available anymore, lowest available height is {1}",
+                    e.height, e.lowest_height)
+            },
+
         InvalidHeight
             | _ | {
                 "invalid height: given height must be greater than 0"
@@ -50,6 +74,25 @@ define_error! {
             [ tendermint::Error ]
             | _ | { "fetched validator set is invalid" },
 
+        InvalidSignedHeader
+            [ tendermint::Error ]
+            | _ | { "fetched signed header is invalid" },
+
+        MissingField
+            { field: &'static str }
+            | e | {
+                format_args!("fetched light block is missing field `{}`",
+                    e.field)
+            },
+
+        Grpc
+            [ GrpcStatusError ]
+            | _ | { "grpc error" },
+
+        GrpcTransport
+            [ GrpcTransportError ]
+            | _ | { "grpc transport error" },
+
         Timeout
             { duration: Duration }
             [ TimeoutError ]
@@ -73,12 +116,68 @@ impl IoErrorDetail {
             _ => None,
         }
     }
+
+    /// Whether this error means that the node pruned the requested height,
+    /// in which case the lowest height still available on the node is returned.
+    pub fn is_height_pruned(&self) -> Option<Height> {
+        match self {
+            Self::HeightPruned(e) => Some(e.lowest_height),
+            _ => None,
+        }
+    }
+}
+
+impl IoError {
+    /// Wrap the given RPC error, detecting when it means that the node
+    /// pruned the requested height.
+    pub fn from_rpc(error: rpc::Error) -> Self {
+        match pruned_heights(&error) {
+            Some((height, lowest_height)) => Self::height_pruned(height, lowest_height, error),
+            None => Self::rpc(error),
+        }
+    }
+}
+
+/// Extract the requested and lowest available heights from the error returned
+/// by Tendermint when a height has been pruned, ie.
+/// `height 5 is not available, lowest height is 10`.
+fn pruned_heights(error: &rpc::Error) -> Option<(Height, Height)> {
+    const MARKER: &str = " is not available, lowest height is ";
+
+    let response = match error.detail() {
+        rpc::error::ErrorDetail::Response(e) => &e.source,
+        _ => return None,
+    };
+
+    let message = response.data().unwrap_or_else(|| response.message());
+    let (before, after) = message.split_once(MARKER)?;
+
+    let height = before.rsplit(' ').next()?.parse::<u64>().ok()?;
+    let lowest_height = after
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+
+    Some((height.try_into().ok()?, lowest_height.try_into().ok()?))
 }
 
 /// Interface for fetching light blocks from a full node, typically via the RPC client.
 pub trait Io: Send + Sync {
     /// Fetch a light block at the given height from a peer
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError>;
+
+    /// Fetch the light blocks at the given heights from a peer.
+    ///
+    /// The default implementation fetches them one after the other. Implementations
+    /// backed by an asynchronous client should override it to issue the requests
+    /// concurrently.
+    fn fetch_light_blocks(&self, heights: &[Height]) -> Vec<Result<LightBlock, IoError>> {
+        heights
+            .iter()
+            .map(|height| self.fetch_light_block(AtHeight::At(*height)))
+            .collect()
+    }
 }
 
 impl<F: Send + Sync> Io for F
@@ -90,9 +189,26 @@ where
     }
 }
 
+impl Io for Box<dyn Io> {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        (**self).fetch_light_block(height)
+    }
+
+    fn fetch_light_blocks(&self, heights: &[Height]) -> Vec<Result<LightBlock, IoError>> {
+        (**self).fetch_light_blocks(heights)
+    }
+}
+
 #[cfg(feature = "rpc-client")]
 pub use self::prod::ProdIo;
 
+#[cfg(feature = "grpc-client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc-client")))]
+pub mod grpc;
+
+#[cfg(feature = "grpc-client")]
+pub use self::grpc::GrpcIo;
+
 #[cfg(feature = "rpc-client")]
 mod prod {
     use super::*;
@@ -100,10 +216,8 @@ mod prod {
     use std::time::Duration;
 
     use crate::types::PeerId;
-    use crate::utils::block_on;
+    use crate::utils::{block_on, block_on_runtime};
 
-    use tendermint::account::Id as TMAccountId;
-    use tendermint::block::signed_header::SignedHeader as TMSignedHeader;
     use tendermint::validator::Set as TMValidatorSet;
     use tendermint_rpc::Paging;
 
@@ -114,28 +228,97 @@ mod prod {
         peer_id: PeerId,
         rpc_client: rpc::HttpClient,
         timeout: Option<Duration>,
+        runtime: Option<tokio::runtime::Handle>,
     }
 
     impl Io for ProdIo {
         fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
-            let signed_header = self.fetch_signed_header(height)?;
-            let height = signed_header.header.height;
-            let proposer_address = signed_header.header.proposer_address;
-
-            let validator_set = self.fetch_validator_set(height.into(), Some(proposer_address))?;
-            let next_validator_set = self.fetch_validator_set(height.increment().into(), None)?;
-
-            let light_block = LightBlock::new(
-                signed_header,
-                validator_set,
-                next_validator_set,
-                self.peer_id,
-            );
+            let client = self.rpc_client.clone();
+            let peer_id = self.peer_id;
+            let timeout = self.timeout;
+
+            self.block_on(None, async move {
+                fetch_light_block_async(&client, peer_id, height, timeout).await
+            })?
+        }
 
-            Ok(light_block)
+        /// Fetch the light blocks at the given heights concurrently,
+        /// applying the timeout to each of their requests.
+        fn fetch_light_blocks(&self, heights: &[Height]) -> Vec<Result<LightBlock, IoError>> {
+            let client = self.rpc_client.clone();
+            let peer_id = self.peer_id;
+            let timeout = self.timeout;
+            let batch = heights.to_vec();
+
+            let results = self.block_on(None, async move {
+                let fetches = batch.into_iter().map(|height| {
+                    fetch_light_block_async(&client, peer_id, AtHeight::At(height), timeout)
+                });
+
+                futures::future::join_all(fetches).await
+            });
+
+            match results {
+                Ok(results) => results,
+                // If we could not even spin up the runtime, fall back to fetching one by one.
+                Err(_) => heights
+                    .iter()
+                    .map(|height| self.fetch_light_block(AtHeight::At(*height)))
+                    .collect(),
+            }
         }
     }
 
+    /// Fetch the light block at the given height, or the latest one, along
+    /// with its validator sets, applying the timeout to each of the requests
+    async fn fetch_light_block_async(
+        client: &rpc::HttpClient,
+        peer_id: PeerId,
+        height: AtHeight,
+        timeout: Option<Duration>,
+    ) -> Result<LightBlock, IoError> {
+        let signed_header = match height {
+            AtHeight::Highest => with_timeout(timeout, client.latest_commit()).await,
+            AtHeight::At(height) => with_timeout(timeout, client.commit(height)).await,
+        }?
+        .signed_header;
+
+        let height = signed_header.header.height;
+        let proposer_address = signed_header.header.proposer_address;
+
+        let validators = with_timeout(timeout, client.validators(height, Paging::All))
+            .await?
+            .validators;
+
+        let next_validator_set =
+            with_timeout(timeout, client.all_validators(height.increment())).await?;
+
+        let validator_set = TMValidatorSet::with_proposer(validators, proposer_address)
+            .map_err(IoError::invalid_validator_set)?;
+
+        Ok(LightBlock::new(
+            signed_header,
+            validator_set,
+            next_validator_set,
+            peer_id,
+        ))
+    }
+
+    /// Await the given RPC request, failing if it does not complete within the timeout
+    async fn with_timeout<T>(
+        timeout: Option<Duration>,
+        request: impl std::future::Future<Output = Result<T, rpc::Error>>,
+    ) -> Result<T, IoError> {
+        let response = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, request)
+                .await
+                .map_err(|e| IoError::timeout(timeout, e))?,
+            None => request.await,
+        };
+
+        response.map_err(IoError::from_rpc)
+    }
+
     impl ProdIo {
         /// Constructs a new ProdIo component.
         ///
@@ -150,51 +333,60 @@ mod prod {
                 peer_id,
                 rpc_client,
                 timeout,
+                runtime: None,
             }
         }
 
-        fn fetch_signed_header(&self, height: AtHeight) -> Result<TMSignedHeader, IoError> {
-            let client = self.rpc_client.clone();
-            let res = block_on(self.timeout, async move {
-                match height {
-                    AtHeight::Highest => client.latest_commit().await,
-                    AtHeight::At(height) => client.commit(height).await,
-                }
-            })?;
-
-            match res {
-                Ok(response) => Ok(response.signed_header),
-                Err(err) => Err(IoError::rpc(err)),
+        /// Run the requests of this component on the given runtime, eg. one
+        /// shared by the light clients of a [`SupervisorPool`], instead of
+        /// spinning up a new runtime for each of them.
+        ///
+        /// [`SupervisorPool`]: crate::pool::SupervisorPool
+        pub fn with_runtime(mut self, runtime: tokio::runtime::Handle) -> Self {
+            self.runtime = Some(runtime);
+            self
+        }
+
+        fn block_on<F>(&self, timeout: Option<Duration>, f: F) -> Result<F::Output, IoError>
+        where
+            F: std::future::Future + Send + 'static,
+            F::Output: Send,
+        {
+            match &self.runtime {
+                Some(runtime) => block_on_runtime(runtime, timeout, f),
+                None => block_on(timeout, f),
             }
         }
+    }
 
-        fn fetch_validator_set(
-            &self,
-            height: AtHeight,
-            proposer_address: Option<TMAccountId>,
-        ) -> Result<TMValidatorSet, IoError> {
-            let height = match height {
-                AtHeight::Highest => {
-                    return Err(IoError::invalid_height());
-                }
-                AtHeight::At(height) => height,
-            };
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-            let client = self.rpc_client.clone();
-            let response = block_on(self.timeout, async move {
-                client.validators(height, Paging::All).await
-            })?
-            .map_err(IoError::rpc)?;
+        use std::net::TcpListener;
 
-            let validator_set = match proposer_address {
-                Some(proposer_address) => {
-                    TMValidatorSet::with_proposer(response.validators, proposer_address)
-                        .map_err(IoError::invalid_validator_set)?
-                }
-                None => TMValidatorSet::without_proposer(response.validators),
-            };
+        #[test]
+        fn requests_time_out() {
+            // Accepts connections, but never responds to the requests.
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+
+            let io = ProdIo::new(
+                PeerId::new([0; 20]),
+                rpc::HttpClient::new(url.as_str()).unwrap(),
+                Some(Duration::from_millis(100)),
+            );
+
+            let error = io.fetch_light_block(AtHeight::Highest).unwrap_err();
+            assert_eq!(
+                error.detail().is_timeout(),
+                Some(Duration::from_millis(100))
+            );
 
-            Ok(validator_set)
+            let errors = io.fetch_light_blocks(&[Height::from(1_u32), Height::from(2_u32)]);
+            assert!(errors
+                .iter()
+                .all(|e| e.as_ref().unwrap_err().detail().is_timeout().is_some()));
         }
     }
 }
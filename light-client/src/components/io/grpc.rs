@@ -0,0 +1,247 @@
+//! Implementation of the `Io` component which fetches light blocks over gRPC.
+//!
+//! The full node (or a sidecar process next to it) is expected to expose the
+//! following service:
+//!
+//! ```protobuf
+//! syntax = "proto3";
+//! package tendermint.light;
+//!
+//! import "tendermint/types/types.proto";
+//! import "tendermint/types/validator.proto";
+//!
+//! service LightBlockProvider {
+//!   // Returns the signed header and validator set at the given height,
+//!   // or at the latest height if `height` is 0.
+//!   rpc LightBlock(LightBlockRequest) returns (tendermint.types.LightBlock);
+//!   // Returns the validator set at the given height.
+//!   rpc ValidatorSet(ValidatorSetRequest) returns (tendermint.types.ValidatorSet);
+//! }
+//!
+//! message LightBlockRequest { int64 height = 1; }
+//! message ValidatorSetRequest { int64 height = 1; }
+//! ```
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tendermint::account::Id as TMAccountId;
+use tendermint::block::signed_header::SignedHeader as TMSignedHeader;
+use tendermint::validator::Set as TMValidatorSet;
+use tendermint_proto::types::{LightBlock as RawLightBlock, ValidatorSet as RawValidatorSet};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+
+use super::{AtHeight, Io, IoError};
+use crate::types::{Height, LightBlock, PeerId};
+use crate::utils::block_on_runtime;
+
+const LIGHT_BLOCK_PATH: &str = "/tendermint.light.LightBlockProvider/LightBlock";
+const VALIDATOR_SET_PATH: &str = "/tendermint.light.LightBlockProvider/ValidatorSet";
+
+/// Request for the light block at the given height (0 meaning the latest one).
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LightBlockRequest {
+    /// The requested height, or 0 for the latest block
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+}
+
+/// Request for the validator set at the given height.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValidatorSetRequest {
+    /// The requested height
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+}
+
+/// Implementation of the Io component which fetches light blocks from
+/// a `tendermint.light.LightBlockProvider` gRPC service, for environments
+/// where the JSON-RPC endpoint of the full node is not exposed.
+///
+/// The connection to the service is established on the first request, and
+/// then reused by the following ones.
+#[derive(Clone, Debug)]
+pub struct GrpcIo {
+    peer_id: PeerId,
+    channel: Channel,
+    runtime: Arc<tokio::runtime::Runtime>,
+    timeout: Option<Duration>,
+}
+
+impl Io for GrpcIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        let raw_light_block = self.fetch_raw_light_block(height)?;
+
+        let signed_header = raw_light_block
+            .signed_header
+            .ok_or_else(|| IoError::missing_field("signed_header"))?;
+
+        let signed_header =
+            TMSignedHeader::try_from(signed_header).map_err(IoError::invalid_signed_header)?;
+
+        let height = signed_header.header.height;
+        let proposer_address = signed_header.header.proposer_address;
+
+        let validator_set = raw_light_block
+            .validator_set
+            .ok_or_else(|| IoError::missing_field("validator_set"))?;
+
+        let validator_set = into_validator_set(validator_set, Some(proposer_address))?;
+        let next_validator_set = self.fetch_validator_set(height.increment())?;
+
+        Ok(LightBlock::new(
+            signed_header,
+            validator_set,
+            next_validator_set,
+            self.peer_id,
+        ))
+    }
+}
+
+impl GrpcIo {
+    /// Constructs a new GrpcIo component, which will connect to
+    /// the service listening at the given endpoint.
+    ///
+    /// The channel to the service, and the runtime driving it, are shared by
+    /// the clones of the component.
+    pub fn new(
+        peer_id: PeerId,
+        endpoint: Endpoint,
+        timeout: Option<Duration>,
+    ) -> Result<Self, IoError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(IoError::runtime)?;
+
+        // The channel spawns its background worker on the current runtime
+        let channel = {
+            let _guard = runtime.enter();
+            endpoint.connect_lazy().map_err(IoError::grpc_transport)?
+        };
+
+        Ok(Self {
+            peer_id,
+            channel,
+            runtime: Arc::new(runtime),
+            timeout,
+        })
+    }
+
+    fn fetch_raw_light_block(&self, height: AtHeight) -> Result<RawLightBlock, IoError> {
+        let height = match height {
+            AtHeight::Highest => 0,
+            AtHeight::At(height) => height.value() as i64,
+        };
+
+        self.unary(LIGHT_BLOCK_PATH, LightBlockRequest { height })
+    }
+
+    fn fetch_validator_set(&self, height: Height) -> Result<TMValidatorSet, IoError> {
+        let height = height.value() as i64;
+        let response = self.unary(VALIDATOR_SET_PATH, ValidatorSetRequest { height })?;
+
+        into_validator_set(response, None)
+    }
+
+    fn unary<Req, Res>(&self, path: &'static str, request: Req) -> Result<Res, IoError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Res: prost::Message + Default + Send + Sync + 'static,
+    {
+        let channel = self.channel.clone();
+
+        block_on_runtime(self.runtime.handle(), self.timeout, async move {
+            let mut client = tonic::client::Grpc::new(channel);
+            client.ready().await.map_err(IoError::grpc_transport)?;
+
+            let codec = tonic::codec::ProstCodec::default();
+            let path = PathAndQuery::from_static(path);
+
+            let response = client
+                .unary(tonic::Request::new(request), path, codec)
+                .await
+                .map_err(IoError::grpc)?;
+
+            Ok(response.into_inner())
+        })?
+    }
+}
+
+fn into_validator_set(
+    raw: RawValidatorSet,
+    proposer_address: Option<TMAccountId>,
+) -> Result<TMValidatorSet, IoError> {
+    let validator_set = TMValidatorSet::try_from(raw).map_err(IoError::invalid_validator_set)?;
+    let validators = validator_set.validators().clone();
+
+    match proposer_address {
+        Some(proposer_address) => TMValidatorSet::with_proposer(validators, proposer_address)
+            .map_err(IoError::invalid_validator_set),
+        None => {
+            TMValidatorSet::without_proposer(validators).map_err(IoError::invalid_validator_set)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::io::IoErrorDetail;
+    use tendermint_testgen::{light_block::TmLightBlock, Generator, LightChain};
+
+    fn raw_light_block(lb: &TmLightBlock) -> RawLightBlock {
+        RawLightBlock {
+            signed_header: Some(lb.signed_header.clone().into()),
+            validator_set: Some(lb.validators.clone().into()),
+        }
+    }
+
+    #[test]
+    fn validator_set_round_trips_through_proto() {
+        let chain = LightChain::default_with_length(1);
+        let lb = chain.light_blocks[0].generate().unwrap();
+        let proposer = lb.signed_header.header.proposer_address;
+
+        let raw = raw_light_block(&lb).validator_set.unwrap();
+        let validator_set = into_validator_set(raw, Some(proposer)).unwrap();
+
+        assert_eq!(validator_set.validators(), lb.validators.validators());
+        assert_eq!(
+            validator_set.proposer().as_ref().map(|p| p.address),
+            Some(proposer)
+        );
+    }
+
+    #[test]
+    fn request_message_encodes_height() {
+        let request = LightBlockRequest { height: 42 };
+        let mut bytes = Vec::new();
+        prost::Message::encode(&request, &mut bytes).unwrap();
+        let decoded: LightBlockRequest = prost::Message::decode(bytes.as_slice()).unwrap();
+
+        assert_eq!(decoded.height, 42);
+    }
+
+    #[test]
+    fn unreachable_endpoint_yields_grpc_error() {
+        let endpoint = Endpoint::from_static("http://127.0.0.1:1");
+        let io = GrpcIo::new(
+            tendermint_testgen::light_block::default_peer_id(),
+            endpoint,
+            Some(Duration::from_secs(5)),
+        )
+        .unwrap();
+
+        // The channel connects on each request until it succeeds
+        for _ in 0..2 {
+            match io.fetch_light_block(AtHeight::Highest) {
+                Err(IoError(IoErrorDetail::Grpc(_), _)) => {}
+                other => panic!("expected Grpc error, instead got {:?}", other),
+            }
+        }
+    }
+}
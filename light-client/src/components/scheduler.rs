@@ -3,7 +3,7 @@
 use contracts::*;
 
 use crate::store::LightStore;
-use crate::types::Height;
+use crate::types::{Height, LightBlock, Status, ValidatorSet};
 use std::convert::TryInto;
 
 /// The scheduler decides what block to verify next given the current and target heights.
@@ -45,6 +45,18 @@ where
     }
 }
 
+#[contract_trait]
+impl Scheduler for Box<dyn Scheduler> {
+    fn schedule(
+        &self,
+        light_store: &dyn LightStore,
+        current_height: Height,
+        target_height: Height,
+    ) -> Height {
+        (**self).schedule(light_store, current_height, target_height)
+    }
+}
+
 /// Basic bisecting scheduler which picks the appropriate midpoint without
 /// optimizing for performance using the blocks available in the light store.
 ///
@@ -75,14 +87,138 @@ pub fn basic_bisecting_schedule(
     }
 }
 
+/// Scheduler tuned for catching up over large height gaps, which bases the length
+/// of its jumps on the rate at which the validator set was observed to change.
+///
+/// After a successful verification, instead of jumping straight to the target height,
+/// it estimates how far ahead the validator set is still likely to overlap enough with
+/// the trusted one, based on the changes between the latest trusted and verified blocks
+/// in the light store, and jumps that far. If no change has been observed, it jumps
+/// straight to the target. On failure, it falls back to bisection, as
+/// [`basic_bisecting_schedule`] does.
+///
+/// On chains whose validator set changes slowly, this avoids both trying (and failing)
+/// to verify a target hundreds of thousands of heights away, and the many steps of
+/// bisection needed to come back down from there.
+#[derive(Copy, Clone, Debug)]
+pub struct AdaptiveScheduler {
+    tolerated_change: f64,
+    samples: usize,
+}
+
+impl Default for AdaptiveScheduler {
+    /// Tolerate a change of a third of the voting power per jump, estimated
+    /// from the latest 10 trusted or verified blocks.
+    fn default() -> Self {
+        Self::new(1.0 / 3.0, 10)
+    }
+}
+
+impl AdaptiveScheduler {
+    /// Jump ahead by the distance over which the given fraction of the voting power
+    /// of the validator set is expected to change, as estimated from the given
+    /// number of the latest trusted or verified blocks.
+    ///
+    /// With the default trust threshold of 1/3, jumps over which more than 2/3 of the
+    /// voting power changes are bound to fail, so `tolerated_change` should stay well
+    /// below that.
+    pub fn new(tolerated_change: f64, samples: usize) -> Self {
+        Self {
+            tolerated_change,
+            samples,
+        }
+    }
+
+    /// The fraction of the voting power which changed per height, on average,
+    /// between the latest trusted or verified blocks up to the given height,
+    /// or `None` if there are not enough such blocks.
+    fn change_rate(&self, light_store: &dyn LightStore, up_to: Height) -> Option<f64> {
+        let mut blocks: Vec<LightBlock> = light_store
+            .all(Status::Trusted)
+            .chain(light_store.all(Status::Verified))
+            .filter(|lb| lb.height() <= up_to)
+            .collect();
+
+        blocks.sort_by_key(|lb| lb.height());
+        blocks.dedup_by_key(|lb| lb.height());
+
+        let start = blocks.len().saturating_sub(self.samples.max(2));
+        let blocks = &blocks[start..];
+
+        if blocks.len() < 2 {
+            return None;
+        }
+
+        let changed: f64 = blocks
+            .windows(2)
+            .map(|w| 1.0 - overlap(&w[0].next_validators, &w[1].validators))
+            .sum();
+
+        let span = blocks[blocks.len() - 1].height().value() - blocks[0].height().value();
+
+        Some(changed / span as f64)
+    }
+}
+
+#[contract_trait]
+impl Scheduler for AdaptiveScheduler {
+    fn schedule(
+        &self,
+        light_store: &dyn LightStore,
+        current_height: Height,
+        target_height: Height,
+    ) -> Height {
+        let trusted_height = light_store
+            .highest_trusted_or_verified()
+            .map(|lb| lb.height())
+            .unwrap();
+
+        if trusted_height != current_height {
+            // The block at the current height could not be verified, bisect.
+            return midpoint(trusted_height, current_height);
+        }
+
+        let jump = match self.change_rate(light_store, current_height) {
+            Some(rate) if rate > 0.0 => ((self.tolerated_change / rate).round() as u64).max(1),
+            _ => return target_height,
+        };
+
+        let next_height = current_height.value().saturating_add(jump);
+
+        if next_height >= target_height.value() {
+            target_height
+        } else {
+            next_height.try_into().unwrap()
+        }
+    }
+}
+
+/// The fraction of the voting power of `trusted` held by validators which are also in `untrusted`.
+fn overlap(trusted: &ValidatorSet, untrusted: &ValidatorSet) -> f64 {
+    let total: u64 = trusted.validators().iter().map(|v| v.power.value()).sum();
+
+    if total == 0 {
+        return 1.0;
+    }
+
+    let kept: u64 = trusted
+        .validators()
+        .iter()
+        .filter(|v| untrusted.validator(v.address).is_some())
+        .map(|v| v.power.value())
+        .sum();
+
+    kept as f64 / total as f64
+}
+
 /// Checks whether the given `scheduled_height` is a valid schedule according to the
 /// following specification.
 ///
 /// - i) If `latest_verified_height == current_height` and `latest_verified_height < target_height`
-/// then `current_height < scheduled_height <= target_height`.
+///   then `current_height < scheduled_height <= target_height`.
 ///
 /// - ii) If `latest_verified_height < current_height` and `latest_verified_height < target_height`
-/// then `latest_verified_height < scheduled_height < current_height`.
+///   then `latest_verified_height < scheduled_height < current_height`.
 ///
 /// - iii) If `latest_verified_height = target_height` then `scheduled_height == target_height`.
 ///
@@ -127,3 +263,88 @@ fn midpoint(low: Height, high: Height) -> Height {
         .try_into()
         .unwrap() // Will panic if midpoint is higher than i64::MAX
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::memory::MemoryStore;
+    use tendermint_testgen::{light_block::LightBlock as TestgenLightBlock, Generator};
+
+    fn light_block(height: u64, validators: Vec<&str>, next_validators: Vec<&str>) -> LightBlock {
+        let mut light_block: LightBlock = TestgenLightBlock::new_default(height)
+            .generate()
+            .unwrap()
+            .into();
+
+        light_block.validators = tendermint_testgen::ValidatorSet::new(validators)
+            .generate()
+            .unwrap();
+        light_block.next_validators = tendermint_testgen::ValidatorSet::new(next_validators)
+            .generate()
+            .unwrap();
+
+        light_block
+    }
+
+    #[test]
+    fn adaptive_scheduler_jumps_based_on_validator_changes() {
+        let scheduler = AdaptiveScheduler::default();
+        let target_height = Height::from(1000_u32);
+
+        let mut light_store = MemoryStore::new();
+        light_store.insert(
+            light_block(1, vec!["a", "b", "c"], vec!["a", "b", "c"]),
+            Status::Trusted,
+        );
+
+        // A single trusted block does not tell anything about the change rate.
+        let current_height = Height::from(1_u32);
+        assert_eq!(
+            scheduler.schedule(&light_store, current_height, target_height),
+            target_height
+        );
+
+        // A third of the voting power changed over 10 heights.
+        light_store.insert(
+            light_block(11, vec!["a", "b", "d"], vec!["a", "b", "d"]),
+            Status::Verified,
+        );
+
+        let current_height = Height::from(11_u32);
+        assert_eq!(
+            scheduler.schedule(&light_store, current_height, target_height),
+            Height::from(21_u32)
+        );
+
+        // The jump never goes past the target.
+        assert_eq!(
+            scheduler.schedule(&light_store, current_height, Height::from(15_u32)),
+            Height::from(15_u32)
+        );
+
+        // On failure, fall back to bisection.
+        assert_eq!(
+            scheduler.schedule(&light_store, Height::from(21_u32), target_height),
+            Height::from(16_u32)
+        );
+    }
+
+    #[test]
+    fn adaptive_scheduler_jumps_to_target_without_validator_changes() {
+        let scheduler = AdaptiveScheduler::default();
+        let target_height = Height::from(1000_u32);
+
+        let mut light_store = MemoryStore::new();
+        for height in [1, 5, 9] {
+            light_store.insert(
+                light_block(height, vec!["a", "b", "c"], vec!["a", "b", "c"]),
+                Status::Trusted,
+            );
+        }
+
+        assert_eq!(
+            scheduler.schedule(&light_store, Height::from(9_u32), target_height),
+            target_height
+        );
+    }
+}
@@ -19,7 +19,7 @@ use serde::{Deserialize, Serialize};
 
 /// Represents the result of the verification performed by the
 /// verifier component.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Verdict {
     /// Verification succeeded, the block is valid.
     Success,
@@ -62,6 +62,18 @@ pub trait Verifier: Send + Sync {
     ) -> Verdict;
 }
 
+impl<T: Verifier + ?Sized> Verifier for Box<T> {
+    fn verify(
+        &self,
+        untrusted: &LightBlock,
+        trusted: &LightBlock,
+        options: &Options,
+        now: Time,
+    ) -> Verdict {
+        (**self).verify(untrusted, trusted, options, now)
+    }
+}
+
 /// Production implementation of the verifier.
 ///
 /// For testing purposes, this implementation is parametrized by:
@@ -98,10 +110,10 @@ impl ProdVerifier {
 impl Default for ProdVerifier {
     fn default() -> Self {
         Self::new(
-            ProdPredicates::default(),
-            ProdVotingPowerCalculator::default(),
+            ProdPredicates,
+            ProdVotingPowerCalculator,
             ProdCommitValidator::default(),
-            ProdHasher::default(),
+            ProdHasher,
         )
     }
 }
@@ -127,3 +139,21 @@ impl Verifier for ProdVerifier {
         .into()
     }
 }
+
+/// Check whether the given untrusted block can be trusted based on the given trusted block,
+/// using the production verifier.
+///
+/// This is a stateless, one-shot version of the verification performed by the light client,
+/// for embedders (eg. IBC implementations) which manage storage and networking themselves,
+/// and therefore have no use for a `LightClient`, its `State`, or an `Io` component.
+///
+/// Note that this does not perform any bisection: if the verdict is `NotEnoughTrust`,
+/// it is up to the caller to fetch and verify intermediate blocks.
+pub fn verify_update_header(
+    trusted: &LightBlock,
+    untrusted: &LightBlock,
+    options: &Options,
+    now: Time,
+) -> Verdict {
+    ProdVerifier::default().verify(untrusted, trusted, options, now)
+}
@@ -5,11 +5,13 @@ use std::time::Duration;
 
 use crate::operations::voting_power::VotingPowerTally;
 use crossbeam_channel as crossbeam;
+use tendermint::chain;
 
 use crate::{
     components::io::IoError,
     light_client::Options,
-    predicates::errors::VerificationErrorDetail,
+    peer_store::PeerStoreError,
+    predicates::errors::{Predicate, VerificationErrorDetail},
     types::{Hash, Height, LightBlock, PeerId, Status},
 };
 use flex_error::{define_error, DisplayError, TraceError};
@@ -82,9 +84,40 @@ define_error! {
                     e.target_height, e.trusted_height)
             },
 
+        Cancelled
+            {
+                target_height: Height,
+                height: Height,
+            }
+            | e | {
+                format_args!("verification of block at height {0} was cancelled before verifying block at height {1}",
+                    e.target_height, e.height)
+            },
+
+        BisectionStepsExceeded
+            {
+                target_height: Height,
+                max_steps: u64,
+            }
+            | e | {
+                format_args!("verification of block at height {0} exceeded the maximum number of bisection steps ({1})",
+                    e.target_height, e.max_steps)
+            },
+
+        InvalidTrustThresholdSchedule
+            { height: Height }
+            | e | {
+                format_args!("trust threshold overrides must be sorted by strictly increasing height, found override at height {0} out of order",
+                    e.height)
+            },
+
         InvalidLightBlock
+            { height: Height }
             [ DisplayError<VerificationErrorDetail> ]
-            | _ | { "invalid light block" },
+            | e | {
+                format_args!("invalid light block at height {0}",
+                    e.height)
+            },
 
         InvalidAdjacentHeaders
             {
@@ -106,6 +139,34 @@ define_error! {
         ChannelDisconnected
             | _ | { "internal channel disconnected" },
 
+        Spawn
+            [ TraceError<std::io::Error> ]
+            | _ | { "failed to spawn thread" },
+
+        UnknownChain
+            { chain_id: chain::Id }
+            | e | {
+                format_args!("unknown chain {0}",
+                    e.chain_id)
+            },
+
+        DuplicateChain
+            { chain_id: chain::Id }
+            | e | {
+                format_args!("chain {0} is already followed",
+                    e.chain_id)
+            },
+
+        ChainIdMismatch
+            {
+                expected: chain::Id,
+                got: chain::Id,
+            }
+            | e | {
+                format_args!("supervisor follows chain {1} instead of chain {0}",
+                    e.expected, e.got)
+            },
+
         Sled
             [ SledError ]
             | _ | { "sled error" },
@@ -114,6 +175,10 @@ define_error! {
             [ TraceError<serde_cbor::Error> ]
             | _ | { "serde cbor error" },
 
+        PeerStore
+            [ PeerStoreError ]
+            | _ | { "failed to persist the records of the peers" },
+
     }
 }
 
@@ -130,6 +195,14 @@ pub trait ErrorExt {
     /// Whether this error means that a timeout occured when
     /// querying a node.
     fn is_timeout(&self) -> Option<Duration>;
+
+    /// Whether this error means that the node pruned the requested height,
+    /// in which case the lowest height still available on the node is returned.
+    fn is_height_pruned(&self) -> Option<Height>;
+
+    /// Whether this error means that the light block failed verification,
+    /// in which case the predicate which does not hold is returned.
+    fn failed_predicate(&self) -> Option<Predicate>;
 }
 
 impl ErrorExt for ErrorDetail {
@@ -157,6 +230,22 @@ impl ErrorExt for ErrorDetail {
             None
         }
     }
+
+    fn is_height_pruned(&self) -> Option<Height> {
+        if let Self::Io(e) = self {
+            e.source.is_height_pruned()
+        } else {
+            None
+        }
+    }
+
+    fn failed_predicate(&self) -> Option<Predicate> {
+        if let Self::InvalidLightBlock(e) = self {
+            e.source.failed_predicate()
+        } else {
+            None
+        }
+    }
 }
 
 impl Error {
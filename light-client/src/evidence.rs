@@ -1,12 +1,15 @@
 //! Fork evidence data structures and interfaces.
 
-use crate::{components::io::IoError, types::PeerId};
+use crate::{
+    components::io::IoError,
+    types::{LightBlock, PeerId},
+};
 
 use tendermint::abci::transaction::Hash;
 
 use contracts::contract_trait;
 
-pub use tendermint::evidence::Evidence;
+pub use tendermint::evidence::{Evidence, LightClientAttackEvidence};
 
 /// Interface for reporting evidence to full nodes, typically via the RPC client.
 #[contract_trait]
@@ -16,6 +19,42 @@ pub trait EvidenceReporter: Send + Sync {
     fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError>;
 }
 
+/// Build the evidence of an attack on the light client from a block which conflicts with
+/// the trusted block at the same height, eg. as found by the fork detector, and the latest
+/// block both chains agree on, eg. the trusted block the conflicting block was verified against,
+/// as `newLightClientAttackEvidence` in Go.
+///
+/// In case of a lunatic attack, ie. when the conflicting block could not have been derived
+/// from the trusted state, the evidence is recorded at the height of the common block, and the
+/// validators at that height which signed the conflicting block are deemed byzantine.
+/// Otherwise, the evidence is recorded at the height of the conflicting block, and in case of
+/// an equivocation, the validators which signed both blocks are deemed byzantine.
+pub fn light_client_attack_evidence(
+    conflicting: &LightBlock,
+    trusted: &LightBlock,
+    common: &LightBlock,
+) -> LightClientAttackEvidence {
+    let mut evidence = LightClientAttackEvidence {
+        conflicting_header: conflicting.signed_header.clone(),
+        conflicting_validators: conflicting.validators.clone(),
+        common_height: trusted.height(),
+        byzantine_validators: vec![],
+        total_voting_power: trusted.validators.total_voting_power(),
+        timestamp: trusted.signed_header.header.time,
+    };
+
+    if evidence.conflicting_header_is_invalid(&trusted.signed_header) {
+        evidence.common_height = common.height();
+        evidence.total_voting_power = common.validators.total_voting_power();
+        evidence.timestamp = common.signed_header.header.time;
+    }
+
+    evidence.byzantine_validators =
+        evidence.byzantine_validators(&common.validators, &trusted.signed_header);
+
+    evidence
+}
+
 #[cfg(feature = "rpc-client")]
 pub use self::prod::ProdEvidenceReporter;
 
@@ -72,3 +111,114 @@ mod prod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint::evidence::Params;
+    use tendermint_proto::Protobuf;
+    use tendermint_testgen::{
+        Commit, Generator, Header, LightBlock as TestgenLightBlock, Validator,
+    };
+
+    fn generate(light_block: TestgenLightBlock) -> LightBlock {
+        light_block.generate().unwrap().into()
+    }
+
+    fn light_block(validators: &[Validator], height: u64) -> LightBlock {
+        let header = Header::new(validators)
+            .height(height)
+            .chain_id("test-chain")
+            .next_validators(validators)
+            .time(height);
+        let commit = Commit::new(header.clone(), 1);
+
+        generate(
+            TestgenLightBlock::new(header, commit)
+                .validators(validators)
+                .next_validators(validators),
+        )
+    }
+
+    fn verify(evidence: &LightClientAttackEvidence, trusted: &LightBlock, common: &LightBlock) {
+        evidence
+            .verify(
+                &common.signed_header,
+                &trusted.signed_header,
+                &common.validators,
+                &Params::default(),
+                trusted.height(),
+                trusted.signed_header.header.time,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn lunatic_attack_evidence() {
+        let validators = [
+            Validator::new("1").voting_power(50),
+            Validator::new("2").voting_power(40),
+            Validator::new("3").voting_power(50),
+        ];
+        let common = light_block(&validators, 1);
+        let trusted = light_block(&validators, 3);
+
+        // Two of the common validators sign a block with another validator set.
+        let conflicting = light_block(
+            &[
+                Validator::new("1").voting_power(50),
+                Validator::new("2").voting_power(40),
+                Validator::new("4").voting_power(50),
+            ],
+            3,
+        );
+
+        let evidence = light_client_attack_evidence(&conflicting, &trusted, &common);
+
+        assert_eq!(evidence.common_height, common.height());
+        assert_eq!(evidence.timestamp, common.signed_header.header.time);
+        assert_eq!(evidence.total_voting_power, 140_u32.into());
+
+        // Ordered by decreasing voting power
+        let powers = evidence
+            .byzantine_validators
+            .iter()
+            .map(|v| v.power())
+            .collect::<Vec<_>>();
+        assert_eq!(powers, vec![50, 40]);
+
+        verify(&evidence, &trusted, &common);
+    }
+
+    #[test]
+    fn equivocation_attack_evidence() {
+        let common = generate(TestgenLightBlock::new_default(1));
+
+        // Same validators, same height and round, but a different time than on the main chain.
+        let main = TestgenLightBlock::new_default(3);
+        let conflicting = generate(TestgenLightBlock::new_default_with_time_and_chain_id(
+            main.chain_id(),
+            100,
+            3,
+        ));
+        let trusted = generate(main);
+
+        let evidence = light_client_attack_evidence(&conflicting, &trusted, &common);
+
+        assert_eq!(evidence.common_height, trusted.height());
+        assert_eq!(
+            evidence.byzantine_validators.len(),
+            trusted.validators.validators().len()
+        );
+
+        verify(&evidence, &trusted, &trusted);
+
+        let bytes = evidence.encode_vec().unwrap();
+        let decoded = LightClientAttackEvidence::decode_vec(&bytes).unwrap();
+        assert_eq!(decoded, evidence);
+
+        let evidence = Evidence::LightClientAttack(Box::new(evidence));
+        let bytes = evidence.encode_vec().unwrap();
+        assert_eq!(Evidence::decode_vec(&bytes).unwrap(), evidence);
+    }
+}
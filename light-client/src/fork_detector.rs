@@ -1,12 +1,15 @@
 //! Fork detection data structures and implementation.
 
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
 use crate::{
     errors::{Error, ErrorDetail, ErrorExt},
     operations::{Hasher, ProdHasher},
     state::State,
-    store::memory::MemoryStore,
+    store::{content_addressed::ContentAddressedStore, memory::MemoryStore},
     supervisor::Instance,
-    types::{LightBlock, PeerId, Status},
+    types::{Hash, Height, LightBlock, PeerId, Status},
 };
 
 /// Result of fork detection
@@ -20,6 +23,7 @@ pub enum ForkDetection {
 
 /// Types of fork
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Fork {
     /// An actual fork was found for this `LightBlock`
     Forked {
@@ -48,6 +52,10 @@ pub trait ForkDetector: Send + Sync {
 
 /// A production-ready fork detector which compares
 /// light blocks fetched from the witnesses by hash.
+/// Light blocks are collected in a [`ContentAddressedStore`] kept across
+/// detections, so that witnesses agreeing with the primary do not add a copy
+/// of the block, and that the headers each peer provided can be looked up
+/// afterwards. Blocks below the trusted block of a detection are pruned.
 /// If the hashes don't match, this fork detector
 /// then attempts to verify the light block pulled from
 /// the witness against a light block containing only
@@ -57,16 +65,28 @@ pub trait ForkDetector: Send + Sync {
 /// - If verification fails because of lack of trust, we have a potential fork.
 /// - If verification fails for any other reason, the witness is deemed faulty.
 pub struct ProdForkDetector {
-    hasher: Box<dyn Hasher>,
+    blocks: Mutex<ContentAddressedStore>,
 }
 
 impl ProdForkDetector {
     /// Construct a new fork detector that will use the given header hasher.
     pub fn new(hasher: impl Hasher + 'static) -> Self {
         Self {
-            hasher: Box::new(hasher),
+            blocks: Mutex::new(ContentAddressedStore::new(hasher)),
         }
     }
+
+    /// The distinct header hashes provided at the given height by the primary
+    /// and the witnesses during fork detection, each with the peers which
+    /// provided it.
+    pub fn conflicts(&self, height: Height) -> HashMap<Hash, Vec<PeerId>> {
+        self.blocks().conflicts(height)
+    }
+
+    fn blocks(&self) -> MutexGuard<'_, ContentAddressedStore> {
+        // The store is left consistent by a panic of another thread
+        self.blocks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 impl Default for ProdForkDetector {
@@ -83,9 +103,11 @@ impl ForkDetector for ProdForkDetector {
         trusted_block: &LightBlock,
         witnesses: Vec<&Instance>,
     ) -> Result<ForkDetection, Error> {
-        let primary_hash = self
-            .hasher
-            .hash_header(&verified_block.signed_header.header);
+        let primary_hash = {
+            let mut blocks = self.blocks();
+            blocks.prune_below(trusted_block.height());
+            blocks.insert(verified_block.clone())
+        };
 
         let mut forks = Vec::with_capacity(witnesses.len());
 
@@ -96,7 +118,7 @@ impl ForkDetector for ProdForkDetector {
                 .light_client
                 .get_or_fetch_block(verified_block.height(), &mut state)?;
 
-            let witness_hash = self.hasher.hash_header(&witness_block.signed_header.header);
+            let witness_hash = self.blocks().insert(witness_block.clone());
 
             if primary_hash == witness_hash {
                 // Hashes match, continue with next witness, if any.
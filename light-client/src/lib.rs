@@ -23,13 +23,22 @@ pub mod errors;
 pub mod evidence;
 pub mod fork_detector;
 pub mod light_client;
+#[cfg(feature = "prometheus-exporter")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prometheus-exporter")))]
+pub mod metrics;
 pub mod operations;
 pub mod peer_list;
+pub mod peer_store;
+pub mod pool;
 pub mod predicates;
 pub mod state;
 pub mod store;
 pub mod supervisor;
+#[cfg(any(test, feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
 pub mod types;
+pub mod watchdog;
 
 pub(crate) mod utils;
 
@@ -2,7 +2,7 @@
 //!
 //! [1]: https://github.com/informalsystems/tendermint-rs/blob/master/docs/spec/lightclient/verification/verification.md
 
-use std::{fmt, time::Duration};
+use std::{convert::TryFrom, fmt, time::Duration};
 
 use contracts::*;
 use derive_more::Display;
@@ -11,14 +11,15 @@ use serde::{Deserialize, Serialize};
 use crate::{
     components::{clock::Clock, io::*, scheduler::*, verifier::*},
     contracts::*,
-    errors::Error,
+    errors::{Error, ErrorExt},
     operations::Hasher,
     state::State,
-    types::{Height, LightBlock, PeerId, Status, TrustThreshold},
+    types::{Height, LightBlock, PeerId, Status, Time, TrustThreshold},
 };
 
 /// Verification parameters
-#[derive(Copy, Clone, Debug, PartialEq, Display, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Display, Serialize, Deserialize)]
+#[serde(try_from = "OptionsFields")]
 #[display(fmt = "{:?}", self)]
 pub struct Options {
     /// Defines what fraction of the total voting power of a known
@@ -35,6 +36,100 @@ pub struct Options {
     /// is the maximum amount that the local clock may drift behind a timestamp from the
     /// blockchain.
     pub clock_drift: Duration,
+
+    /// Maximum number of light blocks to check during a single call to
+    /// `verify_to_target`, or `None` for no limit. This protects resource-constrained
+    /// embedders against faulty peers forcing the light client into very long bisections.
+    #[serde(default)]
+    pub max_bisection_steps: Option<u64>,
+
+    /// Trust thresholds which apply instead of `trust_threshold` from a given height onward,
+    /// sorted by strictly increasing height. This allows chains whose security assumptions
+    /// change across upgrade boundaries to eg. require a stricter threshold for recent heights.
+    ///
+    /// The threshold used to verify a block is the one of the last override starting at or
+    /// below the height of that block, or `trust_threshold` if there is none.
+    #[serde(default)]
+    pub trust_threshold_overrides: Vec<TrustThresholdOverride>,
+
+    /// Maximum amount by which the time of an untrusted header may be ahead of the local
+    /// clock, when checking that the header is not from the future. Defaults to
+    /// `clock_drift` if `None`, for compatibility with existing configurations.
+    #[serde(default)]
+    pub forward_clock_drift: Option<Duration>,
+}
+
+/// The serialized fields of the options, which are validated when
+/// deserializing them
+#[derive(Deserialize)]
+struct OptionsFields {
+    trust_threshold: TrustThreshold,
+    trusting_period: Duration,
+    clock_drift: Duration,
+    #[serde(default)]
+    max_bisection_steps: Option<u64>,
+    #[serde(default)]
+    trust_threshold_overrides: Vec<TrustThresholdOverride>,
+    #[serde(default)]
+    forward_clock_drift: Option<Duration>,
+}
+
+impl TryFrom<OptionsFields> for Options {
+    type Error = Error;
+
+    fn try_from(fields: OptionsFields) -> Result<Self, Error> {
+        let options = Options {
+            trust_threshold: fields.trust_threshold,
+            trusting_period: fields.trusting_period,
+            clock_drift: fields.clock_drift,
+            max_bisection_steps: fields.max_bisection_steps,
+            trust_threshold_overrides: fields.trust_threshold_overrides,
+            forward_clock_drift: fields.forward_clock_drift,
+        };
+        options.validate()?;
+        Ok(options)
+    }
+}
+
+impl Options {
+    /// The maximum amount by which the time of an untrusted header may be ahead of
+    /// the local clock, ie. `forward_clock_drift` if set, or `clock_drift` otherwise.
+    pub fn max_forward_drift(&self) -> Duration {
+        self.forward_clock_drift.unwrap_or(self.clock_drift)
+    }
+
+    /// The trust threshold to use when verifying the block at the given height.
+    pub fn trust_threshold_at(&self, height: Height) -> TrustThreshold {
+        self.trust_threshold_overrides
+            .iter()
+            .take_while(|o| o.from_height <= height)
+            .last()
+            .map_or(self.trust_threshold, |o| o.trust_threshold)
+    }
+
+    /// Check that the trust threshold overrides are sorted by strictly increasing height.
+    pub fn validate(&self) -> Result<(), Error> {
+        let unsorted = self
+            .trust_threshold_overrides
+            .windows(2)
+            .find(|w| w[0].from_height >= w[1].from_height);
+
+        match unsorted {
+            Some(w) => Err(Error::invalid_trust_threshold_schedule(w[1].from_height)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A trust threshold which applies from the given height onward,
+/// until the next override if any.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrustThresholdOverride {
+    /// The first height at which this threshold applies
+    pub from_height: Height,
+
+    /// The trust threshold to use from that height onward
+    pub trust_threshold: TrustThreshold,
 }
 
 /// The light client implements a read operation of a header from the blockchain,
@@ -47,23 +142,42 @@ pub struct Options {
 /// of the header, more than two-thirds of the next validators of a new block are
 /// correct for the duration of the trusted period.  The fault-tolerant read operation
 /// is designed for this security model.
-pub struct LightClient {
+///
+/// ## Components
+///
+/// The light client is generic over its clock `C`, scheduler `S`, verifier `V`
+/// and I/O component `I`. By default, each of these is a boxed trait object,
+/// so that `LightClient` (without type parameters) refers to the dynamically
+/// dispatched version constructed by [`LightClient::new`] and
+/// [`LightClient::from_boxed`].
+///
+/// Embedders who want static dispatch can instead use [`LightClient::from_components`]
+/// with concrete component types, eg. `LightClient<SystemClock, _, ProdVerifier, ProdIo>`.
+pub struct LightClient<
+    C = Box<dyn Clock>,
+    S = Box<dyn Scheduler>,
+    V = Box<dyn Verifier>,
+    I = Box<dyn Io>,
+> {
     /// The peer id of the peer this client is connected to
     pub peer: PeerId,
     /// Options for this light client
     pub options: Options,
 
-    clock: Box<dyn Clock>,
-    scheduler: Box<dyn Scheduler>,
-    verifier: Box<dyn Verifier>,
-    io: Box<dyn Io>,
+    clock: C,
+    scheduler: S,
+    verifier: V,
+    io: I,
+
+    // How many of the upcoming bisection pivots to fetch ahead of time
+    prefetch_depth: usize,
 
     // Only used in verify_backwards when "unstable" feature is enabled
     #[allow(dead_code)]
     hasher: Box<dyn Hasher>,
 }
 
-impl fmt::Debug for LightClient {
+impl<C, S, V, I> fmt::Debug for LightClient<C, S, V, I> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("LightClient")
             .field("peer", &self.peer)
@@ -73,7 +187,8 @@ impl fmt::Debug for LightClient {
 }
 
 impl LightClient {
-    /// Constructs a new light client
+    /// Constructs a new light client, failing if the options are invalid,
+    /// see [`Options::validate`]
     pub fn new(
         peer: PeerId,
         options: Options,
@@ -82,19 +197,20 @@ impl LightClient {
         verifier: impl Verifier + 'static,
         hasher: impl Hasher + 'static,
         io: impl Io + 'static,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, Error> {
+        Self::from_boxed(
             peer,
             options,
-            clock: Box::new(clock),
-            scheduler: Box::new(scheduler),
-            verifier: Box::new(verifier),
-            hasher: Box::new(hasher),
-            io: Box::new(io),
-        }
+            Box::new(clock),
+            Box::new(scheduler),
+            Box::new(verifier),
+            Box::new(hasher),
+            Box::new(io),
+        )
     }
 
-    /// Constructs a new light client from boxed components
+    /// Constructs a new light client from boxed components, failing if the
+    /// options are invalid, see [`Options::validate`]
     pub fn from_boxed(
         peer: PeerId,
         options: Options,
@@ -103,6 +219,23 @@ impl LightClient {
         verifier: Box<dyn Verifier>,
         hasher: Box<dyn Hasher>,
         io: Box<dyn Io>,
+    ) -> Result<Self, Error> {
+        options.validate()?;
+        Ok(Self::from_validated_boxed(
+            peer, options, clock, scheduler, verifier, hasher, io,
+        ))
+    }
+
+    /// Constructs a new light client from boxed components, with options
+    /// which were already validated
+    pub(crate) fn from_validated_boxed(
+        peer: PeerId,
+        options: Options,
+        clock: Box<dyn Clock>,
+        scheduler: Box<dyn Scheduler>,
+        verifier: Box<dyn Verifier>,
+        hasher: Box<dyn Hasher>,
+        io: Box<dyn Io>,
     ) -> Self {
         Self {
             peer,
@@ -112,8 +245,59 @@ impl LightClient {
             verifier,
             io,
             hasher,
+            prefetch_depth: 0,
         }
     }
+}
+
+impl<C, S, V, I> LightClient<C, S, V, I>
+where
+    C: Clock,
+    S: Scheduler,
+    V: Verifier,
+    I: Io,
+{
+    /// Constructs a new light client from statically dispatched components,
+    /// failing if the options are invalid, see [`Options::validate`].
+    pub fn from_components(
+        peer: PeerId,
+        options: Options,
+        clock: C,
+        scheduler: S,
+        verifier: V,
+        hasher: impl Hasher + 'static,
+        io: I,
+    ) -> Result<Self, Error> {
+        options.validate()?;
+        Ok(Self {
+            peer,
+            options,
+            clock,
+            scheduler,
+            verifier,
+            io,
+            hasher: Box::new(hasher),
+            prefetch_depth: 0,
+        })
+    }
+
+    /// When bisection is engaged, fetch the light blocks at the next `depth` heights
+    /// the scheduler would pick if the verification keeps failing for lack of trust,
+    /// all at once rather than one after the other.
+    ///
+    /// With an I/O component which issues these requests concurrently, such as `ProdIo`,
+    /// this reduces the end-to-end latency of verification over high-latency links,
+    /// at the cost of fetching blocks which may end up not being needed.
+    /// Prefetching is disabled by default, ie. `depth` is 0.
+    pub fn with_prefetch_depth(mut self, depth: usize) -> Self {
+        self.prefetch_depth = depth;
+        self
+    }
+
+    /// Get the current time, according to the clock of this light client.
+    pub fn now(&self) -> Time {
+        self.clock.now()
+    }
 
     /// Attempt to update the light client to the highest block of the primary node.
     ///
@@ -157,16 +341,40 @@ impl LightClient {
     /// - If the core verification loop invariant is violated [LCV-INV-TP.1]
     /// - If verification of a light block fails
     /// - If the fetching a light block from the primary node fails
+    ///
+    /// ## Verification trace
+    /// The light blocks used to bridge trust to the target block are recorded in
+    /// the given `state`, and can be retrieved with `State::get_trace(target_height)`.
+    pub fn verify_to_target(
+        &self,
+        target_height: Height,
+        state: &mut State,
+    ) -> Result<LightBlock, Error> {
+        self.verify_to_target_cancellable(target_height, state, &|| true)
+    }
+
+    /// Same as `verify_to_target`, but checks the given `should_continue` callback
+    /// before each verification step, and aborts the verification with a
+    /// `Cancelled` error as soon as the callback returns `false`.
+    ///
+    /// This allows callers to put a bound on verifications which take too long,
+    /// eg. because of a huge gap between the trusted state and the target height,
+    /// without having to kill the thread running the light client.
+    ///
+    /// Blocks which were verified before the verification was cancelled are kept
+    /// in the light store, so that a subsequent call can pick up where this one left off.
+    #[allow(clippy::nonminimal_bool)]
     #[post(
         ret.is_ok() ==> trusted_store_contains_block_at_target_height(
             state.light_store.as_ref(),
             target_height,
         )
     )]
-    pub fn verify_to_target(
+    pub fn verify_to_target_cancellable(
         &self,
         target_height: Height,
         state: &mut State,
+        should_continue: &dyn Fn() -> bool,
     ) -> Result<LightBlock, Error> {
         // Let's first look in the store to see whether
         // we have already successfully verified this block.
@@ -182,10 +390,10 @@ impl LightClient {
 
         if target_height >= highest.height() {
             // Perform forward verification with bisection
-            self.verify_forward(target_height, state)
+            self.verify_forward(target_height, state, should_continue)
         } else {
             // Perform sequential backward verification
-            self.verify_backward(target_height, state)
+            self.verify_backward(target_height, state, should_continue)
         }
     }
 
@@ -194,8 +402,10 @@ impl LightClient {
         &self,
         target_height: Height,
         state: &mut State,
+        should_continue: &dyn Fn() -> bool,
     ) -> Result<LightBlock, Error> {
         let mut current_height = target_height;
+        let mut steps = 0;
 
         loop {
             let now = self.clock.now();
@@ -217,7 +427,7 @@ impl LightClient {
             if !is_within_trust_period(&trusted_state, self.options.trusting_period, now) {
                 return Err(Error::trusted_state_outside_trusting_period(
                     Box::new(trusted_state),
-                    self.options,
+                    self.options.clone(),
                 ));
             }
 
@@ -230,9 +440,33 @@ impl LightClient {
                 return Ok(trusted_state);
             }
 
+            // Give the caller a chance to abort the verification before doing more work.
+            if !should_continue() {
+                return Err(Error::cancelled(target_height, current_height));
+            }
+
+            steps += 1;
+            self.check_bisection_steps(target_height, steps)?;
+
             // Fetch the block at the current height from the light store if already present,
             // or from the primary peer otherwise.
-            let (current_block, status) = self.get_or_fetch_block(current_height, state)?;
+            let (current_block, status) = match self.get_or_fetch_block(current_height, state) {
+                Ok(fetched) => fetched,
+                Err(e) => match e.detail().is_height_pruned() {
+                    // The primary pruned the block at the current height, but still has some
+                    // of the blocks up to the target height: try the lowest of those instead,
+                    // unless we already did. Otherwise, give up on this peer.
+                    Some(lowest)
+                        if trusted_state.height() < lowest
+                            && lowest <= target_height
+                            && state.light_store.get_non_failed(lowest).is_none() =>
+                    {
+                        current_height = lowest;
+                        continue;
+                    }
+                    _ => return Err(e),
+                },
+            };
 
             // Validate and verify the current block
             let verdict = self
@@ -245,13 +479,17 @@ impl LightClient {
                     // the `Verified` status or higher if already trusted.
                     let new_status = Status::most_trusted(Status::Verified, status);
                     state.light_store.update(&current_block, new_status);
+
+                    // Log the trusted state the block was verified against as part of
+                    // the chain of blocks bridging trust to the target height.
+                    state.trace_block(target_height, trusted_state.height());
                 }
                 Verdict::Invalid(e) => {
                     // Verification failed, add the block to the light store with `Failed` status,
                     // and abort.
                     state.light_store.update(&current_block, Status::Failed);
 
-                    return Err(Error::invalid_light_block(e));
+                    return Err(Error::invalid_light_block(current_block.height(), e));
                 }
                 Verdict::NotEnoughTrust(_) => {
                     // The current block cannot be trusted because of a missing overlap in the
@@ -260,6 +498,8 @@ impl LightClient {
                     // attempt to raise the height of the highest trusted state
                     // until there is enough overlap.
                     state.light_store.update(&current_block, Status::Unverified);
+
+                    self.prefetch(current_height, target_height, state);
                 }
             }
 
@@ -277,6 +517,7 @@ impl LightClient {
         &self,
         target_height: Height,
         state: &mut State,
+        _should_continue: &dyn Fn() -> bool,
     ) -> Result<LightBlock, Error> {
         let trusted_state = state
             .light_store
@@ -312,6 +553,7 @@ impl LightClient {
         &self,
         target_height: Height,
         state: &mut State,
+        should_continue: &dyn Fn() -> bool,
     ) -> Result<LightBlock, Error> {
         use std::convert::TryFrom;
 
@@ -326,7 +568,7 @@ impl LightClient {
         if !is_within_trust_period(&root, self.options.trusting_period, self.clock.now()) {
             return Err(Error::trusted_state_outside_trusting_period(
                 Box::new(root),
-                self.options,
+                self.options.clone(),
             ));
         }
 
@@ -336,7 +578,13 @@ impl LightClient {
 
         let mut latest = root;
 
-        for height in heights {
+        for (steps, height) in (1..).zip(heights) {
+            if !should_continue() {
+                return Err(Error::cancelled(target_height, height));
+            }
+
+            self.check_bisection_steps(target_height, steps)?;
+
             let (current, _status) = self.get_or_fetch_block(height, state)?;
 
             let latest_last_block_id = latest
@@ -371,6 +619,71 @@ impl LightClient {
         Ok(latest)
     }
 
+    /// Fetch, all at once, the light blocks at the heights the scheduler would pick next
+    /// if every one of them failed verification for lack of trust, starting from
+    /// `current_height`, up to the configured prefetch depth.
+    ///
+    /// The blocks are stored with the `Unverified` status, so that they can later be
+    /// picked up by `get_or_fetch_block`. Failures are ignored, as the corresponding
+    /// blocks will simply be fetched again when they are actually needed.
+    fn prefetch(&self, current_height: Height, target_height: Height, state: &mut State) {
+        if self.prefetch_depth == 0 {
+            return;
+        }
+
+        let trusted_height = match state.light_store.highest_trusted_or_verified() {
+            Some(trusted_state) => trusted_state.height(),
+            None => return,
+        };
+
+        let mut heights = Vec::with_capacity(self.prefetch_depth);
+        let mut height = current_height;
+
+        // Adjacent blocks do not require bisection, so we can stop right above the trusted state.
+        while heights.len() < self.prefetch_depth && height.value() > trusted_height.value() + 1 {
+            let next_height =
+                self.scheduler
+                    .schedule(state.light_store.as_ref(), height, target_height);
+
+            if next_height >= height {
+                break;
+            }
+
+            if state.light_store.get_non_failed(next_height).is_none() {
+                heights.push(next_height);
+            }
+
+            height = next_height;
+        }
+
+        self.fetch_blocks(&heights, state);
+    }
+
+    /// Fetch the blocks at the given heights all at once, and store them with
+    /// the `Unverified` status. Failures are ignored.
+    pub(crate) fn fetch_blocks(&self, heights: &[Height], state: &mut State) {
+        if heights.is_empty() {
+            return;
+        }
+
+        for light_block in self.io.fetch_light_blocks(heights).into_iter().flatten() {
+            if light_block.provider == self.peer {
+                state.light_store.insert(light_block, Status::Unverified);
+            }
+        }
+    }
+
+    /// Check that the given number of verification steps does not exceed
+    /// the limit set in the options, if any.
+    fn check_bisection_steps(&self, target_height: Height, steps: u64) -> Result<(), Error> {
+        match self.options.max_bisection_steps {
+            Some(max_steps) if steps > max_steps => {
+                Err(Error::bisection_steps_exceeded(target_height, max_steps))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Look in the light store for a block from the given peer at the given height,
     /// which has not previously failed verification (ie. its status is not `Failed`).
     ///
@@ -401,3 +714,103 @@ impl LightClient {
         Ok((block, Status::Unverified))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorDetail;
+
+    fn options(overrides: &[(u32, TrustThreshold)]) -> Options {
+        Options {
+            trust_threshold: TrustThreshold::ONE_THIRD,
+            trusting_period: Duration::from_secs(60),
+            clock_drift: Duration::from_secs(1),
+            max_bisection_steps: None,
+            trust_threshold_overrides: overrides
+                .iter()
+                .map(|&(height, trust_threshold)| TrustThresholdOverride {
+                    from_height: height.into(),
+                    trust_threshold,
+                })
+                .collect(),
+            forward_clock_drift: None,
+        }
+    }
+
+    #[test]
+    fn trust_threshold_overrides_apply_from_their_height() {
+        let options = options(&[
+            (10, TrustThreshold::TWO_THIRDS),
+            (20, TrustThreshold::ONE_THIRD),
+        ]);
+
+        let at = |h: u32| options.trust_threshold_at(h.into());
+
+        assert_eq!(at(9), TrustThreshold::ONE_THIRD);
+        assert_eq!(at(10), TrustThreshold::TWO_THIRDS);
+        assert_eq!(at(19), TrustThreshold::TWO_THIRDS);
+        assert_eq!(at(20), TrustThreshold::ONE_THIRD);
+        assert_eq!(at(100), TrustThreshold::ONE_THIRD);
+    }
+
+    #[test]
+    fn unsorted_trust_threshold_overrides_are_rejected() {
+        assert!(options(&[(10, TrustThreshold::TWO_THIRDS)])
+            .validate()
+            .is_ok());
+
+        let unsorted = options(&[
+            (10, TrustThreshold::TWO_THIRDS),
+            (10, TrustThreshold::ONE_THIRD),
+        ]);
+
+        match unsorted.validate() {
+            Err(Error(ErrorDetail::InvalidTrustThresholdSchedule(e), _)) => {
+                assert_eq!(e.height, Height::from(10_u32));
+            }
+            other => panic!("expected InvalidTrustThresholdSchedule, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn trust_threshold_overrides_default_to_empty() {
+        let json = r#"{
+            "trust_threshold": { "numerator": "1", "denominator": "3" },
+            "trusting_period": { "secs": 60, "nanos": 0 },
+            "clock_drift": { "secs": 1, "nanos": 0 }
+        }"#;
+
+        let options: Options = serde_json::from_str(json).unwrap();
+        assert!(options.trust_threshold_overrides.is_empty());
+        assert_eq!(options.forward_clock_drift, None);
+    }
+
+    #[test]
+    fn unsorted_trust_threshold_overrides_are_rejected_when_deserializing() {
+        let unsorted = options(&[
+            (20, TrustThreshold::TWO_THIRDS),
+            (10, TrustThreshold::ONE_THIRD),
+        ]);
+
+        let json = serde_json::to_string(&unsorted).unwrap();
+        let err = serde_json::from_str::<Options>(&json).unwrap_err();
+        assert!(err.to_string().contains("out of order"));
+
+        let sorted = options(&[
+            (10, TrustThreshold::TWO_THIRDS),
+            (20, TrustThreshold::ONE_THIRD),
+        ]);
+
+        let json = serde_json::to_string(&sorted).unwrap();
+        assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), sorted);
+    }
+
+    #[test]
+    fn forward_clock_drift_defaults_to_clock_drift() {
+        let mut options = options(&[]);
+        assert_eq!(options.max_forward_drift(), Duration::from_secs(1));
+
+        options.forward_clock_drift = Some(Duration::from_secs(5));
+        assert_eq!(options.max_forward_drift(), Duration::from_secs(5));
+    }
+}
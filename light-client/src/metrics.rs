@@ -0,0 +1,261 @@
+//! Prometheus metrics for the light client, along with a minimal HTTP exporter.
+//!
+//! The [`Metrics`] are fed by the [`Supervisor`](crate::supervisor::Supervisor)
+//! when enabled with `Supervisor::with_metrics`, and can then be scraped by
+//! Prometheus at the `/metrics` endpoint served by [`serve`].
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flex_error::{define_error, TraceError};
+use prometheus::{Encoder, Gauge, IntCounter, IntGauge, Registry, TextEncoder};
+
+use crate::types::LightBlock;
+
+define_error! {
+    #[derive(Debug)]
+    MetricsError {
+        Prometheus
+            [ TraceError<prometheus::Error> ]
+            | _ | { "prometheus error" },
+
+        Io
+            [ TraceError<std::io::Error> ]
+            | _ | { "I/O error" },
+    }
+}
+
+/// Metrics about the operation of the light client, registered in a Prometheus registry.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    registry: Registry,
+    trusted_height: IntGauge,
+    trusted_header_timestamp: Gauge,
+    trusted_header_age: Gauge,
+    fetch_failures: IntCounter,
+    fork_detections: IntCounter,
+}
+
+impl Metrics {
+    /// Create the light client metrics, and register them in a new registry.
+    pub fn new() -> Result<Self, MetricsError> {
+        Self::with_registry(Registry::new())
+    }
+
+    /// Create the light client metrics, and register them in the given registry.
+    pub fn with_registry(registry: Registry) -> Result<Self, MetricsError> {
+        let trusted_height = IntGauge::new(
+            "light_client_trusted_height",
+            "Height of the latest trusted block",
+        )
+        .map_err(MetricsError::prometheus)?;
+
+        let trusted_header_timestamp = Gauge::new(
+            "light_client_trusted_header_timestamp_seconds",
+            "Time of the header of the latest trusted block, in seconds since the UNIX epoch",
+        )
+        .map_err(MetricsError::prometheus)?;
+
+        let trusted_header_age = Gauge::new(
+            "light_client_trusted_header_age_seconds",
+            "Time elapsed since the header of the latest trusted block was created, in seconds",
+        )
+        .map_err(MetricsError::prometheus)?;
+
+        let fetch_failures = IntCounter::new(
+            "light_client_fetch_failures_total",
+            "Number of failures to fetch a light block from a full node",
+        )
+        .map_err(MetricsError::prometheus)?;
+
+        let fork_detections = IntCounter::new(
+            "light_client_fork_detections_total",
+            "Number of forks detected between the primary and the witnesses",
+        )
+        .map_err(MetricsError::prometheus)?;
+
+        registry
+            .register(Box::new(trusted_height.clone()))
+            .and_then(|_| registry.register(Box::new(trusted_header_timestamp.clone())))
+            .and_then(|_| registry.register(Box::new(trusted_header_age.clone())))
+            .and_then(|_| registry.register(Box::new(fetch_failures.clone())))
+            .and_then(|_| registry.register(Box::new(fork_detections.clone())))
+            .map_err(MetricsError::prometheus)?;
+
+        Ok(Self {
+            registry,
+            trusted_height,
+            trusted_header_timestamp,
+            trusted_header_age,
+            fetch_failures,
+            fork_detections,
+        })
+    }
+
+    /// The registry in which the metrics are registered.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Record that the given light block is now the latest trusted block.
+    pub fn observe_trusted_block(&self, light_block: &LightBlock) {
+        let time = light_block.signed_header.header.time;
+        let timestamp = time
+            .duration_since(tendermint::Time::unix_epoch())
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+
+        self.trusted_height.set(light_block.height().value() as i64);
+        self.trusted_header_timestamp.set(timestamp);
+        self.update_trusted_header_age();
+    }
+
+    /// Record a failure to fetch a light block from a full node.
+    pub fn observe_fetch_failure(&self) {
+        self.fetch_failures.inc();
+    }
+
+    /// Record that forks with the given number of witnesses were detected.
+    pub fn observe_fork_detections(&self, count: usize) {
+        self.fork_detections.inc_by(count as u64);
+    }
+
+    /// Encode all the metrics in the registry in the Prometheus text format.
+    pub fn encode(&self) -> Result<String, MetricsError> {
+        self.update_trusted_header_age();
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(MetricsError::prometheus)?;
+
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    /// The age of the latest trusted header grows over time,
+    /// so we compute it anew every time the metrics are read.
+    fn update_trusted_header_age(&self) {
+        let timestamp = self.trusted_header_timestamp.get();
+        if timestamp <= 0.0 {
+            return;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+
+        self.trusted_header_age.set((now - timestamp).max(0.0));
+    }
+}
+
+/// Serve the given metrics over HTTP at the `/metrics` path of the given address,
+/// from a new background thread.
+///
+/// Returns the address the exporter is listening on (useful when binding to port 0),
+/// and the handle of the thread serving the requests.
+pub fn serve(
+    metrics: Metrics,
+    addr: impl ToSocketAddrs,
+) -> Result<(SocketAddr, JoinHandle<()>), MetricsError> {
+    let listener = TcpListener::bind(addr).map_err(MetricsError::io)?;
+    let local_addr = listener.local_addr().map_err(MetricsError::io)?;
+
+    let handle = thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A misbehaving client must not bring down the exporter.
+            let _ = handle_request(&metrics, stream);
+        }
+    });
+
+    Ok((local_addr, handle))
+}
+
+fn handle_request(metrics: &Metrics, mut stream: TcpStream) -> Result<(), MetricsError> {
+    let mut request_line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut request_line)
+        .map_err(MetricsError::io)?;
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+
+    let (status, content_type, body) = if path == "/metrics" {
+        let body = metrics.encode()?;
+        ("200 OK", TextEncoder::new().format_type().to_string(), body)
+    } else {
+        (
+            "404 Not Found",
+            "text/plain".to_string(),
+            "not found\n".to_string(),
+        )
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+    .map_err(MetricsError::io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tendermint_testgen::{Generator, LightChain};
+
+    fn trusted_block() -> LightBlock {
+        let chain = LightChain::default_with_length(3);
+        chain.light_blocks[2].generate().unwrap().into()
+    }
+
+    #[test]
+    fn encode_reports_observations() {
+        let metrics = Metrics::new().unwrap();
+
+        metrics.observe_trusted_block(&trusted_block());
+        metrics.observe_fetch_failure();
+        metrics.observe_fetch_failure();
+        metrics.observe_fork_detections(1);
+
+        let text = metrics.encode().unwrap();
+
+        assert!(text.contains("light_client_trusted_height 3"));
+        assert!(text.contains("light_client_fetch_failures_total 2"));
+        assert!(text.contains("light_client_fork_detections_total 1"));
+        assert!(text.contains("light_client_trusted_header_age_seconds"));
+    }
+
+    #[test]
+    fn metrics_cannot_be_registered_twice() {
+        let metrics = Metrics::new().unwrap();
+        assert!(Metrics::with_registry(metrics.registry().clone()).is_err());
+    }
+
+    #[test]
+    fn exporter_serves_metrics() {
+        let metrics = Metrics::new().unwrap();
+        metrics.observe_trusted_block(&trusted_block());
+
+        let (addr, _handle) = serve(metrics, "127.0.0.1:0").unwrap();
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = get("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("light_client_trusted_height 3"));
+
+        assert!(get("/").starts_with("HTTP/1.1 404 Not Found"));
+    }
+}
@@ -42,7 +42,7 @@ impl ProdCommitValidator {
 
 impl Default for ProdCommitValidator {
     fn default() -> Self {
-        Self::new(ProdHasher::default())
+        Self::new(ProdHasher)
     }
 }
 
@@ -94,7 +94,7 @@ impl CommitValidator for ProdCommitValidator {
                 } => validator_address,
             };
 
-            if validator_set.validator(*validator_address) == None {
+            if validator_set.validator(*validator_address).is_none() {
                 return Err(VerificationError::faulty_signer(
                     *validator_address,
                     self.hasher.hash_validator_set(validator_set),
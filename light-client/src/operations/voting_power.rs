@@ -5,35 +5,15 @@ use crate::{
     types::{Commit, SignedHeader, TrustThreshold, ValidatorSet},
 };
 
-use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::fmt;
 
 use std::convert::TryFrom;
 use tendermint::block::CommitSig;
+use tendermint::signature::BatchVerifier;
 use tendermint::trust_threshold::TrustThreshold as _;
-use tendermint::vote::{SignedVote, ValidatorIndex, Vote};
-
-/// Tally for the voting power computed by the `VotingPowerCalculator`
-#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
-pub struct VotingPowerTally {
-    /// Total voting power
-    pub total: u64,
-    /// Tallied voting power
-    pub tallied: u64,
-    /// Trust threshold for voting power
-    pub trust_threshold: TrustThreshold,
-}
+use tendermint::vote::{self, SignedVote, ValidatorIndex, Vote};
 
-impl fmt::Display for VotingPowerTally {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "VotingPower(total={} tallied={} trust_threshold={})",
-            self.total, self.tallied, self.trust_threshold
-        )
-    }
-}
+pub use tendermint::vote::VotingPowerTally;
 
 /// Computes the voting power in a commit against a validator set.
 ///
@@ -110,8 +90,12 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
     ) -> Result<VotingPowerTally, VerificationError> {
         let signatures = &signed_header.commit.signatures;
 
-        let mut tallied_voting_power = 0_u64;
         let mut seen_validators = HashSet::new();
+        let mut duplicate_validator = None;
+
+        // Signatures to verify as a batch, along with what to do once verified.
+        let mut batch = BatchVerifier::new();
+        let mut batched = Vec::new();
 
         // Get non-absent votes from the signatures
         let non_absent_votes = signatures.iter().enumerate().flat_map(|(idx, signature)| {
@@ -126,9 +110,10 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
         for (signature, vote) in non_absent_votes {
             // Ensure we only count a validator's power once
             if seen_validators.contains(&vote.validator_address) {
-                return Err(VerificationError::duplicate_validator(
-                    vote.validator_address,
-                ));
+                // Invalid signatures of the votes before take precedence,
+                // so only fail once those have been verified.
+                duplicate_validator = Some(vote.validator_address);
+                break;
             } else {
                 seen_validators.insert(vote.validator_address);
             }
@@ -145,22 +130,39 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
                 vote.signature,
             );
 
-            // Check vote is valid
             let sign_bytes = signed_vote.sign_bytes();
-            if validator
-                .verify_signature(&sign_bytes, signed_vote.signature())
-                .is_err()
-            {
-                return Err(VerificationError::invalid_signature(
-                    signed_vote.signature().to_bytes(),
-                    Box::new(validator),
-                    sign_bytes,
-                ));
-            }
+            batch.push(
+                validator.pub_key,
+                sign_bytes.clone(),
+                *signed_vote.signature(),
+            );
+            batched.push((signature, signed_vote, validator, sign_bytes));
+        }
+
+        // Check votes are valid, all at once
+        if let Err(invalid) = batch.verify() {
+            let (_, signed_vote, validator, sign_bytes) = batched.swap_remove(invalid[0]);
+            return Err(VerificationError::invalid_signature(
+                signed_vote.signature().to_bytes(),
+                Box::new(validator),
+                sign_bytes,
+            ));
+        }
+
+        if let Some(validator_address) = duplicate_validator {
+            return Err(VerificationError::duplicate_validator(validator_address));
+        }
+
+        let total = vote::Power::try_from(self.total_power_of(validator_set))
+            .map_err(|e| VerificationError::implementation_specific(e.to_string()))?;
+        let mut voting_power = VotingPowerTally::new(total, trust_threshold);
 
+        for (signature, _, validator, _) in batched {
             // If the vote is neither absent nor nil, tally its power
             if signature.is_commit() {
-                tallied_voting_power += validator.power();
+                voting_power
+                    .tally(validator.power)
+                    .map_err(|e| VerificationError::implementation_specific(e.to_string()))?;
             } else {
                 // It's OK. We include stray signatures (~votes for nil)
                 // to measure validator availability.
@@ -170,12 +172,6 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
             // See https://github.com/informalsystems/tendermint-rs/issues/235
         }
 
-        let voting_power = VotingPowerTally {
-            total: self.total_power_of(validator_set),
-            tallied: tallied_voting_power,
-            trust_threshold,
-        };
-
         Ok(voting_power)
     }
 }
@@ -186,7 +182,7 @@ fn non_absent_vote(
     commit: &Commit,
 ) -> Option<Vote> {
     let (validator_address, timestamp, signature, block_id) = match commit_sig {
-        CommitSig::BlockIdFlagAbsent { .. } => return None,
+        CommitSig::BlockIdFlagAbsent => return None,
         CommitSig::BlockIdFlagCommit {
             validator_address,
             timestamp,
@@ -205,7 +201,7 @@ fn non_absent_vote(
     };
 
     Some(Vote {
-        vote_type: tendermint::vote::Type::Precommit,
+        vote_type: vote::Type::Precommit,
         height: commit.height,
         round: commit.round,
         block_id,
@@ -238,9 +234,30 @@ mod tests {
         trust_threshold: TrustThresholdFraction::ONE_THIRD,
     };
 
+    #[test]
+    fn test_required_voting_power() {
+        assert_eq!(EXPECTED_RESULT.required(), 34);
+        assert_eq!(EXPECTED_RESULT.missing(), 34);
+
+        let tally = VotingPowerTally {
+            total: 90,
+            tallied: 61,
+            trust_threshold: TrustThresholdFraction::TWO_THIRDS,
+        };
+
+        assert_eq!(tally.required(), 61);
+        assert_eq!(tally.missing(), 0);
+        assert!(tally
+            .trust_threshold
+            .is_enough_power(tally.required(), tally.total));
+        assert!(!tally
+            .trust_threshold
+            .is_enough_power(tally.required() - 1, tally.total));
+    }
+
     #[test]
     fn test_empty_signatures() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let mut light_block: LightBlock = TestgenLightBlock::new_default(10)
@@ -261,7 +278,7 @@ mod tests {
 
     #[test]
     fn test_all_signatures_absent() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let mut testgen_lb = TestgenLightBlock::new_default(10);
@@ -283,7 +300,7 @@ mod tests {
 
     #[test]
     fn test_all_signatures_nil() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let validator_set = ValidatorSet::new(vec!["a", "b"]);
@@ -305,7 +322,7 @@ mod tests {
 
     #[test]
     fn test_one_invalid_signature() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let mut testgen_lb = TestgenLightBlock::new_default(10);
@@ -333,7 +350,7 @@ mod tests {
 
     #[test]
     fn test_all_signatures_invalid() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let mut testgen_lb = TestgenLightBlock::new_default(10);
@@ -355,7 +372,7 @@ mod tests {
 
     #[test]
     fn test_signatures_from_diff_valset() {
-        let vp_calculator = ProdVotingPowerCalculator::default();
+        let vp_calculator = ProdVotingPowerCalculator;
         let trust_threshold = TrustThreshold::default();
 
         let mut light_block: LightBlock = TestgenLightBlock::new_default(10)
@@ -133,6 +133,7 @@ impl<T> PeerList<T> {
     ///
     /// ## Errors
     /// - If there are no witness left, returns `ErrorKind::NoWitnessLeft`.
+    #[allow(clippy::nonminimal_bool)]
     #[post(ret.is_ok() ==> Self::invariant(self))]
     pub fn replace_faulty_primary(
         &mut self,
@@ -151,6 +152,25 @@ impl<T> PeerList<T> {
         }
     }
 
+    /// Mark the given peer as faulty, wherever it is in the peer list.
+    /// A faulty witness is replaced by a full node, if any, and a faulty
+    /// primary by a witness.
+    ///
+    /// ## Errors
+    /// - If the given peer is the primary and there are no witness left,
+    ///   returns `ErrorKind::NoWitnessLeft`.
+    pub fn mark_faulty(&mut self, peer_id: PeerId) -> Result<(), Error> {
+        if peer_id == self.primary {
+            self.replace_faulty_primary(None)?;
+        } else if self.witnesses.contains(&peer_id) {
+            self.replace_faulty_witness(peer_id);
+        } else if self.full_nodes.remove(&peer_id) {
+            self.faulty_nodes.insert(peer_id);
+        }
+
+        Ok(())
+    }
+
     /// Get a reference to the underlying `HashMap`
     pub fn values(&self) -> &HashMap<PeerId, T> {
         &self.values
@@ -332,4 +352,21 @@ mod tests {
         let _ = peer_list.replace_faulty_witness(d());
         unreachable!();
     }
+
+    #[test]
+    fn mark_faulty_full_node_succeeds() {
+        let mut peer_list = dummy_peer_list();
+        peer_list.mark_faulty(c()).unwrap();
+        assert!(peer_list.full_nodes_ids().is_empty());
+        assert_eq!(peer_list.faulty_nodes_ids().to_vec(), vec![c()]);
+        assert!(PeerList::invariant(&peer_list));
+    }
+
+    #[test]
+    fn mark_faulty_primary_promotes_witness() {
+        let mut peer_list = dummy_peer_list();
+        peer_list.mark_faulty(a()).unwrap();
+        assert_eq!(peer_list.primary_id(), b());
+        assert_eq!(peer_list.faulty_nodes_ids().to_vec(), vec![a()]);
+    }
 }
@@ -0,0 +1,66 @@
+//! Interface and implementations of the peer store.
+//!
+//! See the `memory` and `json` modules for:
+//!
+//! - a transient, in-memory implementation for testing purposes
+//! - a persistent, file-backed implementation for production
+
+use std::fmt::Debug;
+
+use flex_error::{define_error, TraceError};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{PeerId, PeerRole, Time};
+
+pub mod json;
+pub mod memory;
+
+define_error! {
+    #[derive(Debug)]
+    PeerStoreError {
+        Io
+            [ TraceError<std::io::Error> ]
+            | _ | { "I/O error" },
+
+        Json
+            [ TraceError<serde_json::Error> ]
+            | _ | { "JSON error" },
+    }
+}
+
+/// What the supervisor knows about a full node, across restarts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// The peer ID of the node
+    pub peer_id: PeerId,
+    /// The latest role of the node in the peer list
+    pub role: PeerRole,
+    /// The time at which a light block provided by the node was last verified, if ever
+    pub last_success: Option<Time>,
+    /// Whether the node has been deemed faulty, and must not be used anymore
+    pub banned: bool,
+}
+
+/// Store for the records of the full nodes known to the supervisor.
+///
+/// The supervisor consults the store on startup to avoid banned nodes,
+/// and updates it as the roles of the nodes change during operation.
+pub trait PeerStore: Debug + Send {
+    /// Get the record of the given peer, if any.
+    fn get(&self, peer_id: PeerId) -> Option<PeerRecord>;
+
+    /// Insert the given record, overriding any other record for the same peer.
+    fn update(&mut self, record: PeerRecord) -> Result<(), PeerStoreError>;
+
+    /// Get all the records in the store.
+    fn all(&self) -> Vec<PeerRecord>;
+
+    /// Get the peer IDs of all the banned nodes.
+    fn banned(&self) -> Vec<PeerId> {
+        self.all()
+            .into_iter()
+            .filter(|record| record.banned)
+            .map(|record| record.peer_id)
+            .collect()
+    }
+}
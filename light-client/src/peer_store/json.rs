@@ -0,0 +1,123 @@
+//! Persistent peer store backed by a JSON file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::peer_store::{PeerRecord, PeerStore, PeerStoreError};
+use crate::types::PeerId;
+
+/// Persistent peer store backed by a JSON file, which is rewritten on every update.
+#[derive(Debug, Clone)]
+pub struct JsonPeerStore {
+    path: PathBuf,
+    records: BTreeMap<PeerId, PeerRecord>,
+}
+
+impl JsonPeerStore {
+    /// Load the peer store from the JSON file at the given path,
+    /// or start with an empty store if the file does not exist yet.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PeerStoreError> {
+        let path = path.as_ref().to_path_buf();
+
+        let records = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<PeerRecord>>(&bytes)
+                .map_err(PeerStoreError::json)?
+                .into_iter()
+                .map(|record| (record.peer_id, record))
+                .collect(),
+            Err(e) if e.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(PeerStoreError::io(e)),
+        };
+
+        Ok(Self { path, records })
+    }
+
+    /// Write the records to a temporary file first, and then move it over
+    /// the previous one, so that a crash cannot leave a truncated file behind.
+    fn save(&self) -> Result<(), PeerStoreError> {
+        let records = self.records.values().collect::<Vec<_>>();
+        let bytes = serde_json::to_vec_pretty(&records).map_err(PeerStoreError::json)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, bytes).map_err(PeerStoreError::io)?;
+        fs::rename(&tmp_path, &self.path).map_err(PeerStoreError::io)
+    }
+}
+
+impl PeerStore for JsonPeerStore {
+    fn get(&self, peer_id: PeerId) -> Option<PeerRecord> {
+        self.records.get(&peer_id).cloned()
+    }
+
+    fn update(&mut self, record: PeerRecord) -> Result<(), PeerStoreError> {
+        if self.records.get(&record.peer_id) == Some(&record) {
+            return Ok(());
+        }
+
+        self.records.insert(record.peer_id, record);
+        self.save()
+    }
+
+    fn all(&self) -> Vec<PeerRecord> {
+        self.records.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PeerRole;
+
+    fn peer_id() -> PeerId {
+        "6de6deefcc12585340af922a0dd332084546a207".parse().unwrap()
+    }
+
+    #[test]
+    fn records_survive_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+
+        let mut store = JsonPeerStore::open(&path).unwrap();
+        assert!(store.all().is_empty());
+
+        let record = PeerRecord {
+            peer_id: peer_id(),
+            role: PeerRole::Faulty,
+            last_success: None,
+            banned: true,
+        };
+
+        store.update(record.clone()).unwrap();
+
+        let store = JsonPeerStore::open(&path).unwrap();
+        assert_eq!(store.get(peer_id()), Some(record));
+        assert_eq!(store.banned(), vec![peer_id()]);
+    }
+
+    #[test]
+    fn failed_writes_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("peers.json");
+
+        let mut store = JsonPeerStore::open(&path).unwrap();
+        let record = PeerRecord {
+            peer_id: peer_id(),
+            role: PeerRole::Primary,
+            last_success: None,
+            banned: false,
+        };
+
+        assert!(store.update(record).is_err());
+    }
+
+    #[test]
+    fn invalid_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert!(JsonPeerStore::open(&path).is_err());
+    }
+}
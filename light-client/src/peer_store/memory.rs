@@ -0,0 +1,36 @@
+//! Transient in-memory peer store
+
+use std::collections::BTreeMap;
+
+use crate::peer_store::{PeerRecord, PeerStore, PeerStoreError};
+use crate::types::PeerId;
+
+/// Transient in-memory peer store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryPeerStore {
+    records: BTreeMap<PeerId, PeerRecord>,
+}
+
+impl MemoryPeerStore {
+    /// Create a new, empty, in-memory peer store
+    pub fn new() -> Self {
+        Self {
+            records: BTreeMap::new(),
+        }
+    }
+}
+
+impl PeerStore for MemoryPeerStore {
+    fn get(&self, peer_id: PeerId) -> Option<PeerRecord> {
+        self.records.get(&peer_id).cloned()
+    }
+
+    fn update(&mut self, record: PeerRecord) -> Result<(), PeerStoreError> {
+        self.records.insert(record.peer_id, record);
+        Ok(())
+    }
+
+    fn all(&self) -> Vec<PeerRecord> {
+        self.records.values().cloned().collect()
+    }
+}
@@ -0,0 +1,428 @@
+//! Pool of supervisors following several chains at once.
+//!
+//! Relayer-style applications need to follow many chains, each with its own
+//! light client, peers and verification options. The [`SupervisorPool`] runs one
+//! [`Supervisor`] per chain, each in its own thread, and exposes all of them behind
+//! a single [`PoolHandle`] which dispatches requests based on the chain ID.
+//!
+//! With the `rpc-client` feature, the pool also provides a runtime which the
+//! light clients of all chains can share, see [`SupervisorPool::runtime`].
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+use tendermint::chain;
+
+#[cfg(feature = "rpc-client")]
+use crate::components::io::IoError;
+use crate::errors::Error;
+use crate::supervisor::{Handle, Supervisor, SupervisorHandle};
+use crate::types::{Height, LatestStatus, LightBlock, Status, SupervisorStatus, VerifiedBlock};
+
+type Handles = Arc<RwLock<BTreeMap<chain::Id, SupervisorHandle>>>;
+
+/// Runs the supervisors of several chains, and manages their threads.
+///
+/// Each supervisor is configured independently, eg. with its own light client options,
+/// before being added to the pool with [`SupervisorPool::add`].
+///
+/// Dropping the pool terminates all its supervisors, see [`SupervisorPool::terminate`]
+/// to get hold of the errors they exited with.
+#[derive(Default)]
+pub struct SupervisorPool {
+    handles: Handles,
+    threads: BTreeMap<chain::Id, JoinHandle<Result<(), Error>>>,
+    #[cfg(feature = "rpc-client")]
+    runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl SupervisorPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run the given supervisor in a new thread, to follow the chain with the
+    /// given ID.
+    ///
+    /// Fails if the pool already follows this chain, or if the latest block
+    /// trusted by the supervisor belongs to another chain.
+    pub fn add(&mut self, chain_id: chain::Id, supervisor: Supervisor) -> Result<(), Error> {
+        if self.threads.contains_key(&chain_id) {
+            return Err(Error::duplicate_chain(chain_id));
+        }
+
+        let trusted = supervisor
+            .latest_trusted()
+            .ok_or_else(|| Error::no_trusted_state(Status::Trusted))?;
+
+        let trusted_chain_id = &trusted.signed_header.header.chain_id;
+        if trusted_chain_id != &chain_id {
+            return Err(Error::chain_id_mismatch(chain_id, trusted_chain_id.clone()));
+        }
+
+        let handle = supervisor.handle();
+
+        let thread = thread::Builder::new()
+            .name(format!("light-client-{}", chain_id))
+            .spawn(move || supervisor.run())
+            .map_err(Error::spawn)?;
+
+        self.handles
+            .write()
+            .unwrap()
+            .insert(chain_id.clone(), handle);
+
+        self.threads.insert(chain_id, thread);
+
+        Ok(())
+    }
+
+    /// Terminate the supervisor of the chain with the given ID, and remove it from the pool.
+    ///
+    /// Returns the error the supervisor exited with, if it stopped on its own
+    /// beforehand, eg. because it detected a fork.
+    pub fn remove(&mut self, chain_id: &chain::Id) -> Result<(), Error> {
+        let handle = self
+            .handles
+            .write()
+            .unwrap()
+            .remove(chain_id)
+            .ok_or_else(|| Error::unknown_chain(chain_id.clone()))?;
+
+        let thread = self
+            .threads
+            .remove(chain_id)
+            .ok_or_else(|| Error::unknown_chain(chain_id.clone()))?;
+
+        // If the supervisor has already exited, the error it exited with is
+        // more informative than the failure to send it the termination request.
+        let terminated = handle.terminate();
+        let exited = thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+        exited.and(terminated)
+    }
+
+    /// The IDs of the chains followed by the pool.
+    pub fn chain_ids(&self) -> Vec<chain::Id> {
+        self.threads.keys().cloned().collect()
+    }
+
+    /// Create a new handle to the supervisors of this pool.
+    ///
+    /// The handle also gives access to the chains added to the pool after its creation.
+    pub fn handle(&self) -> PoolHandle {
+        PoolHandle {
+            handles: self.handles.clone(),
+        }
+    }
+
+    /// A handle to the runtime shared by the light clients of the pool,
+    /// which is started on first use and shut down along with the pool.
+    ///
+    /// Pass it to [`ProdIo::with_runtime`] so that the light clients of all
+    /// chains issue their requests on this runtime, instead of spinning up a
+    /// new one for each request.
+    ///
+    /// [`ProdIo::with_runtime`]: crate::components::io::ProdIo::with_runtime
+    #[cfg(feature = "rpc-client")]
+    pub fn runtime(&mut self) -> Result<tokio::runtime::Handle, Error> {
+        if let Some(runtime) = &self.runtime {
+            return Ok(runtime.handle().clone());
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .thread_name("light-client-pool")
+            .enable_all()
+            .build()
+            .map_err(|e| Error::io(IoError::runtime(e)))?;
+
+        Ok(self.runtime.insert(runtime).handle().clone())
+    }
+
+    /// Terminate all the supervisors of the pool and wait for their threads to exit.
+    ///
+    /// All supervisors are terminated, even if some of them fail to, in which
+    /// case the first error is returned.
+    pub fn terminate(mut self) -> Result<(), Error> {
+        self.terminate_all()
+    }
+
+    fn terminate_all(&mut self) -> Result<(), Error> {
+        let mut result = Ok(());
+
+        for chain_id in self.chain_ids() {
+            let outcome = self.remove(&chain_id);
+            result = result.and(outcome);
+        }
+
+        result
+    }
+}
+
+impl Drop for SupervisorPool {
+    fn drop(&mut self) {
+        // Errors cannot be reported from here, callers interested in them
+        // should use `SupervisorPool::terminate` instead.
+        let _ = self.terminate_all();
+
+        // Unlike dropping it, shutting down the runtime in the background
+        // does not panic when the pool is dropped from within another runtime.
+        #[cfg(feature = "rpc-client")]
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// A handle to the supervisors of a [`SupervisorPool`], which dispatches
+/// requests to the supervisor of the given chain.
+#[derive(Clone)]
+pub struct PoolHandle {
+    handles: Handles,
+}
+
+impl PoolHandle {
+    /// Get a handle to the supervisor of the chain with the given ID.
+    pub fn chain(&self, chain_id: &chain::Id) -> Result<SupervisorHandle, Error> {
+        self.handles
+            .read()
+            .unwrap()
+            .get(chain_id)
+            .cloned()
+            .ok_or_else(|| Error::unknown_chain(chain_id.clone()))
+    }
+
+    /// Get the latest trusted block of the given chain.
+    pub fn latest_trusted(&self, chain_id: &chain::Id) -> Result<Option<LightBlock>, Error> {
+        self.chain(chain_id)?.latest_trusted()
+    }
+
+    /// Get the latest status of the given chain.
+    pub fn latest_status(&self, chain_id: &chain::Id) -> Result<LatestStatus, Error> {
+        self.chain(chain_id)?.latest_status()
+    }
+
+    /// Get health and status information about the supervisor of the given chain.
+    pub fn status(&self, chain_id: &chain::Id) -> Result<SupervisorStatus, Error> {
+        self.chain(chain_id)?.status()
+    }
+
+    /// Verify the given chain to its highest block.
+    pub fn verify_to_highest(&self, chain_id: &chain::Id) -> Result<VerifiedBlock, Error> {
+        self.chain(chain_id)?.verify_to_highest()
+    }
+
+    /// Verify the given chain to the block at the given height.
+    pub fn verify_to_target(
+        &self,
+        chain_id: &chain::Id,
+        height: Height,
+    ) -> Result<VerifiedBlock, Error> {
+        self.chain(chain_id)?.verify_to_target(height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    use crate::components::{scheduler, verifier::ProdVerifier};
+    use crate::errors::ErrorDetail;
+    use crate::fork_detector::ProdForkDetector;
+    use crate::light_client::{LightClient, Options};
+    use crate::operations::ProdHasher;
+    use crate::peer_list::PeerList;
+    use crate::state::State;
+    use crate::store::{memory::MemoryStore, LightStore};
+    use crate::supervisor::Instance;
+    use crate::tests::{MockClock, MockEvidenceReporter, MockIo};
+    use crate::types::{Time, TrustThreshold};
+
+    fn make_supervisor(light_blocks: Vec<LightBlock>) -> Supervisor {
+        let chain_id = light_blocks[0].signed_header.header.chain_id.to_string();
+        let peer_id = light_blocks[0].provider;
+
+        let mut light_store = MemoryStore::new();
+        light_store.insert(light_blocks[0].clone(), Status::Trusted);
+
+        let state = State {
+            light_store: Box::new(light_store),
+            verification_trace: HashMap::new(),
+        };
+
+        let options = Options {
+            trust_threshold: TrustThreshold::TWO_THIRDS,
+            trusting_period: Duration::from_secs(604800),
+            clock_drift: Duration::from_secs(0),
+            max_bisection_steps: None,
+            trust_threshold_overrides: Vec::new(),
+            forward_clock_drift: None,
+        };
+
+        let now = Time::parse_from_rfc3339("1970-01-01T00:00:11Z").unwrap();
+
+        let light_client = LightClient::new(
+            peer_id,
+            options,
+            MockClock { now },
+            scheduler::basic_bisecting_schedule,
+            ProdVerifier::default(),
+            ProdHasher,
+            MockIo::new(chain_id, light_blocks),
+        )
+        .unwrap();
+
+        let mut peer_list = PeerList::builder();
+        peer_list.primary(peer_id, Instance::new(light_client, state));
+
+        Supervisor::new(
+            peer_list.build(),
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .danger_skip_fork_detection()
+    }
+
+    fn light_blocks(chain_id: &chain::Id, length: u64) -> Vec<LightBlock> {
+        let mut light_block =
+            TestgenLightBlock::new_default_with_time_and_chain_id(chain_id.to_string(), 1, 1);
+        let mut light_blocks = vec![light_block.clone()];
+
+        for _ in 1..length {
+            light_block = light_block.next();
+            light_blocks.push(light_block.clone());
+        }
+
+        light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect()
+    }
+
+    fn chain_id(id: &str) -> chain::Id {
+        chain::Id::try_from(id).unwrap()
+    }
+
+    #[test]
+    fn dispatches_requests_by_chain_id() {
+        let (chain_a, chain_b) = (chain_id("chain-a"), chain_id("chain-b"));
+
+        let mut pool = SupervisorPool::new();
+        let handle = pool.handle();
+
+        pool.add(chain_a.clone(), make_supervisor(light_blocks(&chain_a, 5)))
+            .unwrap();
+        pool.add(chain_b.clone(), make_supervisor(light_blocks(&chain_b, 10)))
+            .unwrap();
+
+        assert_eq!(pool.chain_ids(), vec![chain_a.clone(), chain_b.clone()]);
+
+        assert_eq!(
+            handle
+                .verify_to_highest(&chain_a)
+                .unwrap()
+                .light_block()
+                .height()
+                .value(),
+            5
+        );
+        assert_eq!(
+            handle
+                .verify_to_target(&chain_b, Height::from(7_u32))
+                .unwrap()
+                .light_block()
+                .height()
+                .value(),
+            7
+        );
+        assert_eq!(
+            handle.status(&chain_b).unwrap().latest_trusted_height,
+            Some(Height::from(7_u32))
+        );
+
+        match handle.status(&chain_id("chain-c")) {
+            Err(Error(ErrorDetail::UnknownChain(_), _)) => {}
+            other => panic!("expected UnknownChain error, instead got {:?}", other),
+        }
+
+        pool.terminate().unwrap();
+
+        assert!(handle.status(&chain_a).is_err());
+    }
+
+    #[test]
+    fn chains_are_added_and_removed() {
+        let chain_a = chain_id("chain-a");
+
+        let mut pool = SupervisorPool::new();
+        pool.add(chain_a.clone(), make_supervisor(light_blocks(&chain_a, 3)))
+            .unwrap();
+
+        match pool.add(chain_a.clone(), make_supervisor(light_blocks(&chain_a, 3))) {
+            Err(Error(ErrorDetail::DuplicateChain(_), _)) => {}
+            other => panic!("expected DuplicateChain error, instead got {:?}", other),
+        }
+
+        pool.remove(&chain_a).unwrap();
+        assert!(pool.chain_ids().is_empty());
+        assert!(pool.remove(&chain_a).is_err());
+    }
+
+    #[test]
+    fn rejects_supervisors_of_other_chains() {
+        let (chain_a, chain_b) = (chain_id("chain-a"), chain_id("chain-b"));
+
+        let mut pool = SupervisorPool::new();
+
+        match pool.add(chain_a.clone(), make_supervisor(light_blocks(&chain_b, 3))) {
+            Err(Error(ErrorDetail::ChainIdMismatch(e), _)) => {
+                assert_eq!(e.expected, chain_a);
+                assert_eq!(e.got, chain_b);
+            }
+            other => panic!("expected ChainIdMismatch error, instead got {:?}", other),
+        }
+
+        assert!(pool.chain_ids().is_empty());
+    }
+
+    #[test]
+    fn dropping_the_pool_terminates_its_supervisors() {
+        let chain_a = chain_id("chain-a");
+
+        let mut pool = SupervisorPool::new();
+        let handle = pool.handle();
+
+        pool.add(chain_a.clone(), make_supervisor(light_blocks(&chain_a, 3)))
+            .unwrap();
+        let supervisor = handle.chain(&chain_a).unwrap();
+
+        drop(pool);
+
+        assert!(supervisor.status().is_err());
+    }
+
+    #[cfg(feature = "rpc-client")]
+    #[test]
+    fn runtime_is_shared() {
+        let mut pool = SupervisorPool::new();
+
+        let first = pool.runtime().unwrap();
+        let second = pool.runtime().unwrap();
+
+        let task = first.spawn(async { thread::current().name().map(str::to_owned) });
+        let worker = crate::utils::block_on_runtime(&second, None, task)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(worker.as_deref(), Some("light-client-pool"));
+    }
+}
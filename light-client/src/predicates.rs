@@ -8,6 +8,7 @@ use crate::{
 
 use errors::VerificationError;
 use std::time::Duration;
+use tendermint::chain;
 
 pub mod errors;
 
@@ -79,6 +80,18 @@ pub trait VerificationPredicates: Send + Sync {
         }
     }
 
+    /// Check that the signed header belongs to the given chain and that its commit
+    /// is consistent with its header, see [`tendermint::block::signed_header::SignedHeader::validate`].
+    fn is_consistent_signed_header(
+        &self,
+        signed_header: &SignedHeader,
+        chain_id: &chain::Id,
+    ) -> Result<(), VerificationError> {
+        signed_header
+            .validate(chain_id)
+            .map_err(|e| VerificationError::inconsistent_signed_header(e.to_string()))
+    }
+
     /// Validate the commit using the given commit validator.
     fn valid_commit(
         &self,
@@ -196,7 +209,7 @@ pub trait VerificationPredicates: Send + Sync {
         {
             Ok(())
         } else {
-            Err(VerificationError::invalid_next_validator_set(
+            Err(VerificationError::invalid_adjacent_validator_set(
                 light_block.signed_header.header.validators_hash,
                 trusted_state.signed_header.header.next_validators_hash,
             ))
@@ -209,12 +222,13 @@ pub trait VerificationPredicates: Send + Sync {
 /// - Ensure the latest trusted header hasn't expired
 /// - Ensure the header validator hashes match the given validators
 /// - Ensure the header next validator hashes match the given next validators
+/// - Ensure the header belongs to the trusted chain, and is consistent with the commit
 /// - Additional implementation specific validation via `commit_validator`
 /// - Check that the untrusted block is more recent than the trusted state
 /// - If the untrusted block is the very next block after the trusted block,
-/// check that their (next) validator sets hashes match.
+///   check that their (next) validator sets hashes match.
 /// - Otherwise, ensure that the untrusted block has a greater height than
-/// the trusted block.
+///   the trusted block.
 #[allow(clippy::too_many_arguments)]
 pub fn verify(
     vp: &dyn VerificationPredicates,
@@ -230,17 +244,27 @@ pub fn verify(
     vp.is_within_trust_period(&trusted.signed_header.header, options.trusting_period, now)?;
 
     // Ensure the header isn't from a future time
-    vp.is_header_from_past(&untrusted.signed_header.header, options.clock_drift, now)?;
+    vp.is_header_from_past(
+        &untrusted.signed_header.header,
+        options.max_forward_drift(),
+        now,
+    )?;
 
     // Ensure the header validator hashes match the given validators
-    vp.validator_sets_match(untrusted, &*hasher)?;
+    vp.validator_sets_match(untrusted, hasher)?;
 
     // Ensure the header next validator hashes match the given next validators
-    vp.next_validators_match(untrusted, &*hasher)?;
+    vp.next_validators_match(untrusted, hasher)?;
 
     // Ensure the header matches the commit
     vp.header_matches_commit(&untrusted.signed_header, hasher)?;
 
+    // Ensure the header belongs to the trusted chain, and is consistent with the commit
+    vp.is_consistent_signed_header(
+        &untrusted.signed_header,
+        &trusted.signed_header.header.chain_id,
+    )?;
+
     // Additional implementation specific validation
     vp.valid_commit(
         &untrusted.signed_header,
@@ -273,7 +297,7 @@ pub fn verify(
         vp.has_sufficient_validators_overlap(
             &untrusted.signed_header,
             &trusted.next_validators,
-            &options.trust_threshold,
+            &options.trust_threshold_at(untrusted.height()),
             voting_power_calculator,
         )?;
     }
@@ -328,7 +352,7 @@ mod tests {
         let header_one = Header::new(&val).generate().unwrap();
         let header_two = Header::new(&val).generate().unwrap();
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
 
         // 1. ensure valid header verifies
         let result_ok = vp.is_monotonic_bft_time(&header_two, &header_one);
@@ -351,7 +375,7 @@ mod tests {
         let header_one = Header::new(&val).generate().unwrap();
         let header_two = Header::new(&val).height(2).generate().unwrap();
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
 
         // 1. ensure valid header verifies
         let result_ok = vp.is_monotonic_height(&header_two, &header_one);
@@ -374,7 +398,7 @@ mod tests {
         let val = Validator::new("val-1");
         let header = Header::new(&[val]).generate().unwrap();
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
 
         // 1. ensure valid header verifies
         let mut trusting_period = Duration::new(1000, 0);
@@ -403,7 +427,7 @@ mod tests {
         let val = Validator::new("val-1");
         let header = Header::new(&[val]).generate().unwrap();
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
         let one_second = Duration::new(1, 0);
 
         // 1. ensure valid header verifies
@@ -432,8 +456,8 @@ mod tests {
 
         let bad_validator_set = ValidatorSet::new(vec!["bad-val"]).generate().unwrap();
 
-        let vp = ProdPredicates::default();
-        let hasher = ProdHasher::default();
+        let vp = ProdPredicates;
+        let hasher = ProdHasher;
 
         // Test positive case
         // 1. For predicate: validator_sets_match
@@ -492,8 +516,8 @@ mod tests {
             .unwrap()
             .signed_header;
 
-        let vp = ProdPredicates::default();
-        let hasher = ProdHasher::default();
+        let vp = ProdPredicates;
+        let hasher = ProdHasher;
 
         // 1. ensure valid signed header verifies
         let result_ok = vp.header_matches_commit(&signed_header, &hasher);
@@ -519,6 +543,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_consistent_signed_header() {
+        let light_block: LightBlock = TestgenLightBlock::new_default(1).generate().unwrap().into();
+        let signed_header = light_block.signed_header;
+        let vp = ProdPredicates;
+
+        // 1. ensure a valid signed header passes
+        assert!(vp
+            .is_consistent_signed_header(&signed_header, &signed_header.header.chain_id)
+            .is_ok());
+
+        // 2. ensure a signed header of another chain fails
+        let result_err =
+            vp.is_consistent_signed_header(&signed_header, &"other-chain".parse().unwrap());
+        match result_err {
+            Err(VerificationError(VerificationErrorDetail::InconsistentSignedHeader(e), _)) => {
+                assert!(e.detail.contains("another chain"), "{}", e.detail);
+            }
+            _ => panic!("expected InconsistentSignedHeader error"),
+        }
+    }
+
     #[test]
     fn test_valid_commit() {
         let light_block: LightBlock = TestgenLightBlock::new_default(1).generate().unwrap().into();
@@ -526,8 +572,8 @@ mod tests {
         let mut signed_header = light_block.signed_header;
         let val_set = light_block.validators;
 
-        let vp = ProdPredicates::default();
-        let hasher = ProdHasher::default();
+        let vp = ProdPredicates;
+        let hasher = ProdHasher;
         let commit_validator = ProdCommitValidator::new(hasher);
 
         // Test scenarios -->
@@ -576,7 +622,7 @@ mod tests {
                 .generate()
                 .expect("Failed to generate validator"),
         );
-        let val_set_with_faulty_signer = Set::without_proposer(bad_vals);
+        let val_set_with_faulty_signer = Set::without_proposer(bad_vals).unwrap();
 
         // reset signatures
         signed_header.commit.signatures = signatures;
@@ -617,7 +663,7 @@ mod tests {
 
         let light_block2: LightBlock = test_lb1.next().generate().unwrap().into();
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
 
         // Test scenarios -->
         // 1. next_validator_set hash matches
@@ -638,17 +684,17 @@ mod tests {
         let result_err = vp.valid_next_validator_set(&light_block3, &light_block2);
 
         match result_err {
-            Err(VerificationError(VerificationErrorDetail::InvalidNextValidatorSet(e), _)) => {
+            Err(VerificationError(VerificationErrorDetail::InvalidAdjacentValidatorSet(e), _)) => {
                 assert_eq!(
-                    e.header_next_validators_hash,
+                    e.validators_hash,
                     light_block3.signed_header.header.validators_hash
                 );
                 assert_eq!(
-                    e.next_validators_hash,
+                    e.trusted_next_validators_hash,
                     light_block2.signed_header.header.next_validators_hash
                 );
             }
-            _ => panic!("expected InvalidNextValidatorSet error"),
+            _ => panic!("expected InvalidAdjacentValidatorSet error"),
         }
     }
 
@@ -658,9 +704,9 @@ mod tests {
         let val_set = light_block.validators;
         let signed_header = light_block.signed_header;
 
-        let vp = ProdPredicates::default();
+        let vp = ProdPredicates;
         let mut trust_threshold = TrustThreshold::new(1, 3).expect("Cannot make trust threshold");
-        let voting_power_calculator = ProdVotingPowerCalculator::default();
+        let voting_power_calculator = ProdVotingPowerCalculator;
 
         // Test scenarios -->
         // 1. > trust_threshold validators overlap
@@ -681,7 +727,7 @@ mod tests {
                 .generate()
                 .unwrap(),
         );
-        let bad_valset = Set::without_proposer(vals);
+        let bad_valset = Set::without_proposer(vals).unwrap();
 
         trust_threshold = TrustThreshold::new(2, 3).expect("Cannot make trust threshold");
 
@@ -712,8 +758,8 @@ mod tests {
         let mut light_block: LightBlock =
             TestgenLightBlock::new_default(2).generate().unwrap().into();
 
-        let vp = ProdPredicates::default();
-        let voting_power_calculator = ProdVotingPowerCalculator::default();
+        let vp = ProdPredicates;
+        let voting_power_calculator = ProdVotingPowerCalculator;
 
         // Test scenarios -->
         // 1. +2/3 validators sign
@@ -89,6 +89,16 @@ define_error! {
                     e.header_next_validators_hash, e.next_validators_hash)
             },
 
+        InvalidAdjacentValidatorSet
+            {
+                validators_hash: Hash,
+                trusted_next_validators_hash: Hash,
+            }
+            | e | {
+                format_args!("validator set does not match the next validator set of the trusted block: validators_hash={0} trusted_next_validators_hash={1}",
+                    e.validators_hash, e.trusted_next_validators_hash)
+            },
+
         InvalidValidatorSet
             {
                 header_validators_hash: Hash,
@@ -132,6 +142,14 @@ define_error! {
         NoSignatureForCommit
             | _ | { "no signatures for commit"  },
 
+        InconsistentSignedHeader
+            {
+                detail: String,
+            }
+            | e | {
+                format_args!("inconsistent signed header: {}", e.detail)
+            },
+
         MismatchPreCommitLength
             {
                 pre_commit_length: usize,
@@ -161,6 +179,67 @@ define_error! {
     }
 }
 
+/// The verification predicates, as defined in the light client specification.
+///
+/// Each verification error is raised by exactly one of these predicates,
+/// see [`VerificationErrorDetail::predicate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Predicate {
+    /// The validator set matches the validators hash of the header
+    ValidatorSetsMatch,
+    /// The next validator set matches the next validators hash of the header
+    NextValidatorsMatch,
+    /// The header matches the block ID of the commit
+    HeaderMatchesCommit,
+    /// The signed header belongs to the trusted chain and its commit is consistent with its header
+    ConsistentSignedHeader,
+    /// The commit is well-formed and its signatures are valid
+    ValidCommit,
+    /// The trusted header is within the trusting period
+    WithinTrustPeriod,
+    /// The untrusted header is not from the future
+    HeaderFromPast,
+    /// The time of the untrusted header is after the one of the trusted header
+    MonotonicBftTime,
+    /// The height of the untrusted header is greater than the one of the trusted header
+    MonotonicHeight,
+    /// Enough voting power of the trusted validators signed the untrusted header
+    SufficientValidatorsOverlap,
+    /// More than two thirds of the untrusted validators signed the untrusted header
+    SufficientSignersOverlap,
+    /// The validator set of an adjacent block matches the next validators of the trusted block
+    ValidNextValidatorSet,
+}
+
+impl VerificationErrorDetail {
+    /// The predicate which raised this error.
+    ///
+    /// This allows telling apart eg. a block which cannot be trusted yet because
+    /// of a lack of overlap with the trusted validators, in which case bisection
+    /// may help, from a block with an invalid commit.
+    pub fn predicate(&self) -> Predicate {
+        match self {
+            Self::HeaderFromTheFuture(_) => Predicate::HeaderFromPast,
+            Self::ImplementationSpecific(_) => Predicate::ValidCommit,
+            Self::NotEnoughTrust(_) => Predicate::SufficientValidatorsOverlap,
+            Self::InsufficientSignersOverlap(_) => Predicate::SufficientSignersOverlap,
+            Self::DuplicateValidator(_) => Predicate::ValidCommit,
+            Self::InvalidSignature(_) => Predicate::ValidCommit,
+            Self::InvalidCommitValue(_) => Predicate::HeaderMatchesCommit,
+            Self::InvalidNextValidatorSet(_) => Predicate::NextValidatorsMatch,
+            Self::InvalidAdjacentValidatorSet(_) => Predicate::ValidNextValidatorSet,
+            Self::InvalidValidatorSet(_) => Predicate::ValidatorSetsMatch,
+            Self::NonIncreasingHeight(_) => Predicate::MonotonicHeight,
+            Self::NonMonotonicBftTime(_) => Predicate::MonotonicBftTime,
+            Self::NotWithinTrustPeriod(_) => Predicate::WithinTrustPeriod,
+            Self::NoSignatureForCommit(_) => Predicate::ValidCommit,
+            Self::InconsistentSignedHeader(_) => Predicate::ConsistentSignedHeader,
+            Self::MismatchPreCommitLength(_) => Predicate::ValidCommit,
+            Self::FaultySigner(_) => Predicate::ValidCommit,
+        }
+    }
+}
+
 impl ErrorExt for VerificationErrorDetail {
     fn not_enough_trust(&self) -> Option<VotingPowerTally> {
         match &self {
@@ -176,4 +255,12 @@ impl ErrorExt for VerificationErrorDetail {
     fn is_timeout(&self) -> Option<Duration> {
         None
     }
+
+    fn is_height_pruned(&self) -> Option<Height> {
+        None
+    }
+
+    fn failed_predicate(&self) -> Option<Predicate> {
+        Some(self.predicate())
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::{
     store::LightStore,
-    types::{Height, LightBlock, Status},
+    types::{Height, LightBlock},
 };
 
 use contracts::*;
@@ -38,18 +38,26 @@ impl State {
     pub fn trace_block(&mut self, target_height: Height, height: Height) {
         self.verification_trace
             .entry(target_height)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(height);
     }
 
     /// Get the verification trace for the block at `target_height`.
+    ///
+    /// The trace is the list of light blocks which were used to bridge trust from
+    /// a previously trusted block to the block at `target_height`, ordered by
+    /// decreasing height: the first element is the target block, and the last
+    /// one is the trusted block verification started from.
+    ///
+    /// Blocks which were fetched during bisection but could not be verified
+    /// are not part of the trace.
     pub fn get_trace(&self, target_height: Height) -> Vec<LightBlock> {
         let mut trace = self
             .verification_trace
             .get(&target_height)
             .unwrap_or(&HashSet::new())
             .iter()
-            .flat_map(|h| self.light_store.get(*h, Status::Verified))
+            .flat_map(|h| self.light_store.get_trusted_or_verified(*h))
             .collect::<Vec<_>>();
 
         trace.sort_by_key(|lb| lb.height());
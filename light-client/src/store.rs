@@ -7,15 +7,57 @@
 
 use std::fmt::Debug;
 
+use serde::{Deserialize, Serialize};
+
 use crate::types::{Height, LightBlock, Status};
 use crate::utils::std_ext;
 
+pub mod content_addressed;
 pub mod memory;
 
 #[cfg(feature = "lightstore-sled")]
 #[cfg_attr(docsrs, doc(cfg(feature = "lightstore-sled")))]
 pub mod sled;
 
+/// Statistics about the content of a light store.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreStats {
+    /// Number of unverified light blocks
+    pub unverified: usize,
+    /// Number of verified light blocks
+    pub verified: usize,
+    /// Number of trusted light blocks
+    pub trusted: usize,
+    /// Number of light blocks which failed verification
+    pub failed: usize,
+    /// Lowest height of a light block in the store, whatever its status
+    pub lowest_height: Option<Height>,
+    /// Highest height of a light block in the store, whatever its status
+    pub highest_height: Option<Height>,
+    /// Space used by the store on disk, in bytes, for persistent stores
+    pub size_on_disk: Option<u64>,
+}
+
+impl StoreStats {
+    /// Number of light blocks with the given status.
+    pub fn count(&self, status: Status) -> usize {
+        match status {
+            Status::Unverified => self.unverified,
+            Status::Verified => self.verified,
+            Status::Trusted => self.trusted,
+            Status::Failed => self.failed,
+        }
+    }
+
+    /// Total number of light blocks in the store.
+    pub fn total(&self) -> usize {
+        Status::iter()
+            .iter()
+            .map(|status| self.count(*status))
+            .sum()
+    }
+}
+
 /// Store for light blocks.
 ///
 /// The light store records light blocks received from peers, and their verification status.
@@ -47,6 +89,70 @@ pub trait LightStore: Debug + Send + Sync {
     /// Get an iterator of all light blocks with the given status.
     fn all(&self, status: Status) -> Box<dyn Iterator<Item = LightBlock>>;
 
+    /// Get the number of light blocks with the given status.
+    fn count(&self, status: Status) -> usize {
+        self.all(status).count()
+    }
+
+    /// Get the space used by the store on disk, in bytes, or `None` for transient stores.
+    fn size_on_disk(&self) -> Option<u64> {
+        None
+    }
+
+    /// Get statistics about the content of the store.
+    fn stats(&self) -> StoreStats {
+        let heights = |f: fn(&Self, Status) -> Option<LightBlock>| {
+            Status::iter()
+                .iter()
+                .filter_map(move |status| f(self, *status))
+                .map(|lb| lb.height())
+        };
+
+        StoreStats {
+            unverified: self.count(Status::Unverified),
+            verified: self.count(Status::Verified),
+            trusted: self.count(Status::Trusted),
+            failed: self.count(Status::Failed),
+            lowest_height: heights(Self::lowest).min(),
+            highest_height: heights(Self::highest).max(),
+            size_on_disk: self.size_on_disk(),
+        }
+    }
+
+    /// Remove the light blocks which are no longer needed by the light client, ie. all the
+    /// blocks which are not trusted and whose height is not greater than the one of the
+    /// highest trusted block. Returns the number of removed blocks.
+    ///
+    /// Trusted blocks are kept, as they serve as trust anchors for backward verification
+    /// and fork detection. They can be removed selectively with [`LightStore::remove`].
+    fn compact(&mut self) -> usize {
+        let highest_trusted = match self.highest(Status::Trusted) {
+            Some(highest_trusted) => highest_trusted.height(),
+            None => return 0,
+        };
+
+        let mut removed = 0;
+
+        for status in Status::iter() {
+            if *status == Status::Trusted {
+                continue;
+            }
+
+            let heights: Vec<_> = self
+                .all(*status)
+                .map(|lb| lb.height())
+                .filter(|height| *height <= highest_trusted)
+                .collect();
+
+            for height in heights {
+                self.remove(height, *status);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
     /// Get a block at a given height whatever its verification status as long as it hasn't failed
     /// verification (ie. its status is not `Status::Failed`).
     fn get_non_failed(&self, height: Height) -> Option<(LightBlock, Status)> {
@@ -0,0 +1,211 @@
+//! Content-addressed storage of the light blocks fetched from several peers.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::operations::{Hasher, ProdHasher};
+use crate::types::{Hash, Height, LightBlock, PeerId};
+
+/// Stores light blocks by the hash of their header, along with an index of which
+/// peers provided which header at which height.
+///
+/// When the same header is fetched from several peers, eg. during fork detection,
+/// a single copy of the light block is kept, and checking whether two peers agree
+/// on a height boils down to comparing the hashes they are indexed with.
+///
+/// The stored copy of a light block is the one which was inserted first,
+/// and as such carries the first peer which provided it in its `provider` field.
+pub struct ContentAddressedStore {
+    hasher: Box<dyn Hasher>,
+    blocks: HashMap<Hash, Arc<LightBlock>>,
+    providers: BTreeMap<Height, HashMap<PeerId, Hash>>,
+}
+
+impl fmt::Debug for ContentAddressedStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentAddressedStore")
+            .field("blocks", &self.blocks.len())
+            .field("providers", &self.providers)
+            .finish()
+    }
+}
+
+impl Default for ContentAddressedStore {
+    fn default() -> Self {
+        Self::new(ProdHasher)
+    }
+}
+
+impl ContentAddressedStore {
+    /// Create an empty store, which hashes headers with the given hasher.
+    pub fn new(hasher: impl Hasher + 'static) -> Self {
+        Self {
+            hasher: Box::new(hasher),
+            blocks: HashMap::new(),
+            providers: BTreeMap::new(),
+        }
+    }
+
+    /// Record that the provider of the given light block served it at its height,
+    /// and store the light block unless one with the same header is already stored.
+    ///
+    /// Returns the hash of the header of the light block.
+    pub fn insert(&mut self, light_block: LightBlock) -> Hash {
+        let hash = self.hasher.hash_header(&light_block.signed_header.header);
+
+        self.providers
+            .entry(light_block.height())
+            .or_default()
+            .insert(light_block.provider, hash);
+
+        self.blocks
+            .entry(hash)
+            .or_insert_with(|| Arc::new(light_block));
+
+        hash
+    }
+
+    /// Get the light block with the given header hash, if any.
+    pub fn get(&self, hash: &Hash) -> Option<Arc<LightBlock>> {
+        self.blocks.get(hash).cloned()
+    }
+
+    /// Get the hash of the header the given peer provided at the given height, if any.
+    pub fn hash_from(&self, peer: PeerId, height: Height) -> Option<Hash> {
+        self.providers.get(&height)?.get(&peer).copied()
+    }
+
+    /// Get the light block the given peer provided at the given height, if any.
+    pub fn get_from(&self, peer: PeerId, height: Height) -> Option<Arc<LightBlock>> {
+        self.hash_from(peer, height)
+            .and_then(|hash| self.get(&hash))
+    }
+
+    /// The peers which provided the header with the given hash at the given height.
+    pub fn providers_of(&self, height: Height, hash: &Hash) -> Vec<PeerId> {
+        self.providers
+            .get(&height)
+            .into_iter()
+            .flatten()
+            .filter(|(_, h)| *h == hash)
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
+    /// The distinct header hashes provided by peers at the given height,
+    /// each with the peers which provided it. More than one entry means
+    /// that the peers disagree on the header at that height.
+    pub fn conflicts(&self, height: Height) -> HashMap<Hash, Vec<PeerId>> {
+        let mut conflicts: HashMap<Hash, Vec<PeerId>> = HashMap::new();
+
+        for (peer, hash) in self.providers.get(&height).into_iter().flatten() {
+            conflicts.entry(*hash).or_default().push(*peer);
+        }
+
+        conflicts
+    }
+
+    /// Remove the blocks and provider records at heights lower than the given one.
+    pub fn prune_below(&mut self, height: Height) {
+        self.providers = self.providers.split_off(&height);
+
+        let providers = &self.providers;
+        self.blocks.retain(|hash, _| {
+            providers
+                .values()
+                .any(|by_peer| by_peer.values().any(|h| h == hash))
+        });
+    }
+
+    /// The number of distinct light blocks in the store.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+    use tendermint_testgen::{light_block::TmLightBlock, Generator, LightChain};
+
+    fn from_peer(light_block: &LightBlock, peer: &str) -> LightBlock {
+        let mut light_block = light_block.clone();
+        light_block.provider = peer.parse().unwrap();
+        light_block
+    }
+
+    fn peer(id: &str) -> PeerId {
+        id.parse().unwrap()
+    }
+
+    const PEER_A: &str = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE";
+    const PEER_B: &str = "0BEFEEDC0C0ADEADBEBADFADADEFC0FFEEFACADE";
+    const PEER_C: &str = "DEADBEEFC0FFEEFACADEBADFADAD0BEFEEDC0C0A";
+
+    #[test]
+    fn identical_headers_are_stored_once() {
+        let chain = LightChain::default_with_length(2);
+        let blocks: Vec<LightBlock> = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect();
+
+        let mut store = ContentAddressedStore::default();
+
+        let hash = store.insert(from_peer(&blocks[1], PEER_A));
+        assert_eq!(store.insert(from_peer(&blocks[1], PEER_B)), hash);
+        store.insert(from_peer(&blocks[0], PEER_A));
+
+        assert_eq!(store.len(), 2);
+
+        let height = blocks[1].height();
+        assert_eq!(store.hash_from(peer(PEER_B), height), Some(hash));
+        assert_eq!(
+            store.get_from(peer(PEER_B), height).unwrap().provider,
+            peer(PEER_A)
+        );
+
+        let mut providers = store.providers_of(height, &hash);
+        providers.sort_by_key(|p| p.to_string());
+        let mut expected = vec![peer(PEER_A), peer(PEER_B)];
+        expected.sort_by_key(|p| p.to_string());
+        assert_eq!(providers, expected);
+
+        assert_eq!(store.conflicts(height).len(), 1);
+
+        store.prune_below(height);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get_from(peer(PEER_A), blocks[0].height()), None);
+    }
+
+    #[test]
+    fn conflicting_headers_are_told_apart_by_hash() {
+        let chain = LightChain::default_with_length(1);
+        let block: TmLightBlock = chain.light_blocks[0].generate().unwrap();
+        let block: LightBlock = block.into();
+
+        let mut forked = from_peer(&block, PEER_C);
+        forked.signed_header.header.app_hash =
+            tendermint::hash::AppHash::try_from(vec![1; 32]).unwrap();
+
+        let mut store = ContentAddressedStore::default();
+        let hash = store.insert(from_peer(&block, PEER_A));
+        store.insert(from_peer(&block, PEER_B));
+        let forked_hash = store.insert(forked);
+
+        assert_ne!(hash, forked_hash);
+
+        let conflicts = store.conflicts(block.height());
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[&forked_hash], vec![peer(PEER_C)]);
+        assert_eq!(store.len(), 2);
+    }
+}
@@ -93,3 +93,37 @@ impl LightStore for MemoryStore {
         Box::new(light_blocks.into_iter())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_testgen::{Generator, LightChain};
+
+    #[test]
+    fn compact_keeps_trusted_and_pending_blocks() {
+        let chain = LightChain::default_with_length(4);
+        let blocks: Vec<LightBlock> = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect();
+
+        let mut store = MemoryStore::new();
+        assert_eq!(store.compact(), 0);
+
+        store.insert(blocks[0].clone(), Status::Trusted);
+        store.insert(blocks[1].clone(), Status::Verified);
+        store.insert(blocks[2].clone(), Status::Trusted);
+        store.insert(blocks[3].clone(), Status::Unverified);
+
+        assert_eq!(store.compact(), 1);
+
+        let stats = store.stats();
+        assert_eq!(stats.trusted, 2);
+        assert_eq!(stats.verified, 0);
+        assert_eq!(stats.unverified, 1);
+        assert_eq!(stats.lowest_height, Some(blocks[0].height()));
+        assert_eq!(stats.highest_height, Some(blocks[3].height()));
+        assert_eq!(stats.size_on_disk, None);
+    }
+}
@@ -91,6 +91,14 @@ impl LightStore for SledStore {
     fn all(&self, status: Status) -> Box<dyn Iterator<Item = LightBlock>> {
         Box::new(self.db(status).iter())
     }
+
+    fn count(&self, status: Status) -> usize {
+        self.db(status).len()
+    }
+
+    fn size_on_disk(&self) -> Option<u64> {
+        self.db.size_on_disk().ok()
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +131,46 @@ mod tests {
         })
     }
 
+    #[test]
+    fn stats_and_compact() {
+        with_blocks(10, |mut db, blocks| {
+            for block in &blocks[..3] {
+                db.insert(block.clone(), Status::Failed);
+            }
+            for block in &blocks[3..6] {
+                db.insert(block.clone(), Status::Verified);
+            }
+            db.insert(blocks[4].clone(), Status::Trusted);
+            for block in &blocks[6..] {
+                db.insert(block.clone(), Status::Unverified);
+            }
+
+            let stats = db.stats();
+            assert_eq!(stats.failed, 3);
+            assert_eq!(stats.verified, 3);
+            assert_eq!(stats.trusted, 1);
+            assert_eq!(stats.unverified, 4);
+            assert_eq!(stats.total(), 11);
+            assert_eq!(stats.lowest_height, Some(blocks[0].height()));
+            assert_eq!(stats.highest_height, Some(blocks[9].height()));
+            assert!(stats.size_on_disk.is_some());
+
+            // Everything up to the trusted block at index 4 goes, except for the trusted block.
+            assert_eq!(db.compact(), 5);
+
+            let stats = db.stats();
+            assert_eq!(stats.failed, 0);
+            assert_eq!(stats.verified, 1);
+            assert_eq!(stats.trusted, 1);
+            assert_eq!(stats.unverified, 4);
+            assert_eq!(stats.lowest_height, Some(blocks[4].height()));
+            assert_eq!(
+                db.get(blocks[5].height(), Status::Verified),
+                Some(blocks[5].clone())
+            );
+        })
+    }
+
     fn with_blocks(height: u64, f: impl FnOnce(SledStore, Vec<LightBlock>)) {
         let tmp_dir = tempdir().unwrap();
         let db = SledStore::open(tmp_dir).unwrap();
@@ -79,6 +79,16 @@ where
         Ok(())
     }
 
+    /// Return the number of values within this tree
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Return whether there are no values within this tree
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
     /// Return an iterator over all values within this tree
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = V> {
         self.tree
@@ -1,16 +1,26 @@
 //! Supervisor and Handle implementation.
 
+use std::time::Duration;
+
 use crossbeam_channel as channel;
 
 use tendermint::evidence::{ConflictingHeadersEvidence, Evidence};
 
 use crate::errors::Error;
+#[cfg(feature = "prometheus-exporter")]
+use crate::errors::ErrorDetail;
 use crate::evidence::EvidenceReporter;
 use crate::fork_detector::{Fork, ForkDetection, ForkDetector};
 use crate::light_client::LightClient;
+#[cfg(feature = "prometheus-exporter")]
+use crate::metrics::Metrics;
 use crate::peer_list::PeerList;
+use crate::peer_store::{PeerRecord, PeerStore};
 use crate::state::State;
-use crate::types::{Height, LatestStatus, LightBlock, PeerId, Status};
+use crate::types::{
+    Height, LatestStatus, LightBlock, PeerId, PeerRole, PeerStatus, Status, SupervisorStatus, Time,
+    VerifiedBlock,
+};
 
 /// Provides an interface to the supervisor for use in downstream code.
 pub trait Handle: Send + Sync {
@@ -20,11 +30,14 @@ pub trait Handle: Send + Sync {
     /// Get the latest status.
     fn latest_status(&self) -> Result<LatestStatus, Error>;
 
+    /// Get health and status information about the supervisor.
+    fn status(&self) -> Result<SupervisorStatus, Error>;
+
     /// Verify to the highest block.
-    fn verify_to_highest(&self) -> Result<LightBlock, Error>;
+    fn verify_to_highest(&self) -> Result<VerifiedBlock, Error>;
 
     /// Verify to the block at the given height.
-    fn verify_to_target(&self, _height: Height) -> Result<LightBlock, Error>;
+    fn verify_to_target(&self, _height: Height) -> Result<VerifiedBlock, Error>;
 
     /// Terminate the underlying [`Supervisor`].
     fn terminate(&self) -> Result<(), Error>;
@@ -38,16 +51,19 @@ enum HandleInput {
     Terminate(channel::Sender<()>),
 
     /// Verify to the highest height, call the provided callback with result
-    VerifyToHighest(channel::Sender<Result<LightBlock, Error>>),
+    VerifyToHighest(channel::Sender<Result<VerifiedBlock, Error>>),
 
     /// Verify to the given height, call the provided callback with result
-    VerifyToTarget(Height, channel::Sender<Result<LightBlock, Error>>),
+    VerifyToTarget(Height, channel::Sender<Result<VerifiedBlock, Error>>),
 
     /// Get the latest trusted block.
     LatestTrusted(channel::Sender<Option<LightBlock>>),
 
     /// Get the current status of the LightClient
     GetStatus(channel::Sender<LatestStatus>),
+
+    /// Get health and status information about the supervisor
+    GetSupervisorStatus(channel::Sender<SupervisorStatus>),
 }
 
 /// A light client `Instance` packages a `LightClient` together with its `State`.
@@ -80,13 +96,17 @@ impl Instance {
     }
 }
 
+/// Callback to which the supervisor passes the errors which do not fail a request
+type ErrorCallback = Box<dyn FnMut(&Error) + Send>;
+
 /// The supervisor manages multiple light client instances, of which one
 /// is deemed to be the primary instance through which blocks are retrieved
 /// and verified. The other instances are considered as witnesses
 /// which are consulted to perform fork detection.
 ///
 /// If primary verification fails, the primary client is removed and a witness
-/// is promoted to primary. If a witness is deemed faulty, then the witness is
+/// is promoted to primary, after being backfilled with the trusted blocks of the
+/// former primary it also serves. If a witness is deemed faulty, then the witness is
 /// removed.
 ///
 /// The supervisor is intended to be ran in its own thread, and queried
@@ -105,8 +125,8 @@ impl Instance {
 ///     // Asynchronously query the supervisor via a handle
 ///     let maybe_block = handle.verify_to_highest();
 ///     match maybe_block {
-///         Ok(light_block) => {
-///             println!("[info] synced to block {}", light_block.height());
+///         Ok(verified) => {
+///             println!("[info] synced to block {}", verified.light_block().height());
 ///         }
 ///         Err(e) => {
 ///             println!("[error] sync failed: {}", e);
@@ -127,6 +147,17 @@ pub struct Supervisor {
     sender: channel::Sender<HandleInput>,
     /// Channel through which to receive events from the `Handle`s
     receiver: channel::Receiver<HandleInput>,
+    /// How often to re-run fork detection on the latest trusted block, if at all
+    detection_interval: Option<Duration>,
+    /// Metrics to record the operation of the supervisor into, if any
+    #[cfg(feature = "prometheus-exporter")]
+    metrics: Option<Metrics>,
+    /// Store in which to persist the records of the peers, if any
+    peer_store: Option<Box<dyn PeerStore>>,
+    /// Whether to trust blocks verified against the primary alone when there are no witnesses
+    skip_fork_detection: bool,
+    /// Callback to which the errors which do not fail a request are passed, if any
+    on_error: Option<ErrorCallback>,
 }
 
 impl std::fmt::Debug for Supervisor {
@@ -155,9 +186,144 @@ impl Supervisor {
             receiver,
             fork_detector: Box::new(fork_detector),
             evidence_reporter: Box::new(evidence_reporter),
+            detection_interval: None,
+            #[cfg(feature = "prometheus-exporter")]
+            metrics: None,
+            peer_store: None,
+            skip_fork_detection: false,
+            on_error: None,
+        }
+    }
+
+    /// Periodically re-run fork detection on the latest trusted block against
+    /// the witnesses, every `interval`, when no requests are received from the
+    /// handles in the meantime. This allows catching forks even when the supervisor
+    /// is otherwise idle. Set to `None` (the default) to disable this behavior.
+    pub fn with_detection_interval(mut self, interval: Option<Duration>) -> Self {
+        self.detection_interval = interval;
+        self
+    }
+
+    /// Pass the errors which do not fail the requests of the handles, eg. those
+    /// of the periodic fork detection or of the peer store, to the given callback,
+    /// eg. to log them.
+    ///
+    /// These errors never stop the supervisor.
+    pub fn with_error_callback(mut self, on_error: impl FnMut(&Error) + Send + 'static) -> Self {
+        self.on_error = Some(Box::new(on_error));
+        self
+    }
+
+    /// Pass the given error to the error callback, if any.
+    fn report_error(&mut self, error: &Error) {
+        if let Some(on_error) = self.on_error.as_mut() {
+            on_error(error)
+        }
+    }
+
+    /// Run without any witness, trusting the blocks verified against the primary
+    /// without performing fork detection.
+    ///
+    /// **This is unsafe**: a single faulty or malicious primary can then make the
+    /// light client trust a forked chain. It is only meant for development and test
+    /// setups with a single full node. The blocks trusted this way are returned as
+    /// [`VerifiedBlock::Unchecked`], and reported as unchecked in
+    /// [`SupervisorStatus::fork_detection_skipped`].
+    ///
+    /// Fork detection is still performed as usual whenever there are witnesses.
+    pub fn danger_skip_fork_detection(mut self) -> Self {
+        self.skip_fork_detection = true;
+        self
+    }
+
+    /// Whether blocks are currently trusted without fork detection,
+    /// see [`Supervisor::danger_skip_fork_detection`].
+    pub fn is_fork_detection_skipped(&self) -> bool {
+        self.skip_fork_detection && self.peers.witnesses_ids().is_empty()
+    }
+
+    /// Record the latest trusted block, fetch failures and fork detections
+    /// into the given Prometheus metrics.
+    #[cfg(feature = "prometheus-exporter")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prometheus-exporter")))]
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Call the given function with the metrics, if any.
+    #[cfg(feature = "prometheus-exporter")]
+    fn record(&self, f: impl FnOnce(&Metrics)) {
+        if let Some(metrics) = &self.metrics {
+            f(metrics)
         }
     }
 
+    /// Persist the role, time of the last successful verification, and ban status
+    /// of the peers into the given store, as they change.
+    ///
+    /// The peers which were banned by a previous run are marked as faulty right away.
+    /// This fails if the primary has been banned and there is no witness left to replace it.
+    pub fn with_peer_store(mut self, peer_store: impl PeerStore + 'static) -> Result<Self, Error> {
+        for peer_id in peer_store.banned() {
+            if self.peers.get(&peer_id).is_some() {
+                let former_primary = self.peers.primary_id();
+                self.peers.mark_faulty(peer_id)?;
+
+                if self.peers.primary_id() != former_primary {
+                    self.backfill_primary(former_primary);
+                }
+            }
+        }
+
+        self.peer_store = Some(Box::new(peer_store));
+        self.store_peers(None)?;
+
+        Ok(self)
+    }
+
+    /// Record the current role of each peer in the peer store, if any,
+    /// along with the time at which the given peer successfully provided
+    /// a light block, if any.
+    ///
+    /// Failing to persist the peers does not fail the request which changed them,
+    /// eg. a successful verification, so the error is passed to the error callback instead.
+    fn update_peer_store(&mut self, success: Option<(PeerId, Time)>) {
+        if let Err(e) = self.store_peers(success) {
+            self.report_error(&e);
+        }
+    }
+
+    /// Persist the peers into the peer store, if any. See `update_peer_store`.
+    fn store_peers(&mut self, success: Option<(PeerId, Time)>) -> Result<(), Error> {
+        let statuses = self.peer_statuses();
+
+        let peer_store = match self.peer_store.as_mut() {
+            Some(peer_store) => peer_store,
+            None => return Ok(()),
+        };
+
+        for status in statuses {
+            let last_success = match success {
+                Some((peer_id, time)) if peer_id == status.peer_id => Some(time),
+                _ => peer_store
+                    .get(status.peer_id)
+                    .and_then(|record| record.last_success),
+            };
+
+            peer_store
+                .update(PeerRecord {
+                    peer_id: status.peer_id,
+                    role: status.role,
+                    last_success,
+                    banned: !status.is_healthy(),
+                })
+                .map_err(Error::peer_store)?;
+        }
+
+        Ok(())
+    }
+
     /// Create a new handle to this supervisor.
     pub fn handle(&self) -> SupervisorHandle {
         SupervisorHandle::new(self.sender.clone())
@@ -169,7 +335,7 @@ impl Supervisor {
     }
 
     /// Verify to the highest block.
-    pub fn verify_to_highest(&mut self) -> Result<LightBlock, Error> {
+    pub fn verify_to_highest(&mut self) -> Result<VerifiedBlock, Error> {
         self.verify(None)
     }
 
@@ -191,14 +357,71 @@ impl Supervisor {
         }
     }
 
+    /// Return health and status information about the supervisor: the latest trusted
+    /// block of the primary and its age, and the role of each known full node.
+    pub fn status(&self) -> SupervisorStatus {
+        let primary = self.peers.primary();
+        let latest_trusted = primary.latest_trusted();
+        let now = primary.light_client.now();
+
+        SupervisorStatus {
+            latest_trusted_height: latest_trusted.as_ref().map(|lb| lb.height()),
+            latest_trusted_hash: latest_trusted
+                .as_ref()
+                .map(|lb| lb.signed_header.header.hash()),
+            // A block from the future (within the clock drift) is considered brand new.
+            latest_trusted_age: latest_trusted.as_ref().map(|lb| {
+                now.duration_since(lb.signed_header.header.time)
+                    .unwrap_or_else(|_| Duration::from_secs(0))
+            }),
+            trusting_period: primary.light_client.options.trusting_period,
+            peers: self.peer_statuses(),
+            fork_detection_skipped: self.is_fork_detection_skipped(),
+        }
+    }
+
+    /// The role of each full node known to the supervisor.
+    fn peer_statuses(&self) -> Vec<PeerStatus> {
+        let peer_status = |role| {
+            move |peer_id: &PeerId| PeerStatus {
+                peer_id: *peer_id,
+                role,
+            }
+        };
+
+        std::iter::once(PeerStatus {
+            peer_id: self.peers.primary_id(),
+            role: PeerRole::Primary,
+        })
+        .chain(
+            self.peers
+                .witnesses_ids()
+                .iter()
+                .map(peer_status(PeerRole::Witness)),
+        )
+        .chain(
+            self.peers
+                .full_nodes_ids()
+                .iter()
+                .map(peer_status(PeerRole::FullNode)),
+        )
+        .chain(
+            self.peers
+                .faulty_nodes_ids()
+                .iter()
+                .map(peer_status(PeerRole::Faulty)),
+        )
+        .collect()
+    }
+
     /// Verify to the block at the given height.
-    pub fn verify_to_target(&mut self, height: Height) -> Result<LightBlock, Error> {
+    pub fn verify_to_target(&mut self, height: Height) -> Result<VerifiedBlock, Error> {
         self.verify(Some(height))
     }
 
     /// Verify either to the latest block (if `height == None`) or to a given block (if `height ==
     /// Some(height)`).
-    fn verify(&mut self, height: Option<Height>) -> Result<LightBlock, Error> {
+    fn verify(&mut self, height: Option<Height>) -> Result<VerifiedBlock, Error> {
         let primary = self.peers.primary_mut();
 
         // Perform light client core verification for the given height (or highest).
@@ -232,27 +455,106 @@ impl Supervisor {
                         self.verify(height)
                     }
                     ForkDetection::NotDetected => {
+                        let unchecked = self.is_fork_detection_skipped();
+
                         // We need to re-ask for the primary here as the compiler
                         // is not smart enough to realize that we do not mutate
                         // the `primary` field of `PeerList` between the initial
                         // borrow of the primary and here (can't blame it, it's
                         // not that obvious).
-                        self.peers.primary_mut().trust_block(&verified_block);
+                        let primary = self.peers.primary_mut();
+                        primary.trust_block(&verified_block);
+
+                        let success = (primary.light_client.peer, primary.light_client.now());
+                        self.update_peer_store(Some(success));
+
+                        #[cfg(feature = "prometheus-exporter")]
+                        self.record(|m| m.observe_trusted_block(&verified_block));
 
                         // No fork detected, exiting
-                        Ok(verified_block)
+                        if unchecked {
+                            Ok(VerifiedBlock::Unchecked(verified_block))
+                        } else {
+                            Ok(VerifiedBlock::Checked(verified_block))
+                        }
                     }
                 }
             }
             // Verification failed
             Err(err) => {
+                #[cfg(feature = "prometheus-exporter")]
+                if let ErrorDetail::Io(_) = err.detail() {
+                    self.record(|m| m.observe_fetch_failure());
+                }
+
                 // Swap primary, and continue with new primary, if there is any witness left.
-                self.peers.replace_faulty_primary(Some(err))?;
+                self.replace_faulty_primary(err)?;
+
                 self.verify(height)
             }
         }
     }
 
+    /// Mark the primary as faulty and promote a witness in its place,
+    /// backfilling the trusted state of the new primary from the former one.
+    fn replace_faulty_primary(&mut self, primary_error: Error) -> Result<(), Error> {
+        let former_primary = self.peers.primary_id();
+
+        let replaced = self.peers.replace_faulty_primary(Some(primary_error));
+        self.update_peer_store(None);
+        replaced?;
+
+        self.backfill_primary(former_primary);
+
+        Ok(())
+    }
+
+    /// Backfill the new primary with the trusted blocks of the former primary
+    /// which are higher than its own latest trusted block, so that verification
+    /// carries on from where the former primary left off instead of starting
+    /// over from an older trusted block, or failing for lack of one.
+    ///
+    /// Each of these blocks is only trusted if the new primary serves the very
+    /// same header. Only the blocks the new primary does not hold yet are fetched
+    /// from it, all at once. Blocks which cannot be fetched or differ are left to
+    /// be verified as usual.
+    fn backfill_primary(&mut self, former_primary: PeerId) {
+        let latest_trusted = self.peers.primary().latest_trusted().map(|lb| lb.height());
+
+        let missing: Vec<LightBlock> = match self.peers.get(&former_primary) {
+            Some(former) => former
+                .state
+                .light_store
+                .all(Status::Trusted)
+                .filter(|lb| Some(lb.height()) > latest_trusted)
+                .collect(),
+            None => return,
+        };
+
+        let primary = self.peers.primary_mut();
+
+        let unknown: Vec<Height> = missing
+            .iter()
+            .map(|lb| lb.height())
+            .filter(|height| primary.state.light_store.get_non_failed(*height).is_none())
+            .collect();
+
+        primary
+            .light_client
+            .fetch_blocks(&unknown, &mut primary.state);
+
+        for mut trusted in missing {
+            let served = primary.state.light_store.get_non_failed(trusted.height());
+
+            if let Some((light_block, _)) = served {
+                if light_block.signed_header == trusted.signed_header {
+                    trusted.provider = primary.light_client.peer;
+                    primary.trust_block(&trusted);
+                }
+            }
+        }
+    }
+
     fn process_forks(&mut self, forks: Vec<Fork>) -> Result<Vec<PeerId>, Error> {
         let mut forked = Vec::with_capacity(forks.len());
 
@@ -268,6 +570,9 @@ impl Supervisor {
                 }
                 // A witness has timed out, remove it from the peer list.
                 Fork::Timeout(provider, _error) => {
+                    #[cfg(feature = "prometheus-exporter")]
+                    self.record(|m| m.observe_fetch_failure());
+
                     self.peers.replace_faulty_witness(provider);
                     // TODO: Log/record the error
                 }
@@ -279,6 +584,13 @@ impl Supervisor {
             }
         }
 
+        #[cfg(feature = "prometheus-exporter")]
+        if !forked.is_empty() {
+            self.record(|m| m.observe_fork_detections(forked.len()));
+        }
+
+        self.update_peer_store(None);
+
         Ok(forked)
     }
 
@@ -301,12 +613,76 @@ impl Supervisor {
         Ok(())
     }
 
+    /// Re-run fork detection on the latest trusted block of the primary against the
+    /// witnesses, using the highest trusted block below it as the trust root.
+    ///
+    /// Faulty witnesses are removed from the peer list, and evidence is reported
+    /// for every actual fork, in which case a `ForkDetected` error is returned.
+    /// Does nothing if the primary does not have at least two trusted blocks.
+    pub fn detect_forks_on_latest_trusted(&mut self) -> Result<(), Error> {
+        let light_store = &self.peers.primary().state.light_store;
+
+        let latest_trusted = match light_store.highest(Status::Trusted) {
+            Some(latest_trusted) => latest_trusted,
+            None => return Ok(()),
+        };
+
+        let trust_root = light_store
+            .all(Status::Trusted)
+            .filter(|lb| lb.height() < latest_trusted.height())
+            .max_by_key(|lb| lb.height());
+
+        let trust_root = match trust_root {
+            Some(trust_root) => trust_root,
+            None => return Ok(()),
+        };
+
+        match self.detect_forks(&latest_trusted, &trust_root)? {
+            ForkDetection::Detected(forks) => {
+                let forked = self.process_forks(forks)?;
+                if forked.is_empty() {
+                    Ok(())
+                } else {
+                    Err(Error::fork_detected(forked))
+                }
+            }
+            ForkDetection::NotDetected => Ok(()),
+        }
+    }
+
+    /// Wait for the next event from the handles, re-running fork detection on the latest
+    /// trusted block every time the detection interval elapses without an event, if enabled.
+    fn next_event(&mut self) -> Result<HandleInput, Error> {
+        let interval = match self.detection_interval {
+            Some(interval) => interval,
+            None => return self.receiver.recv().map_err(Error::recv),
+        };
+
+        loop {
+            match self.receiver.recv_timeout(interval) {
+                Ok(event) => return Ok(event),
+                Err(channel::RecvTimeoutError::Timeout) => {
+                    if let Err(e) = self.detect_forks_on_latest_trusted() {
+                        self.report_error(&e);
+                    }
+                }
+                Err(channel::RecvTimeoutError::Disconnected) => {
+                    return Err(Error::channel_disconnected())
+                }
+            }
+        }
+    }
+
     /// Perform fork detection with the given verified block and trusted block.
     fn detect_forks(
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
     ) -> Result<ForkDetection, Error> {
+        if self.is_fork_detection_skipped() {
+            return Ok(ForkDetection::NotDetected);
+        }
+
         if self.peers.witnesses_ids().is_empty() {
             return Err(Error::no_witnesses());
         }
@@ -325,9 +701,13 @@ impl Supervisor {
     /// Run the supervisor event loop in the same thread.
     ///
     /// This method should typically be called within a new thread with `std::thread::spawn`.
+    ///
+    /// If a detection interval is set, the failures of the periodic fork detection,
+    /// including the `ForkDetected` errors raised once the evidence of a fork has been
+    /// reported, are passed to the error callback and the supervisor keeps running.
     pub fn run(mut self) -> Result<(), Error> {
         loop {
-            let event = self.receiver.recv().map_err(Error::recv)?;
+            let event = self.next_event()?;
 
             match event {
                 HandleInput::LatestTrusted(sender) => {
@@ -350,6 +730,10 @@ impl Supervisor {
                     let outcome = self.latest_status();
                     sender.send(outcome).map_err(Error::send)?;
                 }
+                HandleInput::GetSupervisorStatus(sender) => {
+                    let outcome = self.status();
+                    sender.send(outcome).map_err(Error::send)?;
+                }
             }
         }
     }
@@ -371,9 +755,9 @@ impl SupervisorHandle {
 
     fn verify(
         &self,
-        make_event: impl FnOnce(channel::Sender<Result<LightBlock, Error>>) -> HandleInput,
-    ) -> Result<LightBlock, Error> {
-        let (sender, receiver) = channel::bounded::<Result<LightBlock, Error>>(1);
+        make_event: impl FnOnce(channel::Sender<Result<VerifiedBlock, Error>>) -> HandleInput,
+    ) -> Result<VerifiedBlock, Error> {
+        let (sender, receiver) = channel::bounded::<Result<VerifiedBlock, Error>>(1);
 
         let event = make_event(sender);
         self.sender.send(event).map_err(Error::send)?;
@@ -401,11 +785,19 @@ impl Handle for SupervisorHandle {
         receiver.recv().map_err(Error::recv)
     }
 
-    fn verify_to_highest(&self) -> Result<LightBlock, Error> {
+    fn status(&self) -> Result<SupervisorStatus, Error> {
+        let (sender, receiver) = channel::bounded::<SupervisorStatus>(1);
+        self.sender
+            .send(HandleInput::GetSupervisorStatus(sender))
+            .map_err(Error::send)?;
+        receiver.recv().map_err(Error::recv)
+    }
+
+    fn verify_to_highest(&self) -> Result<VerifiedBlock, Error> {
         self.verify(HandleInput::VerifyToHighest)
     }
 
-    fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error> {
+    fn verify_to_target(&self, height: Height) -> Result<VerifiedBlock, Error> {
         self.verify(|sender| HandleInput::VerifyToTarget(height, sender))
     }
 
@@ -423,7 +815,7 @@ impl Handle for SupervisorHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::errors::{Error, ErrorDetail};
+    use crate::errors::ErrorDetail;
     use crate::light_client::Options;
     use crate::operations::ProdHasher;
     use crate::{
@@ -437,6 +829,7 @@ mod tests {
         tests::{MockClock, MockEvidenceReporter, MockIo, TrustOptions},
         types::Time,
     };
+    use std::sync::{Arc, Mutex};
     use std::{collections::HashMap, convert::TryFrom, time::Duration};
     use tendermint::block::Height;
     use tendermint::evidence::Duration as DurationStr;
@@ -453,7 +846,7 @@ mod tests {
     fn make_instance(
         peer_id: PeerId,
         trust_options: TrustOptions,
-        io: MockIo,
+        io: impl Io + 'static,
         now: Time,
     ) -> Instance {
         let trusted_height = trust_options.height;
@@ -473,15 +866,18 @@ mod tests {
             trust_threshold: trust_options.trust_level,
             trusting_period: trust_options.period.into(),
             clock_drift: Duration::from_secs(0),
+            max_bisection_steps: None,
+            trust_threshold_overrides: Vec::new(),
+            forward_clock_drift: None,
         };
 
         let verifier = ProdVerifier::default();
         let clock = MockClock { now };
         let scheduler = scheduler::basic_bisecting_schedule;
-        let hasher = ProdHasher::default();
+        let hasher = ProdHasher;
 
         let light_client =
-            LightClient::new(peer_id, options, clock, scheduler, verifier, hasher, io);
+            LightClient::new(peer_id, options, clock, scheduler, verifier, hasher, io).unwrap();
 
         Instance::new(light_client, state)
     }
@@ -502,7 +898,9 @@ mod tests {
         let target_height = Height::try_from(height_to_verify).expect("Error while making height");
 
         (
-            handle.verify_to_target(target_height),
+            handle
+                .verify_to_target(target_height)
+                .map(|verified| verified.checked().unwrap()),
             handle.latest_status().unwrap(),
         )
     }
@@ -608,6 +1006,40 @@ mod tests {
         assert_eq!(expected_state, new_state);
     }
 
+    #[test]
+    fn test_fork_detector_keeps_the_provided_blocks() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+        let witness = change_provider(primary.clone(), None);
+        let peer_list = make_peer_list(Some(primary.clone()), Some(vec![witness]), get_time(11));
+        let witnesses = || {
+            peer_list
+                .witnesses_ids()
+                .iter()
+                .filter_map(|id| peer_list.get(id))
+                .collect()
+        };
+
+        let fork_detector = ProdForkDetector::default();
+        for &(trusted, verified) in &[(0, 3), (4, 8)] {
+            let detection = fork_detector
+                .detect_forks(&primary[verified], &primary[trusted], witnesses())
+                .unwrap();
+            assert!(matches!(detection, ForkDetection::NotDetected));
+        }
+
+        // Both peers provided the same headers, which were kept across
+        // detections, while those below the last trusted block were pruned
+        assert!(fork_detector.conflicts(primary[3].height()).is_empty());
+        let conflicts = fork_detector.conflicts(primary[8].height());
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts.values().next().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_bisection_no_witnesses() {
         let chain = LightChain::default_with_length(10);
@@ -627,6 +1059,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bisection_no_witnesses_with_fork_detection_skipped() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let peer_list = make_peer_list(Some(primary.clone()), None, get_time(11));
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .danger_skip_fork_detection();
+
+        assert!(supervisor.is_fork_detection_skipped());
+
+        let target_height = Height::try_from(10_u64).unwrap();
+        let verified = supervisor.verify_to_target(target_height).unwrap();
+
+        assert_eq!(verified, VerifiedBlock::Unchecked(primary[9].clone()));
+        assert_eq!(supervisor.latest_trusted(), Some(primary[9].clone()));
+        assert!(supervisor.status().fork_detection_skipped);
+    }
+
     #[test]
     fn test_bisection_io_error() {
         let chain = LightChain::default_with_length(10);
@@ -678,9 +1138,9 @@ mod tests {
         // treated as a NoWitnessLeft error, which was misclassified.
         match result {
             Err(Error(ErrorDetail::Io(e), _)) => match e.source {
-                crate::components::io::IoErrorDetail::Rpc(e) => match e.source {
+                io::IoErrorDetail::Rpc(e) => match e.source {
                     rpc::error::ErrorDetail::Response(e) => {
-                        assert_eq!(e.source.code(), rpc::Code::InvalidRequest)
+                        assert_eq!(e.source.code(), Code::InvalidRequest)
                     }
                     _ => {
                         panic!("expected Response error, instead got {:?}", e)
@@ -730,6 +1190,253 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_peer_store_bans_and_successes() {
+        use crate::peer_store::json::JsonPeerStore;
+
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness1 = change_provider(primary.clone(), None);
+        let witness2 = change_provider(
+            primary.clone(),
+            Some("2a515002827b5cc0c6fdb73bcb162f516fad75c8"),
+        );
+
+        let banned = primary[0].provider;
+        let new_primary = witness1[0].provider;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peers.json");
+
+        let mut peer_store = JsonPeerStore::open(&path).unwrap();
+        peer_store
+            .update(PeerRecord {
+                peer_id: banned,
+                role: PeerRole::Primary,
+                last_success: None,
+                banned: true,
+            })
+            .unwrap();
+
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness1, witness2]), get_time(11));
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .with_peer_store(peer_store)
+        .unwrap();
+
+        // The banned primary was replaced on startup.
+        assert_eq!(supervisor.peers.primary_id(), new_primary);
+
+        supervisor
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+
+        let peer_store = JsonPeerStore::open(&path).unwrap();
+
+        let record = peer_store.get(banned).unwrap();
+        assert_eq!(record.role, PeerRole::Faulty);
+        assert!(record.banned);
+
+        let record = peer_store.get(new_primary).unwrap();
+        assert_eq!(record.role, PeerRole::Primary);
+        assert_eq!(record.last_success, Some(get_time(11)));
+        assert!(!record.banned);
+
+        assert_eq!(peer_store.all().len(), 3);
+    }
+
+    #[test]
+    fn test_peer_store_failure_does_not_fail_verification() {
+        use crate::peer_store::json::JsonPeerStore;
+
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let peer_store = JsonPeerStore::open(dir.path().join("peers.json")).unwrap();
+
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11));
+
+        let (errors_tx, errors_rx) = channel::unbounded();
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .with_error_callback(move |e| errors_tx.send(format!("{:?}", e.detail())).unwrap())
+        .with_peer_store(peer_store)
+        .unwrap();
+
+        // Writing the peers fails from now on.
+        dir.close().unwrap();
+
+        let verified = supervisor
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+        assert_eq!(verified.light_block().height().value(), 10);
+
+        let errors = errors_rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("PeerStore"),
+            "expected PeerStore error, instead got {}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn test_supervisor_status() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let peer_list = make_peer_list(Some(primary.clone()), Some(vec![witness]), get_time(11));
+
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+
+        let handle = supervisor.handle();
+        std::thread::spawn(|| supervisor.run());
+
+        handle
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+
+        let status = handle.status().unwrap();
+
+        assert_eq!(status.latest_trusted_height, Some(primary[9].height()));
+        assert_eq!(
+            status.latest_trusted_hash,
+            Some(primary[9].signed_header.header.hash())
+        );
+        assert_eq!(status.latest_trusted_age, Some(Duration::from_secs(1)));
+        assert!(status.is_within_trusting_period());
+
+        assert_eq!(
+            status.primary().map(|p| p.peer_id),
+            Some(primary[0].provider)
+        );
+        assert_eq!(status.witnesses().count(), 1);
+        assert!(status.peers.iter().all(|p| p.is_healthy()));
+    }
+
+    #[test]
+    #[cfg(feature = "prometheus-exporter")]
+    fn test_supervisor_records_metrics() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let peer_list = make_peer_list(Some(primary), Some(vec![witness]), get_time(11));
+
+        let metrics = Metrics::new().unwrap();
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .with_metrics(metrics.clone());
+
+        supervisor
+            .verify_to_target(Height::try_from(10_u64).unwrap())
+            .unwrap();
+
+        assert!(metrics
+            .encode()
+            .unwrap()
+            .contains("light_client_trusted_height 10"));
+    }
+
+    #[test]
+    fn test_periodic_fork_detection() {
+        let mut chain = LightChain::default_with_length(5);
+        let primary = chain
+            .light_blocks
+            .clone()
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let mut header = chain.light_blocks[4].header.clone().unwrap();
+        let mut time = header.time.unwrap();
+        time += 3;
+        header.time = Some(time);
+        chain.light_blocks[4].header = Some(header.clone());
+        chain.light_blocks[4].commit = Some(Commit::new(header, 1));
+
+        let witness = change_provider(
+            chain
+                .light_blocks
+                .into_iter()
+                .map(|lb| lb.generate().unwrap().into())
+                .collect::<Vec<LightBlock>>(),
+            None,
+        );
+
+        let mut peer_list =
+            make_peer_list(Some(primary.clone()), Some(vec![witness]), get_time(11));
+
+        // Pretend the primary block at height 5 was previously trusted without any witness
+        // disagreeing, eg. because the witness only started serving the forked block later.
+        peer_list.primary_mut().trust_block(&primary[4]);
+
+        let (errors_tx, errors_rx) = channel::unbounded();
+
+        let supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        )
+        .with_detection_interval(Some(Duration::from_millis(10)))
+        .with_error_callback(move |e| errors_tx.send(format!("{:?}", e.detail())).unwrap());
+
+        let handle = supervisor.handle();
+        let supervisor = std::thread::spawn(|| supervisor.run());
+
+        let error = errors_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(
+            error.starts_with("ForkDetected"),
+            "expected ForkDetected error, instead got {}",
+            error
+        );
+
+        // The supervisor keeps serving requests after reporting the fork.
+        assert!(handle.status().is_ok());
+
+        handle.terminate().unwrap();
+        supervisor.join().unwrap().unwrap();
+    }
+
     #[test]
     fn test_bisection_no_initial_trusted_state() {
         let chain = LightChain::default_with_length(10);
@@ -778,6 +1485,138 @@ mod tests {
             .any(|&peer| peer == primary[0].provider));
     }
 
+    #[test]
+    fn test_promoted_witness_is_backfilled_from_former_primary() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness1 = change_provider(primary.clone(), None);
+        let witness2 = change_provider(
+            witness1.clone(),
+            Some("EDC0C0ADEADBEBA0BEFEDFADADEFC0FFEEFACADE"),
+        );
+
+        // The primary trusts the block at height 5, but cannot serve any block above it.
+        let mut peer_list = make_peer_list(
+            Some(primary[..5].to_vec()),
+            Some(vec![witness1.clone(), witness2]),
+            get_time(11),
+        );
+        peer_list
+            .get_mut(&primary[0].provider)
+            .expect("cannot find instance")
+            .state
+            .light_store
+            .insert(primary[4].clone(), Status::Trusted);
+
+        let mut supervisor = Supervisor::new(
+            peer_list,
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+
+        let verified = supervisor.verify_to_target(10_u32.into()).unwrap();
+        assert_eq!(verified, VerifiedBlock::Checked(witness1[9].clone()));
+
+        let new_primary = supervisor.peers.primary();
+        assert_eq!(new_primary.light_client.peer, witness1[0].provider);
+
+        let backfilled = new_primary
+            .state
+            .light_store
+            .get(5_u32.into(), Status::Trusted)
+            .expect("block at height 5 was not backfilled");
+        assert_eq!(backfilled, witness1[4]);
+    }
+
+    /// Records the heights requested from the wrapped `Io`.
+    struct RecordingIo {
+        io: MockIo,
+        requested: Arc<Mutex<Vec<Height>>>,
+    }
+
+    impl Io for RecordingIo {
+        fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, io::IoError> {
+            if let AtHeight::At(height) = height {
+                self.requested.lock().unwrap().push(height);
+            }
+
+            self.io.fetch_light_block(height)
+        }
+    }
+
+    #[test]
+    fn test_backfill_only_fetches_missing_blocks() {
+        let chain = LightChain::default_with_length(10);
+        let primary = chain
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap().into())
+            .collect::<Vec<LightBlock>>();
+
+        let witness = change_provider(primary.clone(), None);
+
+        let trust_options = TrustOptions {
+            period: DurationStr(Duration::new(604800, 0)),
+            height: Height::try_from(1_u64).unwrap(),
+            trust_level: TrustThresholdFraction::TWO_THIRDS,
+        };
+
+        // The primary trusts the blocks at heights 3 and 5.
+        let chain_id = primary[0].signed_header.header.chain_id.to_string();
+        let mut primary_instance = make_instance(
+            primary[0].provider,
+            trust_options.clone(),
+            MockIo::new(chain_id.clone(), primary.clone()),
+            get_time(11),
+        );
+        primary_instance.trust_block(&primary[2]);
+        primary_instance.trust_block(&primary[4]);
+
+        // The witness already holds the block at height 3.
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let io = RecordingIo {
+            io: MockIo::new(chain_id, witness.clone()),
+            requested: requested.clone(),
+        };
+        let mut witness_instance =
+            make_instance(witness[0].provider, trust_options, io, get_time(11));
+        witness_instance
+            .state
+            .light_store
+            .insert(witness[2].clone(), Status::Unverified);
+        requested.lock().unwrap().clear();
+
+        let mut peer_list = PeerList::builder();
+        peer_list.primary(primary[0].provider, primary_instance);
+        peer_list.witness(witness[0].provider, witness_instance);
+
+        let mut supervisor = Supervisor::new(
+            peer_list.build(),
+            ProdForkDetector::default(),
+            MockEvidenceReporter::new(),
+        );
+
+        supervisor
+            .replace_faulty_primary(Error::no_witnesses())
+            .unwrap();
+
+        assert_eq!(*requested.lock().unwrap(), vec![Height::from(5_u32)]);
+
+        let new_primary = supervisor.peers.primary();
+        for height in [3_u32, 5] {
+            assert!(new_primary
+                .state
+                .light_store
+                .get(height.into(), Status::Trusted)
+                .is_some());
+        }
+    }
+
     #[test]
     fn test_bisection_trusted_state_outside_trusting_period() {
         let chain = LightChain::default_with_length(10);
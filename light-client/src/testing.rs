@@ -0,0 +1,163 @@
+//! Test doubles for crates embedding the light client, enabled with the `testing` feature.
+//!
+//! These allow writing deterministic tests of code built on top of the light client,
+//! without any full node: [`MockIo`] serves a fixed set of light blocks, [`MockClock`]
+//! sets the current time, and [`MockVerifier`] returns scripted verdicts.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use contracts::contract_trait;
+use tendermint::abci::transaction::Hash;
+use tendermint::evidence::Evidence;
+use tendermint_rpc as rpc;
+
+use crate::components::clock::Clock;
+use crate::components::io::{AtHeight, Io, IoError};
+use crate::components::verifier::{Verdict, Verifier};
+use crate::evidence::EvidenceReporter;
+use crate::light_client::Options;
+use crate::types::{Height, LightBlock, PeerId, Time};
+
+/// A clock which always returns the given time.
+#[derive(Clone)]
+pub struct MockClock {
+    pub now: Time,
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Time {
+        self.now
+    }
+}
+
+/// An `Io` component serving the light blocks it holds in a map indexed by height,
+/// and failing with an RPC error for any other height.
+#[derive(Clone)]
+pub struct MockIo {
+    #[allow(dead_code)]
+    chain_id: String,
+    light_blocks: HashMap<Height, LightBlock>,
+    latest_height: Height,
+}
+
+impl MockIo {
+    /// Serve the given light blocks, the highest one being the latest block.
+    pub fn new(chain_id: String, light_blocks: Vec<LightBlock>) -> Self {
+        let latest_height = light_blocks.iter().map(|lb| lb.height()).max().unwrap();
+
+        let light_blocks = light_blocks
+            .into_iter()
+            .map(|lb| (lb.height(), lb))
+            .collect();
+
+        Self {
+            chain_id,
+            light_blocks,
+            latest_height,
+        }
+    }
+}
+
+impl Io for MockIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        let height = match height {
+            AtHeight::Highest => self.latest_height,
+            AtHeight::At(height) => height,
+        };
+
+        self.light_blocks.get(&height).cloned().ok_or_else(|| {
+            IoError::rpc(rpc::Error::response(
+                rpc::response_error::ResponseError::new((-32600).into(), None),
+            ))
+        })
+    }
+}
+
+/// An evidence reporter which accepts all evidence without reporting it anywhere.
+#[derive(Clone, Debug, Default)]
+pub struct MockEvidenceReporter;
+
+#[contract_trait]
+impl EvidenceReporter for MockEvidenceReporter {
+    fn report(&self, _e: Evidence, _peer: PeerId) -> Result<Hash, IoError> {
+        Ok(Hash::new([0; 32]))
+    }
+}
+
+impl MockEvidenceReporter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A verifier which returns scripted verdicts instead of actually verifying light blocks,
+/// and records which blocks it has been asked to verify.
+///
+/// Clones share the record of the verified blocks, so that a clone can be handed
+/// to the light client while the original is inspected by the test.
+#[derive(Clone, Debug)]
+pub struct MockVerifier {
+    verdicts: Arc<Mutex<HashMap<Height, VecDeque<Verdict>>>>,
+    default_verdict: Verdict,
+    verified: Arc<Mutex<Vec<(Height, Height)>>>,
+}
+
+impl Default for MockVerifier {
+    fn default() -> Self {
+        Self::new(Verdict::Success)
+    }
+}
+
+impl MockVerifier {
+    /// Return the given verdict for all the blocks without a scripted verdict.
+    pub fn new(default_verdict: Verdict) -> Self {
+        Self {
+            verdicts: Arc::new(Mutex::new(HashMap::new())),
+            default_verdict,
+            verified: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Return the given verdict the next time the block at the given height is verified.
+    ///
+    /// Verdicts scripted for the same height are returned in order, one per verification,
+    /// after which the default verdict applies again.
+    pub fn with_verdict(self, height: impl Into<Height>, verdict: Verdict) -> Self {
+        self.verdicts
+            .lock()
+            .unwrap()
+            .entry(height.into())
+            .or_default()
+            .push_back(verdict);
+
+        self
+    }
+
+    /// The heights of the trusted and untrusted blocks of each verification, in order.
+    pub fn verified(&self) -> Vec<(Height, Height)> {
+        self.verified.lock().unwrap().clone()
+    }
+}
+
+impl Verifier for MockVerifier {
+    fn verify(
+        &self,
+        untrusted: &LightBlock,
+        trusted: &LightBlock,
+        _options: &Options,
+        _now: Time,
+    ) -> Verdict {
+        self.verified
+            .lock()
+            .unwrap()
+            .push((trusted.height(), untrusted.height()));
+
+        self.verdicts
+            .lock()
+            .unwrap()
+            .get_mut(&untrusted.height())
+            .and_then(|verdicts| verdicts.pop_front())
+            .unwrap_or_else(|| self.default_verdict.clone())
+    }
+}
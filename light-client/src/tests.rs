@@ -1,23 +1,25 @@
 //! Utilities and datatypes for use in tests.
 
-use crate::types::{Height, LightBlock, PeerId, SignedHeader, Time, TrustThreshold, ValidatorSet};
+use crate::types::{Height, LightBlock, SignedHeader, Time, TrustThreshold, ValidatorSet};
 
 use serde::{Deserialize, Serialize};
-use tendermint::abci::transaction::Hash;
-use tendermint_rpc as rpc;
 
 use crate::components::clock::Clock;
-use crate::components::io::{AtHeight, Io, IoError};
+use crate::components::io::Io;
+use crate::components::scheduler::Scheduler;
 use crate::components::verifier::{ProdVerifier, Verdict, Verifier};
 use crate::errors::Error;
-use crate::evidence::EvidenceReporter;
 use crate::light_client::{LightClient, Options};
 use crate::state::State;
-use contracts::contract_trait;
-use std::collections::HashMap;
 use std::time::Duration;
 use tendermint::block::Height as HeightStr;
-use tendermint::evidence::{Duration as DurationStr, Evidence};
+use tendermint::evidence::Duration as DurationStr;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod model_based;
+
+#[cfg(any(test, feature = "testing"))]
+pub use crate::testing::{MockClock, MockEvidenceReporter, MockIo, MockVerifier};
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct TestCases<LB> {
@@ -71,72 +73,6 @@ pub struct TrustOptions {
     pub trust_level: TrustThreshold,
 }
 
-#[derive(Clone)]
-pub struct MockClock {
-    pub now: Time,
-}
-
-impl Clock for MockClock {
-    fn now(&self) -> Time {
-        self.now
-    }
-}
-
-#[derive(Clone)]
-pub struct MockIo {
-    chain_id: String,
-    light_blocks: HashMap<Height, LightBlock>,
-    latest_height: Height,
-}
-
-impl MockIo {
-    pub fn new(chain_id: String, light_blocks: Vec<LightBlock>) -> Self {
-        let latest_height = light_blocks.iter().map(|lb| lb.height()).max().unwrap();
-
-        let light_blocks = light_blocks
-            .into_iter()
-            .map(|lb| (lb.height(), lb))
-            .collect();
-
-        Self {
-            chain_id,
-            light_blocks,
-            latest_height,
-        }
-    }
-}
-
-impl Io for MockIo {
-    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
-        let height = match height {
-            AtHeight::Highest => self.latest_height,
-            AtHeight::At(height) => height,
-        };
-
-        self.light_blocks.get(&height).cloned().ok_or_else(|| {
-            IoError::rpc(rpc::Error::response(
-                rpc::response_error::ResponseError::new((-32600).into(), None),
-            ))
-        })
-    }
-}
-
-#[derive(Clone, Debug, Default)]
-pub struct MockEvidenceReporter;
-
-#[contract_trait]
-impl EvidenceReporter for MockEvidenceReporter {
-    fn report(&self, _e: Evidence, _peer: PeerId) -> Result<Hash, IoError> {
-        Ok(Hash::new([0; 32]))
-    }
-}
-
-impl MockEvidenceReporter {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
 pub fn verify_single(
     trusted_state: LightBlock,
     input: LightBlock,
@@ -151,6 +87,9 @@ pub fn verify_single(
         trust_threshold,
         trusting_period,
         clock_drift,
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let result = verifier.verify(&input, &trusted_state, &options, now);
@@ -161,11 +100,17 @@ pub fn verify_single(
     }
 }
 
-pub fn verify_bisection(
+pub fn verify_bisection<C, S, V, I>(
     untrusted_height: Height,
-    light_client: &mut LightClient,
+    light_client: &mut LightClient<C, S, V, I>,
     state: &mut State,
-) -> Result<Vec<LightBlock>, Error> {
+) -> Result<Vec<LightBlock>, Error>
+where
+    C: Clock,
+    S: Scheduler,
+    V: Verifier,
+    I: Io,
+{
     light_client
         .verify_to_target(untrusted_height, state)
         .map(|_| state.get_trace(untrusted_height))
@@ -0,0 +1,280 @@
+//! Executor for model-based test traces.
+//!
+//! A trace, eg. generated by Apalache from the TLA+ specification of the light client
+//! and transformed to JSON by Jsonatr, describes an initial trusted state followed by
+//! a sequence of light blocks, each with the verdict the verifier is expected to reach.
+//! The [`TraceExecutor`] drives the real `Verifier` or `LightClient` through such a
+//! trace and reports the first step whose outcome does not match the model.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::scheduler;
+use crate::components::verifier::{ProdVerifier, Verdict, Verifier};
+use crate::errors::ErrorExt;
+use crate::light_client::{LightClient, Options};
+use crate::operations::ProdHasher;
+use crate::state::State;
+use crate::store::{memory::MemoryStore, LightStore};
+use crate::types::{LightBlock, PeerId, Status, Time, TrustThreshold};
+
+use super::{Initial, MockClock, MockIo};
+
+/// An abstraction of the LightClient verification verdict
+#[derive(Copy, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum LiteVerdict {
+    /// verified successfully
+    #[serde(rename = "SUCCESS")]
+    Success,
+    /// outside of trusting period
+    #[serde(rename = "FAILED_TRUSTING_PERIOD")]
+    FailedTrustingPeriod,
+    /// block verification based on the header and commit structure failed
+    #[serde(rename = "INVALID")]
+    Invalid,
+    /// passed block verification, but the validator set is too different to verify it
+    #[serde(rename = "NOT_ENOUGH_TRUST")]
+    NotEnoughTrust,
+    /// the block could not be parsed
+    #[serde(rename = "PARSE_ERROR")]
+    ParseError,
+}
+
+impl LiteVerdict {
+    /// Whether the given verdict of the verifier matches this abstract verdict.
+    ///
+    /// A block outside of the trusting period is an invalid block as well,
+    /// so `Invalid` matches all verification failures.
+    pub fn matches(&self, verdict: &Verdict) -> bool {
+        match (self, verdict) {
+            (Self::Success, Verdict::Success) => true,
+            (Self::NotEnoughTrust, Verdict::NotEnoughTrust(_)) => true,
+            (Self::Invalid, Verdict::Invalid(_)) => true,
+            (Self::FailedTrustingPeriod, Verdict::Invalid(e)) => e.has_expired(),
+            _ => false,
+        }
+    }
+}
+
+/// A light block together with the time when it's being checked, and the expected verdict
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TraceStep {
+    pub block: LightBlock,
+    pub now: Time,
+    pub verdict: LiteVerdict,
+}
+
+/// A single-step trace contains an initial trusted block, plus a sequence of input blocks,
+/// each with the expected verdict. The trusted state is to be updated only if the verdict
+/// is `Success`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SingleStepTrace {
+    pub description: String,
+    pub initial: Initial,
+    pub input: Vec<TraceStep>,
+}
+
+/// The outcome of a trace step did not match the verdict expected by the model.
+#[derive(Clone, Debug)]
+pub struct TraceMismatch {
+    /// The index of the offending step in the trace
+    pub step: usize,
+    /// The verdict expected by the model
+    pub expected: LiteVerdict,
+    /// A description of the actual outcome
+    pub actual: String,
+}
+
+impl fmt::Display for TraceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {}: expected {:?}, got {}",
+            self.step, self.expected, self.actual
+        )
+    }
+}
+
+/// Drives the light client verification logic through model-based traces.
+pub struct TraceExecutor<V> {
+    verifier: V,
+    trust_threshold: TrustThreshold,
+    clock_drift: Duration,
+}
+
+impl Default for TraceExecutor<ProdVerifier> {
+    fn default() -> Self {
+        Self::new(
+            ProdVerifier::default(),
+            TrustThreshold::default(),
+            Duration::from_secs(0),
+        )
+    }
+}
+
+impl<V: Verifier> TraceExecutor<V> {
+    /// Constructs an executor which checks traces against the given verifier,
+    /// with the given trust threshold and clock drift.
+    pub fn new(verifier: V, trust_threshold: TrustThreshold, clock_drift: Duration) -> Self {
+        Self {
+            verifier,
+            trust_threshold,
+            clock_drift,
+        }
+    }
+
+    /// Check each step of the trace with `Verifier::verify`, against the latest block
+    /// which was successfully verified (or the initial one).
+    ///
+    /// Steps expected to fail parsing are about deserialization, and are skipped.
+    pub fn run_verifier(&self, trace: &SingleStepTrace) -> Result<(), TraceMismatch> {
+        let mut latest_trusted = match initial_block(trace) {
+            Some(initial) => initial,
+            None => return Ok(()),
+        };
+
+        let options = self.options(trace);
+
+        for (i, step) in trace.input.iter().enumerate() {
+            if step.verdict == LiteVerdict::ParseError {
+                continue;
+            }
+
+            let verdict = self
+                .verifier
+                .verify(&step.block, &latest_trusted, &options, step.now);
+
+            if !step.verdict.matches(&verdict) {
+                return Err(TraceMismatch {
+                    step: i,
+                    expected: step.verdict,
+                    actual: format!("{:?}", verdict),
+                });
+            }
+
+            if matches!(verdict, Verdict::Success) {
+                latest_trusted = step.block.clone();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check each step of the trace by running `LightClient::verify_to_target` against
+    /// a primary which only knows about the latest trusted block and the step's block,
+    /// so that no bisection can happen. Verification must succeed if and only if the
+    /// model expects the `Success` verdict.
+    ///
+    /// Steps which are not above the latest trusted block are checked with the verifier
+    /// only, and steps expected to fail parsing are skipped.
+    pub fn run_light_client(&self, trace: &SingleStepTrace) -> Result<(), TraceMismatch> {
+        let mut latest_trusted = match initial_block(trace) {
+            Some(initial) => initial,
+            None => return Ok(()),
+        };
+
+        let options = self.options(trace);
+
+        for (i, step) in trace.input.iter().enumerate() {
+            if step.verdict == LiteVerdict::ParseError {
+                continue;
+            }
+
+            let mismatch = |actual: String| TraceMismatch {
+                step: i,
+                expected: step.verdict,
+                actual,
+            };
+
+            if step.block.height() <= latest_trusted.height() {
+                let verdict =
+                    self.verifier
+                        .verify(&step.block, &latest_trusted, &options, step.now);
+
+                if !step.verdict.matches(&verdict) {
+                    return Err(mismatch(format!("{:?}", verdict)));
+                }
+
+                if matches!(verdict, Verdict::Success) {
+                    latest_trusted = step.block.clone();
+                }
+
+                continue;
+            }
+
+            let chain_id = step.block.signed_header.header.chain_id.to_string();
+            let io = MockIo::new(chain_id, vec![latest_trusted.clone(), step.block.clone()]);
+
+            let mut light_store = MemoryStore::new();
+            light_store.insert(latest_trusted.clone(), Status::Trusted);
+            let mut state = State::new(light_store);
+
+            let light_client = LightClient::from_components(
+                step.block.provider,
+                options.clone(),
+                MockClock { now: step.now },
+                scheduler::basic_bisecting_schedule,
+                VerifierRef(&self.verifier),
+                ProdHasher,
+                io,
+            )
+            .unwrap();
+
+            let result = light_client.verify_to_target(step.block.height(), &mut state);
+
+            match (step.verdict, result) {
+                (LiteVerdict::Success, Ok(verified)) => latest_trusted = verified,
+                (LiteVerdict::Success, Err(e)) => return Err(mismatch(format!("error: {}", e))),
+                (_, Ok(verified)) => {
+                    return Err(mismatch(format!(
+                        "block at height {} verified",
+                        verified.height()
+                    )))
+                }
+                (_, Err(_)) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn options(&self, trace: &SingleStepTrace) -> Options {
+        Options {
+            trust_threshold: self.trust_threshold,
+            trusting_period: trace.initial.trusting_period.into(),
+            clock_drift: self.clock_drift,
+            max_bisection_steps: None,
+            trust_threshold_overrides: Vec::new(),
+            forward_clock_drift: None,
+        }
+    }
+}
+
+/// The initial trusted block of the trace, attributed to the provider of the first step.
+fn initial_block(trace: &SingleStepTrace) -> Option<LightBlock> {
+    let provider: PeerId = trace.input.first()?.block.provider;
+
+    Some(LightBlock::new(
+        trace.initial.signed_header.clone(),
+        trace.initial.next_validator_set.clone(),
+        trace.initial.next_validator_set.clone(),
+        provider,
+    ))
+}
+
+/// Lends the verifier of the executor to a light client.
+struct VerifierRef<'a, V>(&'a V);
+
+impl<V: Verifier> Verifier for VerifierRef<'_, V> {
+    fn verify(
+        &self,
+        untrusted: &LightBlock,
+        trusted: &LightBlock,
+        options: &Options,
+        now: Time,
+    ) -> Verdict {
+        self.0.verify(untrusted, trusted, options, now)
+    }
+}
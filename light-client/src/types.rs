@@ -1,5 +1,7 @@
 //! Defines or just re-exports the main datatypes used by the light client.
 
+use std::time::Duration;
+
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
@@ -154,6 +156,128 @@ impl LatestStatus {
     }
 }
 
+/// Role of a full node in the peer list of the supervisor.
+///
+/// Faulty nodes have been removed from the set of primary and witnesses,
+/// eg. because they failed to provide valid light blocks or timed out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerRole {
+    /// The node is the primary, through which light blocks are verified.
+    Primary,
+    /// The node is a witness, consulted during fork detection.
+    Witness,
+    /// The node is a full node kept in reserve to replace faulty witnesses.
+    FullNode,
+    /// The node has been deemed faulty.
+    Faulty,
+}
+
+/// Status of a full node known to the supervisor.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerStatus {
+    /// The peer ID of the node.
+    pub peer_id: PeerId,
+    /// The role of the node in the peer list.
+    pub role: PeerRole,
+}
+
+impl PeerStatus {
+    /// Whether the node has not been deemed faulty.
+    pub fn is_healthy(&self) -> bool {
+        self.role != PeerRole::Faulty
+    }
+}
+
+/// Health and status information about the supervisor, eg. for wiring
+/// liveness and readiness probes.
+#[derive(Clone, Debug, Display, PartialEq, Serialize, Deserialize)]
+#[display(fmt = "{:?}", self)]
+pub struct SupervisorStatus {
+    /// The height of the latest trusted block, if any.
+    pub latest_trusted_height: Option<Height>,
+    /// The hash of the latest trusted block, if any.
+    #[serde(with = "tendermint::serializers::option_hash")]
+    pub latest_trusted_hash: Option<Hash>,
+    /// The time elapsed since the latest trusted block was created, if any.
+    pub latest_trusted_age: Option<Duration>,
+    /// The trusting period of the primary.
+    pub trusting_period: Duration,
+    /// The full nodes known to the supervisor, along with their role.
+    pub peers: Vec<PeerStatus>,
+    /// Whether the supervisor runs without witnesses and trusts the blocks of the
+    /// primary without fork detection, in which case the latest trusted block
+    /// is unchecked and must not be relied upon outside of development setups.
+    #[serde(default)]
+    pub fork_detection_skipped: bool,
+}
+
+impl SupervisorStatus {
+    /// Whether the latest trusted block is still within the trusting period.
+    /// Returns `false` if there is no trusted block.
+    pub fn is_within_trusting_period(&self) -> bool {
+        self.remaining_trusting_period().is_some()
+    }
+
+    /// How long the latest trusted block will stay within the trusting period,
+    /// if there is a trusted block and it has not already expired.
+    pub fn remaining_trusting_period(&self) -> Option<Duration> {
+        self.latest_trusted_age
+            .and_then(|age| self.trusting_period.checked_sub(age))
+            .filter(|remaining| *remaining > Duration::from_secs(0))
+    }
+
+    /// The status of the primary node, if any.
+    pub fn primary(&self) -> Option<&PeerStatus> {
+        self.peers.iter().find(|p| p.role == PeerRole::Primary)
+    }
+
+    /// The status of the witnesses.
+    pub fn witnesses(&self) -> impl Iterator<Item = &PeerStatus> {
+        self.peers.iter().filter(|p| p.role == PeerRole::Witness)
+    }
+}
+
+/// A light block verified by the supervisor, tagged with whether it was
+/// also checked for forks against the witnesses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum VerifiedBlock {
+    /// The block was verified, and no fork was detected by the witnesses.
+    Checked(LightBlock),
+    /// The block was verified against the primary alone, without fork detection,
+    /// see `Supervisor::danger_skip_fork_detection`. It must not be relied upon
+    /// outside of development setups.
+    Unchecked(LightBlock),
+}
+
+impl VerifiedBlock {
+    /// The verified light block, whether it was checked for forks or not.
+    pub fn light_block(&self) -> &LightBlock {
+        match self {
+            Self::Checked(light_block) | Self::Unchecked(light_block) => light_block,
+        }
+    }
+
+    /// The verified light block, whether it was checked for forks or not.
+    pub fn into_light_block(self) -> LightBlock {
+        match self {
+            Self::Checked(light_block) | Self::Unchecked(light_block) => light_block,
+        }
+    }
+
+    /// The light block, if it was checked for forks.
+    pub fn checked(self) -> Option<LightBlock> {
+        match self {
+            Self::Checked(light_block) => Some(light_block),
+            Self::Unchecked(_) => None,
+        }
+    }
+
+    /// Whether fork detection was skipped for this block.
+    pub fn is_unchecked(&self) -> bool {
+        matches!(self, Self::Unchecked(_))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -189,4 +313,34 @@ mod tests {
                 .collect()
         }
     }
+
+    mod supervisor_status {
+        use crate::types::SupervisorStatus;
+        use std::time::Duration;
+
+        fn status(age: Option<u64>) -> SupervisorStatus {
+            SupervisorStatus {
+                latest_trusted_height: None,
+                latest_trusted_hash: None,
+                latest_trusted_age: age.map(Duration::from_secs),
+                trusting_period: Duration::from_secs(100),
+                peers: vec![],
+                fork_detection_skipped: false,
+            }
+        }
+
+        #[test]
+        fn remaining_trusting_period() {
+            assert_eq!(
+                status(Some(40)).remaining_trusting_period(),
+                Some(Duration::from_secs(60))
+            );
+            assert!(status(Some(40)).is_within_trusting_period());
+
+            assert_eq!(status(Some(100)).remaining_trusting_period(), None);
+            assert_eq!(status(Some(200)).remaining_trusting_period(), None);
+            assert_eq!(status(None).remaining_trusting_period(), None);
+            assert!(!status(None).is_within_trusting_period());
+        }
+    }
 }
@@ -1,8 +1,8 @@
 //! Various general-purpose utilities
 
-#[cfg(feature = "rpc-client")]
+#[cfg(any(feature = "rpc-client", feature = "grpc-client"))]
 mod block_on;
-#[cfg(feature = "rpc-client")]
-pub use block_on::block_on;
+#[cfg(any(feature = "rpc-client", feature = "grpc-client"))]
+pub use block_on::{block_on, block_on_runtime};
 
 pub mod std_ext;
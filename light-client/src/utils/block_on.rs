@@ -26,3 +26,37 @@ where
     .join()
     .unwrap()
 }
+
+/// Run a future to completion on the given runtime, with the given timeout.
+///
+/// Unlike [`block_on`], this reuses an existing runtime instead of spinning up
+/// a new one for every call. As a runtime cannot be blocked on from within
+/// another one, the future is driven from a new thread when the caller is
+/// itself running on a runtime.
+pub fn block_on_runtime<F>(
+    runtime: &tokio::runtime::Handle,
+    timeout: Option<Duration>,
+    f: F,
+) -> Result<F::Output, IoError>
+where
+    F: Future + Send + 'static,
+    F::Output: Send,
+{
+    let run = move |runtime: &tokio::runtime::Handle| {
+        if let Some(timeout) = timeout {
+            let task = async { tokio::time::timeout(timeout, f).await };
+            runtime
+                .block_on(task)
+                .map_err(|e| IoError::timeout(timeout, e))
+        } else {
+            Ok(runtime.block_on(f))
+        }
+    };
+
+    if tokio::runtime::Handle::try_current().is_ok() {
+        let runtime = runtime.clone();
+        std::thread::spawn(move || run(&runtime)).join().unwrap()
+    } else {
+        run(runtime)
+    }
+}
@@ -0,0 +1,256 @@
+//! Watchdog which keeps the latest trusted block of a supervisor from expiring.
+//!
+//! A light client which is not asked to verify new blocks for longer than its trusting
+//! period cannot verify anything anymore, as its latest trusted block has expired.
+//! This typically happens to relayers on quiet chains. The [`ExpiryWatchdog`]
+//! periodically checks how close the latest trusted block is to expiry, and either
+//! refreshes it with `verify_to_highest` or reports a warning before trust lapses.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::errors::Error;
+use crate::supervisor::Handle;
+use crate::types::Height;
+
+/// Default interval between two checks of the trusting period.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Outcome of a check performed by the [`ExpiryWatchdog`].
+#[derive(Debug)]
+pub enum WatchdogEvent {
+    /// The latest trusted block is not close to expiry.
+    Healthy {
+        /// Time until the latest trusted block expires
+        remaining: Duration,
+    },
+    /// The latest trusted block is close to expiry, and auto-refresh is disabled.
+    NearExpiry {
+        /// Time until the latest trusted block expires
+        remaining: Duration,
+    },
+    /// The latest trusted block was close to expiry, and a newer block has been trusted.
+    Refreshed {
+        /// Height of the newly trusted block
+        height: Height,
+    },
+    /// The latest trusted block is close to expiry, and refreshing it failed.
+    RefreshFailed {
+        /// Time until the latest trusted block expires
+        remaining: Duration,
+        /// The verification error
+        error: Error,
+    },
+    /// The latest trusted block has already expired, or there is no trusted block.
+    Expired,
+}
+
+impl WatchdogEvent {
+    /// Whether this event calls for the attention of the operator.
+    pub fn is_warning(&self) -> bool {
+        !matches!(self, Self::Healthy { .. } | Self::Refreshed { .. })
+    }
+}
+
+/// Monitors how close the latest trusted block of a supervisor is to the end
+/// of its trusting period, and proactively refreshes it before it expires.
+pub struct ExpiryWatchdog<H> {
+    handle: H,
+    refresh_margin: Duration,
+    check_interval: Duration,
+    auto_refresh: bool,
+}
+
+impl<H: Handle> ExpiryWatchdog<H> {
+    /// Create a watchdog for the supervisor behind the given handle, which acts
+    /// when the latest trusted block expires in less than `refresh_margin`.
+    ///
+    /// Auto-refresh is enabled by default, and checks run every [`DEFAULT_CHECK_INTERVAL`].
+    pub fn new(handle: H, refresh_margin: Duration) -> Self {
+        Self {
+            handle,
+            refresh_margin,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            auto_refresh: true,
+        }
+    }
+
+    /// Set the interval between two checks. It should be well below the refresh
+    /// margin, so that the margin is not missed between two checks.
+    pub fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    /// Whether to call `verify_to_highest` when the latest trusted block is close
+    /// to expiry, or to only report it with a [`WatchdogEvent::NearExpiry`] event.
+    pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.auto_refresh = auto_refresh;
+        self
+    }
+
+    /// Check the remaining trusting period of the latest trusted block once,
+    /// refreshing it if it is close to expiry and auto-refresh is enabled.
+    ///
+    /// Fails only if the supervisor cannot be reached through the handle.
+    pub fn check(&self) -> Result<WatchdogEvent, Error> {
+        let remaining = match self.handle.status()?.remaining_trusting_period() {
+            Some(remaining) => remaining,
+            None => return Ok(WatchdogEvent::Expired),
+        };
+
+        if remaining > self.refresh_margin {
+            return Ok(WatchdogEvent::Healthy { remaining });
+        }
+
+        if !self.auto_refresh {
+            return Ok(WatchdogEvent::NearExpiry { remaining });
+        }
+
+        match self.handle.verify_to_highest() {
+            Ok(verified) => Ok(WatchdogEvent::Refreshed {
+                height: verified.light_block().height(),
+            }),
+            Err(error) => Ok(WatchdogEvent::RefreshFailed { remaining, error }),
+        }
+    }
+
+    /// Run the checks every check interval in the same thread, passing the outcome
+    /// of each check to the given callback, eg. to log warnings.
+    ///
+    /// Returns once the supervisor cannot be reached anymore, eg. because it
+    /// has been terminated, with the error reported by the handle.
+    ///
+    /// This method should typically be called within a new thread with `std::thread::spawn`.
+    pub fn run(self, mut on_event: impl FnMut(WatchdogEvent)) -> Error {
+        loop {
+            match self.check() {
+                Ok(event) => on_event(event),
+                Err(e) => return e,
+            }
+
+            thread::sleep(self.check_interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use crate::types::{LatestStatus, LightBlock, SupervisorStatus, VerifiedBlock};
+    use tendermint_testgen::{Generator, LightChain};
+
+    /// A handle whose latest trusted block has the given age, and which
+    /// counts the number of refreshes. The requests the watchdog never makes
+    /// fail as if the supervisor was unreachable.
+    struct MockHandle {
+        age: Mutex<Option<u64>>,
+        refreshes: Mutex<usize>,
+        refreshed_block: LightBlock,
+    }
+
+    impl MockHandle {
+        fn new(age: Option<u64>) -> Self {
+            let chain = LightChain::default_with_length(2);
+            Self {
+                age: Mutex::new(age),
+                refreshes: Mutex::new(0),
+                refreshed_block: chain.light_blocks[1].generate().unwrap().into(),
+            }
+        }
+
+        fn refreshes(&self) -> usize {
+            *self.refreshes.lock().unwrap()
+        }
+    }
+
+    impl Handle for &MockHandle {
+        fn latest_trusted(&self) -> Result<Option<LightBlock>, Error> {
+            Err(Error::channel_disconnected())
+        }
+
+        fn latest_status(&self) -> Result<LatestStatus, Error> {
+            Err(Error::channel_disconnected())
+        }
+
+        fn status(&self) -> Result<SupervisorStatus, Error> {
+            match *self.age.lock().unwrap() {
+                Some(age) => Ok(SupervisorStatus {
+                    latest_trusted_height: None,
+                    latest_trusted_hash: None,
+                    latest_trusted_age: Some(Duration::from_secs(age)),
+                    trusting_period: Duration::from_secs(100),
+                    peers: vec![],
+                    fork_detection_skipped: false,
+                }),
+                None => Err(Error::channel_disconnected()),
+            }
+        }
+
+        fn verify_to_highest(&self) -> Result<VerifiedBlock, Error> {
+            *self.refreshes.lock().unwrap() += 1;
+            *self.age.lock().unwrap() = Some(0);
+            Ok(VerifiedBlock::Checked(self.refreshed_block.clone()))
+        }
+
+        fn verify_to_target(&self, _height: Height) -> Result<VerifiedBlock, Error> {
+            Err(Error::channel_disconnected())
+        }
+
+        fn terminate(&self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn refreshes_only_within_margin() {
+        let handle = MockHandle::new(Some(50));
+        let watchdog = ExpiryWatchdog::new(&handle, Duration::from_secs(30));
+
+        match watchdog.check().unwrap() {
+            WatchdogEvent::Healthy { remaining } => assert_eq!(remaining.as_secs(), 50),
+            other => panic!("expected Healthy event, instead got {:?}", other),
+        }
+        assert_eq!(handle.refreshes(), 0);
+
+        *handle.age.lock().unwrap() = Some(80);
+
+        match watchdog.check().unwrap() {
+            WatchdogEvent::Refreshed { height } => assert_eq!(height.value(), 2),
+            other => panic!("expected Refreshed event, instead got {:?}", other),
+        }
+        assert_eq!(handle.refreshes(), 1);
+
+        assert!(!watchdog.check().unwrap().is_warning());
+        assert_eq!(handle.refreshes(), 1);
+    }
+
+    #[test]
+    fn warns_without_auto_refresh() {
+        let handle = MockHandle::new(Some(80));
+        let watchdog =
+            ExpiryWatchdog::new(&handle, Duration::from_secs(30)).with_auto_refresh(false);
+
+        let event = watchdog.check().unwrap();
+        assert!(event.is_warning());
+        assert!(matches!(event, WatchdogEvent::NearExpiry { .. }));
+        assert_eq!(handle.refreshes(), 0);
+
+        *handle.age.lock().unwrap() = Some(120);
+        assert!(matches!(watchdog.check().unwrap(), WatchdogEvent::Expired));
+    }
+
+    #[test]
+    fn run_stops_when_supervisor_is_unreachable() {
+        let handle = MockHandle::new(None);
+        let watchdog = ExpiryWatchdog::new(&handle, Duration::from_secs(30))
+            .with_check_interval(Duration::from_millis(1));
+
+        let mut events = 0;
+        watchdog.run(|_| events += 1);
+
+        assert_eq!(events, 0);
+    }
+}
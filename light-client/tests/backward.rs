@@ -56,6 +56,9 @@ fn make(chain: LightChain, trusted_height: Height) -> (LightClient, State) {
         trust_threshold: Default::default(),
         trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
         clock_drift: Duration::from_secs(10),
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let light_blocks = chain
@@ -90,7 +93,8 @@ fn make(chain: LightChain, trusted_height: Height) -> (LightClient, State) {
         verifier,
         hasher,
         io,
-    );
+    )
+    .unwrap();
 
     (light_client, state)
 }
@@ -1,34 +1,41 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use tendermint_light_client::{
     components::{
-        io::{AtHeight, Io},
-        scheduler,
-        verifier::ProdVerifier,
+        io::{AtHeight, Io, IoError},
+        scheduler::{self, AdaptiveScheduler, Scheduler},
+        verifier::{verify_update_header, ProdVerifier, Verdict, Verifier},
     },
-    errors::Error,
+    errors::{Error, ErrorDetail, ErrorExt},
     light_client::{LightClient, Options},
-    operations::ProdHasher,
+    operations::{ProdHasher, VotingPowerTally},
+    predicates::errors::{Predicate, VerificationErrorDetail},
     state::State,
     store::{memory::MemoryStore, LightStore},
     tests::*,
-    types::{LightBlock, Status},
+    types::{Hash, Height, LightBlock, Status, Time},
 };
 
-use tendermint_testgen::light_block::default_peer_id;
-use tendermint_testgen::Tester;
+use tendermint_rpc::{self as rpc, response_error::ResponseError};
+use tendermint_testgen::light_block::{default_peer_id, TmLightBlock as TGLightBlock};
+use tendermint_testgen::{
+    Commit, Generator, Header, LightBlock as TestgenLightBlock, LightChain, Tester, Validator,
+};
 
 // Link to JSON test files repo:
 // https://github.com/informalsystems/conformance-tests
 const TEST_FILES_PATH: &str = "./tests/support/";
 
 struct BisectionTestResult {
+    trusted_height: Height,
     untrusted_light_block: LightBlock,
     new_states: Result<Vec<LightBlock>, Error>,
 }
 
-fn run_test(tc: LightClientTest<LightBlock>) -> BisectionTestResult {
+fn run_test(tc: LightClientTest<LightBlock>, static_dispatch: bool) -> BisectionTestResult {
     let primary = default_peer_id();
     let untrusted_height = tc.height_to_verify;
     let trust_threshold = tc.trust_options.trust_level;
@@ -45,6 +52,9 @@ fn run_test(tc: LightClientTest<LightBlock>) -> BisectionTestResult {
         trust_threshold,
         trusting_period: trusting_period.into(),
         clock_drift,
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let provider = tc.primary;
@@ -64,43 +74,75 @@ fn run_test(tc: LightClientTest<LightBlock>) -> BisectionTestResult {
     };
 
     let verifier = ProdVerifier::default();
-    let hasher = ProdHasher::default();
+    let hasher = ProdHasher;
 
-    let mut light_client = LightClient::new(
-        primary,
-        options,
-        clock,
-        scheduler::basic_bisecting_schedule,
-        verifier,
-        hasher,
-        io.clone(),
-    );
+    let result = if static_dispatch {
+        let mut light_client = LightClient::from_components(
+            primary,
+            options,
+            clock,
+            scheduler::basic_bisecting_schedule,
+            verifier,
+            hasher,
+            io.clone(),
+        )
+        .unwrap();
 
-    let result = verify_bisection(untrusted_height, &mut light_client, &mut state);
+        verify_bisection(untrusted_height, &mut light_client, &mut state)
+    } else {
+        let mut light_client = LightClient::new(
+            primary,
+            options,
+            clock,
+            scheduler::basic_bisecting_schedule,
+            verifier,
+            hasher,
+            io.clone(),
+        )
+        .unwrap();
+
+        verify_bisection(untrusted_height, &mut light_client, &mut state)
+    };
 
     let untrusted_light_block = io
         .fetch_light_block(AtHeight::At(untrusted_height))
         .expect("header at untrusted height not found");
 
     BisectionTestResult {
+        trusted_height,
         untrusted_light_block,
         new_states: result,
     }
 }
 
 fn forward_test(tc: LightClientTest<LightBlock>) {
+    check_forward_test(tc, false)
+}
+
+fn forward_test_static_dispatch(tc: LightClientTest<LightBlock>) {
+    check_forward_test(tc, true)
+}
+
+fn check_forward_test(tc: LightClientTest<LightBlock>, static_dispatch: bool) {
     let expect_error = match &tc.expected_output {
         Some(eo) => eo.eq("error"),
         None => false,
     };
 
-    let test_result = run_test(tc);
+    let test_result = run_test(tc, static_dispatch);
     let expected_state = test_result.untrusted_light_block;
 
     match test_result.new_states {
         Ok(new_states) => {
             assert_eq!(new_states[0].height(), expected_state.height());
             assert_eq!(new_states[0], expected_state);
+            assert_eq!(
+                new_states.last().map(|lb| lb.height()),
+                Some(test_result.trusted_height)
+            );
+            assert!(new_states
+                .windows(2)
+                .all(|pair| pair[0].height() > pair[1].height()));
             assert!(!expect_error);
         }
         Err(e) => {
@@ -112,10 +154,525 @@ fn forward_test(tc: LightClientTest<LightBlock>) {
     }
 }
 
+fn testgen_to_lb(tm_lb: TGLightBlock) -> LightBlock {
+    LightBlock {
+        signed_header: tm_lb.signed_header,
+        validators: tm_lb.validators,
+        next_validators: tm_lb.next_validators,
+        provider: tm_lb.provider,
+    }
+}
+
+/// Generate the light blocks of a chain of the given length, along with a
+/// time one second after its latest block.
+fn generate_chain(length: u64) -> (Vec<LightBlock>, Time) {
+    let light_blocks = LightChain::default_with_length(length)
+        .light_blocks
+        .into_iter()
+        .map(|lb| testgen_to_lb(lb.generate().unwrap()))
+        .collect();
+
+    let now = tendermint_testgen::helpers::get_time(length + 1);
+
+    (light_blocks, now)
+}
+
+fn test_options() -> Options {
+    Options {
+        trust_threshold: Default::default(),
+        trusting_period: Duration::from_secs(60 * 60 * 24 * 10),
+        clock_drift: Duration::from_secs(10),
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
+    }
+}
+
+/// A light client at the given time, with the given block as its trusted state.
+fn light_client_with(
+    trusted_state: LightBlock,
+    now: Time,
+    options: Options,
+    verifier: impl Verifier + 'static,
+    io: impl Io + 'static,
+) -> (LightClient, State) {
+    let mut light_store = MemoryStore::new();
+    light_store.insert(trusted_state, Status::Trusted);
+
+    let light_client = LightClient::new(
+        default_peer_id(),
+        options,
+        MockClock { now },
+        scheduler::basic_bisecting_schedule,
+        verifier,
+        ProdHasher,
+        io,
+    )
+    .unwrap();
+
+    (light_client, State::new(light_store))
+}
+
+fn make_client(
+    length: u64,
+    trusted_height: Height,
+    max_bisection_steps: Option<u64>,
+) -> (LightClient, State) {
+    let (light_blocks, now) = generate_chain(length);
+    let io = MockIo::new("testchain-1".to_string(), light_blocks);
+
+    let trusted_state = io
+        .fetch_light_block(AtHeight::At(trusted_height))
+        .expect("could not find trusted light block");
+
+    let options = Options {
+        max_bisection_steps,
+        ..test_options()
+    };
+
+    light_client_with(trusted_state, now, options, ProdVerifier::default(), io)
+}
+
+#[test]
+fn verification_trace_bridges_trusted_state_to_target() {
+    let trusted_height = Height::from(1_u32);
+    let target_height = Height::from(10_u32);
+
+    let (mut light_client, mut state) = make_client(10, trusted_height, None);
+
+    let trace = verify_bisection(target_height, &mut light_client, &mut state).unwrap();
+    let heights = trace.iter().map(|lb| lb.height()).collect::<Vec<_>>();
+
+    assert_eq!(heights, vec![target_height, trusted_height]);
+}
+
+#[test]
+fn cancelled_verification_can_be_resumed() {
+    let target_height = Height::from(10_u32);
+    let (light_client, mut state) = make_client(10, Height::from(1_u32), None);
+
+    let result = light_client.verify_to_target_cancellable(target_height, &mut state, &|| false);
+
+    match result {
+        Err(Error(ErrorDetail::Cancelled(e), _)) => {
+            assert_eq!(e.target_height, target_height);
+            assert_eq!(e.height, target_height);
+        }
+        other => panic!("expected Cancelled error, instead got {:?}", other),
+    }
+
+    let light_block = light_client
+        .verify_to_target(target_height, &mut state)
+        .unwrap();
+
+    assert_eq!(light_block.height(), target_height);
+}
+
+#[test]
+fn bisection_steps_are_bounded() {
+    let target_height = Height::from(10_u32);
+
+    let (light_client, mut state) = make_client(10, Height::from(1_u32), Some(0));
+
+    match light_client.verify_to_target(target_height, &mut state) {
+        Err(Error(ErrorDetail::BisectionStepsExceeded(e), _)) => {
+            assert_eq!(e.target_height, target_height);
+            assert_eq!(e.max_steps, 0);
+        }
+        other => panic!(
+            "expected BisectionStepsExceeded error, instead got {:?}",
+            other
+        ),
+    }
+
+    let (light_client, mut state) = make_client(10, Height::from(1_u32), Some(1));
+
+    let light_block = light_client
+        .verify_to_target(target_height, &mut state)
+        .unwrap();
+
+    assert_eq!(light_block.height(), target_height);
+}
+
+/// Only trusts adjacent blocks, so that bisection goes all the way down to the trusted state.
+struct AdjacentOnlyVerifier;
+
+impl Verifier for AdjacentOnlyVerifier {
+    fn verify(
+        &self,
+        untrusted: &LightBlock,
+        trusted: &LightBlock,
+        _: &Options,
+        _: Time,
+    ) -> Verdict {
+        if untrusted.height() == trusted.height().increment() {
+            Verdict::Success
+        } else {
+            Verdict::NotEnoughTrust(VotingPowerTally {
+                total: 1,
+                tallied: 0,
+                trust_threshold: Default::default(),
+            })
+        }
+    }
+}
+
+/// Records the heights requested in each call to `fetch_light_blocks`.
+#[derive(Clone)]
+struct RecordingIo {
+    io: MockIo,
+    batches: Arc<Mutex<Vec<Vec<Height>>>>,
+}
+
+impl Io for RecordingIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        self.io.fetch_light_block(height)
+    }
+
+    fn fetch_light_blocks(&self, heights: &[Height]) -> Vec<Result<LightBlock, IoError>> {
+        self.batches.lock().unwrap().push(heights.to_vec());
+        self.io.fetch_light_blocks(heights)
+    }
+}
+
+/// Only trusts blocks at most the given number of heights above the trusted block.
+struct BoundedJumpVerifier(u64);
+
+impl Verifier for BoundedJumpVerifier {
+    fn verify(
+        &self,
+        untrusted: &LightBlock,
+        trusted: &LightBlock,
+        _: &Options,
+        _: Time,
+    ) -> Verdict {
+        if untrusted.height().value() - trusted.height().value() <= self.0 {
+            Verdict::Success
+        } else {
+            Verdict::NotEnoughTrust(VotingPowerTally {
+                total: 1,
+                tallied: 0,
+                trust_threshold: Default::default(),
+            })
+        }
+    }
+}
+
+/// Fails like a Tendermint node would for the heights below `lowest_height`.
+struct PrunedIo {
+    io: MockIo,
+    lowest_height: Height,
+}
+
+impl Io for PrunedIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        match height {
+            AtHeight::At(height) if height < self.lowest_height => {
+                let data = format!(
+                    "height {} is not available, lowest height is {}",
+                    height, self.lowest_height
+                );
+
+                Err(IoError::from_rpc(rpc::Error::response(ResponseError::new(
+                    rpc::Code::InternalError,
+                    Some(data),
+                ))))
+            }
+            _ => self.io.fetch_light_block(height),
+        }
+    }
+}
+
+#[test]
+fn bisection_skips_pruned_heights() {
+    let (light_blocks, now) = generate_chain(10);
+    let trusted_state = light_blocks[0].clone();
+    let io = MockIo::new("testchain-1".to_string(), light_blocks);
+
+    let make_client = |lowest_height: u32| {
+        light_client_with(
+            trusted_state.clone(),
+            now,
+            test_options(),
+            BoundedJumpVerifier(4),
+            PrunedIo {
+                io: io.clone(),
+                lowest_height: Height::from(lowest_height),
+            },
+        )
+    };
+
+    let target_height = Height::from(10_u32);
+
+    // After failing to verify 10 then 6, the scheduler picks 4, which is pruned,
+    // so the light client tries 5 instead, from which it can reach 10.
+    let (light_client, mut state) = make_client(5);
+    let light_block = light_client
+        .verify_to_target(target_height, &mut state)
+        .unwrap();
+
+    assert_eq!(light_block.height(), target_height);
+
+    // Height 9 cannot be verified from the trusted state, and neither can anything below it.
+    let (light_client, mut state) = make_client(9);
+    match light_client.verify_to_target(target_height, &mut state) {
+        Err(e) => assert_eq!(e.detail().is_height_pruned(), Some(Height::from(9_u32))),
+        Ok(lb) => panic!("expected HeightPruned error, instead verified {:?}", lb),
+    }
+}
+
+#[test]
+fn one_shot_verification_does_not_need_a_light_client() {
+    let (blocks, now) = generate_chain(3);
+    let options = test_options();
+
+    let verdict = verify_update_header(&blocks[0], &blocks[2], &options, now);
+    assert_eq!(verdict, Verdict::Success);
+
+    let expired = Options {
+        trusting_period: Duration::from_secs(1),
+        ..options
+    };
+
+    let verdict = verify_update_header(&blocks[0], &blocks[2], &expired, now);
+    assert!(matches!(verdict, Verdict::Invalid(e) if e.has_expired()));
+}
+
+#[test]
+fn forward_clock_drift_bounds_headers_from_the_future() {
+    let (blocks, _) = generate_chain(3);
+    let now = tendermint_testgen::helpers::get_time(1);
+
+    let options = Options {
+        clock_drift: Duration::from_secs(1),
+        ..test_options()
+    };
+
+    // The header at height 3 is two seconds ahead of the local clock.
+    let verdict = verify_update_header(&blocks[0], &blocks[2], &options, now);
+    assert!(matches!(
+        verdict,
+        Verdict::Invalid(VerificationErrorDetail::HeaderFromTheFuture(_))
+    ));
+
+    let tolerant = Options {
+        forward_clock_drift: Some(Duration::from_secs(5)),
+        ..options
+    };
+
+    let verdict = verify_update_header(&blocks[0], &blocks[2], &tolerant, now);
+    assert_eq!(verdict, Verdict::Success);
+}
+
+#[test]
+fn verification_errors_report_the_failed_predicate() {
+    let (blocks, now) = generate_chain(3);
+    let options = test_options();
+
+    let failed_predicate = |untrusted: &LightBlock, options: &Options| match verify_update_header(
+        &blocks[0], untrusted, options, now,
+    ) {
+        Verdict::Invalid(e) => e.predicate(),
+        verdict => panic!("expected Invalid verdict, instead got {:?}", verdict),
+    };
+
+    let expired = Options {
+        trusting_period: Duration::from_secs(1),
+        ..options.clone()
+    };
+    assert_eq!(
+        failed_predicate(&blocks[2], &expired),
+        Predicate::WithinTrustPeriod
+    );
+
+    let mut tampered = blocks[2].clone();
+    tampered.signed_header.header.validators_hash = Hash::None;
+    assert_eq!(
+        failed_predicate(&tampered, &options),
+        Predicate::ValidatorSetsMatch
+    );
+
+    let mut tampered = blocks[2].clone();
+    tampered.signed_header.header.time = tendermint_testgen::helpers::get_time(0);
+    assert_eq!(
+        failed_predicate(&tampered, &options),
+        Predicate::HeaderMatchesCommit
+    );
+}
+
+#[test]
+fn scripted_verifier_drives_bisection() {
+    let (light_blocks, now) = generate_chain(8);
+    let options = test_options();
+
+    let tally = VotingPowerTally {
+        total: 100,
+        tallied: 10,
+        trust_threshold: options.trust_threshold,
+    };
+
+    // The first attempt at height 8 lacks trust, which makes the light client
+    // bisect to height 5, from which height 8 can then be verified.
+    let verifier = MockVerifier::default().with_verdict(8_u32, Verdict::NotEnoughTrust(tally));
+
+    let (light_client, mut state) = light_client_with(
+        light_blocks[0].clone(),
+        now,
+        options,
+        verifier.clone(),
+        MockIo::new("testchain-1".to_string(), light_blocks),
+    );
+
+    let light_block = light_client
+        .verify_to_target(Height::from(8_u32), &mut state)
+        .unwrap();
+
+    assert_eq!(light_block.height(), Height::from(8_u32));
+
+    let verified: Vec<(u64, u64)> = verifier
+        .verified()
+        .into_iter()
+        .map(|(trusted, untrusted)| (trusted.value(), untrusted.value()))
+        .collect();
+
+    assert_eq!(verified, vec![(1, 8), (1, 5), (5, 8)]);
+}
+
+#[test]
+fn bisection_prefetches_upcoming_pivots() {
+    let (light_blocks, now) = generate_chain(10);
+    let trusted_state = light_blocks[0].clone();
+
+    let io = RecordingIo {
+        io: MockIo::new("testchain-1".to_string(), light_blocks),
+        batches: Arc::new(Mutex::new(Vec::new())),
+    };
+
+    let (light_client, mut state) = light_client_with(
+        trusted_state,
+        now,
+        test_options(),
+        AdjacentOnlyVerifier,
+        io.clone(),
+    );
+    let light_client = light_client.with_prefetch_depth(2);
+
+    let target_height = Height::from(10_u32);
+    let light_block = light_client
+        .verify_to_target(target_height, &mut state)
+        .unwrap();
+
+    assert_eq!(light_block.height(), target_height);
+
+    // After failing to verify height 10, the scheduler picks 6, then 4 if that fails too.
+    let batches = io.batches.lock().unwrap();
+    assert_eq!(batches[0], vec![Height::from(6_u32), Height::from(4_u32)]);
+}
+
+/// Generate the light blocks of a chain of the given length, over which a sixth
+/// of the voting power changes every 10 heights, along with a time one second
+/// after its latest block.
+fn generate_rotating_chain(length: u64) -> (Vec<LightBlock>, Time) {
+    let validators_at = |height: u64| {
+        let first = height / 10;
+        (first..first + 6)
+            .map(|id| Validator::new(&id.to_string()).voting_power(50))
+            .collect::<Vec<_>>()
+    };
+
+    let light_blocks = (1..=length)
+        .map(|height| {
+            let validators = validators_at(height);
+            let next_validators = validators_at(height + 1);
+
+            let header = Header::new(&validators)
+                .next_validators(&next_validators)
+                .chain_id("testchain-1")
+                .height(height)
+                .time(height);
+            let commit = Commit::new(header.clone(), 1);
+
+            let light_block = TestgenLightBlock::new(header, commit)
+                .validators(&validators)
+                .next_validators(&next_validators)
+                .provider(&default_peer_id().to_string());
+
+            testgen_to_lb(light_block.generate().unwrap())
+        })
+        .collect();
+
+    let now = tendermint_testgen::helpers::get_time(length + 1);
+
+    (light_blocks, now)
+}
+
+/// Counts the light blocks fetched from the wrapped `Io`.
+struct CountingIo {
+    io: MockIo,
+    fetched: Arc<AtomicUsize>,
+}
+
+impl Io for CountingIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        self.fetched.fetch_add(1, Ordering::SeqCst);
+        self.io.fetch_light_block(height)
+    }
+}
+
+#[test]
+fn adaptive_scheduler_fetches_fewer_blocks_over_large_gaps() {
+    let (light_blocks, now) = generate_rotating_chain(200);
+    let target_height = Height::from(200_u32);
+
+    let fetched_with = |scheduler: Box<dyn Scheduler>| {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let io = CountingIo {
+            io: MockIo::new("testchain-1".to_string(), light_blocks.clone()),
+            fetched: fetched.clone(),
+        };
+
+        let mut light_store = MemoryStore::new();
+        light_store.insert(light_blocks[0].clone(), Status::Trusted);
+        let mut state = State::new(light_store);
+
+        let light_client = LightClient::new(
+            default_peer_id(),
+            test_options(),
+            MockClock { now },
+            scheduler,
+            ProdVerifier::default(),
+            ProdHasher,
+            io,
+        )
+        .unwrap();
+
+        let light_block = light_client
+            .verify_to_target(target_height, &mut state)
+            .unwrap();
+        assert_eq!(light_block, light_blocks[199]);
+
+        fetched.load(Ordering::SeqCst)
+    };
+
+    let bisection = fetched_with(Box::new(scheduler::basic_bisecting_schedule));
+    let adaptive = fetched_with(Box::<AdaptiveScheduler>::default());
+
+    assert!(
+        adaptive < bisection,
+        "adaptive scheduler fetched {} blocks, bisection fetched {}",
+        adaptive,
+        bisection
+    );
+}
+
 #[test]
 fn run_tests() {
     let mut tester = Tester::new("light client verification", TEST_FILES_PATH);
     tester.add_test("forward verification with bisection", forward_test);
+    tester.add_test(
+        "forward verification with bisection (static dispatch)",
+        forward_test_static_dispatch,
+    );
     tester.run_foreach_in_dir("bisection/single_peer");
     tester.finalize();
 }
@@ -4,15 +4,15 @@ use serde::{Deserialize, Serialize};
 use serde_json::Error;
 use std::convert::TryFrom;
 use std::str::FromStr;
-use std::time::Duration;
 use tendermint::validator::Set;
-use tendermint_light_client::components::verifier::Verdict;
+use tendermint_light_client::tests::model_based::{
+    LiteVerdict, SingleStepTrace, TraceExecutor, TraceStep,
+};
 use tendermint_light_client::types::ValidatorSet;
 use tendermint_light_client::{
     tests::*,
-    types::{LightBlock, Time, TrustThreshold},
+    types::{LightBlock, Time},
 };
-use tendermint_testgen::light_block::default_peer_id;
 use tendermint_testgen::{
     apalache::*, jsonatr::*, light_block::TmLightBlock, validator::generate_validators, Command,
     Generator, LightBlock as TestgenLightBlock, TestEnv, Tester, Validator, Vote,
@@ -33,25 +33,6 @@ pub enum LiteTestKind {
     Bisection,
 }
 
-/// An abstraction of the LightClient verification verdict
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
-pub enum LiteVerdict {
-    /// verified successfully
-    #[serde(rename = "SUCCESS")]
-    Success,
-    /// outside of trusting period
-    #[serde(rename = "FAILED_TRUSTING_PERIOD")]
-    FailedTrustingPeriod,
-    /// block verification based on the header and commit structure failed
-    #[serde(rename = "INVALID")]
-    Invalid,
-    /// passed block verification, but the validator set is too different to verify it
-    #[serde(rename = "NOT_ENOUGH_TRUST")]
-    NotEnoughTrust,
-    #[serde(rename = "PARSE_ERROR")]
-    ParseError,
-}
-
 /// A single-step test case is a test for `Verifier::verify()` function.
 /// It contains an initial trusted block, plus a sequence of input blocks,
 /// each with the expected verdict.
@@ -245,7 +226,7 @@ impl SingleStepTestFuzzer for HeaderValHashFuzzer {
             Validator::new("2"),
             Validator::new("3"),
         ];
-        let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap());
+        let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap()).unwrap();
 
         input.block.validators = valset;
         (String::from("header validators_hash"), LiteVerdict::Invalid)
@@ -261,7 +242,7 @@ impl SingleStepTestFuzzer for HeaderNextValHashFuzzer {
             Validator::new("2"),
             Validator::new("3"),
         ];
-        let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap());
+        let valset = ValidatorSet::without_proposer(generate_validators(&vals).unwrap()).unwrap();
 
         input.block.next_validators = valset;
         (
@@ -472,7 +453,7 @@ impl SingleStepTestFuzzer for SignaturesFuzzer {
 
         let mut rng = rand::thread_rng();
         let random_num: u32 = rng.gen();
-        if random_num % 2 == 0 {
+        if random_num.is_multiple_of(2) {
             let faulty_val = Validator::new("faulty");
             let vote = Vote::new(faulty_val, header);
 
@@ -512,20 +493,11 @@ fn single_step_test(
     _root_env: &TestEnv,
     output_env: &TestEnv,
 ) {
-    let mut latest_trusted = LightBlock::new(
-        tc.initial.signed_header,
-        tc.initial.next_validator_set.clone(),
-        tc.initial.next_validator_set,
-        default_peer_id(),
-    );
-    let clock_drift = Duration::from_secs(0);
-    let trusting_period: Duration = tc.initial.trusting_period.into();
+    let mut steps = Vec::with_capacity(tc.input.len());
 
     for (i, input) in tc.input.iter().enumerate() {
         output_env.logln(&format!("    > step {}, expecting {:?}", i, input.verdict));
-
         let deserialized = serde_roundtrip(input);
-
         if input.verdict == LiteVerdict::ParseError {
             assert!(
                 deserialized.is_err(),
@@ -538,56 +510,46 @@ fn single_step_test(
                 "deserialization failure: {}",
                 deserialized.err().unwrap()
             );
-
-            // ------------------->
-            // Below is a temporary work around to get rid of bug-gy validator sorting
-            // which was making all the tests fail
-            let current_vals = input.block.validators.clone();
-            let current_resorted = Set::without_proposer(current_vals.validators().to_vec());
-
-            let current_next_vals = input.block.next_validators.clone();
-            let current_next_resorted =
-                Set::without_proposer(current_next_vals.validators().to_vec());
-
-            let mut mutated_block = input.block.clone();
-            mutated_block.validators = current_resorted;
-            mutated_block.next_validators = current_next_resorted;
-            // ------------------->
-
-            let now = input.now;
-            match verify_single(
-                latest_trusted.clone(),
-                mutated_block.clone(),
-                TrustThreshold::default(),
-                trusting_period,
-                clock_drift,
-                now,
-            ) {
-                Ok(new_state) => {
-                    assert_eq!(input.verdict, LiteVerdict::Success);
-                    let expected_state: LightBlock = mutated_block.clone();
-                    assert_eq!(new_state, expected_state);
-                    latest_trusted = LightBlock::new(
-                        new_state.signed_header,
-                        new_state.validators,
-                        new_state.next_validators,
-                        new_state.provider,
-                    );
-                }
-                Err(e) => {
-                    output_env.logln(&format!("      > lite: {:?}", e));
-                    match e {
-                        Verdict::Invalid(_) => assert_eq!(input.verdict, LiteVerdict::Invalid),
-                        Verdict::NotEnoughTrust(_) => {
-                            assert_eq!(input.verdict, LiteVerdict::NotEnoughTrust)
-                        }
-                        Verdict::Success => {
-                            panic!("verify_single() returned error with Verdict::Success")
-                        }
-                    }
-                }
-            }
         }
+
+        // ------------------->
+        // Below is a temporary work around to get rid of bug-gy validator sorting
+        // which was making all the tests fail
+        let current_vals = input.block.validators.clone();
+        let current_resorted = Set::without_proposer(current_vals.validators().to_vec()).unwrap();
+
+        let current_next_vals = input.block.next_validators.clone();
+        let current_next_resorted =
+            Set::without_proposer(current_next_vals.validators().to_vec()).unwrap();
+
+        let mut mutated_block = input.block.clone();
+        mutated_block.validators = current_resorted;
+        mutated_block.next_validators = current_next_resorted;
+        // ------------------->
+
+        steps.push(TraceStep {
+            block: mutated_block,
+            now: input.now,
+            verdict: input.verdict,
+        });
+    }
+
+    let trace = SingleStepTrace {
+        description: tc.description,
+        initial: tc.initial,
+        input: steps,
+    };
+
+    let executor = TraceExecutor::default();
+
+    if let Err(e) = executor.run_verifier(&trace) {
+        output_env.logln(&format!("      > verifier: {}", e));
+        panic!("verifier does not conform to the model: {}", e);
+    }
+
+    if let Err(e) = executor.run_light_client(&trace) {
+        output_env.logln(&format!("      > light client: {}", e));
+        panic!("light client does not conform to the model: {}", e);
     }
 }
 
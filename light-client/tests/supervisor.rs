@@ -44,14 +44,18 @@ fn make_instance(peer_id: PeerId, trust_options: TrustOptions, io: MockIo, now:
         trust_threshold: trust_options.trust_level,
         trusting_period: trust_options.period.into(),
         clock_drift: Duration::from_secs(10),
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let clock = MockClock { now };
     let verifier = ProdVerifier::default();
-    let hasher = ProdHasher::default();
+    let hasher = ProdHasher;
     let scheduler = scheduler::basic_bisecting_schedule;
 
-    let light_client = LightClient::new(peer_id, options, clock, scheduler, verifier, hasher, io);
+    let light_client =
+        LightClient::new(peer_id, options, clock, scheduler, verifier, hasher, io).unwrap();
 
     Instance::new(light_client, state)
 }
@@ -97,7 +101,9 @@ fn run_multipeer_test(tc: LightClientTest<LightBlock>) {
     let target_height = tc.height_to_verify;
 
     match handle.verify_to_target(target_height) {
-        Ok(new_state) => {
+        Ok(verified) => {
+            let new_state = verified.into_light_block();
+
             // Check that the expected state and new_state match
             let untrusted_light_block = io
                 .fetch_light_block(AtHeight::At(target_height))
@@ -65,7 +65,29 @@ define_error! {
 
         TransportClone
             { detail: String }
-            | e | { format_args!("failed to clone underlying transport: {}", e.detail) }
+            | e | { format_args!("failed to clone underlying transport: {}", e.detail) },
+
+        MessageTooLarge
+            { size: u64 }
+            | e | { format_args!("privval message too large: {} bytes", e.size) },
+
+        MalformedMessageLength
+            | _ | { "malformed privval message length prefix" },
+
+        MessageDecode
+            [ TraceError<DecodeError> ]
+            | _ | { "malformed privval message" },
+
+        UnexpectedMessage
+            | _ | { "unexpected privval message" },
+
+        RemoteSigner
+            { code: i32, description: String }
+            | e | { format_args!("remote signer error (code {}): {}", e.code, e.description) },
+
+        Tendermint
+            [ tendermint::Error ]
+            | _ | { "tendermint error" },
 
     }
 }
@@ -3,7 +3,6 @@
 #![forbid(unsafe_code)]
 #![deny(
     nonstandard_style,
-    private_in_public,
     rust_2018_idioms,
     trivial_casts,
     trivial_numeric_casts,
@@ -19,11 +18,13 @@
     unused_import_braces,
     unused_qualifications
 )]
+#![allow(clippy::multiple_crate_versions)]
 #![doc(
     html_root_url = "https://docs.rs/tendermint-p2p/0.21.0",
     html_logo_url = "https://raw.githubusercontent.com/informalsystems/tendermint-rs/master/img/logo-tendermint-rs_3961x4001.png"
 )]
 
 pub mod error;
+pub mod privval;
 pub mod secret_connection;
 pub mod transport;
@@ -0,0 +1,416 @@
+//! Remote signer ("privval") protocol, over which Tendermint requests its public
+//! key and signatures of votes and proposals from a remote signer, eg. a KMS.
+//!
+//! Tendermint listens on its `priv_validator_laddr`, and the remote signer connects
+//! to it. Over TCP the messages are exchanged through a [`SecretConnection`], while
+//! over Unix domain sockets they are exchanged in plain. In both cases, messages are
+//! length-delimited protobuf encodings of [`proto::privval::Message`].
+//!
+//! [`SignerClient`] implements the side of Tendermint, sending the requests, and
+//! [`SignerServer`] implements the side of the remote signer, answering them with
+//! any [`Signer`], eg. a [`FilePrivValidator`] protecting against double signing.
+//! [`SignerClient`] is itself a [`Signer`], so that code generic over signers can
+//! use remote signers.
+//!
+//! [`FilePrivValidator`]: tendermint::privval::FilePrivValidator
+
+use std::convert::{TryFrom, TryInto};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::Path;
+
+use ed25519_dalek as ed25519;
+use prost::Message as _;
+
+use tendermint::{chain, proposal::Proposal, signer::Signer, vote::Vote, PublicKey};
+use tendermint_proto as proto;
+use tendermint_proto::privval::message::Sum;
+use tendermint_proto::privval::{
+    Message, PingRequest, PingResponse, PubKeyRequest, PubKeyResponse, RemoteSignerError,
+    SignProposalRequest, SignVoteRequest, SignedProposalResponse, SignedVoteResponse,
+};
+
+use crate::error::Error;
+use crate::secret_connection::{SecretConnection, Version};
+
+/// Maximum size of a message of the protocol, as enforced by Tendermint
+pub const MAX_MESSAGE_SIZE: usize = 10 * 1024;
+
+/// Maximum length of the varint length prefix of a message
+const MAX_VARINT_LENGTH: usize = 10;
+
+/// Read a length-delimited message of the protocol.
+///
+/// # Errors
+///
+/// * if reading from the connection fails
+/// * if the length prefix of the message is not a valid varint
+/// * if the message is larger than [`MAX_MESSAGE_SIZE`] or cannot be decoded
+pub fn read_message<R: Read>(reader: &mut R) -> Result<Message, Error> {
+    let mut length: u64 = 0;
+    for i in 0..MAX_VARINT_LENGTH {
+        let mut byte = [0_u8; 1];
+        reader.read_exact(&mut byte)?;
+        length |= u64::from(byte[0] & 0x7f) << (7 * i);
+
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+
+        if i == MAX_VARINT_LENGTH - 1 {
+            return Err(Error::malformed_message_length());
+        }
+    }
+
+    let length = usize::try_from(length)
+        .ok()
+        .filter(|length| *length <= MAX_MESSAGE_SIZE)
+        .ok_or_else(|| Error::message_too_large(length))?;
+
+    let mut bytes = vec![0_u8; length];
+    reader.read_exact(&mut bytes)?;
+
+    Message::decode(bytes.as_slice()).map_err(Error::message_decode)
+}
+
+/// Write a length-delimited message of the protocol.
+///
+/// # Errors
+///
+/// * if writing to the connection fails
+pub fn write_message<W: Write>(writer: &mut W, message: &Message) -> Result<(), Error> {
+    let mut bytes = Vec::with_capacity(MAX_VARINT_LENGTH + message.encoded_len());
+    prost::encoding::encode_varint(message.encoded_len() as u64, &mut bytes);
+    message.encode_raw(&mut bytes);
+
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Client side of the protocol, as used by Tendermint to request a remote signer.
+pub struct SignerClient<C> {
+    conn: C,
+}
+
+impl SignerClient<SecretConnection<TcpStream>> {
+    /// Accept a connection from a remote signer on the given listener, and
+    /// perform the secret connection handshake with the given node key.
+    ///
+    /// # Errors
+    ///
+    /// * if accepting the connection fails
+    /// * if the handshake fails
+    pub fn accept_tcp(listener: &TcpListener, node_key: ed25519::Keypair) -> Result<Self, Error> {
+        let (stream, _) = listener.accept()?;
+        let conn = SecretConnection::new(stream, node_key, Version::V0_34)?;
+        Ok(Self::new(conn))
+    }
+}
+
+#[cfg(unix)]
+impl SignerClient<UnixStream> {
+    /// Accept a connection from a remote signer on the given listener.
+    ///
+    /// # Errors
+    ///
+    /// * if accepting the connection fails
+    pub fn accept_unix(listener: &UnixListener) -> Result<Self, Error> {
+        let (stream, _) = listener.accept()?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<C: Read + Write> SignerClient<C> {
+    /// Create a client sending its requests over the given connection.
+    pub const fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Check that the remote signer is alive.
+    ///
+    /// # Errors
+    ///
+    /// * if the request cannot be sent or the response cannot be read
+    /// * if the response is not a ping response
+    pub fn ping(&mut self) -> Result<(), Error> {
+        match self.request(Sum::PingRequest(PingRequest {}))? {
+            Sum::PingResponse(PingResponse {}) => Ok(()),
+            _ => Err(Error::unexpected_message()),
+        }
+    }
+
+    /// Request the consensus public key of the remote signer for the given chain.
+    ///
+    /// # Errors
+    ///
+    /// * if the request cannot be sent or the response cannot be read
+    /// * if the remote signer answers with an error or an invalid public key
+    pub fn public_key(&mut self, chain_id: &chain::Id) -> Result<PublicKey, Error> {
+        let request = Sum::PubKeyRequest(PubKeyRequest {
+            chain_id: chain_id.to_string(),
+        });
+
+        match self.request(request)? {
+            Sum::PubKeyResponse(PubKeyResponse {
+                error: Some(error), ..
+            }) => Err(remote_signer_error(error)),
+            Sum::PubKeyResponse(PubKeyResponse {
+                pub_key: Some(pub_key),
+                ..
+            }) => pub_key.try_into().map_err(Error::tendermint),
+            _ => Err(Error::unexpected_message()),
+        }
+    }
+
+    /// Request the remote signer to sign the given vote for the given chain,
+    /// and return the signed vote.
+    ///
+    /// # Errors
+    ///
+    /// * if the request cannot be sent or the response cannot be read
+    /// * if the remote signer answers with an error or an invalid vote
+    pub fn sign_vote(&mut self, chain_id: &chain::Id, vote: Vote) -> Result<Vote, Error> {
+        let request = Sum::SignVoteRequest(SignVoteRequest {
+            vote: Some(vote.into()),
+            chain_id: chain_id.to_string(),
+        });
+
+        match self.request(request)? {
+            Sum::SignedVoteResponse(SignedVoteResponse {
+                error: Some(error), ..
+            }) => Err(remote_signer_error(error)),
+            Sum::SignedVoteResponse(SignedVoteResponse {
+                vote: Some(vote), ..
+            }) => vote.try_into().map_err(Error::tendermint),
+            _ => Err(Error::unexpected_message()),
+        }
+    }
+
+    /// Request the remote signer to sign the given proposal for the given chain,
+    /// and return the signed proposal.
+    ///
+    /// # Errors
+    ///
+    /// * if the request cannot be sent or the response cannot be read
+    /// * if the remote signer answers with an error or an invalid proposal
+    pub fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: Proposal,
+    ) -> Result<Proposal, Error> {
+        let request = Sum::SignProposalRequest(SignProposalRequest {
+            proposal: Some(proposal.into()),
+            chain_id: chain_id.to_string(),
+        });
+
+        match self.request(request)? {
+            Sum::SignedProposalResponse(SignedProposalResponse {
+                error: Some(error), ..
+            }) => Err(remote_signer_error(error)),
+            Sum::SignedProposalResponse(SignedProposalResponse {
+                proposal: Some(proposal),
+                ..
+            }) => proposal.try_into().map_err(Error::tendermint),
+            _ => Err(Error::unexpected_message()),
+        }
+    }
+
+    fn request(&mut self, request: Sum) -> Result<Sum, Error> {
+        write_message(&mut self.conn, &Message { sum: Some(request) })?;
+        read_message(&mut self.conn)?
+            .sum
+            .ok_or_else(Error::unexpected_message)
+    }
+}
+
+/// Remote signer, to which Tendermint delegates its signatures.
+///
+/// The protocol only allows to sign votes and proposals, so that this is not
+/// a [`RawSigner`](tendermint::signer::RawSigner).
+impl<C: Read + Write> Signer for SignerClient<C> {
+    fn public_key(&mut self, chain_id: &chain::Id) -> Result<PublicKey, tendermint::Error> {
+        Self::public_key(self, chain_id).map_err(|e| signer_client_error(&e))
+    }
+
+    fn sign_vote(
+        &mut self,
+        chain_id: &chain::Id,
+        vote: &mut Vote,
+    ) -> Result<(), tendermint::Error> {
+        *vote =
+            Self::sign_vote(self, chain_id, vote.clone()).map_err(|e| signer_client_error(&e))?;
+        Ok(())
+    }
+
+    fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), tendermint::Error> {
+        *proposal = Self::sign_proposal(self, chain_id, proposal.clone())
+            .map_err(|e| signer_client_error(&e))?;
+        Ok(())
+    }
+}
+
+fn signer_client_error(error: &Error) -> tendermint::Error {
+    tendermint::Error::signer(error.to_string())
+}
+
+fn remote_signer_error(error: RemoteSignerError) -> Error {
+    Error::remote_signer(error.code, error.description)
+}
+
+/// Server side of the protocol, as used by a remote signer to answer the
+/// requests of Tendermint with the given signer.
+pub struct SignerServer<C, V> {
+    conn: C,
+    validator: V,
+}
+
+impl<V: Signer> SignerServer<SecretConnection<TcpStream>, V> {
+    /// Connect to the Tendermint node listening at the given address, and
+    /// perform the secret connection handshake with the given key.
+    ///
+    /// # Errors
+    ///
+    /// * if connecting to the node fails
+    /// * if the handshake fails
+    pub fn connect_tcp(
+        addr: impl ToSocketAddrs,
+        key: ed25519::Keypair,
+        validator: V,
+    ) -> Result<Self, Error> {
+        let stream = TcpStream::connect(addr)?;
+        let conn = SecretConnection::new(stream, key, Version::V0_34)?;
+        Ok(Self::new(conn, validator))
+    }
+}
+
+#[cfg(unix)]
+impl<V: Signer> SignerServer<UnixStream, V> {
+    /// Connect to the Tendermint node listening at the given socket path.
+    ///
+    /// # Errors
+    ///
+    /// * if connecting to the node fails
+    pub fn connect_unix(path: impl AsRef<Path>, validator: V) -> Result<Self, Error> {
+        let stream = UnixStream::connect(path)?;
+        Ok(Self::new(stream, validator))
+    }
+}
+
+impl<C: Read + Write, V: Signer> SignerServer<C, V> {
+    /// Create a server answering the requests received over the given connection.
+    pub const fn new(conn: C, validator: V) -> Self {
+        Self { conn, validator }
+    }
+
+    /// Get the validator used to answer requests.
+    pub const fn validator(&self) -> &V {
+        &self.validator
+    }
+
+    /// Answer requests until the connection fails, eg. when it is closed by
+    /// the node.
+    ///
+    /// # Errors
+    ///
+    /// * if a request cannot be read, or a response cannot be sent
+    /// * if a message which is not a request is received
+    pub fn serve(&mut self) -> Result<(), Error> {
+        loop {
+            self.handle_request()?;
+        }
+    }
+
+    /// Answer a single request.
+    ///
+    /// Failures to provide the public key or to sign are sent back to the
+    /// node as errors, rather than returned.
+    ///
+    /// # Errors
+    ///
+    /// * if the request cannot be read, or the response cannot be sent
+    /// * if the message received is not a request
+    pub fn handle_request(&mut self) -> Result<(), Error> {
+        let request = read_message(&mut self.conn)?
+            .sum
+            .ok_or_else(Error::unexpected_message)?;
+
+        let response = match request {
+            Sum::PingRequest(PingRequest {}) => Sum::PingResponse(PingResponse {}),
+            Sum::PubKeyRequest(request) => {
+                let pub_key = chain::Id::try_from(request.chain_id)
+                    .and_then(|chain_id| self.validator.public_key(&chain_id));
+
+                match pub_key {
+                    Ok(pub_key) => Sum::PubKeyResponse(PubKeyResponse {
+                        pub_key: Some(pub_key.into()),
+                        error: None,
+                    }),
+                    Err(e) => Sum::PubKeyResponse(PubKeyResponse {
+                        pub_key: None,
+                        error: Some(signer_error(&e)),
+                    }),
+                }
+            }
+            Sum::SignVoteRequest(request) => match self.sign_vote(request) {
+                Ok(vote) => Sum::SignedVoteResponse(SignedVoteResponse {
+                    vote: Some(vote.into()),
+                    error: None,
+                }),
+                Err(e) => Sum::SignedVoteResponse(SignedVoteResponse {
+                    vote: None,
+                    error: Some(signer_error(&e)),
+                }),
+            },
+            Sum::SignProposalRequest(request) => match self.sign_proposal(request) {
+                Ok(proposal) => Sum::SignedProposalResponse(SignedProposalResponse {
+                    proposal: Some(proposal.into()),
+                    error: None,
+                }),
+                Err(e) => Sum::SignedProposalResponse(SignedProposalResponse {
+                    proposal: None,
+                    error: Some(signer_error(&e)),
+                }),
+            },
+            _ => return Err(Error::unexpected_message()),
+        };
+
+        write_message(
+            &mut self.conn,
+            &Message {
+                sum: Some(response),
+            },
+        )
+    }
+
+    fn sign_vote(&mut self, request: SignVoteRequest) -> Result<Vote, tendermint::Error> {
+        let chain_id = chain::Id::try_from(request.chain_id)?;
+        let mut vote = Vote::try_from(request.vote.unwrap_or_default())?;
+        self.validator.sign_vote(&chain_id, &mut vote)?;
+        Ok(vote)
+    }
+
+    fn sign_proposal(
+        &mut self,
+        request: SignProposalRequest,
+    ) -> Result<Proposal, tendermint::Error> {
+        let chain_id = chain::Id::try_from(request.chain_id)?;
+        let mut proposal = Proposal::try_from(request.proposal.unwrap_or_default())?;
+        self.validator.sign_proposal(&chain_id, &mut proposal)?;
+        Ok(proposal)
+    }
+}
+
+fn signer_error(error: &tendermint::Error) -> RemoteSignerError {
+    RemoteSignerError {
+        code: proto::privval::Errors::Unknown as i32,
+        description: error.to_string(),
+    }
+}
@@ -54,7 +54,7 @@ pub struct Handshake<S> {
     state: S,
 }
 
-/// Handshake states
+// Handshake states
 
 /// `AwaitingEphKey` means we're waiting for the remote ephemeral pubkey.
 pub struct AwaitingEphKey {
@@ -80,7 +80,7 @@ impl Handshake<AwaitingEphKey> {
         protocol_version: Version,
     ) -> (Self, EphemeralPublic) {
         // Generate an ephemeral key for perfect forward secrecy.
-        let local_eph_privkey = EphemeralSecret::new(&mut OsRng);
+        let local_eph_privkey = EphemeralSecret::new(OsRng);
         let local_eph_pubkey = EphemeralPublic::from(&local_eph_privkey);
 
         (
@@ -106,9 +106,8 @@ impl Handshake<AwaitingEphKey> {
         &mut self,
         remote_eph_pubkey: EphemeralPublic,
     ) -> Result<Handshake<AwaitingAuthSig>, Error> {
-        let local_eph_privkey = match self.state.local_eph_privkey.take() {
-            Some(key) => key,
-            None => return Err(Error::missing_secret()),
+        let Some(local_eph_privkey) = self.state.local_eph_privkey.take() else {
+            return Err(Error::missing_secret());
         };
         let local_eph_pubkey = EphemeralPublic::from(&local_eph_privkey);
 
@@ -262,8 +261,12 @@ pub struct SecretConnection<IoHandler> {
 }
 
 impl<IoHandler: Read + Write + Send + Sync> SecretConnection<IoHandler> {
-    /// Returns the remote pubkey. Panics if there's no key.
-    pub fn remote_pubkey(&self) -> PublicKey {
+    /// Returns the remote pubkey.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no key.
+    pub const fn remote_pubkey(&self) -> PublicKey {
         self.remote_pubkey.expect("remote_pubkey uninitialized")
     }
 
@@ -338,6 +341,10 @@ where
     /// ## Errors
     /// Fails when the `try_clone` operation for the underlying I/O handler
     /// fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no remote pubkey.
     pub fn split(self) -> Result<(Sender<IoHandler>, Receiver<IoHandler>), Error> {
         let remote_pubkey = self.remote_pubkey.expect("remote_pubkey to be initialized");
         Ok((
@@ -544,8 +551,8 @@ fn encrypt_and_write<IoHandler: Write>(
     let mut data_copy = data;
     while !data_copy.is_empty() {
         let chunk: &[u8];
-        if DATA_MAX_SIZE < data.len() {
-            chunk = &data[..DATA_MAX_SIZE];
+        if DATA_MAX_SIZE < data_copy.len() {
+            chunk = &data_copy[..DATA_MAX_SIZE];
             data_copy = &data_copy[DATA_MAX_SIZE..];
         } else {
             chunk = data_copy;
@@ -553,7 +560,7 @@ fn encrypt_and_write<IoHandler: Write>(
         }
         let sealed_frame = &mut [0_u8; TAG_SIZE + TOTAL_FRAME_SIZE];
         encrypt(chunk, &send_state.cipher, &send_state.nonce, sealed_frame)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| io::Error::other(e.to_string()))?;
         send_state.nonce.increment();
         // end encryption
 
@@ -606,7 +613,7 @@ fn read_and_decrypt<IoHandler: Read>(
 ) -> io::Result<usize> {
     if !recv_state.buffer.is_empty() {
         let n = cmp::min(data.len(), recv_state.buffer.len());
-        data.copy_from_slice(&recv_state.buffer[..n]);
+        data[..n].copy_from_slice(&recv_state.buffer[..n]);
         let mut leftover_portion = vec![
             0;
             recv_state
@@ -634,7 +641,7 @@ fn read_and_decrypt<IoHandler: Read>(
     );
 
     if let Err(err) = res {
-        return Err(io::Error::new(io::ErrorKind::Other, err.to_string()));
+        return Err(io::Error::other(err.to_string()));
     }
 
     recv_state.nonce.increment();
@@ -643,10 +650,9 @@ fn read_and_decrypt<IoHandler: Read>(
     let chunk_length = u32::from_le_bytes(frame[..4].try_into().expect("chunk framing failed"));
 
     if chunk_length as usize > DATA_MAX_SIZE {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("chunk is too big: {}! max: {}", chunk_length, DATA_MAX_SIZE),
-        ));
+        return Err(io::Error::other(format!(
+            "chunk is too big: {chunk_length}! max: {DATA_MAX_SIZE}"
+        )));
     }
 
     let mut chunk = vec![0; chunk_length as usize];
@@ -659,7 +665,7 @@ fn read_and_decrypt<IoHandler: Read>(
 
     let n = cmp::min(data.len(), chunk.len());
     data[..n].copy_from_slice(&chunk[..n]);
-    recv_state.buffer.copy_from_slice(&chunk[n..]);
+    recv_state.buffer = chunk[n..].to_vec();
 
     Ok(n)
 }
@@ -16,6 +16,10 @@ impl Default for Nonce {
 
 impl Nonce {
     /// Increment the nonce's counter by 1
+    ///
+    /// # Panics
+    ///
+    /// Panics if the counter overflows.
     pub fn increment(&mut self) {
         let counter: u64 = u64::from_le_bytes(self.0[4..].try_into().expect("framing failed"));
         self.0[4..].copy_from_slice(
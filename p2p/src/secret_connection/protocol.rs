@@ -80,6 +80,10 @@ impl Version {
     /// # Errors
     ///
     /// * if the message is malformed
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length checks of the message do not match its framing.
     pub fn decode_initial_handshake(self, bytes: &[u8]) -> Result<EphemeralPublic, Error> {
         let eph_pubkey = if self.is_protobuf() {
             // Equivalent Go implementation:
@@ -113,6 +117,10 @@ impl Version {
     }
 
     /// Encode signature which authenticates the handshake
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `AuthSigMessage` proto cannot be encoded into a `Vec`.
     #[must_use]
     pub fn encode_auth_signature(
         self,
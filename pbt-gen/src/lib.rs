@@ -5,7 +5,7 @@
 //!
 //! 1. The utilities are relatively general.
 //! 2. The utilities don't rely on any code internal to the other crates of
-//! this repository.
+//!    this repository.
 //!
 //! The each module of this crate (and the module's dependencies) are guarded by
 //! a feature, documented along with the module.
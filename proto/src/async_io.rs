@@ -0,0 +1,91 @@
+//! Asynchronous encoding and decoding of domain types into writers and from
+//! readers, with the [`futures`] I/O traits
+
+use crate::io::{decode_length_delimiter, MAX_VARINT_LENGTH};
+use crate::{Error, Protobuf};
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use prost::Message;
+use std::convert::TryFrom;
+use std::fmt::Display;
+
+/// Encode a domain type into a writer in Protobuf format.
+///
+/// See [`Protobuf::encode_to_writer`].
+pub async fn encode_to_writer<T, Raw, W>(value: &T, writer: &mut W) -> Result<(), Error>
+where
+    T: Protobuf<Raw>,
+    Raw: Message + From<T> + Default,
+    <T as TryFrom<Raw>>::Error: Display,
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(&value.encode_vec()?)
+        .await
+        .map_err(Error::io)
+}
+
+/// Encode a domain type with a length-delimiter into a writer in Protobuf
+/// format.
+///
+/// See [`Protobuf::encode_length_delimited_to_writer`].
+pub async fn encode_length_delimited_to_writer<T, Raw, W>(
+    value: &T,
+    writer: &mut W,
+) -> Result<(), Error>
+where
+    T: Protobuf<Raw>,
+    Raw: Message + From<T> + Default,
+    <T as TryFrom<Raw>>::Error: Display,
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(&value.encode_length_delimited_vec()?)
+        .await
+        .map_err(Error::io)
+}
+
+/// Decode a domain type from a reader, which is read to its end.
+///
+/// See [`Protobuf::decode_from_reader`].
+pub async fn decode_from_reader<T, Raw, R>(reader: &mut R) -> Result<T, Error>
+where
+    T: Protobuf<Raw>,
+    Raw: Message + From<T> + Default,
+    <T as TryFrom<Raw>>::Error: Display,
+    R: AsyncRead + Unpin,
+{
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await.map_err(Error::io)?;
+    T::decode_vec(&buf)
+}
+
+/// Decode a length-delimited domain type from a reader, reading no further
+/// than its end.
+///
+/// See [`Protobuf::decode_length_delimited_from_reader`].
+pub async fn decode_length_delimited_from_reader<T, Raw, R>(reader: &mut R) -> Result<T, Error>
+where
+    T: Protobuf<Raw>,
+    Raw: Message + From<T> + Default,
+    <T as TryFrom<Raw>>::Error: Display,
+    R: AsyncRead + Unpin,
+{
+    let len = read_length_delimiter(reader).await?;
+    let mut buf = vec![0; usize::try_from(len).map_err(Error::parse_length)?];
+    reader.read_exact(&mut buf).await.map_err(Error::io)?;
+    T::decode_vec(&buf)
+}
+
+async fn read_length_delimiter<R: AsyncRead + Unpin>(reader: &mut R) -> Result<u64, Error> {
+    let mut buf = [0; MAX_VARINT_LENGTH];
+    for i in 0..MAX_VARINT_LENGTH {
+        reader
+            .read_exact(&mut buf[i..=i])
+            .await
+            .map_err(Error::io)?;
+        if buf[i] < 0x80 {
+            return decode_length_delimiter(&buf[..=i]);
+        }
+    }
+    decode_length_delimiter(&buf)
+}
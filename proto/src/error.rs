@@ -26,6 +26,10 @@ define_error! {
         ParseLength
             [ DisplayOnly<TryFromIntError> ]
             | _ | { "error parsing encoded length" },
+
+        Io
+            [ DisplayOnly<std::io::Error> ]
+            | _ | { "I/O error" },
     }
 }
 
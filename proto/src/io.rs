@@ -0,0 +1,91 @@
+//! Encoding into writers and reading length delimiters from readers
+
+use crate::Error;
+use bytes::{buf::UninitSlice, BufMut};
+use std::io::{self, Read, Write};
+
+/// Maximum length of a varint, in bytes
+pub(crate) const MAX_VARINT_LENGTH: usize = 10;
+
+/// Read a length delimiter, ie. a varint, reading no further than its last byte
+pub(crate) fn read_length_delimiter<R: Read>(reader: &mut R) -> Result<u64, Error> {
+    let mut buf = [0; MAX_VARINT_LENGTH];
+    for i in 0..MAX_VARINT_LENGTH {
+        reader.read_exact(&mut buf[i..=i]).map_err(Error::io)?;
+        if buf[i] < 0x80 {
+            return decode_length_delimiter(&buf[..=i]);
+        }
+    }
+    decode_length_delimiter(&buf)
+}
+
+/// Decode the varint of a length delimiter, failing if it is longer than
+/// [`MAX_VARINT_LENGTH`] bytes
+pub(crate) fn decode_length_delimiter(mut buf: &[u8]) -> Result<u64, Error> {
+    prost::encoding::decode_varint(&mut buf).map_err(Error::decode_message)
+}
+
+/// Size of the buffer through which messages are encoded into writers
+const WRITER_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Buffer encoding messages into a writer, through a buffer of
+/// [`WRITER_BUFFER_SIZE`] bytes which is written into the writer whenever it
+/// is full, so that messages are never buffered whole.
+pub(crate) struct WriterBuf<'a, W> {
+    writer: &'a mut W,
+    buf: Box<[u8]>,
+    len: usize,
+    /// First error of the writer, after which the rest of the message is
+    /// discarded
+    error: Option<io::Error>,
+}
+
+impl<'a, W: Write> WriterBuf<'a, W> {
+    pub(crate) fn new(writer: &'a mut W) -> Self {
+        Self {
+            writer,
+            buf: vec![0; WRITER_BUFFER_SIZE].into_boxed_slice(),
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// Write the rest of the buffer into the writer, and return the first
+    /// error of the writer, if any
+    pub(crate) fn finish(mut self) -> Result<(), Error> {
+        self.write_buf();
+        self.error.map_or(Ok(()), |e| Err(Error::io(e)))
+    }
+
+    fn write_buf(&mut self) {
+        if self.error.is_none() {
+            self.error = self.writer.write_all(&self.buf[..self.len]).err();
+        }
+        self.len = 0;
+    }
+}
+
+// `BufMut` is unsafe to implement, as its users may write into the chunk
+// without initializing it: the buffer is always initialized, and is never
+// advanced past its end.
+#[allow(unsafe_code)]
+unsafe impl<W: Write> BufMut for WriterBuf<'_, W> {
+    fn remaining_mut(&self) -> usize {
+        usize::MAX
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.buf.len() - self.len,
+            "advancing past the end of the buffer"
+        );
+        self.len += cnt;
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        if self.len == self.buf.len() {
+            self.write_buf();
+        }
+        UninitSlice::new(&mut self.buf[self.len..])
+    }
+}
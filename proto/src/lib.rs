@@ -1,8 +1,9 @@
 //! tendermint-proto library gives the developer access to the Tendermint proto-defined structs.
 
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![deny(warnings, trivial_casts, trivial_numeric_casts, unused_import_braces)]
 #![allow(clippy::large_enum_variant)]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![doc(html_root_url = "https://docs.rs/tendermint-proto/0.21.0")]
 
 /// Built-in prost_types with slight customization to enable JSON-encoding
@@ -15,7 +16,11 @@ pub mod google {
     }
 }
 
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod async_io;
 mod error;
+mod io;
 #[allow(warnings)]
 mod tendermint;
 
@@ -27,6 +32,7 @@ use prost::encoding::encoded_len_varint;
 use prost::Message;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Display;
+use std::io::{Read, Write};
 
 pub mod serializers;
 
@@ -202,4 +208,44 @@ where
     fn decode_length_delimited_vec(v: &[u8]) -> Result<Self, Error> {
         Self::decode_length_delimited(v)
     }
+
+    /// Encode into a writer in Protobuf format.
+    ///
+    /// The message is encoded directly into the writer, through a buffer of
+    /// 8 KiB rather than a buffer of its size.
+    fn encode_to_writer<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut buf = io::WriterBuf::new(writer);
+        self.encode(&mut buf)?;
+        buf.finish()
+    }
+
+    /// Encode with a length-delimiter into a writer in Protobuf format.
+    ///
+    /// See [`Protobuf::encode_to_writer`].
+    fn encode_length_delimited_to_writer<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut buf = io::WriterBuf::new(writer);
+        self.encode_length_delimited(&mut buf)?;
+        buf.finish()
+    }
+
+    /// Constructor that attempts to decode an instance from a reader.
+    ///
+    /// The entire reader will be consumed.
+    fn decode_from_reader<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).map_err(Error::io)?;
+        Self::decode_vec(&buf)
+    }
+
+    /// Constructor that attempts to decode a length-delimited instance from a
+    /// reader.
+    ///
+    /// Nothing is read past the end of the instance, so that successive
+    /// length-delimited instances can be decoded from the same reader.
+    fn decode_length_delimited_from_reader<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let len = io::read_length_delimiter(reader)?;
+        let mut buf = vec![0; usize::try_from(len).map_err(Error::parse_length)?];
+        reader.read_exact(&mut buf).map_err(Error::io)?;
+        Self::decode_vec(&buf)
+    }
 }
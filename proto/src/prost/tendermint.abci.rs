@@ -110,15 +110,15 @@ pub struct RequestBeginBlock {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RequestCheckTx {
-    #[prost(bytes="vec", tag="1")]
-    pub tx: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="bytes", tag="1")]
+    pub tx: ::prost::bytes::Bytes,
     #[prost(enumeration="CheckTxType", tag="2")]
     pub r#type: i32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RequestDeliverTx {
-    #[prost(bytes="vec", tag="1")]
-    pub tx: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="bytes", tag="1")]
+    pub tx: ::prost::bytes::Bytes,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RequestEndBlock {
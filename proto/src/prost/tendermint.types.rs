@@ -131,9 +131,9 @@ pub struct Data {
     /// Txs that will be applied by state @ block.Height+1.
     /// NOTE: not all txs here are valid.  We're just agreeing on the order first.
     /// This means that block.AppHash does not include these txs.
-    #[prost(bytes="vec", repeated, tag="1")]
+    #[prost(bytes="bytes", repeated, tag="1")]
     #[serde(with = "crate::serializers::txs")]
-    pub txs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    pub txs: ::prost::alloc::vec::Vec<::prost::bytes::Bytes>,
 }
 /// Vote represents a prevote, precommit, or commit vote from validators for
 /// consensus.
@@ -172,6 +172,7 @@ pub struct Commit {
     #[serde(with = "crate::serializers::from_str")]
     pub height: i64,
     #[prost(int32, tag="2")]
+    #[serde(with = "crate::serializers::int_or_string")]
     pub round: i32,
     #[prost(message, optional, tag="3")]
     pub block_id: ::core::option::Option<BlockId>,
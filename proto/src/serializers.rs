@@ -32,6 +32,11 @@
 //! Available serializers:
 //! i64                  <-> string:               #[serde(with="serializers::from_str")]
 //! u64                  <-> string:               #[serde(with="serializers::from_str")]
+//! u64                  <-> string or integer:    #[serde(with="serializers::string_or_int")]
+//! i32                  <-> integer or string:    #[serde(with="serializers::int_or_string")]
+//! Option<T>            <-> string, "" for None:  #[serde(with="serializers::empty_string_option")]
+//! Vec<T>               <-> comma-separated list: #[serde(with="serializers::comma_separated")]
+//! Vec<T>               <-> list or null:         #[serde(with="serializers::nullable")]
 //! std::time::Duration  <-> nanoseconds as string #[serde(with="serializers::time_duration")]
 //! Vec<u8>              <-> HexString:            #[serde(with="serializers::bytes::hexstring")]
 //! Vec<u8>              <-> Base64String:         #[serde(with="serializers::bytes::base64string")]
@@ -45,12 +50,16 @@
 // Todo: remove dead_code allowance as soon as more types are implemented
 #![allow(dead_code)]
 pub mod bytes;
+pub mod comma_separated;
+pub mod empty_string_option;
 pub mod evidence;
 pub mod from_str;
+pub mod int_or_string;
 pub mod nullable;
 pub mod optional;
 pub mod optional_from_str;
 pub mod part_set_header_total;
+pub mod string_or_int;
 pub mod time_duration;
 pub mod timestamp;
 pub mod txs;
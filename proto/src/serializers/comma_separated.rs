@@ -0,0 +1,38 @@
+//! Serialize/deserialize a list of `T`s that implement [std::str::FromStr] and
+//! [std::fmt::Display] into/from a comma-separated string, eg. the lists of
+//! peers of `config.toml`.
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Deserialize a comma-separated string into a list, the empty string being
+/// the empty list
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    let string = String::deserialize(deserializer)?;
+    if string.is_empty() {
+        return Ok(vec![]);
+    }
+
+    string
+        .split(',')
+        .map(|item| item.parse().map_err(|e| D::Error::custom(format!("{}", e))))
+        .collect()
+}
+
+/// Serialize a list into a comma-separated string
+pub fn serialize<S, T>(list: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    list.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+        .serialize(serializer)
+}
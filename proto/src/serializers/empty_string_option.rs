@@ -0,0 +1,61 @@
+//! Serialize/deserialize `Option<T>` into/from a string, where the empty string
+//! (or `null`) stands for `None`, as Go does for unset string settings.
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::str::FromStr;
+
+/// Deserialize an optional value from a string, possibly empty
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    let string = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    if string.is_empty() {
+        return Ok(None);
+    }
+
+    string
+        .parse()
+        .map(Some)
+        .map_err(|e| D::Error::custom(format!("{}", e)))
+}
+
+/// Serialize an optional value, `None` being serialized as the empty string
+pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    match value {
+        Some(value) => value.serialize(serializer),
+        None => "".serialize(serializer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Settings {
+        #[serde(with = "super")]
+        value: Option<u64>,
+    }
+
+    #[test]
+    fn empty_strings_are_none() {
+        for json in &[r#"{"value":""}"#, r#"{"value":null}"#] {
+            let settings: Settings = serde_json::from_str(json).unwrap();
+            assert_eq!(settings, Settings { value: None });
+        }
+        assert_eq!(
+            serde_json::to_string(&Settings { value: None }).unwrap(),
+            r#"{"value":""}"#
+        );
+
+        let settings: Settings = serde_json::from_str(r#"{"value":"42"}"#).unwrap();
+        assert_eq!(settings, Settings { value: Some(42) });
+        assert!(serde_json::from_str::<Settings>(r#"{"value":"x"}"#).is_err());
+    }
+}
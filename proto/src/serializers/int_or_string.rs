@@ -0,0 +1,38 @@
+//! Serialize integers as plain integers, and deserialize them from either
+//! plain integers or strings, as Tendermint versions older than v0.34 encode
+//! some of the integers which are now plain integers.
+use serde::{Serialize, Serializer};
+
+pub use super::string_or_int::deserialize;
+
+/// Serialize T as a plain integer
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    value.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Round {
+        #[serde(with = "super")]
+        round: i32,
+    }
+
+    #[test]
+    fn integers_or_strings() {
+        for json in &[r#"{"round":"2"}"#, r#"{"round":2}"#] {
+            let round: Round = serde_json::from_str(json).unwrap();
+            assert_eq!(round, Round { round: 2 });
+        }
+        assert_eq!(
+            serde_json::to_string(&Round { round: 2 }).unwrap(),
+            r#"{"round":2}"#
+        );
+    }
+}
@@ -1,4 +1,7 @@
 //! Serialize/deserialize `nil`able type into T, where nil turns into the default impl.
+//!
+//! In particular, lists which Go serializes as `null` when empty deserialize
+//! into empty `Vec`s, and empty `Vec`s serialize into `null`.
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Deserialize Option<T>
@@ -21,3 +24,33 @@ where
     }
     value.serialize(serializer)
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Default, PartialEq, Deserialize, Serialize)]
+    struct Results {
+        #[serde(default, with = "super")]
+        events: Vec<String>,
+    }
+
+    #[test]
+    fn nullable_lists() {
+        for json in &[r#"{"events":null}"#, r#"{}"#] {
+            let results: Results = serde_json::from_str(json).unwrap();
+            assert_eq!(results, Results::default());
+        }
+        assert_eq!(
+            serde_json::to_string(&Results::default()).unwrap(),
+            r#"{"events":null}"#
+        );
+
+        let results = Results {
+            events: vec!["transfer".to_string()],
+        };
+        let json = serde_json::to_string(&results).unwrap();
+        assert_eq!(json, r#"{"events":["transfer"]}"#);
+        assert_eq!(serde_json::from_str::<Results>(&json).unwrap(), results);
+    }
+}
@@ -0,0 +1,61 @@
+//! Serialize integers into strings, as Tendermint does for 64-bit integers,
+//! and deserialize them from either strings or plain integers, as written by
+//! hand in configuration files or by older versions of Tendermint.
+use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrInt<T> {
+    String(String),
+    Int(T),
+}
+
+/// Deserialize T from a string or an integer
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    <T as FromStr>::Err: Display,
+{
+    match StringOrInt::<T>::deserialize(deserializer)? {
+        StringOrInt::String(s) => s
+            .parse()
+            .map_err(|e| D::Error::custom(format!("{}: {:?}", e, s))),
+        StringOrInt::Int(value) => Ok(value),
+    }
+}
+
+/// Serialize T into a string
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Counter {
+        #[serde(with = "super")]
+        count: i64,
+    }
+
+    #[test]
+    fn strings_or_integers() {
+        for json in &[r#"{"count":"-7"}"#, r#"{"count":-7}"#] {
+            let counter: Counter = serde_json::from_str(json).unwrap();
+            assert_eq!(counter, Counter { count: -7 });
+        }
+        assert_eq!(
+            serde_json::to_string(&Counter { count: -7 }).unwrap(),
+            r#"{"count":"-7"}"#
+        );
+        assert!(serde_json::from_str::<Counter>(r#"{"count":"seven"}"#).is_err());
+    }
+}
@@ -1,9 +1,10 @@
-//! Serialize/deserialize Vec<Vec<u8>> type from and into transactions (Base64String array).
+//! Serialize/deserialize Vec<Bytes> type from and into transactions (Base64String array).
+use bytes::Bytes;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use subtle_encoding::base64;
 
-/// Deserialize transactions into Vec<Vec<u8>>
-pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Vec<u8>>, D::Error>
+/// Deserialize transactions into Vec<Bytes>
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Bytes>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -17,12 +18,16 @@ where
     }
     value_vec_base64string
         .into_iter()
-        .map(|s| base64::decode(&s).map_err(serde::de::Error::custom))
+        .map(|s| {
+            base64::decode(&s)
+                .map(Bytes::from)
+                .map_err(serde::de::Error::custom)
+        })
         .collect()
 }
 
-/// Serialize from Vec<Vec<u8>> into transactions
-pub fn serialize<S>(value: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error>
+/// Serialize from Vec<Bytes> into transactions
+pub fn serialize<S>(value: &[Bytes], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -20,7 +20,7 @@ impl TryFrom<RawBlockId> for BlockId {
         Ok(BlockId {
             hash: String::from_utf8(value.hash)
                 .map_err(|_| "Could not convert vector to string")?,
-            part_set_header_exists: value.part_set_header != None,
+            part_set_header_exists: value.part_set_header.is_some(),
         })
     }
 }
@@ -109,3 +109,100 @@ pub fn protobuf_struct_conveniences_example() {
     let new_domain_type = BlockId::decode_length_delimited_vec(&wire).unwrap();
     assert_eq!(my_domain_type, new_domain_type);
 }
+
+#[test]
+pub fn protobuf_struct_streaming_example() {
+    let my_domain_type = BlockId {
+        hash: "Hello world!".to_string(),
+        part_set_header_exists: false,
+    };
+    let other_domain_type = BlockId {
+        hash: "Bye!".to_string(),
+        part_set_header_exists: true,
+    };
+
+    let mut wire = vec![];
+    my_domain_type.encode_to_writer(&mut wire).unwrap();
+    assert_eq!(wire, my_domain_type.encode_vec().unwrap());
+    let new_domain_type = BlockId::decode_from_reader(&mut wire.as_slice()).unwrap();
+    assert_eq!(my_domain_type, new_domain_type);
+
+    // Successive length-delimited messages are read one at a time
+    let mut wire = vec![];
+    my_domain_type
+        .encode_length_delimited_to_writer(&mut wire)
+        .unwrap();
+    other_domain_type
+        .encode_length_delimited_to_writer(&mut wire)
+        .unwrap();
+    let mut reader = wire.as_slice();
+    assert_eq!(
+        BlockId::decode_length_delimited_from_reader(&mut reader).unwrap(),
+        my_domain_type
+    );
+    assert_eq!(
+        BlockId::decode_length_delimited_from_reader(&mut reader).unwrap(),
+        other_domain_type
+    );
+    assert!(reader.is_empty());
+
+    // Truncated messages are rejected
+    let wire = my_domain_type.encode_length_delimited_vec().unwrap();
+    assert!(BlockId::decode_length_delimited_from_reader(&mut &wire[..wire.len() - 1]).is_err());
+
+    // Messages larger than the buffer of the writer are written in chunks
+    let large_domain_type = BlockId {
+        hash: "a".repeat(100_000),
+        part_set_header_exists: true,
+    };
+    let mut wire = vec![];
+    large_domain_type
+        .encode_length_delimited_to_writer(&mut wire)
+        .unwrap();
+    assert_eq!(
+        wire,
+        large_domain_type.encode_length_delimited_vec().unwrap()
+    );
+    assert_eq!(
+        BlockId::decode_length_delimited_from_reader(&mut wire.as_slice()).unwrap(),
+        large_domain_type
+    );
+
+    // Errors of the writer are reported
+    let mut full = [0; 1000];
+    assert!(large_domain_type
+        .encode_to_writer(&mut full.as_mut())
+        .is_err());
+}
+
+#[cfg(feature = "async")]
+#[test]
+pub fn protobuf_struct_async_streaming_example() {
+    use futures::executor::block_on;
+    use tendermint_proto::async_io;
+
+    let my_domain_type = BlockId {
+        hash: "Hello world!".to_string(),
+        part_set_header_exists: true,
+    };
+
+    let mut wire = vec![];
+    block_on(async_io::encode_length_delimited_to_writer(
+        &my_domain_type,
+        &mut wire,
+    ))
+    .unwrap();
+    assert_eq!(wire, my_domain_type.encode_length_delimited_vec().unwrap());
+
+    let mut reader = wire.as_slice();
+    let new_domain_type: BlockId =
+        block_on(async_io::decode_length_delimited_from_reader(&mut reader)).unwrap();
+    assert_eq!(my_domain_type, new_domain_type);
+    assert!(reader.is_empty());
+
+    let mut wire = vec![];
+    block_on(async_io::encode_to_writer(&my_domain_type, &mut wire)).unwrap();
+    let new_domain_type: BlockId =
+        block_on(async_io::decode_from_reader(&mut wire.as_slice())).unwrap();
+    assert_eq!(my_domain_type, new_domain_type);
+}
@@ -1,7 +1,9 @@
 //! Tendermint RPC client.
 
+mod batch;
+pub use batch::Batch;
 mod subscription;
-pub use subscription::{Subscription, SubscriptionClient};
+pub use subscription::{Subscription, SubscriptionClient, TypedSubscription};
 pub mod sync;
 
 mod transport;
@@ -12,16 +14,25 @@ pub use transport::http::{HttpClient, HttpClientUrl};
 #[cfg(feature = "websocket-client")]
 pub use transport::websocket::{WebSocketClient, WebSocketClientDriver, WebSocketClientUrl};
 
+use crate::endpoint::blockchain::MAX_BLOCKCHAIN_RANGE;
+use crate::endpoint::tx_search::MAX_TX_SEARCH_PER_PAGE;
 use crate::endpoint::validators::DEFAULT_VALIDATORS_PER_PAGE;
 use crate::endpoint::*;
+use crate::error::ErrorDetail;
 use crate::paging::Paging;
 use crate::query::Query;
 use crate::{Error, Order, SimpleRequest};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::future::Future;
 use std::time::Duration;
 use tendermint::abci::{self, Transaction};
 use tendermint::block::Height;
 use tendermint::evidence::Evidence;
+use tendermint::validator;
 use tendermint::Genesis;
 use tokio::time;
 
@@ -97,6 +108,44 @@ pub trait Client {
             .await
     }
 
+    /// `/blockchain`: get the metadata of the blocks with `min` <= `height`
+    /// <= `max`, in ascending order of height.
+    ///
+    /// The range is requested in chunks of [`MAX_BLOCKCHAIN_RANGE`] blocks,
+    /// one after the other, as the blocks of the previous chunk have been
+    /// consumed. The stream ends at `max`, at the last block of the chain if
+    /// it is lower, or after an error.
+    fn blockchain_stream<H>(
+        &self,
+        min: H,
+        max: H,
+    ) -> BoxStream<'_, Result<tendermint::block::Meta, Error>>
+    where
+        H: Into<Height>,
+        Self: Sized + Sync,
+    {
+        let chunks = BlockchainChunks {
+            next_height: min.into().value().max(1),
+            max_height: max.into().value(),
+            metas: VecDeque::new(),
+        };
+
+        stream::unfold(chunks, move |mut chunks| async move {
+            while chunks.metas.is_empty() && chunks.next_height <= chunks.max_height {
+                let (min, max) = chunks.next_range();
+                match self.blockchain(min, max).await {
+                    Ok(response) => chunks.add(response),
+                    Err(e) => {
+                        chunks.next_height = chunks.max_height + 1;
+                        return Some((Err(e), chunks));
+                    }
+                }
+            }
+            chunks.metas.pop_front().map(|meta| (Ok(meta), chunks))
+        })
+        .boxed()
+    }
+
     /// `/broadcast_tx_async`: broadcast a transaction, returning immediately.
     async fn broadcast_tx_async(
         &self,
@@ -131,11 +180,31 @@ pub trait Client {
         self.perform(commit::Request::new(height.into())).await
     }
 
+    /// `/consensus_params`: get the consensus parameters at a given height.
+    async fn consensus_params<H>(&self, height: H) -> Result<consensus_params::Response, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        self.perform(consensus_params::Request::new(height.into()))
+            .await
+    }
+
+    /// `/consensus_params`: get the latest consensus parameters.
+    async fn latest_consensus_params(&self) -> Result<consensus_params::Response, Error> {
+        self.perform(consensus_params::Request::default()).await
+    }
+
     /// `/consensus_state`: get current consensus state
     async fn consensus_state(&self) -> Result<consensus_state::Response, Error> {
         self.perform(consensus_state::Request::new()).await
     }
 
+    /// `/dump_consensus_state`: get the full consensus state, along with the
+    /// consensus states of the peers of the node
+    async fn dump_consensus_state(&self) -> Result<dump_consensus_state::Response, Error> {
+        self.perform(dump_consensus_state::Request::new()).await
+    }
+
     // TODO(thane): Simplify once validators endpoint removes pagination.
     /// `/validators`: get validators a given height.
     async fn validators<H>(&self, height: H, paging: Paging) -> Result<validators::Response, Error>
@@ -171,8 +240,10 @@ pub trait Client {
                             Some(per_page),
                         ))
                         .await?;
+                    // Stop on empty pages too, in case the total count is off
+                    let empty_page = response.validators.is_empty();
                     validators.extend(response.validators);
-                    if validators.len() as i32 == response.total {
+                    if empty_page || validators.len() as i32 >= response.total {
                         return Ok(validators::Response::new(
                             response.block_height,
                             validators,
@@ -185,6 +256,19 @@ pub trait Client {
         }
     }
 
+    /// `/validators`: get the complete validator set at a given height,
+    /// walking through all the pages of validators.
+    ///
+    /// The set has no proposer, which is not returned by the endpoint: see
+    /// the `proposer_address` of the header at the same height.
+    async fn all_validators<H>(&self, height: H) -> Result<validator::Set, Error>
+    where
+        H: Into<Height> + Send,
+    {
+        let response = self.validators(height, Paging::All).await?;
+        validator::Set::without_proposer(response.validators).map_err(Error::tendermint)
+    }
+
     /// `/commit`: get the latest block commit
     async fn latest_commit(&self) -> Result<commit::Response, Error> {
         self.perform(commit::Request::default()).await
@@ -203,6 +287,38 @@ pub trait Client {
         Ok(self.perform(genesis::Request).await?.genesis)
     }
 
+    /// `/genesis_chunked`: get the chunk of the genesis file with the given
+    /// index.
+    async fn genesis_chunk(&self, chunk: u64) -> Result<genesis_chunked::Response, Error> {
+        self.perform(genesis_chunked::Request::new(chunk)).await
+    }
+
+    /// `/genesis_chunked`: get the genesis file by fetching and reassembling
+    /// all of its chunks, for genesis files too large to be returned at once
+    /// by `/genesis`.
+    async fn genesis_chunked<AppState>(&self) -> Result<Genesis<AppState>, Error>
+    where
+        AppState: DeserializeOwned + Default,
+    {
+        let first = self.genesis_chunk(0).await?;
+        if first.chunk != 0 || first.total == 0 {
+            return Err(Error::invalid_genesis_chunk(first.chunk, first.total));
+        }
+
+        let total = first.total;
+        let mut data = first.data;
+
+        for chunk in 1..total {
+            let response = self.genesis_chunk(chunk).await?;
+            if response.chunk != chunk || response.total != total {
+                return Err(Error::invalid_genesis_chunk(response.chunk, response.total));
+            }
+            data.extend(response.data);
+        }
+
+        serde_json::from_slice(&data).map_err(Error::serde)
+    }
+
     /// `/net_info`: obtain information about P2P and other network connections.
     async fn net_info(&self) -> Result<net_info::Response, Error> {
         self.perform(net_info::Request).await
@@ -214,6 +330,18 @@ pub trait Client {
         self.perform(status::Request).await
     }
 
+    /// `/unconfirmed_txs`: get at most the given number of transactions in
+    /// the mempool, 30 by default.
+    async fn unconfirmed_txs(&self, limit: Option<u8>) -> Result<unconfirmed_txs::Response, Error> {
+        self.perform(unconfirmed_txs::Request::new(limit)).await
+    }
+
+    /// `/num_unconfirmed_txs`: get the number and size of the transactions in
+    /// the mempool.
+    async fn num_unconfirmed_txs(&self) -> Result<num_unconfirmed_txs::Response, Error> {
+        self.perform(num_unconfirmed_txs::Request).await
+    }
+
     /// `/broadcast_evidence`: broadcast an evidence.
     async fn broadcast_evidence(&self, e: Evidence) -> Result<evidence::Response, Error> {
         self.perform(evidence::Request::new(e)).await
@@ -237,26 +365,168 @@ pub trait Client {
             .await
     }
 
-    /// Poll the `/health` endpoint until it returns a successful result or
-    /// the given `timeout` has elapsed.
+    /// `/tx_search`: search for transactions with their results, walking
+    /// through all the pages of results.
+    ///
+    /// The pages are requested one after the other, as the transactions of
+    /// the previous page have been consumed, with `per_page` transactions
+    /// per page (between 1 and [`MAX_TX_SEARCH_PER_PAGE`]). The stream ends
+    /// after the total number of transactions reported by the endpoint, or
+    /// after an error.
+    fn tx_search_stream(
+        &self,
+        query: Query,
+        prove: bool,
+        per_page: u8,
+        order: Order,
+    ) -> BoxStream<'_, Result<tx::Response, Error>>
+    where
+        Self: Sized + Sync,
+    {
+        let per_page = per_page.clamp(1, MAX_TX_SEARCH_PER_PAGE);
+        let pages = TxSearchPages {
+            next_page: 1,
+            fetched: 0,
+            txs: VecDeque::new(),
+            done: false,
+        };
+
+        stream::unfold(pages, move |mut pages| {
+            let (query, order) = (query.clone(), order.clone());
+            async move {
+                while pages.txs.is_empty() && !pages.done {
+                    let response = self
+                        .tx_search(
+                            query.clone(),
+                            prove,
+                            pages.next_page,
+                            per_page,
+                            order.clone(),
+                        )
+                        .await;
+                    match response {
+                        Ok(response) => pages.add(response),
+                        Err(e) => {
+                            pages.done = true;
+                            return Some((Err(e), pages));
+                        }
+                    }
+                }
+                pages.txs.pop_front().map(|tx| (Ok(tx), pages))
+            }
+        })
+        .boxed()
+    }
+
+    /// Poll the `/health` endpoint, with exponential backoff, until it
+    /// returns a successful result or the given `timeout` has elapsed.
     async fn wait_until_healthy<T>(&self, timeout: T) -> Result<(), Error>
     where
         T: Into<Duration> + Send,
     {
         let timeout = timeout.into();
-        let poll_interval = Duration::from_millis(200);
-        let mut attempts_remaining = timeout.as_millis() / poll_interval.as_millis();
+        poll_with_backoff(timeout, || async { self.health().await.ok() })
+            .await
+            .ok_or_else(|| Error::timeout(timeout))
+    }
 
-        while self.health().await.is_err() {
-            if attempts_remaining == 0 {
-                return Err(Error::timeout(timeout));
-            }
+    /// Poll the `/status` endpoint, with exponential backoff, until the node
+    /// has caught up with the chain or the given `timeout` has elapsed,
+    /// returning its status.
+    ///
+    /// Failing requests are retried, eg. while the node is still starting.
+    async fn wait_until_synced<T>(&self, timeout: T) -> Result<status::Response, Error>
+    where
+        T: Into<Duration> + Send,
+    {
+        let timeout = timeout.into();
+        poll_with_backoff(timeout, || async {
+            self.status()
+                .await
+                .ok()
+                .filter(|status| !status.sync_info.catching_up)
+        })
+        .await
+        .ok_or_else(|| Error::sync_timeout(timeout))
+    }
+
+    /// Broadcast a transaction with `/broadcast_tx_sync`, then poll `/tx`,
+    /// with exponential backoff, until the transaction is committed or the
+    /// given `timeout` has elapsed, returning it along with the result of its
+    /// `DeliverTx`.
+    ///
+    /// Only the errors of the node not finding the transaction, as it is not
+    /// committed yet, are retried: other errors are returned.
+    ///
+    /// Unlike `/broadcast_tx_commit`, this neither depends on the
+    /// `timeout_broadcast_tx_commit` of the node nor holds one of its
+    /// connections, but requires the node to index transactions.
+    async fn broadcast_and_confirm<T>(
+        &self,
+        tx: Transaction,
+        timeout: T,
+    ) -> Result<tx::Response, Error>
+    where
+        T: Into<Duration> + Send,
+    {
+        let timeout = timeout.into();
+        let deadline = time::Instant::now() + timeout;
 
-            attempts_remaining -= 1;
-            time::sleep(poll_interval).await;
+        let response = time::timeout(timeout, self.broadcast_tx_sync(tx))
+            .await
+            .map_err(|_| Error::timeout(timeout))??;
+        if response.code.is_err() {
+            return Err(Error::tx_rejected(
+                response.code.value(),
+                response.log.to_string(),
+            ));
         }
 
-        Ok(())
+        let remaining = deadline.saturating_duration_since(time::Instant::now());
+        poll_with_backoff(remaining, || async {
+            match self.tx(response.hash, false).await {
+                Err(e) if is_tx_not_found(&e) => None,
+                result => Some(result),
+            }
+        })
+        .await
+        .unwrap_or_else(|| {
+            Err(Error::tx_confirmation_timeout(
+                response.hash.to_string(),
+                timeout,
+            ))
+        })
+    }
+
+    /// Start a batch of requests of the same type, eg. `/block` requests for
+    /// a range of heights, to be performed in a single JSON-RPC batch call.
+    fn batch<R>(&self) -> Batch<'_, Self, R>
+    where
+        Self: Sized + Sync,
+        R: SimpleRequest,
+        R::Response: Send,
+    {
+        Batch::new(self)
+    }
+
+    /// Perform the given requests against the RPC endpoint, in a single
+    /// JSON-RPC batch call if the client supports it, returning the result of
+    /// each request in the same order.
+    ///
+    /// By default, the requests are performed one after the other.
+    async fn perform_batch<R>(
+        &self,
+        requests: Vec<R>,
+    ) -> Result<Vec<Result<R::Response, Error>>, Error>
+    where
+        R: SimpleRequest,
+        R::Response: Send,
+    {
+        let mut results = Vec::with_capacity(requests.len());
+        for request in requests {
+            results.push(self.perform(request).await);
+        }
+        Ok(results)
     }
 
     /// Perform a request against the RPC endpoint
@@ -264,3 +534,126 @@ pub trait Client {
     where
         R: SimpleRequest;
 }
+
+/// Delay before the second attempt of [`poll_with_backoff`], doubled after
+/// each attempt
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Upper bound of the delays between the attempts of [`poll_with_backoff`]
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Call `attempt` until it returns some value or the given `timeout` has
+/// elapsed, with exponentially increasing delays between the attempts.
+///
+/// An attempt still pending when the timeout elapses, eg. a request to an
+/// unresponsive node, is cancelled.
+async fn poll_with_backoff<T, F, Fut>(timeout: Duration, mut attempt: F) -> Option<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    let deadline = time::Instant::now() + timeout;
+    let mut delay = MIN_POLL_INTERVAL;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(time::Instant::now());
+        match time::timeout(remaining, attempt()).await {
+            Ok(Some(value)) => return Some(value),
+            Ok(None) => {}
+            Err(_) => return None,
+        }
+
+        let now = time::Instant::now();
+        if now >= deadline {
+            return None;
+        }
+
+        time::sleep(delay.min(deadline - now)).await;
+        delay = (delay * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+/// Whether the given error is the one of a node not finding a transaction, eg.
+/// because it is not committed yet, as reported by Tendermint with
+/// `tx (<hash>) not found` in the data of the response error.
+fn is_tx_not_found(error: &Error) -> bool {
+    match error.detail() {
+        ErrorDetail::Response(e) => matches!(
+            e.source.data(),
+            Some(data) if data.starts_with("tx") && data.ends_with("not found")
+        ),
+        _ => false,
+    }
+}
+
+/// The state of the chunks walked through by [`Client::blockchain_stream`]
+struct BlockchainChunks {
+    next_height: u64,
+    max_height: u64,
+    metas: VecDeque<tendermint::block::Meta>,
+}
+
+impl BlockchainChunks {
+    /// Last height of the next chunk
+    fn chunk_end(&self) -> u64 {
+        self.max_height
+            .min(self.next_height.saturating_add(MAX_BLOCKCHAIN_RANGE - 1))
+    }
+
+    /// The range of the next chunk, made of valid heights as it is bounded by
+    /// the given maximum height
+    fn next_range(&self) -> (Height, Height) {
+        (
+            Height::try_from(self.next_height).unwrap(),
+            Height::try_from(self.chunk_end()).unwrap(),
+        )
+    }
+
+    fn add(&mut self, response: blockchain::Response) {
+        let chunk_end = self.chunk_end();
+        self.next_height = chunk_end + 1;
+        // Don't request the blocks beyond the end of the chain, nor go on
+        // after a chunk without any block
+        self.max_height = if response.block_metas.is_empty() {
+            chunk_end
+        } else {
+            self.max_height.min(response.last_height.value())
+        };
+        // Blocks are returned in descending order of height
+        self.metas.extend(response.block_metas.into_iter().rev());
+    }
+}
+
+/// The state of the pages walked through by [`Client::tx_search_stream`]
+struct TxSearchPages {
+    next_page: u32,
+    fetched: u32,
+    txs: VecDeque<tx::Response>,
+    done: bool,
+}
+
+impl TxSearchPages {
+    fn add(&mut self, response: tx_search::Response) {
+        self.next_page += 1;
+        self.fetched += response.txs.len() as u32;
+        // Stop on empty pages too, in case the total count is off
+        self.done = response.txs.is_empty() || self.fetched >= response.total_count;
+        self.txs.extend(response.txs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn poll_with_backoff_cancels_pending_attempts() {
+        let timeout = Duration::from_millis(200);
+        let start = time::Instant::now();
+
+        let polled = poll_with_backoff(timeout, futures::future::pending::<Option<()>>).await;
+
+        assert!(polled.is_none());
+        assert!(start.elapsed() < 2 * timeout);
+    }
+}
@@ -0,0 +1,82 @@
+//! Batches of requests, performed in a single JSON-RPC batch call.
+
+use crate::{Client, Error, SimpleRequest};
+
+/// A batch of requests of the same type, eg. `/block` requests for a range of
+/// heights, performed in a single JSON-RPC batch call by [`Batch::perform`].
+///
+/// Batches are created with [`Client::batch`].
+///
+/// ## Examples
+///
+/// ```rust,ignore
+/// use tendermint_rpc::{endpoint::block, Client, HttpClient};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let client = HttpClient::new("http://127.0.0.1:26657").unwrap();
+///
+///     let responses = client
+///         .batch()
+///         .requests((1_u32..=50).map(|height| block::Request::new(height.into())))
+///         .perform()
+///         .await
+///         .unwrap();
+///
+///     for response in responses {
+///         println!("Got block: {:?}", response.unwrap().block);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+#[must_use]
+pub struct Batch<'a, C, R> {
+    client: &'a C,
+    requests: Vec<R>,
+}
+
+impl<'a, C, R> Batch<'a, C, R>
+where
+    C: Client + Sync,
+    R: SimpleRequest,
+    R::Response: Send,
+{
+    /// Create an empty batch of requests for the given client.
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Add the given request to the batch.
+    pub fn request(mut self, request: R) -> Self {
+        self.requests.push(request);
+        self
+    }
+
+    /// Add the given requests to the batch.
+    pub fn requests(mut self, requests: impl IntoIterator<Item = R>) -> Self {
+        self.requests.extend(requests);
+        self
+    }
+
+    /// The number of requests in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether the batch has no requests.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Perform the requests of the batch, returning the result of each
+    /// request in the order in which it was added.
+    ///
+    /// If the batch call as a whole fails, eg. because the connection to the
+    /// RPC endpoint fails, an error is returned instead.
+    pub async fn perform(self) -> Result<Vec<Result<R::Response, Error>>, Error> {
+        self.client.perform_batch(self.requests).await
+    }
+}
@@ -112,10 +112,21 @@ enum ClientRequest {
     },
     /// Get the commit for the given height.
     Commit { height: u32 },
+    /// Get the consensus parameters at a given height.
+    ConsensusParams {
+        /// The height at which to query the consensus parameters, if not the
+        /// latest one.
+        #[structopt(long)]
+        height: Option<u32>,
+    },
     /// Get the current consensus state.
     ConsensusState,
+    /// Get the full consensus state, along with the ones of the node's peers.
+    DumpConsensusState,
     /// Get the node's genesis data.
     Genesis,
+    /// Get the node's genesis data in chunks, for large genesis files.
+    GenesisChunked,
     /// Get the node's health.
     Health,
     /// Request the latest block.
@@ -126,6 +137,8 @@ enum ClientRequest {
     LatestCommit,
     /// Obtain information about the P2P stack and other network connections.
     NetInfo,
+    /// Get the number and total size of the transactions in the mempool.
+    NumUnconfirmedTxs,
     /// Get Tendermint status (node info, public key, latest block hash, etc.).
     Status,
     /// Fetch a transaction by way of its hash.
@@ -151,6 +164,12 @@ enum ClientRequest {
         #[structopt(long)]
         prove: bool,
     },
+    /// Get the transactions in the mempool.
+    UnconfirmedTxs {
+        /// The maximum number of transactions to retrieve.
+        #[structopt(long)]
+        limit: Option<u8>,
+    },
     /// Get the validators at the given height.
     Validators {
         /// The height at which to query the validators.
@@ -337,18 +356,35 @@ where
         ClientRequest::LatestCommit => {
             serde_json::to_string_pretty(&client.latest_commit().await?).map_err(Error::serde)?
         }
+        ClientRequest::ConsensusParams { height } => match height {
+            Some(height) => serde_json::to_string_pretty(&client.consensus_params(height).await?),
+            None => serde_json::to_string_pretty(&client.latest_consensus_params().await?),
+        }
+        .map_err(Error::serde)?,
         ClientRequest::ConsensusState => {
             serde_json::to_string_pretty(&client.consensus_state().await?).map_err(Error::serde)?
         }
+        ClientRequest::DumpConsensusState => {
+            serde_json::to_string_pretty(&client.dump_consensus_state().await?)
+                .map_err(Error::serde)?
+        }
         ClientRequest::Genesis => {
             serde_json::to_string_pretty(&client.genesis().await?).map_err(Error::serde)?
         }
+        ClientRequest::GenesisChunked => {
+            serde_json::to_string_pretty(&client.genesis_chunked::<serde_json::Value>().await?)
+                .map_err(Error::serde)?
+        }
         ClientRequest::Health => {
             serde_json::to_string_pretty(&client.health().await?).map_err(Error::serde)?
         }
         ClientRequest::NetInfo => {
             serde_json::to_string_pretty(&client.net_info().await?).map_err(Error::serde)?
         }
+        ClientRequest::NumUnconfirmedTxs => {
+            serde_json::to_string_pretty(&client.num_unconfirmed_txs().await?)
+                .map_err(Error::serde)?
+        }
         ClientRequest::Status => {
             serde_json::to_string_pretty(&client.status().await?).map_err(Error::serde)?
         }
@@ -373,6 +409,10 @@ where
                 .await?,
         )
         .map_err(Error::serde)?,
+        ClientRequest::UnconfirmedTxs { limit } => {
+            serde_json::to_string_pretty(&client.unconfirmed_txs(limit).await?)
+                .map_err(Error::serde)?
+        }
         ClientRequest::Validators {
             height,
             all,
@@ -8,6 +8,8 @@ use async_trait::async_trait;
 use futures::task::{Context, Poll};
 use futures::Stream;
 use pin_project::pin_project;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
 use std::pin::Pin;
 
 /// A client that exclusively provides [`Event`] subscription capabilities,
@@ -94,4 +96,81 @@ impl Subscription {
     pub fn query(&self) -> &Query {
         &self.query
     }
+
+    /// Convert the events of this subscription into typed events, eg.
+    /// [`NewBlock`]s for a subscription to `NewBlock` events.
+    ///
+    /// [`NewBlock`]: crate::event::NewBlock
+    pub fn typed<T>(self) -> TypedSubscription<T>
+    where
+        T: TryFrom<Event, Error = Error>,
+    {
+        TypedSubscription {
+            subscription: self,
+            _event: PhantomData,
+        }
+    }
+}
+
+/// A [`Subscription`] producing typed events, converted from its [`Event`]s,
+/// eg. [`NewBlock`]s or [`TxInfo`]s for subscriptions to `NewBlock` or `Tx`
+/// events.
+///
+/// Events which cannot be converted are produced as errors.
+///
+/// ## Examples
+///
+/// ```
+/// use tendermint_rpc::event::NewBlock;
+/// use tendermint_rpc::Subscription;
+/// use futures::StreamExt;
+///
+/// /// Prints the heights of the new blocks produced by the given subscription.
+/// async fn print_heights(subs: Subscription) {
+///     let mut new_blocks = subs.typed::<NewBlock>();
+///     while let Some(res) = new_blocks.next().await {
+///         println!("New block at height {}", res.unwrap().block.header.height);
+///     }
+/// }
+/// ```
+///
+/// [`NewBlock`]: crate::event::NewBlock
+/// [`TxInfo`]: crate::event::TxInfo
+#[pin_project]
+#[derive(Debug)]
+pub struct TypedSubscription<T> {
+    #[pin]
+    subscription: Subscription,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<T> Stream for TypedSubscription<T>
+where
+    T: TryFrom<Event, Error = Error>,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .subscription
+            .poll_next(cx)
+            .map(|res| res.map(|res| res.and_then(T::try_from)))
+    }
+}
+
+impl<T> TypedSubscription<T> {
+    /// Return the ID of the underlying subscription.
+    pub fn id(&self) -> &str {
+        self.subscription.id()
+    }
+
+    /// Return the query of the underlying subscription.
+    pub fn query(&self) -> &Query {
+        self.subscription.query()
+    }
+
+    /// Get back the underlying subscription, producing untyped events.
+    pub fn into_inner(self) -> Subscription {
+        self.subscription
+    }
 }
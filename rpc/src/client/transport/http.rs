@@ -4,7 +4,9 @@ use crate::client::Client;
 use crate::{Error, Scheme, SimpleRequest, Url};
 use async_trait::async_trait;
 use std::convert::{TryFrom, TryInto};
+use std::future::Future;
 use std::str::FromStr;
+use std::time::Duration;
 use tendermint::net;
 
 /// A JSON-RPC/HTTP Tendermint RPC client (implements [`crate::Client`]).
@@ -16,9 +18,17 @@ use tendermint::net;
 /// Does not provide [`crate::event::Event`] subscription facilities (see
 /// [`crate::WebSocketClient`] for a client that does).
 ///
+/// By default, requests never time out. A timeout can be set for all the
+/// requests of a client, or for some requests only, with
+/// [`HttpClient::with_timeout`]: requests which time out fail with an
+/// [`Error`] whose detail is [`ErrorDetail::RequestTimeout`].
+///
+/// [`ErrorDetail::RequestTimeout`]: crate::error::ErrorDetail::RequestTimeout
+///
 /// ## Examples
 ///
 /// ```rust,ignore
+/// use std::time::Duration;
 /// use tendermint_rpc::{HttpClient, Client};
 ///
 /// #[tokio::main]
@@ -31,11 +41,20 @@ use tendermint::net;
 ///         .unwrap();
 ///
 ///     println!("Got ABCI info: {:?}", abci_info);
+///
+///     // Give up on this request after a second
+///     let status = client.with_timeout(Duration::from_secs(1))
+///         .status()
+///         .await
+///         .unwrap();
+///
+///     println!("Got status: {:?}", status);
 /// }
 /// ```
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     inner: sealed::HttpClient,
+    timeout: Option<Duration>,
 }
 
 impl HttpClient {
@@ -52,6 +71,7 @@ impl HttpClient {
             } else {
                 sealed::HttpClient::new_http(url.try_into()?)
             },
+            timeout: None,
         })
     }
 
@@ -75,8 +95,39 @@ impl HttpClient {
             } else {
                 sealed::HttpClient::new_http_proxy(url.try_into()?, proxy_url.try_into()?)?
             },
+            timeout: None,
         })
     }
+
+    /// A client sharing the connections of this one, whose requests time out
+    /// after the given duration, including the time to connect to the
+    /// endpoint and to read the whole response.
+    ///
+    /// This can be used to set the timeout of all the requests of a client
+    /// right after creating it, or to override it for some requests only.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeout: Some(timeout),
+        }
+    }
+
+    /// The timeout of the requests of this client, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    async fn with_optional_timeout<T>(
+        &self,
+        response: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, Error> {
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, response)
+                .await
+                .map_err(|_| Error::request_timeout(timeout))?,
+            None => response.await,
+        }
+    }
 }
 
 #[async_trait]
@@ -85,7 +136,21 @@ impl Client for HttpClient {
     where
         R: SimpleRequest,
     {
-        self.inner.perform(request).await
+        self.with_optional_timeout(self.inner.perform(request))
+            .await
+    }
+
+    /// Performs the requests in a single JSON-RPC batch call.
+    async fn perform_batch<R>(
+        &self,
+        requests: Vec<R>,
+    ) -> Result<Vec<Result<R::Response, Error>>, Error>
+    where
+        R: SimpleRequest,
+        R::Response: Send,
+    {
+        self.with_optional_timeout(self.inner.perform_batch(requests))
+            .await
     }
 }
 
@@ -101,7 +166,7 @@ impl TryFrom<Url> for HttpClientUrl {
     fn try_from(value: Url) -> Result<Self, Error> {
         match value.scheme() {
             Scheme::Http | Scheme::Https => Ok(Self(value)),
-            _ => Err(Error::invalid_url(value)),
+            _ => Err(Error::invalid_url(Box::new(value))),
         }
     }
 }
@@ -153,7 +218,7 @@ impl TryFrom<HttpClientUrl> for hyper::Uri {
 }
 
 mod sealed {
-    use crate::{Error, Response, SimpleRequest};
+    use crate::{request, response, Error, Id, Response, SimpleRequest};
     use hyper::body::Buf;
     use hyper::client::connect::Connect;
     use hyper::client::HttpConnector;
@@ -189,6 +254,29 @@ mod sealed {
             tracing::debug!("Incoming response: {}", response_body);
             R::Response::from_string(&response_body)
         }
+
+        pub async fn perform_batch<R>(
+            &self,
+            requests: Vec<R>,
+        ) -> Result<Vec<Result<R::Response, Error>>, Error>
+        where
+            R: SimpleRequest,
+        {
+            // An empty batch is an invalid request
+            if requests.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let requests: Vec<_> = requests.into_iter().map(request::Wrapper::new).collect();
+            let ids: Vec<Id> = requests.iter().map(|r| r.id().clone()).collect();
+            let request_body = serde_json::to_string_pretty(&requests).map_err(Error::serde)?;
+
+            let request = self.build_http_request(request_body)?;
+            let response = self.inner.request(request).await.map_err(Error::hyper)?;
+            let response_body = response_to_string(response).await?;
+            tracing::debug!("Incoming batch response: {}", response_body);
+            batch_results(&ids, &response_body)
+        }
     }
 
     impl<C> HyperClient<C> {
@@ -197,8 +285,14 @@ mod sealed {
             &self,
             request: R,
         ) -> Result<hyper::Request<hyper::Body>, Error> {
-            let request_body = request.into_json();
+            self.build_http_request(request.into_json())
+        }
 
+        /// Build a request with the given JSON body.
+        fn build_http_request(
+            &self,
+            request_body: String,
+        ) -> Result<hyper::Request<hyper::Body>, Error> {
             let mut request = hyper::Request::builder()
                 .method("POST")
                 .uri(&self.uri)
@@ -278,6 +372,51 @@ mod sealed {
                 HttpClient::HttpsProxy(c) => c.perform(request).await,
             }
         }
+
+        pub async fn perform_batch<R>(
+            &self,
+            requests: Vec<R>,
+        ) -> Result<Vec<Result<R::Response, Error>>, Error>
+        where
+            R: SimpleRequest,
+        {
+            match self {
+                HttpClient::Http(c) => c.perform_batch(requests).await,
+                HttpClient::Https(c) => c.perform_batch(requests).await,
+                HttpClient::HttpProxy(c) => c.perform_batch(requests).await,
+                HttpClient::HttpsProxy(c) => c.perform_batch(requests).await,
+            }
+        }
+    }
+
+    /// Match the responses to a batch call with the IDs of its requests,
+    /// as responses may come in any order.
+    ///
+    /// If the whole batch was rejected, the endpoint answers with a single
+    /// error response instead.
+    pub(super) fn batch_results<R: Response>(
+        ids: &[Id],
+        response_body: &str,
+    ) -> Result<Vec<Result<R, Error>>, Error> {
+        let mut responses: Vec<response::Wrapper<R>> = match serde_json::from_str(response_body) {
+            Ok(responses) => responses,
+            Err(e) => {
+                return Err(R::from_string(response_body)
+                    .err()
+                    .unwrap_or_else(|| Error::serde(e)))
+            }
+        };
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let index = responses
+                    .iter()
+                    .position(|response| response.id() == id)
+                    .ok_or_else(Error::mismatch_response)?;
+                responses.swap_remove(index).into_result()
+            })
+            .collect())
     }
 
     async fn response_to_string(response: hyper::Response<hyper::Body>) -> Result<String, Error> {
@@ -292,3 +431,53 @@ mod sealed {
         Ok(response_body)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::sealed::batch_results;
+    use super::*;
+    use crate::endpoint::health;
+    use crate::error::ErrorDetail;
+    use crate::Id;
+
+    #[test]
+    fn match_batch_responses() {
+        let ids = vec![
+            Id::Str("a".to_string()),
+            Id::Str("b".to_string()),
+            Id::Str("c".to_string()),
+        ];
+        let response_body = r#"[
+            {"jsonrpc": "2.0", "id": "b", "error": {"code": -32603, "message": "Internal error", "data": "failure"}},
+            {"jsonrpc": "2.0", "id": "a", "result": {}}
+        ]"#;
+
+        let results = batch_results::<health::Response>(&ids, response_body).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_err());
+
+        // The whole batch can be rejected
+        let response_body =
+            r#"{"jsonrpc": "2.0", "id": -1, "error": {"code": -32700, "message": "Parse error"}}"#;
+        assert!(batch_results::<health::Response>(&ids, response_body).is_err());
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // The endpoint accepts connections but never responds
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let client = HttpClient::new(url.as_str()).unwrap();
+        assert_eq!(client.timeout(), None);
+
+        let timeout = Duration::from_millis(100);
+        let err = client.with_timeout(timeout).health().await.unwrap_err();
+        match err.detail() {
+            ErrorDetail::RequestTimeout(e) => assert_eq!(e.duration, timeout),
+            _ => panic!("expected a timeout, got {}", err),
+        }
+    }
+}
@@ -199,7 +199,7 @@ pub trait MockRequestMatcher: Send + Sync {
 /// requests with specific methods to responses.
 ///
 /// [`MockRequestMatcher`]: trait.MockRequestMatcher.html
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct MockRequestMethodMatcher {
     mappings: HashMap<Method, Result<String, Error>>,
 }
@@ -216,14 +216,6 @@ impl MockRequestMatcher for MockRequestMethodMatcher {
     }
 }
 
-impl Default for MockRequestMethodMatcher {
-    fn default() -> Self {
-        Self {
-            mappings: HashMap::new(),
-        }
-    }
-}
-
 impl MockRequestMethodMatcher {
     /// Maps all incoming requests with the given method such that their
     /// corresponding response will be `response`.
@@ -239,12 +231,21 @@ impl MockRequestMethodMatcher {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::endpoint::{block, blockchain, genesis_chunked, tx_search, validators};
+    use crate::error::ErrorDetail;
     use crate::query::EventType;
+    use crate::{Order, ResponseError};
     use futures::StreamExt;
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::convert::TryFrom;
+    use std::marker::PhantomData;
     use std::path::PathBuf;
+    use std::time::Duration;
+    use tendermint::abci::Transaction;
     use tendermint::block::Height;
     use tendermint::chain::Id;
-    use tokio::fs;
+    use tendermint::validator;
+    use tokio::{fs, time};
 
     async fn read_json_fixture(name: &str) -> String {
         fs::read_to_string(PathBuf::from("./tests/support/").join(name.to_owned() + ".json"))
@@ -274,6 +275,16 @@ mod test {
         assert_eq!(Height::from(10_u32), block.header.height);
         assert_eq!("cosmoshub-2".parse::<Id>().unwrap(), block.header.chain_id);
 
+        let responses = client
+            .batch()
+            .request(block::Request::new(Height::from(10_u32)))
+            .request(block::Request::new(Height::from(11_u32)))
+            .perform()
+            .await
+            .unwrap();
+        assert_eq!(2, responses.len());
+        assert_eq!(block, responses[0].as_ref().unwrap().block);
+
         client.close();
         driver_hdl.await.unwrap().unwrap();
     }
@@ -314,4 +325,324 @@ mod test {
         client.close();
         driver_hdl.await.unwrap().unwrap();
     }
+
+    /// Serves the responses to the requests of type `Req`, eg. the pages of
+    /// the results of a paginated endpoint, computed by the given function
+    struct PagingMatcher<Req, Res, F> {
+        respond: F,
+        _endpoint: PhantomData<fn(Req) -> Res>,
+    }
+
+    impl<Req, Res, F> PagingMatcher<Req, Res, F>
+    where
+        F: Fn(Req) -> Res,
+    {
+        fn new(respond: F) -> Self {
+            Self {
+                respond,
+                _endpoint: PhantomData,
+            }
+        }
+    }
+
+    impl<Req, Res, F> MockRequestMatcher for PagingMatcher<Req, Res, F>
+    where
+        Req: DeserializeOwned,
+        Res: Serialize,
+        F: Fn(Req) -> Res + Send + Sync,
+    {
+        fn response_for<R>(&self, request: R) -> Option<Result<R::Response, Error>>
+        where
+            R: Request,
+        {
+            let request: Req =
+                serde_json::from_value(serde_json::to_value(&request).unwrap()).ok()?;
+            let result = (self.respond)(request);
+            let json = serde_json::json!({ "jsonrpc": "2.0", "id": "", "result": result });
+            Some(R::Response::from_string(json.to_string()))
+        }
+    }
+
+    /// Items of the given page, numbered from 1, of the given items
+    fn page<T: Clone>(items: &[T], page: usize, per_page: usize) -> Vec<T> {
+        items
+            .iter()
+            .skip((page - 1) * per_page)
+            .take(per_page)
+            .cloned()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn mock_client_tx_search_stream() {
+        let response =
+            tx_search::Response::from_string(read_json_fixture("tx_search_no_prove").await)
+                .unwrap();
+        assert_eq!(8, response.total_count);
+        let fixture = response.clone();
+        let (client, driver) =
+            MockClient::new(PagingMatcher::new(move |request: tx_search::Request| {
+                tx_search::Response {
+                    txs: page(
+                        &fixture.txs,
+                        request.page as usize,
+                        usize::from(request.per_page),
+                    ),
+                    total_count: fixture.total_count,
+                }
+            }));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let query = Query::from(EventType::Tx);
+        for per_page in [1, 3, 8, 100] {
+            let txs = client
+                .tx_search_stream(query.clone(), false, per_page, Order::Ascending)
+                .collect::<Vec<_>>()
+                .await;
+            let hashes: Vec<_> = txs.into_iter().map(|tx| tx.unwrap().hash).collect();
+            let expected: Vec<_> = response.txs.iter().map(|tx| tx.hash).collect();
+            assert_eq!(expected, hashes);
+        }
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_blockchain_stream() {
+        let response =
+            blockchain::Response::from_string(read_json_fixture("blockchain").await).unwrap();
+        // Metadata of the blocks of a chain of height 45, copied from the
+        // fixture
+        let meta = response.block_metas[0].clone();
+        let last_height = 45;
+        let (client, driver) =
+            MockClient::new(PagingMatcher::new(move |request: blockchain::Request| {
+                let (min, max) = (request.min_height.value(), request.max_height.value());
+                assert!(min <= max && max - min < blockchain::MAX_BLOCKCHAIN_RANGE);
+
+                blockchain::Response {
+                    last_height: Height::try_from(last_height).unwrap(),
+                    block_metas: (min..=max.min(last_height))
+                        .rev()
+                        .map(|height| {
+                            let mut meta = meta.clone();
+                            meta.header.height = Height::try_from(height).unwrap();
+                            if height == 1 {
+                                meta.header.last_block_id = None;
+                            }
+                            meta
+                        })
+                        .collect(),
+                }
+            }));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        for (min, max, expected) in [(1_u32, 45_u32, 1..=45), (5, 100, 5..=45), (0, 20, 1..=20)] {
+            let heights: Vec<_> = client
+                .blockchain_stream(min, max)
+                .map(|meta| meta.unwrap().header.height.value())
+                .collect()
+                .await;
+            assert_eq!(expected.collect::<Vec<_>>(), heights);
+        }
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_all_validators() {
+        let response =
+            validators::Response::from_string(read_json_fixture("validators").await).unwrap();
+        assert_eq!(65, response.total);
+        let fixture = response.clone();
+        let (client, driver) =
+            MockClient::new(PagingMatcher::new(move |request: validators::Request| {
+                let per_page = request.per_page.unwrap().to_string().parse().unwrap();
+                let page_number = request.page.unwrap().to_string().parse().unwrap();
+                validators::Response::new(
+                    fixture.block_height,
+                    page(&fixture.validators, page_number, per_page),
+                    fixture.total,
+                )
+            }));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        let validator_set = client.all_validators(response.block_height).await.unwrap();
+        assert_eq!(
+            validator::Set::without_proposer(response.validators).unwrap(),
+            validator_set
+        );
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_genesis_chunked() {
+        #[derive(Default, serde::Deserialize)]
+        struct AppState {
+            accounts: Vec<serde_json::Value>,
+        }
+
+        let response: serde_json::Value =
+            serde_json::from_str(&read_json_fixture("genesis").await).unwrap();
+        let genesis = serde_json::to_vec(&response["result"]["genesis"]).unwrap();
+        let (client, driver) = MockClient::new(PagingMatcher::new(
+            move |request: genesis_chunked::Request| {
+                let chunks: Vec<_> = genesis.chunks(200_000).collect();
+                genesis_chunked::Response {
+                    chunk: request.chunk,
+                    total: chunks.len() as u64,
+                    data: chunks[request.chunk as usize].to_vec(),
+                }
+            },
+        ));
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+        assert!(client.genesis_chunk(0).await.unwrap().total > 1);
+        let genesis = client.genesis_chunked::<AppState>().await.unwrap();
+        assert_eq!(genesis.chain_id.as_str(), "cosmoshub-2");
+        assert!(!genesis.app_state.accounts.is_empty());
+
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_genesis_chunked_rejects_invalid_first_chunk() {
+        for (chunk, total) in [(0, 0), (1, 2)] {
+            let (client, driver) =
+                MockClient::new(PagingMatcher::new(move |_: genesis_chunked::Request| {
+                    genesis_chunked::Response {
+                        chunk,
+                        total,
+                        data: vec![],
+                    }
+                }));
+            let driver_hdl = tokio::spawn(async move { driver.run().await });
+
+            let err = client
+                .genesis_chunked::<serde_json::Value>()
+                .await
+                .unwrap_err();
+            match err.detail() {
+                ErrorDetail::InvalidGenesisChunk(e) => {
+                    assert_eq!((e.chunk, e.total), (chunk, total))
+                }
+                _ => panic!("expected an invalid genesis chunk error, got {:?}", err),
+            }
+
+            client.close();
+            driver_hdl.await.unwrap().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn mock_client_wait_until_synced() {
+        let status_fixture = read_json_fixture("status").await;
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::Health, Ok(read_json_fixture("health").await))
+            .map(Method::Status, Ok(status_fixture.clone()));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        client
+            .wait_until_healthy(Duration::from_secs(1))
+            .await
+            .unwrap();
+        let status = client
+            .wait_until_synced(Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(!status.sync_info.catching_up);
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        // The node never catches up, and is never healthy
+        let catching_up =
+            status_fixture.replace(r#""catching_up": false"#, r#""catching_up": true"#);
+        let matcher = MockRequestMethodMatcher::default()
+            .map(
+                Method::Health,
+                Err(Error::response(ResponseError::server_error("unhealthy"))),
+            )
+            .map(Method::Status, Ok(catching_up));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let timeout = Duration::from_millis(300);
+        let err = client.wait_until_healthy(timeout).await.unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Timeout(_)));
+        let err = client.wait_until_synced(timeout).await.unwrap_err();
+        match err.detail() {
+            ErrorDetail::SyncTimeout(e) => assert_eq!(e.duration, timeout),
+            _ => panic!("expected a sync timeout, got {}", err),
+        }
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn mock_client_broadcast_and_confirm() {
+        let tx = Transaction::from(b"key=value".to_vec());
+        let broadcast_fixture = read_json_fixture("broadcast_tx_sync").await;
+        let tx_fixture = read_json_fixture("tx_no_prove").await;
+
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::BroadcastTxSync, Ok(broadcast_fixture.clone()))
+            .map(Method::Tx, Ok(tx_fixture));
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let response = client
+            .broadcast_and_confirm(tx.clone(), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(response.tx_result.code.is_ok());
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        // The transaction is never committed
+        let matcher = MockRequestMethodMatcher::default()
+            .map(Method::BroadcastTxSync, Ok(broadcast_fixture))
+            .map(
+                Method::Tx,
+                Err(Error::response(ResponseError::server_error("tx not found"))),
+            );
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let timeout = Duration::from_millis(400);
+        let err = client
+            .broadcast_and_confirm(tx.clone(), timeout)
+            .await
+            .unwrap_err();
+        match err.detail() {
+            ErrorDetail::TxConfirmationTimeout(e) => assert_eq!(e.duration, timeout),
+            _ => panic!("expected a confirmation timeout, got {}", err),
+        }
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+
+        // Other errors are not retried
+        let matcher = MockRequestMethodMatcher::default()
+            .map(
+                Method::BroadcastTxSync,
+                Ok(read_json_fixture("broadcast_tx_sync").await),
+            )
+            .map(
+                Method::Tx,
+                Err(Error::response(ResponseError::server_error(
+                    "transaction indexing is disabled",
+                ))),
+            );
+        let (client, driver) = MockClient::new(matcher);
+        let driver_hdl = tokio::spawn(async move { driver.run().await });
+        let timeout = Duration::from_secs(60);
+        let err = time::timeout(timeout / 2, client.broadcast_and_confirm(tx, timeout))
+            .await
+            .expect("the error to be returned right away")
+            .unwrap_err();
+        assert!(matches!(err.detail(), ErrorDetail::Response(_)));
+        client.close();
+        driver_hdl.await.unwrap().unwrap();
+    }
 }
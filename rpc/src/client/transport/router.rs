@@ -11,7 +11,7 @@ use crate::event::Event;
 pub type SubscriptionQuery = String;
 pub type SubscriptionId = String;
 
-#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+#[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
 pub type SubscriptionIdRef<'a> = &'a str;
 
 /// Provides a mechanism for tracking [`Subscription`]s and routing [`Event`]s
@@ -19,7 +19,7 @@ pub type SubscriptionIdRef<'a> = &'a str;
 ///
 /// [`Subscription`]: struct.Subscription.html
 /// [`Event`]: ./event/struct.Event.html
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SubscriptionRouter {
     /// A map of subscription queries to collections of subscription IDs and
     /// their result channels. Used for publishing events relating to a specific
@@ -30,7 +30,7 @@ pub struct SubscriptionRouter {
 impl SubscriptionRouter {
     /// Publishes the given error to all of the subscriptions to which the
     /// error is relevant, based on the given subscription id query.
-    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    #[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
     pub fn publish_error(&mut self, id: SubscriptionIdRef<'_>, err: Error) -> PublishResult {
         if let Some(query) = self.subscription_query(id).cloned() {
             self.publish(query, Err(err))
@@ -40,7 +40,7 @@ impl SubscriptionRouter {
     }
 
     /// Get the query associated with the given subscription.
-    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    #[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
     fn subscription_query(&self, id: SubscriptionIdRef<'_>) -> Option<&SubscriptionQuery> {
         for (query, subs) in &self.subscriptions {
             if subs.contains_key(id) {
@@ -53,7 +53,7 @@ impl SubscriptionRouter {
 
     /// Publishes the given event to all of the subscriptions to which the
     /// event is relevant, based on the associated query.
-    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    #[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
     pub fn publish_event(&mut self, ev: Event) -> PublishResult {
         self.publish(ev.query.clone(), Ok(ev))
     }
@@ -126,15 +126,8 @@ impl SubscriptionRouter {
     }
 }
 
-impl Default for SubscriptionRouter {
-    fn default() -> Self {
-        Self {
-            subscriptions: HashMap::new(),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "websocket-client"), allow(dead_code))]
 pub enum PublishResult {
     Success,
     NoSubscribers,
@@ -757,6 +757,7 @@ impl WebSocketClientDriver {
 mod test {
     use super::*;
     use crate::client::sync::unbounded;
+    use crate::event::NewBlock;
     use crate::query::EventType;
     use crate::{request, Id, Method};
     use async_tungstenite::tokio::{accept_async, TokioAdapter};
@@ -1110,4 +1111,46 @@ mod test {
             );
         }
     }
+
+    #[tokio::test]
+    async fn websocket_client_multiplexes_typed_subscriptions() {
+        let test_events = vec![
+            read_event("event_new_block_1").await,
+            read_event("event_new_block_2").await,
+        ];
+
+        let mut server = TestServer::new("127.0.0.1:0").await;
+        let (client, driver) = WebSocketClient::new(server.node_addr.clone())
+            .await
+            .unwrap();
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        // Both subscriptions share the same subscription of the connection
+        let query: Query = EventType::NewBlock.into();
+        let mut new_blocks = client
+            .subscribe(query.clone())
+            .await
+            .unwrap()
+            .typed::<NewBlock>();
+        let mut subs = client.subscribe(query.clone()).await.unwrap();
+        assert_ne!(new_blocks.id(), subs.id());
+
+        for ev in &test_events {
+            server.publish_event(ev.clone()).unwrap();
+        }
+        for ev in &test_events {
+            let new_block = new_blocks.next().await.unwrap().unwrap();
+            assert_eq!(new_block, NewBlock::try_from(ev.clone()).unwrap());
+            assert_eq!(&subs.next().await.unwrap().unwrap(), ev);
+        }
+
+        // Unsubscribing terminates both subscriptions
+        client.unsubscribe(query).await.unwrap();
+        assert!(new_blocks.next().await.is_none());
+        assert!(subs.next().await.is_none());
+
+        client.close().unwrap();
+        server.terminate().await.unwrap();
+        let _ = driver_handle.await.unwrap();
+    }
 }
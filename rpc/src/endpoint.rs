@@ -7,14 +7,19 @@ pub mod block_results;
 pub mod blockchain;
 pub mod broadcast;
 pub mod commit;
+pub mod consensus_params;
 pub mod consensus_state;
+pub mod dump_consensus_state;
 pub mod evidence;
 pub mod genesis;
+pub mod genesis_chunked;
 pub mod health;
 pub mod net_info;
+pub mod num_unconfirmed_txs;
 pub mod status;
 pub mod subscribe;
 pub mod tx;
 pub mod tx_search;
+pub mod unconfirmed_txs;
 pub mod unsubscribe;
 pub mod validators;
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use tendermint::{abci, block, consensus, validator};
+use tendermint::{abci, block, consensus, validator, Hash};
 
 /// Get ABCI results at a given height.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -55,4 +55,63 @@ pub struct Response {
     pub consensus_param_updates: Option<consensus::Params>,
 }
 
+/// Stage of the execution of a block in which an event was emitted
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EventSource {
+    /// `BeginBlock`
+    BeginBlock,
+
+    /// `DeliverTx` of the transaction with the given index in the block
+    DeliverTx(usize),
+
+    /// `EndBlock`
+    EndBlock,
+}
+
+impl Response {
+    /// All the events emitted while executing the block, in order, along
+    /// with the stage of the execution which emitted them
+    pub fn events(&self) -> impl Iterator<Item = (EventSource, &abci::Event)> + '_ {
+        let begin_block = self
+            .begin_block_events
+            .iter()
+            .flatten()
+            .map(|event| (EventSource::BeginBlock, event));
+        let deliver_tx =
+            self.txs_results
+                .iter()
+                .flatten()
+                .enumerate()
+                .flat_map(|(index, result)| {
+                    result
+                        .events
+                        .iter()
+                        .map(move |event| (EventSource::DeliverTx(index), event))
+                });
+        let end_block = self
+            .end_block_events
+            .iter()
+            .flatten()
+            .map(|event| (EventSource::EndBlock, event));
+
+        begin_block.chain(deliver_tx).chain(end_block)
+    }
+
+    /// The events of the given type emitted while executing the block, in
+    /// order, along with the stage of the execution which emitted them
+    pub fn events_of_type<'a>(
+        &'a self,
+        type_str: &'a str,
+    ) -> impl Iterator<Item = (EventSource, &'a abci::Event)> + 'a {
+        self.events()
+            .filter(move |(_, event)| event.type_str == type_str)
+    }
+
+    /// Merkle root of the results of the transactions of this block, ie. the
+    /// `last_results_hash` of the header of the next block
+    pub fn results_hash(&self) -> Hash {
+        abci::results_hash(self.txs_results.as_deref().unwrap_or_default())
+    }
+}
+
 impl crate::Response for Response {}
@@ -5,6 +5,10 @@ use std::ops::Range;
 
 use tendermint::block;
 
+/// The maximum number of blocks in the range of a request, beyond which the
+/// range is truncated by the endpoint.
+pub const MAX_BLOCKCHAIN_RANGE: u64 = 20;
+
 /// Get information about a specific block
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Request {
@@ -0,0 +1,47 @@
+//! `/consensus_params` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::{block, consensus};
+
+/// Get the consensus parameters at a given height.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Height at which to fetch the consensus parameters.
+    ///
+    /// If no height is provided, it will fetch the parameters of the latest
+    /// block.
+    pub height: Option<block::Height>,
+}
+
+impl Request {
+    /// Create a new request for the consensus parameters at a particular
+    /// height
+    pub fn new(height: block::Height) -> Self {
+        Self {
+            height: Some(height),
+        }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::ConsensusParams
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Consensus parameters response.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Height at which the consensus parameters apply
+    pub block_height: block::Height,
+
+    /// Consensus parameters
+    pub consensus_params: consensus::Params,
+}
+
+impl crate::Response for Response {}
@@ -8,6 +8,8 @@ use subtle_encoding::hex;
 use tendermint::block::{Height, Round};
 use tendermint::{account, hash, vote, Hash, Time};
 
+pub use tendermint::consensus::HeightRoundStep;
+
 // From <https://github.com/tendermint/tendermint/blob/e820e68acd69737cfb63bc9ccca5f5450a42b5cf/types/vote.go#L16>
 const NIL_VOTE_STR: &str = "nil-Vote";
 
@@ -64,56 +66,6 @@ pub struct RoundState {
     pub proposer: ValidatorInfo,
 }
 
-/// A compound object indicating a height, round and step for consensus state.
-#[derive(Clone, Debug)]
-pub struct HeightRoundStep {
-    /// Current block height
-    pub height: Height,
-    /// Current consensus round
-    pub round: Round,
-    /// Current consensus step
-    pub step: i8,
-}
-
-impl Serialize for HeightRoundStep {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let hrs = format!(
-            "{}/{}/{}",
-            self.height.value(),
-            self.round.value(),
-            self.step
-        );
-        serializer.serialize_str(&hrs)
-    }
-}
-
-impl<'de> Deserialize<'de> for HeightRoundStep {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let hrs: Vec<&str> = s.split('/').collect();
-        if hrs.len() != 3 {
-            return Err(serde::de::Error::custom(format!(
-                "expected 3 components to height/round/step field, but got {}",
-                hrs.len()
-            )));
-        }
-        let height = Height::from_str(hrs[0]).map_err(serde::de::Error::custom)?;
-        let round = Round::from_str(hrs[1]).map_err(serde::de::Error::custom)?;
-        let step = i8::from_str(hrs[2]).map_err(serde::de::Error::custom)?;
-        Ok(Self {
-            height,
-            round,
-            step,
-        })
-    }
-}
-
 /// Details of all votes for a particular consensus round.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoundVotes {
@@ -0,0 +1,145 @@
+//! `/dump_consensus_state` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use tendermint::block::{self, Height, Round};
+use tendermint::consensus::{PeerRoundState, RoundStep};
+use tendermint::{validator, Time};
+
+use super::consensus_state::{RoundVote, RoundVotes};
+use crate::Method;
+
+/// Get the full consensus state of the node, along with the consensus
+/// states of its peers.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl Request {
+    /// Create a new request for the full consensus state
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> Method {
+        Method::DumpConsensusState
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// The full consensus state of a node and of its peers (UNSTABLE).
+///
+/// Currently based on <https://github.com/tendermint/tendermint/blob/v0.34.x/rpc/core/types/responses.go>
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Consensus state of the node
+    pub round_state: RoundState,
+
+    /// Consensus states of the peers of the node
+    pub peers: Vec<PeerState>,
+}
+
+impl crate::Response for Response {}
+
+/// The state of the consensus of a node in its current round.
+///
+/// The proposal and the proposed, locked and valid blocks are left out, being
+/// better obtained from the `/block` endpoint once committed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoundState {
+    /// Current block height
+    pub height: Height,
+
+    /// Current consensus round
+    #[serde(with = "tendermint::serializers::round")]
+    pub round: Round,
+
+    /// Current consensus step
+    pub step: RoundStep,
+
+    /// Time at which the current round started
+    #[serde(with = "tendermint::serializers::time")]
+    pub start_time: Time,
+
+    /// Time at which the block of the previous height was committed
+    #[serde(with = "tendermint::serializers::time")]
+    pub commit_time: Time,
+
+    /// Validators of the current height
+    pub validators: validator::Set,
+
+    /// Round in which a block is locked, if any
+    #[serde(with = "tendermint::serializers::optional_round")]
+    pub locked_round: Option<Round>,
+
+    /// Round of the last valid block, if any
+    #[serde(with = "tendermint::serializers::optional_round")]
+    pub valid_round: Option<Round>,
+
+    /// Votes of each round of the current height
+    pub votes: Vec<RoundVotes>,
+
+    /// Round in which a block was committed at the current height, if any
+    #[serde(with = "tendermint::serializers::optional_round")]
+    pub commit_round: Option<Round>,
+
+    /// Precommits for the block of the previous height
+    pub last_commit: Option<VoteSet>,
+
+    /// Validators of the previous height
+    pub last_validators: validator::Set,
+
+    /// Whether the precommit timeout of the current round was triggered
+    pub triggered_timeout_precommit: bool,
+}
+
+/// The votes of a given type in a given round.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VoteSet {
+    /// Votes, by validator index
+    pub votes: Vec<RoundVote>,
+
+    /// Bit array of the validators which voted
+    pub votes_bit_array: String,
+
+    /// Blocks for which peers claimed to have seen two thirds of the votes,
+    /// by peer ID
+    pub peer_maj_23s: HashMap<String, block::Id>,
+}
+
+/// The consensus state of a peer of the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerState {
+    /// Address of the peer, ie. `id@host:port`
+    pub node_address: String,
+
+    /// Consensus state of the peer, as known by the node
+    pub peer_state: PeerStateInfo,
+}
+
+/// What the node knows of the consensus state of a peer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStateInfo {
+    /// Round state of the peer
+    pub round_state: PeerRoundState,
+
+    /// Useful messages received from the peer
+    pub stats: PeerStats,
+}
+
+/// Useful messages received from a peer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PeerStats {
+    /// Number of useful votes
+    #[serde(with = "tendermint::serializers::from_str")]
+    pub votes: u64,
+
+    /// Number of useful block parts
+    #[serde(with = "tendermint::serializers::from_str")]
+    pub block_parts: u64,
+}
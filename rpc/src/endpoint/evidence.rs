@@ -9,13 +9,13 @@ use tendermint::{abci::transaction, evidence::Evidence};
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Request {
     /// Evidence to broadcast
-    pub ev: Evidence,
+    pub evidence: Evidence,
 }
 
 impl Request {
     /// Create a new evidence broadcast RPC request
-    pub fn new(ev: Evidence) -> Request {
-        Request { ev }
+    pub fn new(evidence: Evidence) -> Request {
+        Request { evidence }
     }
 }
 
@@ -32,8 +32,8 @@ impl crate::SimpleRequest for Request {}
 /// Response from either an evidence broadcast request.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Response {
-    /// Evidence hash
-    /// TODO: transaction::Hash should be tmhash (github.com/tendermint/tendermint/crypto/tmhash)
+    /// Evidence hash, ie. the SHA-256 hash of its Protobuf encoding
+    #[serde(with = "tendermint::serializers::hash_base64")]
     pub hash: transaction::Hash,
 }
 
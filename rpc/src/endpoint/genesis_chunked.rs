@@ -0,0 +1,49 @@
+//! `/genesis_chunked` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::serializers;
+
+/// Get a chunk of the genesis file of the chain, for genesis files too large
+/// to be returned at once by `/genesis`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// Index of the chunk, starting from 0
+    #[serde(with = "serializers::from_str")]
+    pub chunk: u64,
+}
+
+impl Request {
+    /// Create a new request for the chunk with the given index
+    pub fn new(chunk: u64) -> Self {
+        Self { chunk }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::GenesisChunked
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Genesis chunk responses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Index of the chunk
+    #[serde(with = "serializers::from_str")]
+    pub chunk: u64,
+
+    /// Total number of chunks
+    #[serde(with = "serializers::from_str")]
+    pub total: u64,
+
+    /// Data of the chunk, ie. a part of the JSON encoding of the genesis file
+    #[serde(with = "serializers::bytes::base64string")]
+    pub data: Vec<u8>,
+}
+
+impl crate::Response for Response {}
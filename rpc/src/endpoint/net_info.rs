@@ -5,7 +5,7 @@ use std::fmt::{self, Display};
 use std::net::IpAddr;
 use std::time::Duration;
 
-use tendermint::{channel::Channel, node, serializers, Time};
+use tendermint::{channel::Channel, net, node, serializers, Time};
 
 /// Request network information from a node
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -44,6 +44,23 @@ impl crate::Response for Response {}
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Listener(String);
 
+impl Listener {
+    /// The external address of this listener, if any, as advertised to the
+    /// peers of the node
+    pub fn address(&self) -> Option<net::Address> {
+        let address = self
+            .0
+            .strip_prefix("Listener(")?
+            .strip_suffix(')')?
+            .trim_start_matches('@');
+        if address.is_empty() {
+            None
+        } else {
+            format!("tcp://{}", address).parse().ok()
+        }
+    }
+}
+
 impl Display for Listener {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -66,6 +83,22 @@ pub struct PeerInfo {
     pub remote_ip: IpAddr,
 }
 
+impl PeerInfo {
+    /// Address at which the peer can be dialed, ie. its node ID at its remote
+    /// IP address along with the port it listens on, if its listen address
+    /// is valid
+    pub fn address(&self) -> Option<net::Address> {
+        match self.node_info.listen_addr.to_net_address()? {
+            net::Address::Tcp { port, .. } => Some(net::Address::Tcp {
+                peer_id: Some(self.node_info.id),
+                host: self.remote_ip.to_string(),
+                port,
+            }),
+            net::Address::Unix { .. } => None,
+        }
+    }
+}
+
 /// Connection status information
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConnectionStatus {
@@ -0,0 +1,33 @@
+//! `/num_unconfirmed_txs` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::serializers;
+
+/// Get the number and size of the transactions in the mempool of the node
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request;
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::NumUnconfirmedTxs
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Number of unconfirmed transactions responses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Number of transactions in the mempool
+    #[serde(with = "serializers::from_str")]
+    pub total: u64,
+
+    /// Size of the transactions in the mempool, in bytes
+    #[serde(with = "serializers::from_str")]
+    pub total_bytes: u64,
+}
+
+impl crate::Response for Response {}
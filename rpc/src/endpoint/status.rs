@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use tendermint::{block, node, validator, AppHash, Hash, Time};
+use tendermint::{block, compat::CompatMode, node, validator, AppHash, Hash, Time};
 
 /// Node status request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -31,6 +31,13 @@ pub struct Response {
     pub validator_info: validator::Info,
 }
 
+impl Response {
+    /// The compatibility mode of the version of Tendermint the node runs
+    pub fn compat_mode(&self) -> Result<CompatMode, tendermint::Error> {
+        CompatMode::from_version(&self.node_info.version)
+    }
+}
+
 impl crate::Response for Response {}
 
 /// Sync information
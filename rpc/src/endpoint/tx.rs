@@ -1,9 +1,8 @@
 //! `/tx` endpoint JSON-RPC wrapper
 
-use crate::Method;
+use crate::{Error, Method};
 use serde::{Deserialize, Serialize};
 use tendermint::{abci, block};
-use tendermint_proto::types::TxProof;
 
 /// Request for finding a transaction by its hash.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -49,7 +48,40 @@ pub struct Response {
     pub tx_result: abci::DeliverTx,
     pub tx: abci::Transaction,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub proof: Option<TxProof>,
+    pub proof: Option<abci::transaction::TxProof>,
 }
 
 impl crate::Response for Response {}
+
+impl Response {
+    /// Verify that the transaction is included in the block with the given
+    /// header, with the proof returned when the transaction is requested with
+    /// `prove` set.
+    ///
+    /// The header is to be obtained from a trusted source, eg. a light
+    /// client, rather than from the node which returned the transaction.
+    pub fn verify_inclusion(&self, header: &block::Header) -> Result<(), Error> {
+        if header.height != self.height {
+            return Err(Error::invalid_tx_proof(format!(
+                "transaction at height {} but header at height {}",
+                self.height, header.height
+            )));
+        }
+
+        let proof = self
+            .proof
+            .as_ref()
+            .ok_or_else(|| Error::invalid_tx_proof("missing proof".to_string()))?;
+        if proof.data != self.tx || proof.data.hash() != self.hash {
+            return Err(Error::invalid_tx_proof(format!(
+                "proof is not for transaction {}",
+                self.hash
+            )));
+        }
+
+        let data_hash = header.data_hash.ok_or_else(|| {
+            Error::invalid_tx_proof(format!("block {} has no transactions", header.height))
+        })?;
+        proof.validate(&data_hash).map_err(Error::tendermint)
+    }
+}
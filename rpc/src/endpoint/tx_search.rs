@@ -5,6 +5,10 @@ pub use super::tx;
 use crate::{Method, Order};
 use serde::{Deserialize, Serialize};
 
+/// The maximum number of results per page, beyond which the results are
+/// truncated by the endpoint.
+pub const MAX_TX_SEARCH_PER_PAGE: u8 = 100;
+
 /// Request for searching for transactions with their results.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Request {
@@ -0,0 +1,57 @@
+//! `/unconfirmed_txs` endpoint JSON-RPC wrapper
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::abci::Transaction;
+use tendermint::serializers;
+
+/// The maximum number of transactions returned by `/unconfirmed_txs`.
+pub const MAX_UNCONFIRMED_TXS_LIMIT: u8 = 100;
+
+/// Get the transactions in the mempool of the node
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Request {
+    /// The maximum number of transactions to return, 30 by default and at
+    /// most [`MAX_UNCONFIRMED_TXS_LIMIT`].
+    #[serde(with = "serializers::optional_from_str")]
+    pub limit: Option<u8>,
+}
+
+impl Request {
+    /// Create a new request for at most the given number of transactions
+    pub fn new(limit: Option<u8>) -> Self {
+        Self { limit }
+    }
+}
+
+impl crate::Request for Request {
+    type Response = Response;
+
+    fn method(&self) -> crate::Method {
+        crate::Method::UnconfirmedTxs
+    }
+}
+
+impl crate::SimpleRequest for Request {}
+
+/// Unconfirmed transactions responses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    /// Number of transactions returned
+    #[serde(with = "serializers::from_str")]
+    pub n_txs: u64,
+
+    /// Number of transactions in the mempool
+    #[serde(with = "serializers::from_str")]
+    pub total: u64,
+
+    /// Size of the transactions in the mempool, in bytes
+    #[serde(with = "serializers::from_str")]
+    pub total_bytes: u64,
+
+    /// Transactions, in the order they entered the mempool
+    #[serde(with = "serializers::nullable")]
+    pub txs: Vec<Transaction>,
+}
+
+impl crate::Response for Response {}
@@ -124,11 +124,63 @@ define_error! {
                 )
             },
 
+        SyncTimeout
+            {
+                duration: Duration
+            }
+            | e | {
+                format_args!(
+                    "timed out waiting for the node to catch up after {}ms",
+                    e.duration.as_millis()
+                )
+            },
+
+        RequestTimeout
+            {
+                duration: Duration
+            }
+            | e | {
+                format_args!(
+                    "request timed out after {}ms",
+                    e.duration.as_millis()
+                )
+            },
+
+        TxRejected
+            {
+                code: u32,
+                log: String,
+            }
+            | e | {
+                format_args!("transaction rejected with code {}: {}", e.code, e.log)
+            },
+
+        TxConfirmationTimeout
+            {
+                hash: String,
+                duration: Duration,
+            }
+            | e | {
+                format_args!(
+                    "transaction {} not committed after {}ms",
+                    e.hash,
+                    e.duration.as_millis()
+                )
+            },
+
+        InvalidTxProof
+            {
+                reason: String,
+            }
+            | e | {
+                format_args!("invalid transaction inclusion proof: {}", e.reason)
+            },
+
         ChannelSend
             | _ | { "failed to send message to internal channel" },
 
         InvalidUrl
-            { url: Url }
+            { url: Box<Url> }
             | e | {
                 format_args!(
                     "cannot use URL {} with HTTP clients",
@@ -166,6 +218,25 @@ define_error! {
                 format_args!("unrecognized event type: {}", e.event_type)
             },
 
+        UnexpectedEvent
+            {
+                expected: String,
+                query: String,
+            }
+            | e | {
+                format_args!("expected a {} event, got an event for query \"{}\"",
+                    e.expected, e.query)
+            },
+
+        InvalidGenesisChunk
+            {
+                chunk: u64,
+                total: u64,
+            }
+            | e | {
+                format_args!("unexpected genesis chunk {} out of {}", e.chunk, e.total)
+            },
+
         Serde
             [ DisplayOnly<serde_json::Error> ]
             | _ | { "serde parse error" },
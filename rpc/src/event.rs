@@ -2,11 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use tendermint::abci::responses::{BeginBlock, EndBlock};
 use tendermint::Block;
 
 use crate::query::EventType;
-use crate::{response::Wrapper, Response};
+use crate::{response::Wrapper, Error, Response};
 
 /// An incoming event produced by a [`Subscription`].
 ///
@@ -36,10 +37,20 @@ impl Event {
             _ => None,
         }
     }
+
+    /// The values of the attributes with the given key of the events of the
+    /// given type, from the composite keys of [`Event::events`].
+    pub fn attribute_values(&self, event_type: &str, key: &str) -> &[String] {
+        self.events
+            .as_ref()
+            .and_then(|events| events.get(&tendermint::abci::composite_key(event_type, key)))
+            .map_or(&[], Vec::as_slice)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type", content = "value")]
+#[allow(clippy::large_enum_variant)]
 pub enum EventData {
     #[serde(alias = "tendermint/event/NewBlock")]
     NewBlock {
@@ -55,6 +66,55 @@ pub enum EventData {
     GenericJsonEvent(serde_json::Value),
 }
 
+/// The data of a `NewBlock` event, into which [`Event`]s can be converted,
+/// eg. to produce typed events with `Subscription::typed`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewBlock {
+    /// The new block
+    pub block: Block,
+    /// The result of `BeginBlock` for the new block
+    pub result_begin_block: Option<BeginBlock>,
+    /// The result of `EndBlock` for the new block
+    pub result_end_block: Option<EndBlock>,
+}
+
+impl TryFrom<Event> for NewBlock {
+    type Error = Error;
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        match event.data {
+            EventData::NewBlock {
+                block: Some(block),
+                result_begin_block,
+                result_end_block,
+            } => Ok(Self {
+                block,
+                result_begin_block,
+                result_end_block,
+            }),
+            _ => Err(Error::unexpected_event(
+                EventType::NewBlock.to_string(),
+                event.query,
+            )),
+        }
+    }
+}
+
+/// Converts `Tx` events into the info of their transaction.
+impl TryFrom<Event> for TxInfo {
+    type Error = Error;
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        match event.data {
+            EventData::Tx { tx_result } => Ok(tx_result),
+            _ => Err(Error::unexpected_event(
+                EventType::Tx.to_string(),
+                event.query,
+            )),
+        }
+    }
+}
+
 /// Transaction result info.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TxInfo {
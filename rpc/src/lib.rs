@@ -29,8 +29,8 @@
 mod client;
 #[cfg(any(feature = "http-client", feature = "websocket-client"))]
 pub use client::{
-    Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, Subscription,
-    SubscriptionClient,
+    Batch, Client, MockClient, MockRequestMatcher, MockRequestMethodMatcher, Subscription,
+    SubscriptionClient, TypedSubscription,
 };
 
 #[cfg(feature = "http-client")]
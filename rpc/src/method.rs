@@ -39,18 +39,30 @@ pub enum Method {
     /// Get commit info for a block
     Commit,
 
+    /// Get consensus parameters
+    ConsensusParams,
+
     /// Get consensus state
     ConsensusState,
 
+    /// Get the full consensus state, along with the ones of peers
+    DumpConsensusState,
+
     /// Get genesis file
     Genesis,
 
+    /// Get a chunk of the genesis file
+    GenesisChunked,
+
     /// Get health info
     Health,
 
     /// Get network info
     NetInfo,
 
+    /// Get the number of unconfirmed transactions
+    NumUnconfirmedTxs,
+
     /// Get node status
     Status,
 
@@ -60,6 +72,9 @@ pub enum Method {
     /// Search for transactions with their results
     TxSearch,
 
+    /// Get unconfirmed transactions
+    UnconfirmedTxs,
+
     /// Get validator info for a block
     Validators,
 
@@ -87,14 +102,19 @@ impl Method {
             Method::BroadcastTxSync => "broadcast_tx_sync",
             Method::BroadcastTxCommit => "broadcast_tx_commit",
             Method::Commit => "commit",
+            Method::ConsensusParams => "consensus_params",
             Method::ConsensusState => "consensus_state",
+            Method::DumpConsensusState => "dump_consensus_state",
             Method::Genesis => "genesis",
+            Method::GenesisChunked => "genesis_chunked",
             Method::Health => "health",
             Method::NetInfo => "net_info",
+            Method::NumUnconfirmedTxs => "num_unconfirmed_txs",
             Method::Status => "status",
             Method::Subscribe => "subscribe",
             Method::Tx => "tx",
             Method::TxSearch => "tx_search",
+            Method::UnconfirmedTxs => "unconfirmed_txs",
             Method::Unsubscribe => "unsubscribe",
             Method::Validators => "validators",
         }
@@ -116,14 +136,19 @@ impl FromStr for Method {
             "broadcast_tx_sync" => Method::BroadcastTxSync,
             "broadcast_tx_commit" => Method::BroadcastTxCommit,
             "commit" => Method::Commit,
+            "consensus_params" => Method::ConsensusParams,
             "consensus_state" => Method::ConsensusState,
+            "dump_consensus_state" => Method::DumpConsensusState,
             "genesis" => Method::Genesis,
+            "genesis_chunked" => Method::GenesisChunked,
             "health" => Method::Health,
             "net_info" => Method::NetInfo,
+            "num_unconfirmed_txs" => Method::NumUnconfirmedTxs,
             "status" => Method::Status,
             "subscribe" => Method::Subscribe,
             "tx" => Method::Tx,
             "tx_search" => Method::TxSearch,
+            "unconfirmed_txs" => Method::UnconfirmedTxs,
             "unsubscribe" => Method::Unsubscribe,
             "validators" => Method::Validators,
             other => return Err(Error::method_not_found(other.to_string())),
@@ -6,6 +6,8 @@
 
 use crate::Error;
 use chrono::{Date, DateTime, FixedOffset, NaiveDate, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
@@ -48,6 +50,23 @@ use std::str::FromStr;
 /// assert_eq!(query, Query::from(EventType::Tx).and_gte("tx.height", 100_u64));
 /// ```
 ///
+/// ### Matching events
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use tendermint_rpc::query::Query;
+///
+/// let mut events = HashMap::new();
+/// events.insert("tm.event".to_string(), vec!["Tx".to_string()]);
+/// events.insert("tx.height".to_string(), vec!["7".to_string()]);
+///
+/// let query: Query = "tm.event = 'Tx' AND tx.height > 5".parse().unwrap();
+/// assert!(query.matches(&events));
+///
+/// let query: Query = "tm.event = 'Tx' AND tx.height > 10".parse().unwrap();
+/// assert!(!query.matches(&events));
+/// ```
+///
 /// [subscribe endpoint documentation]: https://docs.tendermint.com/master/rpc/#/Websocket/subscribe
 #[derive(Debug, Clone, PartialEq)]
 pub struct Query {
@@ -164,6 +183,30 @@ impl Query {
         self.conditions.push(Condition::Exists(key.to_string()));
         self
     }
+
+    /// Whether the given events satisfy this query, as evaluated by
+    /// Tendermint: each condition must be satisfied by at least one of the
+    /// values of its key.
+    ///
+    /// The events are given as the values of their attributes by composite
+    /// key (`type.key`, or `tm.event` for the event type), as in the
+    /// `events` of subscription events. Such maps can be built from ABCI
+    /// events with [`tendermint::abci::responses::events_by_composite_key`].
+    pub fn matches(&self, events: &HashMap<String, Vec<String>>) -> bool {
+        if let Some(event_type) = &self.event_type {
+            let event_type = event_type.to_string();
+            match events.get("tm.event") {
+                Some(values) if values.contains(&event_type) => {}
+                _ => return false,
+            }
+        }
+
+        self.conditions.iter().all(|condition| {
+            events
+                .get(condition.key())
+                .is_some_and(|values| values.iter().any(|v| condition.matches(v)))
+        })
+    }
 }
 
 impl Default for Query {
@@ -436,6 +479,41 @@ pub enum Condition {
     Exists(String),
 }
 
+impl Condition {
+    /// The key of the attributes this condition applies to.
+    pub fn key(&self) -> &str {
+        match self {
+            Condition::Eq(key, _)
+            | Condition::Lt(key, _)
+            | Condition::Lte(key, _)
+            | Condition::Gt(key, _)
+            | Condition::Gte(key, _)
+            | Condition::Contains(key, _)
+            | Condition::Exists(key) => key,
+        }
+    }
+
+    /// Whether the given attribute value satisfies this condition.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            Condition::Eq(_, Operand::String(s)) => value == s,
+            Condition::Eq(_, op) => op.compare(value) == Some(Ordering::Equal),
+            Condition::Lt(_, op) => op.compare(value) == Some(Ordering::Less),
+            Condition::Lte(_, op) => matches!(
+                op.compare(value),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            ),
+            Condition::Gt(_, op) => op.compare(value) == Some(Ordering::Greater),
+            Condition::Gte(_, op) => matches!(
+                op.compare(value),
+                Some(Ordering::Greater) | Some(Ordering::Equal)
+            ),
+            Condition::Contains(_, s) => value.contains(s.as_str()),
+            Condition::Exists(_) => true,
+        }
+    }
+}
+
 impl fmt::Display for Condition {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -468,6 +546,53 @@ pub enum Operand {
     DateTime(DateTime<Utc>),
 }
 
+impl Operand {
+    /// Compare the given attribute value to this operand, if they are
+    /// comparable.
+    ///
+    /// As in Tendermint, numbers are read from the first number within the
+    /// value (eg. `100` in `100stake`), and dates and times from RFC3339
+    /// values. Strings can only be compared for equality.
+    fn compare(&self, value: &str) -> Option<Ordering> {
+        match self {
+            Operand::String(s) if value == s => Some(Ordering::Equal),
+            Operand::String(_) => None,
+            Operand::Signed(i) => compare_number(value, *i as f64, i128::from(*i)),
+            Operand::Unsigned(u) => compare_number(value, *u as f64, i128::from(*u)),
+            Operand::Float(f) => parse_number(value)?.parse::<f64>().ok()?.partial_cmp(f),
+            Operand::Date(d) => {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+                Some(Date::<Utc>::from_utc(date, Utc).cmp(d))
+            }
+            Operand::DateTime(dt) => {
+                let value = DateTime::parse_from_rfc3339(value).ok()?;
+                Some(value.with_timezone(&Utc).cmp(dt))
+            }
+        }
+    }
+}
+
+/// The first number within the given attribute value, as matched by the
+/// `[0-9\.]+` regular expression of Tendermint: signs are not part of it.
+fn parse_number(value: &str) -> Option<&str> {
+    let is_number_char = |c: char| c.is_ascii_digit() || c == '.';
+    let start = value.find(is_number_char)?;
+    let len = value[start..]
+        .find(|c: char| !is_number_char(c))
+        .unwrap_or(value.len() - start);
+    Some(&value[start..start + len])
+}
+
+/// Compare the number within the given attribute value to an integer.
+fn compare_number(value: &str, float: f64, integer: i128) -> Option<Ordering> {
+    let number = parse_number(value)?;
+    if number.contains('.') {
+        number.parse::<f64>().ok()?.partial_cmp(&float)
+    } else {
+        Some(number.parse::<i128>().ok()?.cmp(&integer))
+    }
+}
+
 impl fmt::Display for Operand {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -475,7 +600,7 @@ impl fmt::Display for Operand {
             Operand::Signed(i) => write!(f, "{}", i),
             Operand::Unsigned(u) => write!(f, "{}", u),
             Operand::Float(h) => write!(f, "{}", h),
-            Operand::Date(d) => write!(f, "DATE {}", d.format("%Y-%m-%d").to_string()),
+            Operand::Date(d) => write!(f, "DATE {}", d.format("%Y-%m-%d")),
             Operand::DateTime(dt) => write!(f, "TIME {}", dt.to_rfc3339()),
         }
     }
@@ -861,6 +986,82 @@ mod test {
         a == b
     }
 
+    fn events(pairs: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        let mut events = HashMap::<String, Vec<String>>::new();
+        for (key, value) in pairs {
+            events
+                .entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+        events
+    }
+
+    #[test]
+    fn match_events() {
+        let events = events(&[
+            ("tm.event", "Tx"),
+            ("tx.height", "42"),
+            ("transfer.recipient", "cosmos1a"),
+            ("transfer.recipient", "cosmos1b"),
+            ("transfer.amount", "100stake"),
+            ("reward.ratio", "0.25"),
+            ("delay.until", "2021-05-01T12:00:00Z"),
+            ("delay.day", "2021-05-01"),
+            ("balance.change", "-5"),
+        ]);
+
+        let matching = [
+            "",
+            "tm.event = 'Tx'",
+            "tx.height = 42",
+            "tx.height > 5 AND tx.height <= 42",
+            "tm.event = 'Tx' AND transfer.recipient = 'cosmos1b'",
+            "transfer.amount >= 100 AND transfer.amount < 101",
+            "transfer.recipient CONTAINS 'mos1'",
+            "reward.ratio > 0.2",
+            "reward.ratio < 1",
+            "delay.until > TIME 2021-05-01T11:59:59Z",
+            "delay.day = DATE 2021-05-01",
+            "tx.height > -1",
+            "transfer.amount EXISTS",
+            // As in Tendermint, the sign of attribute values is ignored
+            "balance.change = 5",
+        ];
+        for query in &matching {
+            let query = Query::from_str(query).unwrap();
+            assert!(query.matches(&events), "{} should match", query);
+        }
+
+        let non_matching = [
+            "tm.event = 'NewBlock'",
+            "tx.height = 41",
+            "tx.height > 42",
+            "transfer.recipient = 'cosmos1c'",
+            "transfer.recipient > 5",
+            "transfer.sender EXISTS",
+            "tx.height > 5 AND transfer.sender = 'cosmos1a'",
+            "reward.ratio >= 0.3",
+            "delay.until < TIME 2021-05-01T12:00:00Z",
+            "balance.change < 0",
+        ];
+        for query in &non_matching {
+            let query = Query::from_str(query).unwrap();
+            assert!(!query.matches(&events), "{} should not match", query);
+        }
+
+        assert!(!Query::from(EventType::Tx).matches(&HashMap::new()));
+    }
+
+    #[test]
+    fn first_number() {
+        assert_eq!(parse_number("100stake"), Some("100"));
+        assert_eq!(parse_number("-12.5"), Some("12.5"));
+        assert_eq!(parse_number("ratio: .5"), Some(".5"));
+        assert_eq!(parse_number("ratio: 3"), Some("3"));
+        assert_eq!(parse_number("none"), None);
+    }
+
     #[test]
     fn query_conditions() {
         let query = Query::from_str("some.field = 'string'").unwrap();
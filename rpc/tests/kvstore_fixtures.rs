@@ -1,5 +1,6 @@
 //! Tendermint kvstore RPC endpoint testing.
 
+use std::convert::TryInto;
 use std::str::FromStr;
 use std::{fs, path::PathBuf};
 use subtle_encoding::{base64, hex};
@@ -394,6 +395,21 @@ fn incoming_fixtures() {
                 assert!(result.block.header.last_block_id.is_some());
                 assert!(result.block.header.last_commit_hash.is_some());
                 assert!(result.block.header.last_results_hash.is_some());
+                // The hashes of the header match the contents of the block
+                let block = &result.block;
+                assert_eq!(block.header.data_hash, Some(block.data.hash()));
+                assert_eq!(
+                    block.header.evidence_hash,
+                    Some(block.evidence.hash().unwrap())
+                );
+                assert_eq!(
+                    block.header.last_commit_hash,
+                    block.last_commit.as_ref().map(|commit| commit.hash())
+                );
+                assert_eq!(
+                    block.header.consensus_hash,
+                    tendermint::consensus::Params::default().hash()
+                );
                 assert!(!result.block.header.next_validators_hash.is_empty());
                 assert_ne!(
                     result.block.header.proposer_address.as_bytes(),
@@ -436,6 +452,8 @@ fn incoming_fixtures() {
                 assert_eq!(result.height.value(), 10);
                 assert!(result.txs_results.is_none());
                 assert!(result.validator_updates.is_empty());
+                // The next block has no transactions either
+                assert_eq!(Some(result.results_hash()), empty_merkle_root_hash);
             }
             "blockchain_from_1_to_10" => {
                 let result = endpoint::blockchain::Response::from_string(content).unwrap();
@@ -663,6 +681,13 @@ fn incoming_fixtures() {
                     100000
                 );
                 assert_eq!(result.genesis.consensus_params.evidence.max_bytes, 1048576);
+                assert_eq!(
+                    result.genesis.consensus_params,
+                    tendermint::consensus::Params {
+                        version: None,
+                        ..Default::default()
+                    }
+                );
                 assert_eq!(
                     result
                         .genesis
@@ -714,6 +739,10 @@ fn incoming_fixtures() {
                     tendermint::net::Address::from_str("tcp://0.0.0.0:26656").unwrap()
                 );
                 assert_eq!(result.node_info.moniker.to_string(), "dockernode");
+                assert_eq!(
+                    result.compat_mode().unwrap(),
+                    tendermint::compat::CompatMode::V0_34
+                );
                 assert_eq!(result.node_info.network.to_string(), CHAIN_ID);
                 assert_eq!(
                     result.node_info.other.rpc_address,
@@ -1368,9 +1397,9 @@ fn incoming_fixtures() {
                     assert!(tx.tx_result.info.to_string().is_empty());
                     assert!(tx.tx_result.log.value().is_empty());
                     let proof = tx.proof.unwrap();
-                    assert_eq!(proof.data, tx.tx.as_bytes());
-                    assert!(proof.proof.is_some());
-                    assert_ne!(proof.root_hash, [0; 32]);
+                    assert_eq!(proof.data, tx.tx);
+                    assert_eq!(proof.proof.index, tx.index as u64);
+                    assert!(proof.validate(&proof.root_hash).is_ok());
                 }
             }
             _ => {
@@ -1379,3 +1408,37 @@ fn incoming_fixtures() {
         }
     }
 }
+
+#[test]
+fn build_first_block_header_from_genesis() {
+    let read_fixture = |name: &str| {
+        fs::read_to_string(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("kvstore_fixtures")
+                .join("incoming")
+                .join(format!("{}.json", name)),
+        )
+        .unwrap()
+    };
+    let genesis = endpoint::genesis::Response::from_string(read_fixture("genesis"))
+        .unwrap()
+        .genesis;
+    let block = endpoint::block::Response::from_string(read_fixture("block_at_height_1"))
+        .unwrap()
+        .block;
+
+    let header = tendermint::block::HeaderBuilder::new(
+        genesis.chain_id,
+        1_u32.into(),
+        block.header.time,
+        tendermint::validator::Set::without_proposer(genesis.validators).unwrap(),
+    )
+    .version(block.header.version.clone())
+    .consensus_params(&genesis.consensus_params)
+    .app_hash(genesis.app_hash.try_into().unwrap())
+    .build()
+    .unwrap();
+    assert_eq!(header, block.header);
+    assert_eq!(header.hash(), block.header.hash());
+}
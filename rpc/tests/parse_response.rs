@@ -2,9 +2,11 @@
 
 use std::{fs, path::PathBuf};
 use tendermint::abci::Code;
+use tendermint::merkle::proof::DecodedProofOp;
 
 use std::str::FromStr;
-use tendermint::vote;
+use tendermint::consensus::RoundStep;
+use tendermint::{vote, Hash};
 use tendermint_rpc::endpoint::consensus_state::RoundVote;
 use tendermint_rpc::{
     endpoint,
@@ -21,7 +23,7 @@ fn read_json_fixture(name: &str) -> String {
 
 #[test]
 fn abci_info() {
-    let response = endpoint::abci_info::Response::from_string(&read_json_fixture("abci_info"))
+    let response = endpoint::abci_info::Response::from_string(read_json_fixture("abci_info"))
         .unwrap()
         .response;
 
@@ -31,7 +33,7 @@ fn abci_info() {
 
 #[test]
 fn abci_query() {
-    let response = endpoint::abci_query::Response::from_string(&read_json_fixture("abci_query"))
+    let response = endpoint::abci_query::Response::from_string(read_json_fixture("abci_query"))
         .unwrap()
         .response;
 
@@ -41,11 +43,21 @@ fn abci_query() {
     assert_eq!(proof.ops.len(), 2);
     assert_eq!(proof.ops[0].field_type, "iavl:v");
     assert_eq!(proof.ops[1].field_type, "multistore");
+
+    // Proofs of Cosmos SDK stores are to be decoded by the application
+    let decoded = proof.decode_ops().unwrap();
+    assert_eq!(
+        decoded,
+        vec![
+            DecodedProofOp::Unknown(proof.ops[0].clone()),
+            DecodedProofOp::Unknown(proof.ops[1].clone()),
+        ]
+    );
 }
 
 #[test]
 fn block() {
-    let response = endpoint::block::Response::from_string(&read_json_fixture("block")).unwrap();
+    let response = endpoint::block::Response::from_string(read_json_fixture("block")).unwrap();
 
     assert_eq!(response.block.header.version.block, 10);
     assert_eq!(response.block.header.chain_id.as_str(), EXAMPLE_CHAIN);
@@ -67,7 +79,7 @@ fn block() {
 #[test]
 fn block_with_evidences() {
     let response =
-        endpoint::block::Response::from_string(&read_json_fixture("block_with_evidences")).unwrap();
+        endpoint::block::Response::from_string(read_json_fixture("block_with_evidences")).unwrap();
 
     let evidence = response.block.evidence.iter().next().unwrap();
 
@@ -81,7 +93,7 @@ fn block_with_evidences() {
 // #[test]
 // fn block_empty_block_id() {
 //     let response =
-//         endpoint::block::Response::from_string(&read_json_fixture("block_empty_block_id"))
+//         endpoint::block::Response::from_string(read_json_fixture("block_empty_block_id"))
 //             .unwrap();
 //
 //     let tendermint::Block { last_commit, .. } = response.block;
@@ -97,7 +109,7 @@ fn block_with_evidences() {
 #[test]
 fn first_block() {
     let response =
-        endpoint::block::Response::from_string(&read_json_fixture("first_block")).unwrap();
+        endpoint::block::Response::from_string(read_json_fixture("first_block")).unwrap();
 
     assert_eq!(response.block.header.version.block, 10);
     assert_eq!(response.block.header.chain_id.as_str(), EXAMPLE_CHAIN);
@@ -111,8 +123,7 @@ fn first_block() {
 #[test]
 fn block_results() {
     let response =
-        endpoint::block_results::Response::from_string(&read_json_fixture("block_results"))
-            .unwrap();
+        endpoint::block_results::Response::from_string(read_json_fixture("block_results")).unwrap();
     assert_eq!(response.height.value(), 1814);
 
     let validator_updates = response.validator_updates;
@@ -136,10 +147,44 @@ fn block_results() {
     assert_eq!(validator_updates[0].power.value(), 1_233_243);
 }
 
+#[test]
+fn block_results_events() {
+    use endpoint::block_results::EventSource;
+    use tendermint::abci::{tag::Tag, Event};
+
+    let mut response =
+        endpoint::block_results::Response::from_string(read_json_fixture("block_results")).unwrap();
+    response.end_block_events = Some(vec![Event::new(
+        "someevent2",
+        vec![Tag::new("action", "unbond")],
+    )]);
+
+    let sources: Vec<_> = response.events().map(|(source, _)| source).collect();
+    assert_eq!(
+        sources,
+        vec![
+            EventSource::DeliverTx(0),
+            EventSource::DeliverTx(1),
+            EventSource::DeliverTx(2),
+            EventSource::EndBlock
+        ]
+    );
+
+    let events: Vec<_> = response.events_of_type("someevent2").collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].0, EventSource::DeliverTx(1));
+    assert_eq!(
+        events[0].1.attr("delegator").unwrap().as_str(),
+        "cosmos10a7evyydck42nhta93tnmv7yu4haqzt94xyu54"
+    );
+    assert_eq!(events[1].0, EventSource::EndBlock);
+    assert_eq!(events[1].1.attr("action").unwrap().as_str(), "unbond");
+}
+
 #[test]
 fn blockchain() {
     let response =
-        endpoint::blockchain::Response::from_string(&read_json_fixture("blockchain")).unwrap();
+        endpoint::blockchain::Response::from_string(read_json_fixture("blockchain")).unwrap();
 
     assert_eq!(response.last_height.value(), 488_556);
     assert_eq!(response.block_metas.len(), 10);
@@ -148,9 +193,30 @@ fn blockchain() {
     assert_eq!(block_meta.header.chain_id.as_str(), EXAMPLE_CHAIN)
 }
 
+#[test]
+fn broadcast_evidence() {
+    let block =
+        endpoint::block::Response::from_string(read_json_fixture("block_with_evidences")).unwrap();
+    let evidence = block.block.evidence.iter().next().unwrap().clone();
+
+    let request = serde_json::to_value(endpoint::evidence::Request::new(evidence)).unwrap();
+    assert_eq!(
+        request["evidence"]["type"],
+        "tendermint/DuplicateVoteEvidence"
+    );
+
+    let response =
+        endpoint::evidence::Response::from_string(read_json_fixture("broadcast_evidence")).unwrap();
+
+    assert_eq!(
+        &response.hash.to_string(),
+        "88D4266FD4E6338D13B845FCF289579D209C897823B9217DA3E161936F031589"
+    );
+}
+
 #[test]
 fn broadcast_tx_async() {
-    let response = endpoint::broadcast::tx_async::Response::from_string(&read_json_fixture(
+    let response = endpoint::broadcast::tx_async::Response::from_string(read_json_fixture(
         "broadcast_tx_async",
     ))
     .unwrap();
@@ -163,10 +229,9 @@ fn broadcast_tx_async() {
 
 #[test]
 fn broadcast_tx_sync() {
-    let response = endpoint::broadcast::tx_sync::Response::from_string(&read_json_fixture(
-        "broadcast_tx_sync",
-    ))
-    .unwrap();
+    let response =
+        endpoint::broadcast::tx_sync::Response::from_string(read_json_fixture("broadcast_tx_sync"))
+            .unwrap();
 
     assert_eq!(response.code, Code::Ok);
     assert_eq!(
@@ -177,7 +242,7 @@ fn broadcast_tx_sync() {
 
 #[test]
 fn broadcast_tx_sync_int() {
-    let response = endpoint::broadcast::tx_sync::Response::from_string(&read_json_fixture(
+    let response = endpoint::broadcast::tx_sync::Response::from_string(read_json_fixture(
         "broadcast_tx_sync_int",
     ))
     .unwrap();
@@ -191,7 +256,7 @@ fn broadcast_tx_sync_int() {
 
 #[test]
 fn broadcast_tx_commit() {
-    let response = endpoint::broadcast::tx_commit::Response::from_string(&read_json_fixture(
+    let response = endpoint::broadcast::tx_commit::Response::from_string(read_json_fixture(
         "broadcast_tx_commit",
     ))
     .unwrap();
@@ -212,7 +277,7 @@ fn broadcast_tx_commit() {
 
 #[test]
 fn broadcast_tx_commit_null_data() {
-    let response = endpoint::broadcast::tx_commit::Response::from_string(&read_json_fixture(
+    let response = endpoint::broadcast::tx_commit::Response::from_string(read_json_fixture(
         "broadcast_tx_commit_null_data",
     ))
     .unwrap();
@@ -225,7 +290,7 @@ fn broadcast_tx_commit_null_data() {
 
 #[test]
 fn commit() {
-    let response = endpoint::commit::Response::from_string(&read_json_fixture("commit")).unwrap();
+    let response = endpoint::commit::Response::from_string(read_json_fixture("commit")).unwrap();
     let header = response.signed_header.header;
     assert_eq!(header.chain_id.as_ref(), "dockerchain");
     // For now we just want to make sure the commit including precommits and a block_id exist
@@ -239,16 +304,76 @@ fn commit() {
 
 #[test]
 fn commit_height_1() {
-    let response = endpoint::commit::Response::from_string(&read_json_fixture("commit_1")).unwrap();
+    let response = endpoint::commit::Response::from_string(read_json_fixture("commit_1")).unwrap();
     let header = response.signed_header.header;
     let commit = response.signed_header.commit;
     let block_id = commit.block_id;
     assert_eq!(header.hash(), block_id.hash);
 }
 
+#[test]
+fn consensus_params() {
+    let response =
+        endpoint::consensus_params::Response::from_string(read_json_fixture("consensus_params"))
+            .unwrap();
+
+    assert_eq!(response.block_height.value(), 42);
+    let params = response.consensus_params;
+    assert_eq!(params.block.max_bytes, 22_020_096);
+    assert_eq!(params.block.max_gas, -1);
+    assert_eq!(params.evidence.max_bytes, 1_048_576);
+    params.validate().unwrap();
+}
+
+#[test]
+fn dump_consensus_state() {
+    let response = endpoint::dump_consensus_state::Response::from_string(read_json_fixture(
+        "dump_consensus_state",
+    ))
+    .unwrap();
+
+    let round_state = response.round_state;
+    assert_eq!(round_state.height.value(), 7185);
+    assert_eq!(round_state.step, RoundStep::NewHeight);
+    assert_eq!(round_state.locked_round, None);
+    assert_eq!(round_state.validators.validators().len(), 1);
+    assert_eq!(round_state.validators.total_voting_power().value(), 10);
+    assert_eq!(round_state.votes[0].prevotes, vec![RoundVote::Nil]);
+
+    let last_commit = round_state.last_commit.unwrap();
+    match &last_commit.votes[0] {
+        RoundVote::Vote(vote) => {
+            assert_eq!(vote.height.value(), 7184);
+            assert_eq!(vote.vote_type, vote::Type::Precommit);
+        }
+        RoundVote::Nil => panic!("expected a precommit"),
+    }
+    assert_eq!(last_commit.peer_maj_23s.len(), 1);
+
+    assert_eq!(response.peers.len(), 2);
+    let peer_state = &response.peers[0].peer_state;
+    assert_eq!(
+        peer_state.round_state.height_round_step().to_string(),
+        "7185/0/1"
+    );
+    assert!(!peer_state.round_state.proposal);
+    let last_commit = peer_state.round_state.last_commit.as_ref().unwrap();
+    assert_eq!(last_commit.count_ones(), 1);
+    assert!(peer_state.round_state.catchup_commit.is_none());
+    assert_eq!(peer_state.stats.votes, 7122);
+    assert_eq!(peer_state.stats.block_parts, 7184);
+
+    // A peer which did not report its round state yet
+    let peer_state = &response.peers[1].peer_state;
+    assert_eq!(
+        peer_state.round_state.height_round_step().to_string(),
+        "0/-1/0"
+    );
+}
+
 #[test]
 fn genesis() {
-    let response = endpoint::genesis::Response::from_string(&read_json_fixture("genesis")).unwrap();
+    let response = endpoint::genesis::Response::from_string(read_json_fixture("genesis")).unwrap();
 
     let tendermint::Genesis {
         chain_id,
@@ -262,31 +387,80 @@ fn genesis() {
 
 #[test]
 fn health() {
-    endpoint::health::Response::from_string(&read_json_fixture("health")).unwrap();
+    endpoint::health::Response::from_string(read_json_fixture("health")).unwrap();
 }
 
 #[test]
 fn net_info() {
     let response =
-        endpoint::net_info::Response::from_string(&read_json_fixture("net_info")).unwrap();
+        endpoint::net_info::Response::from_string(read_json_fixture("net_info")).unwrap();
 
     assert_eq!(response.n_peers, 2);
     assert_eq!(response.peers[0].node_info.network.as_str(), EXAMPLE_CHAIN);
+    assert!(response.listeners[0].address().is_none());
+
+    // The second peer listens on all of its interfaces
+    let peer = &response.peers[1];
+    assert_eq!(peer.node_info.moniker.to_string(), "kraang");
+    assert_eq!(
+        peer.address().unwrap().to_string(),
+        "tcp://A5CEAAD3A1907665B2514DB4E741939F0A5AB7DD@77.66.55.44:26656"
+    );
+
+    let channel_ids: Vec<_> = peer
+        .node_info
+        .channels
+        .ids()
+        .unwrap()
+        .into_iter()
+        .map(|id| id.value())
+        .collect();
+    assert_eq!(
+        channel_ids,
+        vec![0x40, 0x20, 0x21, 0x22, 0x23, 0x30, 0x38, 0x00]
+    );
+    let channel = &peer.connection_status.channels[0];
+    assert!(channel_ids.contains(&channel.id.value()));
+}
+
+#[test]
+fn num_unconfirmed_txs() {
+    let response = endpoint::num_unconfirmed_txs::Response::from_string(read_json_fixture(
+        "num_unconfirmed_txs",
+    ))
+    .unwrap();
+
+    assert_eq!(response.total, 3);
+    assert_eq!(response.total_bytes, 33);
 }
 
 #[test]
 fn status() {
-    let response = endpoint::status::Response::from_string(&read_json_fixture("status")).unwrap();
+    let response = endpoint::status::Response::from_string(read_json_fixture("status")).unwrap();
 
     assert_eq!(response.node_info.network.as_str(), EXAMPLE_CHAIN);
     assert_eq!(response.sync_info.latest_block_height.value(), 410_744);
     assert_eq!(response.validator_info.power.value(), 0);
 }
 
+#[test]
+fn unconfirmed_txs() {
+    let response =
+        endpoint::unconfirmed_txs::Response::from_string(read_json_fixture("unconfirmed_txs"))
+            .unwrap();
+
+    assert_eq!(response.n_txs, 2);
+    assert_eq!(response.total, 3);
+    assert_eq!(response.total_bytes, 33);
+    assert_eq!(response.txs.len(), 2);
+    assert_eq!(response.txs[0].as_bytes(), b"key1=value1");
+    assert_eq!(response.txs[1].as_bytes(), b"key2=value2");
+}
+
 #[test]
 fn validators() {
     let response =
-        endpoint::validators::Response::from_string(&read_json_fixture("validators")).unwrap();
+        endpoint::validators::Response::from_string(read_json_fixture("validators")).unwrap();
 
     assert_eq!(response.block_height.value(), 42);
 
@@ -296,7 +470,7 @@ fn validators() {
 
 #[test]
 fn jsonrpc_error() {
-    let result = endpoint::blockchain::Response::from_string(&read_json_fixture("error"));
+    let result = endpoint::blockchain::Response::from_string(read_json_fixture("error"));
 
     match result {
         Err(Error(ErrorDetail::Response(e), _)) => {
@@ -314,7 +488,7 @@ fn jsonrpc_error() {
 
 #[test]
 fn tx_no_prove() {
-    let tx = endpoint::tx::Response::from_string(&read_json_fixture("tx_no_prove")).unwrap();
+    let tx = endpoint::tx::Response::from_string(read_json_fixture("tx_no_prove")).unwrap();
 
     assert_eq!(
         "291B44C883803751917D547238EAC419E968C0171A3154D777B2EA8EA5039C57",
@@ -332,11 +506,18 @@ fn tx_no_prove() {
     );
 
     assert!(tx.proof.is_none());
+
+    let mut header = endpoint::block::Response::from_string(read_json_fixture("block"))
+        .unwrap()
+        .block
+        .header;
+    header.height = tx.height;
+    assert!(tx.verify_inclusion(&header).is_err());
 }
 
 #[test]
 fn tx_with_prove() {
-    let tx = endpoint::tx::Response::from_string(&read_json_fixture("tx_with_prove")).unwrap();
+    let tx = endpoint::tx::Response::from_string(read_json_fixture("tx_with_prove")).unwrap();
 
     assert_eq!(
         "291B44C883803751917D547238EAC419E968C0171A3154D777B2EA8EA5039C57",
@@ -375,21 +556,36 @@ fn tx_with_prove() {
             68, 87, 22, 14, 235, 208, 43, 62, 93, 129, 228, 237, 222, 77, 146, 245, 107, 123, 173,
             19, 73, 154, 174, 249
         ],
-        proof.data
+        proof.data.as_bytes()
     );
     assert_eq!(
         vec![
             105, 196, 2, 216, 75, 198, 114, 80, 111, 27, 54, 17, 4, 107, 139, 37, 40, 156, 38, 0,
             253, 122, 0, 118, 137, 197, 148, 154, 51, 32, 101, 87
         ],
-        proof.root_hash
+        proof.root_hash.as_bytes()
     );
+
+    let mut header = endpoint::block::Response::from_string(read_json_fixture("block"))
+        .unwrap()
+        .block
+        .header;
+    header.height = tx.height;
+    header.data_hash = Some(proof.root_hash);
+    tx.verify_inclusion(&header).unwrap();
+
+    // Header of another block
+    header.data_hash = Some(Hash::Sha256([0xAB; 32]));
+    assert!(tx.verify_inclusion(&header).is_err());
+    header.data_hash = Some(proof.root_hash);
+    header.height = header.height.increment();
+    assert!(tx.verify_inclusion(&header).is_err());
 }
 
 #[test]
 fn tx_search_no_prove() {
     let response =
-        endpoint::tx_search::Response::from_string(&read_json_fixture("tx_search_no_prove"))
+        endpoint::tx_search::Response::from_string(read_json_fixture("tx_search_no_prove"))
             .unwrap();
 
     assert_eq!(8, response.total_count);
@@ -411,7 +607,7 @@ fn tx_search_no_prove() {
 #[test]
 fn tx_search_with_prove() {
     let response =
-        endpoint::tx_search::Response::from_string(&read_json_fixture("tx_search_with_prove"))
+        endpoint::tx_search::Response::from_string(read_json_fixture("tx_search_with_prove"))
             .unwrap();
 
     assert_eq!(8, response.total_count);
@@ -424,14 +620,14 @@ fn tx_search_with_prove() {
     let proof = response.txs[0].proof.as_ref().unwrap();
     assert_eq!(
         vec![97, 115, 121, 110, 99, 45, 107, 101, 121, 61, 118, 97, 108, 117, 101],
-        proof.data
+        proof.data.as_bytes()
     );
     assert_eq!(
         vec![
             245, 70, 67, 176, 5, 16, 101, 200, 125, 163, 26, 101, 69, 49, 182, 95, 155, 87, 56, 15,
             155, 243, 51, 47, 245, 188, 167, 88, 69, 103, 38, 140
         ],
-        proof.root_hash
+        proof.root_hash.as_bytes()
     );
 
     let events = &response.txs[0].tx_result.events;
@@ -444,13 +640,13 @@ fn tx_search_with_prove() {
 #[test]
 fn consensus_state() {
     let response =
-        endpoint::consensus_state::Response::from_string(&read_json_fixture("consensus_state"))
+        endpoint::consensus_state::Response::from_string(read_json_fixture("consensus_state"))
             .unwrap();
 
     let hrs = &response.round_state.height_round_step;
     assert_eq!(hrs.height.value(), 1262197);
-    assert_eq!(hrs.round.value(), 0);
-    assert_eq!(hrs.step, 8);
+    assert_eq!(hrs.round.unwrap().value(), 0);
+    assert_eq!(hrs.step, Some(RoundStep::Commit));
 
     let hvs = &response.round_state.height_vote_set;
     assert_eq!(hvs.len(), 1);
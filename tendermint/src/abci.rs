@@ -1,7 +1,9 @@
 //! Application BlockChain Interface (ABCI)
 //!
 //! NOTE: This module contains types for ABCI responses as consumed from RPC
-//! endpoints. It does not contain an ABCI protocol implementation.
+//! endpoints, and for the information about validators delivered to
+//! applications in `BeginBlock`. It does not contain an ABCI protocol
+//! implementation.
 //!
 //! For that, see:
 //!
@@ -11,7 +13,9 @@ mod code;
 mod data;
 mod gas;
 mod info;
+mod last_commit_info;
 mod log;
+mod misbehavior;
 mod path;
 pub mod responses;
 pub mod tag;
@@ -22,8 +26,13 @@ pub use self::{
     data::Data,
     gas::Gas,
     info::Info,
+    last_commit_info::{LastCommitInfo, Validator, VoteInfo},
     log::Log,
+    misbehavior::{Misbehavior, MisbehaviorKind},
     path::Path,
-    responses::{DeliverTx, Event, Responses},
+    responses::{
+        composite_key, events_by_composite_key, results_hash, split_composite_key, DeliverTx,
+        Event, Responses,
+    },
     transaction::Transaction,
 };
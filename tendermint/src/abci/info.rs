@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
 /// ABCI info
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 pub struct Info(String);
 
 impl AsRef<str> for Info {
@@ -16,9 +16,3 @@ impl Display for Info {
         write!(f, "{}", self.0)
     }
 }
-
-impl Default for Info {
-    fn default() -> Self {
-        Self(String::new())
-    }
-}
@@ -0,0 +1,202 @@
+//! Votes for the last block, as delivered to applications in `BeginBlock`.
+
+use crate::{account, block, vote, Error};
+use std::convert::{TryFrom, TryInto};
+use tendermint_proto::abci::{
+    LastCommitInfo as RawLastCommitInfo, Validator as RawValidator, VoteInfo as RawVoteInfo,
+};
+use tendermint_proto::Protobuf;
+
+/// Validator, as known to applications: its address and its voting power,
+/// without its public key.
+///
+/// This type corresponds to the `Validator` proto from:
+///
+/// <https://github.com/tendermint/tendermint/blob/master/proto/tendermint/abci/types.proto>
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Validator {
+    /// The first 20 bytes of SHA256(public key)
+    pub address: account::Id,
+
+    /// The voting power of the validator
+    pub power: vote::Power,
+}
+
+impl Protobuf<RawValidator> for Validator {}
+
+impl TryFrom<RawValidator> for Validator {
+    type Error = Error;
+
+    fn try_from(value: RawValidator) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: value.address.try_into()?,
+            power: value.power.try_into()?,
+        })
+    }
+}
+
+impl From<Validator> for RawValidator {
+    fn from(value: Validator) -> Self {
+        Self {
+            address: value.address.into(),
+            power: value.power.into(),
+        }
+    }
+}
+
+/// Vote of a validator of the last block, ie. whether the last commit
+/// includes its signature.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct VoteInfo {
+    /// The validator
+    pub validator: Validator,
+
+    /// Whether the validator signed the last block
+    pub signed_last_block: bool,
+}
+
+impl Protobuf<RawVoteInfo> for VoteInfo {}
+
+impl TryFrom<RawVoteInfo> for VoteInfo {
+    type Error = Error;
+
+    fn try_from(value: RawVoteInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            validator: value
+                .validator
+                .ok_or_else(Error::missing_validator)?
+                .try_into()?,
+            signed_last_block: value.signed_last_block,
+        })
+    }
+}
+
+impl From<VoteInfo> for RawVoteInfo {
+    fn from(value: VoteInfo) -> Self {
+        Self {
+            validator: Some(value.validator.into()),
+            signed_last_block: value.signed_last_block,
+        }
+    }
+}
+
+/// Votes of the validators of the last block, for applications to reward
+/// or punish them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LastCommitInfo {
+    /// The round of the last commit
+    pub round: block::Round,
+
+    /// The votes of all the validators of the last block, including those
+    /// which did not sign it
+    pub votes: Vec<VoteInfo>,
+}
+
+impl LastCommitInfo {
+    /// Validators which signed the last block
+    pub fn signers(&self) -> impl Iterator<Item = &Validator> {
+        self.votes
+            .iter()
+            .filter(|vote| vote.signed_last_block)
+            .map(|vote| &vote.validator)
+    }
+
+    /// Validators of the last block which did not sign it
+    pub fn absent_validators(&self) -> impl Iterator<Item = &Validator> {
+        self.votes
+            .iter()
+            .filter(|vote| !vote.signed_last_block)
+            .map(|vote| &vote.validator)
+    }
+}
+
+impl Protobuf<RawLastCommitInfo> for LastCommitInfo {}
+
+impl TryFrom<RawLastCommitInfo> for LastCommitInfo {
+    type Error = Error;
+
+    fn try_from(value: RawLastCommitInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            round: value.round.try_into()?,
+            votes: value
+                .votes
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<LastCommitInfo> for RawLastCommitInfo {
+    fn from(value: LastCommitInfo) -> Self {
+        Self {
+            round: value.round.into(),
+            votes: value.votes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    fn raw_vote(address: u8, power: i64, signed_last_block: bool) -> RawVoteInfo {
+        RawVoteInfo {
+            validator: Some(RawValidator {
+                address: vec![address; account::LENGTH],
+                power,
+            }),
+            signed_last_block,
+        }
+    }
+
+    #[test]
+    fn last_commit_info_round_trip() {
+        let raw = RawLastCommitInfo {
+            round: 2,
+            votes: vec![raw_vote(1, 10, true), raw_vote(2, 20, false)],
+        };
+        let info = LastCommitInfo::try_from(raw.clone()).unwrap();
+
+        assert_eq!(info.round.value(), 2);
+        let signers: Vec<_> = info.signers().map(|v| v.power.value()).collect();
+        assert_eq!(signers, vec![10]);
+        let absent: Vec<_> = info.absent_validators().map(|v| v.address).collect();
+        assert_eq!(absent, vec![account::Id::new([2; account::LENGTH])]);
+
+        assert_eq!(RawLastCommitInfo::from(info.clone()), raw);
+        let bytes = info.encode_vec().unwrap();
+        assert_eq!(LastCommitInfo::decode_vec(&bytes).unwrap(), info);
+    }
+
+    #[test]
+    fn rejects_invalid_votes() {
+        let missing_validator = RawLastCommitInfo {
+            round: 0,
+            votes: vec![RawVoteInfo {
+                validator: None,
+                signed_last_block: true,
+            }],
+        };
+        match LastCommitInfo::try_from(missing_validator)
+            .unwrap_err()
+            .detail()
+        {
+            ErrorDetail::MissingValidator(_) => {}
+            e => panic!("unexpected error: {}", e),
+        }
+
+        let negative_power = RawLastCommitInfo {
+            round: 0,
+            votes: vec![raw_vote(1, -1, true)],
+        };
+        assert!(LastCommitInfo::try_from(negative_power).is_err());
+
+        let negative_round = RawLastCommitInfo {
+            round: -1,
+            votes: vec![],
+        };
+        assert!(LastCommitInfo::try_from(negative_round).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Misbehavior of validators, as delivered to applications in `BeginBlock`.
+
+use super::Validator;
+use crate::{block, vote, Error, Time};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{self, Display};
+use tendermint_proto::abci::{Evidence as RawMisbehavior, EvidenceType as RawMisbehaviorKind};
+use tendermint_proto::Protobuf;
+
+/// Kind of misbehavior of a validator
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MisbehaviorKind {
+    /// Unknown misbehavior
+    #[default]
+    Unknown,
+
+    /// Votes for different blocks at the same height and round
+    DuplicateVote,
+
+    /// Signature of a conflicting block, forged to attack light clients
+    LightClientAttack,
+}
+
+impl Display for MisbehaviorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            MisbehaviorKind::Unknown => "UNKNOWN",
+            MisbehaviorKind::DuplicateVote => "DUPLICATE_VOTE",
+            MisbehaviorKind::LightClientAttack => "LIGHT_CLIENT_ATTACK",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+impl TryFrom<i32> for MisbehaviorKind {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawMisbehaviorKind::from_i32(value) {
+            Some(RawMisbehaviorKind::Unknown) => Ok(MisbehaviorKind::Unknown),
+            Some(RawMisbehaviorKind::DuplicateVote) => Ok(MisbehaviorKind::DuplicateVote),
+            Some(RawMisbehaviorKind::LightClientAttack) => Ok(MisbehaviorKind::LightClientAttack),
+            None => Err(Error::invalid_misbehavior_kind(value)),
+        }
+    }
+}
+
+impl From<MisbehaviorKind> for i32 {
+    fn from(value: MisbehaviorKind) -> Self {
+        let kind = match value {
+            MisbehaviorKind::Unknown => RawMisbehaviorKind::Unknown,
+            MisbehaviorKind::DuplicateVote => RawMisbehaviorKind::DuplicateVote,
+            MisbehaviorKind::LightClientAttack => RawMisbehaviorKind::LightClientAttack,
+        };
+        kind as i32
+    }
+}
+
+/// Misbehavior of a validator, proven by evidence committed in a block, for
+/// applications to punish the validator.
+///
+/// This type corresponds to the `Evidence` proto from:
+///
+/// <https://github.com/tendermint/tendermint/blob/master/proto/tendermint/abci/types.proto>
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Misbehavior {
+    /// The kind of misbehavior
+    pub kind: MisbehaviorKind,
+
+    /// The offending validator
+    pub validator: Validator,
+
+    /// The height at which the offense occurred
+    pub height: block::Height,
+
+    /// The time of the block at which the offense occurred
+    pub time: Time,
+
+    /// The total voting power of the validator set at the height of the
+    /// offense, for applications which do not store historical validators
+    pub total_voting_power: vote::Power,
+}
+
+impl Protobuf<RawMisbehavior> for Misbehavior {}
+
+impl TryFrom<RawMisbehavior> for Misbehavior {
+    type Error = Error;
+
+    fn try_from(value: RawMisbehavior) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: value.r#type.try_into()?,
+            validator: value
+                .validator
+                .ok_or_else(Error::missing_validator)?
+                .try_into()?,
+            height: value.height.try_into()?,
+            time: value.time.ok_or_else(Error::missing_timestamp)?.into(),
+            total_voting_power: value.total_voting_power.try_into()?,
+        })
+    }
+}
+
+impl From<Misbehavior> for RawMisbehavior {
+    fn from(value: Misbehavior) -> Self {
+        Self {
+            r#type: value.kind.into(),
+            validator: Some(value.validator.into()),
+            height: value.height.into(),
+            time: Some(value.time.into()),
+            total_voting_power: value.total_voting_power.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account;
+    use crate::error::ErrorDetail;
+    use tendermint_proto::abci::Validator as RawValidator;
+
+    fn raw_misbehavior(kind: i32) -> RawMisbehavior {
+        RawMisbehavior {
+            r#type: kind,
+            validator: Some(RawValidator {
+                address: vec![1; account::LENGTH],
+                power: 10,
+            }),
+            height: 42,
+            time: Some(Time::unix_epoch().into()),
+            total_voting_power: 100,
+        }
+    }
+
+    #[test]
+    fn misbehavior_round_trip() {
+        let raw = raw_misbehavior(RawMisbehaviorKind::DuplicateVote as i32);
+        let misbehavior = Misbehavior::try_from(raw.clone()).unwrap();
+
+        assert_eq!(misbehavior.kind, MisbehaviorKind::DuplicateVote);
+        assert_eq!(misbehavior.kind.to_string(), "DUPLICATE_VOTE");
+        assert_eq!(misbehavior.validator.power.value(), 10);
+        assert_eq!(misbehavior.height.value(), 42);
+        assert_eq!(misbehavior.total_voting_power.value(), 100);
+
+        assert_eq!(RawMisbehavior::from(misbehavior), raw);
+        let bytes = misbehavior.encode_vec().unwrap();
+        assert_eq!(Misbehavior::decode_vec(&bytes).unwrap(), misbehavior);
+    }
+
+    #[test]
+    fn rejects_invalid_misbehavior() {
+        match Misbehavior::try_from(raw_misbehavior(3))
+            .unwrap_err()
+            .detail()
+        {
+            ErrorDetail::InvalidMisbehaviorKind(e) => assert_eq!(e.kind, 3),
+            e => panic!("unexpected error: {}", e),
+        }
+
+        let mut missing_time = raw_misbehavior(RawMisbehaviorKind::LightClientAttack as i32);
+        missing_time.time = None;
+        assert!(Misbehavior::try_from(missing_time).is_err());
+    }
+}
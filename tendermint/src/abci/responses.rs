@@ -1,9 +1,22 @@
 //! ABCI response types used by the `/block_results` RPC endpoint.
 
-use super::{code::Code, data::Data, gas::Gas, info::Info, log::Log, tag::Tag};
-use crate::{consensus, serializers, validator};
+use super::{
+    code::Code,
+    data::Data,
+    gas::Gas,
+    info::Info,
+    log::Log,
+    tag::{Tag, Value},
+};
+use crate::merkle::simple_hash_from_byte_vectors;
+use crate::{consensus, serializers, validator, Error, Hash};
+use prost::Message;
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Display};
+use std::str::FromStr;
+use tendermint_proto::abci::{Event as RawEvent, ResponseDeliverTx as RawDeliverTx};
 
 /// Responses for ABCI calls which occur during block processing.
 ///
@@ -30,6 +43,15 @@ pub struct Responses {
     pub end_block: Option<EndBlock>,
 }
 
+impl Responses {
+    /// Merkle root of the results of the transactions, ie. the
+    /// `last_results_hash` of the header of the next block (see
+    /// [`results_hash`])
+    pub fn results_hash(&self) -> Hash {
+        results_hash(&self.deliver_tx)
+    }
+}
+
 /// Return an empty vec in the event `deliver_tx` is `null`
 fn deserialize_deliver_tx<'de, D>(deserializer: D) -> Result<Vec<DeliverTx>, D::Error>
 where
@@ -74,6 +96,36 @@ pub struct DeliverTx {
     pub codespace: Codespace,
 }
 
+impl DeliverTx {
+    /// Protobuf encoding of the deterministic fields of this response, ie. its
+    /// code, data, and gas wanted and used, as committed in block headers
+    pub fn to_deterministic_bytes(&self) -> Vec<u8> {
+        let deterministic = RawDeliverTx {
+            code: self.code.value(),
+            data: self.data.value().clone(),
+            gas_wanted: self.gas_wanted.value() as i64,
+            gas_used: self.gas_used.value() as i64,
+            ..RawDeliverTx::default()
+        };
+        let mut bytes = Vec::with_capacity(deterministic.encoded_len());
+        deterministic
+            .encode(&mut bytes)
+            .expect("vectors have enough capacity");
+        bytes
+    }
+}
+
+/// Merkle root of the deterministic fields of the given results of the
+/// transactions of a block (see [`DeliverTx::to_deterministic_bytes`]), ie.
+/// the `last_results_hash` of the header of the next block
+pub fn results_hash(deliver_tx: &[DeliverTx]) -> Hash {
+    let leaves = deliver_tx
+        .iter()
+        .map(DeliverTx::to_deterministic_bytes)
+        .collect();
+    Hash::Sha256(simple_hash_from_byte_vectors(leaves))
+}
+
 /// Event
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Event {
@@ -85,6 +137,109 @@ pub struct Event {
     pub attributes: Vec<Tag>,
 }
 
+impl Event {
+    /// Create an event of the given type with the given attributes
+    pub fn new(type_str: impl Into<String>, attributes: impl IntoIterator<Item = Tag>) -> Self {
+        Self {
+            type_str: type_str.into(),
+            attributes: attributes.into_iter().collect(),
+        }
+    }
+
+    /// The value of the first attribute with the given key, if any
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        self.attributes
+            .iter()
+            .find(|tag| tag.key.as_str() == key)
+            .map(|tag| &tag.value)
+    }
+
+    /// The values of all the attributes with the given key
+    pub fn attrs<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Value> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |tag| tag.key.as_str() == key)
+            .map(|tag| &tag.value)
+    }
+
+    /// Parse the value of the first attribute with the given key as the
+    /// given type, failing if there is no such attribute
+    pub fn parse_attr<T>(&self, key: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.attr(key)
+            .ok_or_else(|| {
+                Error::invalid_event_attribute(format!(
+                    "missing attribute {:?}",
+                    composite_key(&self.type_str, key)
+                ))
+            })?
+            .parse()
+    }
+
+    /// The attributes of this event along with their composite keys, ie.
+    /// `type.key`, as indexed by Tendermint and used in event queries
+    pub fn composite_attributes(&self) -> impl Iterator<Item = (String, &Value)> + '_ {
+        self.attributes
+            .iter()
+            .map(move |tag| (composite_key(&self.type_str, tag.key.as_str()), &tag.value))
+    }
+}
+
+/// ABCI events are only accepted if all of their attributes are valid UTF-8.
+impl TryFrom<RawEvent> for Event {
+    type Error = Error;
+
+    fn try_from(raw: RawEvent) -> Result<Self, Error> {
+        Ok(Self {
+            type_str: raw.r#type,
+            attributes: raw
+                .attributes
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<Event> for RawEvent {
+    fn from(event: Event) -> Self {
+        Self {
+            r#type: event.type_str,
+            attributes: event.attributes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The composite key of the attribute with the given key of events of the
+/// given type, ie. `type.key`
+pub fn composite_key(type_str: &str, key: &str) -> String {
+    format!("{}.{}", type_str, key)
+}
+
+/// Split the given composite key into its event type and attribute key, at
+/// its first `.`
+pub fn split_composite_key(composite_key: &str) -> Option<(&str, &str)> {
+    let index = composite_key.find('.')?;
+    Some((&composite_key[..index], &composite_key[index + 1..]))
+}
+
+/// The values of the attributes of the given events by composite key, as
+/// in the `events` of RPC subscription events
+pub fn events_by_composite_key<'a>(
+    events: impl IntoIterator<Item = &'a Event>,
+) -> HashMap<String, Vec<String>> {
+    let mut map = HashMap::<String, Vec<String>>::new();
+    for event in events {
+        for (key, value) in event.composite_attributes() {
+            map.entry(key).or_default().push(value.to_string());
+        }
+    }
+    map
+}
+
 /// Begin block response.
 ///
 /// This type corresponds to the `ResponseBeginBlock` proto from:
@@ -129,7 +284,7 @@ where
 }
 
 /// Codespace
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 pub struct Codespace(String);
 
 impl AsRef<str> for Codespace {
@@ -144,8 +299,97 @@ impl Display for Codespace {
     }
 }
 
-impl Default for Codespace {
-    fn default() -> Self {
-        Self(String::new())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn deliver_tx(code: u32, data: &[u8]) -> DeliverTx {
+        DeliverTx {
+            code: code.into(),
+            data: data.to_vec().into(),
+            log: Log::default(),
+            info: Info::default(),
+            gas_wanted: 10.into(),
+            gas_used: 5.into(),
+            events: vec![],
+            codespace: Codespace::default(),
+        }
+    }
+
+    fn transfer() -> Event {
+        Event::new(
+            "transfer",
+            vec![
+                Tag::new("recipient", "cosmos1a"),
+                Tag::new("amount", "10"),
+                Tag::new("recipient", "cosmos1b"),
+            ],
+        )
+    }
+
+    #[test]
+    fn attributes() {
+        let event = transfer();
+        assert_eq!(event.attr("recipient").unwrap().as_str(), "cosmos1a");
+        assert_eq!(event.attrs("recipient").count(), 2);
+        assert!(event.attr("sender").is_none());
+
+        assert_eq!(event.parse_attr::<u64>("amount").unwrap(), 10);
+        assert!(event.parse_attr::<u64>("recipient").is_err());
+        assert!(event.parse_attr::<u64>("sender").is_err());
+    }
+
+    #[test]
+    fn composite_keys() {
+        assert_eq!(composite_key("transfer", "amount"), "transfer.amount");
+        assert_eq!(
+            split_composite_key("transfer.amount"),
+            Some(("transfer", "amount"))
+        );
+        assert_eq!(
+            split_composite_key("tm.event.type"),
+            Some(("tm", "event.type"))
+        );
+        assert_eq!(split_composite_key("amount"), None);
+
+        let events = vec![
+            transfer(),
+            Event::new("message", vec![Tag::new("action", "send")]),
+        ];
+        let map = events_by_composite_key(&events);
+        assert_eq!(map["transfer.recipient"], vec!["cosmos1a", "cosmos1b"]);
+        assert_eq!(map["message.action"], vec!["send"]);
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn deterministic_results_hash() {
+        // The hash of no results is the hash of the empty tree
+        assert_eq!(results_hash(&[]), Hash::Sha256(Sha256::digest(&[]).into()));
+
+        // A single result is a leaf
+        let result = deliver_tx(0, b"one");
+        let bytes = [0x12, 0x03, b'o', b'n', b'e', 0x28, 10, 0x30, 5];
+        assert_eq!(result.to_deterministic_bytes(), bytes);
+        let mut leaf = vec![0];
+        leaf.extend_from_slice(&bytes);
+        assert_eq!(
+            results_hash(std::slice::from_ref(&result)),
+            Hash::Sha256(Sha256::digest(&leaf).into())
+        );
+
+        // Nondeterministic fields are not committed
+        let mut other = result.clone();
+        other.log = "log".into();
+        other.events.push(transfer());
+        assert_eq!(
+            results_hash(&[other]),
+            results_hash(std::slice::from_ref(&result))
+        );
+        assert_ne!(
+            results_hash(&[deliver_tx(14, b"one")]),
+            results_hash(&[result])
+        );
     }
 }
@@ -2,7 +2,10 @@
 
 use crate::error::Error;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::{fmt, str::FromStr};
+use subtle_encoding::base64;
+use tendermint_proto::abci::EventAttribute as RawEventAttribute;
 use tendermint_proto::serializers::bytes::base64string;
 
 /// Tags
@@ -15,6 +18,46 @@ pub struct Tag {
     pub value: Value,
 }
 
+impl Tag {
+    /// Create a tag with the given key and value
+    pub fn new(key: impl Into<Key>, value: impl Into<Value>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// ABCI event attributes are raw bytes, which are only accepted as tags if
+/// they are valid UTF-8, as Tendermint requires to index them.
+impl TryFrom<RawEventAttribute> for Tag {
+    type Error = Error;
+
+    fn try_from(raw: RawEventAttribute) -> Result<Self, Error> {
+        let utf8 = |bytes: Vec<u8>| {
+            String::from_utf8(bytes).map_err(|e| {
+                Error::invalid_event_attribute(format!("not UTF-8: {:?}", e.as_bytes()))
+            })
+        };
+
+        Ok(Self {
+            key: Key(utf8(raw.key)?),
+            value: Value(utf8(raw.value)?),
+        })
+    }
+}
+
+/// Tags are converted to indexed ABCI event attributes.
+impl From<Tag> for RawEventAttribute {
+    fn from(tag: Tag) -> Self {
+        Self {
+            key: tag.key.0.into_bytes(),
+            value: tag.value.0.into_bytes(),
+            index: true,
+        }
+    }
+}
+
 /// Tag keys
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct Key(
@@ -25,6 +68,25 @@ pub struct Key(
     String,
 );
 
+impl Key {
+    /// Borrow this key as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Key {
+    fn from(s: &str) -> Self {
+        Key(s.into())
+    }
+}
+
+impl From<String> for Key {
+    fn from(s: String) -> Self {
+        Key(s)
+    }
+}
+
 impl AsRef<str> for Key {
     fn as_ref(&self) -> &str {
         self.0.as_ref()
@@ -55,6 +117,46 @@ pub struct Value(
     String,
 );
 
+impl Value {
+    /// Borrow this value as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Borrow the UTF-8 bytes of this value
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Parse this value as the given type, eg. a number or a height
+    pub fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.0
+            .parse()
+            .map_err(|e| Error::invalid_event_attribute(format!("{:?}: {}", self.0, e)))
+    }
+
+    /// Decode this value as Base64, for values holding binary data
+    pub fn decode_base64(&self) -> Result<Vec<u8>, Error> {
+        base64::decode(&self.0).map_err(Error::subtle_encoding)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value(s.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value(s)
+    }
+}
+
 impl AsRef<str> for Value {
     fn as_ref(&self) -> &str {
         self.0.as_ref()
@@ -86,4 +188,27 @@ mod test {
         assert_eq!("packet_timeout_height", tag.key.0);
         assert_eq!("0-4840", tag.value.0);
     }
+
+    #[test]
+    fn typed_values() {
+        assert_eq!(Value::from("4840").parse::<u64>().unwrap(), 4840);
+        assert!(Value::from("0-4840").parse::<u64>().is_err());
+        assert_eq!(Value::from("AQID").decode_base64().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn raw_attributes() {
+        let tag = Tag::new("key", "value");
+        let raw = RawEventAttribute::from(tag.clone());
+        assert_eq!(raw.key, b"key");
+        assert!(raw.index);
+        assert_eq!(Tag::try_from(raw).unwrap(), tag);
+
+        let invalid = RawEventAttribute {
+            key: b"key".to_vec(),
+            value: vec![0xff],
+            index: true,
+        };
+        assert!(Tag::try_from(invalid).is_err());
+    }
 }
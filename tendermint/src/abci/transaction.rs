@@ -1,25 +1,47 @@
 //! Transactions
 
 mod hash;
+mod proof;
 
 pub use self::hash::{Hash, LENGTH as HASH_LENGTH};
+pub use self::proof::TxProof;
+use crate::{hash::HashFunction, merkle::simple_hash_from_byte_vectors_with};
+use bytes::Bytes;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
 use std::{fmt, slice};
 use subtle_encoding::base64;
 use tendermint_proto::types::Data as RawData;
 
 /// Transactions are arbitrary byte arrays whose contents are validated by the
 /// underlying Tendermint application.
+///
+/// Their bytes are reference counted, so that cloning a transaction or
+/// converting it from or into [`Bytes`] or `Vec<u8>` does not copy them.
 #[derive(Clone, Debug, Eq, PartialEq)] // Custom serde serialization used by RPC /broadcast_tx_async endpoint
-pub struct Transaction(Vec<u8>);
+pub struct Transaction(Bytes);
 
 impl From<Vec<u8>> for Transaction {
     fn from(value: Vec<u8>) -> Self {
+        Transaction(value.into())
+    }
+}
+
+impl From<Bytes> for Transaction {
+    fn from(value: Bytes) -> Self {
         Transaction(value)
     }
 }
 
 impl From<Transaction> for Vec<u8> {
+    /// Copies the bytes of the transaction only if they are shared with
+    /// another transaction or buffer.
+    fn from(value: Transaction) -> Self {
+        value.0.into()
+    }
+}
+
+impl From<Transaction> for Bytes {
     fn from(value: Transaction) -> Self {
         value.0
     }
@@ -28,7 +50,32 @@ impl From<Transaction> for Vec<u8> {
 impl Transaction {
     /// Borrow the contents of this transaction as a byte slice
     pub fn as_bytes(&self) -> &[u8] {
-        self.0.as_slice()
+        &self.0
+    }
+
+    /// Get the contents of this transaction as [`Bytes`], sharing them
+    pub fn to_bytes(&self) -> Bytes {
+        self.0.clone()
+    }
+
+    /// The size of this transaction in the `data` of a block, ie. in the
+    /// Protobuf encoding of the list of its transactions
+    pub fn proto_size(&self) -> usize {
+        let len = self.0.len();
+        // The key of the field, the length of the transaction and its bytes
+        1 + prost::length_delimiter_len(len) + len
+    }
+
+    /// Compute the hash of this transaction, ie. the SHA-256 digest of its bytes,
+    /// by which it can be looked up once broadcast.
+    pub fn hash(&self) -> Hash {
+        self.hash_with::<Sha256>()
+    }
+
+    /// Compute the hash of this transaction with the given hash function
+    /// instead of SHA-256
+    pub fn hash_with<H: HashFunction>(&self) -> Hash {
+        Hash::new(H::digest(self.as_bytes()).into())
     }
 }
 
@@ -81,13 +128,7 @@ impl From<RawData> for Data {
             return Data::default();
         }
         Data {
-            txs: Some(
-                value
-                    .txs
-                    .iter()
-                    .map(|tx| Transaction::from(tx.clone()))
-                    .collect(),
-            ),
+            txs: Some(value.txs.into_iter().map(Transaction::from).collect()),
         }
     }
 }
@@ -101,29 +142,62 @@ impl From<Data> for RawData {
             txs: value
                 .txs
                 .unwrap_or_default()
-                .iter()
-                .map(|tx| tx.clone().into())
+                .into_iter()
+                .map(Into::into)
                 .collect(),
         }
     }
 }
 
 impl Data {
+    /// Create a new transaction data collection
+    pub fn new<I>(into_transactions: I) -> Data
+    where
+        I: Into<Vec<Transaction>>,
+    {
+        Data {
+            txs: Some(into_transactions.into()),
+        }
+    }
+
     /// Iterate over the transactions in the collection
     pub fn iter(&self) -> slice::Iter<'_, Transaction> {
         self.as_ref().iter()
     }
+
+    /// The size of the Protobuf encoding of the collection, ie. the sum of the
+    /// [`Transaction::proto_size`]s of its transactions
+    pub fn proto_size(&self) -> usize {
+        self.iter().map(Transaction::proto_size).sum()
+    }
+
+    /// Compute the Merkle root of the hashes of the transactions in the
+    /// collection, ie. the `data_hash` of the header of the block they are in.
+    pub fn hash(&self) -> crate::Hash {
+        self.hash_with::<Sha256>()
+    }
+
+    /// Compute the Merkle root of the hashes of the transactions in the
+    /// collection with the given hash function instead of SHA-256
+    pub fn hash_with<H: HashFunction>(&self) -> crate::Hash {
+        let tx_hashes = self
+            .iter()
+            .map(|tx| tx.hash_with::<H>().as_bytes().to_vec())
+            .collect();
+        crate::Hash::Sha256(simple_hash_from_byte_vectors_with::<H>(tx_hashes))
+    }
 }
 
 impl AsRef<[Transaction]> for Data {
     fn as_ref(&self) -> &[Transaction] {
-        self.txs.as_deref().unwrap_or_else(|| &[])
+        self.txs.as_deref().unwrap_or(&[])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Transaction;
+    use super::{Bytes, Data, Transaction};
+    use std::str::FromStr;
 
     #[test]
     fn upper_hex_serialization() {
@@ -131,4 +205,83 @@ mod tests {
         let tx_hex = format!("{:X}", &tx);
         assert_eq!(&tx_hex, "FF01FE02");
     }
+
+    #[test]
+    fn conversions_share_bytes() {
+        let bytes = b"key=value".to_vec();
+        let ptr = bytes.as_ptr();
+
+        let tx = Transaction::from(bytes);
+        assert_eq!(tx.as_bytes().as_ptr(), ptr);
+        let shared = tx.to_bytes();
+        assert_eq!(shared.as_ptr(), ptr);
+        drop(shared);
+        let bytes = Vec::from(tx);
+        assert_eq!(bytes.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn proto_conversions_share_bytes() {
+        let tx = Bytes::from_static(b"key=value");
+        let ptr = tx.as_ptr();
+
+        let data = Data::from(tendermint_proto::types::Data { txs: vec![tx] });
+        assert_eq!(data.iter().next().unwrap().as_bytes().as_ptr(), ptr);
+        let raw = tendermint_proto::types::Data::from(data);
+        assert_eq!(raw.txs[0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn proto_size() {
+        let data = Data::new(vec![
+            Transaction::from(vec![0; 3]),
+            Transaction::from(vec![0; 200]),
+        ]);
+        assert_eq!(data.iter().next().unwrap().proto_size(), 5);
+        assert_eq!(data.proto_size(), 5 + 203);
+        assert_eq!(
+            data.proto_size(),
+            prost::Message::encoded_len(&tendermint_proto::types::Data::from(data))
+        );
+    }
+
+    // The following vectors are taken from the `tx_search` RPC fixtures of a
+    // kvstore node, which give the hash of each transaction and the root hash
+    // of the transactions of its block in its Merkle proof.
+
+    #[test]
+    fn transaction_hash() {
+        let tx = Transaction::from(b"async-key=value".to_vec());
+        assert_eq!(
+            tx.hash().to_string(),
+            "9F28904F9C0F3AB74A81CBA48E39124DA1C680B47FBFCBA0126870DB722BCC30"
+        );
+    }
+
+    #[test]
+    fn data_hash() {
+        let data = Data::from(tendermint_proto::types::Data {
+            txs: vec![
+                Bytes::from_static(b"async-key=value"),
+                Bytes::from_static(b"sync-key=value"),
+                Bytes::from_static(b"commit-key=value"),
+            ],
+        });
+        assert_eq!(
+            data.hash(),
+            crate::Hash::from_str(
+                "F54643B0051065C87DA31A654531B65F9B57380F9BF3332FF5BCA7584567268C"
+            )
+            .unwrap()
+        );
+
+        // The hash of an empty tree, as found in the headers of empty blocks
+        assert_eq!(
+            Data::default().hash(),
+            crate::Hash::from_str(
+                "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+            )
+            .unwrap()
+        );
+    }
 }
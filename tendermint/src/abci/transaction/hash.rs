@@ -82,7 +82,7 @@ impl FromStr for Hash {
             .map_err(Error::subtle_encoding)?;
 
         if bytes.len() != LENGTH {
-            return Err(Error::invalid_hash_size());
+            return Err(Error::invalid_hash_size(LENGTH, bytes.len()));
         }
 
         let mut result_bytes = [0u8; LENGTH];
@@ -0,0 +1,133 @@
+//! Proofs of the inclusion of transactions in blocks
+
+use super::{Data, Transaction};
+use crate::{merkle::proof::SimpleProof, Error, Hash};
+use serde::{Deserialize, Serialize};
+use std::convert::{TryFrom, TryInto};
+use tendermint_proto::types::TxProof as RawTxProof;
+use tendermint_proto::Protobuf;
+
+/// Proof of the inclusion of a transaction in the `data` of a block, as
+/// returned by the `/tx` endpoint when called with `prove=true`
+///
+/// <https://github.com/tendermint/tendermint/blob/v0.34.11/types/tx.go#L81>
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawTxProof", into = "RawTxProof")]
+pub struct TxProof {
+    /// The root of the Merkle tree of the hashes of the transactions of the
+    /// block, ie. its `data_hash`
+    pub root_hash: Hash,
+    /// The transaction
+    pub data: Transaction,
+    /// The proof of the inclusion of the hash of the transaction in the tree
+    pub proof: SimpleProof,
+}
+
+impl TxProof {
+    /// Create the proof of the inclusion of the transaction with the given
+    /// index in the given transactions of a block.
+    ///
+    /// Returns `None` if there is no transaction with the given index.
+    pub fn new(txs: &Data, index: usize) -> Option<Self> {
+        let tx_hashes: Vec<Vec<u8>> = txs.iter().map(|tx| tx.hash().as_bytes().to_vec()).collect();
+
+        Some(Self {
+            root_hash: txs.hash(),
+            data: txs.as_ref().get(index)?.clone(),
+            proof: SimpleProof::from_byte_vectors(&tx_hashes, index)?,
+        })
+    }
+
+    /// Verify that the transaction of this proof is included in the block
+    /// with the given `data_hash`, as done by `TxProof.Validate` in Go.
+    pub fn validate(&self, data_hash: &Hash) -> Result<(), Error> {
+        if &self.root_hash != data_hash {
+            return Err(Error::invalid_merkle_proof(format!(
+                "root hash {} does not match the data hash {}",
+                self.root_hash, data_hash
+            )));
+        }
+
+        let root_hash = match self.root_hash {
+            Hash::Sha256(root_hash) => root_hash,
+            Hash::None => return Err(Error::invalid_merkle_proof("empty root hash".to_string())),
+        };
+
+        // The leaves of the tree are the hashes of the transactions.
+        self.proof.verify(&root_hash, self.data.hash().as_bytes())
+    }
+}
+
+impl Protobuf<RawTxProof> for TxProof {}
+
+impl TryFrom<RawTxProof> for TxProof {
+    type Error = Error;
+
+    fn try_from(value: RawTxProof) -> Result<Self, Self::Error> {
+        Ok(Self {
+            root_hash: value.root_hash.try_into()?,
+            data: value.data.into(),
+            proof: value.proof.ok_or_else(Error::missing_proof)?.try_into()?,
+        })
+    }
+}
+
+impl From<TxProof> for RawTxProof {
+    fn from(value: TxProof) -> Self {
+        Self {
+            root_hash: value.root_hash.into(),
+            data: value.data.into(),
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn txs() -> Data {
+        Data::new(
+            (0_u8..5)
+                .map(|i| Transaction::from(vec![i; 4]))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn validate_proofs() {
+        let txs = txs();
+        let data_hash = txs.hash();
+
+        for index in 0..5 {
+            let proof = TxProof::new(&txs, index).unwrap();
+            assert_eq!(proof.data, txs.as_ref()[index]);
+            assert!(proof.validate(&data_hash).is_ok());
+
+            let decoded = TxProof::decode_vec(&proof.encode_vec().unwrap()).unwrap();
+            assert_eq!(decoded, proof);
+        }
+        assert!(TxProof::new(&txs, 5).is_none());
+    }
+
+    #[test]
+    fn reject_invalid_proofs() {
+        let txs = txs();
+        let proof = TxProof::new(&txs, 1).unwrap();
+
+        // Proof for another block
+        assert!(proof.validate(&Data::default().hash()).is_err());
+
+        // Proof for another transaction
+        let other = TxProof {
+            data: Transaction::from(b"other".to_vec()),
+            ..proof.clone()
+        };
+        assert!(other.validate(&txs.hash()).is_err());
+
+        // Proof for another position
+        let mut moved = proof;
+        moved.proof.index = 2;
+        assert!(moved.validate(&txs.hash()).is_err());
+    }
+}
@@ -1,6 +1,10 @@
 //! Tendermint accounts
 
-use crate::{error::Error, public_key::Ed25519};
+use crate::{
+    error::Error,
+    public_key::{decode_bech32, Ed25519},
+    PublicKey,
+};
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha256};
@@ -10,10 +14,12 @@ use std::{
     str::FromStr,
 };
 use subtle::{self, ConstantTimeEq};
-use subtle_encoding::hex;
+use subtle_encoding::{bech32, hex};
 
 #[cfg(feature = "secp256k1")]
 use crate::public_key::Secp256k1;
+#[cfg(feature = "sr25519")]
+use crate::public_key::Sr25519;
 #[cfg(feature = "secp256k1")]
 use ripemd160::Ripemd160;
 use std::convert::TryFrom;
@@ -23,7 +29,10 @@ use tendermint_proto::Protobuf;
 pub const LENGTH: usize = 20;
 
 /// Account IDs
-#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+///
+/// Account IDs are compared in constant time.
+#[allow(clippy::derived_hash_with_manual_eq)]
+#[derive(Copy, Clone, Eq, Hash, PartialOrd, Ord)]
 pub struct Id([u8; LENGTH]); // JSON custom serialization for priv_validator_key.json
 
 impl Protobuf<Vec<u8>> for Id {}
@@ -33,7 +42,7 @@ impl TryFrom<Vec<u8>> for Id {
 
     fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
         if value.len() != LENGTH {
-            return Err(Error::invalid_account_id_length());
+            return Err(Error::invalid_account_id_length(LENGTH, value.len()));
         }
         let mut slice: [u8; LENGTH] = [0; LENGTH];
         slice.copy_from_slice(&value[..]);
@@ -57,6 +66,19 @@ impl Id {
     pub fn as_bytes(&self) -> &[u8] {
         &self.0[..]
     }
+
+    /// Serialize this account ID as Bech32 with the given human readable prefix,
+    /// eg. `cosmos` for account addresses or `cosmosvalcons` for consensus
+    /// addresses in the Cosmos Hub.
+    pub fn to_bech32(&self, hrp: &str) -> String {
+        bech32::encode(hrp, self.0)
+    }
+
+    /// Parse an account ID serialized as Bech32, checking that it has the given
+    /// human readable prefix.
+    pub fn from_bech32(encoded: &str, hrp: &str) -> Result<Self, Error> {
+        decode_bech32(encoded, hrp)?.try_into()
+    }
 }
 
 impl AsRef<[u8]> for Id {
@@ -72,6 +94,12 @@ impl ConstantTimeEq for Id {
     }
 }
 
+impl PartialEq for Id {
+    fn eq(&self, other: &Id) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl Display for Id {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for byte in &self.0 {
@@ -99,6 +127,15 @@ impl From<Secp256k1> for Id {
     }
 }
 
+// SHA256(pk)[:20]
+#[cfg(feature = "sr25519")]
+impl From<Sr25519> for Id {
+    fn from(pk: Sr25519) -> Id {
+        let digest = Sha256::digest(pk.as_bytes());
+        Id(digest[..LENGTH].try_into().unwrap())
+    }
+}
+
 // SHA256(pk)[:20]
 impl From<Ed25519> for Id {
     fn from(pk: Ed25519) -> Id {
@@ -107,6 +144,20 @@ impl From<Ed25519> for Id {
     }
 }
 
+/// Address of the given public key, derived as Tendermint Core does for its
+/// type: RIPEMD160(SHA256(pk)) for secp256k1 keys, SHA256(pk)[:20] otherwise
+impl From<PublicKey> for Id {
+    fn from(pub_key: PublicKey) -> Id {
+        match pub_key {
+            PublicKey::Ed25519(pk) => Id::from(pk),
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(pk) => Id::from(pk),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => Id::from(pk),
+        }
+    }
+}
+
 /// Decode account ID from hex
 impl FromStr for Id {
     type Err = Error;
@@ -150,6 +201,7 @@ impl Serialize for Id {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ErrorDetail;
 
     #[test]
     fn test_ed25519_id() {
@@ -168,6 +220,53 @@ mod tests {
         assert_eq!(id_bytes.ct_eq(&id).unwrap_u8(), 1);
     }
 
+    #[test]
+    fn test_public_key_id() {
+        // Addresses of ed25519 keys of Tendermint Core nodes, from the
+        // `priv_validator_key.json` and `/validators` fixtures
+        let vectors = [
+            (
+                "8mv0sqLoTOt6U8PxrndAh3myAGR4L7rb3w42WVnuRTQ=",
+                "AD7DAE5FEC609CF02F9BDE7D81D0C3CD66141563",
+            ),
+            (
+                "9tK9IT+FPdf2qm+5c2qaxi10sWP+3erWTKgftn2PaQM=",
+                "000001E443FD237E4B616E2FA69DF4EE3D49A94F",
+            ),
+        ];
+        for (pubkey_base64, id_hex) in vectors.iter() {
+            let pubkey_bytes = subtle_encoding::base64::decode(pubkey_base64).unwrap();
+            let pubkey = PublicKey::from_raw_ed25519(&pubkey_bytes).unwrap();
+            assert_eq!(Id::from(pubkey), Id::from_str(id_hex).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        let err = Id::try_from(vec![0; 3]).unwrap_err();
+        match err.detail() {
+            ErrorDetail::InvalidAccountIdLength(e) if e.expected == LENGTH && e.found == 3 => {}
+            _ => panic!("unexpected error: {}", err),
+        }
+        assert_eq!(
+            err.detail().to_string(),
+            "invalid account ID length: expected 20 bytes, found 3"
+        );
+    }
+
+    #[test]
+    fn test_bech32() {
+        let id = Id::from_str("0CDA3F47EF3C4906693B170EF650EB968C5F4B2C").unwrap();
+
+        let encoded = id.to_bech32("cosmosvalcons");
+        assert!(encoded.starts_with("cosmosvalcons1"));
+        assert_eq!(Id::from_bech32(&encoded, "cosmosvalcons").unwrap(), id);
+
+        assert!(Id::from_bech32(&encoded, "cosmos").is_err());
+        let too_short = bech32::encode("cosmosvalcons", [0; 10]);
+        assert!(Id::from_bech32(&too_short, "cosmosvalcons").is_err());
+    }
+
     #[test]
     #[cfg(feature = "secp256k1")]
     fn test_secp_id() {
@@ -186,4 +285,23 @@ mod tests {
 
         assert_eq!(id_bytes.ct_eq(&id).unwrap_u8(), 1);
     }
+
+    #[test]
+    #[cfg(feature = "secp256k1")]
+    fn test_secp_public_key_id() {
+        // Key of the signer of a genesis transaction of a Cosmos SDK chain,
+        // whose address is cosmos14l0fp639yudfl46zauvv8rkzjgd4u0zk0fyvgr
+        let pubkey_base64 = "A6WjB8Rb39iqfkPqTU+2oNa/EFzhzCqo4MPNFiMgNQHQ";
+        let id_hex = "AFDE90EA25271A9FD742EF18C38EC2921B5E3C56";
+
+        let pubkey_bytes = subtle_encoding::base64::decode(pubkey_base64).unwrap();
+        let pubkey = PublicKey::from_raw_secp256k1(&pubkey_bytes).unwrap();
+        let id = Id::from(pubkey);
+
+        assert_eq!(id, Id::from_str(id_hex).unwrap());
+        assert_eq!(
+            id.to_bech32("cosmos"),
+            "cosmos14l0fp639yudfl46zauvv8rkzjgd4u0zk0fyvgr"
+        );
+    }
 }
@@ -0,0 +1,43 @@
+//! Amino encodings of the Tendermint types, as used by Tendermint versions
+//! older than v0.34, for interoperability with the networks still running
+//! them.
+//!
+//! This module provides the Amino binary encodings of votes and their sign
+//! bytes, and of block headers and their hashes. The Amino binary encoding of
+//! public keys is [`PublicKey::to_amino_bytes`], which Bech32 encodings also
+//! use, and their JSON encoding is the same in Amino and Protobuf.
+//!
+//! [`PublicKey::to_amino_bytes`]: crate::PublicKey::to_amino_bytes
+
+pub mod block_id;
+pub mod header;
+pub mod time;
+pub mod vote;
+
+pub use self::{
+    block_id::{BlockId, CanonicalBlockId, CanonicalPartSetHeader, PartSetHeader},
+    header::ConsensusVersion,
+    time::TimeMsg,
+    vote::{CanonicalVote, Vote},
+};
+
+/// Amino encoding of a message, without length prefix
+pub(crate) fn encode_bare<M: prost_amino::Message>(message: &M) -> Vec<u8> {
+    let mut encoded = vec![];
+    message
+        .encode(&mut encoded)
+        .expect("vectors have enough capacity");
+    encoded
+}
+
+/// Amino encoding of a byte slice, ie. its length followed by its bytes, or
+/// nothing if it is empty, as Tendermint encodes header fields.
+pub(crate) fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.is_empty() {
+        return vec![];
+    }
+    let mut encoded = vec![];
+    prost_amino::encoding::encode_varint(bytes.len() as u64, &mut encoded);
+    encoded.extend_from_slice(bytes);
+    encoded
+}
@@ -0,0 +1,108 @@
+//! Amino block IDs
+
+use crate::{block, error::Error, hash::Hash};
+use prost_amino_derive::Message;
+use std::convert::{TryFrom, TryInto};
+
+/// Amino encoding of a block ID
+#[derive(Clone, PartialEq, Message)]
+pub struct BlockId {
+    /// Hash of the block header
+    #[prost_amino(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+
+    /// Header of the block parts
+    #[prost_amino(message, tag = "2")]
+    pub parts_header: Option<PartSetHeader>,
+}
+
+/// Amino encoding of the header of block parts
+#[derive(Clone, PartialEq, Message)]
+pub struct PartSetHeader {
+    /// Number of parts
+    #[prost_amino(int64, tag = "1")]
+    pub total: i64,
+
+    /// Merkle root of the parts
+    #[prost_amino(bytes, tag = "2")]
+    pub hash: Vec<u8>,
+}
+
+/// Amino encoding of a block ID in sign bytes
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalBlockId {
+    /// Hash of the block header
+    #[prost_amino(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+
+    /// Header of the block parts
+    #[prost_amino(message, tag = "2")]
+    pub parts_header: Option<CanonicalPartSetHeader>,
+}
+
+/// Amino encoding of the header of block parts in sign bytes, whose fields
+/// are in the reverse order
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalPartSetHeader {
+    /// Merkle root of the parts
+    #[prost_amino(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+
+    /// Number of parts
+    #[prost_amino(int64, tag = "2")]
+    pub total: i64,
+}
+
+impl From<&block::Id> for BlockId {
+    fn from(id: &block::Id) -> Self {
+        let parts_header = PartSetHeader {
+            total: id.part_set_header.total.into(),
+            hash: id.part_set_header.hash.as_bytes().to_vec(),
+        };
+        Self {
+            hash: id.hash.as_bytes().to_vec(),
+            parts_header: if parts_header == PartSetHeader::default() {
+                None
+            } else {
+                Some(parts_header)
+            },
+        }
+    }
+}
+
+impl TryFrom<BlockId> for block::Id {
+    type Error = Error;
+
+    fn try_from(id: BlockId) -> Result<Self, Error> {
+        let parts_header = id.parts_header.unwrap_or_default();
+        let part_set_header = if parts_header == PartSetHeader::default() {
+            block::parts::Header::default()
+        } else {
+            block::parts::Header::new(
+                parts_header
+                    .total
+                    .try_into()
+                    .map_err(|_| Error::invalid_part_set_header("negative total".to_string()))?,
+                Hash::from_bytes(crate::hash::Algorithm::Sha256, &parts_header.hash)?,
+            )?
+        };
+
+        Ok(Self {
+            hash: Hash::from_bytes(crate::hash::Algorithm::Sha256, &id.hash)?,
+            part_set_header,
+        })
+    }
+}
+
+impl From<&block::Id> for CanonicalBlockId {
+    fn from(id: &block::Id) -> Self {
+        let BlockId { hash, parts_header } = id.into();
+        Self {
+            hash,
+            parts_header: parts_header.map(|header| CanonicalPartSetHeader {
+                hash: header.hash,
+                total: header.total,
+            }),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Amino block headers and their hashes
+
+use super::{encode_bare, encode_bytes, BlockId, TimeMsg};
+use crate::{block, error::Error, merkle::simple_hash_from_byte_vectors, Hash};
+use prost_amino::Message;
+use prost_amino_derive::Message;
+use std::convert::{TryFrom, TryInto};
+
+/// Amino encoding of the protocol versions of a block
+#[derive(Clone, PartialEq, Message)]
+pub struct ConsensusVersion {
+    /// Version of the block protocol
+    #[prost_amino(uint64, tag = "1")]
+    pub block: u64,
+
+    /// Version of the application
+    #[prost_amino(uint64, tag = "2")]
+    pub app: u64,
+}
+
+impl From<&block::header::Version> for ConsensusVersion {
+    fn from(version: &block::header::Version) -> Self {
+        Self {
+            block: version.block,
+            app: version.app,
+        }
+    }
+}
+
+/// Amino encoding of a block header
+#[derive(Clone, PartialEq, Message)]
+pub struct Header {
+    /// Protocol versions
+    #[prost_amino(message, tag = "1")]
+    pub version: Option<ConsensusVersion>,
+
+    /// Chain ID
+    #[prost_amino(string, tag = "2")]
+    pub chain_id: String,
+
+    /// Block height
+    #[prost_amino(int64, tag = "3")]
+    pub height: i64,
+
+    /// Timestamp
+    #[prost_amino(message, tag = "4")]
+    pub time: Option<TimeMsg>,
+
+    /// ID of the previous block
+    #[prost_amino(message, tag = "5")]
+    pub last_block_id: Option<BlockId>,
+
+    /// Hash of the commit of the previous block
+    #[prost_amino(bytes, tag = "6")]
+    pub last_commit_hash: Vec<u8>,
+
+    /// Merkle root of the transactions
+    #[prost_amino(bytes, tag = "7")]
+    pub data_hash: Vec<u8>,
+
+    /// Hash of the validators of this block
+    #[prost_amino(bytes, tag = "8")]
+    pub validators_hash: Vec<u8>,
+
+    /// Hash of the validators of the next block
+    #[prost_amino(bytes, tag = "9")]
+    pub next_validators_hash: Vec<u8>,
+
+    /// Hash of the consensus parameters of this block
+    #[prost_amino(bytes, tag = "10")]
+    pub consensus_hash: Vec<u8>,
+
+    /// State of the application after the transactions of the previous block
+    #[prost_amino(bytes, tag = "11")]
+    pub app_hash: Vec<u8>,
+
+    /// Merkle root of the results of the transactions of the previous block
+    #[prost_amino(bytes, tag = "12")]
+    pub last_results_hash: Vec<u8>,
+
+    /// Hash of the evidence included in this block
+    #[prost_amino(bytes, tag = "13")]
+    pub evidence_hash: Vec<u8>,
+
+    /// Address of the proposer of this block
+    #[prost_amino(bytes, tag = "14")]
+    pub proposer_address: Vec<u8>,
+}
+
+impl From<&block::Header> for Header {
+    fn from(header: &block::Header) -> Self {
+        let hash_bytes = |hash: &Option<Hash>| {
+            hash.as_ref()
+                .map_or(vec![], |hash| hash.as_bytes().to_vec())
+        };
+
+        Self {
+            version: Some((&header.version).into()),
+            chain_id: header.chain_id.to_string(),
+            height: header.height.into(),
+            time: Some(header.time.into()),
+            last_block_id: header.last_block_id.as_ref().map(Into::into),
+            last_commit_hash: hash_bytes(&header.last_commit_hash),
+            data_hash: hash_bytes(&header.data_hash),
+            validators_hash: header.validators_hash.as_bytes().to_vec(),
+            next_validators_hash: header.next_validators_hash.as_bytes().to_vec(),
+            consensus_hash: header.consensus_hash.as_bytes().to_vec(),
+            app_hash: header.app_hash.value(),
+            last_results_hash: hash_bytes(&header.last_results_hash),
+            evidence_hash: hash_bytes(&header.evidence_hash),
+            proposer_address: header.proposer_address.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<Header> for block::Header {
+    type Error = Error;
+
+    fn try_from(header: Header) -> Result<Self, Error> {
+        let hash = |bytes: Vec<u8>| Hash::from_bytes(crate::hash::Algorithm::Sha256, &bytes);
+        let optional_hash = |bytes: Vec<u8>| {
+            hash(bytes).map(|hash| if hash.is_empty() { None } else { Some(hash) })
+        };
+        let version = header.version.ok_or_else(Error::missing_version)?;
+
+        Ok(Self {
+            version: block::header::Version {
+                block: version.block,
+                app: version.app,
+            },
+            chain_id: header.chain_id.try_into()?,
+            height: header.height.try_into()?,
+            time: header
+                .time
+                .ok_or_else(Error::missing_timestamp)?
+                .try_into()?,
+            last_block_id: header
+                .last_block_id
+                .map(TryInto::try_into)
+                .transpose()?
+                .filter(|id: &block::Id| !id.is_nil()),
+            last_commit_hash: optional_hash(header.last_commit_hash)?,
+            data_hash: optional_hash(header.data_hash)?,
+            validators_hash: hash(header.validators_hash)?,
+            next_validators_hash: hash(header.next_validators_hash)?,
+            consensus_hash: hash(header.consensus_hash)?,
+            app_hash: header.app_hash.try_into()?,
+            last_results_hash: optional_hash(header.last_results_hash)?,
+            evidence_hash: optional_hash(header.evidence_hash)?,
+            proposer_address: header.proposer_address.try_into()?,
+        })
+    }
+}
+
+impl block::Header {
+    /// Encode this header with Amino
+    pub fn to_amino_bytes(&self) -> Vec<u8> {
+        encode_bare(&Header::from(self))
+    }
+
+    /// Decode a header encoded with Amino
+    pub fn from_amino_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Header::decode(bytes)
+            .map_err(|e| Error::protocol(format!("invalid Amino header: {}", e)))?
+            .try_into()
+    }
+
+    /// Hash this header as Tendermint versions older than v0.34 do, ie.
+    /// compute the root of the simple Merkle tree whose leaves are the Amino
+    /// encodings of the header fields, in order.
+    pub fn amino_hash(&self) -> Hash {
+        let hash_bytes = |hash: &Option<Hash>| {
+            hash.as_ref()
+                .map_or(vec![], |hash| encode_bytes(hash.as_bytes()))
+        };
+
+        let mut height = vec![];
+        prost_amino::encoding::encode_varint(self.height.value(), &mut height);
+
+        let fields_bytes = vec![
+            encode_bare(&ConsensusVersion::from(&self.version)),
+            encode_bytes(self.chain_id.as_bytes()),
+            height,
+            encode_bare(&TimeMsg::from(self.time)),
+            self.last_block_id
+                .as_ref()
+                .map_or(vec![], |id| encode_bare(&BlockId::from(id))),
+            hash_bytes(&self.last_commit_hash),
+            hash_bytes(&self.data_hash),
+            encode_bytes(self.validators_hash.as_bytes()),
+            encode_bytes(self.next_validators_hash.as_bytes()),
+            encode_bytes(self.consensus_hash.as_bytes()),
+            encode_bytes(self.app_hash.as_ref()),
+            hash_bytes(&self.last_results_hash),
+            hash_bytes(&self.evidence_hash),
+            encode_bytes(self.proposer_address.as_bytes()),
+        ];
+
+        Hash::Sha256(simple_hash_from_byte_vectors(fields_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account, Time};
+    use sha2::{Digest, Sha256};
+
+    fn sha256(data: &[u8]) -> Hash {
+        Hash::Sha256(Sha256::digest(data).into())
+    }
+
+    fn header() -> block::Header {
+        block::Header {
+            version: block::header::Version { block: 1, app: 2 },
+            chain_id: "chainId".parse().unwrap(),
+            height: 3_u32.into(),
+            time: Time::parse_from_rfc3339("2019-10-13T16:14:44Z").unwrap(),
+            last_block_id: Some(block::Id {
+                hash: Hash::Sha256([0; 32]),
+                part_set_header: block::parts::Header::new(6, Hash::Sha256([0; 32])).unwrap(),
+            }),
+            last_commit_hash: Some(sha256(b"last_commit_hash")),
+            data_hash: Some(sha256(b"data_hash")),
+            validators_hash: sha256(b"validators_hash"),
+            next_validators_hash: sha256(b"next_validators_hash"),
+            consensus_hash: sha256(b"consensus_hash"),
+            app_hash: sha256(b"app_hash").as_bytes().to_vec().try_into().unwrap(),
+            last_results_hash: Some(sha256(b"last_results_hash")),
+            evidence_hash: Some(sha256(b"evidence_hash")),
+            proposer_address: account::Id::try_from(
+                sha256(b"proposer_address").as_bytes()[..20].to_vec(),
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn amino_hash() {
+        // Test vector of Tendermint v0.33
+        assert_eq!(
+            header().amino_hash(),
+            Hash::from_hex_upper(
+                crate::hash::Algorithm::Sha256,
+                "ABDC78921B18A47EE6BEF5E31637BADB0F3E587E3C0F4DB2D1E93E9FF0533862"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let header = header();
+        let decoded = block::Header::from_amino_bytes(&header.to_amino_bytes()).unwrap();
+        assert_eq!(decoded, header);
+    }
+}
@@ -0,0 +1,41 @@
+//! Amino timestamps
+
+use crate::{error::Error, Time};
+use prost_amino_derive::Message;
+use std::convert::{TryFrom, TryInto};
+
+/// Amino encoding of a timestamp, as a number of seconds and nanoseconds
+/// since the Unix epoch
+#[derive(Clone, PartialEq, Message)]
+pub struct TimeMsg {
+    /// Seconds since the Unix epoch
+    #[prost_amino(int64, tag = "1")]
+    pub seconds: i64,
+
+    /// Additional nanoseconds
+    #[prost_amino(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl From<Time> for TimeMsg {
+    fn from(time: Time) -> Self {
+        let nanos = time.unix_timestamp_nanos();
+        // Nanoseconds are always positive, even before the Unix epoch.
+        Self {
+            seconds: nanos.div_euclid(1_000_000_000) as i64,
+            nanos: nanos.rem_euclid(1_000_000_000) as i32,
+        }
+    }
+}
+
+impl TryFrom<TimeMsg> for Time {
+    type Error = Error;
+
+    fn try_from(msg: TimeMsg) -> Result<Self, Error> {
+        let nanos = msg
+            .nanos
+            .try_into()
+            .map_err(|_| Error::invalid_timestamp(format!("negative nanos: {}", msg.nanos)))?;
+        Time::from_unix_timestamp(msg.seconds, nanos)
+    }
+}
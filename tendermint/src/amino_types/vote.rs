@@ -0,0 +1,212 @@
+//! Amino votes and their sign bytes
+
+use super::{encode_bare, BlockId, CanonicalBlockId, TimeMsg};
+use crate::{block, chain, error::Error, vote};
+use prost_amino::Message;
+use prost_amino_derive::Message;
+use std::convert::{TryFrom, TryInto};
+
+/// Amino encoding of a vote
+#[derive(Clone, PartialEq, Message)]
+pub struct Vote {
+    /// Type of the vote
+    #[prost_amino(uint32, tag = "1")]
+    pub vote_type: u32,
+
+    /// Block height
+    #[prost_amino(int64, tag = "2")]
+    pub height: i64,
+
+    /// Round
+    #[prost_amino(int64, tag = "3")]
+    pub round: i64,
+
+    /// ID of the block voted for, if any
+    #[prost_amino(message, tag = "4")]
+    pub block_id: Option<BlockId>,
+
+    /// Timestamp
+    #[prost_amino(message, tag = "5")]
+    pub timestamp: Option<TimeMsg>,
+
+    /// Address of the validator
+    #[prost_amino(bytes, tag = "6")]
+    pub validator_address: Vec<u8>,
+
+    /// Index of the validator
+    #[prost_amino(int64, tag = "7")]
+    pub validator_index: i64,
+
+    /// Signature
+    #[prost_amino(bytes, tag = "8")]
+    pub signature: Vec<u8>,
+}
+
+/// Amino encoding of the canonical form of a vote, which validators sign
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalVote {
+    /// Type of the vote
+    #[prost_amino(uint32, tag = "1")]
+    pub vote_type: u32,
+
+    /// Block height
+    #[prost_amino(sfixed64, tag = "2")]
+    pub height: i64,
+
+    /// Round
+    #[prost_amino(sfixed64, tag = "3")]
+    pub round: i64,
+
+    /// ID of the block voted for, if any
+    #[prost_amino(message, tag = "4")]
+    pub block_id: Option<CanonicalBlockId>,
+
+    /// Timestamp
+    #[prost_amino(message, tag = "5")]
+    pub timestamp: Option<TimeMsg>,
+
+    /// Chain ID
+    #[prost_amino(string, tag = "6")]
+    pub chain_id: String,
+}
+
+impl CanonicalVote {
+    /// Canonical form of the given vote on the given chain
+    pub fn new(vote: &vote::Vote, chain_id: &chain::Id) -> Self {
+        Self {
+            vote_type: vote.vote_type as u32,
+            height: vote.height.into(),
+            round: vote.round.value().into(),
+            block_id: vote.block_id.as_ref().map(Into::into),
+            timestamp: vote.timestamp.map(Into::into),
+            chain_id: chain_id.to_string(),
+        }
+    }
+}
+
+impl From<&vote::Vote> for Vote {
+    fn from(vote: &vote::Vote) -> Self {
+        Self {
+            vote_type: vote.vote_type as u32,
+            height: vote.height.into(),
+            round: vote.round.value().into(),
+            block_id: vote.block_id.as_ref().map(Into::into),
+            timestamp: vote.timestamp.map(Into::into),
+            validator_address: vote.validator_address.as_bytes().to_vec(),
+            validator_index: vote.validator_index.value().into(),
+            signature: vote.signature.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<Vote> for vote::Vote {
+    type Error = Error;
+
+    fn try_from(vote: Vote) -> Result<Self, Error> {
+        let vote_type: i32 = vote
+            .vote_type
+            .try_into()
+            .map_err(|_| Error::invalid_message_type())?;
+        let round: u32 = vote.round.try_into().map_err(Error::negative_round)?;
+        let validator_index: u32 = vote
+            .validator_index
+            .try_into()
+            .map_err(Error::negative_validator_index)?;
+
+        Ok(Self {
+            vote_type: vote_type.try_into()?,
+            height: vote.height.try_into()?,
+            round: round.try_into()?,
+            block_id: vote
+                .block_id
+                .map(TryInto::try_into)
+                .transpose()?
+                .filter(|id: &block::Id| !id.is_nil()),
+            timestamp: Some(
+                vote.timestamp
+                    .ok_or_else(Error::missing_timestamp)?
+                    .try_into()?,
+            ),
+            validator_address: vote.validator_address.try_into()?,
+            validator_index: validator_index.try_into()?,
+            signature: vote.signature.try_into()?,
+        })
+    }
+}
+
+impl vote::Vote {
+    /// Encode this vote with Amino
+    pub fn to_amino_bytes(&self) -> Vec<u8> {
+        encode_bare(&Vote::from(self))
+    }
+
+    /// Decode a vote encoded with Amino
+    pub fn from_amino_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Vote::decode(bytes)
+            .map_err(|e| Error::protocol(format!("invalid Amino vote: {}", e)))?
+            .try_into()
+    }
+
+    /// The bytes validators of chains running Tendermint versions older than
+    /// v0.34 sign for this vote, ie. its length-delimited Amino canonical form
+    pub fn to_amino_sign_bytes(&self, chain_id: chain::Id) -> Vec<u8> {
+        let mut bytes = vec![];
+        CanonicalVote::new(self, &chain_id)
+            .encode_length_delimited(&mut bytes)
+            .expect("vectors have enough capacity");
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account, Time};
+
+    fn zero_time() -> Time {
+        // Go's zero time, 0001-01-01T00:00:00Z
+        Time::from_unix_timestamp(-62_135_596_800, 0).unwrap()
+    }
+
+    #[test]
+    fn sign_bytes() {
+        // Test vectors of Tendermint v0.33, with a vote type
+        let vote = vote::Vote {
+            vote_type: vote::Type::Precommit,
+            height: 1_u32.into(),
+            round: 1_u8.into(),
+            block_id: None,
+            timestamp: Some(zero_time()),
+            ..vote::Vote::default()
+        };
+        assert_eq!(
+            vote.to_amino_sign_bytes("test_chain_id".parse().unwrap()),
+            vec![
+                0x30, 0x8, 0x2, 0x11, 0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x19, 0x1, 0x0, 0x0,
+                0x0, 0x0, 0x0, 0x0, 0x0, 0x2a, 0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff,
+                0xff, 0xff, 0x1, 0x32, 0xd, 0x74, 0x65, 0x73, 0x74, 0x5f, 0x63, 0x68, 0x61, 0x69,
+                0x6e, 0x5f, 0x69, 0x64
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let vote = vote::Vote {
+            vote_type: vote::Type::Prevote,
+            height: 12345_u32.into(),
+            round: 2_u8.into(),
+            block_id: Some(block::Id {
+                hash: crate::Hash::Sha256([0xAB; 32]),
+                part_set_header: block::parts::Header::new(1, crate::Hash::Sha256([0xCD; 32]))
+                    .unwrap(),
+            }),
+            timestamp: Some(Time::from_unix_timestamp(1_600_000_000, 123).unwrap()),
+            validator_address: account::Id::new([0x42; 20]),
+            validator_index: 7_u32.try_into().unwrap(),
+            signature: vec![0x01; 64].try_into().unwrap(),
+        };
+        let decoded = vote::Vote::from_amino_bytes(&vote.to_amino_bytes()).unwrap();
+        assert_eq!(decoded, vote);
+    }
+}
@@ -1,5 +1,6 @@
 //! Blocks within the chains of a Tendermint network
 
+pub mod builder;
 mod commit;
 pub mod commit_sig;
 pub mod header;
@@ -12,6 +13,7 @@ pub mod signed_header;
 mod size;
 
 pub use self::{
+    builder::{BlockBuilder, CommitBuilder, HeaderBuilder},
     commit::*,
     commit_sig::*,
     header::Header,
@@ -19,9 +21,15 @@ pub use self::{
     id::{Id, ParseId},
     meta::Meta,
     round::*,
-    size::Size,
+    size::{
+        max_commit_bytes, Size, MAX_BLOCK_SIZE_BYTES, MAX_COMMIT_OVERHEAD_BYTES,
+        MAX_COMMIT_SIG_BYTES, MAX_HEADER_BYTES, MAX_OVERHEAD_FOR_BLOCK,
+    },
 };
-use crate::{abci::transaction, error::Error, evidence};
+use crate::{
+    abci::transaction, consensus::TimestampRules, error::Error, evidence, validator, Hash, Time,
+};
+use parts::{PartSet, BLOCK_PART_SIZE_BYTES};
 use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Block as RawBlock;
@@ -55,30 +63,30 @@ impl TryFrom<RawBlock> for Block {
     type Error = Error;
 
     fn try_from(value: RawBlock) -> Result<Self, Self::Error> {
-        let header: Header = value.header.ok_or_else(Error::missing_header)?.try_into()?;
-        // if last_commit is Commit::Default, it is considered nil by Go.
+        let header: Header = value
+            .header
+            .ok_or_else(Error::missing_header)?
+            .try_into()
+            .map_err(|e| Error::field("header", e))?;
+        // The empty last commit of the first block, which Go creates as
+        // `NewCommit(0, 0, BlockID{}, nil)`, is considered nil.
         let last_commit = value
             .last_commit
             .map(TryInto::try_into)
-            .transpose()?
-            .filter(|c| c != &Commit::default());
-        if last_commit.is_none() && header.height.value() != 1 {
-            return Err(Error::invalid_block(
-                "last_commit is empty on non-first block".to_string(),
-            ));
-        }
-        // Todo: Figure out requirements.
-        //if last_commit.is_some() && header.height.value() == 1 {
-        //    return Err(Kind::InvalidFirstBlock.context("last_commit is not null on first
-        // height").into());
-        //}
+            .transpose()
+            .map_err(|e| Error::field("last_commit", e))?
+            .filter(|c: &Commit| !(c.block_id.is_nil() && c.signatures.is_empty()));
+        // The initial height of the chain is unknown here, but only the
+        // block at the initial height has no last block ID.
+        check_first_block(&header, &last_commit, header.last_block_id.is_none())?;
         Ok(Block {
             header,
             data: value.data.ok_or_else(Error::missing_data)?.into(),
             evidence: value
                 .evidence
                 .ok_or_else(Error::missing_evidence)?
-                .try_into()?,
+                .try_into()
+                .map_err(|e| Error::field("evidence", e))?,
             last_commit,
         })
     }
@@ -96,23 +104,33 @@ impl From<Block> for RawBlock {
 }
 
 impl Block {
-    /// constructor
+    /// constructor, for a block of a chain starting at height 1
     pub fn new(
         header: Header,
         data: transaction::Data,
         evidence: evidence::Data,
         last_commit: Option<Commit>,
     ) -> Result<Self, Error> {
-        if last_commit.is_none() && header.height.value() != 1 {
-            return Err(Error::invalid_block(
-                "last_commit is empty on non-first block".to_string(),
-            ));
-        }
-        if last_commit.is_some() && header.height.value() == 1 {
-            return Err(Error::invalid_block(
-                "last_commit is filled on first block".to_string(),
-            ));
+        Self::new_with_initial_height(header, data, evidence, last_commit, Height::from(1_u32))
+    }
+
+    /// Constructor for a block of a chain starting at the given height (the
+    /// `initial_height` of its genesis), eg. after a hard fork: only the
+    /// block at the initial height has no last commit.
+    pub fn new_with_initial_height(
+        header: Header,
+        data: transaction::Data,
+        evidence: evidence::Data,
+        last_commit: Option<Commit>,
+        initial_height: Height,
+    ) -> Result<Self, Error> {
+        if header.height < initial_height {
+            return Err(Error::invalid_block(format!(
+                "height {} is lower than the initial height {}",
+                header.height, initial_height
+            )));
         }
+        check_first_block(&header, &last_commit, header.height == initial_height)?;
         Ok(Block {
             header,
             data,
@@ -140,4 +158,227 @@ impl Block {
     pub fn last_commit(&self) -> &Option<Commit> {
         &self.last_commit
     }
+
+    /// Compute the hash of this block, ie. the hash of its header.
+    pub fn hash(&self) -> Hash {
+        self.header.hash()
+    }
+
+    /// The size of the Protobuf encoding of this block, which must not exceed
+    /// the `max_bytes` block size parameter
+    pub fn estimated_proto_size(&self) -> usize {
+        prost::Message::encoded_len(&RawBlock::from(self.clone()))
+    }
+
+    /// Check that the Protobuf encoding of this block does not exceed the
+    /// `max_bytes` of the given block size parameters
+    pub fn validate_size(&self, params: &Size) -> Result<(), Error> {
+        let size = self.estimated_proto_size() as u64;
+        if size > params.max_bytes {
+            return Err(Error::invalid_block(format!(
+                "block is too big: {} > {}",
+                size, params.max_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the time of this block against the given timestamp rules, as
+    /// `validateBlock` does in Go, given the time of the previous block and
+    /// the validators which signed the last commit of this block.
+    ///
+    /// The block at the initial height, which has no last commit, must
+    /// instead have the time of the genesis, given as the last block time.
+    pub fn validate_time(
+        &self,
+        rules: &TimestampRules,
+        last_block_time: Time,
+        last_validators: &validator::Set,
+    ) -> Result<(), Error> {
+        match &self.last_commit {
+            Some(last_commit) => rules.validate_block_time(
+                self.header.time,
+                last_block_time,
+                last_commit,
+                last_validators,
+            ),
+            None if self.header.time != last_block_time => Err(Error::invalid_timestamp(format!(
+                "block time {} is not the genesis time {}",
+                self.header.time, last_block_time
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Split the Protobuf encoding of this block into parts, as done to
+    /// gossip it over the network.
+    pub fn make_part_set(&self) -> PartSet {
+        let bytes = self.clone().encode_vec().unwrap();
+        PartSet::from_bytes(&bytes, BLOCK_PART_SIZE_BYTES)
+    }
+
+    /// Compute the ID of this block, ie. the hash of its header along with
+    /// the header of its part set.
+    pub fn id(&self) -> Id {
+        Id {
+            hash: self.hash(),
+            part_set_header: self.make_part_set().header(),
+        }
+    }
+
+    /// Reassemble a block from a complete part set.
+    pub fn from_part_set(part_set: &PartSet) -> Result<Self, Error> {
+        let bytes = part_set
+            .to_bytes()
+            .ok_or_else(|| Error::invalid_part("incomplete part set".to_string()))?;
+
+        Self::decode_vec(&bytes).map_err(|e| Error::invalid_block(e.to_string()))
+    }
+}
+
+/// Check that only the first block of the chain has neither a last commit nor
+/// a last block ID in its header.
+fn check_first_block(
+    header: &Header,
+    last_commit: &Option<Commit>,
+    is_first: bool,
+) -> Result<(), Error> {
+    let error = match (
+        is_first,
+        header.last_block_id.is_some(),
+        last_commit.is_some(),
+    ) {
+        (true, true, _) => "last_block_id is filled on first block",
+        (true, _, true) => "last_commit is filled on first block",
+        (false, false, _) => "last_block_id is empty on non-first block",
+        (false, _, false) => "last_commit is empty on non-first block",
+        _ => return Ok(()),
+    };
+    Err(Error::invalid_block(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    #[derive(Deserialize)]
+    struct BlockWithId {
+        block: Block,
+        block_id: Id,
+    }
+
+    /// Block served by a Go node, along with the block ID computed by the node.
+    fn block_from_go_node() -> BlockWithId {
+        serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/block_from_go_node.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn block_id_matches_go_node() {
+        let fixture = block_from_go_node();
+
+        assert_eq!(fixture.block.id(), fixture.block_id);
+
+        let part_set = fixture.block.make_part_set();
+        assert_eq!(Block::from_part_set(&part_set).unwrap(), fixture.block);
+    }
+
+    #[test]
+    fn last_commit_matches_last_block_id() {
+        let block = block_from_go_node().block;
+        let header = block.header.clone();
+        let last_commit = block.last_commit.clone();
+        let new = |header: &Header, last_commit: &Option<Commit>, initial_height: u32| {
+            Block::new_with_initial_height(
+                header.clone(),
+                block.data.clone(),
+                block.evidence.clone(),
+                last_commit.clone(),
+                Height::from(initial_height),
+            )
+        };
+
+        assert!(Block::try_from(RawBlock::from(block.clone())).is_ok());
+        assert!(new(&header, &last_commit, 1).is_ok());
+
+        // A block with a last block ID but no last commit
+        let mut raw = RawBlock::from(block.clone());
+        raw.last_commit = None;
+        assert!(Block::try_from(raw).is_err());
+        assert!(new(&header, &None, 1).is_err());
+
+        // A block with a last commit but no last block ID
+        let first = Header {
+            last_block_id: None,
+            ..header.clone()
+        };
+        let mut raw = RawBlock::from(block.clone());
+        raw.header = Some(first.clone().into());
+        assert!(Block::try_from(raw.clone()).is_err());
+        assert!(new(&first, &last_commit, 1).is_err());
+
+        // The first block of a chain has neither
+        raw.last_commit = None;
+        let height = header.height.value() as u32;
+        assert!(Block::try_from(raw).is_ok());
+        assert!(new(&first, &None, height).is_ok());
+        assert!(new(&header, &None, height).is_err());
+        assert!(new(&first, &last_commit, height).is_err());
+    }
+
+    #[test]
+    fn block_size() {
+        let block = block_from_go_node().block;
+
+        let size = block.estimated_proto_size();
+        assert_eq!(size, block.encode_vec().unwrap().len());
+
+        assert!(block.validate_size(&Size::default()).is_ok());
+        let params = Size {
+            max_bytes: size as u64 - 1,
+            ..Size::default()
+        };
+        assert!(block.validate_size(&params).is_err());
+    }
+
+    #[test]
+    fn block_time() {
+        let mut block = block_from_go_node().block;
+        let time = block.header.time;
+        let earlier = time - std::time::Duration::from_secs(1);
+        let validators = validator::Set::without_proposer(vec![]).unwrap();
+
+        let pbts = TimestampRules::ProposerBased(Default::default());
+        assert!(block.validate_time(&pbts, earlier, &validators).is_ok());
+        assert!(block.validate_time(&pbts, time, &validators).is_err());
+
+        // The first block has the time of the genesis, whatever the rules.
+        block.last_commit = None;
+        let bft_time = TimestampRules::BftTime;
+        assert!(block.validate_time(&bft_time, time, &validators).is_ok());
+        assert!(block
+            .validate_time(&bft_time, earlier, &validators)
+            .is_err());
+    }
+
+    #[test]
+    fn report_the_invalid_field() {
+        let mut raw = RawBlock::from(block_from_go_node().block);
+        let last_block_id = raw.header.as_mut().unwrap().last_block_id.as_mut();
+        last_block_id.unwrap().hash = vec![0; 3];
+
+        match Block::try_from(raw).unwrap_err().detail() {
+            ErrorDetail::InvalidField(e) => {
+                assert_eq!(e.field, "header.last_block_id.hash");
+                assert!(matches!(
+                    *e.source,
+                    ErrorDetail::InvalidHashSize(ref e) if e.found == 3
+                ));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
 }
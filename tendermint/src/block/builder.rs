@@ -0,0 +1,599 @@
+//! Builders of headers, commits and blocks, which compute the fields derived
+//! from the data they commit to, eg. for tests and simulators.
+
+use super::{header::Version, Block, Commit, CommitSig, Header, Height, Id, Round};
+use crate::{
+    abci::{self, transaction},
+    account, chain, consensus,
+    error::Error,
+    evidence,
+    merkle::simple_hash_from_byte_vectors,
+    private_key::Ed25519,
+    signature::Signer as _,
+    validator,
+    vote::{self, SignedVote, ValidatorIndex, Vote},
+    AppHash, Hash, Signature, Time,
+};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// Version of the block protocol of the blocks built by default
+pub const BLOCK_PROTOCOL_VERSION: u64 = 11;
+
+/// Builder of block headers, which computes their hashes from the data they
+/// commit to
+///
+/// By default, a header is for an empty block of an application in its initial
+/// state, on a chain using the default consensus parameters.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct HeaderBuilder {
+    version: Version,
+    chain_id: chain::Id,
+    height: Height,
+    initial_height: Height,
+    time: Time,
+    last_block_id: Option<Id>,
+    last_commit_hash: Hash,
+    data_hash: Hash,
+    validators: validator::Set,
+    next_validators: Option<validator::Set>,
+    consensus_hash: Hash,
+    app_hash: AppHash,
+    last_results_hash: Hash,
+    evidence_hash: Hash,
+    proposer_address: Option<account::Id>,
+}
+
+impl HeaderBuilder {
+    /// Builder of a header of a block of the given chain at the given height
+    /// and time, to be signed by the given validators
+    pub fn new(
+        chain_id: chain::Id,
+        height: Height,
+        time: Time,
+        validators: validator::Set,
+    ) -> Self {
+        Self {
+            version: Version {
+                block: BLOCK_PROTOCOL_VERSION,
+                app: 0,
+            },
+            chain_id,
+            height,
+            initial_height: Height::from(1_u32),
+            time,
+            last_block_id: None,
+            last_commit_hash: Commit::default().hash(),
+            data_hash: transaction::Data::default().hash(),
+            validators,
+            next_validators: None,
+            consensus_hash: consensus::Params::default().hash(),
+            app_hash: AppHash::default(),
+            last_results_hash: abci::results_hash(&[]),
+            // The Merkle root of no evidence
+            evidence_hash: Hash::Sha256(simple_hash_from_byte_vectors(Vec::new())),
+            proposer_address: None,
+        }
+    }
+
+    /// Set the initial height of the chain, 1 by default: only the block at
+    /// this height has no previous block
+    pub fn initial_height(mut self, initial_height: Height) -> Self {
+        self.initial_height = initial_height;
+        self
+    }
+
+    /// Set the versions of the block protocol and of the application
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the ID of the previous block, which is required unless the header
+    /// is at height 1
+    pub fn last_block_id(mut self, last_block_id: Id) -> Self {
+        self.last_block_id = Some(last_block_id);
+        self
+    }
+
+    /// Set the commit of the previous block, setting the ID of the previous
+    /// block and the hash of its commit
+    pub fn last_commit(mut self, last_commit: &Commit) -> Self {
+        self.last_block_id = Some(last_commit.block_id);
+        self.last_commit_hash = last_commit.hash();
+        self
+    }
+
+    /// Set the transactions of the block
+    pub fn transactions(mut self, data: &transaction::Data) -> Self {
+        self.data_hash = data.hash();
+        self
+    }
+
+    /// Set the evidence of misbehavior included in the block, failing if it
+    /// cannot be hashed (see [`evidence::Data::hash`])
+    pub fn evidence(mut self, evidence: &evidence::Data) -> Result<Self, Error> {
+        self.evidence_hash = evidence.hash()?;
+        Ok(self)
+    }
+
+    /// Set the validators of the next block, the same as the ones of this
+    /// block by default
+    pub fn next_validators(mut self, next_validators: validator::Set) -> Self {
+        self.next_validators = Some(next_validators);
+        self
+    }
+
+    /// Set the consensus parameters of the block
+    pub fn consensus_params(mut self, params: &consensus::Params) -> Self {
+        self.consensus_hash = params.hash();
+        self
+    }
+
+    /// Set the state of the application after the transactions of the
+    /// previous block
+    pub fn app_hash(mut self, app_hash: AppHash) -> Self {
+        self.app_hash = app_hash;
+        self
+    }
+
+    /// Set the results of the transactions of the previous block
+    pub fn last_results(mut self, results: &[abci::DeliverTx]) -> Self {
+        self.last_results_hash = abci::results_hash(results);
+        self
+    }
+
+    /// Set the proposer of the block, by default the proposer of the
+    /// validator set if it has one, or else its first validator
+    pub fn proposer_address(mut self, proposer_address: account::Id) -> Self {
+        self.proposer_address = Some(proposer_address);
+        self
+    }
+
+    /// Build the header, checking that it has validators including its
+    /// proposer, and that it has the ID of the previous block unless it is at
+    /// the initial height of the chain
+    pub fn build(self) -> Result<Header, Error> {
+        let invalid = |reason: &str| Err(Error::invalid_header(reason.to_string()));
+
+        if self.validators.validators().is_empty() {
+            return invalid("header has no validators");
+        }
+        let proposer_address = self.proposer_address.unwrap_or_else(|| {
+            self.validators
+                .proposer()
+                .as_ref()
+                .unwrap_or(&self.validators.validators()[0])
+                .address
+        });
+        if self.validators.validator(proposer_address).is_none() {
+            return invalid("proposer is not a validator");
+        }
+
+        if self.height < self.initial_height {
+            return invalid("height is lower than the initial height");
+        }
+        let is_first = self.height == self.initial_height;
+        if is_first && self.last_block_id.is_some() {
+            return invalid("last_block_id is set on first block");
+        }
+        if !is_first && self.last_block_id.is_none() {
+            return invalid("last_block_id is missing on non-first block");
+        }
+
+        let next_validators = self.next_validators.as_ref().unwrap_or(&self.validators);
+
+        Ok(Header {
+            version: self.version,
+            chain_id: self.chain_id,
+            height: self.height,
+            time: self.time,
+            last_block_id: self.last_block_id,
+            last_commit_hash: Some(self.last_commit_hash),
+            data_hash: Some(self.data_hash),
+            validators_hash: self.validators.hash(),
+            next_validators_hash: next_validators.hash(),
+            consensus_hash: self.consensus_hash,
+            app_hash: self.app_hash,
+            last_results_hash: Some(self.last_results_hash),
+            evidence_hash: Some(self.evidence_hash),
+            proposer_address,
+        })
+    }
+}
+
+/// Builder of commits, which signs them with the keys of the validators
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct CommitBuilder {
+    chain_id: chain::Id,
+    height: Height,
+    round: Round,
+    block_id: Id,
+    signatures: Vec<CommitSig>,
+}
+
+impl CommitBuilder {
+    /// Builder of a commit of the block of the given chain with the given ID
+    /// at the given height
+    pub fn new(chain_id: chain::Id, height: Height, block_id: Id) -> Self {
+        Self {
+            chain_id,
+            height,
+            round: Round::default(),
+            block_id,
+            signatures: vec![],
+        }
+    }
+
+    /// Builder of a commit of the given block
+    pub fn for_block(block: &Block) -> Self {
+        Self::new(
+            block.header.chain_id.clone(),
+            block.header.height,
+            block.id(),
+        )
+    }
+
+    /// Set the round of the commit, 0 by default
+    pub fn round(mut self, round: Round) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Add the given signature
+    ///
+    /// The signatures are in the order of the validator set, one per
+    /// validator, and the ones of the validators which did not vote are
+    /// absent.
+    pub fn signature(mut self, signature: CommitSig) -> Self {
+        self.signatures.push(signature);
+        self
+    }
+
+    /// Add an absent signature, of a validator which did not vote
+    pub fn absent(self) -> Self {
+        self.signature(CommitSig::BlockIdFlagAbsent)
+    }
+
+    /// Add the signature of the given validator key of a precommit for the
+    /// block at the given time
+    pub fn sign(self, keypair: &Ed25519, timestamp: Time) -> Self {
+        let validator_address = account::Id::from(keypair.public);
+        let signature = self.sign_precommit(keypair, Some(self.block_id), timestamp);
+        self.signature(CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp,
+            signature,
+        })
+    }
+
+    /// Add the signature of the given validator key of a precommit for no
+    /// block (nil) at the given time
+    pub fn sign_nil(self, keypair: &Ed25519, timestamp: Time) -> Self {
+        let validator_address = account::Id::from(keypair.public);
+        let signature = self.sign_precommit(keypair, None, timestamp);
+        self.signature(CommitSig::BlockIdFlagNil {
+            validator_address,
+            timestamp,
+            signature,
+        })
+    }
+
+    fn sign_precommit(
+        &self,
+        keypair: &Ed25519,
+        block_id: Option<Id>,
+        timestamp: Time,
+    ) -> Signature {
+        let validator_address = account::Id::from(keypair.public);
+        let vote = Vote {
+            vote_type: vote::Type::Precommit,
+            height: self.height,
+            round: self.round,
+            block_id,
+            timestamp: Some(timestamp),
+            validator_address,
+            validator_index: ValidatorIndex::try_from(self.signatures.len())
+                .expect("commits have less than 2^31 signatures"),
+            signature: Signature::default(),
+        };
+        let sign_bytes = SignedVote::new(
+            vote,
+            self.chain_id.clone(),
+            validator_address,
+            Signature::default(),
+        )
+        .sign_bytes();
+        keypair.sign(&sign_bytes).into()
+    }
+
+    /// Build the commit, checking that it has signatures, and at most one per
+    /// validator
+    pub fn build(self) -> Result<Commit, Error> {
+        if self.signatures.iter().all(CommitSig::is_absent) {
+            return Err(Error::invalid_commit(
+                "commit has no signatures".to_string(),
+            ));
+        }
+
+        let mut validators = HashSet::new();
+        for signature in &self.signatures {
+            if let Some(address) = signature.validator_address() {
+                if !validators.insert(address) {
+                    return Err(Error::invalid_commit(format!(
+                        "validator {} signed more than once",
+                        address
+                    )));
+                }
+            }
+        }
+
+        Ok(Commit {
+            height: self.height,
+            round: self.round,
+            block_id: self.block_id,
+            signatures: self.signatures,
+        })
+    }
+}
+
+/// Builder of blocks, which computes the hashes of their contents in their
+/// header
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct BlockBuilder {
+    header: HeaderBuilder,
+    data: transaction::Data,
+    evidence: evidence::Data,
+    last_commit: Option<Commit>,
+}
+
+impl BlockBuilder {
+    /// Builder of a block with the header built by the given builder
+    pub fn new(header: HeaderBuilder) -> Self {
+        Self {
+            header,
+            data: transaction::Data::default(),
+            evidence: evidence::Data::default(),
+            last_commit: None,
+        }
+    }
+
+    /// Set the transactions of the block
+    pub fn transactions(mut self, data: transaction::Data) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Set the evidence of misbehavior included in the block
+    pub fn evidence(mut self, evidence: evidence::Data) -> Self {
+        self.evidence = evidence;
+        self
+    }
+
+    /// Set the commit of the previous block, which is required unless the
+    /// block is at the initial height of the chain
+    pub fn last_commit(mut self, last_commit: Commit) -> Self {
+        self.last_commit = Some(last_commit);
+        self
+    }
+
+    /// Build the block, checking the invariants of its header (see
+    /// [`HeaderBuilder::build`]) and that it has the commit of the previous
+    /// block unless it is at the initial height of the chain
+    pub fn build(self) -> Result<Block, Error> {
+        let initial_height = self.header.initial_height;
+        let mut header = self
+            .header
+            .transactions(&self.data)
+            .evidence(&self.evidence)?;
+        if let Some(last_commit) = &self.last_commit {
+            header = header.last_commit(last_commit);
+        }
+
+        Block::new_with_initial_height(
+            header.build()?,
+            self.data,
+            self.evidence,
+            self.last_commit,
+            initial_height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abci::Transaction;
+    use crate::test::{keypair, validator};
+    use tendermint_proto::Protobuf;
+
+    fn header(height: u32, validators: validator::Set) -> HeaderBuilder {
+        HeaderBuilder::new(
+            "test-chain".parse().unwrap(),
+            height.into(),
+            Time::from_unix_timestamp(1_600_000_000, 0).unwrap(),
+            validators,
+        )
+    }
+
+    #[test]
+    fn build_chain_of_blocks() {
+        let keypairs = [keypair(1), keypair(2)];
+        let validators =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+        let data = transaction::Data::new(vec![Transaction::from(b"key=value".to_vec())]);
+
+        let first = BlockBuilder::new(header(1, validators.clone()))
+            .transactions(data.clone())
+            .build()
+            .unwrap();
+        assert_eq!(first.header.data_hash, Some(data.hash()));
+        assert_eq!(first.header.validators_hash, validators.hash());
+        assert_eq!(first.header.next_validators_hash, validators.hash());
+        assert_eq!(
+            first.header.proposer_address,
+            validators.validators()[0].address
+        );
+
+        // The signatures are in the order of the validator set
+        let timestamp = first.header.time;
+        let mut commit = CommitBuilder::for_block(&first);
+        for info in validators.validators() {
+            let keypair = keypairs
+                .iter()
+                .find(|keypair| account::Id::from(keypair.public) == info.address)
+                .unwrap();
+            commit = commit.sign(keypair, timestamp);
+        }
+        let commit = commit.build().unwrap();
+
+        for (index, (signature, info)) in commit
+            .signatures
+            .iter()
+            .zip(validators.validators())
+            .enumerate()
+        {
+            let vote = Vote {
+                vote_type: vote::Type::Precommit,
+                height: first.header.height,
+                round: Round::default(),
+                block_id: Some(first.id()),
+                timestamp: Some(timestamp),
+                validator_address: info.address,
+                validator_index: ValidatorIndex::try_from(index).unwrap(),
+                signature: Signature::default(),
+            };
+            let sign_bytes = SignedVote::new(
+                vote,
+                first.header.chain_id.clone(),
+                info.address,
+                Signature::default(),
+            )
+            .sign_bytes();
+            match signature {
+                CommitSig::BlockIdFlagCommit { signature, .. } => {
+                    info.verify_signature(&sign_bytes, signature).unwrap()
+                }
+                _ => panic!("expected a commit signature"),
+            }
+        }
+
+        let second = BlockBuilder::new(header(2, validators))
+            .last_commit(commit.clone())
+            .build()
+            .unwrap();
+        assert_eq!(second.header.last_block_id, Some(first.id()));
+        assert_eq!(second.header.last_commit_hash, Some(commit.hash()));
+        assert_eq!(
+            second.header.data_hash,
+            Some(transaction::Data::default().hash())
+        );
+    }
+
+    #[test]
+    fn reject_invalid_headers() {
+        let keypair = keypair(1);
+        let validators = validator::Set::without_proposer(vec![validator(&keypair, 10)]).unwrap();
+
+        // Missing or superfluous last block ID
+        assert!(header(2, validators.clone()).build().is_err());
+        let last_block_id = header(1, validators.clone()).build().unwrap().hash();
+        assert!(header(1, validators.clone())
+            .last_block_id(Id {
+                hash: last_block_id,
+                part_set_header: Default::default(),
+            })
+            .build()
+            .is_err());
+
+        // Proposer not in the validator set
+        assert!(header(1, validators)
+            .proposer_address(account::Id::new([0; 20]))
+            .build()
+            .is_err());
+        assert!(header(1, validator::Set::without_proposer(vec![]).unwrap())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn build_blocks_from_initial_height() {
+        let keypair = keypair(1);
+        let validators = validator::Set::without_proposer(vec![validator(&keypair, 10)]).unwrap();
+        let initial_height = Height::from(1000_u32);
+
+        let first =
+            BlockBuilder::new(header(1000, validators.clone()).initial_height(initial_height))
+                .build()
+                .unwrap();
+        assert_eq!(first.header.last_block_id, None);
+        assert_eq!(
+            Block::decode_vec(&first.encode_vec().unwrap()).unwrap(),
+            first
+        );
+
+        // The first block of the chain has neither a last block ID nor a last commit
+        let commit = CommitBuilder::for_block(&first)
+            .sign(&keypair, first.header.time)
+            .build()
+            .unwrap();
+        assert!(
+            BlockBuilder::new(header(1000, validators.clone()).initial_height(initial_height))
+                .last_commit(commit.clone())
+                .build()
+                .is_err()
+        );
+        assert!(BlockBuilder::new(header(1000, validators.clone()))
+            .build()
+            .is_err());
+        assert!(
+            BlockBuilder::new(header(999, validators.clone()).initial_height(initial_height))
+                .build()
+                .is_err()
+        );
+
+        let second = BlockBuilder::new(header(1001, validators).initial_height(initial_height))
+            .last_commit(commit)
+            .build()
+            .unwrap();
+        assert_eq!(second.header.last_block_id, Some(first.id()));
+        assert_eq!(
+            Block::decode_vec(&second.encode_vec().unwrap()).unwrap(),
+            second
+        );
+
+        assert!(Block::new_with_initial_height(
+            second.header,
+            second.data,
+            second.evidence,
+            None,
+            initial_height,
+        )
+        .is_err());
+        assert!(Block::new(first.header, first.data, first.evidence, None).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_commits() {
+        let keypair = keypair(1);
+        let block_id = Id {
+            hash: Hash::None,
+            part_set_header: Default::default(),
+        };
+        let commit = CommitBuilder::new("test-chain".parse().unwrap(), 1_u32.into(), block_id);
+        let timestamp = Time::unix_epoch();
+
+        assert!(commit.clone().absent().build().is_err());
+        assert!(commit
+            .clone()
+            .sign(&keypair, timestamp)
+            .sign_nil(&keypair, timestamp)
+            .build()
+            .is_err());
+        assert!(commit.sign(&keypair, timestamp).absent().build().is_ok());
+    }
+}
@@ -3,9 +3,15 @@
 use crate::block::commit_sig::CommitSig;
 use crate::block::{Height, Id, Round};
 use crate::error::Error;
+use crate::merkle::simple_hash_from_byte_vectors;
+use crate::trust_threshold::TrustThresholdFraction;
+use crate::vote::VotingPowerTally;
+use crate::{validator, Hash};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Commit as RawCommit;
+use tendermint_proto::Protobuf;
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
 /// of validators.
@@ -28,6 +34,8 @@ pub struct Commit {
     pub signatures: Vec<CommitSig>,
 }
 
+impl Protobuf<RawCommit> for Commit {}
+
 impl TryFrom<RawCommit> for Commit {
     type Error = Error;
 
@@ -35,15 +43,27 @@ impl TryFrom<RawCommit> for Commit {
         let signatures: Result<Vec<CommitSig>, Error> = value
             .signatures
             .into_iter()
-            .map(TryFrom::try_from)
+            .enumerate()
+            .map(|(index, signature)| {
+                signature
+                    .try_into()
+                    .map_err(|e| Error::field(&format!("signatures[{}]", index), e))
+            })
             .collect();
         Ok(Self {
-            height: value.height.try_into()?,
-            round: value.round.try_into()?,
+            height: value
+                .height
+                .try_into()
+                .map_err(|e| Error::field("height", e))?,
+            round: value
+                .round
+                .try_into()
+                .map_err(|e| Error::field("round", e))?,
             block_id: value
                 .block_id
                 .ok_or_else(|| Error::invalid_block("missing block id".to_string()))?
-                .try_into()?, /* gogoproto.nullable = false */
+                .try_into()
+                .map_err(|e| Error::field("block_id", e))?, /* gogoproto.nullable = false */
             signatures: signatures?,
         })
     }
@@ -60,6 +80,82 @@ impl From<Commit> for RawCommit {
     }
 }
 
+impl Commit {
+    /// Signatures which count for the block ID of this commit, ie. those of
+    /// the validators which voted for the block
+    pub fn signatures_for_block(&self) -> impl Iterator<Item = &CommitSig> {
+        self.signatures
+            .iter()
+            .filter(|signature| signature.is_commit())
+    }
+
+    /// Check that this commit is well-formed, as `Commit.ValidateBasic` in
+    /// Go: commits for blocks other than the empty commit of the first block
+    /// must have a block ID and signatures, whose fields must be consistent
+    /// with their flags.
+    ///
+    /// The signatures themselves are not verified.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.height.value() > 0 {
+            if self.block_id.is_nil() {
+                return Err(Error::invalid_commit(
+                    "commit cannot be for nil block".to_string(),
+                ));
+            }
+            if self.signatures.is_empty() {
+                return Err(Error::invalid_commit("no signatures in commit".to_string()));
+            }
+        }
+
+        for (index, signature) in self.signatures.iter().enumerate() {
+            signature.validate().map_err(|e| {
+                Error::invalid_commit(format!("signature {}: {}", index, e.detail()))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Tally of the voting power of the validators of the given set which
+    /// signed for the block, as claimed by the signatures of this commit,
+    /// against the given trust threshold.
+    ///
+    /// Signatures of validators which are not in the set, and repeated
+    /// signatures of validators, are ignored. The signatures themselves are
+    /// not verified, see `SignedHeader::verify` for this.
+    pub fn signed_voting_power(
+        &self,
+        validators: &validator::Set,
+        trust_threshold: TrustThresholdFraction,
+    ) -> Result<VotingPowerTally, Error> {
+        let mut voting_power =
+            VotingPowerTally::new(validators.total_voting_power(), trust_threshold);
+        let mut seen_validators = HashSet::new();
+
+        for validator in self
+            .signatures_for_block()
+            .filter_map(CommitSig::validator_address)
+            .filter(|address| seen_validators.insert(*address))
+            .filter_map(|address| validators.validator(address))
+        {
+            voting_power.tally(validator.power)?;
+        }
+
+        Ok(voting_power)
+    }
+
+    /// Compute the Merkle root of the Protobuf encodings of the signatures of
+    /// this commit, ie. the `last_commit_hash` of the header of the next block.
+    pub fn hash(&self) -> Hash {
+        let signature_bytes = self
+            .signatures
+            .iter()
+            .map(|signature| signature.clone().encode_vec().unwrap())
+            .collect();
+        Hash::Sha256(simple_hash_from_byte_vectors(signature_bytes))
+    }
+}
+
 impl Default for Commit {
     fn default() -> Self {
         Commit {
@@ -71,3 +167,113 @@ impl Default for Commit {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::seeded_validator;
+    use crate::{account, Signature, Time};
+
+    fn signature(validator_address: account::Id, for_block: bool) -> CommitSig {
+        let signature = Signature::try_from(vec![1; 64]).unwrap();
+        if for_block {
+            CommitSig::BlockIdFlagCommit {
+                validator_address,
+                timestamp: Time::unix_epoch(),
+                signature,
+            }
+        } else {
+            CommitSig::BlockIdFlagNil {
+                validator_address,
+                timestamp: Time::unix_epoch(),
+                signature,
+            }
+        }
+    }
+
+    fn commit(signatures: Vec<CommitSig>) -> Commit {
+        Commit {
+            height: Height::from(10_u32),
+            round: Round::from(0_u16),
+            block_id: Id {
+                hash: Hash::Sha256([1; 32]),
+                part_set_header: crate::block::parts::Header::new(1, Hash::Sha256([2; 32]))
+                    .unwrap(),
+            },
+            signatures,
+        }
+    }
+
+    #[test]
+    fn tally_signed_voting_power() {
+        let validators = vec![
+            seeded_validator(1, 10),
+            seeded_validator(2, 20),
+            seeded_validator(3, 30),
+        ];
+        let set = validator::Set::without_proposer(validators.clone()).unwrap();
+        let outsider = seeded_validator(4, 40);
+
+        let commit = commit(vec![
+            signature(validators[0].address, true),
+            signature(validators[1].address, false),
+            CommitSig::BlockIdFlagAbsent,
+            signature(validators[0].address, true),
+            signature(outsider.address, true),
+        ]);
+        commit.validate().unwrap();
+
+        assert_eq!(commit.signatures_for_block().count(), 3);
+        let voting_power = commit
+            .signed_voting_power(&set, TrustThresholdFraction::ONE_THIRD)
+            .unwrap();
+        assert_eq!(voting_power.tallied, 10);
+        assert_eq!(voting_power.total, 60);
+        assert!(!voting_power.is_enough());
+        assert_eq!(
+            commit.signatures[0].block_id(commit.block_id),
+            Some(commit.block_id)
+        );
+        assert_eq!(commit.signatures[1].block_id(commit.block_id), None);
+        assert_eq!(commit.signatures[2].block_id(commit.block_id), None);
+    }
+
+    #[test]
+    fn validate_commits() {
+        let address = seeded_validator(1, 10).address;
+
+        assert!(commit(vec![]).validate().is_err());
+        assert!(Commit {
+            block_id: Id::nil(),
+            ..commit(vec![signature(address, true)])
+        }
+        .validate()
+        .is_err());
+        assert!(commit(vec![CommitSig::BlockIdFlagNil {
+            validator_address: address,
+            timestamp: Time::unix_epoch(),
+            signature: Signature::default(),
+        }])
+        .validate()
+        .is_err());
+
+        // The empty last commit of the first block
+        Commit::default().validate().unwrap();
+    }
+
+    #[test]
+    fn absent_signatures_have_no_fields() {
+        let raw = |validator_address: Vec<u8>| tendermint_proto::types::CommitSig {
+            block_id_flag: tendermint_proto::types::BlockIdFlag::Absent as i32,
+            validator_address,
+            timestamp: None,
+            signature: vec![],
+        };
+
+        assert_eq!(
+            CommitSig::try_from(raw(vec![])).unwrap(),
+            CommitSig::BlockIdFlagAbsent
+        );
+        assert!(CommitSig::try_from(raw(vec![1; account::LENGTH])).is_err());
+    }
+}
@@ -1,11 +1,13 @@
 //! CommitSig within Commit
 
+use crate::block::Id;
 use crate::error::Error;
 use crate::{account, Signature, Time};
 use num_traits::ToPrimitive;
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::BlockIdFlag;
 use tendermint_proto::types::CommitSig as RawCommitSig;
+use tendermint_proto::Protobuf;
 
 /// CommitSig represents a signature of a validator.
 /// It's a part of the Commit and can be used to reconstruct the vote set given the validator set.
@@ -61,17 +63,48 @@ impl CommitSig {
     pub fn is_nil(&self) -> bool {
         matches!(self, Self::BlockIdFlagNil { .. })
     }
+
+    /// Get the block ID this validator voted for, given the block ID of the
+    /// commit, if it voted for a block (as `CommitSig.BlockID` in Go, which
+    /// returns the nil block ID otherwise)
+    pub fn block_id(&self, commit_block_id: Id) -> Option<Id> {
+        if self.is_commit() {
+            Some(commit_block_id)
+        } else {
+            None
+        }
+    }
+
+    /// Check that the fields of this signature are consistent with its flag,
+    /// ie. that the validators which voted have non-empty signatures (as
+    /// `CommitSig.ValidateBasic` in Go)
+    pub fn validate(&self) -> Result<(), Error> {
+        let (kind, signature) = match self {
+            Self::BlockIdFlagAbsent => return Ok(()),
+            Self::BlockIdFlagCommit { signature, .. } => ("regular", signature),
+            Self::BlockIdFlagNil { signature, .. } => ("nil", signature),
+        };
+        if signature.as_bytes().is_empty() {
+            return Err(Error::invalid_signature(format!(
+                "{} commitsig has no signature",
+                kind
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 // Todo: https://github.com/informalsystems/tendermint-rs/issues/259 - CommitSig Timestamp can be zero time
 // Todo: https://github.com/informalsystems/tendermint-rs/issues/260 - CommitSig validator address missing in Absent vote
+impl Protobuf<RawCommitSig> for CommitSig {}
+
 impl TryFrom<RawCommitSig> for CommitSig {
     type Error = Error;
 
     fn try_from(value: RawCommitSig) -> Result<Self, Self::Error> {
         if value.block_id_flag == BlockIdFlag::Absent.to_i32().unwrap() {
-            if value.timestamp.is_some() {
-                let timestamp = value.timestamp.unwrap();
+            if let Some(timestamp) = value.timestamp {
                 // 0001-01-01T00:00:00.000Z translates to EPOCH-62135596800 seconds
                 if timestamp.nanos != 0 || timestamp.seconds != -62135596800 {
                     return Err(Error::invalid_timestamp(
@@ -82,6 +115,9 @@ impl TryFrom<RawCommitSig> for CommitSig {
             if !value.signature.is_empty() {
                 return Err(Error::invalid_signature("empty signature".to_string()));
             }
+            if !value.validator_address.is_empty() {
+                return Err(Error::invalid_validator_address());
+            }
             return Ok(CommitSig::BlockIdFlagAbsent);
         }
         if value.block_id_flag == BlockIdFlag::Commit.to_i32().unwrap() {
@@ -96,9 +132,15 @@ impl TryFrom<RawCommitSig> for CommitSig {
             let timestamp = value.timestamp.ok_or_else(Error::missing_timestamp)?.into();
 
             return Ok(CommitSig::BlockIdFlagCommit {
-                validator_address: value.validator_address.try_into()?,
+                validator_address: value
+                    .validator_address
+                    .try_into()
+                    .map_err(|e| Error::field("validator_address", e))?,
                 timestamp,
-                signature: value.signature.try_into()?,
+                signature: value
+                    .signature
+                    .try_into()
+                    .map_err(|e| Error::field("signature", e))?,
             });
         }
         if value.block_id_flag == BlockIdFlag::Nil.to_i32().unwrap() {
@@ -111,9 +153,15 @@ impl TryFrom<RawCommitSig> for CommitSig {
                 return Err(Error::invalid_validator_address());
             }
             return Ok(CommitSig::BlockIdFlagNil {
-                validator_address: value.validator_address.try_into()?,
+                validator_address: value
+                    .validator_address
+                    .try_into()
+                    .map_err(|e| Error::field("validator_address", e))?,
                 timestamp: value.timestamp.ok_or_else(Error::missing_timestamp)?.into(),
-                signature: value.signature.try_into()?,
+                signature: value
+                    .signature
+                    .try_into()
+                    .map_err(|e| Error::field("signature", e))?,
             });
         }
         Err(Error::block_id_flag())
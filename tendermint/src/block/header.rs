@@ -1,8 +1,10 @@
 //! Block headers
 
-use crate::merkle::simple_hash_from_byte_vectors;
-use crate::{account, block, chain, AppHash, Error, Hash, Time};
+use crate::hash::HashFunction;
+use crate::merkle::simple_hash_from_byte_vectors_with;
+use crate::{account, block, chain, compat::CompatMode, AppHash, Error, Hash, Time};
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::Header as RawHeader;
 use tendermint_proto::version::Consensus as RawConsensusVersion;
@@ -66,23 +68,16 @@ impl TryFrom<RawHeader> for Header {
 
     fn try_from(value: RawHeader) -> Result<Self, Self::Error> {
         // If last block id is unfilled, it is considered nil by Go.
-        let last_block_id = value
-            .last_block_id
-            .map(TryInto::try_into)
-            .transpose()?
-            .filter(|l| l != &block::Id::default());
-        let last_commit_hash = if value.last_commit_hash.is_empty() {
-            None
-        } else {
-            Some(value.last_commit_hash.try_into()?)
-        };
-        let last_results_hash = if value.last_results_hash.is_empty() {
-            None
-        } else {
-            Some(value.last_results_hash.try_into()?)
-        };
-        let height: block::Height = value.height.try_into()?;
-
+        let last_block_id = block::Id::try_from_optional(value.last_block_id)
+            .map_err(|e| Error::field("last_block_id", e))?;
+        let last_commit_hash = optional_hash(value.last_commit_hash)
+            .map_err(|e| Error::field("last_commit_hash", e))?;
+        let last_results_hash = optional_hash(value.last_results_hash)
+            .map_err(|e| Error::field("last_results_hash", e))?;
+        let height: block::Height = value
+            .height
+            .try_into()
+            .map_err(|e| Error::field("height", e))?;
         // Todo: fix domain logic
         //if last_block_id.is_none() && height.value() != 1 {
         //    return Err(Kind::InvalidHeader.context("last_block_id is null on non-first
@@ -110,31 +105,52 @@ impl TryFrom<RawHeader> for Header {
         //}
         Ok(Header {
             version: value.version.ok_or_else(Error::missing_version)?.into(),
-            chain_id: value.chain_id.try_into()?,
+            chain_id: value
+                .chain_id
+                .try_into()
+                .map_err(|e| Error::field("chain_id", e))?,
             height,
             time: value.time.ok_or_else(Error::missing_timestamp)?.into(),
             last_block_id,
             last_commit_hash,
-            data_hash: if value.data_hash.is_empty() {
-                None
-            } else {
-                Some(value.data_hash.try_into()?)
-            },
-            validators_hash: value.validators_hash.try_into()?,
-            next_validators_hash: value.next_validators_hash.try_into()?,
-            consensus_hash: value.consensus_hash.try_into()?,
-            app_hash: value.app_hash.try_into()?,
+            data_hash: optional_hash(value.data_hash).map_err(|e| Error::field("data_hash", e))?,
+            validators_hash: value
+                .validators_hash
+                .try_into()
+                .map_err(|e| Error::field("validators_hash", e))?,
+            next_validators_hash: value
+                .next_validators_hash
+                .try_into()
+                .map_err(|e| Error::field("next_validators_hash", e))?,
+            consensus_hash: value
+                .consensus_hash
+                .try_into()
+                .map_err(|e| Error::field("consensus_hash", e))?,
+            app_hash: value
+                .app_hash
+                .try_into()
+                .map_err(|e| Error::field("app_hash", e))?,
             last_results_hash,
-            evidence_hash: if value.evidence_hash.is_empty() {
-                None
-            } else {
-                Some(value.evidence_hash.try_into()?)
-            }, // Todo: Is it illegal to have evidence of wrongdoing in the first block?
-            proposer_address: value.proposer_address.try_into()?,
+            // Todo: Is it illegal to have evidence of wrongdoing in the first block?
+            evidence_hash: optional_hash(value.evidence_hash)
+                .map_err(|e| Error::field("evidence_hash", e))?,
+            proposer_address: value
+                .proposer_address
+                .try_into()
+                .map_err(|e| Error::field("proposer_address", e))?,
         })
     }
 }
 
+/// Hash of a header field which is empty when unset
+fn optional_hash(bytes: Vec<u8>) -> Result<Option<Hash>, Error> {
+    if bytes.is_empty() {
+        Ok(None)
+    } else {
+        bytes.try_into().map(Some)
+    }
+}
+
 impl From<Header> for RawHeader {
     fn from(value: Header) -> Self {
         RawHeader {
@@ -157,8 +173,16 @@ impl From<Header> for RawHeader {
 }
 
 impl Header {
-    /// Hash this header
+    /// Hash this header, ie. compute the root of the simple Merkle tree whose
+    /// leaves are the protobuf encodings of the header fields, in order.
+    /// This is the hash included in the ID of the block, and can be computed
+    /// without querying a node.
     pub fn hash(&self) -> Hash {
+        self.hash_with::<Sha256>()
+    }
+
+    /// Hash this header with the given hash function instead of SHA-256
+    pub fn hash_with<H: HashFunction>(&self) -> Hash {
         // Note that if there is an encoding problem this will
         // panic (as the golang code would):
         // https://github.com/tendermint/tendermint/blob/134fe2896275bb926b49743c1e25493f6b24cc31/types/block.go#L393
@@ -187,7 +211,21 @@ impl Header {
             self.proposer_address.encode_vec().unwrap(),
         ];
 
-        Hash::Sha256(simple_hash_from_byte_vectors(fields_bytes))
+        Hash::Sha256(simple_hash_from_byte_vectors_with::<H>(fields_bytes))
+    }
+
+    /// Hash this header as the given version of the protocol does, ie. with
+    /// [`Header::hash`] since v0.34
+    ///
+    /// Fails for Tendermint 0.33 without the `amino` feature.
+    pub fn hash_with_compat(&self, compat: CompatMode) -> Result<Hash, Error> {
+        match compat {
+            #[cfg(feature = "amino")]
+            CompatMode::V0_33 => Ok(self.amino_hash()),
+            #[cfg(not(feature = "amino"))]
+            CompatMode::V0_33 => Err(Error::unsupported_compat_mode(compat.to_string())),
+            CompatMode::V0_34 => Ok(self.hash()),
+        }
     }
 }
 
@@ -227,6 +265,7 @@ impl From<Version> for RawConsensusVersion {
 #[cfg(test)]
 mod tests {
     use super::Header;
+    use crate::block::signed_header::SignedHeader;
     use crate::hash::Algorithm;
     use crate::test::test_serialization_roundtrip;
     use crate::Hash;
@@ -249,5 +288,21 @@ mod tests {
         ))
         .unwrap();
         assert_eq!(expected_hash, header.hash());
+        assert_eq!(expected_hash, header.hash_with::<sha2::Sha256>());
+        assert_ne!(expected_hash, header.hash_with::<sha2::Sha512Trunc256>());
+    }
+
+    #[test]
+    fn header_hash_matches_go_block_id() {
+        // Signed header served by a Go node, whose commit carries the block ID
+        // computed by the node, including the hash of the header.
+        let signed_header: SignedHeader = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/signed_header_from_go_node.json"
+        ))
+        .unwrap();
+        assert_eq!(
+            signed_header.commit.block_id.hash,
+            signed_header.header.hash()
+        );
     }
 }
@@ -23,11 +23,12 @@ pub const PREFIX_LENGTH: usize = 10;
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#blockid>
 ///
-/// Default implementation is an empty Id as defined by the Go implementation in
+/// Default implementation is the nil (empty) Id as defined by the Go implementation in
 /// <https://github.com/tendermint/tendermint/blob/1635d1339c73ae6a82e062cd2dc7191b029efa14/types/block.go#L1204>.
 ///
-/// If the Hash is empty in BlockId, the BlockId should be empty (encoded to None).
-/// This is implemented outside of this struct. Use the Default trait to check for an empty BlockId.
+/// Go does not distinguish absent block IDs from nil ones in most places, eg.
+/// in the votes for no block: see [`Id::is_nil`], [`Id::try_from_optional`]
+/// and [`Id::canonicalize`].
 /// See: <https://github.com/informalsystems/tendermint-rs/issues/663>
 #[derive(
     Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord,
@@ -69,8 +70,12 @@ impl TryFrom<RawBlockId> for Id {
             ));
         }
         Ok(Self {
-            hash: value.hash.try_into()?,
-            part_set_header: value.part_set_header.unwrap().try_into()?,
+            hash: value.hash.try_into().map_err(|e| Error::field("hash", e))?,
+            part_set_header: value
+                .part_set_header
+                .unwrap()
+                .try_into()
+                .map_err(|e| Error::field("part_set_header", e))?,
         })
     }
 }
@@ -78,10 +83,8 @@ impl TryFrom<RawBlockId> for Id {
 impl From<Id> for RawBlockId {
     fn from(value: Id) -> Self {
         // https://github.com/tendermint/tendermint/blob/1635d1339c73ae6a82e062cd2dc7191b029efa14/types/block.go#L1204
-        // The Go implementation encodes a nil value into an empty struct. We try our best to
-        // anticipate an empty struct by using the default implementation which would otherwise be
-        // invalid.
-        if value == Id::default() {
+        // The Go implementation encodes a nil value into an empty struct.
+        if value.is_nil() {
             RawBlockId {
                 hash: vec![],
                 part_set_header: Some(RawPartSetHeader {
@@ -108,8 +111,12 @@ impl TryFrom<RawCanonicalBlockId> for Id {
             ));
         }
         Ok(Self {
-            hash: value.hash.try_into()?,
-            part_set_header: value.part_set_header.unwrap().try_into()?,
+            hash: value.hash.try_into().map_err(|e| Error::field("hash", e))?,
+            part_set_header: value
+                .part_set_header
+                .unwrap()
+                .try_into()
+                .map_err(|e| Error::field("part_set_header", e))?,
         })
     }
 }
@@ -124,6 +131,48 @@ impl From<Id> for RawCanonicalBlockId {
 }
 
 impl Id {
+    /// The nil block ID, ie. the block ID of votes for no block, which Go
+    /// also uses in place of absent block IDs
+    pub fn nil() -> Self {
+        Self::default()
+    }
+
+    /// Whether this block ID is nil, ie. it has neither a hash nor a part
+    /// set header (as `BlockID.IsZero` in Go)
+    pub fn is_nil(&self) -> bool {
+        self.hash.is_empty()
+            && self.part_set_header.total == 0
+            && self.part_set_header.hash.is_empty()
+    }
+
+    /// Whether this block ID identifies a block, ie. it has both a hash and
+    /// a part set header (as `BlockID.IsComplete` in Go)
+    pub fn is_complete(&self) -> bool {
+        !self.hash.is_empty()
+            && self.part_set_header.total > 0
+            && !self.part_set_header.hash.is_empty()
+    }
+
+    /// Decode an optional Protobuf block ID, which is `None` if it is either
+    /// absent or nil
+    pub fn try_from_optional(value: Option<RawBlockId>) -> Result<Option<Self>, Error> {
+        Ok(value
+            .map(TryInto::try_into)
+            .transpose()?
+            .filter(|id: &Self| !id.is_nil()))
+    }
+
+    /// The canonical form of this block ID, as included in the sign bytes of
+    /// votes and proposals, which is `None` for the nil block ID (as
+    /// `CanonicalizeBlockID` in Go)
+    pub fn canonicalize(self) -> Option<RawCanonicalBlockId> {
+        if self.is_nil() {
+            None
+        } else {
+            Some(self.into())
+        }
+    }
+
     /// Get a shortened 12-character prefix of a block ID (ala git)
     pub fn prefix(&self) -> String {
         let mut result = self.to_string();
@@ -180,4 +229,29 @@ mod tests {
         let id = Id::from_str(EXAMPLE_SHA256_ID).unwrap();
         assert_eq!(&id.to_string(), EXAMPLE_SHA256_ID)
     }
+
+    #[test]
+    fn nil_block_ids() {
+        let nil = Id::nil();
+        assert!(nil.is_nil());
+        assert!(!nil.is_complete());
+        assert!(nil.canonicalize().is_none());
+
+        // Only the hash of a block ID is parsed from strings
+        let id = Id::from_str(EXAMPLE_SHA256_ID).unwrap();
+        assert!(!id.is_nil());
+        assert!(!id.is_complete());
+        let id = Id {
+            part_set_header: PartSetHeader::new(1, id.hash).unwrap(),
+            ..id
+        };
+        assert!(id.is_complete());
+        assert_eq!(id.canonicalize(), Some(RawCanonicalBlockId::from(id)));
+
+        // Go encodes nil block IDs as empty structs
+        let raw_nil = RawBlockId::from(nil);
+        assert_eq!(Id::try_from_optional(Some(raw_nil)).unwrap(), None);
+        assert_eq!(Id::try_from_optional(None).unwrap(), None);
+        assert_eq!(Id::try_from_optional(Some(id.into())).unwrap(), Some(id));
+    }
 }
@@ -5,6 +5,7 @@ use crate::error::Error;
 use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::BlockMeta as RawMeta;
+use tendermint_proto::Protobuf;
 
 /// Block metadata - Todo: implement constructor and getters
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -23,6 +24,8 @@ pub struct Meta {
     pub num_txs: i64,
 }
 
+impl Protobuf<RawMeta> for Meta {}
+
 impl TryFrom<RawMeta> for Meta {
     type Error = Error;
 
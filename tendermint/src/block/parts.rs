@@ -1,16 +1,27 @@
 //! Block parts
 
+use crate::block::MAX_BLOCK_SIZE_BYTES;
 use crate::error::Error;
 use crate::hash::Algorithm;
 use crate::hash::SHA256_HASH_SIZE;
+use crate::merkle::{proof::SimpleProof, simple_hash_from_byte_vectors};
 use crate::Hash;
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::{
-    CanonicalPartSetHeader as RawCanonicalPartSetHeader, PartSetHeader as RawPartSetHeader,
+    CanonicalPartSetHeader as RawCanonicalPartSetHeader, Part as RawPart,
+    PartSetHeader as RawPartSetHeader,
 };
 use tendermint_proto::Protobuf;
 
+/// Size of the parts blocks are split into, in bytes
+pub const BLOCK_PART_SIZE_BYTES: usize = 65536;
+
+/// Maximum number of parts of a block, ie. of parts of
+/// [`MAX_BLOCK_SIZE_BYTES`] in [`BLOCK_PART_SIZE_BYTES`]
+pub const MAX_BLOCK_PARTS_COUNT: u32 =
+    (MAX_BLOCK_SIZE_BYTES / BLOCK_PART_SIZE_BYTES as u64) as u32 + 1;
+
 /// Block parts header
 #[derive(
     Clone, Copy, Debug, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Deserialize, Serialize,
@@ -32,7 +43,7 @@ impl TryFrom<RawPartSetHeader> for Header {
 
     fn try_from(value: RawPartSetHeader) -> Result<Self, Self::Error> {
         if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
-            return Err(Error::invalid_hash_size());
+            return Err(Error::invalid_hash_size(SHA256_HASH_SIZE, value.hash.len()));
         }
         Ok(Self {
             total: value.total,
@@ -55,7 +66,7 @@ impl TryFrom<RawCanonicalPartSetHeader> for Header {
 
     fn try_from(value: RawCanonicalPartSetHeader) -> Result<Self, Self::Error> {
         if !value.hash.is_empty() && value.hash.len() != SHA256_HASH_SIZE {
-            return Err(Error::invalid_hash_size());
+            return Err(Error::invalid_hash_size(SHA256_HASH_SIZE, value.hash.len()));
         }
         Ok(Self {
             total: value.total,
@@ -89,3 +100,235 @@ impl Header {
         Ok(Header { total, hash })
     }
 }
+
+/// Block part, ie. a chunk of the Protobuf encoding of a block, along with the
+/// proof of its inclusion in the Merkle tree of the parts of the block.
+///
+/// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#part>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Part {
+    /// Index of this part in the part set
+    pub index: u32,
+
+    /// Bytes of this part
+    pub bytes: Vec<u8>,
+
+    /// Proof of the inclusion of this part in the part set
+    pub proof: SimpleProof,
+}
+
+impl Protobuf<RawPart> for Part {}
+
+impl TryFrom<RawPart> for Part {
+    type Error = Error;
+
+    fn try_from(value: RawPart) -> Result<Self, Self::Error> {
+        Ok(Self {
+            index: value.index,
+            bytes: value.bytes,
+            proof: value
+                .proof
+                .ok_or_else(|| Error::invalid_part("missing proof".to_string()))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<Part> for RawPart {
+    fn from(value: Part) -> Self {
+        RawPart {
+            index: value.index,
+            bytes: value.bytes,
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+impl Part {
+    /// Check that this part belongs to the part set with the given header.
+    pub fn verify(&self, header: &Header) -> Result<(), Error> {
+        if self.index >= header.total {
+            return Err(Error::invalid_part(format!(
+                "index {} is out of bounds for {} parts",
+                self.index, header.total
+            )));
+        }
+
+        if self.proof.index != u64::from(self.index) || self.proof.total != u64::from(header.total)
+        {
+            return Err(Error::invalid_part(
+                "proof does not match the index of the part".to_string(),
+            ));
+        }
+
+        match header.hash {
+            Hash::Sha256(root_hash) => self.proof.verify(&root_hash, &self.bytes),
+            Hash::None => Err(Error::invalid_part(
+                "part set header has no hash".to_string(),
+            )),
+        }
+    }
+}
+
+/// Set of the parts of a block, either complete when the block was split into
+/// parts, or being filled as parts are received.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartSet {
+    header: Header,
+    parts: Vec<Option<Part>>,
+}
+
+impl PartSet {
+    /// Split the given bytes into parts of the given size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero.
+    pub fn from_bytes(bytes: &[u8], part_size: usize) -> Self {
+        let chunks: Vec<Vec<u8>> = bytes.chunks(part_size).map(<[u8]>::to_vec).collect();
+
+        let header = Header {
+            total: chunks.len() as u32,
+            hash: Hash::Sha256(simple_hash_from_byte_vectors(chunks.clone())),
+        };
+
+        let proofs: Vec<SimpleProof> = (0..chunks.len())
+            .map(|index| SimpleProof::from_byte_vectors(&chunks, index).unwrap())
+            .collect();
+
+        let parts = proofs
+            .into_iter()
+            .zip(chunks)
+            .enumerate()
+            .map(|(index, (proof, bytes))| {
+                Some(Part {
+                    index: index as u32,
+                    bytes,
+                    proof,
+                })
+            })
+            .collect();
+
+        Self { header, parts }
+    }
+
+    /// Create an empty part set, to be filled with the parts of the part set
+    /// with the given header.
+    ///
+    /// Fails if the header has more than [`MAX_BLOCK_PARTS_COUNT`] parts.
+    pub fn new(header: Header) -> Result<Self, Error> {
+        if header.total > MAX_BLOCK_PARTS_COUNT {
+            return Err(Error::invalid_part_set_header(format!(
+                "too many parts: {} (max: {})",
+                header.total, MAX_BLOCK_PARTS_COUNT
+            )));
+        }
+
+        Ok(Self {
+            header,
+            parts: vec![None; header.total as usize],
+        })
+    }
+
+    /// Get the header of this part set.
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    /// Get the part with the given index, if it is in this part set.
+    pub fn part(&self, index: u32) -> Option<&Part> {
+        self.parts.get(index as usize)?.as_ref()
+    }
+
+    /// Add the given part to this part set, after checking that it belongs to it.
+    ///
+    /// Returns `false` if the part was already in this part set.
+    pub fn add_part(&mut self, part: Part) -> Result<bool, Error> {
+        part.verify(&self.header)?;
+
+        let slot = &mut self.parts[part.index as usize];
+        if slot.is_some() {
+            return Ok(false);
+        }
+
+        *slot = Some(part);
+        Ok(true)
+    }
+
+    /// The number of parts in this part set.
+    pub fn count(&self) -> u32 {
+        self.parts.iter().filter(|part| part.is_some()).count() as u32
+    }
+
+    /// Whether this part set contains all its parts.
+    pub fn is_complete(&self) -> bool {
+        self.parts.iter().all(Option::is_some)
+    }
+
+    /// Reassemble the bytes of the parts, if this part set is complete.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        self.parts.iter().try_fold(Vec::new(), |mut bytes, part| {
+            bytes.extend_from_slice(&part.as_ref()?.bytes);
+            Some(bytes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_and_reassemble() {
+        let bytes: Vec<u8> = (0..10_000_u32).map(|i| i as u8).collect();
+        let part_set = PartSet::from_bytes(&bytes, 1024);
+
+        assert_eq!(part_set.header().total, 10);
+        assert!(part_set.is_complete());
+        assert_eq!(part_set.to_bytes().unwrap(), bytes);
+
+        let mut received = PartSet::new(part_set.header()).unwrap();
+        assert_eq!(received.to_bytes(), None);
+
+        for index in (0..10).rev() {
+            let part = part_set.part(index).unwrap().clone();
+            assert!(received.add_part(part.clone()).unwrap());
+            assert!(!received.add_part(part).unwrap());
+        }
+
+        assert_eq!(received.count(), 10);
+        assert_eq!(received, part_set);
+    }
+
+    #[test]
+    fn reject_invalid_parts() {
+        let bytes: Vec<u8> = (0..1000_u32).map(|i| i as u8).collect();
+        let part_set = PartSet::from_bytes(&bytes, 100);
+        let mut received = PartSet::new(part_set.header()).unwrap();
+
+        let mut tampered = part_set.part(3).unwrap().clone();
+        tampered.bytes[0] ^= 1;
+        assert!(received.add_part(tampered).is_err());
+
+        let mut misplaced = part_set.part(3).unwrap().clone();
+        misplaced.index = 4;
+        assert!(received.add_part(misplaced).is_err());
+
+        let other = PartSet::from_bytes(&bytes[1..], 100);
+        assert!(received.add_part(other.part(3).unwrap().clone()).is_err());
+
+        let raw = RawPart::from(part_set.part(3).unwrap().clone());
+        assert_eq!(Part::try_from(raw).unwrap(), *part_set.part(3).unwrap());
+
+        assert_eq!(received.count(), 0);
+    }
+
+    #[test]
+    fn reject_too_many_parts() {
+        let header = Header::new(MAX_BLOCK_PARTS_COUNT, Hash::Sha256([0; 32])).unwrap();
+        assert!(PartSet::new(header).is_ok());
+
+        let header = Header::new(u32::MAX, Hash::Sha256([0; 32])).unwrap();
+        assert!(PartSet::new(header).is_err());
+    }
+}
@@ -8,7 +8,7 @@ use std::{
 };
 
 /// Block round for a particular chain
-#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord, Default)]
 pub struct Round(u32);
 
 impl TryFrom<i32> for Round {
@@ -71,12 +71,6 @@ impl Debug for Round {
     }
 }
 
-impl Default for Round {
-    fn default() -> Self {
-        Round(0)
-    }
-}
-
 impl Display for Round {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -1,10 +1,17 @@
 //! SignedHeader contains commit and and block header.
 //! It is what the rpc endpoint /commit returns and hence can be used by a
 //! light client.
-use crate::{block, Error};
+use crate::block::CommitSig;
+use crate::compat::CompatMode;
+use crate::signature::BatchVerifier;
+use crate::trust_threshold::TrustThresholdFraction;
+use crate::vote::{self, ValidatorIndex, Vote, VotingPowerTally};
+use crate::{account, block, chain, validator, Error};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use tendermint_proto::types::SignedHeader as RawSignedHeader;
+use tendermint_proto::Protobuf;
 
 /// Signed block headers
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -17,6 +24,8 @@ pub struct SignedHeader {
     pub commit: block::Commit,
 }
 
+impl Protobuf<RawSignedHeader> for SignedHeader {}
+
 impl TryFrom<RawSignedHeader> for SignedHeader {
     type Error = Error;
 
@@ -24,11 +33,13 @@ impl TryFrom<RawSignedHeader> for SignedHeader {
         let header = value
             .header
             .ok_or_else(Error::invalid_signed_header)?
-            .try_into()?;
+            .try_into()
+            .map_err(|e| Error::field("header", e))?;
         let commit = value
             .commit
             .ok_or_else(Error::invalid_signed_header)?
-            .try_into()?;
+            .try_into()
+            .map_err(|e| Error::field("commit", e))?;
         Self::new(header, commit) // Additional checks
     }
 }
@@ -60,4 +71,417 @@ impl SignedHeader {
     pub fn commit(&self) -> &block::Commit {
         &self.commit
     }
+
+    /// Check that this signed header belongs to the given chain and that its
+    /// commit is consistent with its header, as done by
+    /// `SignedHeader.ValidateBasic` in Go.
+    ///
+    /// This checks that the commit is for the height and the hash of the
+    /// header, and that it has signatures, the ones which are not absent
+    /// being non-empty. The signatures themselves are not verified, see
+    /// [`SignedHeader::verify`] for this.
+    pub fn validate(&self, chain_id: &chain::Id) -> Result<(), Error> {
+        let inconsistent = |detail: String| Err(Error::inconsistent_signed_header(detail));
+
+        if &self.header.chain_id != chain_id {
+            return inconsistent(format!(
+                "header belongs to another chain: {}, not {}",
+                self.header.chain_id, chain_id
+            ));
+        }
+
+        if self.commit.height != self.header.height {
+            return inconsistent(format!(
+                "commit is for height {}, but header is at height {}",
+                self.commit.height, self.header.height
+            ));
+        }
+
+        let header_hash = self.header.hash();
+        if self.commit.block_id.hash != header_hash {
+            return inconsistent(format!(
+                "commit is for block {}, but header hash is {}",
+                self.commit.block_id.hash, header_hash
+            ));
+        }
+
+        if self.commit.signatures.is_empty() {
+            return inconsistent("commit has no signatures".to_string());
+        }
+
+        for (index, commit_sig) in self.commit.signatures.iter().enumerate() {
+            let signature = match commit_sig {
+                CommitSig::BlockIdFlagAbsent => continue,
+                CommitSig::BlockIdFlagCommit { signature, .. }
+                | CommitSig::BlockIdFlagNil { signature, .. } => signature,
+            };
+            if signature.as_bytes().is_empty() {
+                return inconsistent(format!("signature {} of the commit is empty", index));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify that the commit is for this header, and that the validators of the given set
+    /// which signed for it hold more than the given fraction of its total voting power.
+    ///
+    /// Fails if any signature of a validator of the set is invalid, or if a validator
+    /// signed more than once, reporting all of the signatures at fault.
+    /// Signatures of validators which are not in the set are ignored.
+    ///
+    /// Signatures are verified as a batch, see [`BatchVerifier`].
+    pub fn verify(
+        &self,
+        validator_set: &validator::Set,
+        trust_threshold: TrustThresholdFraction,
+    ) -> Result<CommitVerification, Error> {
+        self.verify_with_compat(validator_set, trust_threshold, CompatMode::LATEST)
+    }
+
+    /// Verify this signed header as [`SignedHeader::verify`] does, hashing the header
+    /// and the sign bytes of the votes as the given version of the protocol does.
+    pub fn verify_with_compat(
+        &self,
+        validator_set: &validator::Set,
+        trust_threshold: TrustThresholdFraction,
+        compat: CompatMode,
+    ) -> Result<CommitVerification, Error> {
+        let header_hash = self.header.hash_with_compat(compat)?;
+        if header_hash != self.commit.block_id.hash {
+            return Err(Error::header_commit_mismatch(
+                header_hash.to_string(),
+                self.commit.block_id.hash.to_string(),
+            ));
+        }
+
+        let verification =
+            self.verify_commit_signatures_with_compat(validator_set, trust_threshold, compat)?;
+
+        if !verification.invalid_signatures.is_empty() {
+            return Err(Error::invalid_commit_signatures(
+                verification.invalid_signatures,
+            ));
+        }
+
+        if !verification.voting_power.is_enough() {
+            return Err(Error::insufficient_voting_power(
+                verification.voting_power.tallied,
+                verification.voting_power.total,
+            ));
+        }
+
+        Ok(verification)
+    }
+
+    /// Verify the signatures of the commit made by the validators of the given set,
+    /// against the canonical sign bytes of their votes, and tally their voting power
+    /// against the given trust threshold.
+    ///
+    /// Unlike [`SignedHeader::verify`], this does not fail on invalid signatures,
+    /// nor on insufficient voting power, but reports them in the outcome along with
+    /// the voting power of the valid ones.
+    pub fn verify_commit_signatures(
+        &self,
+        validator_set: &validator::Set,
+        trust_threshold: TrustThresholdFraction,
+    ) -> Result<CommitVerification, Error> {
+        self.verify_commit_signatures_with_compat(
+            validator_set,
+            trust_threshold,
+            CompatMode::LATEST,
+        )
+    }
+
+    /// Verify the signatures of the commit as [`SignedHeader::verify_commit_signatures`]
+    /// does, against the sign bytes of the votes of the given version of the protocol.
+    ///
+    /// Fails if the sign bytes of this version cannot be computed, see
+    /// [`Vote::to_signable_vec_with_compat`].
+    pub fn verify_commit_signatures_with_compat(
+        &self,
+        validator_set: &validator::Set,
+        trust_threshold: TrustThresholdFraction,
+        compat: CompatMode,
+    ) -> Result<CommitVerification, Error> {
+        let mut invalid_signatures = Vec::new();
+        let mut seen_validators = HashSet::new();
+
+        // Signatures to verify as a batch, along with what to do once verified.
+        let mut batch = BatchVerifier::new();
+        let mut batched = Vec::new();
+
+        for (index, commit_sig) in self.commit.signatures.iter().enumerate() {
+            let vote = match self.vote(index, commit_sig) {
+                Some(vote) => vote,
+                None => continue,
+            };
+
+            let invalid = |reason| InvalidCommitSignature {
+                index,
+                validator_address: vote.validator_address,
+                reason,
+            };
+
+            if !seen_validators.insert(vote.validator_address) {
+                invalid_signatures.push(invalid(SignatureFailure::DuplicateValidator));
+                continue;
+            }
+
+            let validator = match validator_set.validator(vote.validator_address) {
+                Some(validator) => validator,
+                None => continue,
+            };
+
+            let sign_bytes =
+                vote.to_signable_vec_with_compat(self.header.chain_id.clone(), compat)?;
+
+            batch.push(validator.pub_key, sign_bytes, vote.signature);
+            batched.push((
+                invalid(SignatureFailure::InvalidSignature),
+                commit_sig.is_commit(),
+                validator.power,
+            ));
+        }
+
+        let invalid_batched = batch.verify().err().unwrap_or_default();
+
+        let mut voting_power =
+            VotingPowerTally::new(validator_set.total_voting_power(), trust_threshold);
+        for (batch_index, (invalid, is_commit, power)) in batched.into_iter().enumerate() {
+            if invalid_batched.contains(&batch_index) {
+                invalid_signatures.push(invalid);
+            } else if is_commit {
+                voting_power.tally(power)?;
+            }
+        }
+        invalid_signatures.sort_by_key(|invalid| invalid.index);
+
+        Ok(CommitVerification {
+            voting_power,
+            invalid_signatures,
+        })
+    }
+
+    /// The vote the given signature of the commit was made for, if it is not absent.
+    fn vote(&self, index: usize, commit_sig: &CommitSig) -> Option<Vote> {
+        let (validator_address, timestamp, signature, block_id) = match commit_sig {
+            CommitSig::BlockIdFlagAbsent => return None,
+            CommitSig::BlockIdFlagCommit {
+                validator_address,
+                timestamp,
+                signature,
+            } => (
+                *validator_address,
+                *timestamp,
+                signature,
+                Some(self.commit.block_id),
+            ),
+            CommitSig::BlockIdFlagNil {
+                validator_address,
+                timestamp,
+                signature,
+            } => (*validator_address, *timestamp, signature, None),
+        };
+
+        Some(Vote {
+            vote_type: vote::Type::Precommit,
+            height: self.commit.height,
+            round: self.commit.round,
+            block_id,
+            timestamp: Some(timestamp),
+            validator_address,
+            validator_index: ValidatorIndex::try_from(index).ok()?,
+            signature: *signature,
+        })
+    }
+}
+
+/// Outcome of the verification of the signatures of a commit against a validator set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitVerification {
+    /// Tally of the voting power of the validators of the set which validly signed
+    /// for the block, out of the total voting power of the set
+    pub voting_power: VotingPowerTally,
+    /// Signatures which failed verification
+    pub invalid_signatures: Vec<InvalidCommitSignature>,
+}
+
+/// A signature of a commit which failed verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCommitSignature {
+    /// Index of the signature in the commit
+    pub index: usize,
+    /// Address of the validator which made the signature
+    pub validator_address: account::Id,
+    /// Why the signature failed verification
+    pub reason: SignatureFailure,
+}
+
+/// Reason why a signature of a commit failed verification.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SignatureFailure {
+    /// The signature does not match the sign bytes of the vote,
+    /// and the public key of the validator
+    InvalidSignature,
+    /// The validator already signed the commit
+    DuplicateValidator,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{header, keypair, sign_header, validator};
+    use crate::trust_threshold::TrustThresholdFraction;
+    use crate::Signature;
+    use ed25519_dalek::Signer;
+
+    #[test]
+    fn validate_checks_consistency() {
+        let keypairs = vec![keypair(1), keypair(2)];
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+        let signed_header = sign_header(header(), &validator_set, &keypairs);
+        let chain_id = signed_header.header.chain_id.clone();
+
+        signed_header.validate(&chain_id).unwrap();
+
+        assert!(signed_header
+            .validate(&"other-chain".parse().unwrap())
+            .is_err());
+
+        let mut other = signed_header.clone();
+        other.commit.height = other.commit.height.increment();
+        assert!(other.validate(&chain_id).is_err());
+
+        let mut other = signed_header.clone();
+        other.commit.block_id.hash = crate::Hash::None;
+        assert!(other.validate(&chain_id).is_err());
+
+        let mut other = signed_header.clone();
+        other.commit.signatures.clear();
+        assert!(other.validate(&chain_id).is_err());
+
+        let mut other = signed_header;
+        if let CommitSig::BlockIdFlagCommit { signature, .. } = &mut other.commit.signatures[1] {
+            *signature = Signature::default();
+        }
+        assert!(other.validate(&chain_id).is_err());
+    }
+
+    #[test]
+    fn verify_tallies_valid_signatures() {
+        let keypairs = vec![keypair(1), keypair(2), keypair(3)];
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+
+        let signed_header = sign_header(header(), &validator_set, &keypairs);
+
+        let verification = signed_header
+            .verify(&validator_set, TrustThresholdFraction::TWO_THIRDS)
+            .unwrap();
+        assert_eq!(verification.voting_power.tallied, 30);
+        assert_eq!(verification.voting_power.total, 30);
+    }
+
+    #[test]
+    fn verify_reports_invalid_signatures() {
+        let keypairs = vec![keypair(1), keypair(2), keypair(3)];
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+
+        let mut signed_header = sign_header(header(), &validator_set, &keypairs);
+
+        // Have the first validator sign for another block.
+        let forged = signed_header.commit.signatures[1].clone();
+        if let (
+            CommitSig::BlockIdFlagCommit { signature, .. },
+            CommitSig::BlockIdFlagCommit {
+                signature: other, ..
+            },
+        ) = (&mut signed_header.commit.signatures[0], forged)
+        {
+            *signature = other;
+        }
+
+        let verification = signed_header
+            .verify_commit_signatures(&validator_set, TrustThresholdFraction::TWO_THIRDS)
+            .unwrap();
+        assert_eq!(verification.voting_power.tallied, 20);
+        assert!(!verification.voting_power.is_enough());
+        assert_eq!(
+            verification.invalid_signatures,
+            vec![InvalidCommitSignature {
+                index: 0,
+                validator_address: validator_set.validators()[0].address,
+                reason: SignatureFailure::InvalidSignature,
+            }]
+        );
+
+        let err = signed_header
+            .verify(&validator_set, TrustThresholdFraction::ONE_THIRD)
+            .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            crate::error::ErrorDetail::InvalidCommitSignatures(e) if e.signatures.len() == 1
+        ));
+    }
+
+    #[test]
+    fn verify_reports_all_invalid_signatures_of_large_commits() {
+        let keypairs: Vec<_> = (1..=150).map(keypair).collect();
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+
+        let mut signed_header = sign_header(header(), &validator_set, &keypairs);
+
+        let forged = [17, 101];
+        for &index in &forged {
+            if let CommitSig::BlockIdFlagCommit { signature, .. } =
+                &mut signed_header.commit.signatures[index]
+            {
+                *signature = Signature::Ed25519(keypairs[0].sign(b"another message"));
+            }
+        }
+
+        let verification = signed_header
+            .verify_commit_signatures(&validator_set, TrustThresholdFraction::TWO_THIRDS)
+            .unwrap();
+        assert_eq!(verification.voting_power.tallied, 1480);
+        assert_eq!(
+            verification
+                .invalid_signatures
+                .iter()
+                .map(|invalid| invalid.index)
+                .collect::<Vec<_>>(),
+            forged
+        );
+    }
+
+    #[test]
+    fn verify_requires_enough_voting_power() {
+        let keypairs = vec![keypair(1), keypair(2), keypair(3)];
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+
+        let signed_header = sign_header(header(), &validator_set, &keypairs);
+
+        // Only the first validator is part of the larger, trusted set.
+        let mut trusted = vec![validator(&keypairs[0], 10)];
+        trusted.push(validator(&keypair(4), 50));
+        let trusted_set = validator::Set::without_proposer(trusted).unwrap();
+
+        let err = signed_header
+            .verify(&trusted_set, TrustThresholdFraction::ONE_THIRD)
+            .unwrap_err();
+        assert!(matches!(
+            err.detail(),
+            crate::error::ErrorDetail::InsufficientVotingPower(e) if e.signed == 10 && e.total == 60
+        ));
+    }
 }
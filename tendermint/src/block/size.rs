@@ -9,6 +9,28 @@ use {
     tendermint_proto::abci::BlockParams as RawSize,
 };
 
+/// Maximum value of the `max_bytes` block size parameter
+pub const MAX_BLOCK_SIZE_BYTES: u64 = 104_857_600;
+
+/// Maximum size of the Protobuf encoding of a block header
+pub const MAX_HEADER_BYTES: u64 = 626;
+
+/// Maximum size of the Protobuf encoding of a block besides its header, data,
+/// evidence and last commit, ie. of their keys and lengths
+pub const MAX_OVERHEAD_FOR_BLOCK: u64 = 11;
+
+/// Maximum size of the Protobuf encoding of a commit besides its signatures
+pub const MAX_COMMIT_OVERHEAD_BYTES: u64 = 94;
+
+/// Maximum size of the Protobuf encoding of a commit signature
+pub const MAX_COMMIT_SIG_BYTES: u64 = 109;
+
+/// Maximum size of the Protobuf encoding of a commit of the given number of
+/// validators
+pub fn max_commit_bytes(validator_count: usize) -> u64 {
+    MAX_COMMIT_OVERHEAD_BYTES + MAX_COMMIT_SIG_BYTES * validator_count as u64
+}
+
 /// Block size parameters
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Size {
@@ -30,6 +52,42 @@ impl Size {
     pub fn default_time_iota_ms() -> i64 {
         1000
     }
+
+    /// The maximum size of the transactions of a block, ie. of the Protobuf
+    /// encoding of its `data`, given the size of its evidence and the number
+    /// of validators of its last commit
+    ///
+    /// Transactions can be selected for a block until the sum of their
+    /// [`Transaction::proto_size`](crate::abci::Transaction::proto_size)s
+    /// would exceed it.
+    pub fn max_data_bytes(
+        &self,
+        evidence_bytes: u64,
+        validator_count: usize,
+    ) -> Result<u64, Error> {
+        let overhead = MAX_OVERHEAD_FOR_BLOCK
+            + MAX_HEADER_BYTES
+            + max_commit_bytes(validator_count)
+            + evidence_bytes;
+
+        self.max_bytes.checked_sub(overhead).ok_or_else(|| {
+            Error::invalid_block(format!(
+                "block.max_bytes is too small to fit a header, a commit of {} validators and {} bytes of evidence: {} < {}",
+                validator_count, evidence_bytes, self.max_bytes, overhead
+            ))
+        })
+    }
+}
+
+impl Default for Size {
+    /// The default block size parameters of Tendermint
+    fn default() -> Self {
+        Self {
+            max_bytes: 22_020_096,
+            max_gas: -1,
+            time_iota_ms: Self::default_time_iota_ms(),
+        }
+    }
 }
 
 impl Protobuf<RawSize> for Size {}
@@ -58,3 +116,27 @@ impl From<Size> for RawSize {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_data_bytes() {
+        let size = Size::default();
+        assert_eq!(
+            size.max_data_bytes(0, 1).unwrap(),
+            22_020_096 - 11 - 626 - (94 + 109)
+        );
+        assert_eq!(
+            size.max_data_bytes(1000, 100).unwrap(),
+            22_020_096 - 11 - 626 - (94 + 109 * 100) - 1000
+        );
+
+        let size = Size {
+            max_bytes: 1000,
+            ..Size::default()
+        };
+        assert!(size.max_data_bytes(0, 10).is_err());
+    }
+}
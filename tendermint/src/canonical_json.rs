@@ -0,0 +1,173 @@
+//! Canonical JSON encoding, as used by legacy (Amino JSON) signing schemes
+//! and to hash JSON documents such as genesis files
+//!
+//! The canonical encoding of a value is the encoding of Go's `encoding/json`
+//! with the keys of its objects sorted, as done by the Cosmos SDK to compute
+//! sign bytes:
+//! - object keys are sorted by their bytes, at every level
+//! - no whitespace is inserted between tokens
+//! - integers are encoded in decimal, while floating-point numbers, which
+//!   have no canonical encoding, are rejected
+//! - strings escape `<`, `>`, `&`, U+2028 and U+2029, along with quotes,
+//!   backslashes and control characters
+//!
+//! The values are first serialized with their `Serialize` implementation, so
+//! eg. 64-bit integers which Tendermint encodes as JSON strings remain strings.
+
+use crate::error::Error;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Serialize the given value into its canonical JSON encoding
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    let value = serde_json::to_value(value).map_err(Error::serde_json)?;
+    let mut out = String::new();
+    write_value(&value, &mut out)?;
+    Ok(out)
+}
+
+/// Serialize the given value into the bytes of its canonical JSON encoding,
+/// eg. to sign them
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    to_string(value).map(String::into_bytes)
+}
+
+fn write_value(value: &Value, out: &mut String) -> Result<(), Error> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            if !(n.is_i64() || n.is_u64()) {
+                return Err(Error::non_canonical_json(format!(
+                    "floating-point number: {}",
+                    n
+                )));
+            }
+            out.push_str(&n.to_string());
+        }
+        Value::String(s) => write_string(s, out),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(value, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_value(value, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => {
+                write!(out, "\\u{:04x}", c as u32).unwrap()
+            }
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Msg {
+        to_address: String,
+        from_address: String,
+        amount: Vec<HashMap<String, String>>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SignDoc {
+        sequence: String,
+        memo: String,
+        msgs: Vec<Msg>,
+        chain_id: String,
+        account_number: u64,
+        fee: Option<i64>,
+    }
+
+    fn sign_doc() -> SignDoc {
+        let mut coin = HashMap::new();
+        coin.insert("denom".to_string(), "stake".to_string());
+        coin.insert("amount".to_string(), "10".to_string());
+
+        SignDoc {
+            sequence: "6".to_string(),
+            memo: "<a & b>\n\"quoted\"\u{1}\u{2028}".to_string(),
+            msgs: vec![Msg {
+                to_address: "cosmos1b".to_string(),
+                from_address: "cosmos1a".to_string(),
+                amount: vec![coin],
+            }],
+            chain_id: "test-chain".to_string(),
+            account_number: 3,
+            fee: None,
+        }
+    }
+
+    #[test]
+    fn golden_encoding() {
+        assert_eq!(
+            to_string(&sign_doc()).unwrap(),
+            concat!(
+                r#"{"account_number":3,"chain_id":"test-chain","fee":null,"#,
+                r#""memo":"\u003ca \u0026 b\u003e\n\"quoted\"\u0001\u2028","#,
+                r#""msgs":[{"amount":[{"amount":"10","denom":"stake"}],"#,
+                r#""from_address":"cosmos1a","to_address":"cosmos1b"}],"#,
+                r#""sequence":"6"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let encoded = to_string(&sign_doc()).unwrap();
+
+        let decoded: SignDoc = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, sign_doc());
+
+        // Canonicalizing a canonical encoding leaves it unchanged
+        let value: Value = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(to_string(&value).unwrap(), encoded);
+        assert_eq!(to_vec(&value).unwrap(), encoded.into_bytes());
+    }
+
+    #[test]
+    fn reject_floats() {
+        assert!(to_string(&1.5_f64).is_err());
+        assert_eq!(to_string(&u64::MAX).unwrap(), "18446744073709551615");
+        assert_eq!(to_string(&i64::MIN).unwrap(), "-9223372036854775808");
+    }
+}
@@ -16,6 +16,11 @@ use tendermint_proto::Protobuf;
 // TODO: update this when `chain::Id` is derived from a digest output
 pub const MAX_LENGTH: usize = 50;
 
+/// Separator between the name and the revision number of chain IDs in the
+/// revision format, eg. `cosmoshub-4`, as used by IBC to tell upgrades of a
+/// chain apart
+pub const REVISION_SEPARATOR: char = '-';
+
 /// Chain identifier (e.g. 'gaia-9000')
 #[derive(Clone)]
 pub struct Id(String);
@@ -30,10 +35,15 @@ impl TryFrom<String> for Id {
             return Err(Error::length());
         }
 
-        for byte in value.as_bytes() {
-            match byte {
-                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (),
-                _ => return Err(Error::parse("chain id charset".to_string())),
+        for c in value.chars() {
+            match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => (),
+                _ => {
+                    return Err(Error::parse(format!(
+                        "chain id charset: invalid character {:?}",
+                        c
+                    )))
+                }
             }
         }
 
@@ -55,7 +65,68 @@ impl Id {
 
     /// Get the chain ID as a raw bytes.
     pub fn as_bytes(&self) -> &[u8] {
-        self.0.as_str().as_bytes()
+        self.0.as_bytes()
+    }
+
+    /// Chain ID in the revision format, with the given name and positive
+    /// revision number, eg. `cosmoshub-4`
+    pub fn from_name_and_revision(name: &str, revision_number: u64) -> Result<Self, Error> {
+        if revision_number == 0 {
+            return Err(Error::parse(
+                "chain id revision number must be positive".to_string(),
+            ));
+        }
+
+        let id = Self::try_from(format!("{}{}{}", name, REVISION_SEPARATOR, revision_number))?;
+        if !id.is_revision_format() {
+            return Err(Error::parse(format!(
+                "chain id name {:?} is not followed by its revision number",
+                name
+            )));
+        }
+
+        Ok(id)
+    }
+
+    /// Split this chain ID into its name and its revision number, if it is
+    /// in the revision format `{name}-{revision_number}`, where the revision
+    /// number is a positive integer without leading zeros
+    pub fn split_revision(&self) -> Option<(&str, u64)> {
+        let index = self.0.rfind(REVISION_SEPARATOR)?;
+        let (name, revision) = (&self.0[..index], &self.0[index + 1..]);
+
+        if name.is_empty() || name.ends_with(REVISION_SEPARATOR) || revision.starts_with('0') {
+            return None;
+        }
+        if !revision.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some((name, revision.parse().ok()?))
+    }
+
+    /// Whether this chain ID is in the revision format, eg. `cosmoshub-4`
+    pub fn is_revision_format(&self) -> bool {
+        self.split_revision().is_some()
+    }
+
+    /// Name of the chain, ie. this chain ID without its revision number, if
+    /// any
+    pub fn name(&self) -> &str {
+        self.split_revision()
+            .map_or(self.as_str(), |(name, _)| name)
+    }
+
+    /// Revision number of this chain ID, or zero if it is not in the
+    /// revision format
+    pub fn revision_number(&self) -> u64 {
+        self.split_revision().map_or(0, |(_, revision)| revision)
+    }
+
+    /// This chain ID with the given revision number, eg. for the chain after
+    /// an upgrade
+    pub fn with_revision_number(&self, revision_number: u64) -> Result<Self, Error> {
+        Self::from_name_and_revision(self.name(), revision_number)
     }
 }
 
@@ -77,7 +148,7 @@ impl Display for Id {
     }
 }
 
-impl<'a> TryFrom<&'a str> for Id {
+impl TryFrom<&str> for Id {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
@@ -158,6 +229,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejects_invalid_characters() {
+        match "gaia 9000".parse::<Id>().unwrap_err().detail() {
+            ErrorDetail::Parse(e) => assert!(e.data.contains("' '")),
+            _ => panic!("expected parse error"),
+        }
+    }
+
+    #[test]
+    fn revision_numbers() {
+        let id = Id::from_str("cosmoshub-4").unwrap();
+        assert_eq!(id.split_revision(), Some(("cosmoshub", 4)));
+        assert_eq!(id.name(), "cosmoshub");
+        assert_eq!(id.revision_number(), 4);
+        assert_eq!(id.with_revision_number(5).unwrap().as_str(), "cosmoshub-5");
+
+        let id = Id::from_str("ibc-test-chain-12").unwrap();
+        assert_eq!(id.split_revision(), Some(("ibc-test-chain", 12)));
+
+        for non_revision in &[
+            "gaia", "chain-0", "chain-01", "chain-", "-4", "chain--4", "chain-4a",
+        ] {
+            let id = Id::from_str(non_revision).unwrap();
+            assert!(!id.is_revision_format(), "{}", non_revision);
+            assert_eq!(id.revision_number(), 0);
+            assert_eq!(id.name(), *non_revision);
+        }
+
+        assert_eq!(
+            Id::from_name_and_revision("gaia", 9000).unwrap().as_str(),
+            "gaia-9000"
+        );
+        assert_eq!(
+            Id::from_str("gaia")
+                .unwrap()
+                .with_revision_number(1)
+                .unwrap()
+                .as_str(),
+            "gaia-1"
+        );
+        assert!(Id::from_name_and_revision("gaia", 0).is_err());
+        assert!(Id::from_name_and_revision("", 1).is_err());
+        assert!(Id::from_name_and_revision("gaia-", 1).is_err());
+    }
+
     #[test]
     fn rejects_overlength_chain_ids() {
         let overlong_id = String::from_utf8(vec![b'x'; MAX_LENGTH + 1]).unwrap();
@@ -3,9 +3,10 @@
 mod id;
 
 pub use self::id::Id;
-use crate::serializers;
+use crate::{serializers, Error};
 pub use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use subtle_encoding::hex;
 
 /// Channels
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -35,6 +36,15 @@ pub struct Channel {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 pub struct Channels(String);
 
+impl Channels {
+    /// The IDs of the channels, encoded in hexadecimal with one byte per
+    /// channel
+    pub fn ids(&self) -> Result<Vec<Id>, Error> {
+        let ids = hex::decode_upper(&self.0).map_err(Error::subtle_encoding)?;
+        Ok(ids.into_iter().map(|id| Id(id.into())).collect())
+    }
+}
+
 impl Display for Channels {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -0,0 +1,265 @@
+//! Compatibility with the versions of the Tendermint protocol, which encode
+//! the same data differently, eg. when signing votes or hashing headers.
+//!
+//! Tendermint 0.33 encodes data with Amino, while Tendermint 0.34 encodes data
+//! with Protobuf: signing and hashing data as Tendermint 0.33 does requires
+//! the `amino` feature, without which it fails.
+//!
+//! The JSON encodings of both versions are deserialized into the same domain
+//! types, eg. the commits of Tendermint 0.33, whose round and part set header
+//! total are strings and whose block IDs have `parts`, not `part_set_header`.
+
+use crate::{error::Error, Version};
+use std::fmt;
+
+/// Version of the protocol by which data is encoded
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CompatMode {
+    /// Tendermint 0.33
+    V0_33,
+    /// Tendermint 0.34
+    V0_34,
+}
+
+impl CompatMode {
+    /// The latest version of the protocol
+    pub const LATEST: CompatMode = CompatMode::V0_34;
+
+    /// The compatibility mode of nodes of the given version of Tendermint, eg.
+    /// as reported by their `/status` endpoint
+    pub fn from_version(version: &Version) -> Result<CompatMode, Error> {
+        let version = version.to_string();
+        let mut numbers = version
+            .trim_start_matches('v')
+            .split(|c: char| !c.is_ascii_digit())
+            .map(str::parse::<u64>);
+
+        match (numbers.next(), numbers.next()) {
+            (Some(Ok(0)), Some(Ok(33))) => Ok(CompatMode::V0_33),
+            (Some(Ok(0)), Some(Ok(34))) => Ok(CompatMode::V0_34),
+            _ => Err(Error::unsupported_tendermint_version(version)),
+        }
+    }
+}
+
+impl Default for CompatMode {
+    fn default() -> Self {
+        CompatMode::LATEST
+    }
+}
+
+impl fmt::Display for CompatMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompatMode::V0_33 => f.write_str("v0.33"),
+            CompatMode::V0_34 => f.write_str("v0.34"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> Version {
+        serde_json::from_value(serde_json::Value::String(version.to_string())).unwrap()
+    }
+
+    #[test]
+    fn compat_mode_from_version() {
+        assert_eq!(
+            CompatMode::from_version(&version("v0.34.9")).unwrap(),
+            CompatMode::V0_34
+        );
+        assert_eq!(
+            CompatMode::from_version(&version("0.34.0-rc6")).unwrap(),
+            CompatMode::V0_34
+        );
+        assert_eq!(
+            CompatMode::from_version(&version("0.33.9")).unwrap(),
+            CompatMode::V0_33
+        );
+        assert!(CompatMode::from_version(&version("0.35.0")).is_err());
+        assert!(CompatMode::from_version(&version("1.34.0")).is_err());
+        assert!(CompatMode::from_version(&version("")).is_err());
+    }
+
+    #[test]
+    fn deserialize_commits_of_each_version() {
+        use crate::block::{Commit, CommitSig};
+
+        let v0_33 = r#"{
+            "height": "10",
+            "round": "1",
+            "block_id": {
+                "hash": "0101010101010101010101010101010101010101010101010101010101010101",
+                "parts": {
+                    "total": "1",
+                    "hash": "0202020202020202020202020202020202020202020202020202020202020202"
+                }
+            },
+            "signatures": [
+                {
+                    "block_id_flag": 1,
+                    "validator_address": "",
+                    "timestamp": "0001-01-01T00:00:00Z",
+                    "signature": null
+                }
+            ]
+        }"#;
+        let commit: Commit = serde_json::from_str(v0_33).unwrap();
+        assert_eq!(commit.round.value(), 1);
+        assert_eq!(commit.block_id.part_set_header.total, 1);
+        assert_eq!(commit.signatures, vec![CommitSig::BlockIdFlagAbsent]);
+
+        let v0_34 = serde_json::to_value(&commit).unwrap();
+        assert_eq!(v0_34["round"], 1);
+        assert_eq!(v0_34["block_id"]["part_set_header"]["total"], 1);
+        assert_eq!(serde_json::from_value::<Commit>(v0_34).unwrap(), commit);
+    }
+
+    /// A header signed by a single validator for the given version of the
+    /// protocol, along with the validator set.
+    fn signed_header(
+        compat: CompatMode,
+    ) -> (
+        crate::block::signed_header::SignedHeader,
+        crate::validator::Set,
+    ) {
+        use crate::block::{signed_header::SignedHeader, Commit, CommitSig};
+        use crate::test::{header, keypair, validator};
+        use crate::{block, vote, Signature, Vote};
+        use ed25519_dalek::Signer;
+        use std::convert::TryFrom;
+
+        let keypair = keypair(7);
+        let info = validator(&keypair, 10);
+
+        let header = header();
+        let block_id = block::Id {
+            // the hash of the header cannot be computed as Tendermint 0.33
+            // does without the amino feature
+            hash: header.hash_with_compat(compat).unwrap_or_default(),
+            part_set_header: Default::default(),
+        };
+        let vote = Vote {
+            vote_type: vote::Type::Precommit,
+            height: header.height,
+            round: Default::default(),
+            block_id: Some(block_id),
+            timestamp: Some(header.time),
+            validator_address: info.address,
+            validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+            signature: Signature::default(),
+        };
+        let signature = vote
+            .to_signable_vec_with_compat(header.chain_id.clone(), compat)
+            .map(|sign_bytes| Signature::Ed25519(keypair.sign(&sign_bytes)))
+            .unwrap_or_default();
+        let commit = Commit {
+            height: header.height,
+            round: Default::default(),
+            block_id,
+            signatures: vec![CommitSig::BlockIdFlagCommit {
+                validator_address: info.address,
+                timestamp: header.time,
+                signature,
+            }],
+        };
+
+        (
+            SignedHeader::new(header, commit).unwrap(),
+            crate::validator::Set::without_proposer(vec![info]).unwrap(),
+        )
+    }
+
+    #[cfg(feature = "amino")]
+    #[test]
+    fn verify_signed_headers_of_each_version() {
+        use crate::trust_threshold::TrustThresholdFraction;
+
+        for &(signed, other) in &[
+            (CompatMode::V0_33, CompatMode::V0_34),
+            (CompatMode::V0_34, CompatMode::V0_33),
+        ] {
+            let (signed_header, validators) = signed_header(signed);
+            let threshold = TrustThresholdFraction::TWO_THIRDS;
+
+            let verification = signed_header
+                .verify_with_compat(&validators, threshold, signed)
+                .unwrap();
+            assert_eq!(verification.voting_power.tallied, 10);
+            assert!(signed_header
+                .verify_with_compat(&validators, threshold, other)
+                .is_err());
+        }
+    }
+
+    #[cfg(not(feature = "amino"))]
+    #[test]
+    fn amino_is_required_for_v0_33() {
+        use crate::trust_threshold::TrustThresholdFraction;
+
+        let (signed_header, validators) = signed_header(CompatMode::V0_34);
+        signed_header
+            .verify(&validators, TrustThresholdFraction::TWO_THIRDS)
+            .unwrap();
+        assert!(signed_header
+            .header
+            .hash_with_compat(CompatMode::V0_33)
+            .is_err());
+        assert!(signed_header
+            .verify_commit_signatures_with_compat(
+                &validators,
+                TrustThresholdFraction::TWO_THIRDS,
+                CompatMode::V0_33
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "amino")]
+    #[test]
+    fn verify_votes_of_each_version() {
+        use crate::{
+            account, block, chain, signature::Signer, vote, Hash, PublicKey, Signature, Vote,
+        };
+        use std::convert::TryFrom;
+
+        let keypair = crate::test::keypair(7);
+        let pub_key = PublicKey::from(keypair.public);
+        let chain_id: chain::Id = "test-chain".parse().unwrap();
+
+        let mut vote = Vote {
+            vote_type: vote::Type::Precommit,
+            height: 10_u32.into(),
+            round: 1_u16.into(),
+            // The part set headers of the canonical forms of the block IDs
+            // differ between Amino and Protobuf
+            block_id: Some(block::Id {
+                hash: Hash::Sha256([1; 32]),
+                part_set_header: block::parts::Header::new(1, Hash::Sha256([2; 32])).unwrap(),
+            }),
+            timestamp: Some(crate::Time::unix_epoch()),
+            validator_address: account::Id::from(pub_key),
+            validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+            signature: Signature::default(),
+        };
+
+        for &(signed, other) in &[
+            (CompatMode::V0_33, CompatMode::V0_34),
+            (CompatMode::V0_34, CompatMode::V0_33),
+        ] {
+            let sign_bytes = vote
+                .to_signable_vec_with_compat(chain_id.clone(), signed)
+                .unwrap();
+            vote.signature = keypair.sign(&sign_bytes).into();
+
+            assert!(vote
+                .verify_signature_with_compat(chain_id.clone(), &pub_key, signed)
+                .is_ok());
+            assert!(vote
+                .verify_signature_with_compat(chain_id.clone(), &pub_key, other)
+                .is_err());
+        }
+    }
+}
@@ -3,25 +3,32 @@
 //! This module contains types which correspond to the following config files:
 //!
 //! - `config.toml`: `config::TendermintConfig`
-//! - `node_key.rs`: `config::node_key::NodeKey`
-//! - `priv_validator_key.rs`: `config::priv_validator_key::PrivValidatorKey`
+//! - `node_key.json`: `config::NodeKey`, ie. `node::Key`
+//! - `priv_validator_key.json`: `config::PrivValidatorKey`
+//! - `priv_validator_state.json`: `config::PrivValidatorState`
 
-mod node_key;
 mod priv_validator_key;
+mod priv_validator_state;
 
-pub use self::{node_key::NodeKey, priv_validator_key::PrivValidatorKey};
+pub use self::{priv_validator_key::PrivValidatorKey, priv_validator_state::PrivValidatorState};
+pub use crate::node::Key as NodeKey;
 
-use crate::{error::Error, genesis::Genesis, net, node, Moniker, Timeout};
+use crate::{error::Error, genesis::Genesis, net, node, serializers, Moniker, Timeout};
 use serde::{de, de::Error as _, ser, Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     fmt, fs,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
-/// Tendermint `config.toml` file
+/// Tendermint `config.toml` file.
+///
+/// Missing settings and sections take the default values of Tendermint Core,
+/// so that files written by older versions can still be parsed.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct TendermintConfig {
     /// TCP or UNIX socket address of the ABCI application,
     /// or the name of an ABCI application compiled in with the Tendermint binary.
@@ -59,10 +66,7 @@ pub struct TendermintConfig {
 
     /// TCP or UNIX socket address for Tendermint to listen on for
     /// connections from an external PrivValidator process
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub priv_validator_laddr: Option<net::Address>,
 
     /// Path to the JSON file containing the private key to use for node authentication in the p2p
@@ -99,6 +103,39 @@ pub struct TendermintConfig {
 
     /// fastsync configuration options
     pub fastsync: FastsyncConfig,
+
+    /// storage configuration options
+    pub storage: StorageConfig,
+}
+
+impl Default for TendermintConfig {
+    fn default() -> Self {
+        Self {
+            proxy_app: parse_default("tcp://127.0.0.1:26658"),
+            moniker: parse_default("anonymous"),
+            fast_sync: true,
+            db_backend: DbBackend::GoLevelDb,
+            db_dir: PathBuf::from("data"),
+            log_level: parse_default("info"),
+            log_format: LogFormat::Plain,
+            genesis_file: PathBuf::from("config/genesis.json"),
+            priv_validator_key_file: Some(PathBuf::from("config/priv_validator_key.json")),
+            priv_validator_state_file: PathBuf::from("data/priv_validator_state.json"),
+            priv_validator_laddr: None,
+            node_key_file: PathBuf::from("config/node_key.json"),
+            abci: AbciMode::Socket,
+            filter_peers: false,
+            rpc: RpcConfig::default(),
+            p2p: P2PConfig::default(),
+            mempool: MempoolConfig::default(),
+            consensus: ConsensusConfig::default(),
+            tx_index: TxIndexConfig::default(),
+            instrumentation: InstrumentationConfig::default(),
+            statesync: StatesyncConfig::default(),
+            fastsync: FastsyncConfig::default(),
+            storage: StorageConfig::default(),
+        }
+    }
 }
 
 impl TendermintConfig {
@@ -120,6 +157,16 @@ impl TendermintConfig {
         Self::parse_toml(toml_string)
     }
 
+    /// Check the consistency of the configuration, as Tendermint Core does
+    /// before starting a node
+    pub fn validate(&self) -> Result<(), Error> {
+        self.rpc.validate()?;
+        self.mempool.validate()?;
+        self.tx_index.validate()?;
+        self.statesync.validate()?;
+        self.fastsync.validate()
+    }
+
     /// Load `genesis.json` file from the configured location
     pub fn load_genesis_file(&self, home: impl AsRef<Path>) -> Result<Genesis, Error> {
         let path = home.as_ref().join(&self.genesis_file);
@@ -138,6 +185,46 @@ impl TendermintConfig {
     }
 }
 
+/// Atomically write the given contents to a file which, on Unix platforms, is
+/// only readable and writable by its owner, as Tendermint does for key files.
+///
+/// As done by `WriteFileAtomic` in Go, the contents are written to a temporary
+/// file in the same directory and synced to disk before this file is renamed
+/// to the given path, so that a crash leaves either the old or the new file.
+pub(crate) fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    use rand_core::{OsRng, RngCore};
+    use std::io::{self, Write};
+
+    let file_io = |e| Error::file_io(format!("{}", path.display()), e);
+
+    let mut temp_name = path
+        .file_name()
+        .ok_or_else(|| file_io(io::ErrorKind::InvalidInput.into()))?
+        .to_os_string();
+    temp_name.push(format!(".{}.tmp", OsRng.next_u64()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let result = options
+        .open(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.map_err(file_io)
+}
+
 /// Database backend
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum DbBackend {
@@ -178,7 +265,7 @@ impl LogLevel {
     {
         self.components
             .get(key.as_ref())
-            .or_else(|| self.global.as_ref())
+            .or(self.global.as_ref())
             .map(AsRef::as_ref)
     }
 
@@ -282,6 +369,7 @@ pub enum AbciMode {
 
 /// Tendermint `config.toml` file's `[rpc]` section
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct RpcConfig {
     /// TCP or UNIX socket address for the RPC server to listen on
     pub laddr: net::Address,
@@ -299,10 +387,7 @@ pub struct RpcConfig {
 
     /// TCP or UNIX socket address for the gRPC server to listen on
     /// NOTE: This server only supports `/broadcast_tx_commit`
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub grpc_laddr: Option<net::Address>,
 
     /// Maximum number of simultaneous GRPC connections.
@@ -323,6 +408,18 @@ pub struct RpcConfig {
     /// Maximum number of unique queries a given client can `/subscribe` to.
     pub max_subscriptions_per_client: u64,
 
+    /// Maximum number of events that can be buffered per subscription before
+    /// the subscriber is considered too slow, at least 100.
+    pub experimental_subscription_buffer_size: u64,
+
+    /// Maximum number of messages that can be buffered per WebSocket client,
+    /// at least `experimental_subscription_buffer_size`.
+    pub experimental_websocket_write_buffer_size: u64,
+
+    /// If `true`, close the WebSocket connections of clients which can't keep
+    /// up with their subscriptions instead of dropping the subscriptions.
+    pub experimental_close_on_slow_client: bool,
+
     /// How long to wait for a tx to be committed during `/broadcast_tx_commit`.
     pub timeout_broadcast_tx_commit: Timeout,
 
@@ -333,27 +430,85 @@ pub struct RpcConfig {
     pub max_header_bytes: u64,
 
     /// The name of a file containing certificate that is used to create the HTTPS server.
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub tls_cert_file: Option<PathBuf>,
 
     /// The name of a file containing matching private key that is used to create the HTTPS server.
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub tls_key_file: Option<PathBuf>,
 
     /// pprof listen address <https://golang.org/pkg/net/http/pprof>
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub pprof_laddr: Option<net::Address>,
 }
 
+impl RpcConfig {
+    /// Minimum size of the subscription buffers
+    pub const MIN_SUBSCRIPTION_BUFFER_SIZE: u64 = 100;
+
+    /// Check the consistency of the `[rpc]` section
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.experimental_subscription_buffer_size < Self::MIN_SUBSCRIPTION_BUFFER_SIZE {
+            return Err(Error::invalid_config(format!(
+                "rpc.experimental_subscription_buffer_size must be at least {}, found {}",
+                Self::MIN_SUBSCRIPTION_BUFFER_SIZE,
+                self.experimental_subscription_buffer_size
+            )));
+        }
+
+        if self.experimental_websocket_write_buffer_size
+            < self.experimental_subscription_buffer_size
+        {
+            return Err(Error::invalid_config(format!(
+                "rpc.experimental_websocket_write_buffer_size must be at least \
+                 rpc.experimental_subscription_buffer_size ({}), found {}",
+                self.experimental_subscription_buffer_size,
+                self.experimental_websocket_write_buffer_size
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            laddr: parse_default("tcp://127.0.0.1:26657"),
+            cors_allowed_origins: vec![],
+            cors_allowed_methods: ["HEAD", "GET", "POST"]
+                .iter()
+                .map(|method| CorsMethod(method.to_string()))
+                .collect(),
+            cors_allowed_headers: [
+                "Origin",
+                "Accept",
+                "Content-Type",
+                "X-Requested-With",
+                "X-Server-Time",
+            ]
+            .iter()
+            .map(|header| CorsHeader(header.to_string()))
+            .collect(),
+            grpc_laddr: None,
+            grpc_max_open_connections: 900,
+            unsafe_commands: false,
+            max_open_connections: 900,
+            max_subscription_clients: 100,
+            max_subscriptions_per_client: 5,
+            experimental_subscription_buffer_size: 200,
+            experimental_websocket_write_buffer_size: 200,
+            experimental_close_on_slow_client: false,
+            timeout_broadcast_tx_commit: Duration::from_secs(10).into(),
+            max_body_bytes: 1_000_000,
+            max_header_bytes: 1 << 20,
+            tls_cert_file: None,
+            tls_key_file: None,
+            pprof_laddr: None,
+        }
+    }
+}
+
 /// Origin hosts allowed with CORS requests to the RPC API
 // TODO(tarcieri): parse and validate this string
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
@@ -407,6 +562,7 @@ impl fmt::Display for CorsHeader {
 
 /// peer to peer configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct P2PConfig {
     /// Address to listen for incoming connections
     pub laddr: net::Address,
@@ -415,24 +571,15 @@ pub struct P2PConfig {
     /// If empty, will use the same port as the laddr,
     /// and will introspect on the listener or use UPnP
     /// to figure out the address.
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub external_address: Option<net::Address>,
 
     /// Comma separated list of seed nodes to connect to
-    #[serde(
-        serialize_with = "serialize_comma_separated_list",
-        deserialize_with = "deserialize_comma_separated_list"
-    )]
+    #[serde(with = "serializers::comma_separated")]
     pub seeds: Vec<net::Address>,
 
     /// Comma separated list of nodes to keep persistent connections to
-    #[serde(
-        serialize_with = "serialize_comma_separated_list",
-        deserialize_with = "deserialize_comma_separated_list"
-    )]
+    #[serde(with = "serializers::comma_separated")]
     pub persistent_peers: Vec<net::Address>,
 
     /// UPNP port forwarding
@@ -453,10 +600,7 @@ pub struct P2PConfig {
 
     /// List of node IDs, to which a connection will be (re)established ignoring any existing
     /// limits
-    #[serde(
-        serialize_with = "serialize_comma_separated_list",
-        deserialize_with = "deserialize_comma_separated_list"
-    )]
+    #[serde(with = "serializers::comma_separated")]
     pub unconditional_peer_ids: Vec<node::Id>,
 
     /// Maximum pause when redialing a persistent peer (if zero, exponential backoff is used)
@@ -484,10 +628,7 @@ pub struct P2PConfig {
     pub seed_mode: bool,
 
     /// Comma separated list of peer IDs to keep private (will not be gossiped to other peers)
-    #[serde(
-        serialize_with = "serialize_comma_separated_list",
-        deserialize_with = "deserialize_comma_separated_list"
-    )]
+    #[serde(with = "serializers::comma_separated")]
     pub private_peer_ids: Vec<node::Id>,
 
     /// Toggle to disable guard against peers connecting from the same ip.
@@ -500,9 +641,43 @@ pub struct P2PConfig {
     pub dial_timeout: Timeout,
 }
 
+impl Default for P2PConfig {
+    fn default() -> Self {
+        Self {
+            laddr: parse_default("tcp://0.0.0.0:26656"),
+            external_address: None,
+            seeds: vec![],
+            persistent_peers: vec![],
+            upnp: false,
+            addr_book_file: PathBuf::from("config/addrbook.json"),
+            addr_book_strict: true,
+            max_num_inbound_peers: 40,
+            max_num_outbound_peers: 10,
+            unconditional_peer_ids: vec![],
+            persistent_peers_max_dial_period: Duration::from_secs(0).into(),
+            flush_throttle_timeout: Duration::from_millis(100).into(),
+            max_packet_msg_payload_size: 1024,
+            send_rate: TransferRate(5_120_000),
+            recv_rate: TransferRate(5_120_000),
+            pex: true,
+            seed_mode: false,
+            private_peer_ids: vec![],
+            allow_duplicate_ip: false,
+            handshake_timeout: Duration::from_secs(20).into(),
+            dial_timeout: Duration::from_secs(3).into(),
+        }
+    }
+}
+
 /// mempool configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct MempoolConfig {
+    /// Mempool version to use:
+    ///   1) "v0" (default) - FIFO mempool
+    ///   2) "v1" - prioritized mempool
+    pub version: String,
+
     /// Recheck enabled
     pub recheck: bool,
 
@@ -510,10 +685,7 @@ pub struct MempoolConfig {
     pub broadcast: bool,
 
     /// WAL dir
-    #[serde(
-        deserialize_with = "deserialize_optional_value",
-        serialize_with = "serialize_optional_value"
-    )]
+    #[serde(with = "serializers::empty_string_option")]
     pub wal_dir: Option<PathBuf>,
 
     /// Maximum number of transactions in the mempool
@@ -541,10 +713,53 @@ pub struct MempoolConfig {
     /// Including space needed by encoding (one varint per transaction).
     /// XXX: Unused due to <https://github.com/tendermint/tendermint/issues/5796>
     pub max_batch_bytes: u64,
+
+    /// Maximum time a transaction can remain in the mempool, if non-zero.
+    /// Only used by the "v1" mempool.
+    #[serde(rename = "ttl-duration")]
+    pub ttl_duration: Timeout,
+
+    /// Maximum number of blocks a transaction can remain in the mempool, if
+    /// non-zero. Only used by the "v1" mempool.
+    #[serde(rename = "ttl-num-blocks")]
+    pub ttl_num_blocks: u64,
+}
+
+impl MempoolConfig {
+    /// Check the consistency of the `[mempool]` section
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.version.as_str() {
+            "v0" | "v1" => Ok(()),
+            version => Err(Error::invalid_config(format!(
+                "unknown mempool.version: {:?}",
+                version
+            ))),
+        }
+    }
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            version: "v0".to_string(),
+            recheck: true,
+            broadcast: true,
+            wal_dir: None,
+            size: 5000,
+            max_txs_bytes: 1024 * 1024 * 1024,
+            cache_size: 10000,
+            keep_invalid_txs_in_cache: false,
+            max_tx_bytes: 1024 * 1024,
+            max_batch_bytes: 0,
+            ttl_duration: Duration::from_secs(0).into(),
+            ttl_num_blocks: 0,
+        }
+    }
 }
 
 /// consensus configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct ConsensusConfig {
     /// Path to WAL file
     pub wal_file: PathBuf,
@@ -593,16 +808,55 @@ pub struct ConsensusConfig {
     pub peer_query_maj23_sleep_duration: Timeout,
 }
 
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            wal_file: PathBuf::from("data/cs.wal/wal"),
+            timeout_propose: Duration::from_secs(3).into(),
+            timeout_propose_delta: Duration::from_millis(500).into(),
+            timeout_prevote: Duration::from_secs(1).into(),
+            timeout_prevote_delta: Duration::from_millis(500).into(),
+            timeout_precommit: Duration::from_secs(1).into(),
+            timeout_precommit_delta: Duration::from_millis(500).into(),
+            timeout_commit: Duration::from_secs(1).into(),
+            double_sign_check_height: 0,
+            skip_timeout_commit: false,
+            create_empty_blocks: true,
+            create_empty_blocks_interval: Duration::from_secs(0).into(),
+            peer_gossip_sleep_duration: Duration::from_millis(100).into(),
+            peer_query_maj23_sleep_duration: Duration::from_secs(2).into(),
+        }
+    }
+}
+
 /// transactions indexer configuration options
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct TxIndexConfig {
     /// What indexer to use for transactions
-    #[serde(default)]
     pub indexer: TxIndexer,
+
+    /// PostgreSQL connection string, required by the "psql" indexer, eg.
+    /// `postgresql://<user>:<password>@<host>:<port>/<db>?<opts>`
+    #[serde(rename = "psql-conn", with = "serializers::empty_string_option")]
+    pub psql_conn: Option<String>,
+}
+
+impl TxIndexConfig {
+    /// Check the consistency of the `[tx_index]` section
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.indexer == TxIndexer::Psql && self.psql_conn.is_none() {
+            return Err(Error::invalid_config(
+                "tx_index.psql-conn is required by the psql indexer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// What indexer to use for transactions
-#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, Default)]
 pub enum TxIndexer {
     /// "null"
     // TODO(tarcieri): use an `Option` type here?
@@ -612,17 +866,17 @@ pub enum TxIndexer {
     /// "kv" (default) - the simplest possible indexer, backed by key-value storage (defaults to
     /// levelDB; see DBBackend).
     #[serde(rename = "kv")]
+    #[default]
     Kv,
-}
 
-impl Default for TxIndexer {
-    fn default() -> TxIndexer {
-        TxIndexer::Kv
-    }
+    /// "psql" - the indexer services backed by PostgreSQL, see `psql_conn`.
+    #[serde(rename = "psql")]
+    Psql,
 }
 
 /// instrumentation configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct InstrumentationConfig {
     /// When `true`, Prometheus metrics are served under /metrics on
     /// PrometheusListenAddr.
@@ -639,8 +893,20 @@ pub struct InstrumentationConfig {
     pub namespace: String,
 }
 
+impl Default for InstrumentationConfig {
+    fn default() -> Self {
+        Self {
+            prometheus: false,
+            prometheus_listen_addr: ":26660".to_string(),
+            max_open_connections: 3,
+            namespace: "tendermint".to_string(),
+        }
+    }
+}
+
 /// statesync configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct StatesyncConfig {
     /// State sync rapidly bootstraps a new node by discovering, fetching, and restoring a state
     /// machine snapshot from peers instead of fetching and replaying historical blocks.
@@ -656,10 +922,7 @@ pub struct StatesyncConfig {
     ///
     /// For Cosmos SDK-based chains, trust-period should usually be about 2/3 of the unbonding time
     /// (~2 weeks) during which they can be financially punished (slashed) for misbehavior.
-    #[serde(
-        serialize_with = "serialize_comma_separated_list",
-        deserialize_with = "deserialize_comma_separated_list"
-    )]
+    #[serde(with = "serializers::comma_separated")]
     pub rpc_servers: Vec<String>,
 
     /// Trust height. See `rpc_servers` above.
@@ -677,88 +940,167 @@ pub struct StatesyncConfig {
     /// Temporary directory for state sync snapshot chunks, defaults to the OS tempdir (typically
     /// /tmp). Will create a new, randomly named directory within, and remove it when done.
     pub temp_dir: String,
+
+    /// The timeout duration before re-requesting a chunk, possibly from a
+    /// different peer, at least 5 seconds.
+    pub chunk_request_timeout: Timeout,
+
+    /// The number of concurrent chunk fetchers to run.
+    #[serde(with = "serializers::string_or_int")]
+    pub chunk_fetchers: u64,
+}
+
+impl StatesyncConfig {
+    /// Minimum snapshot discovery time, unless zero
+    pub const MIN_DISCOVERY_TIME: Duration = Duration::from_secs(5);
+
+    /// Minimum chunk request timeout
+    pub const MIN_CHUNK_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Check the consistency of the `[statesync]` section, if state sync is
+    /// enabled
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.enable {
+            return Ok(());
+        }
+
+        if self.rpc_servers.len() < 2 {
+            return Err(Error::invalid_config(format!(
+                "statesync.rpc_servers requires at least two servers, found {}",
+                self.rpc_servers.len()
+            )));
+        }
+
+        if self
+            .rpc_servers
+            .iter()
+            .any(|server| server.trim().is_empty())
+        {
+            return Err(Error::invalid_config(
+                "statesync.rpc_servers contains an empty server address".to_string(),
+            ));
+        }
+
+        if *self.discovery_time != Duration::from_secs(0)
+            && *self.discovery_time < Self::MIN_DISCOVERY_TIME
+        {
+            return Err(Error::invalid_config(format!(
+                "statesync.discovery_time must be 0 or at least {:?}, found {:?}",
+                Self::MIN_DISCOVERY_TIME,
+                *self.discovery_time
+            )));
+        }
+
+        if self.trust_height == 0 {
+            return Err(Error::invalid_config(
+                "statesync.trust_height is required".to_string(),
+            ));
+        }
+
+        if self.trust_hash.is_empty() {
+            return Err(Error::invalid_config(
+                "statesync.trust_hash is required".to_string(),
+            ));
+        }
+
+        if self.trust_period.is_empty() {
+            return Err(Error::invalid_config(
+                "statesync.trust_period is required".to_string(),
+            ));
+        }
+
+        if *self.chunk_request_timeout < Self::MIN_CHUNK_REQUEST_TIMEOUT {
+            return Err(Error::invalid_config(format!(
+                "statesync.chunk_request_timeout must be at least {:?}, found {:?}",
+                Self::MIN_CHUNK_REQUEST_TIMEOUT,
+                *self.chunk_request_timeout
+            )));
+        }
+
+        if self.chunk_fetchers == 0 {
+            return Err(Error::invalid_config(
+                "statesync.chunk_fetchers must be positive".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for StatesyncConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            rpc_servers: vec![],
+            trust_height: 0,
+            trust_hash: String::new(),
+            trust_period: "168h0m0s".to_string(),
+            discovery_time: Duration::from_secs(15).into(),
+            temp_dir: String::new(),
+            chunk_request_timeout: Duration::from_secs(10).into(),
+            chunk_fetchers: 4,
+        }
+    }
 }
 
 /// fastsync configuration options
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct FastsyncConfig {
     /// Fast Sync version to use:
     ///   1) "v0" (default) - the legacy fast sync implementation
-    ///   2) "v2" - complete redesign of v0, optimized for testability & readability
+    ///   2) "v1" - refactor of v0 version for better testability
+    ///   3) "v2" - complete redesign of v0, optimized for testability & readability
     pub version: String,
 }
 
-/// Rate at which bytes can be sent/received
-#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
-pub struct TransferRate(u64);
-
-impl TransferRate {
-    /// Get the trasfer rate in bytes per second
-    pub fn bytes_per_sec(self) -> u64 {
-        self.0
+impl FastsyncConfig {
+    /// Check the consistency of the `[fastsync]` section
+    pub fn validate(&self) -> Result<(), Error> {
+        match self.version.as_str() {
+            "v0" | "v1" | "v2" => Ok(()),
+            version => Err(Error::invalid_config(format!(
+                "unknown fastsync.version: {:?}",
+                version
+            ))),
+        }
     }
 }
 
-/// Deserialize `Option<T: FromStr>` where an empty string indicates `None`
-fn deserialize_optional_value<'de, D, T, E>(deserializer: D) -> Result<Option<T>, D::Error>
-where
-    D: de::Deserializer<'de>,
-    T: FromStr<Err = E>,
-    E: fmt::Display,
-{
-    let string = Option::<String>::deserialize(deserializer).map(|str| str.unwrap_or_default())?;
-
-    if string.is_empty() {
-        return Ok(None);
+impl Default for FastsyncConfig {
+    fn default() -> Self {
+        Self {
+            version: "v0".to_string(),
+        }
     }
-
-    string
-        .parse()
-        .map(Some)
-        .map_err(|e| D::Error::custom(format!("{}", e)))
 }
 
-fn serialize_optional_value<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: ser::Serializer,
-    T: Serialize,
-{
-    match value {
-        Some(value) => value.serialize(serializer),
-        None => "".serialize(serializer),
-    }
+/// storage configuration options
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Set to `true` to discard ABCI responses from the state store, which can
+    /// save a considerable amount of disk space. Note that the
+    /// `/block_results` RPC endpoint is unavailable for pruned heights.
+    pub discard_abci_responses: bool,
 }
 
-/// Deserialize a comma separated list of types that impl `FromStr` as a `Vec`
-fn deserialize_comma_separated_list<'de, D, T, E>(deserializer: D) -> Result<Vec<T>, D::Error>
-where
-    D: de::Deserializer<'de>,
-    T: FromStr<Err = E>,
-    E: fmt::Display,
-{
-    let mut result = vec![];
-    let string = String::deserialize(deserializer)?;
-
-    if string.is_empty() {
-        return Ok(result);
-    }
+/// Rate at which bytes can be sent/received
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TransferRate(u64);
 
-    for item in string.split(',') {
-        result.push(
-            item.parse()
-                .map_err(|e| D::Error::custom(format!("{}", e)))?,
-        );
+impl TransferRate {
+    /// Get the trasfer rate in bytes per second
+    pub fn bytes_per_sec(self) -> u64 {
+        self.0
     }
-
-    Ok(result)
 }
 
-/// Serialize a comma separated list types that impl `ToString`
-fn serialize_comma_separated_list<S, T>(list: &[T], serializer: S) -> Result<S::Ok, S::Error>
+/// Parse a default value which is known to be valid
+fn parse_default<T>(value: &str) -> T
 where
-    S: ser::Serializer,
-    T: ToString,
+    T: FromStr,
+    T::Err: fmt::Debug,
 {
-    let str_list = list.iter().map(|addr| addr.to_string()).collect::<Vec<_>>();
-    str_list.join(",").serialize(serializer)
+    value.parse().expect("invalid default configuration value")
 }
@@ -1,12 +1,14 @@
 //! Validator private keys
 
 use crate::public_key::TendermintKey;
-use crate::{account, error::Error, private_key::PrivateKey, public_key::PublicKey};
+use crate::{account, config, error::Error, private_key::PrivateKey, public_key::PublicKey};
+use rand_core::OsRng;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
+use zeroize::Zeroizing;
 
 /// Validator private key
-#[derive(Serialize, Deserialize)] // JSON custom serialization for priv_validator_key.json
+#[derive(Debug, Serialize, Deserialize)] // JSON custom serialization for priv_validator_key.json
 pub struct PrivValidatorKey {
     /// Address
     pub address: account::Id,
@@ -19,6 +21,18 @@ pub struct PrivValidatorKey {
 }
 
 impl PrivValidatorKey {
+    /// Generate a new random Ed25519 validator key
+    pub fn generate() -> Self {
+        let priv_key = PrivateKey::Ed25519(ed25519_dalek::Keypair::generate(&mut OsRng));
+        let pub_key = priv_key.public_key();
+
+        Self {
+            address: account::Id::from(pub_key),
+            pub_key,
+            priv_key,
+        }
+    }
+
     /// Parse `priv_validator_key.json`
     pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
         let result =
@@ -30,15 +44,33 @@ impl PrivValidatorKey {
         Ok(result)
     }
 
-    /// Load `node_key.json` from a file
+    /// Load `priv_validator_key.json` from a file
     pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
     where
         P: AsRef<Path>,
     {
-        let json_string = fs::read_to_string(path)
-            .map_err(|e| Error::file_io(format!("{}", path.as_ref().display()), e))?;
+        let json_string = Zeroizing::new(
+            fs::read_to_string(path)
+                .map_err(|e| Error::file_io(format!("{}", path.as_ref().display()), e))?,
+        );
+
+        Self::parse_json(&*json_string)
+    }
 
-        Self::parse_json(json_string)
+    /// Serialize this key in the `priv_validator_key.json` format
+    pub fn to_json(&self) -> Result<Zeroizing<String>, Error> {
+        serde_json::to_string_pretty(self)
+            .map(Zeroizing::new)
+            .map_err(Error::serde_json)
+    }
+
+    /// Atomically save this key as `priv_validator_key.json` to a file,
+    /// readable by its owner only on Unix platforms, as Tendermint does
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        config::write_private_file(path.as_ref(), self.to_json()?.as_bytes())
     }
 
     /// Get the consensus public key for this validator private key
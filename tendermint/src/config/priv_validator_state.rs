@@ -0,0 +1,212 @@
+//! Validator signing state
+
+use crate::{block, config, error::Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::{fs, path::Path};
+use tendermint_proto::serializers::bytes::{base64string, hexstring};
+
+/// Last height, round and step signed by a validator, along with the
+/// signature and the sign bytes of the last signed message, as found in
+/// `priv_validator_state.json`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PrivValidatorState {
+    /// Height of the last signed message
+    pub height: block::Height,
+
+    /// Round of the last signed message
+    #[serde(with = "round_as_number")]
+    pub round: block::Round,
+
+    /// Step of the last signed message: 1 for proposals, 2 for prevotes
+    /// and 3 for precommits
+    pub step: i8,
+
+    /// Signature of the last signed message
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "base64string")]
+    pub signature: Vec<u8>,
+
+    /// Sign bytes of the last signed message
+    #[serde(
+        default,
+        rename = "signbytes",
+        skip_serializing_if = "Vec::is_empty",
+        with = "hexstring"
+    )]
+    pub sign_bytes: Vec<u8>,
+}
+
+/// The state of a validator which has not signed anything yet
+impl Default for PrivValidatorState {
+    fn default() -> Self {
+        Self {
+            height: block::Height::from(0_u32),
+            round: block::Round::default(),
+            step: 0,
+            signature: Vec::new(),
+            sign_bytes: Vec::new(),
+        }
+    }
+}
+
+impl PrivValidatorState {
+    /// Parse `priv_validator_state.json`
+    pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
+        serde_json::from_str(json_string.as_ref()).map_err(Error::serde_json)
+    }
+
+    /// Load `priv_validator_state.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = fs::read_to_string(path)
+            .map_err(|e| Error::file_io(format!("{}", path.as_ref().display()), e))?;
+
+        Self::parse_json(json_string)
+    }
+
+    /// Serialize this state in the `priv_validator_state.json` format
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(Error::serde_json)
+    }
+
+    /// Atomically save this state as `priv_validator_state.json` to a file
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        config::write_private_file(path.as_ref(), self.to_json()?.as_bytes())
+    }
+
+    /// Check that signing a message at the given height, round and step
+    /// does not regress from the last signed message, as done by
+    /// `FilePVLastSignState.CheckHRS` in Go.
+    ///
+    /// Returns whether the height, round and step are the ones of the last
+    /// signed message, in which case the caller must make sure that the same
+    /// message is signed again.
+    pub fn check_hrs(
+        &self,
+        height: block::Height,
+        round: block::Round,
+        step: i8,
+    ) -> Result<bool, Error> {
+        let regression = |what: &str, got: String, last: String| {
+            Err(Error::double_sign(format!(
+                "{} regression: got {}, last {}",
+                what, got, last
+            )))
+        };
+
+        if self.height > height {
+            return regression("height", height.to_string(), self.height.to_string());
+        }
+        if self.height < height {
+            return Ok(false);
+        }
+
+        if self.round > round {
+            return regression("round", round.to_string(), self.round.to_string());
+        }
+        if self.round < round {
+            return Ok(false);
+        }
+
+        if self.step > step {
+            return regression("step", step.to_string(), self.step.to_string());
+        }
+        if self.step < step {
+            return Ok(false);
+        }
+
+        if self.sign_bytes.is_empty() {
+            return Err(Error::double_sign(
+                "no sign bytes found for the last signed message".to_string(),
+            ));
+        }
+
+        Ok(true)
+    }
+}
+
+/// Tendermint encodes the round of the state file as a JSON number.
+mod round_as_number {
+    use super::*;
+    use serde::de::Error as _;
+
+    pub fn serialize<S: Serializer>(
+        round: &block::Round,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        round.value().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<block::Round, D::Error> {
+        block::Round::try_from(i32::deserialize(deserializer)?)
+            .map_err(|e| D::Error::custom(format!("{}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_hrs() {
+        let state = PrivValidatorState {
+            height: block::Height::from(10_u32),
+            round: block::Round::from(2_u16),
+            step: 2,
+            signature: vec![1],
+            sign_bytes: vec![2],
+        };
+
+        let check = |height: u32, round: u16, step| {
+            state.check_hrs(block::Height::from(height), block::Round::from(round), step)
+        };
+
+        assert!(check(9, 3, 3).is_err());
+        assert!(check(10, 1, 3).is_err());
+        assert!(check(10, 2, 1).is_err());
+        assert!(check(10, 2, 2).unwrap());
+        assert!(!check(10, 2, 3).unwrap());
+        assert!(!check(10, 3, 1).unwrap());
+        assert!(!check(11, 0, 0).unwrap());
+
+        let unsigned = PrivValidatorState {
+            sign_bytes: vec![],
+            ..state
+        };
+        assert!(unsigned
+            .check_hrs(block::Height::from(10_u32), block::Round::from(2_u16), 2)
+            .is_err());
+    }
+
+    #[test]
+    fn save_json_file_replaces_the_state() {
+        let dir = std::env::temp_dir().join(format!(
+            "tendermint-priv-validator-state-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("priv_validator_state.json");
+
+        PrivValidatorState::default().save_json_file(&path).unwrap();
+        let state = PrivValidatorState {
+            height: block::Height::from(10_u32),
+            ..PrivValidatorState::default()
+        };
+        state.save_json_file(&path).unwrap();
+
+        let loaded = PrivValidatorState::load_json_file(&path);
+        let files = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.unwrap(), state);
+        // no temporary file is left behind
+        assert_eq!(files, 1);
+    }
+}
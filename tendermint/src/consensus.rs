@@ -1,6 +1,14 @@
 //! Tendermint consensus
 
 pub mod params;
+pub mod round_state;
 pub mod state;
+pub mod timestamp;
+pub mod wal;
 
-pub use self::{params::Params, state::State};
+pub use self::{
+    params::Params,
+    round_state::{BitArray, HeightRoundStep, PeerRoundState, RoundStep},
+    state::State,
+    timestamp::{SynchronyParams, TimestampRules},
+};
@@ -1,10 +1,14 @@
 //! Tendermint consensus parameters
 
 use crate::error::Error;
-use crate::{block, evidence, public_key};
+use crate::{block, evidence, public_key, Hash};
+use prost::Message;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::convert::{TryFrom, TryInto};
+use std::time::Duration;
 use tendermint_proto::abci::ConsensusParams as RawParams;
+use tendermint_proto::types::HashedParams as RawHashedParams;
 use tendermint_proto::types::ValidatorParams as RawValidatorParams;
 use tendermint_proto::types::VersionParams as RawVersionParams;
 use tendermint_proto::Protobuf;
@@ -26,6 +30,105 @@ pub struct Params {
     pub version: Option<VersionParams>,
 }
 
+impl Params {
+    /// Check that the parameters are within the bounds enforced by Tendermint,
+    /// as done by `ConsensusParams.ValidateBasic` in Go.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |detail: String| Err(Error::invalid_consensus_params(detail));
+
+        if self.block.max_bytes == 0 {
+            return invalid("block.max_bytes must be greater than 0".to_string());
+        }
+        if self.block.max_bytes > block::MAX_BLOCK_SIZE_BYTES {
+            return invalid(format!(
+                "block.max_bytes is too big: {} > {}",
+                self.block.max_bytes,
+                block::MAX_BLOCK_SIZE_BYTES
+            ));
+        }
+        if self.block.time_iota_ms <= 0 {
+            return invalid(format!(
+                "block.time_iota_ms must be greater than 0, got {}",
+                self.block.time_iota_ms
+            ));
+        }
+        if self.block.max_gas < -1 {
+            return invalid(format!(
+                "block.max_gas must be greater or equal to -1, got {}",
+                self.block.max_gas
+            ));
+        }
+
+        if self.evidence.max_age_num_blocks == 0 {
+            return invalid("evidence.max_age_num_blocks must be greater than 0".to_string());
+        }
+        if self.evidence.max_age_duration.0.as_nanos() == 0 {
+            return invalid("evidence.max_age_duration must be greater than 0".to_string());
+        }
+        if self.evidence.max_bytes < 0 {
+            return invalid(format!(
+                "evidence.max_bytes must be non-negative, got {}",
+                self.evidence.max_bytes
+            ));
+        }
+        if self.evidence.max_bytes as u64 > self.block.max_bytes {
+            return invalid(format!(
+                "evidence.max_bytes is greater than block.max_bytes: {} > {}",
+                self.evidence.max_bytes, self.block.max_bytes
+            ));
+        }
+
+        if self.validator.pub_key_types.is_empty() {
+            return invalid("validator.pub_key_types must not be empty".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Compute the hash of the subset of these parameters which is committed
+    /// by the `consensus_hash` of block headers, ie. the maximum size and gas
+    /// of blocks, as done by `HashConsensusParams` in Go.
+    pub fn hash(&self) -> Hash {
+        let hashed_params = RawHashedParams {
+            block_max_bytes: self.block.max_bytes as i64,
+            block_max_gas: self.block.max_gas,
+        };
+        let mut bytes = Vec::with_capacity(hashed_params.encoded_len());
+        hashed_params
+            .encode(&mut bytes)
+            .expect("vectors have enough capacity");
+        Hash::Sha256(Sha256::digest(&bytes).into())
+    }
+
+    /// Check that the given trusting period of a light client is shorter than
+    /// the maximum age of evidence, so that evidence of attacks against the
+    /// light client can still be committed when they are detected.
+    pub fn validate_trusting_period(&self, trusting_period: Duration) -> Result<(), Error> {
+        let max_age_duration = Duration::from(self.evidence.max_age_duration);
+        if trusting_period >= max_age_duration {
+            return Err(Error::invalid_consensus_params(format!(
+                "trusting period must be shorter than evidence.max_age_duration: {:?} >= {:?}",
+                trusting_period, max_age_duration
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Params {
+    /// The default consensus parameters of Tendermint, as
+    /// `DefaultConsensusParams` in Go
+    fn default() -> Self {
+        Self {
+            block: block::Size::default(),
+            evidence: evidence::Params::default(),
+            validator: ValidatorParams::default(),
+            version: Some(VersionParams::default()),
+        }
+    }
+}
+
 impl Protobuf<RawParams> for Params {}
 
 impl TryFrom<RawParams> for Params {
@@ -75,20 +178,32 @@ impl TryFrom<RawValidatorParams> for ValidatorParams {
 
     fn try_from(value: RawValidatorParams) -> Result<Self, Self::Error> {
         Ok(Self {
-            pub_key_types: value.pub_key_types.iter().map(|f| key_type(f)).collect(),
+            pub_key_types: value
+                .pub_key_types
+                .iter()
+                .map(|f| key_type(f))
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
-// Todo: How are these key types created?
-fn key_type(s: &str) -> public_key::Algorithm {
-    if s == "Ed25519" || s == "ed25519" {
-        return public_key::Algorithm::Ed25519;
+impl Default for ValidatorParams {
+    fn default() -> Self {
+        Self {
+            pub_key_types: vec![public_key::Algorithm::Ed25519],
+        }
     }
-    if s == "Secp256k1" || s == "secp256k1" {
-        return public_key::Algorithm::Secp256k1;
+}
+
+fn key_type(s: &str) -> Result<public_key::Algorithm, Error> {
+    match s {
+        "Ed25519" | "ed25519" => Ok(public_key::Algorithm::Ed25519),
+        "Secp256k1" | "secp256k1" => Ok(public_key::Algorithm::Secp256k1),
+        _ => Err(Error::invalid_consensus_params(format!(
+            "unsupported validator.pub_key_types entry: {}",
+            s
+        ))),
     }
-    public_key::Algorithm::Ed25519 // Todo: Shall we error out for invalid key types?
 }
 
 impl From<ValidatorParams> for RawValidatorParams {
@@ -132,3 +247,37 @@ impl From<VersionParams> for RawVersionParams {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_params_are_valid() {
+        let params = Params::default();
+        params.validate().unwrap();
+        assert_eq!(params.block.max_bytes, 22_020_096);
+        assert_eq!(params.evidence.max_age_num_blocks, 100_000);
+
+        // The hash of the default parameters in Go
+        assert_eq!(
+            params.hash().to_string(),
+            "048091BC7DDC283F77BFBF91D73C44DA58C3DF8A9CBC867405D8B7F3DAADA22F"
+        );
+
+        params
+            .validate_trusting_period(Duration::from_secs(24 * 3600))
+            .unwrap();
+        assert!(params
+            .validate_trusting_period(Duration::from_secs(48 * 3600))
+            .is_err());
+    }
+
+    #[test]
+    fn reject_unsupported_key_types() {
+        let raw = RawValidatorParams {
+            pub_key_types: vec!["ed25519".to_string(), "bls12381".to_string()],
+        };
+        assert!(ValidatorParams::try_from(raw).is_err());
+    }
+}
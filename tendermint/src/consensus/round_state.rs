@@ -0,0 +1,480 @@
+//! Consensus round states, as reported by the `/consensus_state` and
+//! `/dump_consensus_state` RPC endpoints
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.x/consensus/types/round_state.go>
+
+use crate::{
+    block::{self, Height, Round},
+    error::Error,
+    Time,
+};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display},
+    iter::FromIterator,
+    str::FromStr,
+};
+
+/// Steps of a consensus round
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum RoundStep {
+    /// Waiting for the commit timeout before starting a new height
+    NewHeight = 1,
+    /// Setting up a new round
+    NewRound = 2,
+    /// Proposing a block, or waiting for the proposal
+    Propose = 3,
+    /// Prevoting
+    Prevote = 4,
+    /// Waiting for more prevotes after +2/3 of any prevotes
+    PrevoteWait = 5,
+    /// Precommitting
+    Precommit = 6,
+    /// Waiting for more precommits after +2/3 of any precommits
+    PrecommitWait = 7,
+    /// Committing the block after +2/3 of precommits for it
+    Commit = 8,
+}
+
+impl TryFrom<u8> for RoundStep {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RoundStep::NewHeight),
+            2 => Ok(RoundStep::NewRound),
+            3 => Ok(RoundStep::Propose),
+            4 => Ok(RoundStep::Prevote),
+            5 => Ok(RoundStep::PrevoteWait),
+            6 => Ok(RoundStep::Precommit),
+            7 => Ok(RoundStep::PrecommitWait),
+            8 => Ok(RoundStep::Commit),
+            _ => Err(Error::parse(format!("invalid round step: {}", value))),
+        }
+    }
+}
+
+impl From<RoundStep> for u8 {
+    fn from(value: RoundStep) -> Self {
+        value as u8
+    }
+}
+
+impl Display for RoundStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RoundStep::NewHeight => "RoundStepNewHeight",
+            RoundStep::NewRound => "RoundStepNewRound",
+            RoundStep::Propose => "RoundStepPropose",
+            RoundStep::Prevote => "RoundStepPrevote",
+            RoundStep::PrevoteWait => "RoundStepPrevoteWait",
+            RoundStep::Precommit => "RoundStepPrecommit",
+            RoundStep::PrecommitWait => "RoundStepPrecommitWait",
+            RoundStep::Commit => "RoundStepCommit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Height, round and step of the consensus, encoded as `height/round/step`,
+/// eg. `1262197/0/8`
+///
+/// The round and step of peers are unknown until they report them, and are
+/// then encoded as `-1` and `0`, eg. `0/-1/0`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct HeightRoundStep {
+    /// Current block height
+    pub height: Height,
+    /// Current consensus round, if known
+    pub round: Option<Round>,
+    /// Current consensus step, if known
+    pub step: Option<RoundStep>,
+}
+
+impl Display for HeightRoundStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.height,
+            self.round.map_or(-1, i32::from),
+            self.step.map_or(0, u8::from)
+        )
+    }
+}
+
+impl FromStr for HeightRoundStep {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let hrs: Vec<&str> = s.split('/').collect();
+        if hrs.len() != 3 {
+            return Err(Error::parse(format!(
+                "expected 3 components to height/round/step, but got {}",
+                hrs.len()
+            )));
+        }
+        let round = i32::from_str(hrs[1]).map_err(|e| Error::parse_int(hrs[1].to_string(), e))?;
+        let step = u8::from_str(hrs[2]).map_err(|e| Error::parse_int(hrs[2].to_string(), e))?;
+        Ok(Self {
+            height: Height::from_str(hrs[0])?,
+            round: optional_round(round)?,
+            step: optional_step(step)?,
+        })
+    }
+}
+
+/// The round encoded by the given integer, `-1` encoding an unknown round
+fn optional_round(round: i32) -> Result<Option<Round>, Error> {
+    match round {
+        -1 => Ok(None),
+        round => Round::try_from(round).map(Some),
+    }
+}
+
+/// The step encoded by the given integer, `0` encoding an unknown step
+fn optional_step(step: u8) -> Result<Option<RoundStep>, Error> {
+    match step {
+        0 => Ok(None),
+        step => RoundStep::try_from(step).map(Some),
+    }
+}
+
+impl<'de> Deserialize<'de> for HeightRoundStep {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_str(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for HeightRoundStep {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+/// Bit arrays, eg. of the validators whose votes were received, encoded with
+/// an `x` for each set bit and an `_` for each unset one
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitArray {
+    bits: Vec<bool>,
+}
+
+impl BitArray {
+    /// Create a bit array of the given size, with all its bits unset
+    pub fn new(size: usize) -> Self {
+        Self {
+            bits: vec![false; size],
+        }
+    }
+
+    /// The number of bits of this bit array
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Whether this bit array has no bits
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// The bit at the given index, if it is in the bit array
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.bits.get(index).copied()
+    }
+
+    /// Set the bit at the given index, returning whether it is in the bit array
+    pub fn set(&mut self, index: usize, value: bool) -> bool {
+        match self.bits.get_mut(index) {
+            Some(bit) => {
+                *bit = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The number of set bits of this bit array
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().filter(|bit| **bit).count()
+    }
+
+    /// Iterate over the bits of this bit array
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.bits.iter().copied()
+    }
+}
+
+impl FromIterator<bool> for BitArray {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        Self {
+            bits: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Display for BitArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            write!(f, "{}", if bit { 'x' } else { '_' })?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BitArray {
+    type Err = Error;
+
+    /// Parse a bit array from its bits, eg. `x_x`, or from its debugging
+    /// format along with its size, eg. `BA{3:x_x}`, possibly followed by the
+    /// tally of the votes of the bit array as in the vote sets reported by
+    /// `/consensus_state`
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (size, bits) = match s.strip_prefix("BA{") {
+            Some(rest) => {
+                let end = rest
+                    .find('}')
+                    .ok_or_else(|| Error::parse(format!("unterminated bit array: {}", s)))?;
+                let (size, bits) = rest[..end]
+                    .split_once(':')
+                    .ok_or_else(|| Error::parse(format!("missing bit array size: {}", s)))?;
+                let size =
+                    usize::from_str(size).map_err(|e| Error::parse_int(size.to_string(), e))?;
+                (Some(size), bits)
+            }
+            None => (None, s),
+        };
+
+        let bit_array = bits
+            .chars()
+            .map(|c| match c {
+                'x' => Ok(true),
+                '_' => Ok(false),
+                _ => Err(Error::parse(format!("invalid bit array: {}", s))),
+            })
+            .collect::<Result<BitArray, Error>>()?;
+
+        match size {
+            Some(size) if size != bit_array.len() => Err(Error::parse(format!(
+                "bit array of size {} has {} bits",
+                size,
+                bit_array.len()
+            ))),
+            _ => Ok(bit_array),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BitArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_str(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for BitArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+/// The state of the current consensus round of a peer, as known by the node
+/// reporting it in `/dump_consensus_state`
+///
+/// Missing bit arrays, rounds and steps, eg. while no proof-of-lock round is
+/// known, or before the peer reported its round, are `None`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PeerRoundState {
+    /// Height of the peer
+    pub height: Height,
+
+    /// Round of the peer, if known
+    #[serde(with = "crate::serializers::optional_round")]
+    pub round: Option<Round>,
+
+    /// Step of the peer, if known
+    #[serde(with = "optional_step_int")]
+    pub step: Option<RoundStep>,
+
+    /// Estimated start of the round of the peer
+    pub start_time: Time,
+
+    /// Whether the peer has the proposal of the round
+    pub proposal: bool,
+
+    /// Part set header of the proposed block
+    pub proposal_block_part_set_header: block::parts::Header,
+
+    /// Parts of the proposed block the peer has
+    pub proposal_block_parts: Option<BitArray>,
+
+    /// Proof-of-lock round of the proposal
+    #[serde(with = "crate::serializers::optional_round")]
+    pub proposal_pol_round: Option<Round>,
+
+    /// Prevotes of the proof-of-lock round of the proposal the peer has
+    pub proposal_pol: Option<BitArray>,
+
+    /// Prevotes of the round the peer has
+    pub prevotes: Option<BitArray>,
+
+    /// Precommits of the round the peer has
+    pub precommits: Option<BitArray>,
+
+    /// Round of the last commit of the peer
+    #[serde(with = "crate::serializers::optional_round")]
+    pub last_commit_round: Option<Round>,
+
+    /// Precommits of the last commit the peer has
+    pub last_commit: Option<BitArray>,
+
+    /// Round of the commit the peer is catching up with
+    #[serde(with = "crate::serializers::optional_round")]
+    pub catchup_commit_round: Option<Round>,
+
+    /// Precommits of the commit the peer is catching up with
+    pub catchup_commit: Option<BitArray>,
+}
+
+impl PeerRoundState {
+    /// The height, round and step of the peer
+    pub fn height_round_step(&self) -> HeightRoundStep {
+        HeightRoundStep {
+            height: self.height,
+            round: self.round,
+            step: self.step,
+        }
+    }
+}
+
+/// Optional steps encoded as JSON integers, `0` encoding the absence of a
+/// step
+mod optional_step_int {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<RoundStep>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.map_or(0, u8::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<RoundStep>, D::Error> {
+        optional_step(u8::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn height_round_step() {
+        let hrs = HeightRoundStep::from_str("1262197/0/8").unwrap();
+        assert_eq!(hrs.height.value(), 1262197);
+        assert_eq!(hrs.round, Some(Round::from(0_u8)));
+        assert_eq!(hrs.step, Some(RoundStep::Commit));
+        assert_eq!(hrs.to_string(), "1262197/0/8");
+
+        // The height, round and step of a peer which did not report them yet
+        let hrs = HeightRoundStep::from_str("0/-1/0").unwrap();
+        assert_eq!(hrs.round, None);
+        assert_eq!(hrs.step, None);
+        assert_eq!(hrs.to_string(), "0/-1/0");
+
+        assert!(HeightRoundStep::from_str("1262197/0/9").is_err());
+        assert!(HeightRoundStep::from_str("1262197/-2/8").is_err());
+        assert!(HeightRoundStep::from_str("1262197/0").is_err());
+    }
+
+    #[test]
+    fn bit_array() {
+        let bit_array = BitArray::from_str("x_xx").unwrap();
+        assert_eq!(bit_array.len(), 4);
+        assert_eq!(bit_array.count_ones(), 3);
+        assert_eq!(bit_array.get(1), Some(false));
+        assert_eq!(bit_array.get(4), None);
+        assert_eq!(bit_array.to_string(), "x_xx");
+
+        assert_eq!(
+            BitArray::from_str("BA{4:x_xx} 169753436/170151262 = 1.00").unwrap(),
+            bit_array
+        );
+        assert!(BitArray::from_str("BA{5:x_xx}").is_err());
+        assert!(BitArray::from_str("x-xx").is_err());
+    }
+
+    #[test]
+    fn peer_round_state() {
+        let json = r#"{
+            "height": "7185",
+            "round": 0,
+            "step": 1,
+            "start_time": "2021-06-14T10:28:56.063172745Z",
+            "proposal": false,
+            "proposal_block_part_set_header": {
+                "total": 0,
+                "hash": ""
+            },
+            "proposal_block_parts": null,
+            "proposal_pol_round": -1,
+            "proposal_pol": "_",
+            "prevotes": "_",
+            "precommits": "_",
+            "last_commit_round": 0,
+            "last_commit": "x",
+            "catchup_commit_round": -1,
+            "catchup_commit": "_"
+        }"#;
+        let state: PeerRoundState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.height_round_step().to_string(), "7185/0/1");
+        assert_eq!(state.proposal_block_parts, None);
+        assert_eq!(state.proposal_pol_round, None);
+        assert_eq!(state.last_commit_round, Some(Round::from(0_u8)));
+        assert_eq!(state.last_commit.as_ref().unwrap().count_ones(), 1);
+
+        let reserialized: PeerRoundState =
+            serde_json::from_value(serde_json::to_value(&state).unwrap()).unwrap();
+        assert_eq!(reserialized, state);
+    }
+
+    /// The round state of a peer right after it was added, as reported by a
+    /// Go node: `NewPeerState` leaves the step unset and all rounds at -1.
+    #[test]
+    fn new_peer_round_state() {
+        let json = r#"{
+            "height": "0",
+            "round": -1,
+            "step": 0,
+            "start_time": "0001-01-01T00:00:00Z",
+            "proposal": false,
+            "proposal_block_part_set_header": {
+                "total": 0,
+                "hash": ""
+            },
+            "proposal_block_parts": null,
+            "proposal_pol_round": -1,
+            "proposal_pol": null,
+            "prevotes": null,
+            "precommits": null,
+            "last_commit_round": -1,
+            "last_commit": null,
+            "catchup_commit_round": -1,
+            "catchup_commit": null
+        }"#;
+        let state: PeerRoundState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.round, None);
+        assert_eq!(state.step, None);
+        assert_eq!(state.proposal_pol_round, None);
+        assert_eq!(state.last_commit_round, None);
+        assert_eq!(state.catchup_commit_round, None);
+        assert_eq!(state.height_round_step().to_string(), "0/-1/0");
+
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Rules for the timestamps of blocks: either BFT time, where the time of a
+//! block is the median of the times of the precommits for the previous block,
+//! or proposer-based timestamps (PBTS), where the time of a block is the time
+//! of its proposer, accepted by validators only when it is timely.
+//!
+//! <https://github.com/tendermint/spec/blob/master/spec/consensus/bft-time.md>
+//! <https://github.com/tendermint/spec/tree/master/spec/consensus/proposer-based-timestamp>
+
+use crate::{
+    block::{Commit, CommitSig, Round},
+    error::Error,
+    serializers, validator, Time,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Synchrony parameters of proposer-based timestamps, bounding the clock
+/// drift of validators and the delay of proposals
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SynchronyParams {
+    /// Bound of the difference between the clocks of correct validators
+    #[serde(with = "serializers::time_duration")]
+    pub precision: Duration,
+
+    /// Bound of the time it takes for proposals to reach correct validators,
+    /// in the first round of a height
+    #[serde(with = "serializers::time_duration")]
+    pub message_delay: Duration,
+}
+
+impl SynchronyParams {
+    /// Check that the parameters are positive, as done by
+    /// `SynchronyParams.ValidateBasic` in Go.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.precision == Duration::from_secs(0) {
+            return Err(Error::invalid_consensus_params(
+                "synchrony.precision must be greater than 0".to_string(),
+            ));
+        }
+        if self.message_delay == Duration::from_secs(0) {
+            return Err(Error::invalid_consensus_params(
+                "synchrony.message_delay must be greater than 0".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The parameters applying to the given round, in which the message
+    /// delay is increased by 10% for each round after the first one, so that
+    /// heights eventually make progress when the delay was underestimated.
+    pub fn in_round(&self, round: Round) -> Self {
+        let factor = 1.1_f64.powi(round.value() as i32);
+        let message_delay = self.message_delay.as_secs_f64() * factor;
+        Self {
+            precision: self.precision,
+            message_delay: if message_delay < u64::MAX as f64 {
+                Duration::from_secs_f64(message_delay)
+            } else {
+                Duration::from_secs(u64::MAX)
+            },
+        }
+    }
+
+    /// Whether a proposal with the given timestamp, received at the given
+    /// time in the given round, is timely: validators only prevote for
+    /// timely proposals.
+    pub fn is_timely(&self, timestamp: Time, received_at: Time, round: Round) -> bool {
+        let params = self.in_round(round);
+
+        let earliest = timestamp.checked_sub(params.precision);
+        let latest = timestamp
+            .checked_add(params.message_delay)
+            .and_then(|time| time.checked_add(params.precision));
+
+        earliest.is_none_or(|earliest| !received_at.before(earliest))
+            && latest.is_none_or(|latest| !received_at.after(latest))
+    }
+}
+
+impl Default for SynchronyParams {
+    /// The default synchrony parameters, as `DefaultSynchronyParams` in Go
+    fn default() -> Self {
+        Self {
+            precision: Duration::from_millis(505),
+            message_delay: Duration::from_secs(15),
+        }
+    }
+}
+
+/// Rules of the timestamps of the blocks of a chain
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TimestampRules {
+    /// BFT time: the time of a block is the median of the times of the
+    /// precommits included in its last commit, weighted by voting power
+    #[default]
+    BftTime,
+
+    /// Proposer-based timestamps: the time of a block is the time of its
+    /// proposal, which validators check to be timely with these parameters
+    ProposerBased(SynchronyParams),
+}
+
+impl TimestampRules {
+    /// Check the time of a block whose height is not the initial height of
+    /// the chain, given the time of the previous block, along with the last
+    /// commit of the block and the validators which signed it.
+    ///
+    /// The times of blocks at the initial height must instead be the time
+    /// of the genesis of the chain, see `Block::validate_time`.
+    pub fn validate_block_time(
+        &self,
+        block_time: Time,
+        last_block_time: Time,
+        last_commit: &Commit,
+        last_validators: &validator::Set,
+    ) -> Result<(), Error> {
+        if !block_time.after(last_block_time) {
+            return Err(Error::invalid_timestamp(format!(
+                "block time {} is not greater than the last block time {}",
+                block_time, last_block_time
+            )));
+        }
+
+        if let TimestampRules::BftTime = self {
+            let median = median_time(last_commit, last_validators).ok_or_else(|| {
+                Error::invalid_timestamp("the last commit has no signature".to_string())
+            })?;
+            if block_time != median {
+                return Err(Error::invalid_timestamp(format!(
+                    "block time {} is not the median time of the last commit {}",
+                    block_time, median
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The median of the times of the votes of the given commit, weighted by the
+/// voting power of their validators in the given set, as `MedianTime` in Go.
+///
+/// Returns `None` if no validator of the set voted.
+pub fn median_time(commit: &Commit, validators: &validator::Set) -> Option<Time> {
+    let mut weighted_times: Vec<(Time, u64)> = commit
+        .signatures
+        .iter()
+        .filter_map(|signature| match signature {
+            CommitSig::BlockIdFlagCommit {
+                validator_address,
+                timestamp,
+                ..
+            }
+            | CommitSig::BlockIdFlagNil {
+                validator_address,
+                timestamp,
+                ..
+            } => validators
+                .validator(*validator_address)
+                .map(|validator| (*timestamp, validator.power())),
+            CommitSig::BlockIdFlagAbsent => None,
+        })
+        .collect();
+    weighted_times.sort_by_key(|(time, _)| *time);
+
+    let total_power: u64 = weighted_times.iter().map(|(_, power)| power).sum();
+    let mut median = total_power / 2;
+    for (time, power) in weighted_times {
+        if median <= power {
+            return Some(time);
+        }
+        median -= power;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::seeded_validator;
+    use crate::{
+        block::{Height, Id},
+        Signature,
+    };
+
+    fn commit_sig(validator: &validator::Info, time: &str) -> CommitSig {
+        CommitSig::BlockIdFlagCommit {
+            validator_address: validator.address,
+            timestamp: Time::parse_from_rfc3339(time).unwrap(),
+            signature: Signature::default(),
+        }
+    }
+
+    fn commit(signatures: Vec<CommitSig>) -> Commit {
+        Commit {
+            height: Height::from(10_u32),
+            round: Round::from(0_u16),
+            block_id: Id::default(),
+            signatures,
+        }
+    }
+
+    #[test]
+    fn weighted_median_time() {
+        let validators = vec![
+            seeded_validator(1, 10),
+            seeded_validator(2, 30),
+            seeded_validator(3, 20),
+        ];
+        let set = validator::Set::without_proposer(validators.clone()).unwrap();
+
+        let commit = commit(vec![
+            commit_sig(&validators[0], "2021-01-01T00:00:01Z"),
+            commit_sig(&validators[1], "2021-01-01T00:00:03Z"),
+            commit_sig(&validators[2], "2021-01-01T00:00:02Z"),
+            CommitSig::BlockIdFlagAbsent,
+        ]);
+        // Half of the total power of 60 is reached by the vote at 00:00:02
+        let median = Time::parse_from_rfc3339("2021-01-01T00:00:02Z").unwrap();
+        assert_eq!(median_time(&commit, &set), Some(median));
+
+        let last_block_time = Time::parse_from_rfc3339("2021-01-01T00:00:00Z").unwrap();
+        let later = Time::parse_from_rfc3339("2021-01-01T00:00:05Z").unwrap();
+        TimestampRules::BftTime
+            .validate_block_time(median, last_block_time, &commit, &set)
+            .unwrap();
+        assert!(TimestampRules::BftTime
+            .validate_block_time(later, last_block_time, &commit, &set)
+            .is_err());
+
+        let pbts = TimestampRules::ProposerBased(SynchronyParams::default());
+        pbts.validate_block_time(later, last_block_time, &commit, &set)
+            .unwrap();
+        assert!(pbts
+            .validate_block_time(last_block_time, last_block_time, &commit, &set)
+            .is_err());
+    }
+
+    #[test]
+    fn timely_proposals() {
+        let params = SynchronyParams {
+            precision: Duration::from_millis(500),
+            message_delay: Duration::from_secs(2),
+        };
+        params.validate().unwrap();
+
+        let timestamp = Time::parse_from_rfc3339("2021-01-01T00:00:10Z").unwrap();
+        let at = |time: &str| Time::parse_from_rfc3339(time).unwrap();
+        let round = Round::from(0_u16);
+
+        assert!(params.is_timely(timestamp, at("2021-01-01T00:00:09.5Z"), round));
+        assert!(params.is_timely(timestamp, at("2021-01-01T00:00:12.5Z"), round));
+        assert!(!params.is_timely(timestamp, at("2021-01-01T00:00:09.4Z"), round));
+        assert!(!params.is_timely(timestamp, at("2021-01-01T00:00:12.6Z"), round));
+
+        // The message delay grows with the rounds
+        assert!(params.is_timely(timestamp, at("2021-01-01T00:00:12.6Z"), Round::from(1_u16)));
+
+        assert!(SynchronyParams {
+            precision: Duration::from_secs(0),
+            ..params
+        }
+        .validate()
+        .is_err());
+    }
+}
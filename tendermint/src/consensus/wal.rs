@@ -0,0 +1,436 @@
+//! Consensus write-ahead log (WAL), in which nodes record the messages and
+//! timeouts processed by the consensus, so as to replay them after a crash
+//!
+//! Each entry of the log is a `TimedWALMessage` encoded in Protobuf,
+//! prefixed with its CRC-32C checksum and its length, both big-endian
+//! 32-bit integers. The log of a node is split in several files, which
+//! must be read in order: `wal.000`, `wal.001`, ..., and finally `wal`.
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.x/consensus/wal.go>
+
+use crate::{
+    block::{Height, Round},
+    consensus::RoundStep,
+    error::Error,
+    Time,
+};
+use std::{
+    convert::{TryFrom, TryInto},
+    io::{self, Read, Write},
+    time::Duration,
+};
+use tendermint_proto::{
+    consensus::{
+        wal_message::Sum, EndHeight as RawEndHeight, MsgInfo,
+        TimedWalMessage as RawTimedWalMessage, TimeoutInfo as RawTimeoutInfo,
+        WalMessage as RawWalMessage,
+    },
+    google::protobuf::Duration as RawDuration,
+    types::EventDataRoundState as RawEventDataRoundState,
+    Protobuf,
+};
+
+/// Maximum size of the messages of the log, in bytes
+pub const MAX_MSG_SIZE_BYTES: usize = 1024 * 1024;
+
+/// Size of the header of the entries of the log: checksum and length
+const HEADER_SIZE: usize = 8;
+
+/// Message of the log, along with the time it was written at
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimedWalMessage {
+    /// Time the message was written at
+    pub time: Time,
+    /// The message
+    pub msg: WalMessage,
+}
+
+impl Protobuf<RawTimedWalMessage> for TimedWalMessage {}
+
+impl TryFrom<RawTimedWalMessage> for TimedWalMessage {
+    type Error = Error;
+
+    fn try_from(value: RawTimedWalMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            time: value.time.ok_or_else(Error::missing_timestamp)?.into(),
+            msg: value
+                .msg
+                .ok_or_else(Error::invalid_message_type)?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<TimedWalMessage> for RawTimedWalMessage {
+    fn from(value: TimedWalMessage) -> Self {
+        Self {
+            time: Some(value.time.into()),
+            msg: Some(value.msg.into()),
+        }
+    }
+}
+
+/// Messages of the log
+#[derive(Clone, Debug, PartialEq)]
+pub enum WalMessage {
+    /// New step of the consensus
+    RoundState(RoundStateEvent),
+    /// Consensus message, received from a peer or created by the node, in
+    /// its Protobuf form
+    MsgInfo(MsgInfo),
+    /// Expired timeout
+    Timeout(TimeoutInfo),
+    /// End of a height, written once its block is committed
+    EndHeight(Height),
+}
+
+impl TryFrom<RawWalMessage> for WalMessage {
+    type Error = Error;
+
+    fn try_from(value: RawWalMessage) -> Result<Self, Self::Error> {
+        match value.sum.ok_or_else(Error::invalid_message_type)? {
+            Sum::EventDataRoundState(event) => Ok(WalMessage::RoundState(event.try_into()?)),
+            Sum::MsgInfo(msg) => Ok(WalMessage::MsgInfo(msg)),
+            Sum::TimeoutInfo(timeout) => Ok(WalMessage::Timeout(timeout.try_into()?)),
+            Sum::EndHeight(end) => Ok(WalMessage::EndHeight(end.height.try_into()?)),
+        }
+    }
+}
+
+impl From<WalMessage> for RawWalMessage {
+    fn from(value: WalMessage) -> Self {
+        let sum = match value {
+            WalMessage::RoundState(event) => Sum::EventDataRoundState(event.into()),
+            WalMessage::MsgInfo(msg) => Sum::MsgInfo(msg),
+            WalMessage::Timeout(timeout) => Sum::TimeoutInfo(timeout.into()),
+            WalMessage::EndHeight(height) => Sum::EndHeight(RawEndHeight {
+                height: height.into(),
+            }),
+        };
+        Self { sum: Some(sum) }
+    }
+}
+
+/// New step of the consensus
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundStateEvent {
+    /// Block height
+    pub height: Height,
+    /// Consensus round
+    pub round: Round,
+    /// Name of the step, eg. `RoundStepPropose`
+    pub step: String,
+}
+
+impl TryFrom<RawEventDataRoundState> for RoundStateEvent {
+    type Error = Error;
+
+    fn try_from(value: RawEventDataRoundState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            height: value.height.try_into()?,
+            round: value.round.try_into()?,
+            step: value.step,
+        })
+    }
+}
+
+impl From<RoundStateEvent> for RawEventDataRoundState {
+    fn from(value: RoundStateEvent) -> Self {
+        Self {
+            height: value.height.into(),
+            round: value.round.into(),
+            step: value.step,
+        }
+    }
+}
+
+/// Expired timeout of the consensus
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TimeoutInfo {
+    /// Duration of the timeout
+    pub duration: Duration,
+    /// Block height
+    pub height: Height,
+    /// Consensus round
+    pub round: Round,
+    /// Step the timeout was scheduled at
+    pub step: RoundStep,
+}
+
+impl TryFrom<RawTimeoutInfo> for TimeoutInfo {
+    type Error = Error;
+
+    fn try_from(value: RawTimeoutInfo) -> Result<Self, Self::Error> {
+        let duration = value.duration.unwrap_or_default();
+        let step = u8::try_from(value.step).map_err(Error::integer_overflow)?;
+        Ok(Self {
+            duration: Duration::new(
+                duration
+                    .seconds
+                    .try_into()
+                    .map_err(Error::integer_overflow)?,
+                duration.nanos.try_into().map_err(Error::integer_overflow)?,
+            ),
+            height: value.height.try_into()?,
+            round: value.round.try_into()?,
+            step: step.try_into()?,
+        })
+    }
+}
+
+impl From<TimeoutInfo> for RawTimeoutInfo {
+    fn from(value: TimeoutInfo) -> Self {
+        Self {
+            duration: Some(RawDuration {
+                seconds: value.duration.as_secs() as i64,
+                nanos: value.duration.subsec_nanos() as i32,
+            }),
+            height: value.height.into(),
+            round: value.round.into(),
+            step: u8::from(value.step).into(),
+        }
+    }
+}
+
+/// Writer of log entries
+#[derive(Debug)]
+pub struct WalEncoder<W> {
+    writer: W,
+}
+
+impl<W: Write> WalEncoder<W> {
+    /// Write log entries to the given writer
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write an entry for the given message
+    pub fn encode(&mut self, msg: &TimedWalMessage) -> Result<(), Error> {
+        let data = msg
+            .encode_vec()
+            .map_err(|e| Error::protocol(e.to_string()))?;
+        if data.len() > MAX_MSG_SIZE_BYTES {
+            return Err(Error::wal_message_too_large(data.len(), MAX_MSG_SIZE_BYTES));
+        }
+
+        let mut entry = Vec::with_capacity(HEADER_SIZE + data.len());
+        entry.extend_from_slice(&crc32c(&data).to_be_bytes());
+        entry.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        entry.extend_from_slice(&data);
+        self.writer.write_all(&entry).map_err(Error::io)
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush().map_err(Error::io)
+    }
+
+    /// Get back the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reader of log entries, checking their checksums
+#[derive(Debug)]
+pub struct WalDecoder<R> {
+    reader: R,
+}
+
+impl<R: Read> WalDecoder<R> {
+    /// Read log entries from the given reader
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the next entry, or `None` at the end of the log
+    pub fn decode(&mut self) -> Result<Option<TimedWalMessage>, Error> {
+        let mut header = [0; HEADER_SIZE];
+        match read_header(&mut self.reader, &mut header) {
+            Ok(false) => return Ok(None),
+            Ok(true) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(Error::corrupted_wal("truncated entry header".to_string()))
+            }
+            Err(e) => return Err(Error::io(e)),
+        }
+
+        let checksum = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let length = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        if length > MAX_MSG_SIZE_BYTES {
+            return Err(Error::corrupted_wal(format!(
+                "length {} exceeds the maximum of {} bytes",
+                length, MAX_MSG_SIZE_BYTES
+            )));
+        }
+
+        let mut data = vec![0; length];
+        self.reader.read_exact(&mut data).map_err(|e| {
+            if e.kind() == io::ErrorKind::UnexpectedEof {
+                Error::corrupted_wal("truncated entry".to_string())
+            } else {
+                Error::io(e)
+            }
+        })?;
+
+        let actual = crc32c(&data);
+        if actual != checksum {
+            return Err(Error::corrupted_wal(format!(
+                "checksums do not match: expected {:#010x}, found {:#010x}",
+                checksum, actual
+            )));
+        }
+
+        TimedWalMessage::decode_vec(&data)
+            .map(Some)
+            .map_err(|e| Error::corrupted_wal(e.to_string()))
+    }
+
+    /// Get back the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for WalDecoder<R> {
+    type Item = Result<TimedWalMessage, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decode().transpose()
+    }
+}
+
+/// Fill the header of an entry, returning `false` at the end of the input
+fn read_header<R: Read>(reader: &mut R, header: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < header.len() {
+        match reader.read(&mut header[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+/// Lookup table of the CRC-32C (Castagnoli) checksum
+const CRC32C_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32C (Castagnoli) checksum of the given data
+fn crc32c(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC32C_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_proto::consensus::{message, HasVote, Message};
+
+    fn messages() -> Vec<TimedWalMessage> {
+        let time = Time::parse_from_rfc3339("2021-03-26T10:13:45.123456789Z").unwrap();
+        let height = Height::from(42_u32);
+        vec![
+            TimedWalMessage {
+                time,
+                msg: WalMessage::RoundState(RoundStateEvent {
+                    height,
+                    round: Round::from(1_u16),
+                    step: RoundStep::Propose.to_string(),
+                }),
+            },
+            TimedWalMessage {
+                time,
+                msg: WalMessage::MsgInfo(MsgInfo {
+                    msg: Some(Message {
+                        sum: Some(message::Sum::HasVote(HasVote {
+                            height: 42,
+                            round: 1,
+                            r#type: 1,
+                            index: 3,
+                        })),
+                    }),
+                    peer_id: "1a7b6bcf3d6fb055ab3aebca415847531b626699".to_string(),
+                }),
+            },
+            TimedWalMessage {
+                time,
+                msg: WalMessage::Timeout(TimeoutInfo {
+                    duration: Duration::from_millis(3000),
+                    height,
+                    round: Round::from(1_u16),
+                    step: RoundStep::Propose,
+                }),
+            },
+            TimedWalMessage {
+                time,
+                msg: WalMessage::EndHeight(height),
+            },
+        ]
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut encoder = WalEncoder::new(Vec::new());
+        for msg in messages() {
+            encoder.encode(&msg).unwrap();
+        }
+        let wal = encoder.into_inner();
+
+        let decoded = WalDecoder::new(wal.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(decoded, messages());
+    }
+
+    #[test]
+    fn detect_corruption() {
+        let mut encoder = WalEncoder::new(Vec::new());
+        encoder.encode(&messages()[0]).unwrap();
+        let wal = encoder.into_inner();
+
+        // Truncated entries
+        for len in &[3, HEADER_SIZE, wal.len() - 1] {
+            let mut decoder = WalDecoder::new(&wal[..*len]);
+            assert!(decoder.decode().is_err());
+        }
+
+        // Altered message
+        let mut altered = wal.clone();
+        *altered.last_mut().unwrap() ^= 1;
+        assert!(WalDecoder::new(altered.as_slice()).decode().is_err());
+
+        // Excessive length
+        let mut oversized = wal;
+        oversized[4..HEADER_SIZE].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(WalDecoder::new(oversized.as_slice()).decode().is_err());
+
+        assert!(WalDecoder::new(&[][..]).decode().unwrap().is_none());
+    }
+}
@@ -1,10 +1,11 @@
 //! Error types
 
 use crate::account;
+use crate::block::signed_header::InvalidCommitSignature;
 use crate::vote;
 use alloc::string::String;
 use core::num::TryFromIntError;
-use flex_error::{define_error, DisplayOnly};
+use flex_error::{define_error, DisplayOnly, ErrorMessageTracer};
 use std::io::Error as IoError;
 use time::OutOfRangeError;
 
@@ -16,7 +17,7 @@ define_error! {
 
         InvalidKey
             { detail: String }
-            |e| { format_args!("invalid key: {}", e) },
+            |e| { format_args!("invalid key: {}", e.detail) },
 
         Io
             [ DisplayOnly<IoError> ]
@@ -30,6 +31,11 @@ define_error! {
         Length
             |_| { format_args!("length error") },
 
+        InvalidField
+            { field: String }
+            [ Self ]
+            |e| { format_args!("invalid field {}: {}", e.field, e.source) },
+
         Parse
             { data: String }
             | e | { format_args!("error parsing data: {}", e.data) },
@@ -45,7 +51,7 @@ define_error! {
 
         Protocol
             { detail: String }
-            |_| { format_args!("protocol error") },
+            |e| { format_args!("protocol error: {}", e.detail) },
 
         OutOfRange
             [ DisplayOnly<OutOfRangeError> ]
@@ -53,7 +59,7 @@ define_error! {
 
         SignatureInvalid
             { detail: String }
-            |_| { format_args!("bad signature") },
+            |e| { format_args!("bad signature: {}", e.detail) },
 
         InvalidMessageType
             |_| { format_args!("invalid message type") },
@@ -74,16 +80,35 @@ define_error! {
             |_| { format_args!("negative validator index") },
 
         InvalidHashSize
-            |_| { format_args!("invalid hash: expected hash size to be 32 bytes") },
+            { expected: usize, found: usize }
+            |e| { format_args!("invalid hash: expected hash size to be {} bytes, found {}", e.expected, e.found) },
 
         NonZeroTimestamp
             | _ | { "absent commitsig has non-zero timestamp" },
 
+        InvalidDerivationPath
+            { detail: String }
+            |e| { format_args!("invalid derivation path: {}", e.detail) },
+
+        InvalidMnemonic
+            { detail: String }
+            |e| { format_args!("invalid mnemonic: {}", e.detail) },
+
+        InvalidEventAttribute
+            { detail: String }
+            |e| { format_args!("invalid event attribute: {}", e.detail) },
+
+        InvalidBech32Prefix
+            { expected: String, found: String }
+            |e| { format_args!("invalid bech32 human readable prefix: expected {}, found {}", e.expected, e.found) },
+
         InvalidAccountIdLength
-            |_| { format_args!("invalid account ID length") },
+            { expected: usize, found: usize }
+            |e| { format_args!("invalid account ID length: expected {} bytes, found {}", e.expected, e.found) },
 
         InvalidSignatureIdLength
-            |_| { format_args!("invalid signature ID length") },
+            { expected: usize, found: usize }
+            |e| { format_args!("invalid signature ID length: expected {} bytes, found {}", e.expected, e.found) },
 
         IntegerOverflow
             [ DisplayOnly<TryFromIntError> ]
@@ -100,7 +125,11 @@ define_error! {
 
         InvalidPartSetHeader
             { detail : String }
-            |_| { format_args!("invalid part set header") },
+            |e| { format_args!("invalid part set header: {}", e.detail) },
+
+        InvalidPart
+            { detail: String }
+            |e| { format_args!("invalid block part: {}", e.detail) },
 
         MissingHeader
             |_| { format_args!("missing header field") },
@@ -114,6 +143,13 @@ define_error! {
         MissingTimestamp
             |_| { format_args!("missing timestamp field") },
 
+        MissingValidator
+            |_| { format_args!("missing validator field") },
+
+        InvalidMisbehaviorKind
+            { kind: i32 }
+            |e| { format_args!("invalid misbehavior kind: {}", e.kind) },
+
         InvalidTimestamp
             { reason: String }
             | e | { format_args!("invalid timestamp: {}", e.reason) },
@@ -122,6 +158,14 @@ define_error! {
             { reason: String }
             | e | { format_args!("invalid block: {}", e.reason) },
 
+        InvalidHeader
+            { detail: String }
+            | e | { format_args!("invalid header: {}", e.detail) },
+
+        InvalidCommit
+            { detail: String }
+            | e | { format_args!("invalid commit: {}", e.detail) },
+
         MissingVersion
             |_| { format_args!("missing version") },
 
@@ -138,9 +182,17 @@ define_error! {
         InvalidSignedHeader
             |_| { format_args!("invalid signed header") },
 
+        InconsistentSignedHeader
+            { detail: String }
+            |e| { format_args!("inconsistent signed header: {}", e.detail) },
+
         InvalidEvidence
             |_| { format_args!("invalid evidence") },
 
+        EvidenceVerification
+            { reason: String }
+            |e| { format_args!("evidence failed verification: {}", e.reason) },
+
         BlockIdFlag
             |_| { format_args!("invalid block id flag") },
 
@@ -148,9 +200,23 @@ define_error! {
             [ DisplayOnly<TryFromIntError> ]
             |_| { format_args!("negative power") },
 
+        TotalVotingPowerOverflow
+            |_| { format_args!("total voting power exceeds the maximum of {}", crate::validator::MAX_TOTAL_VOTING_POWER) },
+
         UnsupportedKeyType
             |_| { format_args!("unsupported key type" ) },
 
+        UnsupportedTendermintVersion
+            { version: String }
+            |e| { format_args!("unsupported Tendermint version: {}", e.version) },
+
+        UnsupportedEvidence
+            |_| { format_args!("conflicting headers evidence has no Protobuf encoding") },
+
+        UnsupportedCompatMode
+            { mode: String }
+            |e| { format_args!("the {} compatibility mode requires the amino feature", e.mode) },
+
         RawVotingPowerMismatch
             { raw: vote::Power, computed: vote::Power }
             |e| { format_args!("mismatch between raw voting ({0:?}) and computed one ({1:?})", e.raw, e.computed) },
@@ -161,6 +227,14 @@ define_error! {
         InvalidValidatorParams
             |_| { format_args!("invalid validator parameters") },
 
+        InvalidConsensusParams
+            { detail: String }
+            |e| { format_args!("invalid consensus parameters: {}", e.detail) },
+
+        InvalidGenesis
+            { detail: String }
+            |e| { format_args!("invalid genesis: {}", e.detail) },
+
         InvalidVersionParams
             |_| { format_args!("invalid version parameters") },
 
@@ -171,10 +245,25 @@ define_error! {
         MissingMaxAgeDuration
             |_| { format_args!("missing max_age_duration") },
 
+        MissingProof
+            |_| { format_args!("missing proof") },
+
         ProposerNotFound
             { account: account::Id }
             |e| { format_args!("proposer with address '{0}' no found in validator set", e.account) },
 
+        DoubleSign
+            { reason: String }
+            |e| { format_args!("refusing to sign to prevent double signing: {}", e.reason) },
+
+        Signer
+            { detail: String }
+            |e| { format_args!("signer error: {}", e.detail) },
+
+        InvalidValidatorSetUpdate
+            { detail: String }
+            |e| { format_args!("invalid validator set update: {}", e.detail) },
+
         ChronoParse
             [ DisplayOnly<chrono::ParseError> ]
             |_| { format_args!("chrono parse error") },
@@ -187,10 +276,30 @@ define_error! {
             [ DisplayOnly<serde_json::Error> ]
             |_| { format_args!("serde json error") },
 
+        NonCanonicalJson
+            { detail: String }
+            | e | { format_args!("value has no canonical JSON encoding: {}", e.detail) },
+
         Toml
             [ DisplayOnly<toml::de::Error> ]
             |_| { format_args!("toml de error") },
 
+        InvalidConfig
+            { detail: String }
+            | e | { format_args!("invalid configuration: {}", e.detail) },
+
+        CorruptedWal
+            { detail: String }
+            | e | { format_args!("corrupted consensus WAL: {}", e.detail) },
+
+        WalMessageTooLarge
+            { size: usize, max: usize }
+            | e | { format_args!("WAL message of {} bytes exceeds the maximum of {} bytes", e.size, e.max) },
+
+        CorruptedBlockStore
+            { detail: String }
+            | e | { format_args!("corrupted block store: {}", e.detail) },
+
         Signature
             [ DisplayOnly<signature::Error> ]
             |_| { format_args!("signature error") },
@@ -203,5 +312,40 @@ define_error! {
 
         TrustThresholdTooSmall
             |_| { "trust threshold too small (must be >= 1/3)" },
+
+        HeaderCommitMismatch
+            { header_hash: String, commit_hash: String }
+            |e| { format_args!("header hash {} does not match the hash of the committed block {}", e.header_hash, e.commit_hash) },
+
+        InvalidCommitSignatures
+            { signatures: Vec<InvalidCommitSignature> }
+            |e| { format_args!("{} commit signature(s) failed verification: {:?}", e.signatures.len(), e.signatures) },
+
+        InsufficientVotingPower
+            { signed: u64, total: u64 }
+            |e| { format_args!("insufficient voting power signed the commit: {} out of {}", e.signed, e.total) },
+
+        InvalidMerkleProof
+            { reason: String }
+            |e| { format_args!("invalid Merkle proof: {}", e.reason) },
+    }
+}
+
+impl Error {
+    /// Error of the conversion of the given field of a message, where the
+    /// fields of nested messages are joined into a path, eg.
+    /// `header.last_block_id`
+    pub fn field(field: &str, source: Error) -> Error {
+        match source {
+            Error(ErrorDetail::InvalidField(nested), trace) => {
+                let detail = ErrorDetail::InvalidField(InvalidFieldSubdetail {
+                    field: format!("{}.{}", field, nested.field),
+                    source: nested.source,
+                });
+                let trace = trace.add_message(&detail);
+                Error(detail, trace)
+            }
+            source => Error::invalid_field(field.to_string(), source),
+        }
     }
 }
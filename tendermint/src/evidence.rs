@@ -1,7 +1,15 @@
 //! Evidence of malfeasance by validators (i.e. signing conflicting votes).
 
 use crate::{
-    block::signed_header::SignedHeader, error::Error, serializers, vote::Power, Time, Vote,
+    block::{signed_header::SignedHeader, Height},
+    chain,
+    error::Error,
+    merkle::simple_hash_from_byte_vectors,
+    serializers,
+    trust_threshold::TrustThresholdFraction,
+    validator,
+    vote::Power,
+    Time, Vote,
 };
 use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
@@ -13,6 +21,8 @@ use tendermint_proto::types::DuplicateVoteEvidence as RawDuplicateVoteEvidence;
 use tendermint_proto::types::Evidence as RawEvidence;
 use tendermint_proto::types::EvidenceList as RawEvidenceList;
 use tendermint_proto::types::EvidenceParams as RawEvidenceParams;
+use tendermint_proto::types::LightBlock as RawLightBlock;
+use tendermint_proto::types::LightClientAttackEvidence as RawLightClientAttackEvidence;
 use tendermint_proto::Protobuf;
 
 /// Evidence of malfeasance by validators (i.e. signing conflicting votes).
@@ -23,6 +33,7 @@ use tendermint_proto::Protobuf;
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 //#[serde(tag = "type", content = "value")]
 #[serde(try_from = "RawEvidence", into = "RawEvidence")] // Used by RPC /broadcast_evidence endpoint
+#[allow(clippy::large_enum_variant)]
 pub enum Evidence {
     /// Duplicate vote evidence
     //#[serde(rename = "tendermint/DuplicateVoteEvidence")]
@@ -32,8 +43,25 @@ pub enum Evidence {
     //#[serde(rename = "tendermint/ConflictingHeadersEvidence")]
     ConflictingHeaders(Box<ConflictingHeadersEvidence>),
 
-    /// LightClient attack evidence - Todo: Implement details
-    LightClientAttackEvidence,
+    /// LightClient attack evidence
+    LightClientAttack(Box<LightClientAttackEvidence>),
+}
+
+impl Protobuf<RawEvidence> for Evidence {}
+
+impl Evidence {
+    /// Protobuf encoding of this evidence, without the type wrapper of
+    /// [`RawEvidence`], as committed by the `evidence_hash` of block headers.
+    ///
+    /// Fails for conflicting headers evidence, which has no Protobuf encoding.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let bytes = match self {
+            Evidence::DuplicateVote(ev) => ev.encode_vec(),
+            Evidence::ConflictingHeaders(_ev) => return Err(Error::unsupported_evidence()),
+            Evidence::LightClientAttack(ev) => ev.encode_vec(),
+        };
+        Ok(bytes.expect("vectors have enough capacity"))
+    }
 }
 
 impl TryFrom<RawEvidence> for Evidence {
@@ -41,8 +69,14 @@ impl TryFrom<RawEvidence> for Evidence {
 
     fn try_from(value: RawEvidence) -> Result<Self, Self::Error> {
         match value.sum.ok_or_else(Error::invalid_evidence)? {
-            Sum::DuplicateVoteEvidence(ev) => Ok(Evidence::DuplicateVote(ev.try_into()?)),
-            Sum::LightClientAttackEvidence(_ev) => Ok(Evidence::LightClientAttackEvidence),
+            Sum::DuplicateVoteEvidence(ev) => Ok(Evidence::DuplicateVote(
+                ev.try_into()
+                    .map_err(|e| Error::field("duplicate_vote_evidence", e))?,
+            )),
+            Sum::LightClientAttackEvidence(ev) => Ok(Evidence::LightClientAttack(Box::new(
+                ev.try_into()
+                    .map_err(|e| Error::field("light_client_attack_evidence", e))?,
+            ))),
         }
     }
 }
@@ -54,7 +88,9 @@ impl From<Evidence> for RawEvidence {
                 sum: Some(RawSum::DuplicateVoteEvidence(ev.into())),
             },
             Evidence::ConflictingHeaders(_ev) => RawEvidence { sum: None }, // Todo: implement
-            Evidence::LightClientAttackEvidence => RawEvidence { sum: None }, // Todo: implement
+            Evidence::LightClientAttack(ev) => RawEvidence {
+                sum: Some(RawSum::LightClientAttackEvidence((*ev).into())),
+            },
         }
     }
 }
@@ -69,6 +105,8 @@ pub struct DuplicateVoteEvidence {
     timestamp: Time,
 }
 
+impl Protobuf<RawDuplicateVoteEvidence> for DuplicateVoteEvidence {}
+
 impl TryFrom<RawDuplicateVoteEvidence> for DuplicateVoteEvidence {
     type Error = Error;
 
@@ -77,13 +115,21 @@ impl TryFrom<RawDuplicateVoteEvidence> for DuplicateVoteEvidence {
             vote_a: value
                 .vote_a
                 .ok_or_else(Error::missing_evidence)?
-                .try_into()?,
+                .try_into()
+                .map_err(|e| Error::field("vote_a", e))?,
             vote_b: value
                 .vote_b
                 .ok_or_else(Error::missing_evidence)?
-                .try_into()?,
-            total_voting_power: value.total_voting_power.try_into()?,
-            validator_power: value.validator_power.try_into()?,
+                .try_into()
+                .map_err(|e| Error::field("vote_b", e))?,
+            total_voting_power: value
+                .total_voting_power
+                .try_into()
+                .map_err(|e| Error::field("total_voting_power", e))?,
+            validator_power: value
+                .validator_power
+                .try_into()
+                .map_err(|e| Error::field("validator_power", e))?,
             timestamp: value.timestamp.ok_or_else(Error::missing_timestamp)?.into(),
         })
     }
@@ -95,7 +141,7 @@ impl From<DuplicateVoteEvidence> for RawDuplicateVoteEvidence {
             vote_a: Some(value.vote_a.into()),
             vote_b: Some(value.vote_b.into()),
             total_voting_power: value.total_voting_power.into(),
-            validator_power: value.total_voting_power.into(),
+            validator_power: value.validator_power.into(),
             timestamp: Some(value.timestamp.into()),
         }
     }
@@ -120,6 +166,79 @@ impl DuplicateVoteEvidence {
     pub fn votes(&self) -> (&Vote, &Vote) {
         (&self.vote_a, &self.vote_b)
     }
+
+    /// Verify that this evidence proves that a validator of the given set, ie. the
+    /// validator set at the height of the votes, signed two conflicting votes on the
+    /// given chain, and that it has not expired as of the given latest block.
+    ///
+    /// This performs the checks of `VerifyDuplicateVote` in Go: the votes must be
+    /// for the same height, round and type but for different blocks, be validly
+    /// signed by the same validator, and the voting powers recorded in the evidence
+    /// must match the validator set.
+    pub fn verify(
+        &self,
+        chain_id: chain::Id,
+        validator_set: &validator::Set,
+        params: &Params,
+        latest_height: Height,
+        latest_time: Time,
+    ) -> Result<(), Error> {
+        let (a, b) = (&self.vote_a, &self.vote_b);
+
+        if params.is_expired(a.height, self.timestamp, latest_height, latest_time) {
+            return Err(Error::evidence_verification(format!(
+                "evidence from height {} has expired",
+                a.height
+            )));
+        }
+
+        let validator = validator_set
+            .validator(a.validator_address)
+            .ok_or_else(|| {
+                Error::evidence_verification(format!(
+                    "address {} was not a validator at height {}",
+                    a.validator_address, a.height
+                ))
+            })?;
+
+        if a.height != b.height || a.round != b.round || a.vote_type != b.vote_type {
+            return Err(Error::evidence_verification(
+                "votes are not for the same height, round and type".to_string(),
+            ));
+        }
+
+        if a.validator_address != b.validator_address {
+            return Err(Error::evidence_verification(
+                "votes are not from the same validator".to_string(),
+            ));
+        }
+
+        if a.block_id == b.block_id {
+            return Err(Error::evidence_verification(
+                "votes are for the same block".to_string(),
+            ));
+        }
+
+        if validator.power != self.validator_power {
+            return Err(Error::evidence_verification(format!(
+                "validator power {} does not match the power of the validator {}",
+                self.validator_power, validator.power
+            )));
+        }
+
+        if validator_set.total_voting_power() != self.total_voting_power {
+            return Err(Error::evidence_verification(format!(
+                "total voting power {} does not match the total voting power of the validator set {}",
+                self.total_voting_power,
+                validator_set.total_voting_power()
+            )));
+        }
+
+        a.verify_signature(chain_id.clone(), &validator.pub_key)?;
+        b.verify_signature(chain_id, &validator.pub_key)?;
+
+        Ok(())
+    }
 }
 
 /// Conflicting headers evidence.
@@ -139,6 +258,275 @@ impl ConflictingHeadersEvidence {
     }
 }
 
+/// Evidence of a set of validators attempting to mislead a light client,
+/// by signing a block which conflicts with the one seen by the light client.
+///
+/// <https://github.com/tendermint/spec/blob/master/spec/core/data_structures.md#lightclientattackevidence>
+#[derive(Clone, Debug, PartialEq)]
+pub struct LightClientAttackEvidence {
+    /// The signed header of the conflicting block
+    pub conflicting_header: SignedHeader,
+    /// The validator set of the conflicting block
+    pub conflicting_validators: validator::Set,
+    /// The height of the last block both chains agree on
+    pub common_height: Height,
+    /// The validators of the common block which signed the conflicting block
+    pub byzantine_validators: Vec<validator::Info>,
+    /// The total voting power of the validator set at the common height
+    pub total_voting_power: Power,
+    /// The time of the block at the common height
+    pub timestamp: Time,
+}
+
+impl LightClientAttackEvidence {
+    /// Verify that this evidence proves an attack on a light client, given the
+    /// header at the common height, the header trusted by the node at the height
+    /// of the conflicting header (or its latest header, in case of a forward
+    /// lunatic attack), and the validator set at the common height.
+    /// The evidence must also not have expired as of the given latest block.
+    ///
+    /// This performs the checks of `VerifyLightClientAttack` in Go:
+    /// - in case of a lunatic attack, ie. when the common height is not the height
+    ///   of the conflicting header, more than 1/3 of the common validators must
+    ///   have signed the conflicting header; otherwise, the conflicting header
+    ///   must have been correctly derived from the common state,
+    /// - more than 2/3 of the conflicting validators must have signed the
+    ///   conflicting header,
+    /// - the conflicting header must actually conflict with the trusted one,
+    /// - the total voting power, timestamp and byzantine validators recorded in
+    ///   the evidence must match the common state.
+    pub fn verify(
+        &self,
+        common_header: &SignedHeader,
+        trusted_header: &SignedHeader,
+        common_validators: &validator::Set,
+        params: &Params,
+        latest_height: Height,
+        latest_time: Time,
+    ) -> Result<(), Error> {
+        let conflicting = &self.conflicting_header;
+
+        if params.is_expired(
+            self.common_height,
+            self.timestamp,
+            latest_height,
+            latest_time,
+        ) {
+            return Err(Error::evidence_verification(format!(
+                "evidence from common height {} has expired",
+                self.common_height
+            )));
+        }
+
+        if common_header.header.height != self.common_height {
+            return Err(Error::evidence_verification(format!(
+                "common header is at height {} instead of {}",
+                common_header.header.height, self.common_height
+            )));
+        }
+
+        if common_header.header.height != conflicting.header.height {
+            conflicting
+                .verify(common_validators, TrustThresholdFraction::ONE_THIRD)
+                .map_err(|e| {
+                    Error::evidence_verification(format!(
+                        "common validators did not sign the conflicting header: {}",
+                        e
+                    ))
+                })?;
+        } else if self.conflicting_header_is_invalid(trusted_header) {
+            return Err(Error::evidence_verification(
+                "common height is the height of the conflicting header, \
+                 but the conflicting header was not correctly derived"
+                    .to_string(),
+            ));
+        }
+
+        if conflicting.header.validators_hash != self.conflicting_validators.hash() {
+            return Err(Error::evidence_verification(
+                "conflicting validators do not match the validators hash of the conflicting header"
+                    .to_string(),
+            ));
+        }
+
+        conflicting
+            .verify(
+                &self.conflicting_validators,
+                TrustThresholdFraction::TWO_THIRDS,
+            )
+            .map_err(|e| {
+                Error::evidence_verification(format!(
+                    "conflicting validators did not commit to the conflicting header: {}",
+                    e
+                ))
+            })?;
+
+        if conflicting.header.height > trusted_header.header.height {
+            if conflicting.header.time > trusted_header.header.time {
+                return Err(Error::evidence_verification(
+                    "conflicting header is ahead of the trusted header \
+                     but does not violate monotonically increasing time"
+                        .to_string(),
+                ));
+            }
+        } else if conflicting.header.hash() == trusted_header.header.hash() {
+            return Err(Error::evidence_verification(
+                "conflicting header is the trusted header".to_string(),
+            ));
+        }
+
+        if self.total_voting_power != common_validators.total_voting_power() {
+            return Err(Error::evidence_verification(format!(
+                "total voting power {} does not match the total voting power of the common validators {}",
+                self.total_voting_power,
+                common_validators.total_voting_power()
+            )));
+        }
+
+        if self.timestamp != common_header.header.time {
+            return Err(Error::evidence_verification(
+                "timestamp does not match the time of the common header".to_string(),
+            ));
+        }
+
+        let byzantine_validators = self.byzantine_validators(common_validators, trusted_header);
+        let matches = |expected: &validator::Info, actual: &validator::Info| {
+            expected.address == actual.address && expected.power == actual.power
+        };
+        if byzantine_validators.len() != self.byzantine_validators.len()
+            || !byzantine_validators
+                .iter()
+                .zip(&self.byzantine_validators)
+                .all(|(expected, actual)| matches(expected, actual))
+        {
+            return Err(Error::evidence_verification(
+                "byzantine validators do not match the validators which signed the conflicting header"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the conflicting header could not have been derived from the state
+    /// of the trusted header, ie. whether this evidence is for a lunatic attack.
+    pub fn conflicting_header_is_invalid(&self, trusted_header: &SignedHeader) -> bool {
+        let trusted = &trusted_header.header;
+        let conflicting = &self.conflicting_header.header;
+
+        trusted.validators_hash != conflicting.validators_hash
+            || trusted.next_validators_hash != conflicting.next_validators_hash
+            || trusted.consensus_hash != conflicting.consensus_hash
+            || trusted.app_hash != conflicting.app_hash
+            || trusted.last_results_hash != conflicting.last_results_hash
+    }
+
+    /// The validators which took part in the attack, ordered by decreasing
+    /// voting power, then by address.
+    ///
+    /// In case of a lunatic attack, these are the common validators which signed
+    /// the conflicting header. In case of an equivocation, ie. when both headers
+    /// were committed in the same round, these are the validators which signed
+    /// both headers. In case of an amnesia attack, the validators at fault cannot
+    /// be told apart, and none are returned.
+    pub fn byzantine_validators(
+        &self,
+        common_validators: &validator::Set,
+        trusted_header: &SignedHeader,
+    ) -> Vec<validator::Info> {
+        let conflicting_commit = &self.conflicting_header.commit;
+
+        let mut validators: Vec<validator::Info> =
+            if self.conflicting_header_is_invalid(trusted_header) {
+                conflicting_commit
+                    .signatures
+                    .iter()
+                    .filter(|sig| !sig.is_absent())
+                    .filter_map(|sig| common_validators.validator(sig.validator_address()?))
+                    .collect()
+            } else if trusted_header.commit.round == conflicting_commit.round {
+                conflicting_commit
+                    .signatures
+                    .iter()
+                    .zip(&trusted_header.commit.signatures)
+                    .filter(|(a, b)| !a.is_absent() && !b.is_absent())
+                    .filter_map(|(sig, _)| {
+                        self.conflicting_validators
+                            .validator(sig.validator_address()?)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        validators.sort_by(|a, b| b.power.cmp(&a.power).then(a.address.cmp(&b.address)));
+        validators
+    }
+}
+
+impl Protobuf<RawLightClientAttackEvidence> for LightClientAttackEvidence {}
+
+impl TryFrom<RawLightClientAttackEvidence> for LightClientAttackEvidence {
+    type Error = Error;
+
+    fn try_from(value: RawLightClientAttackEvidence) -> Result<Self, Self::Error> {
+        let conflicting_block = value
+            .conflicting_block
+            .ok_or_else(Error::missing_evidence)?;
+
+        Ok(Self {
+            conflicting_header: conflicting_block
+                .signed_header
+                .ok_or_else(Error::missing_evidence)?
+                .try_into()
+                .map_err(|e| Error::field("conflicting_block.signed_header", e))?,
+            conflicting_validators: conflicting_block
+                .validator_set
+                .ok_or_else(Error::missing_evidence)?
+                .try_into()
+                .map_err(|e| Error::field("conflicting_block.validator_set", e))?,
+            common_height: value
+                .common_height
+                .try_into()
+                .map_err(|e| Error::field("common_height", e))?,
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .enumerate()
+                .map(|(index, validator)| {
+                    validator
+                        .try_into()
+                        .map_err(|e| Error::field(&format!("byzantine_validators[{}]", index), e))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            total_voting_power: value
+                .total_voting_power
+                .try_into()
+                .map_err(|e| Error::field("total_voting_power", e))?,
+            timestamp: value.timestamp.ok_or_else(Error::missing_timestamp)?.into(),
+        })
+    }
+}
+
+impl From<LightClientAttackEvidence> for RawLightClientAttackEvidence {
+    fn from(value: LightClientAttackEvidence) -> Self {
+        RawLightClientAttackEvidence {
+            conflicting_block: Some(RawLightBlock {
+                signed_header: Some(value.conflicting_header.into()),
+                validator_set: Some(value.conflicting_validators.into()),
+            }),
+            common_height: value.common_height.into(),
+            byzantine_validators: value
+                .byzantine_validators
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            total_voting_power: value.total_voting_power.into(),
+            timestamp: Some(value.timestamp.into()),
+        }
+    }
+}
+
 /// Evidence data is a wrapper for a list of `Evidence`.
 ///
 /// <https://github.com/tendermint/spec/blob/d46cd7f573a2c6a2399fcab2cde981330aa63f37/spec/core/data_structures.md#evidencedata>
@@ -154,8 +542,16 @@ impl TryFrom<RawEvidenceList> for Data {
         if value.evidence.is_empty() {
             return Ok(Self { evidence: None });
         }
-        let evidence: Result<Vec<Evidence>, Error> =
-            value.evidence.into_iter().map(TryInto::try_into).collect();
+        let evidence: Result<Vec<Evidence>, Error> = value
+            .evidence
+            .into_iter()
+            .enumerate()
+            .map(|(index, evidence)| {
+                evidence
+                    .try_into()
+                    .map_err(|e| Error::field(&format!("evidence[{}]", index), e))
+            })
+            .collect();
         Ok(Self {
             evidence: Some(evidence?),
         })
@@ -195,11 +591,24 @@ impl Data {
     pub fn iter(&self) -> slice::Iter<'_, Evidence> {
         self.as_ref().iter()
     }
+
+    /// Compute the Merkle root of the encodings of the evidence in the
+    /// collection (see [`Evidence::to_bytes`]), ie. the `evidence_hash` of
+    /// the header of the block they are in.
+    pub fn hash(&self) -> Result<crate::Hash, Error> {
+        let evidence_bytes = self
+            .iter()
+            .map(Evidence::to_bytes)
+            .collect::<Result<_, _>>()?;
+        Ok(crate::Hash::Sha256(simple_hash_from_byte_vectors(
+            evidence_bytes,
+        )))
+    }
 }
 
 impl AsRef<[Evidence]> for Data {
     fn as_ref(&self) -> &[Evidence] {
-        self.evidence.as_deref().unwrap_or_else(|| &[])
+        self.evidence.as_deref().unwrap_or(&[])
     }
 }
 
@@ -220,6 +629,39 @@ pub struct Params {
     pub max_bytes: i64,
 }
 
+impl Default for Params {
+    /// The default evidence parameters of Tendermint
+    fn default() -> Self {
+        Self {
+            max_age_num_blocks: 100_000,
+            max_age_duration: Duration(std::time::Duration::from_secs(48 * 3600)),
+            max_bytes: 1_048_576,
+        }
+    }
+}
+
+impl Params {
+    /// Whether evidence of misbehavior at the given height and time has expired
+    /// as of the given latest block, ie. whether it is older than both the
+    /// maximum number of blocks and the maximum duration.
+    pub fn is_expired(
+        &self,
+        evidence_height: Height,
+        evidence_time: Time,
+        latest_height: Height,
+        latest_time: Time,
+    ) -> bool {
+        let age_num_blocks = latest_height
+            .value()
+            .saturating_sub(evidence_height.value());
+        let age_duration = latest_time
+            .duration_since(evidence_time)
+            .unwrap_or_default();
+
+        age_num_blocks > self.max_age_num_blocks && age_duration > self.max_age_duration.into()
+    }
+}
+
 impl Protobuf<RawEvidenceParams> for Params {}
 
 impl TryFrom<RawEvidenceParams> for Params {
@@ -234,7 +676,8 @@ impl TryFrom<RawEvidenceParams> for Params {
             max_age_duration: value
                 .max_age_duration
                 .ok_or_else(Error::missing_max_age_duration)?
-                .try_into()?,
+                .try_into()
+                .map_err(|e| Error::field("max_age_duration", e))?,
             max_bytes: value.max_bytes,
         })
     }
@@ -287,3 +730,278 @@ impl From<Duration> for RawDuration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{self, CommitSig, Header, Round};
+    use crate::error::ErrorDetail;
+    use crate::test::{keypair, sign_header, validator};
+    use crate::vote::{self, ValidatorIndex};
+    use crate::{hash::AppHash, Signature};
+    use ed25519_dalek::{Keypair, Signer};
+    use std::time::Duration as StdDuration;
+
+    fn params() -> Params {
+        Params {
+            max_age_num_blocks: 100,
+            max_age_duration: Duration(StdDuration::from_secs(3600)),
+            max_bytes: 1024,
+        }
+    }
+
+    fn header(height: u32, time: Time) -> Header {
+        let mut header = crate::test::header();
+        header.height = height.into();
+        header.time = time;
+        header
+    }
+
+    fn signed_vote(keypair: &Keypair, chain_id: &chain::Id, block_hash: u8) -> Vote {
+        let validator = validator(keypair, 10);
+        let mut vote = Vote {
+            vote_type: vote::Type::Prevote,
+            height: 10_u32.into(),
+            round: Round::from(1_u16),
+            block_id: Some(block::Id {
+                hash: crate::Hash::Sha256([block_hash; 32]),
+                part_set_header: Default::default(),
+            }),
+            timestamp: Some(Time::unix_epoch()),
+            validator_address: validator.address,
+            validator_index: ValidatorIndex::try_from(0).unwrap(),
+            signature: Signature::default(),
+        };
+        let sign_bytes = vote.to_signable_vec(chain_id.clone()).unwrap();
+        vote.signature = Signature::Ed25519(keypair.sign(&sign_bytes));
+        vote
+    }
+
+    #[test]
+    fn verify_duplicate_vote_evidence() {
+        let keypairs = [keypair(1), keypair(2)];
+        let validator_set =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+        let chain_id = chain::Id::try_from("test-chain").unwrap();
+
+        let evidence = DuplicateVoteEvidence {
+            vote_a: signed_vote(&keypairs[0], &chain_id, 1),
+            vote_b: signed_vote(&keypairs[0], &chain_id, 2),
+            total_voting_power: 20_u32.into(),
+            validator_power: 10_u32.into(),
+            timestamp: Time::unix_epoch(),
+        };
+
+        let latest_time = Time::unix_epoch();
+        let verify = |evidence: &DuplicateVoteEvidence, latest_height: u32, latest_time: Time| {
+            evidence.verify(
+                chain_id.clone(),
+                &validator_set,
+                &params(),
+                latest_height.into(),
+                latest_time,
+            )
+        };
+
+        verify(&evidence, 20, latest_time).unwrap();
+
+        // Still within the maximum age in number of blocks
+        let much_later = latest_time + StdDuration::from_secs(7200);
+        verify(&evidence, 20, much_later).unwrap();
+        // Older than both the maximum age in number of blocks and in duration
+        assert!(verify(&evidence, 200, much_later).is_err());
+
+        let same_block = DuplicateVoteEvidence {
+            vote_b: evidence.vote_a.clone(),
+            ..evidence.clone()
+        };
+        assert!(verify(&same_block, 20, latest_time).is_err());
+
+        let mut forged_vote = evidence.vote_b.clone();
+        forged_vote.signature = evidence.vote_a.signature;
+        let forged = DuplicateVoteEvidence {
+            vote_b: forged_vote,
+            ..evidence.clone()
+        };
+        assert!(verify(&forged, 20, latest_time).is_err());
+
+        let other_validators = DuplicateVoteEvidence {
+            vote_b: signed_vote(&keypairs[1], &chain_id, 2),
+            ..evidence.clone()
+        };
+        assert!(verify(&other_validators, 20, latest_time).is_err());
+
+        let wrong_power = DuplicateVoteEvidence {
+            validator_power: 20_u32.into(),
+            ..evidence.clone()
+        };
+        assert!(verify(&wrong_power, 20, latest_time).is_err());
+
+        let not_a_validator = DuplicateVoteEvidence {
+            vote_a: signed_vote(&keypair(3), &chain_id, 1),
+            vote_b: signed_vote(&keypair(3), &chain_id, 2),
+            ..evidence
+        };
+        assert!(verify(&not_a_validator, 20, latest_time).is_err());
+    }
+
+    #[test]
+    fn verify_lunatic_light_client_attack_evidence() {
+        let keypairs = vec![keypair(1), keypair(2), keypair(3), keypair(4)];
+        let common_validators = validator::Set::without_proposer(
+            keypairs[..3].iter().map(|kp| validator(kp, 10)).collect(),
+        )
+        .unwrap();
+
+        // The first two common validators, along with a new validator,
+        // sign a header with another application state.
+        let conflicting_validators = validator::Set::without_proposer(vec![
+            validator(&keypairs[0], 10),
+            validator(&keypairs[1], 10),
+            validator(&keypairs[3], 5),
+        ])
+        .unwrap();
+
+        let common_time = Time::unix_epoch();
+        let later = common_time + StdDuration::from_secs(10);
+
+        let common_header = sign_header(header(5, common_time), &common_validators, &keypairs);
+        let trusted_header = sign_header(header(10, later), &common_validators, &keypairs);
+
+        let mut lunatic = header(10, later);
+        lunatic.app_hash = AppHash::try_from(vec![1; 8]).unwrap();
+        let conflicting_header = sign_header(lunatic, &conflicting_validators, &keypairs);
+
+        let mut byzantine_validators =
+            vec![validator(&keypairs[0], 10), validator(&keypairs[1], 10)];
+        byzantine_validators.sort_by_key(|v| v.address);
+
+        let evidence = LightClientAttackEvidence {
+            conflicting_header,
+            conflicting_validators,
+            common_height: 5_u32.into(),
+            byzantine_validators,
+            total_voting_power: 30_u32.into(),
+            timestamp: common_time,
+        };
+
+        let verify = |evidence: &LightClientAttackEvidence| {
+            evidence.verify(
+                &common_header,
+                &trusted_header,
+                &common_validators,
+                &params(),
+                10_u32.into(),
+                later,
+            )
+        };
+
+        assert!(evidence.conflicting_header_is_invalid(&trusted_header));
+        verify(&evidence).unwrap();
+
+        let missing_byzantine_validator = LightClientAttackEvidence {
+            byzantine_validators: evidence.byzantine_validators[..1].to_vec(),
+            ..evidence.clone()
+        };
+        assert!(verify(&missing_byzantine_validator).is_err());
+
+        let wrong_timestamp = LightClientAttackEvidence {
+            timestamp: later,
+            ..evidence.clone()
+        };
+        assert!(verify(&wrong_timestamp).is_err());
+
+        // Not enough of the common validators signed the conflicting header
+        let mut conflicting_validators =
+            vec![validator(&keypairs[0], 10), validator(&keypairs[3], 5)];
+        conflicting_validators.sort_by_key(|v| v.address);
+        let conflicting_validators =
+            validator::Set::without_proposer(conflicting_validators).unwrap();
+        let mut lunatic = header(10, later);
+        lunatic.app_hash = AppHash::try_from(vec![1; 8]).unwrap();
+        let not_enough_power = LightClientAttackEvidence {
+            conflicting_header: sign_header(lunatic, &conflicting_validators, &keypairs),
+            conflicting_validators,
+            byzantine_validators: vec![validator(&keypairs[0], 10)],
+            ..evidence
+        };
+        assert!(verify(&not_enough_power).is_err());
+
+        // The trusted header does not conflict with itself
+        let no_conflict = LightClientAttackEvidence {
+            conflicting_header: trusted_header.clone(),
+            conflicting_validators: common_validators.clone(),
+            byzantine_validators: vec![],
+            ..evidence
+        };
+        assert!(verify(&no_conflict).is_err());
+    }
+
+    #[test]
+    fn byzantine_validators_include_nil_voters() {
+        let keypairs = vec![keypair(1), keypair(2), keypair(3)];
+        let validators =
+            validator::Set::without_proposer(keypairs.iter().map(|kp| validator(kp, 10)).collect())
+                .unwrap();
+
+        let time = Time::unix_epoch();
+        let trusted_header = sign_header(header(10, time), &validators, &keypairs);
+
+        let mut lunatic = header(10, time);
+        lunatic.app_hash = AppHash::try_from(vec![1; 8]).unwrap();
+        let mut conflicting_header = sign_header(lunatic, &validators, &keypairs);
+
+        // A validator which voted nil for the conflicting header still signed
+        // it, as `!CommitSig.Absent()` in Go.
+        let (validator_address, timestamp, signature) =
+            match conflicting_header.commit.signatures[1].clone() {
+                CommitSig::BlockIdFlagCommit {
+                    validator_address,
+                    timestamp,
+                    signature,
+                } => (validator_address, timestamp, signature),
+                _ => unreachable!(),
+            };
+        conflicting_header.commit.signatures[1] = CommitSig::BlockIdFlagNil {
+            validator_address,
+            timestamp,
+            signature,
+        };
+        conflicting_header.commit.signatures[2] = CommitSig::BlockIdFlagAbsent;
+
+        let evidence = LightClientAttackEvidence {
+            conflicting_header,
+            conflicting_validators: validators.clone(),
+            common_height: 10_u32.into(),
+            byzantine_validators: vec![],
+            total_voting_power: 30_u32.into(),
+            timestamp: time,
+        };
+
+        let mut expected = validators.validators()[..2].to_vec();
+        expected.sort_by_key(|v| v.address);
+        let byzantine_validators = evidence.byzantine_validators(&validators, &trusted_header);
+        assert_eq!(byzantine_validators, expected);
+    }
+
+    #[test]
+    fn conflicting_headers_evidence_cannot_be_hashed() {
+        let keypairs = [keypair(1)];
+        let validators =
+            validator::Set::without_proposer(vec![validator(&keypairs[0], 10)]).unwrap();
+        let time = Time::unix_epoch();
+        let signed_header = sign_header(header(10, time), &validators, &keypairs);
+
+        let evidence = Evidence::ConflictingHeaders(Box::new(ConflictingHeadersEvidence::new(
+            signed_header.clone(),
+            signed_header,
+        )));
+        assert!(matches!(
+            evidence.to_bytes(),
+            Err(Error(ErrorDetail::UnsupportedEvidence(_), _))
+        ));
+        assert!(Data::new(vec![evidence]).hash().is_err());
+        assert!(Data::default().hash().is_ok());
+    }
+}
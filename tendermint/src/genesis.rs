@@ -1,8 +1,10 @@
 //! Genesis data
 
+use crate::error::Error;
 use crate::serializers;
-use crate::{chain, consensus, validator, Time};
+use crate::{account, chain, consensus, validator, vote, Time};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Genesis data
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,7 +19,8 @@ pub struct Genesis<AppState = serde_json::Value> {
     #[serde(with = "serializers::from_str")]
     pub initial_height: i64,
 
-    /// Consensus parameters
+    /// Consensus parameters, defaulting to those of Tendermint
+    #[serde(default)]
     pub consensus_params: consensus::Params,
 
     /// Validators
@@ -32,3 +35,192 @@ pub struct Genesis<AppState = serde_json::Value> {
     #[serde(default)]
     pub app_state: AppState,
 }
+
+impl<AppState> Genesis<AppState> {
+    /// Check that the genesis data is well-formed, as done by
+    /// `GenesisDoc.ValidateAndComplete` in Go.
+    ///
+    /// This checks the chain ID, the consensus parameters, the initial height,
+    /// the genesis time, and that the validators have a positive voting power,
+    /// an address matching their public key, distinct addresses, and a total
+    /// voting power not exceeding [`validator::MAX_TOTAL_VOTING_POWER`].
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |detail: String| Err(Error::invalid_genesis(detail));
+
+        let chain_id = self.chain_id.as_str();
+        if chain_id.is_empty() {
+            return invalid("chain_id must not be empty".to_string());
+        }
+        if chain_id.len() > chain::id::MAX_LENGTH {
+            return invalid(format!(
+                "chain_id is too long: {} > {}",
+                chain_id.len(),
+                chain::id::MAX_LENGTH
+            ));
+        }
+
+        if self.initial_height < 0 {
+            return invalid(format!(
+                "initial_height must be non-negative, got {}",
+                self.initial_height
+            ));
+        }
+
+        if self.genesis_time < Time::unix_epoch() {
+            return invalid(format!(
+                "genesis_time is before the Unix epoch: {}",
+                self.genesis_time
+            ));
+        }
+
+        self.consensus_params.validate()?;
+
+        let mut addresses = HashSet::new();
+
+        for (index, validator) in self.validators.iter().enumerate() {
+            let power = validator.power.value();
+            if power == 0 {
+                return invalid(format!(
+                    "validator {} ({}) has no voting power",
+                    index, validator.address
+                ));
+            }
+
+            if validator.address != account::Id::from(validator.pub_key) {
+                return invalid(format!(
+                    "validator {} has address {} which does not match its public key",
+                    index, validator.address
+                ));
+            }
+
+            if !addresses.insert(validator.address) {
+                return invalid(format!("duplicate validator address {}", validator.address));
+            }
+        }
+
+        vote::Power::checked_total(self.validators.iter().map(|validator| validator.power))
+            .map_err(|e| Error::invalid_genesis(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+    use crate::vote;
+    use std::convert::TryFrom;
+
+    const GENESIS: &str = r#"{
+        "app_hash": "",
+        "chain_id": "dockerchain",
+        "consensus_params": {
+            "block": {
+                "max_bytes": "22020096",
+                "max_gas": "-1"
+            },
+            "evidence": {
+                "max_age_duration": "172800000000000",
+                "max_age_num_blocks": "100000",
+                "max_bytes": "1048576"
+            },
+            "validator": {
+                "pub_key_types": ["ed25519"]
+            },
+            "version": {}
+        },
+        "genesis_time": "2021-07-16T12:16:29.232984022Z",
+        "initial_height": "1",
+        "validators": [
+            {
+                "address": "ABA577531E6D6F4119E7E1E0EE1909B908A8346D",
+                "name": "",
+                "power": "10",
+                "pub_key": {
+                    "type": "tendermint/PubKeyEd25519",
+                    "value": "f+7sWZojwd8xbxZ+GJL+x/zKr1wyM0NMJkp8tCnA4t0="
+                }
+            }
+        ]
+    }"#;
+
+    fn genesis() -> Genesis {
+        serde_json::from_str(GENESIS).unwrap()
+    }
+
+    fn assert_invalid_genesis(genesis: &Genesis, expected: &str) {
+        match genesis.validate().unwrap_err().detail() {
+            ErrorDetail::InvalidGenesis(e) => {
+                assert!(e.detail.contains(expected), "{}", e.detail)
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn valid_genesis() {
+        genesis().validate().unwrap();
+    }
+
+    #[test]
+    fn default_consensus_params() {
+        let mut json: serde_json::Value = serde_json::from_str(GENESIS).unwrap();
+        json.as_object_mut().unwrap().remove("consensus_params");
+        let genesis: Genesis = serde_json::from_value(json).unwrap();
+        assert_eq!(genesis.consensus_params, consensus::Params::default());
+        genesis.validate().unwrap();
+    }
+
+    #[test]
+    fn invalid_initial_height() {
+        let mut genesis = genesis();
+        genesis.initial_height = -1;
+        assert_invalid_genesis(&genesis, "initial_height");
+    }
+
+    #[test]
+    fn invalid_genesis_time() {
+        let mut genesis = genesis();
+        genesis.genesis_time = Time::parse_from_rfc3339("0001-01-01T00:00:00Z").unwrap();
+        assert_invalid_genesis(&genesis, "genesis_time");
+    }
+
+    #[test]
+    fn invalid_consensus_params() {
+        let mut genesis = genesis();
+        genesis.consensus_params.evidence.max_bytes =
+            genesis.consensus_params.block.max_bytes as i64 + 1;
+        assert!(matches!(
+            genesis.validate().unwrap_err().detail(),
+            ErrorDetail::InvalidConsensusParams(_)
+        ));
+
+        let mut genesis = self::genesis();
+        genesis.consensus_params.validator.pub_key_types.clear();
+        assert!(matches!(
+            genesis.validate().unwrap_err().detail(),
+            ErrorDetail::InvalidConsensusParams(_)
+        ));
+    }
+
+    #[test]
+    fn invalid_validators() {
+        let mut genesis = genesis();
+        genesis.validators[0].power = vote::Power::from(0_u32);
+        assert_invalid_genesis(&genesis, "no voting power");
+
+        let mut genesis = self::genesis();
+        genesis.validators[0].address = account::Id::new([0; 20]);
+        assert_invalid_genesis(&genesis, "does not match");
+
+        let mut genesis = self::genesis();
+        genesis.validators.push(genesis.validators[0].clone());
+        assert_invalid_genesis(&genesis, "duplicate");
+
+        let mut genesis = self::genesis();
+        genesis.validators[0].power =
+            vote::Power::try_from(validator::MAX_TOTAL_VOTING_POWER + 1).unwrap();
+        assert_invalid_genesis(&genesis, "total voting power");
+    }
+}
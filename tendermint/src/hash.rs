@@ -3,17 +3,29 @@
 use crate::error::Error;
 use serde::de::Error as _;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::digest::{consts::U32, Digest};
 use std::convert::TryFrom;
 use std::{
     fmt::{self, Debug, Display},
     str::FromStr,
 };
-use subtle_encoding::{Encoding, Hex};
+use subtle::{self, Choice, ConstantTimeEq};
+use subtle_encoding::{base64, Encoding, Hex};
 use tendermint_proto::Protobuf;
 
 /// Output size for the SHA-256 hash function
 pub const SHA256_HASH_SIZE: usize = 32;
 
+/// Hash functions whose outputs have the size of SHA-256 ones, which can
+/// replace SHA-256 when hashing Merkle trees, headers and transactions, eg. in
+/// forks of Tendermint
+///
+/// It is implemented by all such [`Digest`]s, eg. [`sha2::Sha256`] which is the
+/// hash function of the protocol.
+pub trait HashFunction: Digest<OutputSize = U32> {}
+
+impl<H: Digest<OutputSize = U32>> HashFunction for H {}
+
 /// Hash algorithms
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Algorithm {
@@ -22,16 +34,36 @@ pub enum Algorithm {
 }
 
 /// Hash digests
-#[derive(Copy, Clone, Hash, Eq, PartialEq, PartialOrd, Ord)]
+///
+/// Hashes are compared in constant time.
+#[allow(clippy::derived_hash_with_manual_eq)]
+#[derive(Copy, Clone, Hash, Eq, PartialOrd, Ord, Default)]
 pub enum Hash {
     /// SHA-256 hashes
     Sha256([u8; SHA256_HASH_SIZE]),
     /// Empty hash
+    #[default]
     None,
 }
 
 impl Protobuf<Vec<u8>> for Hash {}
 
+impl ConstantTimeEq for Hash {
+    fn ct_eq(&self, other: &Hash) -> Choice {
+        match (self, other) {
+            (Hash::Sha256(a), Hash::Sha256(b)) => a.ct_eq(b),
+            (Hash::None, Hash::None) => Choice::from(1),
+            _ => Choice::from(0),
+        }
+    }
+}
+
+impl PartialEq for Hash {
+    fn eq(&self, other: &Hash) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 /// Default conversion from Vec<u8> is SHA256 Hash or None
 impl TryFrom<Vec<u8>> for Hash {
     type Error = Error;
@@ -66,7 +98,7 @@ impl Hash {
                     h.copy_from_slice(bytes);
                     Ok(Hash::Sha256(h))
                 } else {
-                    Err(Error::invalid_hash_size())
+                    Err(Error::invalid_hash_size(SHA256_HASH_SIZE, bytes.len()))
                 }
             }
         }
@@ -88,6 +120,23 @@ impl Hash {
         }
     }
 
+    /// Decode a `Hash` from hexadecimal, either upper- or lower-case
+    pub fn from_hex(alg: Algorithm, s: &str) -> Result<Hash, Error> {
+        Self::from_hex_upper(alg, &s.to_ascii_uppercase())
+    }
+
+    /// Decode a `Hash` from base64
+    pub fn from_base64(alg: Algorithm, s: &str) -> Result<Hash, Error> {
+        let bytes = base64::decode(s).map_err(Error::subtle_encoding)?;
+        Self::from_bytes(alg, &bytes)
+    }
+
+    /// Decode a `Hash` from upper- or lower-case hexadecimal, or from base64,
+    /// as different versions of Tendermint and tools encode hashes differently
+    pub fn from_hex_or_base64(alg: Algorithm, s: &str) -> Result<Hash, Error> {
+        Self::from_hex(alg, s).or_else(|e| Self::from_base64(alg, s).map_err(|_| e))
+    }
+
     /// Return the digest algorithm used to produce this hash
     pub fn algorithm(self) -> Algorithm {
         match self {
@@ -119,12 +168,6 @@ impl Debug for Hash {
     }
 }
 
-impl Default for Hash {
-    fn default() -> Self {
-        Hash::None
-    }
-}
-
 impl Display for Hash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let hex = match self {
@@ -147,12 +190,13 @@ impl FromStr for Hash {
 // Serialization is used in light-client config
 impl<'de> Deserialize<'de> for Hash {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let hex = String::deserialize(deserializer)?;
+        let string = String::deserialize(deserializer)?;
 
-        if hex.is_empty() {
+        if string.is_empty() {
             Err(D::Error::custom("empty hash"))
         } else {
-            Ok(Self::from_str(&hex).map_err(|e| D::Error::custom(format!("{}", e)))?)
+            Ok(Self::from_hex_or_base64(Algorithm::Sha256, &string)
+                .map_err(|e| D::Error::custom(format!("{}", e)))?)
         }
     }
 }
@@ -187,7 +231,7 @@ pub mod allow_empty {
 }
 
 /// AppHash is usually a SHA256 hash, but in reality it can be any kind of data
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct AppHash(Vec<u8>);
 
 impl Protobuf<Vec<u8>> for AppHash {}
@@ -213,7 +257,7 @@ impl AppHash {
 
     /// Decode a `Hash` from upper-case hexadecimal
     pub fn from_hex_upper(s: &str) -> Result<Self, Error> {
-        if s.len() % 2 != 0 {
+        if !s.len().is_multiple_of(2) {
             return Err(Error::invalid_app_hash_length());
         }
         let mut h = vec![0; s.len() / 2];
@@ -222,6 +266,26 @@ impl AppHash {
             .map_err(Error::subtle_encoding)?;
         Ok(AppHash(h))
     }
+
+    /// Decode an `AppHash` from hexadecimal, either upper- or lower-case
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        Self::from_hex_upper(&s.to_ascii_uppercase())
+    }
+
+    /// Decode an `AppHash` from base64
+    pub fn from_base64(s: &str) -> Result<Self, Error> {
+        Ok(AppHash(base64::decode(s).map_err(Error::subtle_encoding)?))
+    }
+
+    /// Decode an `AppHash` from upper- or lower-case hexadecimal, or from
+    /// base64, as different versions of Tendermint and tools encode app
+    /// hashes differently
+    ///
+    /// As app hashes can be of any length, strings which are valid in both
+    /// encodings are decoded as hexadecimal.
+    pub fn from_hex_or_base64(s: &str) -> Result<Self, Error> {
+        Self::from_hex(s).or_else(|e| Self::from_base64(s).map_err(|_| e))
+    }
 }
 
 impl AsRef<[u8]> for AppHash {
@@ -246,9 +310,15 @@ impl Display for AppHash {
     }
 }
 
+impl ConstantTimeEq for AppHash {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
 impl PartialEq for AppHash {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        self.ct_eq(other).into()
     }
 }
 
@@ -259,3 +329,43 @@ impl FromStr for AppHash {
         Self::from_hex_upper(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        let hash = Hash::Sha256([1; SHA256_HASH_SIZE]);
+        assert_eq!(hash.ct_eq(&hash).unwrap_u8(), 1);
+        assert_eq!(hash, Hash::Sha256([1; SHA256_HASH_SIZE]));
+        assert_ne!(hash, Hash::Sha256([2; SHA256_HASH_SIZE]));
+        assert_ne!(hash, Hash::None);
+        assert_eq!(Hash::None, Hash::None);
+
+        let app_hash = AppHash::try_from(vec![1, 2, 3]).unwrap();
+        assert_eq!(app_hash.ct_eq(&app_hash).unwrap_u8(), 1);
+        assert_ne!(app_hash, AppHash::try_from(vec![1, 2, 4]).unwrap());
+        assert_ne!(app_hash, AppHash::try_from(vec![1, 2]).unwrap());
+    }
+
+    #[test]
+    fn test_decode_hex_or_base64() {
+        let upper = "9F28904F9C0F3AB74A81CBA48E39124DA1C680B47FBFCBA0126870DB722BCC30";
+        let base64 = "nyiQT5wPOrdKgcukjjkSTaHGgLR/v8ugEmhw23IrzDA=";
+        let hash = Hash::from_str(upper).unwrap();
+        for s in &[upper.to_string(), upper.to_lowercase(), base64.to_string()] {
+            assert_eq!(
+                Hash::from_hex_or_base64(Algorithm::Sha256, s).unwrap(),
+                hash
+            );
+        }
+        assert!(Hash::from_hex_or_base64(Algorithm::Sha256, "AQIDBA==").is_err());
+
+        let app_hash = AppHash::try_from(vec![1, 2, 3, 4]).unwrap();
+        for s in &["01020304", "AQIDBA=="] {
+            assert_eq!(AppHash::from_hex_or_base64(s).unwrap(), app_hash);
+        }
+        assert!(AppHash::from_hex_or_base64("not an app hash").is_err());
+    }
+}
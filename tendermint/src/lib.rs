@@ -25,23 +25,34 @@ pub mod error;
 
 pub mod abci;
 pub mod account;
+#[cfg(feature = "amino")]
+#[cfg_attr(docsrs, doc(cfg(feature = "amino")))]
+pub mod amino_types;
 pub mod block;
+pub mod canonical_json;
 pub mod chain;
 pub mod channel;
+pub mod compat;
 pub mod config;
 pub mod consensus;
 pub mod evidence;
 pub mod genesis;
 pub mod hash;
+pub mod mempool;
 pub mod merkle;
 mod moniker;
 pub mod net;
 pub mod node;
 pub mod private_key;
+pub mod privval;
 pub mod proposal;
 pub mod public_key;
 pub mod serializers;
 pub mod signature;
+pub mod signer;
+#[cfg(feature = "blockstore")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blockstore")))]
+pub mod store;
 pub mod time;
 mod timeout;
 pub mod trust_threshold;
@@ -0,0 +1,162 @@
+//! Transactions of the mempool of a node, along with the metadata by which it
+//! orders them, as returned by the application in `CheckTx` responses.
+
+use crate::{
+    abci::{transaction, Gas, Transaction},
+    block, serializers, Time,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Transaction of a mempool, along with its metadata
+///
+/// The priority and sender are the ones returned by the application when
+/// checking the transaction. Transactions with a higher priority are proposed
+/// first, those with no priority being ordered by arrival, and a sender has at
+/// most one transaction in the mempool when it is not empty.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Tx {
+    /// The transaction
+    pub tx: Transaction,
+    /// Priority of the transaction
+    #[serde(with = "serializers::from_str")]
+    pub priority: i64,
+    /// Sender of the transaction, empty if unknown
+    #[serde(default)]
+    pub sender: String,
+    /// Amount of gas the transaction requested
+    pub gas_wanted: Gas,
+    /// Height at which the transaction was checked
+    pub height: block::Height,
+    /// Time at which the transaction entered the mempool
+    pub timestamp: Time,
+}
+
+impl Tx {
+    /// Transaction with no priority nor sender, checked at the given height
+    /// and time
+    pub fn new(tx: Transaction, gas_wanted: Gas, height: block::Height, timestamp: Time) -> Self {
+        Self {
+            tx,
+            priority: 0,
+            sender: String::new(),
+            gas_wanted,
+            height,
+            timestamp,
+        }
+    }
+
+    /// The hash of the transaction
+    pub fn hash(&self) -> transaction::Hash {
+        self.tx.hash()
+    }
+
+    /// The size of the transaction in the `data` of a block, ie. in the
+    /// Protobuf encoding of the list of its transactions
+    pub fn size(&self) -> usize {
+        self.tx.proto_size()
+    }
+
+    /// Compare the transactions in the order in which they are proposed, ie.
+    /// by decreasing priority, then by time of arrival
+    pub fn cmp_priority(&self, other: &Tx) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.timestamp.cmp(&other.timestamp))
+    }
+}
+
+/// Sort the given transactions in the order in which they are proposed (see
+/// [`Tx::cmp_priority`])
+pub fn sort_by_priority(txs: &mut [Tx]) {
+    txs.sort_by(Tx::cmp_priority);
+}
+
+/// The transactions to propose for a block out of the given ones, in order of
+/// priority, up to a total size and amount of gas wanted, either of which may
+/// be negative for no limit, as done by `ReapMaxBytesMaxGas` in Go
+pub fn reap_max_bytes_max_gas(txs: &[Tx], max_bytes: i64, max_gas: i64) -> Vec<&Tx> {
+    let mut by_priority: Vec<&Tx> = txs.iter().collect();
+    by_priority.sort_by(|a, b| a.cmp_priority(b));
+
+    let mut total_bytes: u64 = 0;
+    let mut total_gas: u64 = 0;
+    let mut reaped = Vec::new();
+    for tx in by_priority {
+        total_bytes += tx.size() as u64;
+        if max_bytes >= 0 && total_bytes > max_bytes as u64 {
+            break;
+        }
+
+        total_gas = total_gas.saturating_add(tx.gas_wanted.value());
+        if max_gas >= 0 && total_gas > max_gas as u64 {
+            break;
+        }
+
+        reaped.push(tx);
+    }
+    reaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(bytes: &[u8], priority: i64, gas_wanted: u64, seconds: i64) -> Tx {
+        Tx {
+            priority,
+            ..Tx::new(
+                Transaction::from(bytes.to_vec()),
+                gas_wanted.into(),
+                1_u32.into(),
+                Time::from_unix_timestamp(seconds, 0).unwrap(),
+            )
+        }
+    }
+
+    #[test]
+    fn order_by_priority_then_arrival() {
+        let mut txs = vec![
+            tx(b"a", 1, 1, 3),
+            tx(b"b", 5, 1, 4),
+            tx(b"c", 1, 1, 1),
+            tx(b"d", 0, 1, 0),
+        ];
+        sort_by_priority(&mut txs);
+
+        let order: Vec<&[u8]> = txs.iter().map(|tx| tx.tx.as_bytes()).collect();
+        assert_eq!(order, vec![&b"b"[..], b"c", b"a", b"d"]);
+    }
+
+    #[test]
+    fn reap_within_limits() {
+        let txs = vec![
+            tx(&[1; 10], 3, 10, 0),
+            tx(&[2; 10], 2, 20, 0),
+            tx(&[3; 10], 1, 30, 0),
+        ];
+        // Each transaction takes 12 bytes in the data of a block
+        assert_eq!(txs[0].size(), 12);
+
+        assert_eq!(reap_max_bytes_max_gas(&txs, -1, -1).len(), 3);
+        assert_eq!(reap_max_bytes_max_gas(&txs, 24, -1).len(), 2);
+        assert_eq!(reap_max_bytes_max_gas(&txs, 23, -1).len(), 1);
+        assert_eq!(reap_max_bytes_max_gas(&txs, -1, 30).len(), 2);
+        assert_eq!(reap_max_bytes_max_gas(&txs, -1, 0).len(), 0);
+
+        let reaped = reap_max_bytes_max_gas(&txs, 36, 60);
+        assert_eq!(reaped, txs.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let tx = Tx {
+            sender: "alice".to_string(),
+            ..tx(b"key=value", -2, 10, 0)
+        };
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["priority"], "-2");
+        assert_eq!(serde_json::from_value::<Tx>(json).unwrap(), tx);
+    }
+}
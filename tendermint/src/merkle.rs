@@ -2,7 +2,8 @@
 
 pub mod proof;
 
-use sha2::{Digest, Sha256};
+use crate::hash::HashFunction;
+use sha2::Sha256;
 
 /// Size of Merkle root hash
 pub const HASH_SIZE: usize = 32;
@@ -14,20 +15,26 @@ pub type Hash = [u8; HASH_SIZE];
 /// The leaves of the tree are the bytes of the given byte vectors in
 /// the given order.
 pub fn simple_hash_from_byte_vectors(byte_vecs: Vec<Vec<u8>>) -> Hash {
-    simple_hash_from_byte_slices_inner(byte_vecs.as_slice())
+    simple_hash_from_byte_vectors_with::<Sha256>(byte_vecs)
+}
+
+/// Compute a simple Merkle root from vectors of arbitrary byte vectors, with
+/// the given hash function instead of SHA-256.
+pub fn simple_hash_from_byte_vectors_with<H: HashFunction>(byte_vecs: Vec<Vec<u8>>) -> Hash {
+    simple_hash_from_byte_slices_inner::<H>(byte_vecs.as_slice())
 }
 
 // recurse into subtrees
-fn simple_hash_from_byte_slices_inner(byte_slices: &[Vec<u8>]) -> Hash {
+fn simple_hash_from_byte_slices_inner<H: HashFunction>(byte_slices: &[Vec<u8>]) -> Hash {
     let length = byte_slices.len();
     match length {
-        0 => empty_hash(),
-        1 => leaf_hash(byte_slices[0].as_slice()),
+        0 => empty_hash::<H>(),
+        1 => leaf_hash::<H>(byte_slices[0].as_slice()),
         _ => {
             let k = get_split_point(length);
-            let left = simple_hash_from_byte_slices_inner(&byte_slices[..k]);
-            let right = simple_hash_from_byte_slices_inner(&byte_slices[k..]);
-            inner_hash(&left, &right)
+            let left = simple_hash_from_byte_slices_inner::<H>(&byte_slices[..k]);
+            let right = simple_hash_from_byte_slices_inner::<H>(&byte_slices[k..]);
+            inner_hash::<H>(&left, &right)
         }
     }
 }
@@ -43,37 +50,27 @@ fn get_split_point(length: usize) -> usize {
 }
 
 // tmhash({})
-fn empty_hash() -> Hash {
+fn empty_hash<H: HashFunction>() -> Hash {
     // the empty string / byte slice
     let empty = Vec::with_capacity(0);
 
     // hash it !
-    let digest = Sha256::digest(&empty);
-
-    // copy the GenericArray out
-    let mut hash_bytes = [0u8; HASH_SIZE];
-    hash_bytes.copy_from_slice(&digest);
-    hash_bytes
+    H::digest(&empty).into()
 }
 
 // tmhash(0x00 || leaf)
-fn leaf_hash(bytes: &[u8]) -> Hash {
+fn leaf_hash<H: HashFunction>(bytes: &[u8]) -> Hash {
     // make a new array starting with 0 and copy in the bytes
     let mut leaf_bytes = Vec::with_capacity(bytes.len() + 1);
     leaf_bytes.push(0x00);
     leaf_bytes.extend_from_slice(bytes);
 
     // hash it !
-    let digest = Sha256::digest(&leaf_bytes);
-
-    // copy the GenericArray out
-    let mut hash_bytes = [0u8; HASH_SIZE];
-    hash_bytes.copy_from_slice(&digest);
-    hash_bytes
+    H::digest(&leaf_bytes).into()
 }
 
 // tmhash(0x01 || left || right)
-fn inner_hash(left: &[u8], right: &[u8]) -> Hash {
+fn inner_hash<H: HashFunction>(left: &[u8], right: &[u8]) -> Hash {
     // make a new array starting with 0x1 and copy in the bytes
     let mut inner_bytes = Vec::with_capacity(left.len() + right.len() + 1);
     inner_bytes.push(0x01);
@@ -81,12 +78,7 @@ fn inner_hash(left: &[u8], right: &[u8]) -> Hash {
     inner_bytes.extend_from_slice(right);
 
     // hash it !
-    let digest = Sha256::digest(&inner_bytes);
-
-    // copy the GenericArray out
-    let mut hash_bytes = [0u8; HASH_SIZE];
-    hash_bytes.copy_from_slice(&digest);
-    hash_bytes
+    H::digest(&inner_bytes).into()
 }
 
 #[cfg(test)]
@@ -113,7 +105,7 @@ mod tests {
         let empty_tree_root_hex =
             "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
         let empty_tree_root = &hex::decode(empty_tree_root_hex).unwrap();
-        let empty_tree: Vec<Vec<u8>> = vec![vec![]; 0];
+        let empty_tree: Vec<Vec<u8>> = vec![];
 
         let root = simple_hash_from_byte_vectors(empty_tree);
         assert_eq!(empty_tree_root, &root);
@@ -149,7 +141,23 @@ mod tests {
         let right_string = "N456";
 
         let node_hash = &hex::decode(node_hash_hex).unwrap();
-        let hash = inner_hash(left_string.as_bytes(), right_string.as_bytes());
+        let hash = inner_hash::<Sha256>(left_string.as_bytes(), right_string.as_bytes());
         assert_eq!(node_hash, &hash);
     }
+
+    #[test]
+    fn test_other_hash_function() {
+        use sha2::Sha512Trunc256;
+
+        let leaves: Vec<Vec<u8>> = (0_u8..5).map(|i| vec![i; 3]).collect();
+        let root = simple_hash_from_byte_vectors_with::<Sha512Trunc256>(leaves.clone());
+        assert_ne!(root, simple_hash_from_byte_vectors(leaves.clone()));
+
+        let proof =
+            proof::SimpleProof::from_byte_vectors_with::<Sha512Trunc256>(&leaves, 3).unwrap();
+        assert!(proof
+            .verify_with::<Sha512Trunc256>(&root, &leaves[3])
+            .is_ok());
+        assert!(proof.verify(&root, &leaves[3]).is_err());
+    }
 }
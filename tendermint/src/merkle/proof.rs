@@ -1,13 +1,18 @@
 //! Merkle proofs
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 
+use tendermint_proto::crypto::Proof as RawSimpleProof;
 use tendermint_proto::crypto::ProofOp as RawProofOp;
 use tendermint_proto::crypto::ProofOps as RawProofOps;
+use tendermint_proto::crypto::ValueOp as RawValueOp;
 use tendermint_proto::Protobuf;
 
+use super::{get_split_point, inner_hash, leaf_hash, Hash, HASH_SIZE};
+use crate::hash::HashFunction;
 use crate::serializers;
 use crate::Error;
+use sha2::{Digest, Sha256};
 
 /// Proof is Merkle proof defined by the list of ProofOps
 /// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/merkle.proto#L26>
@@ -78,10 +83,315 @@ impl From<Proof> for RawProofOps {
     }
 }
 
+/// Type of the [`ProofOp`]s holding a [`ValueOp`]
+pub const PROOF_OP_VALUE: &str = "simple:v";
+
+impl Proof {
+    /// Decode the operations of this proof according to their types
+    pub fn decode_ops(&self) -> Result<Vec<DecodedProofOp>, Error> {
+        self.ops.iter().map(ProofOp::decode).collect()
+    }
+}
+
+impl ProofOp {
+    /// Decode this operation according to its type
+    pub fn decode(&self) -> Result<DecodedProofOp, Error> {
+        match self.field_type.as_str() {
+            PROOF_OP_VALUE => {
+                let op = ValueOp::decode_vec(&self.data)
+                    .map_err(|e| Error::invalid_merkle_proof(e.to_string()))?;
+                // As in Go, the key of the operation prevails over the one of
+                // its data
+                Ok(DecodedProofOp::Value(ValueOp {
+                    key: self.key.clone(),
+                    ..op
+                }))
+            }
+            _ => Ok(DecodedProofOp::Unknown(self.clone())),
+        }
+    }
+}
+
+/// Operation of a [`Proof`], decoded according to its type
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodedProofOp {
+    /// Proof of the value of a key, of type [`PROOF_OP_VALUE`]
+    Value(ValueOp),
+
+    /// Operation of a type unknown to Tendermint, eg. the `iavl:v` and
+    /// `multistore` operations of Cosmos SDK applications, to be decoded by
+    /// the application
+    Unknown(ProofOp),
+}
+
+/// Proof of the value of a key in a simple Merkle tree of key-value pairs,
+/// as `ValueOp` in Go.
+///
+/// The leaves of the tree are the length-prefixed keys followed by the
+/// length-prefixed SHA-256 hashes of their values.
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/crypto/merkle/proof_value.go>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValueOp {
+    /// The key of the value
+    pub key: Vec<u8>,
+    /// The proof of the inclusion of the key-value pair in the tree
+    pub proof: SimpleProof,
+}
+
+impl ValueOp {
+    /// Compute the root hash of the tree from the given value of the key of
+    /// this operation, failing if the proof is not about this value.
+    pub fn run(&self, value: &[u8]) -> Result<Hash, Error> {
+        if self.proof.leaf_hash != leaf_hash::<Sha256>(&key_value_bytes(&self.key, value)) {
+            return Err(Error::invalid_merkle_proof(
+                "leaf hash does not match the hash of the key and value".to_string(),
+            ));
+        }
+
+        self.proof.compute_root_hash().ok_or_else(|| {
+            Error::invalid_merkle_proof(format!(
+                "{} aunts are inconsistent with leaf {} out of {}",
+                self.proof.aunts.len(),
+                self.proof.index,
+                self.proof.total
+            ))
+        })
+    }
+}
+
+// The leaf of the given key-value pair in the tree of a `ValueOp`, ie. the
+// key and the hash of the value, each prefixed with its length as a varint.
+fn key_value_bytes(key: &[u8], value: &[u8]) -> Vec<u8> {
+    let value_hash = Sha256::digest(value);
+    let mut bytes = Vec::new();
+    prost::encoding::encode_varint(key.len() as u64, &mut bytes);
+    bytes.extend_from_slice(key);
+    prost::encoding::encode_varint(value_hash.len() as u64, &mut bytes);
+    bytes.extend_from_slice(&value_hash);
+    bytes
+}
+
+impl Protobuf<RawValueOp> for ValueOp {}
+
+impl TryFrom<RawValueOp> for ValueOp {
+    type Error = Error;
+
+    fn try_from(value: RawValueOp) -> Result<Self, Self::Error> {
+        Ok(Self {
+            key: value.key,
+            proof: value.proof.ok_or_else(Error::missing_proof)?.try_into()?,
+        })
+    }
+}
+
+impl From<ValueOp> for RawValueOp {
+    fn from(value: ValueOp) -> Self {
+        RawValueOp {
+            key: value.key,
+            proof: Some(value.proof.into()),
+        }
+    }
+}
+
+impl From<ValueOp> for ProofOp {
+    fn from(value: ValueOp) -> Self {
+        Self {
+            field_type: PROOF_OP_VALUE.to_string(),
+            key: value.key.clone(),
+            data: value.encode_vec().expect("vectors have enough capacity"),
+        }
+    }
+}
+
+/// Proof of the inclusion of a leaf in a simple Merkle tree, as computed by
+/// [`simple_hash_from_byte_vectors`](super::simple_hash_from_byte_vectors).
+///
+/// This is the proof type of Tendermint's `crypto/merkle` package, used eg. in
+/// transaction inclusion proofs.
+/// <https://github.com/tendermint/tendermint/blob/c8483531d8e756f7fbb812db1dd16d841cdf298a/proto/tendermint/crypto/proof.proto#L9>
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawSimpleProof", into = "RawSimpleProof")]
+pub struct SimpleProof {
+    /// The number of leaves in the tree
+    pub total: u64,
+    /// The index of the leaf in the tree
+    pub index: u64,
+    /// The hash of the leaf
+    pub leaf_hash: Hash,
+    /// The hashes of the siblings of the nodes on the path from the leaf to
+    /// the root, starting from the sibling of the leaf
+    pub aunts: Vec<Hash>,
+}
+
+impl SimpleProof {
+    /// Create the proof of the inclusion of the leaf with the given index in
+    /// the simple Merkle tree of the given byte vectors.
+    ///
+    /// Returns `None` if there is no leaf with the given index.
+    pub fn from_byte_vectors(byte_vecs: &[Vec<u8>], index: usize) -> Option<Self> {
+        Self::from_byte_vectors_with::<Sha256>(byte_vecs, index)
+    }
+
+    /// Create the proof of the inclusion of the leaf with the given index in
+    /// the simple Merkle tree of the given byte vectors, hashed with the given
+    /// hash function instead of SHA-256.
+    pub fn from_byte_vectors_with<H: HashFunction>(
+        byte_vecs: &[Vec<u8>],
+        index: usize,
+    ) -> Option<Self> {
+        if index >= byte_vecs.len() {
+            return None;
+        }
+
+        let leaf_hash = leaf_hash::<H>(&byte_vecs[index]);
+        let (_, aunts) = root_hash_and_aunts::<H>(byte_vecs, index);
+
+        Some(Self {
+            total: byte_vecs.len() as u64,
+            index: index as u64,
+            leaf_hash,
+            aunts,
+        })
+    }
+
+    /// Compute the root hash of the tree from the leaf hash and the aunts.
+    ///
+    /// Returns `None` if the index, total and number of aunts are inconsistent.
+    pub fn compute_root_hash(&self) -> Option<Hash> {
+        self.compute_root_hash_with::<Sha256>()
+    }
+
+    /// Compute the root hash of the tree from the leaf hash and the aunts, with
+    /// the given hash function instead of SHA-256.
+    pub fn compute_root_hash_with<H: HashFunction>(&self) -> Option<Hash> {
+        compute_hash_from_aunts::<H>(self.index, self.total, self.leaf_hash, &self.aunts)
+    }
+
+    /// Verify that the given leaf is included in the tree with the given root hash.
+    pub fn verify(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error> {
+        self.verify_with::<Sha256>(root_hash, leaf)
+    }
+
+    /// Verify that the given leaf is included in the tree with the given root
+    /// hash, hashed with the given hash function instead of SHA-256.
+    pub fn verify_with<H: HashFunction>(&self, root_hash: &Hash, leaf: &[u8]) -> Result<(), Error> {
+        if self.leaf_hash != leaf_hash::<H>(leaf) {
+            return Err(Error::invalid_merkle_proof(
+                "leaf hash does not match the hash of the leaf".to_string(),
+            ));
+        }
+
+        match self.compute_root_hash_with::<H>() {
+            Some(computed) if &computed == root_hash => Ok(()),
+            Some(_) => Err(Error::invalid_merkle_proof(
+                "computed root hash does not match the root hash".to_string(),
+            )),
+            None => Err(Error::invalid_merkle_proof(format!(
+                "{} aunts are inconsistent with leaf {} out of {}",
+                self.aunts.len(),
+                self.index,
+                self.total
+            ))),
+        }
+    }
+}
+
+// Compute the root hash of the given byte vectors, along with the aunts of the
+// leaf with the given index, ordered from the leaf up to the root.
+fn root_hash_and_aunts<H: HashFunction>(byte_vecs: &[Vec<u8>], index: usize) -> (Hash, Vec<Hash>) {
+    if byte_vecs.len() == 1 {
+        return (leaf_hash::<H>(&byte_vecs[0]), Vec::new());
+    }
+
+    let k = get_split_point(byte_vecs.len());
+    if index < k {
+        let (left, mut aunts) = root_hash_and_aunts::<H>(&byte_vecs[..k], index);
+        let right = super::simple_hash_from_byte_slices_inner::<H>(&byte_vecs[k..]);
+        aunts.push(right);
+        (inner_hash::<H>(&left, &right), aunts)
+    } else {
+        let left = super::simple_hash_from_byte_slices_inner::<H>(&byte_vecs[..k]);
+        let (right, mut aunts) = root_hash_and_aunts::<H>(&byte_vecs[k..], index - k);
+        aunts.push(left);
+        (inner_hash::<H>(&left, &right), aunts)
+    }
+}
+
+// Compute the root hash of a tree from the hash of a leaf and its aunts,
+// as done by `computeHashFromAunts` in Go.
+fn compute_hash_from_aunts<H: HashFunction>(
+    index: u64,
+    total: u64,
+    leaf_hash: Hash,
+    aunts: &[Hash],
+) -> Option<Hash> {
+    if index >= total || total == 0 {
+        return None;
+    }
+
+    if total == 1 {
+        return if aunts.is_empty() {
+            Some(leaf_hash)
+        } else {
+            None
+        };
+    }
+
+    let (last, rest) = aunts.split_last()?;
+    let num_left = get_split_point(total as usize) as u64;
+    if index < num_left {
+        let left = compute_hash_from_aunts::<H>(index, num_left, leaf_hash, rest)?;
+        Some(inner_hash::<H>(&left, last))
+    } else {
+        let right =
+            compute_hash_from_aunts::<H>(index - num_left, total - num_left, leaf_hash, rest)?;
+        Some(inner_hash::<H>(last, &right))
+    }
+}
+
+impl Protobuf<RawSimpleProof> for SimpleProof {}
+
+impl TryFrom<RawSimpleProof> for SimpleProof {
+    type Error = Error;
+
+    fn try_from(value: RawSimpleProof) -> Result<Self, Self::Error> {
+        let to_hash = |bytes: Vec<u8>| -> Result<Hash, Error> {
+            let len = bytes.len();
+            bytes
+                .try_into()
+                .map_err(|_| Error::invalid_hash_size(HASH_SIZE, len))
+        };
+
+        Ok(Self {
+            total: value.total.try_into().map_err(Error::integer_overflow)?,
+            index: value.index.try_into().map_err(Error::integer_overflow)?,
+            leaf_hash: to_hash(value.leaf_hash)?,
+            aunts: value
+                .aunts
+                .into_iter()
+                .map(to_hash)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<SimpleProof> for RawSimpleProof {
+    fn from(value: SimpleProof) -> Self {
+        RawSimpleProof {
+            total: value.total as i64,
+            index: value.index as i64,
+            leaf_hash: value.leaf_hash.to_vec(),
+            aunts: value.aunts.iter().map(|aunt| aunt.to_vec()).collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Proof;
+    use super::{key_value_bytes, DecodedProofOp, Proof, ProofOp, SimpleProof, ValueOp};
+    use crate::merkle::simple_hash_from_byte_vectors;
     use crate::test::test_serialization_roundtrip;
+    use subtle_encoding::hex;
 
     #[test]
     fn serialization_roundtrip() {
@@ -102,4 +412,98 @@ mod test {
         }"#;
         test_serialization_roundtrip::<Proof>(payload);
     }
+
+    #[test]
+    fn value_ops() {
+        let pairs: Vec<(&[u8], &[u8])> = vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+        let leaves: Vec<Vec<u8>> = pairs
+            .iter()
+            .map(|(key, value)| key_value_bytes(key, value))
+            .collect();
+        let root_hash = simple_hash_from_byte_vectors(leaves.clone());
+
+        let op = ValueOp {
+            key: b"b".to_vec(),
+            proof: SimpleProof::from_byte_vectors(&leaves, 1).unwrap(),
+        };
+        assert_eq!(op.run(b"2").unwrap(), root_hash);
+        assert!(op.run(b"3").is_err());
+
+        let proof = Proof {
+            ops: vec![
+                op.clone().into(),
+                ProofOp {
+                    field_type: "multistore".to_string(),
+                    key: b"store".to_vec(),
+                    data: vec![1, 2, 3],
+                },
+            ],
+        };
+        let decoded = proof.decode_ops().unwrap();
+        assert_eq!(decoded[0], DecodedProofOp::Value(op));
+        assert_eq!(decoded[1], DecodedProofOp::Unknown(proof.ops[1].clone()));
+
+        let invalid = ProofOp {
+            field_type: super::PROOF_OP_VALUE.to_string(),
+            key: b"b".to_vec(),
+            data: vec![0xff],
+        };
+        assert!(invalid.decode().is_err());
+    }
+
+    #[test]
+    fn simple_proofs_verify_every_leaf() {
+        for total in 1..=10 {
+            let leaves: Vec<Vec<u8>> = (0..total).map(|i| vec![i as u8; i + 1]).collect();
+            let root_hash = simple_hash_from_byte_vectors(leaves.clone());
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = SimpleProof::from_byte_vectors(&leaves, index).unwrap();
+                assert_eq!(proof.compute_root_hash(), Some(root_hash));
+                proof.verify(&root_hash, leaf).unwrap();
+                assert!(proof.verify(&root_hash, b"another leaf").is_err());
+            }
+
+            assert!(SimpleProof::from_byte_vectors(&leaves, total).is_none());
+        }
+    }
+
+    #[test]
+    fn simple_proof_from_go_node() {
+        // The proof of inclusion of the first transaction of a block with three
+        // transactions, as returned by the `tx_search` RPC endpoint of a kvstore
+        // node. The leaves of the tree are the hashes of the transactions.
+        let payload = r#"
+        {
+            "aunts": [
+                "oL+OYRo6LtD+lKo0W5A2kcPlbt4Of3c/VN57Ag54iEk=",
+                "wq4Wy/oF+/0xsH+eJq1SqY2BgYS2FVXbLAXNcCLkB74="
+            ],
+            "index": "0",
+            "leaf_hash": "MIH5kVBA0TizrX+JVzLSdnwp6Ful2EOI0E4XpdgmK3o=",
+            "total": "3"
+        }"#;
+        let proof: SimpleProof = serde_json::from_str(payload).unwrap();
+        test_serialization_roundtrip::<SimpleProof>(payload);
+
+        let mut root_hash = [0; 32];
+        root_hash.copy_from_slice(
+            &hex::decode_upper("F54643B0051065C87DA31A654531B65F9B57380F9BF3332FF5BCA7584567268C")
+                .unwrap(),
+        );
+        let tx_hash =
+            hex::decode_upper("9F28904F9C0F3AB74A81CBA48E39124DA1C680B47FBFCBA0126870DB722BCC30")
+                .unwrap();
+
+        proof.verify(&root_hash, &tx_hash).unwrap();
+
+        let leaves = vec![
+            tx_hash,
+            hex::decode_upper("57018296EE0919C9D351F2FFEA82A8D28DE223724D79965FC8D00A7477ED48BC")
+                .unwrap(),
+            hex::decode_upper("D63F9C23791E610410B576D8C27BB5AEAC93CC1A58522428A7B32A1276085860")
+                .unwrap(),
+        ];
+        assert_eq!(SimpleProof::from_byte_vectors(&leaves, 0), Some(proof));
+    }
 }
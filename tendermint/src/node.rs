@@ -2,6 +2,8 @@
 
 mod id;
 pub mod info;
+mod key;
 
 pub use self::id::Id;
 pub use self::info::Info;
+pub use self::key::Key;
@@ -12,6 +12,7 @@ use subtle::{self, ConstantTimeEq};
 use subtle_encoding::hex;
 
 use crate::{
+    account,
     error::Error,
     public_key::{Ed25519, PublicKey},
 };
@@ -20,7 +21,7 @@ use crate::{
 pub const LENGTH: usize = 20;
 
 /// Node IDs
-#[allow(clippy::derive_hash_xor_eq)]
+#[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Copy, Clone, Eq, Hash, PartialOrd, Ord)]
 pub struct Id([u8; LENGTH]);
 
@@ -98,15 +99,23 @@ impl PartialEq for Id {
     }
 }
 
+/// Node ID of the given address, ie. of the public key it was derived from,
+/// as Tendermint Core derives it
+impl From<account::Id> for Id {
+    fn from(address: account::Id) -> Id {
+        let mut bytes = [0; LENGTH];
+        bytes.copy_from_slice(address.as_bytes());
+        Id(bytes)
+    }
+}
+
+/// Node ID of the given public key, derived as Tendermint Core does from its
+/// address, see [`account::Id`]
 impl TryFrom<PublicKey> for Id {
     type Error = Error;
 
     fn try_from(pk: PublicKey) -> Result<Self, Self::Error> {
-        match pk {
-            PublicKey::Ed25519(ed25519) => Ok(Id::from(ed25519)),
-            #[cfg(feature = "secp256k1")]
-            _ => Err(Error::unsupported_key_type()),
-        }
+        Ok(account::Id::from(pk).into())
     }
 }
 
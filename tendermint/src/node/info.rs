@@ -86,10 +86,11 @@ pub struct OtherInfo {
 }
 
 /// Transaction index status
-#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Default)]
 pub enum TxIndexStatus {
     /// Index is on
     #[serde(rename = "on")]
+    #[default]
     On,
 
     /// Index is off
@@ -97,12 +98,6 @@ pub enum TxIndexStatus {
     Off,
 }
 
-impl Default for TxIndexStatus {
-    fn default() -> TxIndexStatus {
-        TxIndexStatus::On
-    }
-}
-
 impl From<TxIndexStatus> for bool {
     fn from(status: TxIndexStatus) -> bool {
         match status {
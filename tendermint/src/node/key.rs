@@ -0,0 +1,132 @@
+//! Node keys
+
+use crate::{account, config, error::Error, node, private_key::PrivateKey, public_key::PublicKey};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use zeroize::Zeroizing;
+
+/// P2P node private keys, as found in `node_key.json`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Key {
+    /// Private key
+    pub priv_key: PrivateKey,
+}
+
+impl Key {
+    /// Generate a new random Ed25519 node key
+    pub fn generate() -> Self {
+        Self {
+            priv_key: PrivateKey::Ed25519(ed25519_dalek::Keypair::generate(&mut OsRng)),
+        }
+    }
+
+    /// Recover the Ed25519 node key of the given mnemonic and passphrase
+    /// (possibly empty), see [`Seed::ed25519_keypair`](crate::private_key::bip39::Seed::ed25519_keypair)
+    #[cfg(feature = "bip39")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bip39")))]
+    pub fn from_mnemonic(mnemonic: &crate::private_key::bip39::Mnemonic, passphrase: &str) -> Self {
+        Self {
+            priv_key: PrivateKey::Ed25519(mnemonic.to_seed(passphrase).ed25519_keypair()),
+        }
+    }
+
+    /// Parse `node_key.json`
+    pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
+        let res: Self = serde_json::from_str(json_string.as_ref()).map_err(Error::serde_json)?;
+
+        // Node IDs are only defined for Ed25519 keys
+        if res.priv_key.ed25519_keypair().is_none() {
+            return Err(Error::unsupported_key_type());
+        }
+
+        Ok(res)
+    }
+
+    /// Load `node_key.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = Zeroizing::new(
+            fs::read_to_string(path)
+                .map_err(|e| Error::file_io(format!("{}", path.as_ref().display()), e))?,
+        );
+
+        Self::parse_json(&*json_string)
+    }
+
+    /// Serialize this key in the `node_key.json` format
+    pub fn to_json(&self) -> Result<Zeroizing<String>, Error> {
+        serde_json::to_string(self)
+            .map(Zeroizing::new)
+            .map_err(Error::serde_json)
+    }
+
+    /// Atomically save this key as `node_key.json` to a file, readable by its
+    /// owner only on Unix platforms, as Tendermint does
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        config::write_private_file(path.as_ref(), self.to_json()?.as_bytes())
+    }
+
+    /// Get the public key for this keypair
+    pub fn public_key(&self) -> PublicKey {
+        self.priv_key.public_key()
+    }
+
+    /// Get node ID for this keypair, derived as Tendermint Core does from the
+    /// address of its public key
+    pub fn node_id(&self) -> node::Id {
+        account::Id::from(self.public_key()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_key_round_trips() {
+        let key = Key::generate();
+        let parsed = Key::parse_json(&*key.to_json().unwrap()).unwrap();
+
+        assert_eq!(parsed.public_key(), key.public_key());
+        assert_eq!(parsed.node_id(), key.node_id());
+        assert_ne!(Key::generate().node_id(), key.node_id());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn secp256k1_node_id_is_its_address() {
+        let key = Key {
+            priv_key: PrivateKey::Secp256k1(
+                crate::private_key::Secp256k1::from_bytes(&[1; 32]).unwrap(),
+            ),
+        };
+
+        assert_eq!(
+            key.node_id().as_bytes(),
+            account::Id::from(key.public_key()).as_bytes()
+        );
+    }
+
+    #[test]
+    fn save_and_load_json_file() {
+        let path = std::env::temp_dir().join(format!(
+            "tendermint-node-key-{}.json",
+            Key::generate().node_id()
+        ));
+
+        Key::generate().save_json_file(&path).unwrap();
+        // saving a key replaces the existing file
+        let key = Key::generate();
+        key.save_json_file(&path).unwrap();
+        let loaded = Key::load_json_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap().node_id(), key.node_id());
+    }
+}
@@ -1,13 +1,27 @@
 //! Cryptographic private keys
 
 pub use ed25519_dalek::{Keypair as Ed25519, EXPANDED_SECRET_KEY_LENGTH as ED25519_KEYPAIR_SIZE};
+#[cfg(feature = "secp256k1")]
+pub use k256::ecdsa::SigningKey as Secp256k1;
+
+#[cfg(feature = "bip32")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bip32")))]
+pub mod bip32;
+#[cfg(feature = "bip39")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bip39")))]
+pub mod bip39;
 
 use crate::public_key::PublicKey;
+use crate::signature::{Signature, Signer as _};
 use serde::{de, ser, Deserialize, Serialize};
+use std::fmt;
 use subtle_encoding::{Base64, Encoding};
 use zeroize::Zeroizing;
 
 /// Private keys as parsed from configuration files
+///
+/// Private keys are zeroized when dropped, and their `Debug` output only
+/// shows their public key.
 #[derive(Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(tag = "type", content = "value")] // JSON custom serialization for priv_validator_key.json
@@ -19,6 +33,19 @@ pub enum PrivateKey {
         deserialize_with = "deserialize_ed25519_keypair"
     )]
     Ed25519(Ed25519),
+
+    /// Secp256k1 keys
+    ///
+    /// Note that, unlike Ed25519 keys, they are not zeroized when dropped,
+    /// as `k256` does not support it yet.
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
+    #[serde(
+        rename = "tendermint/PrivKeySecp256k1",
+        serialize_with = "serialize_secp256k1_key",
+        deserialize_with = "deserialize_secp256k1_key"
+    )]
+    Secp256k1(Secp256k1),
 }
 
 impl PrivateKey {
@@ -26,15 +53,49 @@ impl PrivateKey {
     pub fn public_key(&self) -> PublicKey {
         match self {
             PrivateKey::Ed25519(private_key) => private_key.public.into(),
+            #[cfg(feature = "secp256k1")]
+            PrivateKey::Secp256k1(signing_key) => signing_key.verifying_key().into(),
         }
     }
 
     /// If applicable, borrow the Ed25519 keypair
     pub fn ed25519_keypair(&self) -> Option<&Ed25519> {
+        #[allow(unreachable_patterns)]
         match self {
             PrivateKey::Ed25519(keypair) => Some(keypair),
+            _ => None,
         }
     }
+
+    /// If applicable, borrow the secp256k1 signing key
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
+    pub fn secp256k1_signing_key(&self) -> Option<&Secp256k1> {
+        match self {
+            PrivateKey::Secp256k1(signing_key) => Some(signing_key),
+            _ => None,
+        }
+    }
+
+    /// Sign the given message with this private key.
+    ///
+    /// Secp256k1 signatures are made over the SHA-256 digest of the message,
+    /// and normalized to their lower-S form, as expected by Tendermint.
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        match self {
+            PrivateKey::Ed25519(keypair) => Signature::Ed25519(keypair.sign(msg)),
+            #[cfg(feature = "secp256k1")]
+            PrivateKey::Secp256k1(signing_key) => Signature::Secp256k1(signing_key.sign(msg)),
+        }
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
 }
 
 /// Serialize an Ed25519 keypair as Base64
@@ -65,3 +126,108 @@ where
 
     Ed25519::from_bytes(&*keypair_bytes).map_err(D::Error::custom)
 }
+
+/// Serialize a secp256k1 signing key as Base64
+#[cfg(feature = "secp256k1")]
+fn serialize_secp256k1_key<S>(signing_key: &Secp256k1, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    let key_bytes = Zeroizing::new(signing_key.to_bytes().to_vec());
+    Zeroizing::new(String::from_utf8(Base64::default().encode(&key_bytes[..])).unwrap())
+        .serialize(serializer)
+}
+
+/// Deserialize a secp256k1 signing key from Base64
+#[cfg(feature = "secp256k1")]
+fn deserialize_secp256k1_key<'de, D>(deserializer: D) -> Result<Secp256k1, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    use de::Error;
+    let string = Zeroizing::new(String::deserialize(deserializer)?);
+    let key_bytes = Zeroizing::new(
+        Base64::default()
+            .decode(string.as_bytes())
+            .map_err(D::Error::custom)?,
+    );
+
+    Secp256k1::from_bytes(&key_bytes).map_err(|_| D::Error::custom("invalid secp256k1 key"))
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod tests {
+    use super::*;
+    use crate::validator;
+    use std::convert::TryFrom;
+    use tendermint_proto::Protobuf;
+
+    const MSG: &[u8] = b"canonical vote sign bytes";
+
+    fn secp256k1_key() -> PrivateKey {
+        PrivateKey::Secp256k1(Secp256k1::from_bytes(&[1; 32]).unwrap())
+    }
+
+    #[test]
+    fn secp256k1_sign_and_verify() {
+        let private_key = secp256k1_key();
+        let public_key = private_key.public_key();
+
+        let signature = private_key.sign(MSG);
+        assert!(matches!(signature, Signature::Secp256k1(_)));
+        public_key.verify(MSG, &signature).unwrap();
+        assert!(public_key.verify(b"another message", &signature).is_err());
+
+        // Signatures decoded from their raw bytes can be verified as well.
+        let decoded = Signature::try_from(signature.to_bytes()).unwrap();
+        public_key.verify(MSG, &decoded).unwrap();
+
+        // An Ed25519 key does not accept ECDSA signatures.
+        let ed25519_key = PrivateKey::Ed25519(crate::test::keypair(1));
+        assert!(ed25519_key.public_key().verify(MSG, &signature).is_err());
+        assert!(ed25519_key
+            .public_key()
+            .verify(MSG, &ed25519_key.sign(MSG))
+            .is_ok());
+    }
+
+    #[test]
+    fn secp256k1_json_roundtrip() {
+        let private_key = secp256k1_key();
+
+        let json = serde_json::to_string(&private_key).unwrap();
+        assert!(json.starts_with("{\"type\":\"tendermint/PrivKeySecp256k1\""));
+
+        let parsed: PrivateKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.public_key(), private_key.public_key());
+
+        let public_key = private_key.public_key();
+        let json = serde_json::to_string(&public_key).unwrap();
+        assert!(json.starts_with("{\"type\":\"tendermint/PubKeySecp256k1\""));
+        assert_eq!(
+            serde_json::from_str::<PublicKey>(&json).unwrap(),
+            public_key
+        );
+    }
+
+    #[test]
+    fn secp256k1_proto_roundtrip() {
+        let public_key = secp256k1_key().public_key();
+
+        let encoded = public_key.encode_vec().unwrap();
+        assert_eq!(PublicKey::decode_vec(&encoded).unwrap(), public_key);
+    }
+
+    #[test]
+    fn secp256k1_validator_update() {
+        let public_key = secp256k1_key().public_key();
+
+        let json = format!(
+            "{{\"pub_key\":{{\"type\":\"secp256k1\",\"data\":\"{}\"}},\"power\":\"10\"}}",
+            String::from_utf8(Base64::default().encode(public_key.to_bytes())).unwrap()
+        );
+
+        let update: validator::Update = serde_json::from_str(&json).unwrap();
+        assert_eq!(update.pub_key, public_key);
+    }
+}
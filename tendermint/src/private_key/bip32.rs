@@ -0,0 +1,382 @@
+//! Hierarchical deterministic derivation of secp256k1 keys, as specified by
+//! [BIP32], along [BIP44] paths such as the one of Cosmos accounts.
+//!
+//! [BIP32]: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! [BIP44]: https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+
+use super::{PrivateKey, Secp256k1};
+use crate::error::Error;
+use crate::public_key::PublicKey;
+use hmac::{Hmac, Mac, NewMac};
+use k256::elliptic_curve::group::ff::PrimeField;
+use k256::Scalar;
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256, Sha512};
+use std::{convert::TryInto, fmt, str::FromStr};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Coin type of Cosmos accounts, as registered in [SLIP-0044].
+///
+/// [SLIP-0044]: https://github.com/satoshilabs/slips/blob/master/slip-0044.md
+pub const COSMOS_COIN_TYPE: u32 = 118;
+
+/// Derivation path of the first Cosmos account key
+pub const COSMOS_PATH: &str = "m/44'/118'/0'/0/0";
+
+/// Index from which child numbers are hardened
+const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// Key of the HMAC computing master keys from seeds
+const MASTER_KEY_HMAC_KEY: &[u8] = b"Bitcoin seed";
+
+/// Index of a child key, either hardened or not
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    /// Non-hardened child number of the given index, which must be below 2^31
+    pub fn normal(index: u32) -> Result<Self, Error> {
+        if index >= HARDENED_OFFSET {
+            return Err(Error::invalid_derivation_path(format!(
+                "child index too large: {}",
+                index
+            )));
+        }
+        Ok(Self(index))
+    }
+
+    /// Hardened child number of the given index, which must be below 2^31
+    pub fn hardened(index: u32) -> Result<Self, Error> {
+        Self::normal(index).map(|child| Self(child.0 | HARDENED_OFFSET))
+    }
+
+    /// Whether this is the number of a hardened child
+    pub fn is_hardened(self) -> bool {
+        self.0 & HARDENED_OFFSET != 0
+    }
+
+    /// Index of the child, without the hardened flag
+    pub fn index(self) -> u32 {
+        self.0 & !HARDENED_OFFSET
+    }
+}
+
+impl From<ChildNumber> for u32 {
+    fn from(child: ChildNumber) -> u32 {
+        child.0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+/// Parse a child number, hardened if suffixed with `'` or `h`
+impl FromStr for ChildNumber {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (index, hardened) = match s.strip_suffix(|c| c == '\'' || c == 'h' || c == 'H') {
+            Some(index) => (index, true),
+            None => (s, false),
+        };
+
+        let index = index.parse::<u32>().map_err(|_| {
+            Error::invalid_derivation_path(format!("invalid child number: {:?}", s))
+        })?;
+
+        if hardened {
+            Self::hardened(index)
+        } else {
+            Self::normal(index)
+        }
+    }
+}
+
+/// Path of child numbers from a master key to a derived key, eg. `m/44'/118'/0'/0/0`
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    /// Path of a [BIP44] key: `m/44'/coin_type'/account'/change/index`
+    ///
+    /// [BIP44]: https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+    pub fn bip44(coin_type: u32, account: u32, change: u32, index: u32) -> Result<Self, Error> {
+        Ok(Self(vec![
+            ChildNumber::hardened(44)?,
+            ChildNumber::hardened(coin_type)?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::normal(change)?,
+            ChildNumber::normal(index)?,
+        ]))
+    }
+
+    /// Path of the given Cosmos account key: `m/44'/118'/account'/0/index`
+    pub fn cosmos(account: u32, index: u32) -> Result<Self, Error> {
+        Self::bip44(COSMOS_COIN_TYPE, account, 0, index)
+    }
+
+    /// Child numbers of the path, from the master key
+    pub fn child_numbers(&self) -> &[ChildNumber] {
+        &self.0
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(child_numbers: Vec<ChildNumber>) -> Self {
+        Self(child_numbers)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::invalid_derivation_path(format!(
+                "path does not start with \"m\": {:?}",
+                s
+            )));
+        }
+
+        parts
+            .map(ChildNumber::from_str)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Extended secp256k1 private key: a private key along with the chain code
+/// from which the keys of its children are derived
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    signing_key: Secp256k1,
+    chain_code: [u8; 32],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+}
+
+impl ExtendedPrivateKey {
+    /// Master key of the given seed, eg. the seed of a BIP39 mnemonic
+    pub fn new_master(seed: &[u8]) -> Result<Self, Error> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(Error::invalid_key(format!(
+                "invalid seed length: {}",
+                seed.len()
+            )));
+        }
+
+        let (secret, chain_code) = hmac_sha512(MASTER_KEY_HMAC_KEY, &[seed]);
+
+        Ok(Self {
+            signing_key: Secp256k1::from_bytes(secret.as_ref())
+                .map_err(|_| invalid_derived_key())?,
+            chain_code,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber(0),
+        })
+    }
+
+    /// Derive the key at the given path from this key, eg. from a master key
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, Error> {
+        path.child_numbers()
+            .iter()
+            .try_fold(self.clone(), |key, &child| key.derive_child(child))
+    }
+
+    /// Derive the child key with the given number
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self, Error> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or_else(|| Error::invalid_derivation_path("path too deep".to_string()))?;
+
+        let secret = Zeroizing::new(self.signing_key.to_bytes().to_vec());
+        let public = self.signing_key.verifying_key().to_bytes();
+        let child_number = u32::from(child).to_be_bytes();
+
+        let (tweak, chain_code) = if child.is_hardened() {
+            hmac_sha512(&self.chain_code, &[&[0], secret.as_slice(), &child_number])
+        } else {
+            hmac_sha512(&self.chain_code, &[public.as_slice(), &child_number])
+        };
+
+        // The derived key is invalid with a probability lower than 2^-127,
+        // in which case BIP32 has callers proceed with the next child.
+        let tweak = Scalar::from_repr((*tweak).into()).ok_or_else(invalid_derived_key)?;
+        let parent =
+            Scalar::from_repr(self.signing_key.to_bytes()).ok_or_else(invalid_derived_key)?;
+        let signing_key = Secp256k1::from_bytes(&(tweak + parent).to_bytes())
+            .map_err(|_| invalid_derived_key())?;
+
+        Ok(Self {
+            signing_key,
+            chain_code,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+        })
+    }
+
+    /// The secp256k1 signing key
+    pub fn signing_key(&self) -> &Secp256k1 {
+        &self.signing_key
+    }
+
+    /// The private key, eg. to sign with
+    pub fn private_key(&self) -> PrivateKey {
+        PrivateKey::Secp256k1(self.signing_key.clone())
+    }
+
+    /// The public key
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Secp256k1(self.signing_key.verifying_key())
+    }
+
+    /// The chain code
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Depth of this key, ie. number of derivations from the master key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Number of this key as a child of its parent, `0` for master keys
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    /// Fingerprint of the parent key, all zeroes for master keys
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// Fingerprint of this key, ie. the first 4 bytes of `RIPEMD160(SHA256(public key))`
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let public = self.signing_key.verifying_key().to_bytes();
+        let digest = Ripemd160::digest(&Sha256::digest(&public));
+        digest[..4].try_into().unwrap()
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        // The signing key is zeroized by `k256`, if at all.
+        self.chain_code.zeroize();
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("public_key", &self.public_key())
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_number", &self.child_number)
+            .finish()
+    }
+}
+
+/// Split the HMAC-SHA512 of the given data into its two halves
+pub(super) fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for data in data {
+        mac.update(data);
+    }
+
+    let output = Zeroizing::new(mac.finalize().into_bytes().to_vec());
+    let mut left = Zeroizing::new([0; 32]);
+    left.copy_from_slice(&output[..32]);
+    let mut right = [0; 32];
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+fn invalid_derived_key() -> Error {
+    Error::invalid_key("derived secp256k1 key is invalid".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subtle_encoding::hex;
+
+    fn derive(seed: &str, path: &str) -> ExtendedPrivateKey {
+        ExtendedPrivateKey::new_master(&hex::decode(seed).unwrap())
+            .unwrap()
+            .derive_path(&path.parse().unwrap())
+            .unwrap()
+    }
+
+    fn assert_key(key: &ExtendedPrivateKey, chain_code: &str, secret: &str) {
+        assert_eq!(hex::encode(key.chain_code()), chain_code.as_bytes());
+        assert_eq!(hex::encode(key.signing_key().to_bytes()), secret.as_bytes());
+    }
+
+    /// Test vector 1 of BIP32
+    #[test]
+    fn bip32_test_vector() {
+        let seed = "000102030405060708090a0b0c0d0e0f";
+
+        let master = derive(seed, "m");
+        assert_key(
+            &master,
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        );
+        assert_eq!(hex::encode(master.fingerprint()), b"3442193e");
+
+        let key = derive(seed, "m/0'/1");
+        assert_key(
+            &key,
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        );
+
+        let key = derive(seed, "m/0'/1/2'/2/1000000000");
+        assert_key(
+            &key,
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        );
+        assert_eq!(key.depth(), 5);
+        assert_eq!(
+            key.child_number(),
+            ChildNumber::normal(1_000_000_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_derivation_paths() {
+        let path: DerivationPath = COSMOS_PATH.parse().unwrap();
+        assert_eq!(path, DerivationPath::cosmos(0, 0).unwrap());
+        assert_eq!(path.to_string(), COSMOS_PATH);
+        assert_eq!("m/44h/118H/0'/0/0".parse::<DerivationPath>().unwrap(), path);
+
+        assert!("".parse::<DerivationPath>().is_err());
+        assert!("44'/118'".parse::<DerivationPath>().is_err());
+        assert!("m/44'/".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+    }
+}
@@ -0,0 +1,288 @@
+//! Mnemonic phrases encoding key material, as specified by [BIP39], from
+//! which node keys and account keys can be provisioned and recovered.
+//!
+//! Only the English wordlist is supported.
+//!
+//! [BIP39]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use super::bip32::{hmac_sha512, DerivationPath, ExtendedPrivateKey};
+use super::Ed25519;
+use crate::error::Error;
+use ed25519_dalek::{PublicKey as Ed25519PublicKey, SecretKey as Ed25519SecretKey};
+use hmac::{Hmac, Mac, NewMac};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// The English wordlist, sorted
+const ENGLISH_WORDS: &str = include_str!("bip39/english.txt");
+
+/// Number of PBKDF2 iterations computing seeds from mnemonics
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// Key of the HMAC computing Ed25519 master keys from seeds, as specified by
+/// [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md)
+const ED25519_MASTER_KEY_HMAC_KEY: &[u8] = b"ed25519 seed";
+
+/// Mnemonic phrase of 12, 15, 18, 21 or 24 words, encoding 128 to 256 bits
+/// of entropy along with their checksum
+#[derive(Clone)]
+pub struct Mnemonic {
+    phrase: Zeroizing<String>,
+    entropy: Zeroizing<Vec<u8>>,
+}
+
+impl Mnemonic {
+    /// Generate a new random mnemonic of the given number of words
+    pub fn generate(word_count: usize) -> Result<Self, Error> {
+        if !word_count.is_multiple_of(3) || !(12..=24).contains(&word_count) {
+            return Err(Error::invalid_mnemonic(format!(
+                "invalid number of words: {}",
+                word_count
+            )));
+        }
+
+        let mut entropy = Zeroizing::new(vec![0; word_count / 3 * 4]);
+        OsRng.fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy)
+    }
+
+    /// Mnemonic encoding the given entropy, of 16 to 32 bytes
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, Error> {
+        if !entropy.len().is_multiple_of(4) || !(16..=32).contains(&entropy.len()) {
+            return Err(Error::invalid_mnemonic(format!(
+                "invalid entropy length: {}",
+                entropy.len()
+            )));
+        }
+
+        let words: Vec<&str> = ENGLISH_WORDS.lines().collect();
+
+        // The entropy is followed by its checksum, ie. the first bit of its
+        // SHA-256 digest for each 32 of its bits, then split in 11 bits groups.
+        let mut bits = Zeroizing::new(entropy.to_vec());
+        bits.push(Sha256::digest(entropy)[0]);
+
+        let word_count = entropy.len() * 8 * 33 / 32 / 11;
+        let mut phrase = Zeroizing::new(String::new());
+        for i in 0..word_count {
+            if i > 0 {
+                phrase.push(' ');
+            }
+            phrase.push_str(words[bits_at(&bits, i * 11)]);
+        }
+
+        Ok(Self {
+            phrase,
+            entropy: Zeroizing::new(entropy.to_vec()),
+        })
+    }
+
+    /// Parse the given mnemonic phrase, checking its checksum
+    pub fn from_phrase(phrase: &str) -> Result<Self, Error> {
+        let words: Vec<&str> = ENGLISH_WORDS.lines().collect();
+
+        let indices = Zeroizing::new(
+            phrase
+                .split_whitespace()
+                .enumerate()
+                .map(|(position, word)| {
+                    // The word itself is secret, only report its position
+                    words.binary_search(&word).map_err(|_| {
+                        Error::invalid_mnemonic(format!("unknown word at position {}", position))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+
+        if !indices.len().is_multiple_of(3) || !(12..=24).contains(&indices.len()) {
+            return Err(Error::invalid_mnemonic(format!(
+                "invalid number of words: {}",
+                indices.len()
+            )));
+        }
+
+        let mut bits = Zeroizing::new(vec![0_u8; (indices.len() * 11).div_ceil(8)]);
+        for (i, &index) in indices.iter().enumerate() {
+            for bit in 0..11 {
+                if index & (1 << (10 - bit)) != 0 {
+                    let position = i * 11 + bit;
+                    bits[position / 8] |= 128 >> (position % 8);
+                }
+            }
+        }
+
+        let entropy_len = indices.len() / 3 * 4;
+        let mnemonic = Self::from_entropy(&bits[..entropy_len])?;
+        if mnemonic.words().ne(phrase.split_whitespace()) {
+            return Err(Error::invalid_mnemonic("invalid checksum".to_string()));
+        }
+
+        Ok(mnemonic)
+    }
+
+    /// The phrase, with its words separated by single spaces
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// The words of the phrase
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.phrase.split(' ')
+    }
+
+    /// The entropy encoded by the phrase
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Compute the seed of this mnemonic, with the given passphrase
+    /// (possibly empty).
+    ///
+    /// BIP39 expects passphrases to be in Unicode normalization form NFKD,
+    /// which is up to the caller for passphrases which are not ASCII.
+    pub fn to_seed(&self, passphrase: &str) -> Seed {
+        let mut salt = Zeroizing::new(String::from("mnemonic"));
+        salt.push_str(passphrase);
+
+        // PBKDF2-HMAC-SHA512, whose 64 bytes output is a single block
+        let mac = Hmac::<Sha512>::new_from_slice(self.phrase.as_bytes())
+            .expect("HMAC accepts keys of any size");
+
+        let mut block = mac.clone();
+        block.update(salt.as_bytes());
+        block.update(&1_u32.to_be_bytes());
+        let mut u = Zeroizing::new(block.finalize().into_bytes().to_vec());
+
+        let mut seed = Zeroizing::new([0; 64]);
+        seed.copy_from_slice(&u);
+        for _ in 1..PBKDF2_ROUNDS {
+            let mut block = mac.clone();
+            block.update(&u);
+            u = Zeroizing::new(block.finalize().into_bytes().to_vec());
+
+            for (s, u) in seed.iter_mut().zip(u.iter()) {
+                *s ^= u;
+            }
+        }
+
+        Seed(seed)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic").finish_non_exhaustive()
+    }
+}
+
+/// Seed of a mnemonic, from which keys are derived
+pub struct Seed(Zeroizing<[u8; 64]>);
+
+impl Seed {
+    /// Borrow the bytes of this seed
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+
+    /// Ed25519 keypair of this seed, eg. for a node key: the master key of
+    /// [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md)
+    pub fn ed25519_keypair(&self) -> Ed25519 {
+        let (secret, _) = hmac_sha512(ED25519_MASTER_KEY_HMAC_KEY, &[self.0.as_ref()]);
+        let secret = Ed25519SecretKey::from_bytes(secret.as_ref())
+            .expect("Ed25519 secret keys can be any 32 bytes");
+        let public = Ed25519PublicKey::from(&secret);
+        Ed25519 { secret, public }
+    }
+
+    /// The secp256k1 key derived at the given path from this seed, eg. the
+    /// Cosmos account key at [`bip32::COSMOS_PATH`](super::bip32::COSMOS_PATH)
+    pub fn derive_secp256k1(&self, path: &DerivationPath) -> Result<ExtendedPrivateKey, Error> {
+        ExtendedPrivateKey::new_master(self.as_bytes())?.derive_path(path)
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Seed").finish_non_exhaustive()
+    }
+}
+
+/// The 11 bits starting at the given bit position
+fn bits_at(bytes: &[u8], position: usize) -> usize {
+    (position..position + 11).fold(0, |index, position| {
+        let bit = (bytes[position / 8] >> (7 - position % 8)) & 1;
+        (index << 1) | bit as usize
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorDetail;
+    use subtle_encoding::hex;
+
+    /// Test vectors of the reference implementation, with the passphrase "TREZOR"
+    const VECTORS: &[(&str, &str, &str)] = &[
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for (entropy, phrase, seed) in VECTORS {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.phrase(), *phrase);
+
+            let parsed = Mnemonic::from_phrase(phrase).unwrap();
+            assert_eq!(hex::encode(parsed.entropy()), entropy.as_bytes());
+            assert_eq!(
+                hex::encode(&parsed.to_seed("TREZOR").as_bytes()[..]),
+                seed.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn reject_invalid_phrases() {
+        // Invalid checksum
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(Mnemonic::from_phrase(phrase).is_err());
+        // Unknown word, which is not leaked by the error
+        let unknown = phrase.replacen("abandon", "tendermint", 1);
+        match Mnemonic::from_phrase(&unknown) {
+            Err(Error(ErrorDetail::InvalidMnemonic(e), _)) => {
+                assert_eq!(e.detail, "unknown word at position 0")
+            }
+            _ => panic!("expected an invalid mnemonic error"),
+        }
+        // Invalid number of words
+        assert!(Mnemonic::from_phrase("abandon about").is_err());
+    }
+
+    #[test]
+    fn generate_mnemonics() {
+        for &word_count in &[12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(word_count).unwrap();
+            assert_eq!(mnemonic.words().count(), word_count);
+
+            let parsed = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+            assert_eq!(parsed.entropy(), mnemonic.entropy());
+        }
+        assert!(Mnemonic::generate(13).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! File-based private validator, which signs votes and proposals with a key
+//! stored in `priv_validator_key.json` while keeping track of the last signed
+//! message in `priv_validator_state.json` to prevent double signing.
+//!
+//! This mirrors the `FilePV` of the Go implementation.
+
+use crate::config::{PrivValidatorKey, PrivValidatorState};
+use crate::{
+    account, block, chain, proposal::Proposal, signer::Signer, vote, Error, PublicKey, Signature,
+    Time,
+};
+use prost::Message;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use tendermint_proto::google::protobuf::Timestamp;
+use tendermint_proto::types::{
+    CanonicalProposal as RawCanonicalProposal, CanonicalVote as RawCanonicalVote,
+};
+
+/// Signing step of a proposal
+pub const STEP_PROPOSE: i8 = 1;
+
+/// Signing step of a prevote
+pub const STEP_PREVOTE: i8 = 2;
+
+/// Signing step of a precommit
+pub const STEP_PRECOMMIT: i8 = 3;
+
+/// Private validator backed by a key file and a state file.
+///
+/// Before signing, the height, round and step of a message are checked
+/// against the ones of the last signed message, which are persisted to the
+/// state file before the signature is returned. A message with the same
+/// height, round and step as the last signed one is only signed again if it
+/// is the same message, possibly with a different timestamp, in which case
+/// the last signature (and timestamp) is reused.
+#[derive(Debug)]
+pub struct FilePrivValidator {
+    key: PrivValidatorKey,
+    state: PrivValidatorState,
+    state_file: PathBuf,
+}
+
+impl FilePrivValidator {
+    /// Create a private validator from a key and its last signing state,
+    /// which is saved to the given state file whenever a message is signed.
+    pub fn new(
+        key: PrivValidatorKey,
+        state: PrivValidatorState,
+        state_file: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            key,
+            state,
+            state_file: state_file.into(),
+        }
+    }
+
+    /// Generate a new private validator key and save it to the given key
+    /// file, along with an empty signing state saved to the given state file.
+    pub fn generate(
+        key_file: impl AsRef<Path>,
+        state_file: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let validator = Self::new(
+            PrivValidatorKey::generate(),
+            PrivValidatorState::default(),
+            state_file.as_ref(),
+        );
+
+        validator.key.save_json_file(&key_file)?;
+        validator.state.save_json_file(&validator.state_file)?;
+        Ok(validator)
+    }
+
+    /// Load a private validator from its key and state files.
+    pub fn load(key_file: impl AsRef<Path>, state_file: impl AsRef<Path>) -> Result<Self, Error> {
+        let key = PrivValidatorKey::load_json_file(&key_file)?;
+        let state = PrivValidatorState::load_json_file(&state_file)?;
+        Ok(Self::new(key, state, state_file.as_ref()))
+    }
+
+    /// Address of the validator
+    pub fn address(&self) -> account::Id {
+        self.key.address
+    }
+
+    /// Public key of the validator
+    pub fn public_key(&self) -> PublicKey {
+        self.key.pub_key
+    }
+
+    /// Last signing state of the validator
+    pub fn state(&self) -> &PrivValidatorState {
+        &self.state
+    }
+
+    /// Sign the given vote for the given chain, setting its signature.
+    pub fn sign_vote(&mut self, chain_id: chain::Id, vote: &mut vote::Vote) -> Result<(), Error> {
+        let step = match vote.vote_type {
+            vote::Type::Prevote => STEP_PREVOTE,
+            vote::Type::Precommit => STEP_PRECOMMIT,
+        };
+
+        let sign_bytes = vote
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Error::protocol(e.to_string()))?;
+
+        if self.state.check_hrs(vote.height, vote.round, step)? {
+            let mut same_vote = vote.clone();
+            if sign_bytes != self.state.sign_bytes {
+                same_vote.timestamp =
+                    Some(self.last_timestamp::<RawCanonicalVote>(|v| v.timestamp)?);
+                let sign_bytes = same_vote
+                    .to_signable_vec(chain_id)
+                    .map_err(|e| Error::protocol(e.to_string()))?;
+
+                if sign_bytes != self.state.sign_bytes {
+                    return Err(Error::double_sign(
+                        "conflicting vote for the last signed height, round and step".to_string(),
+                    ));
+                }
+            }
+
+            vote.timestamp = same_vote.timestamp;
+            vote.signature = self.last_signature()?;
+            return Ok(());
+        }
+
+        vote.signature = self.sign(vote.height, vote.round, step, sign_bytes)?;
+        Ok(())
+    }
+
+    /// Sign the given proposal for the given chain, setting its signature.
+    pub fn sign_proposal(
+        &mut self,
+        chain_id: chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), Error> {
+        let sign_bytes = proposal
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Error::protocol(e.to_string()))?;
+
+        if self
+            .state
+            .check_hrs(proposal.height, proposal.round, STEP_PROPOSE)?
+        {
+            let mut same_proposal = proposal.clone();
+            if sign_bytes != self.state.sign_bytes {
+                same_proposal.timestamp =
+                    Some(self.last_timestamp::<RawCanonicalProposal>(|p| p.timestamp)?);
+                let sign_bytes = same_proposal
+                    .to_signable_vec(chain_id)
+                    .map_err(|e| Error::protocol(e.to_string()))?;
+
+                if sign_bytes != self.state.sign_bytes {
+                    return Err(Error::double_sign(
+                        "conflicting proposal for the last signed height and round".to_string(),
+                    ));
+                }
+            }
+
+            proposal.timestamp = same_proposal.timestamp;
+            proposal.signature = self.last_signature()?;
+            return Ok(());
+        }
+
+        proposal.signature =
+            self.sign(proposal.height, proposal.round, STEP_PROPOSE, sign_bytes)?;
+        Ok(())
+    }
+
+    /// Sign the given bytes, after having persisted the new signing state.
+    fn sign(
+        &mut self,
+        height: block::Height,
+        round: block::Round,
+        step: i8,
+        sign_bytes: Vec<u8>,
+    ) -> Result<Signature, Error> {
+        let signature = self.key.priv_key.sign(&sign_bytes);
+
+        let state = PrivValidatorState {
+            height,
+            round,
+            step,
+            signature: signature.as_bytes().to_vec(),
+            sign_bytes,
+        };
+        state.save_json_file(&self.state_file)?;
+        self.state = state;
+
+        Ok(signature)
+    }
+
+    fn last_signature(&self) -> Result<Signature, Error> {
+        Signature::try_from(self.state.signature.clone())
+    }
+
+    /// Timestamp of the last signed message, decoded from its sign bytes.
+    fn last_timestamp<M>(&self, timestamp: impl Fn(M) -> Option<Timestamp>) -> Result<Time, Error>
+    where
+        M: Message + Default,
+    {
+        M::decode_length_delimited(self.state.sign_bytes.as_slice())
+            .ok()
+            .and_then(timestamp)
+            .map(Time::from)
+            .ok_or_else(|| Error::double_sign("cannot decode the last signed message".to_string()))
+    }
+}
+
+/// Signs votes and proposals with double signing protection.
+///
+/// As it would bypass this protection, signing arbitrary messages with
+/// [`RawSigner`](crate::signer::RawSigner) is not supported.
+impl Signer for FilePrivValidator {
+    fn public_key(&mut self, _chain_id: &chain::Id) -> Result<PublicKey, Error> {
+        Ok(Self::public_key(self))
+    }
+
+    fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut vote::Vote) -> Result<(), Error> {
+        Self::sign_vote(self, chain_id.clone(), vote)
+    }
+
+    fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), Error> {
+        Self::sign_proposal(self, chain_id.clone(), proposal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hash::Algorithm, Hash};
+
+    fn chain_id() -> chain::Id {
+        chain::Id::try_from("test-chain").unwrap()
+    }
+
+    fn block_id(byte: u8) -> block::Id {
+        block::Id {
+            hash: Hash::from_bytes(Algorithm::Sha256, &[byte; 32]).unwrap(),
+            part_set_header: block::parts::Header::new(
+                1,
+                Hash::from_bytes(Algorithm::Sha256, &[byte; 32]).unwrap(),
+            )
+            .unwrap(),
+        }
+    }
+
+    fn vote(
+        validator: &FilePrivValidator,
+        vote_type: vote::Type,
+        height: u32,
+        round: u16,
+    ) -> vote::Vote {
+        vote::Vote {
+            vote_type,
+            height: block::Height::from(height),
+            round: block::Round::from(round),
+            block_id: Some(block_id(1)),
+            timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+            validator_address: validator.address(),
+            validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+            signature: Signature::default(),
+        }
+    }
+
+    fn validator(name: &str) -> FilePrivValidator {
+        let state_file = std::env::temp_dir().join(format!(
+            "tendermint-privval-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+        FilePrivValidator::new(
+            PrivValidatorKey::generate(),
+            PrivValidatorState::default(),
+            state_file,
+        )
+    }
+
+    #[test]
+    fn signs_votes_monotonically() {
+        let mut validator = validator("votes");
+
+        let mut prevote = vote(&validator, vote::Type::Prevote, 10, 0);
+        validator.sign_vote(chain_id(), &mut prevote).unwrap();
+        prevote
+            .verify_signature(chain_id(), &validator.public_key())
+            .unwrap();
+
+        // the state is persisted before the signature is returned
+        let saved = PrivValidatorState::load_json_file(&validator.state_file).unwrap();
+        assert_eq!(&saved, validator.state());
+        assert_eq!(saved.step, STEP_PREVOTE);
+
+        // signing the same vote, possibly with another timestamp, gives the same signature
+        let mut same = vote(&validator, vote::Type::Prevote, 10, 0);
+        same.timestamp = Some(Time::now());
+        validator.sign_vote(chain_id(), &mut same).unwrap();
+        assert_eq!(same, prevote);
+
+        // signing another block at the same height, round and step is refused
+        let mut conflicting = vote(&validator, vote::Type::Prevote, 10, 0);
+        conflicting.block_id = Some(block_id(2));
+        assert!(validator.sign_vote(chain_id(), &mut conflicting).is_err());
+
+        let mut precommit = vote(&validator, vote::Type::Precommit, 10, 0);
+        validator.sign_vote(chain_id(), &mut precommit).unwrap();
+
+        // going back to a previous step, round or height is refused
+        assert!(validator
+            .sign_vote(chain_id(), &mut prevote.clone())
+            .is_err());
+        let mut earlier = vote(&validator, vote::Type::Precommit, 9, 3);
+        assert!(validator.sign_vote(chain_id(), &mut earlier).is_err());
+
+        std::fs::remove_file(&validator.state_file).unwrap();
+    }
+
+    #[test]
+    fn signs_proposals_monotonically() {
+        let mut validator = validator("proposals");
+
+        let mut proposal = Proposal {
+            msg_type: crate::proposal::Type::Proposal,
+            height: block::Height::from(10_u32),
+            round: block::Round::from(1_u16),
+            pol_round: None,
+            block_id: Some(block_id(1)),
+            timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+            signature: Signature::default(),
+        };
+
+        let mut unsigned = proposal.clone();
+        validator.sign_proposal(chain_id(), &mut proposal).unwrap();
+        proposal
+            .verify(chain_id(), &validator.public_key())
+            .unwrap();
+
+        unsigned.timestamp = Some(Time::now());
+        validator.sign_proposal(chain_id(), &mut unsigned).unwrap();
+        assert_eq!(unsigned, proposal);
+
+        // a vote at the same height and round comes at a later step
+        let mut prevote = vote(&validator, vote::Type::Prevote, 10, 1);
+        validator.sign_vote(chain_id(), &mut prevote).unwrap();
+        assert!(validator.sign_proposal(chain_id(), &mut proposal).is_err());
+
+        std::fs::remove_file(&validator.state_file).unwrap();
+    }
+}
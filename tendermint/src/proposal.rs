@@ -10,8 +10,9 @@ pub use sign_proposal::{SignProposalRequest, SignedProposalResponse};
 
 use crate::block::{Height, Id as BlockId, Round};
 use crate::chain::Id as ChainId;
-use crate::consensus::State;
+use crate::consensus::{State, SynchronyParams};
 use crate::error::Error;
+use crate::PublicKey;
 use crate::Signature;
 use crate::Time;
 use bytes::BufMut;
@@ -49,16 +50,32 @@ impl TryFrom<RawProposal> for Proposal {
         }
         let pol_round = match value.pol_round {
             -1 => None,
-            n => Some(Round::try_from(n)?),
+            n => Some(Round::try_from(n).map_err(|e| Error::field("pol_round", e))?),
         };
         Ok(Proposal {
-            msg_type: value.r#type.try_into()?,
-            height: value.height.try_into()?,
-            round: value.round.try_into()?,
+            msg_type: value
+                .r#type
+                .try_into()
+                .map_err(|e| Error::field("type", e))?,
+            height: value
+                .height
+                .try_into()
+                .map_err(|e| Error::field("height", e))?,
+            round: value
+                .round
+                .try_into()
+                .map_err(|e| Error::field("round", e))?,
             pol_round,
-            block_id: value.block_id.map(TryInto::try_into).transpose()?,
+            block_id: value
+                .block_id
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|e| Error::field("block_id", e))?,
             timestamp: value.timestamp.map(|t| t.into()),
-            signature: value.signature.try_into()?,
+            signature: value
+                .signature
+                .try_into()
+                .map_err(|e| Error::field("signature", e))?,
         })
     }
 }
@@ -96,6 +113,35 @@ impl Proposal {
         CanonicalProposal::new(self.clone(), chain_id).encode_length_delimited_vec()
     }
 
+    /// Verify that this proposal was signed for the given chain by the proposer
+    /// with the given public key, against its canonical sign bytes.
+    pub fn verify(&self, chain_id: ChainId, pub_key: &PublicKey) -> Result<(), Error> {
+        let sign_bytes = self
+            .to_signable_vec(chain_id)
+            .map_err(|e| Error::protocol(e.to_string()))?;
+
+        pub_key.verify(&sign_bytes, &self.signature)
+    }
+
+    /// Check that this proposal, received at the given time, is timely with
+    /// the given synchrony parameters of proposer-based timestamps, as
+    /// `Proposal.IsTimely` in Go: validators must not prevote for the blocks
+    /// of untimely proposals.
+    pub fn check_timely(&self, received_at: Time, params: &SynchronyParams) -> Result<(), Error> {
+        let timestamp = self
+            .timestamp
+            .ok_or_else(|| Error::invalid_timestamp("the proposal has no timestamp".to_string()))?;
+
+        if !params.is_timely(timestamp, received_at, self.round) {
+            return Err(Error::invalid_timestamp(format!(
+                "proposal timestamp {} is not timely in round {} when received at {}",
+                timestamp, self.round, received_at
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Consensus state from this proposal - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
@@ -290,6 +336,105 @@ mod tests {
         assert_eq!(got, want)
     }
 
+    #[test]
+    fn test_verify() {
+        use crate::test::keypair;
+        use crate::PublicKey;
+        use ed25519_dalek::Signer;
+
+        let keypair = keypair(7);
+        let pub_key = PublicKey::from(keypair.public);
+
+        let dt = "2018-02-11T07:09:22.765Z".parse::<DateTime<Utc>>().unwrap();
+        let mut proposal = Proposal {
+            msg_type: Type::Proposal,
+            height: Height::from(12345_u32),
+            round: Round::from(23456_u16),
+            pol_round: None,
+            block_id: Some(BlockId {
+                hash: Hash::from_hex_upper(
+                    Algorithm::Sha256,
+                    "DEADBEEFDEADBEEFBAFBAFBAFBAFBAFADEADBEEFDEADBEEFBAFBAFBAFBAFBAFA",
+                )
+                .unwrap(),
+                part_set_header: Header::new(
+                    65535,
+                    Hash::from_hex_upper(
+                        Algorithm::Sha256,
+                        "0022446688AACCEE1133557799BBDDFF0022446688AACCEE1133557799BBDDFF",
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            }),
+            timestamp: Some(dt.into()),
+            signature: Signature::default(),
+        };
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+
+        let sign_bytes = proposal.to_signable_vec(chain_id.clone()).unwrap();
+        proposal.signature = Signature::Ed25519(keypair.sign(&sign_bytes));
+
+        proposal.verify(chain_id.clone(), &pub_key).unwrap();
+
+        // Signed for another chain
+        assert!(proposal
+            .verify(ChainId::from_str("other_chain_id").unwrap(), &pub_key)
+            .is_err());
+
+        // Signed by another proposer
+        let other_pub_key = PublicKey::from(crate::test::keypair(8).public);
+        assert!(proposal.verify(chain_id.clone(), &other_pub_key).is_err());
+
+        // Tampered with after signing
+        proposal.pol_round = Some(Round::from(1_u16));
+        assert!(proposal.verify(chain_id, &pub_key).is_err());
+    }
+
+    #[test]
+    fn test_check_timely() {
+        use crate::consensus::SynchronyParams;
+        use crate::Time;
+        use std::time::Duration;
+
+        let params = SynchronyParams {
+            precision: Duration::from_millis(500),
+            message_delay: Duration::from_secs(2),
+        };
+        let at = |time: &str| Time::parse_from_rfc3339(time).unwrap();
+
+        let mut proposal = Proposal {
+            msg_type: Type::Proposal,
+            height: Height::from(12345_u32),
+            round: Round::from(0_u16),
+            pol_round: None,
+            block_id: None,
+            timestamp: Some(at("2021-01-01T00:00:10Z")),
+            signature: Signature::default(),
+        };
+
+        assert!(proposal
+            .check_timely(at("2021-01-01T00:00:12Z"), &params)
+            .is_ok());
+        assert!(proposal
+            .check_timely(at("2021-01-01T00:00:13Z"), &params)
+            .is_err());
+        assert!(proposal
+            .check_timely(at("2021-01-01T00:00:09Z"), &params)
+            .is_err());
+
+        // The message delay grows with the rounds
+        proposal.round = Round::from(10_u16);
+        assert!(proposal
+            .check_timely(at("2021-01-01T00:00:13Z"), &params)
+            .is_ok());
+
+        proposal.timestamp = None;
+        assert!(proposal
+            .check_timely(at("2021-01-01T00:00:10Z"), &params)
+            .is_err());
+    }
+
     #[test]
     fn test_deserialization() {
         let dt = "2018-02-11T07:09:22.765Z".parse::<DateTime<Utc>>().unwrap();
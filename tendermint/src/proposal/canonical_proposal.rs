@@ -61,9 +61,6 @@ impl TryFrom<RawCanonicalProposal> for CanonicalProposal {
 
 impl From<CanonicalProposal> for RawCanonicalProposal {
     fn from(value: CanonicalProposal) -> Self {
-        // If the Hash is empty in BlockId, the BlockId should be empty.
-        // See: https://github.com/informalsystems/tendermint-rs/issues/663
-        let block_id = value.block_id.filter(|i| i != &BlockId::default());
         RawCanonicalProposal {
             r#type: value.msg_type.into(),
             height: value.height.into(),
@@ -72,7 +69,9 @@ impl From<CanonicalProposal> for RawCanonicalProposal {
                 None => -1,
                 Some(p) => i32::from(p) as i64,
             },
-            block_id: block_id.map(Into::into),
+            // The nil block ID is not encoded
+            // See: https://github.com/informalsystems/tendermint-rs/issues/663
+            block_id: value.block_id.and_then(BlockId::canonicalize),
             timestamp: value.timestamp.map(Into::into),
             chain_id: value.chain_id.as_str().to_string(),
         }
@@ -3,14 +3,20 @@
 pub use ed25519_dalek::PublicKey as Ed25519;
 #[cfg(feature = "secp256k1")]
 pub use k256::ecdsa::VerifyingKey as Secp256k1;
+#[cfg(feature = "sr25519")]
+pub use sr25519::Sr25519;
 
 mod pub_key_request;
 mod pub_key_response;
+#[cfg(feature = "sr25519")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+pub mod sr25519;
 pub use pub_key_request::PubKeyRequest;
 pub use pub_key_response::PubKeyResponse;
 
 use crate::{error::Error, signature::Signature};
 use serde::{de, ser, Deserialize, Serialize};
+#[cfg(feature = "secp256k1")]
 use signature::Verifier as _;
 use std::convert::TryFrom;
 use std::{cmp::Ordering, fmt, ops::Deref, str::FromStr};
@@ -19,6 +25,25 @@ use tendermint_proto::crypto::public_key::Sum;
 use tendermint_proto::crypto::PublicKey as RawPublicKey;
 use tendermint_proto::Protobuf;
 
+/// Length of the Amino prefix of keys, ie. their type prefix followed by
+/// their length
+const AMINO_PREFIX_LENGTH: usize = 5;
+
+const AMINO_ED25519_PREFIX: &[u8] = &[0x16, 0x24, 0xDE, 0x64, 0x20];
+#[cfg(feature = "secp256k1")]
+const AMINO_SECP256K1_PREFIX: &[u8] = &[0xEB, 0x5A, 0xE9, 0x87, 0x21];
+#[cfg(feature = "sr25519")]
+const AMINO_SR25519_PREFIX: &[u8] = &[0x0D, 0xFB, 0x10, 0x05, 0x20];
+
+/// Decode the given Bech32 string, checking that it has the given human readable prefix.
+pub(crate) fn decode_bech32(encoded: &str, hrp: &str) -> Result<Vec<u8>, Error> {
+    let (found, bytes) = bech32::decode(encoded).map_err(Error::subtle_encoding)?;
+    if found != hrp {
+        return Err(Error::invalid_bech32_prefix(hrp.to_string(), found));
+    }
+    Ok(bytes)
+}
+
 // Note:On the golang side this is generic in the sense that it could everything that implements
 // github.com/tendermint/tendermint/crypto.PubKey
 // While this is meant to be used with different key-types, it currently only uses a PubKeyEd25519
@@ -51,6 +76,17 @@ pub enum PublicKey {
         deserialize_with = "deserialize_secp256k1_base64"
     )]
     Secp256k1(Secp256k1),
+
+    /// Sr25519 keys, which have a JSON and an Amino encoding but no Protobuf
+    /// encoding
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    #[serde(
+        rename = "tendermint/PubKeySr25519",
+        serialize_with = "serialize_sr25519_base64",
+        deserialize_with = "deserialize_sr25519_base64"
+    )]
+    Sr25519(Sr25519),
 }
 
 impl Protobuf<RawPublicKey> for PublicKey {}
@@ -79,16 +115,16 @@ impl From<PublicKey> for RawPublicKey {
     fn from(value: PublicKey) -> Self {
         match value {
             PublicKey::Ed25519(ref pk) => RawPublicKey {
-                sum: Some(tendermint_proto::crypto::public_key::Sum::Ed25519(
-                    pk.as_bytes().to_vec(),
-                )),
+                sum: Some(Sum::Ed25519(pk.as_bytes().to_vec())),
             },
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(ref pk) => RawPublicKey {
-                sum: Some(tendermint_proto::crypto::public_key::Sum::Secp256k1(
-                    pk.to_bytes().to_vec(),
-                )),
+                sum: Some(Sum::Secp256k1(pk.to_bytes().to_vec())),
             },
+            // The Protobuf definitions of Tendermint v0.34 have no Sr25519
+            // keys: they are encoded as an empty key, which fails to decode.
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => RawPublicKey { sum: None },
         }
     }
 }
@@ -103,6 +139,13 @@ impl PublicKey {
             .map(PublicKey::Secp256k1)
     }
 
+    /// From raw Sr25519 public key bytes
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn from_raw_sr25519(bytes: &[u8]) -> Option<PublicKey> {
+        Sr25519::from_bytes(bytes).ok().map(PublicKey::Sr25519)
+    }
+
     /// From raw Ed25519 public key bytes
     pub fn from_raw_ed25519(bytes: &[u8]) -> Option<PublicKey> {
         Ed25519::from_bytes(bytes).map(Into::into).ok()
@@ -127,19 +170,45 @@ impl PublicKey {
         }
     }
 
+    /// Get Sr25519 public key
+    #[cfg(feature = "sr25519")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sr25519")))]
+    pub fn sr25519(self) -> Option<Sr25519> {
+        match self {
+            PublicKey::Sr25519(pk) => Some(pk),
+            _ => None,
+        }
+    }
+
     /// Verify the given [`Signature`] using this public key
     pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), Error> {
         match self {
             PublicKey::Ed25519(pk) => match signature {
-                Signature::Ed25519(sig) => pk.verify(msg, sig).map_err(|_| {
-                    Error::signature_invalid("Ed25519 signature verification failed".to_string())
-                }),
+                Signature::Ed25519(sig) => crate::signature::zip215::verify(pk, msg, sig),
+                #[cfg(feature = "secp256k1")]
+                Signature::Secp256k1(_) => Err(Error::signature_invalid(
+                    "ECDSA/secp256k1 signature given for an Ed25519 public key".to_string(),
+                )),
                 Signature::None => Err(Error::signature_invalid("missing signature".to_string())),
             },
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(_) => Err(Error::invalid_key(
-                "unsupported signature algorithm (ECDSA/secp256k1)".to_string(),
-            )),
+            PublicKey::Secp256k1(pk) => match signature {
+                Signature::None => Err(Error::signature_invalid("missing signature".to_string())),
+                // Signatures decoded from raw bytes are typed as Ed25519 signatures.
+                _ => {
+                    let sig = signature.secp256k1().ok_or_else(|| {
+                        Error::signature_invalid("malformed ECDSA/secp256k1 signature".to_string())
+                    })?;
+
+                    pk.verify(msg, &sig).map_err(|_| {
+                        Error::signature_invalid(
+                            "ECDSA/secp256k1 signature verification failed".to_string(),
+                        )
+                    })
+                }
+            },
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => pk.verify(msg, signature),
         }
     }
 
@@ -149,25 +218,63 @@ impl PublicKey {
             PublicKey::Ed25519(pk) => pk.as_bytes().to_vec(),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(pk) => pk.to_bytes().to_vec(),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(pk) => pk.as_bytes().to_vec(),
+        }
+    }
+
+    /// Serialize this key as Amino, ie. as its Amino type prefix, its
+    /// length and its bytes, as Tendermint versions older than v0.34 do
+    pub fn to_amino_bytes(self) -> Vec<u8> {
+        let mut bytes = self.amino_prefix().to_vec();
+        bytes.extend(self.to_bytes());
+        bytes
+    }
+
+    /// Parse a key serialized as Amino (see [`PublicKey::to_amino_bytes`])
+    pub fn from_amino_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let invalid = || Error::invalid_key("invalid Amino public key".to_string());
+        if bytes.len() < AMINO_PREFIX_LENGTH {
+            return Err(invalid());
+        }
+        let (prefix, key_bytes) = bytes.split_at(AMINO_PREFIX_LENGTH);
+
+        match prefix {
+            AMINO_ED25519_PREFIX => Self::from_raw_ed25519(key_bytes),
+            #[cfg(feature = "secp256k1")]
+            AMINO_SECP256K1_PREFIX => Self::from_raw_secp256k1(key_bytes),
+            #[cfg(feature = "sr25519")]
+            AMINO_SR25519_PREFIX => Self::from_raw_sr25519(key_bytes),
+            _ => {
+                return Err(Error::invalid_key(
+                    "unsupported Amino public key type".to_string(),
+                ))
+            }
         }
+        .ok_or_else(invalid)
     }
 
     /// Serialize this key as Bech32 with the given human readable prefix
     pub fn to_bech32(self, hrp: &str) -> String {
-        let backward_compatible_amino_prefixed_pubkey = match self {
-            PublicKey::Ed25519(ref pk) => {
-                let mut key_bytes = vec![0x16, 0x24, 0xDE, 0x64, 0x20];
-                key_bytes.extend(pk.as_bytes());
-                key_bytes
-            }
+        // The Amino encoding, for backward compatibility
+        bech32::encode(hrp, self.to_amino_bytes())
+    }
+
+    /// Parse a key serialized as Bech32 (see [`PublicKey::to_bech32`]),
+    /// checking that it has the given human readable prefix
+    pub fn from_bech32(encoded: &str, hrp: &str) -> Result<Self, Error> {
+        Self::from_amino_bytes(&decode_bech32(encoded, hrp)?)
+    }
+
+    /// The Amino prefix (type prefix and length) of this key
+    fn amino_prefix(&self) -> &'static [u8] {
+        match self {
+            PublicKey::Ed25519(_) => AMINO_ED25519_PREFIX,
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(ref pk) => {
-                let mut key_bytes = vec![0xEB, 0x5A, 0xE9, 0x87, 0x21];
-                key_bytes.extend(pk.to_bytes());
-                key_bytes
-            }
-        };
-        bech32::encode(hrp, backward_compatible_amino_prefixed_pubkey)
+            PublicKey::Secp256k1(_) => AMINO_SECP256K1_PREFIX,
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => AMINO_SR25519_PREFIX,
+        }
     }
 
     /// Serialize this key as hexadecimal
@@ -196,19 +303,29 @@ impl PartialOrd for PublicKey {
 }
 
 impl Ord for PublicKey {
+    /// Keys of different types are ordered as Ed25519 < Secp256k1 < Sr25519.
     fn cmp(&self, other: &Self) -> Ordering {
+        #[allow(unreachable_patterns)]
+        match (self, other) {
+            (PublicKey::Ed25519(a), PublicKey::Ed25519(b)) => a.as_bytes().cmp(b.as_bytes()),
+            #[cfg(feature = "secp256k1")]
+            (PublicKey::Secp256k1(a), PublicKey::Secp256k1(b)) => a.cmp(b),
+            #[cfg(feature = "sr25519")]
+            (PublicKey::Sr25519(a), PublicKey::Sr25519(b)) => a.cmp(b),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PublicKey {
+    /// Rank of the type of this key, used to order keys of different types.
+    fn type_rank(&self) -> u8 {
         match self {
-            PublicKey::Ed25519(a) => match other {
-                PublicKey::Ed25519(b) => a.as_bytes().cmp(b.as_bytes()),
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(_) => Ordering::Less,
-            },
+            PublicKey::Ed25519(_) => 0,
             #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(a) => match other {
-                PublicKey::Ed25519(_) => Ordering::Greater,
-                #[cfg(feature = "secp256k1")]
-                PublicKey::Secp256k1(b) => a.cmp(b),
-            },
+            PublicKey::Secp256k1(_) => 1,
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => 2,
         }
     }
 }
@@ -230,6 +347,8 @@ impl TendermintKey {
             PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
             #[cfg(feature = "secp256k1")]
             PublicKey::Secp256k1(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::AccountKey(public_key)),
         }
     }
 
@@ -238,8 +357,12 @@ impl TendermintKey {
         #[allow(unreachable_patterns)]
         match public_key {
             PublicKey::Ed25519(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(_) => Ok(TendermintKey::AccountKey(public_key)),
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => Ok(TendermintKey::AccountKey(public_key)),
             _ => Err(Error::invalid_key(
-                "only ed25519 consensus keys are supported".to_string(),
+                "only ed25519, secp256k1 and sr25519 consensus keys are supported".to_string(),
             )),
         }
     }
@@ -356,11 +479,33 @@ where
     Secp256k1::from_sec1_bytes(&bytes).map_err(|_| D::Error::custom("invalid secp256k1 key"))
 }
 
+/// Serialize the bytes of an Sr25519 public key as Base64. Used for serializing JSON
+#[cfg(feature = "sr25519")]
+fn serialize_sr25519_base64<S>(pk: &Sr25519, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    String::from_utf8(base64::encode(pk.as_bytes()))
+        .unwrap()
+        .serialize(serializer)
+}
+
+#[cfg(feature = "sr25519")]
+fn deserialize_sr25519_base64<'de, D>(deserializer: D) -> Result<Sr25519, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    use de::Error;
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(&encoded).map_err(D::Error::custom)?;
+    Sr25519::from_bytes(&bytes).map_err(|_| D::Error::custom("invalid sr25519 key"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::{PublicKey, TendermintKey};
     use crate::public_key::PubKeyResponse;
-    use subtle_encoding::hex;
+    use subtle_encoding::{bech32, hex};
     use tendermint_proto::Protobuf;
 
     const EXAMPLE_CONSENSUS_KEY: &str =
@@ -393,6 +538,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bech32_parsing() {
+        let encoded =
+            "cosmosvalconspub1zcjduepqfgjuveq2raetnjt4xwpffm63kmguxv2chdhvhf5lhslmtgeunh8qmf7exk";
+        let key = PublicKey::from_bech32(encoded, "cosmosvalconspub").unwrap();
+        assert_eq!(
+            key.to_hex(),
+            "4A25C6640A1F72B9C975338294EF51B6D1C33158BB6ECBA69FBC3FB5A33C9DCE"
+        );
+        assert_eq!(key.to_bech32("cosmosvalconspub"), encoded);
+
+        assert!(PublicKey::from_bech32(encoded, "cosmospub").is_err());
+        // A valid bech32 string which is not an Amino-prefixed key
+        let address = bech32::encode("cosmosvalconspub", [0x2A; 20]);
+        assert!(PublicKey::from_bech32(&address, "cosmosvalconspub").is_err());
+    }
+
     #[test]
     #[cfg(feature = "secp256k1")]
     fn test_account_serialization() {
@@ -425,6 +587,31 @@ mod tests {
         assert_eq!(reserialized_json.as_str(), json_string);
     }
 
+    #[test]
+    #[cfg(feature = "sr25519")]
+    fn sr25519_json_round_trip() {
+        // The compressed Ristretto basepoint
+        let json_string = "{\"type\":\"tendermint/PubKeySr25519\",\"value\":\"4vKuCmq8TnGohKlhxQBRX1jjC2qlgt2NtqZZReCNLXY=\"}";
+        let pubkey: PublicKey = serde_json::from_str(json_string).unwrap();
+
+        assert_eq!(
+            pubkey.sr25519().unwrap().as_bytes(),
+            &[
+                0xe2, 0xf2, 0xae, 0x0a, 0x6a, 0xbc, 0x4e, 0x71, 0xa8, 0x84, 0xa9, 0x61, 0xc5, 0x00,
+                0x51, 0x5f, 0x58, 0xe3, 0x0b, 0x6a, 0xa5, 0x82, 0xdd, 0x8d, 0xb6, 0xa6, 0x59, 0x45,
+                0xe0, 0x8d, 0x2d, 0x76
+            ]
+        );
+        assert_eq!(serde_json::to_string(&pubkey).unwrap(), json_string);
+
+        // There is no Protobuf encoding of Sr25519 keys
+        let encoded = pubkey.encode_vec().unwrap();
+        assert!(PublicKey::decode_vec(&encoded).is_err());
+
+        let invalid = "{\"type\":\"tendermint/PubKeySr25519\",\"value\":\"//////////////////////////////////////////8=\"}";
+        assert!(serde_json::from_str::<PublicKey>(invalid).is_err());
+    }
+
     #[test]
     fn test_ed25519_pubkey_msg() {
         // test-vector generated from Go
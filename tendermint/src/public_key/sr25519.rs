@@ -0,0 +1,166 @@
+//! Sr25519 public keys, ie. Schnorr public keys over the Ristretto group,
+//! as implemented by `schnorrkel` and used by some Tendermint networks.
+
+use crate::{error::Error, signature::Signature};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use merlin::Transcript;
+use std::fmt;
+
+/// Length of an Sr25519 public key, in bytes
+pub const SR25519_PUBLIC_KEY_LENGTH: usize = 32;
+
+/// Length of an Sr25519 signature, in bytes
+pub const SR25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Sr25519 public key
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct Sr25519([u8; SR25519_PUBLIC_KEY_LENGTH]);
+
+impl Sr25519 {
+    /// Parse an Sr25519 public key from its 32 bytes compressed Ristretto encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != SR25519_PUBLIC_KEY_LENGTH {
+            return Err(Error::invalid_key(format!(
+                "invalid sr25519 key length: {}",
+                bytes.len()
+            )));
+        }
+
+        let mut key = [0; SR25519_PUBLIC_KEY_LENGTH];
+        key.copy_from_slice(bytes);
+
+        if CompressedRistretto(key).decompress().is_none() {
+            return Err(Error::invalid_key(
+                "sr25519 key is not a valid Ristretto point".to_string(),
+            ));
+        }
+
+        Ok(Self(key))
+    }
+
+    /// Borrow the bytes of this public key.
+    pub fn as_bytes(&self) -> &[u8; SR25519_PUBLIC_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Verify the given signature of the given message with this public key.
+    ///
+    /// As done by Tendermint, signatures are made with an empty `schnorrkel`
+    /// signing context, and have their high bit set to mark them as such.
+    pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), Error> {
+        let invalid = |reason: &str| Error::signature_invalid(reason.to_string());
+
+        let bytes = signature.as_bytes();
+        if bytes.len() != SR25519_SIGNATURE_LENGTH {
+            return Err(invalid("invalid sr25519 signature length"));
+        }
+
+        let mut r = [0; 32];
+        r.copy_from_slice(&bytes[..32]);
+
+        let mut s = [0; 32];
+        s.copy_from_slice(&bytes[32..]);
+        if s[31] & 128 == 0 {
+            return Err(invalid(
+                "sr25519 signature is not marked as a schnorrkel signature",
+            ));
+        }
+        s[31] &= 127;
+
+        let s = Scalar::from_canonical_bytes(s)
+            .ok_or_else(|| invalid("sr25519 signature is not canonical"))?;
+
+        // `from_bytes` ensures the key decompresses.
+        let public_key = CompressedRistretto(self.0).decompress().unwrap();
+
+        let mut transcript = signing_transcript(msg);
+        transcript.append_message(b"proto-name", b"Schnorr-sig");
+        transcript.append_message(b"sign:pk", &self.0);
+        transcript.append_message(b"sign:R", &r);
+
+        let mut challenge = [0; 64];
+        transcript.challenge_bytes(b"sign:c", &mut challenge);
+        let k = Scalar::from_bytes_mod_order_wide(&challenge);
+
+        let expected_r = RistrettoPoint::vartime_double_scalar_mul_basepoint(&k, &-public_key, &s);
+
+        if expected_r.compress() == CompressedRistretto(r) {
+            Ok(())
+        } else {
+            Err(invalid("Sr25519 signature verification failed"))
+        }
+    }
+}
+
+/// The `schnorrkel` transcript of a message signed with an empty signing context.
+fn signing_transcript(msg: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(b"SigningContext");
+    transcript.append_message(b"", b"");
+    transcript.append_message(b"sign-bytes", msg);
+    transcript
+}
+
+impl fmt::Debug for Sr25519 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sr25519({:02X?})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use std::convert::TryFrom;
+
+    /// Sign the message as `schnorrkel` does, with the given nonce.
+    fn sign(secret: Scalar, nonce: Scalar, msg: &[u8]) -> Vec<u8> {
+        let public_key = (secret * RISTRETTO_BASEPOINT_POINT).compress();
+        let r = (nonce * RISTRETTO_BASEPOINT_POINT).compress();
+
+        let mut transcript = signing_transcript(msg);
+        transcript.append_message(b"proto-name", b"Schnorr-sig");
+        transcript.append_message(b"sign:pk", public_key.as_bytes());
+        transcript.append_message(b"sign:R", r.as_bytes());
+
+        let mut challenge = [0; 64];
+        transcript.challenge_bytes(b"sign:c", &mut challenge);
+        let k = Scalar::from_bytes_mod_order_wide(&challenge);
+
+        let s = k * secret + nonce;
+
+        let mut signature = r.as_bytes().to_vec();
+        signature.extend_from_slice(s.as_bytes());
+        signature[63] |= 128;
+        signature
+    }
+
+    #[test]
+    fn verify_signatures() {
+        let secret = Scalar::from(1234_u64);
+        let public_key =
+            Sr25519::from_bytes((secret * RISTRETTO_BASEPOINT_POINT).compress().as_bytes())
+                .unwrap();
+
+        let msg = b"canonical vote sign bytes";
+        let signature = sign(secret, Scalar::from(42_u64), msg);
+
+        let valid = Signature::try_from(signature.clone()).unwrap();
+        public_key.verify(msg, &valid).unwrap();
+        assert!(public_key.verify(b"another message", &valid).is_err());
+
+        let mut unmarked = signature;
+        unmarked[63] &= 127;
+        let unmarked = Signature::try_from(unmarked).unwrap();
+        assert!(public_key.verify(msg, &unmarked).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_keys() {
+        assert!(Sr25519::from_bytes(&[0; 31]).is_err());
+        // Not a canonical encoding of a Ristretto point.
+        assert!(Sr25519::from_bytes(&[0xff; 32]).is_err());
+    }
+}
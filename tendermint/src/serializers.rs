@@ -11,4 +11,6 @@ pub mod apphash;
 pub mod hash;
 pub mod hash_base64;
 pub mod option_hash;
+pub mod optional_round;
+pub mod round;
 pub mod time;
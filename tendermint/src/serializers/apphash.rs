@@ -1,16 +1,23 @@
 //! AppHash serialization with validation
+//!
+//! App hashes are deserialized from upper- or lower-case hexadecimal or from
+//! base64, and serialized in upper-case hexadecimal. As app hashes can be of
+//! any length, strings which are valid in both encodings are deserialized as
+//! hexadecimal: fields in a known encoding should rather use the [`lower_hex`]
+//! and [`base64`] submodules, which only deserialize and serialize app hashes
+//! in this encoding.
 
 use crate::AppHash;
 use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::hex;
 
-/// Deserialize hexstring into AppHash
+/// Deserialize hexstring or base64 string into AppHash
 pub fn deserialize<'de, D>(deserializer: D) -> Result<AppHash, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let hexstring: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
-    AppHash::from_hex_upper(hexstring.as_str()).map_err(serde::de::Error::custom)
+    let string: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    AppHash::from_hex_or_base64(string.as_str()).map_err(serde::de::Error::custom)
 }
 
 /// Serialize from AppHash into hexstring
@@ -22,3 +29,106 @@ where
     let hex_string = String::from_utf8(hex_bytes).map_err(serde::ser::Error::custom)?;
     serializer.serialize_str(&hex_string)
 }
+
+/// AppHash serialization into lower-case hexadecimal
+pub mod lower_hex {
+    use crate::AppHash;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use subtle_encoding::hex;
+
+    /// Deserialize upper- or lower-case hexstring into AppHash
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<AppHash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hexstring: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        AppHash::from_hex(hexstring.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Serialize from AppHash into lower-case hexstring
+    pub fn serialize<S>(value: &AppHash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let hex_bytes = hex::encode(value.as_ref());
+        let hex_string = String::from_utf8(hex_bytes).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&hex_string)
+    }
+}
+
+/// AppHash serialization into base64
+pub mod base64 {
+    use crate::AppHash;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+
+    /// Deserialize base64 string into AppHash
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<AppHash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        AppHash::from_base64(string.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Serialize from AppHash into base64 string
+    pub fn serialize<S>(value: &AppHash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let base64_bytes = base64::encode(value.as_ref());
+        let base64_string = String::from_utf8(base64_bytes).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&base64_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::AppHash;
+    use serde::{Deserialize, Serialize};
+    use std::convert::TryFrom;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AppHashes {
+        #[serde(with = "super")]
+        upper: AppHash,
+        #[serde(with = "super::lower_hex")]
+        lower: AppHash,
+        #[serde(with = "super::base64")]
+        base64: AppHash,
+    }
+
+    #[test]
+    fn round_trip_ambiguous_encoding() {
+        // "AAAA" is both the base64 encoding of [0, 0, 0] and the hexadecimal
+        // encoding of [0xAA, 0xAA]
+        let json = r#"{"upper":"AAAA","lower":"aaaa","base64":"AAAA"}"#;
+        let app_hashes: AppHashes = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            app_hashes,
+            AppHashes {
+                upper: AppHash::try_from(vec![0xAA, 0xAA]).unwrap(),
+                lower: AppHash::try_from(vec![0xAA, 0xAA]).unwrap(),
+                base64: AppHash::try_from(vec![0, 0, 0]).unwrap(),
+            }
+        );
+        assert_eq!(serde_json::to_string(&app_hashes).unwrap(), json);
+    }
+
+    #[test]
+    fn deserialize_known_encoding_only() {
+        // the default deserializer also accepts base64
+        assert!(serde_json::from_str::<AppHashes>(
+            r#"{"upper":"AQIDBA==","lower":"01020304","base64":"AQIDBA=="}"#
+        )
+        .is_ok());
+        assert!(serde_json::from_str::<AppHashes>(
+            r#"{"upper":"01020304","lower":"AQIDBA==","base64":"AQIDBA=="}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<AppHashes>(
+            r#"{"upper":"01020304","lower":"01020304","base64":"01020304-"}"#
+        )
+        .is_err());
+    }
+}
@@ -1,16 +1,21 @@
 //! Hash serialization with validation
+//!
+//! Hashes are deserialized from upper- or lower-case hexadecimal or from
+//! base64, and serialized in upper-case hexadecimal. The [`lower_hex`] and
+//! [`base64`] submodules only deserialize and serialize hashes in lower- or
+//! upper-case hexadecimal or in base64.
 
 use crate::{hash::Algorithm, Hash};
 use serde::{Deserialize, Deserializer, Serializer};
 use subtle_encoding::hex;
 
-/// Deserialize hexstring into Hash
+/// Deserialize hexstring or base64 string into Hash
 pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let hexstring: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
-    Hash::from_hex_upper(Algorithm::Sha256, hexstring.as_str()).map_err(serde::de::Error::custom)
+    let string: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    Hash::from_hex_or_base64(Algorithm::Sha256, string.as_str()).map_err(serde::de::Error::custom)
 }
 
 /// Serialize from Hash into hexstring
@@ -22,3 +27,119 @@ where
     let hex_string = String::from_utf8(hex_bytes).map_err(serde::ser::Error::custom)?;
     serializer.serialize_str(&hex_string)
 }
+
+/// Hash serialization into lower-case hexadecimal
+pub mod lower_hex {
+    use crate::{hash::Algorithm, Hash};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use subtle_encoding::hex;
+
+    /// Deserialize upper- or lower-case hexstring into Hash
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hexstring: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        Hash::from_hex(Algorithm::Sha256, hexstring.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Serialize from Hash into lower-case hexstring
+    pub fn serialize<S>(value: &Hash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let hex_bytes = hex::encode(value.as_bytes());
+        let hex_string = String::from_utf8(hex_bytes).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&hex_string)
+    }
+}
+
+/// Hash serialization into base64
+pub mod base64 {
+    use crate::{hash::Algorithm, Hash};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+
+    /// Deserialize base64 string into Hash
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Hash, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string: String = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        Hash::from_base64(Algorithm::Sha256, string.as_str()).map_err(serde::de::Error::custom)
+    }
+
+    /// Serialize from Hash into base64 string
+    pub fn serialize<S>(value: &Hash, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let base64_bytes = base64::encode(value.as_bytes());
+        let base64_string = String::from_utf8(base64_bytes).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&base64_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Hash;
+    use serde::{Deserialize, Serialize};
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Hashes {
+        #[serde(with = "super")]
+        upper: Hash,
+        #[serde(with = "super::lower_hex")]
+        lower: Hash,
+        #[serde(with = "super::base64")]
+        base64: Hash,
+    }
+
+    const HEX: &str = "9F28904F9C0F3AB74A81CBA48E39124DA1C680B47FBFCBA0126870DB722BCC30";
+    const BASE64: &str = "nyiQT5wPOrdKgcukjjkSTaHGgLR/v8ugEmhw23IrzDA=";
+
+    #[test]
+    fn deserialize_any_encoding() {
+        let hash = Hash::from_str(HEX).unwrap();
+        for encoded in &[HEX.to_string(), HEX.to_lowercase(), BASE64.to_string()] {
+            let json = format!(
+                r#"{{"upper":"{0}","lower":"{1}","base64":"{2}"}}"#,
+                encoded,
+                HEX.to_lowercase(),
+                BASE64
+            );
+            let hashes: Hashes = serde_json::from_str(&json).unwrap();
+            assert_eq!(hashes.upper, hash);
+            assert_eq!(hashes.lower, hash);
+            assert_eq!(hashes.base64, hash);
+        }
+    }
+
+    #[test]
+    fn deserialize_known_encoding_only() {
+        let json = format!(r#"{{"upper":"{0}","lower":"{0}","base64":"{0}"}}"#, BASE64);
+        assert!(serde_json::from_str::<Hashes>(&json).is_err());
+        let json = format!(r#"{{"upper":"{0}","lower":"{0}","base64":"{0}"}}"#, HEX);
+        assert!(serde_json::from_str::<Hashes>(&json).is_err());
+    }
+
+    #[test]
+    fn serialize_canonical_encoding() {
+        let hash = Hash::from_str(HEX).unwrap();
+        let hashes = Hashes {
+            upper: hash,
+            lower: hash,
+            base64: hash,
+        };
+        assert_eq!(
+            serde_json::to_string(&hashes).unwrap(),
+            format!(
+                r#"{{"upper":"{}","lower":"{}","base64":"{}"}}"#,
+                HEX,
+                HEX.to_lowercase(),
+                BASE64
+            )
+        );
+    }
+}
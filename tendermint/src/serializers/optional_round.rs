@@ -0,0 +1,25 @@
+//! Option<Round> serialization as a JSON integer, `-1` encoding the absence
+//! of a round, as in consensus states
+
+use crate::block::Round;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+
+/// Deserialize integer into Option<Round>
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Round>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match i32::deserialize(deserializer)? {
+        -1 => Ok(None),
+        round => Round::try_from(round).map(Some).map_err(D::Error::custom),
+    }
+}
+
+/// Serialize from Option<Round> into integer
+pub fn serialize<S>(value: &Option<Round>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.map_or(-1, i32::from).serialize(serializer)
+}
@@ -0,0 +1,21 @@
+//! Round serialization as a JSON integer, as in consensus states
+
+use crate::block::Round;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+
+/// Deserialize integer into Round
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Round, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Round::try_from(u32::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+/// Serialize from Round into integer
+pub fn serialize<S>(value: &Round, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.value().serialize(serializer)
+}
@@ -6,17 +6,33 @@ pub use signature::{Signer, Verifier};
 #[cfg(feature = "secp256k1")]
 pub use k256::ecdsa::Signature as Secp256k1;
 
+pub(crate) mod zip215;
+
 use crate::error::Error;
+use crate::public_key::PublicKey;
 use std::convert::TryFrom;
+use subtle::{Choice, ConstantTimeEq};
 use tendermint_proto::Protobuf;
 
 /// Signatures
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// Ed25519 and ECDSA/secp256k1 signatures have the same length, and are encoded
+/// as raw bytes without their algorithm. Signatures decoded from their raw bytes,
+/// eg. from protobuf, are thus decoded as Ed25519 signatures, and verifying them
+/// with a secp256k1 public key interprets their bytes as an ECDSA signature.
+///
+/// Signatures are compared in constant time.
+#[derive(Copy, Clone, Debug, Default)]
 #[non_exhaustive]
 pub enum Signature {
     /// Ed25519 block signature
     Ed25519(Ed25519Signature),
+    /// ECDSA/secp256k1 signature
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
+    Secp256k1(Secp256k1),
     /// No signature present
+    #[default]
     None, /* This could have been implemented as an `Option<>` but then handling it would be
            * outside the scope of this enum. */
 }
@@ -31,7 +47,10 @@ impl TryFrom<Vec<u8>> for Signature {
             return Ok(Self::default());
         }
         if value.len() != ED25519_SIGNATURE_SIZE {
-            return Err(Error::invalid_signature_id_length());
+            return Err(Error::invalid_signature_id_length(
+                ED25519_SIGNATURE_SIZE,
+                value.len(),
+            ));
         }
         let mut slice: [u8; ED25519_SIGNATURE_SIZE] = [0; ED25519_SIGNATURE_SIZE];
         slice.copy_from_slice(&value[..]);
@@ -45,17 +64,13 @@ impl From<Signature> for Vec<u8> {
     }
 }
 
-impl Default for Signature {
-    fn default() -> Self {
-        Signature::None
-    }
-}
-
 impl Signature {
     /// Return the algorithm used to create this particular signature
     pub fn algorithm(&self) -> Algorithm {
         match self {
             Signature::Ed25519(_) => Algorithm::Ed25519,
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(_) => Algorithm::EcdsaSecp256k1,
             Signature::None => Algorithm::Ed25519, /* It doesn't matter what algorithm an empty
                                                     * signature has. */
         }
@@ -63,8 +78,23 @@ impl Signature {
 
     /// Get Ed25519 signature
     pub fn ed25519(self) -> Option<Ed25519Signature> {
+        #[allow(unreachable_patterns)]
         match self {
             Signature::Ed25519(sig) => Some(sig),
+            _ => None,
+        }
+    }
+
+    /// Get ECDSA/secp256k1 signature
+    ///
+    /// As noted in the documentation of [`Signature`], signatures decoded from
+    /// their raw bytes are interpreted as ECDSA signatures by this method.
+    #[cfg(feature = "secp256k1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secp256k1")))]
+    pub fn secp256k1(self) -> Option<Secp256k1> {
+        match self {
+            Signature::Secp256k1(sig) => Some(sig),
+            Signature::Ed25519(sig) => Secp256k1::try_from(sig.as_ref()).ok(),
             Signature::None => None,
         }
     }
@@ -84,17 +114,115 @@ impl AsRef<[u8]> for Signature {
     fn as_ref(&self) -> &[u8] {
         match self {
             Signature::Ed25519(sig) => sig.as_ref(),
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(sig) => sig.as_ref(),
             Signature::None => &[],
         }
     }
 }
 
+impl ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Signature) -> Choice {
+        // Only the bytes of the signatures are secret, not their algorithm
+        Choice::from((self.algorithm() == other.algorithm()) as u8)
+            & self.as_bytes().ct_eq(other.as_bytes())
+    }
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Signature) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl From<Ed25519Signature> for Signature {
     fn from(pk: Ed25519Signature) -> Signature {
         Signature::Ed25519(pk)
     }
 }
 
+#[cfg(feature = "secp256k1")]
+impl From<Secp256k1> for Signature {
+    fn from(sig: Secp256k1) -> Signature {
+        Signature::Secp256k1(sig)
+    }
+}
+
+/// Verifier of several signatures at once.
+///
+/// Ed25519 signatures are verified in a single batch, which is much faster than
+/// verifying them one by one, eg. for the signatures of a commit by a large
+/// validator set. Only if the batch fails are they verified one by one, to
+/// tell which signatures are invalid. Other signatures are verified one by one.
+///
+/// Ed25519 signatures are verified with the cofactored equation of
+/// [ZIP 215](https://zips.z.cash/zip-0215), both in batches and one by one by
+/// [`PublicKey::verify`], so that a batch fails if and only if one of its
+/// signatures is invalid.
+#[derive(Clone, Debug, Default)]
+pub struct BatchVerifier {
+    entries: Vec<(PublicKey, Vec<u8>, Signature)>,
+}
+
+impl BatchVerifier {
+    /// Create an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the given signature of the given message by the given public key
+    /// to the batch.
+    pub fn push(&mut self, pub_key: PublicKey, msg: Vec<u8>, signature: Signature) {
+        self.entries.push((pub_key, msg, signature));
+    }
+
+    /// Number of signatures in the batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Verify all the signatures of the batch.
+    ///
+    /// On failure, returns the indices of the invalid signatures, in the
+    /// order in which they were added to the batch.
+    pub fn verify(&self) -> Result<(), Vec<usize>> {
+        let mut ed25519_entries = Vec::new();
+        let mut others = Vec::new();
+
+        #[allow(unreachable_patterns)]
+        for (index, (pub_key, msg, signature)) in self.entries.iter().enumerate() {
+            match (pub_key, signature) {
+                (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => {
+                    ed25519_entries.push((pk, msg.as_slice(), sig))
+                }
+                _ => others.push(index),
+            }
+        }
+
+        let batch_is_valid = zip215::verify_batch(&ed25519_entries).is_ok();
+
+        let invalid: Vec<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !batch_is_valid || others.contains(index))
+            .filter(|(_, (pub_key, msg, signature))| pub_key.verify(msg, signature).is_err())
+            .map(|(index, _)| index)
+            .collect();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid)
+        }
+    }
+}
+
 /// Digital signature algorithms
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Algorithm {
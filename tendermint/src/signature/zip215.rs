@@ -0,0 +1,230 @@
+//! Cofactored verification of Ed25519 signatures, following the validation
+//! rules of [ZIP 215] as `ed25519-consensus` does, so that verifying
+//! signatures one by one or as a batch always gives the same outcome.
+//!
+//! [ZIP 215]: https://zips.z.cash/zip-0215
+
+use super::Ed25519Signature;
+use crate::error::Error;
+use crate::public_key::Ed25519;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_POINT,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::{IsIdentity, VartimeMultiscalarMul},
+};
+use sha2::{Digest, Sha512};
+
+/// Domain separator of the hash deriving the coefficients of a batch
+const BATCH_COEFFICIENTS_DOMAIN: &[u8] = b"tendermint-rs ed25519 batch";
+
+/// A signature, decoded for verification
+struct Decoded {
+    r: EdwardsPoint,
+    s: Scalar,
+    a: EdwardsPoint,
+    k: Scalar,
+}
+
+/// Decode the given signature of the given message by the given public key,
+/// and compute its challenge `k = H(R || A || M)`
+fn decode(pk: &Ed25519, msg: &[u8], signature: &Ed25519Signature) -> Result<Decoded, Error> {
+    let invalid = |reason: &str| Error::signature_invalid(reason.to_string());
+
+    let bytes = signature.to_bytes();
+    let mut r_bytes = [0; 32];
+    r_bytes.copy_from_slice(&bytes[..32]);
+    let mut s_bytes = [0; 32];
+    s_bytes.copy_from_slice(&bytes[32..]);
+
+    // Non-canonical encodings of R and A are accepted, but not of s
+    let s = Scalar::from_canonical_bytes(s_bytes)
+        .ok_or_else(|| invalid("Ed25519 signature is not canonical"))?;
+    let r = CompressedEdwardsY(r_bytes)
+        .decompress()
+        .ok_or_else(|| invalid("Ed25519 signature is not a valid curve point"))?;
+    let a = CompressedEdwardsY(*pk.as_bytes())
+        .decompress()
+        .ok_or_else(|| invalid("Ed25519 public key is not a valid curve point"))?;
+
+    let mut hasher = Sha512::new();
+    hasher.update(r_bytes);
+    hasher.update(pk.as_bytes());
+    hasher.update(msg);
+    let k = Scalar::from_hash(hasher);
+
+    Ok(Decoded { r, s, a, k })
+}
+
+/// Verify the given signature of the given message by the given public key,
+/// checking that `[8]([s]B - R - [k]A)` is the identity.
+pub(crate) fn verify(pk: &Ed25519, msg: &[u8], signature: &Ed25519Signature) -> Result<(), Error> {
+    let Decoded { r, s, a, k } = decode(pk, msg, signature)?;
+
+    let check = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-k, &a, &s) - r;
+    if check.mul_by_cofactor().is_identity() {
+        Ok(())
+    } else {
+        Err(Error::signature_invalid(
+            "Ed25519 signature verification failed".to_string(),
+        ))
+    }
+}
+
+/// Verify the given signatures of the given messages by the given public keys
+/// all at once, checking that `[8]∑ z_i([s_i]B - R_i - [k_i]A_i)` is the
+/// identity for coefficients `z_i` which the signers cannot predict.
+///
+/// Succeeds if and only if [`verify`] succeeds on each of the signatures
+/// (except with negligible probability), but does not tell which ones are
+/// invalid when it fails.
+pub(crate) fn verify_batch(entries: &[(&Ed25519, &[u8], &Ed25519Signature)]) -> Result<(), Error> {
+    // The coefficients are derived from a hash of the whole batch, so that
+    // they are fixed only once all the signatures are.
+    let mut hasher = Sha512::new();
+    hasher.update(BATCH_COEFFICIENTS_DOMAIN);
+    for (pk, msg, signature) in entries {
+        hasher.update(pk.as_bytes());
+        hasher.update(signature.to_bytes());
+        hasher.update((msg.len() as u64).to_le_bytes());
+        hasher.update(msg);
+    }
+    let seed = hasher.finalize();
+
+    let mut b_coefficient = Scalar::zero();
+    let mut scalars = Vec::with_capacity(2 * entries.len() + 1);
+    let mut points = Vec::with_capacity(2 * entries.len() + 1);
+
+    for (index, (pk, msg, signature)) in entries.iter().enumerate() {
+        let Decoded { r, s, a, k } = decode(pk, msg, signature)?;
+
+        // 128 bits coefficient
+        let digest = Sha512::new()
+            .chain(seed)
+            .chain((index as u64).to_le_bytes())
+            .finalize();
+        let mut z_bytes = [0; 32];
+        z_bytes[..16].copy_from_slice(&digest[..16]);
+        let z = Scalar::from_bytes_mod_order(z_bytes);
+
+        b_coefficient += z * s;
+        scalars.push(-z);
+        points.push(r);
+        scalars.push(-(z * k));
+        points.push(a);
+    }
+
+    scalars.push(b_coefficient);
+    points.push(ED25519_BASEPOINT_POINT);
+
+    let check = EdwardsPoint::vartime_multiscalar_mul(scalars, points);
+    if check.mul_by_cofactor().is_identity() {
+        Ok(())
+    } else {
+        Err(Error::signature_invalid(
+            "Ed25519 batch verification failed".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::keypair;
+    use curve25519_dalek::constants::EIGHT_TORSION;
+    use ed25519_dalek::Signer;
+
+    #[test]
+    fn verify_signatures_one_by_one_and_as_a_batch() {
+        let keypairs: Vec<_> = (1..=10).map(keypair).collect();
+        let messages: Vec<Vec<u8>> = (0..10_u8).map(|i| vec![i; i as usize * 7]).collect();
+        let mut signatures: Vec<_> = keypairs
+            .iter()
+            .zip(&messages)
+            .map(|(kp, msg)| kp.sign(msg))
+            .collect();
+
+        let entries = |signatures: &[Ed25519Signature]| {
+            keypairs
+                .iter()
+                .zip(&messages)
+                .zip(signatures.to_vec())
+                .map(|((kp, msg), sig)| (kp.public, msg.clone(), sig))
+                .collect::<Vec<_>>()
+        };
+        let batch = |entries: &[(Ed25519, Vec<u8>, Ed25519Signature)]| {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(pk, msg, sig)| (pk, msg.as_slice(), sig))
+                .collect();
+            verify_batch(&entries)
+        };
+
+        for (pk, msg, sig) in entries(&signatures) {
+            verify(&pk, &msg, &sig).unwrap();
+        }
+        batch(&entries(&signatures)).unwrap();
+        batch(&[]).unwrap();
+
+        signatures[3] = keypairs[3].sign(b"another message");
+        assert!(verify(&keypairs[3].public, &messages[3], &signatures[3]).is_err());
+        assert!(batch(&entries(&signatures)).is_err());
+    }
+
+    /// Sign the given message with the given secret scalar and nonce, adding
+    /// the given point to the commitment `R` of the signature.
+    fn sign_with_torsion(
+        secret: Scalar,
+        nonce: Scalar,
+        torsion: EdwardsPoint,
+        msg: &[u8],
+    ) -> (Ed25519, Ed25519Signature) {
+        let a = (secret * ED25519_BASEPOINT_POINT).compress();
+        let r = (nonce * ED25519_BASEPOINT_POINT + torsion).compress();
+
+        let mut hasher = Sha512::new();
+        hasher.update(r.as_bytes());
+        hasher.update(a.as_bytes());
+        hasher.update(msg);
+        let k = Scalar::from_hash(hasher);
+        let s = nonce + k * secret;
+
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(r.as_bytes());
+        bytes[32..].copy_from_slice(s.as_bytes());
+        (
+            Ed25519::from_bytes(a.as_bytes()).unwrap(),
+            Ed25519Signature::new(bytes),
+        )
+    }
+
+    #[test]
+    fn accept_signatures_with_small_order_components() {
+        let msg = b"message";
+        let (pk, signature) = sign_with_torsion(
+            Scalar::from(1234_u64),
+            Scalar::from(42_u64),
+            EIGHT_TORSION[1],
+            msg,
+        );
+
+        // Rejected by the cofactorless verification of `ed25519-dalek`, but
+        // accepted both one by one and as a batch
+        assert!(ed25519_dalek::Verifier::verify(&pk, msg, &signature).is_err());
+        verify(&pk, msg, &signature).unwrap();
+        verify_batch(&[(&pk, &msg[..], &signature)]).unwrap();
+    }
+
+    #[test]
+    fn reject_non_canonical_scalars() {
+        let keypair = keypair(1);
+        let msg = b"message";
+        let mut bytes = keypair.sign(msg).to_bytes();
+
+        // s is then larger than the order of the basepoint
+        bytes[63] |= 0xf0;
+        let signature = Ed25519Signature::new(bytes);
+        assert!(verify(&keypair.public, msg, &signature).is_err());
+        assert!(verify_batch(&[(&keypair.public, &msg[..], &signature)]).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Signers of votes and proposals, abstracting over where the signing key is held:
+//!
+//! - in memory, with a [`PrivateKey`]
+//! - in a key file, with double signing protection, with a
+//!   [`FilePrivValidator`](crate::privval::FilePrivValidator)
+//! - by a remote signer such as a KMS or HSM, with the `SignerClient` of the
+//!   `tendermint-p2p` crate
+//!
+//! Code which needs signatures should be generic over [`Signer`], so that the
+//! signing backend can be swapped without changes to it.
+//!
+//! Signing arbitrary messages is kept apart, in [`RawSigner`], as it would
+//! bypass the double signing protection of the signers which have one.
+
+use crate::{chain, Error, PrivateKey, Proposal, PublicKey, Signature, Vote};
+
+/// Holder of a signing key, which signs votes and proposals with it.
+pub trait Signer {
+    /// Get the public key of the signer for the given chain.
+    fn public_key(&mut self, chain_id: &chain::Id) -> Result<PublicKey, Error>;
+
+    /// Sign the given vote for the given chain, setting its signature.
+    fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut Vote) -> Result<(), Error>;
+
+    /// Sign the given proposal for the given chain, setting its signature.
+    fn sign_proposal(&mut self, chain_id: &chain::Id, proposal: &mut Proposal)
+        -> Result<(), Error>;
+}
+
+/// Holder of a signing key, which signs arbitrary messages with it.
+///
+/// No check can be made on the messages, so this is only implemented by
+/// signers without double signing protection.
+pub trait RawSigner {
+    /// Sign the given message.
+    fn sign_bytes(&mut self, msg: &[u8]) -> Result<Signature, Error>;
+}
+
+/// Software signer, with the same key for all chains and no protection
+/// against double signing.
+impl Signer for PrivateKey {
+    fn public_key(&mut self, _chain_id: &chain::Id) -> Result<PublicKey, Error> {
+        Ok(PrivateKey::public_key(self))
+    }
+
+    fn sign_vote(&mut self, chain_id: &chain::Id, vote: &mut Vote) -> Result<(), Error> {
+        let sign_bytes = vote
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Error::protocol(e.to_string()))?;
+        vote.signature = self.sign(&sign_bytes);
+        Ok(())
+    }
+
+    fn sign_proposal(
+        &mut self,
+        chain_id: &chain::Id,
+        proposal: &mut Proposal,
+    ) -> Result<(), Error> {
+        let sign_bytes = proposal
+            .to_signable_vec(chain_id.clone())
+            .map_err(|e| Error::protocol(e.to_string()))?;
+        proposal.signature = self.sign(&sign_bytes);
+        Ok(())
+    }
+}
+
+impl RawSigner for PrivateKey {
+    fn sign_bytes(&mut self, msg: &[u8]) -> Result<Signature, Error> {
+        Ok(self.sign(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PrivValidatorKey, PrivValidatorState};
+    use crate::{account, block, hash::Algorithm, privval::FilePrivValidator, vote, Hash, Time};
+    use std::convert::TryFrom;
+
+    fn chain_id() -> chain::Id {
+        chain::Id::try_from("test-chain").unwrap()
+    }
+
+    fn prevote(public_key: PublicKey, round: u16) -> Vote {
+        Vote {
+            vote_type: vote::Type::Prevote,
+            height: block::Height::from(10_u32),
+            round: block::Round::from(round),
+            block_id: None,
+            timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+            validator_address: account::Id::from(public_key),
+            validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+            signature: Signature::default(),
+        }
+    }
+
+    fn block_id() -> block::Id {
+        let hash = Hash::from_bytes(Algorithm::Sha256, &[1; 32]).unwrap();
+        block::Id {
+            hash,
+            part_set_header: block::parts::Header::new(1, hash).unwrap(),
+        }
+    }
+
+    /// Sign the same vote twice, then a later vote, with the given signer
+    fn sign_with<S: Signer>(signer: &mut S) -> Result<(), Error> {
+        let public_key = signer.public_key(&chain_id())?;
+
+        let mut vote = prevote(public_key, 0);
+        signer.sign_vote(&chain_id(), &mut vote)?;
+        vote.verify_signature(chain_id(), &public_key)?;
+
+        let mut same_vote = prevote(public_key, 0);
+        signer.sign_vote(&chain_id(), &mut same_vote)?;
+        assert_eq!(same_vote, vote);
+
+        let mut later_vote = prevote(public_key, 1);
+        signer.sign_vote(&chain_id(), &mut later_vote)?;
+        later_vote.verify_signature(chain_id(), &public_key)
+    }
+
+    #[test]
+    fn software_signer() {
+        let mut signer = PrivValidatorKey::generate().priv_key;
+        sign_with(&mut signer).unwrap();
+
+        let signature = signer.sign_bytes(b"message").unwrap();
+        signer.public_key().verify(b"message", &signature).unwrap();
+
+        // A software signer signs conflicting votes
+        let mut vote = prevote(signer.public_key(), 1);
+        vote.block_id = Some(block_id());
+        let sign_bytes = vote.to_signable_vec(chain_id()).unwrap();
+        signer.sign_vote(&chain_id(), &mut vote).unwrap();
+        assert_eq!(vote.signature, signer.sign(&sign_bytes));
+    }
+
+    #[test]
+    fn file_signer() {
+        let state_file =
+            std::env::temp_dir().join(format!("tendermint-signer-{}.json", std::process::id()));
+        let mut signer = FilePrivValidator::new(
+            PrivValidatorKey::generate(),
+            PrivValidatorState::default(),
+            state_file.clone(),
+        );
+        sign_with(&mut signer).unwrap();
+        assert_eq!(signer.state().round, block::Round::from(1_u16));
+
+        let mut vote = prevote(signer.public_key(), 1);
+        vote.block_id = Some(block_id());
+        assert!(Signer::sign_vote(&mut signer, &chain_id(), &mut vote).is_err());
+
+        std::fs::remove_file(state_file).unwrap();
+    }
+}
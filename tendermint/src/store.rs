@@ -0,0 +1,311 @@
+//! Read-only access to the block store of Tendermint nodes, ie. their
+//! `data/blockstore.db` database, which holds the blocks of the chain split
+//! in parts, along with their metadata and commits.
+//!
+//! This module decodes the key layout and the values of the block store, but
+//! does not bundle any database backend: the database is accessed through the
+//! [`KeyValueStore`] trait, to be implemented on top of a binding of the
+//! backend used by the node (`goleveldb` by default, see `db_backend` in
+//! `config.toml`), eg. a LevelDB reader for `goleveldb` databases. Such
+//! databases are locked by the node while it runs, so they are to be read
+//! once it is stopped, or from a copy.
+//!
+//! [`KeyValueStore`] is also implemented for in-memory maps, eg. of the
+//! entries exported from a database.
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.x/store/store.go>
+
+use crate::{
+    block::{
+        parts::{Part, PartSet},
+        Commit, Height, Meta,
+    },
+    error::Error,
+    Block, Hash,
+};
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+    str,
+};
+use subtle_encoding::hex;
+use tendermint_proto::{store::BlockStoreState as RawBlockStoreState, Protobuf};
+
+/// Read access to a key-value database, with the keys and values written by
+/// the node
+pub trait KeyValueStore {
+    /// Get the value of the given key, if any
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+}
+
+impl KeyValueStore for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(BTreeMap::get(self, key).cloned())
+    }
+}
+
+/// Range of the heights of the blocks of a block store
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BlockStoreState {
+    /// Height of the first block of the store, or zero if the store is empty
+    pub base: Height,
+    /// Height of the last block of the store, or zero if the store is empty
+    pub height: Height,
+}
+
+impl Protobuf<RawBlockStoreState> for BlockStoreState {}
+
+impl TryFrom<RawBlockStoreState> for BlockStoreState {
+    type Error = Error;
+
+    fn try_from(value: RawBlockStoreState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            base: value.base.try_into()?,
+            height: value.height.try_into()?,
+        })
+    }
+}
+
+impl From<BlockStoreState> for RawBlockStoreState {
+    fn from(value: BlockStoreState) -> Self {
+        Self {
+            base: value.base.into(),
+            height: value.height.into(),
+        }
+    }
+}
+
+impl BlockStoreState {
+    /// Whether the store contains no block
+    pub fn is_empty(&self) -> bool {
+        self.height.value() == 0
+    }
+
+    /// Number of blocks in the store
+    pub fn size(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.height.value() - self.base.value() + 1
+        }
+    }
+}
+
+/// Block store of a node, backed by the given database
+#[derive(Clone, Debug)]
+pub struct BlockStore<S> {
+    db: S,
+}
+
+impl<S: KeyValueStore> BlockStore<S> {
+    /// Read the block store in the given database
+    pub fn new(db: S) -> Self {
+        Self { db }
+    }
+
+    /// Range of the heights of the blocks of the store
+    pub fn state(&self) -> Result<BlockStoreState, Error> {
+        let mut state = self
+            .load::<BlockStoreState, _>("blockStore".to_string())?
+            .unwrap_or(BlockStoreState {
+                base: Height::from(0_u32),
+                height: Height::from(0_u32),
+            });
+
+        // Stores written before the base was recorded start at height 1
+        if state.base.value() == 0 && !state.is_empty() {
+            state.base = Height::from(1_u32);
+        }
+
+        Ok(state)
+    }
+
+    /// Metadata of the block at the given height
+    pub fn block_meta(&self, height: Height) -> Result<Option<Meta>, Error> {
+        self.load(format!("H:{}", height))
+    }
+
+    /// Part of the block at the given height
+    pub fn block_part(&self, height: Height, index: u32) -> Result<Option<Part>, Error> {
+        self.load(format!("P:{}:{}", height, index))
+    }
+
+    /// Block at the given height, reassembled from its parts
+    pub fn block(&self, height: Height) -> Result<Option<Block>, Error> {
+        let meta = match self.block_meta(height)? {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        let mut part_set = PartSet::new(meta.block_id.part_set_header)?;
+        for index in 0..meta.block_id.part_set_header.total {
+            let part = self.block_part(height, index)?.ok_or_else(|| {
+                Error::corrupted_block_store(format!("missing part {} of block {}", index, height))
+            })?;
+            part_set.add_part(part)?;
+        }
+
+        Block::from_part_set(&part_set).map(Some)
+    }
+
+    /// Block with the given hash
+    pub fn block_by_hash(&self, hash: &Hash) -> Result<Option<Block>, Error> {
+        let key = [b"BH:".as_ref(), &hex::encode(hash.as_bytes())].concat();
+        let height = match self.db.get(&key)? {
+            Some(height) => height,
+            None => return Ok(None),
+        };
+
+        let height = str::from_utf8(&height)
+            .map_err(|e| Error::corrupted_block_store(e.to_string()))?
+            .parse()?;
+        self.block(height)
+    }
+
+    /// Commit of the block at the given height, as included in the next
+    /// block: the store of a node does not have the commits of its last
+    /// block, see [`BlockStore::seen_commit`]
+    pub fn block_commit(&self, height: Height) -> Result<Option<Commit>, Error> {
+        self.load(format!("C:{}", height))
+    }
+
+    /// Commit of the block at the given height, as seen by the node, ie.
+    /// with the precommits it received
+    pub fn seen_commit(&self, height: Height) -> Result<Option<Commit>, Error> {
+        self.load(format!("SC:{}", height))
+    }
+
+    /// Get back the database of the store
+    pub fn into_inner(self) -> S {
+        self.db
+    }
+
+    fn load<T, R>(&self, key: String) -> Result<Option<T>, Error>
+    where
+        T: Protobuf<R> + TryFrom<R, Error = Error>,
+        R: prost::Message + Default + From<T>,
+    {
+        self.db
+            .get(key.as_bytes())?
+            .map(|bytes| {
+                T::decode_vec(&bytes)
+                    .map_err(|e| Error::corrupted_block_store(format!("{}: {}", key, e)))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Id;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct BlockWithId {
+        block: Block,
+        block_id: Id,
+    }
+
+    /// Store holding the given block, as written by Tendermint Core
+    fn store_with(block: &Block, block_id: Id) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        let height = block.header.height;
+        let mut db = BTreeMap::new();
+
+        let meta = Meta {
+            block_id,
+            block_size: block.encode_vec().unwrap().len() as i64,
+            header: block.header.clone(),
+            num_txs: block.data.iter().count() as i64,
+        };
+        db.insert(
+            format!("H:{}", height).into_bytes(),
+            meta.encode_vec().unwrap(),
+        );
+
+        let part_set = block.make_part_set();
+        for index in 0..part_set.header().total {
+            db.insert(
+                format!("P:{}:{}", height, index).into_bytes(),
+                part_set.part(index).unwrap().encode_vec().unwrap(),
+            );
+        }
+
+        let last_commit = block.last_commit.clone().unwrap();
+        db.insert(
+            format!("C:{}", last_commit.height).into_bytes(),
+            last_commit.encode_vec().unwrap(),
+        );
+        db.insert(
+            [b"BH:".as_ref(), &hex::encode(block_id.hash.as_bytes())].concat(),
+            height.to_string().into_bytes(),
+        );
+        db.insert(
+            b"blockStore".to_vec(),
+            BlockStoreState {
+                base: Height::from(0_u32),
+                height,
+            }
+            .encode_vec()
+            .unwrap(),
+        );
+        db
+    }
+
+    #[test]
+    fn read_blocks() {
+        let fixture: BlockWithId = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/block_from_go_node.json"
+        ))
+        .unwrap();
+        let block = fixture.block;
+        let height = block.header.height;
+        let store = BlockStore::new(store_with(&block, fixture.block_id));
+
+        let state = store.state().unwrap();
+        assert_eq!(state.base.value(), 1);
+        assert_eq!(state.height, height);
+        assert_eq!(state.size(), height.value());
+
+        let meta = store.block_meta(height).unwrap().unwrap();
+        assert_eq!(meta.block_id, fixture.block_id);
+        assert_eq!(store.block(height).unwrap().unwrap(), block);
+        assert_eq!(
+            store
+                .block_by_hash(&fixture.block_id.hash)
+                .unwrap()
+                .unwrap(),
+            block
+        );
+
+        let commit_height = block.last_commit.as_ref().unwrap().height;
+        assert_eq!(
+            store.block_commit(commit_height).unwrap(),
+            block.last_commit
+        );
+        assert!(store.seen_commit(height).unwrap().is_none());
+        assert!(store.block(height.increment()).unwrap().is_none());
+    }
+
+    #[test]
+    fn empty_store() {
+        let store = BlockStore::new(BTreeMap::new());
+        let state = store.state().unwrap();
+        assert!(state.is_empty());
+        assert_eq!(state.size(), 0);
+        assert!(store.block(Height::from(1_u32)).unwrap().is_none());
+    }
+
+    #[test]
+    fn detect_missing_parts() {
+        let fixture: BlockWithId = serde_json::from_str(include_str!(
+            "../tests/support/serialization/block/block_from_go_node.json"
+        ))
+        .unwrap();
+        let height = fixture.block.header.height;
+        let mut db = store_with(&fixture.block, fixture.block_id);
+        db.remove(format!("P:{}:0", height).as_bytes());
+
+        assert!(BlockStore::new(db).block(height).is_err());
+    }
+}
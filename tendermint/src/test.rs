@@ -1,3 +1,6 @@
+use crate::block::{builder::CommitBuilder, signed_header::SignedHeader, Header};
+use crate::{block, private_key::Ed25519, validator, PublicKey};
+use ed25519_dalek::{PublicKey as Ed25519PublicKey, SecretKey};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
@@ -25,3 +28,57 @@ where
 
     assert_eq!(parsed0, parsed1);
 }
+
+/// Ed25519 key pair derived from the given seed
+pub fn keypair(seed: u8) -> Ed25519 {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = Ed25519PublicKey::from(&secret);
+    Ed25519 { secret, public }
+}
+
+/// Validator with the given key pair and voting power
+pub fn validator(keypair: &Ed25519, power: u32) -> validator::Info {
+    validator::Info::new(PublicKey::from(keypair.public), power.into())
+}
+
+/// Validator with the key pair derived from the given seed, see [`keypair`]
+pub fn seeded_validator(seed: u8, power: u32) -> validator::Info {
+    validator(&keypair(seed), power)
+}
+
+/// A header whose hash is known
+pub fn header() -> Header {
+    serde_json::from_str(include_str!(
+        "../tests/support/serialization/block/header_with_known_hash.json"
+    ))
+    .unwrap()
+}
+
+/// The given header, for the given validator set, with a commit for it
+/// signed at its time by the validators of the set which have one of the
+/// given key pairs, the signatures of the others being absent
+pub fn sign_header(
+    mut header: Header,
+    validator_set: &validator::Set,
+    keypairs: &[Ed25519],
+) -> SignedHeader {
+    header.validators_hash = validator_set.hash();
+
+    let block_id = block::Id {
+        hash: header.hash(),
+        part_set_header: Default::default(),
+    };
+
+    let mut commit = CommitBuilder::new(header.chain_id.clone(), header.height, block_id);
+    for info in validator_set.validators() {
+        let keypair = keypairs
+            .iter()
+            .find(|keypair| PublicKey::from(keypair.public) == info.pub_key);
+        commit = match keypair {
+            Some(keypair) => commit.sign(keypair, header.time),
+            None => commit.absent(),
+        };
+    }
+
+    SignedHeader::new(header, commit.build().unwrap()).unwrap()
+}
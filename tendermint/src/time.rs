@@ -1,6 +1,6 @@
 //! Timestamps used by Tendermint blockchains
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 use std::fmt;
@@ -57,6 +57,54 @@ impl Time {
         UNIX_EPOCH.into()
     }
 
+    /// Create a [`Time`] from the number of seconds and nanoseconds since the
+    /// [`UNIX_EPOCH`], as found in a protobuf `Timestamp`
+    pub fn from_unix_timestamp(secs: i64, nanos: u32) -> Result<Self, Error> {
+        if nanos >= 1_000_000_000 {
+            return Err(Error::invalid_timestamp(format!(
+                "nanoseconds out of range: {}",
+                nanos
+            )));
+        }
+
+        Utc.timestamp_opt(secs, nanos)
+            .single()
+            .map(Time)
+            .ok_or_else(|| Error::invalid_timestamp(format!("seconds out of range: {}", secs)))
+    }
+
+    /// Number of whole seconds since the [`UNIX_EPOCH`], negative for earlier times
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Number of nanoseconds since the [`UNIX_EPOCH`], negative for earlier times
+    pub fn unix_timestamp_nanos(&self) -> i128 {
+        self.0.timestamp() as i128 * 1_000_000_000 + self.0.timestamp_subsec_nanos() as i128
+    }
+
+    /// Add the given [`Duration`] to this [`Time`], returning `None` on overflow
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        let duration = chrono::Duration::from_std(duration).ok()?;
+        self.0.checked_add_signed(duration).map(Time)
+    }
+
+    /// Subtract the given [`Duration`] from this [`Time`], returning `None` on overflow
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        let duration = chrono::Duration::from_std(duration).ok()?;
+        self.0.checked_sub_signed(duration).map(Time)
+    }
+
+    /// Whether this [`Time`] is strictly earlier than the other one
+    pub fn before(&self, other: Time) -> bool {
+        *self < other
+    }
+
+    /// Whether this [`Time`] is strictly later than the other one
+    pub fn after(&self, other: Time) -> bool {
+        *self > other
+    }
+
     /// Calculate the amount of time which has passed since another [`Time`]
     /// as a [`std::time::Duration`]
     pub fn duration_since(&self, other: Time) -> Result<Duration, Error> {
@@ -66,7 +114,7 @@ impl Time {
             .map_err(Error::out_of_range)
     }
 
-    /// Parse [`Time`] from an RFC 3339 date
+    /// Parse [`Time`] from an RFC 3339 date, with up to nanosecond precision
     pub fn parse_from_rfc3339(s: &str) -> Result<Time, Error> {
         let date = DateTime::parse_from_rfc3339(s)
             .map_err(Error::chrono_parse)?
@@ -74,7 +122,8 @@ impl Time {
         Ok(Time(date))
     }
 
-    /// Return an RFC 3339 and ISO 8601 date and time string with 6 subseconds digits and Z.
+    /// Return an RFC 3339 and ISO 8601 date and time string in UTC, with up to 9
+    /// subsecond digits and no trailing zeros, as done by Go's `time.RFC3339Nano`.
     pub fn as_rfc3339(&self) -> String {
         timestamp::as_rfc3339_nanos(&self.0)
     }
@@ -118,21 +167,23 @@ impl From<Time> for SystemTime {
     }
 }
 
+/// Panics on overflow, see [`Time::checked_add`] for a non-panicking version.
 impl Add<Duration> for Time {
     type Output = Self;
 
     fn add(self, rhs: Duration) -> Self::Output {
-        let st: SystemTime = self.into();
-        (st + rhs).into()
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to time")
     }
 }
 
+/// Panics on overflow, see [`Time::checked_sub`] for a non-panicking version.
 impl Sub<Duration> for Time {
     type Output = Self;
 
     fn sub(self, rhs: Duration) -> Self::Output {
-        let st: SystemTime = self.into();
-        (st - rhs).into()
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from time")
     }
 }
 
@@ -175,6 +226,71 @@ mod tests {
         select(strs)
     }
 
+    #[test]
+    fn formats_like_go_rfc3339_nanos() {
+        let cases = [
+            ("2020-09-14T16:33:00Z", "2020-09-14T16:33:00Z"),
+            ("2020-09-14T16:33:00.100Z", "2020-09-14T16:33:00.1Z"),
+            (
+                "2021-01-07T20:25:59.000955200Z",
+                "2021-01-07T20:25:59.0009552Z",
+            ),
+            (
+                "2020-09-14T16:33:00.211914212Z",
+                "2020-09-14T16:33:00.211914212Z",
+            ),
+            ("2020-09-14T18:33:00.5+02:00", "2020-09-14T16:33:00.5Z"),
+        ];
+
+        for (input, expected) in cases.iter() {
+            assert_eq!(input.parse::<Time>().unwrap().to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        let time = Time::from_unix_timestamp(1_600_101_180, 211_914_212).unwrap();
+        assert_eq!(time.to_string(), "2020-09-14T16:33:00.211914212Z");
+        assert_eq!(time.unix_timestamp(), 1_600_101_180);
+        assert_eq!(time.unix_timestamp_nanos(), 1_600_101_180_211_914_212);
+
+        let before_epoch = Time::from_unix_timestamp(-1, 500_000_000).unwrap();
+        assert_eq!(before_epoch.to_string(), "1969-12-31T23:59:59.5Z");
+        assert_eq!(before_epoch.unix_timestamp_nanos(), -500_000_000);
+
+        assert!(Time::from_unix_timestamp(0, 1_000_000_000).is_err());
+        assert!(Time::from_unix_timestamp(i64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let epoch = Time::unix_epoch();
+        let later = epoch.checked_add(Duration::from_millis(1500)).unwrap();
+        assert_eq!(later.to_string(), "1970-01-01T00:00:01.5Z");
+        assert_eq!(later.checked_sub(Duration::from_millis(1500)), Some(epoch));
+        assert_eq!(
+            later.duration_since(epoch).unwrap(),
+            Duration::from_millis(1500)
+        );
+        assert!(epoch.duration_since(later).is_err());
+
+        assert!(epoch.before(later));
+        assert!(later.after(epoch));
+        assert!(!epoch.after(epoch));
+
+        assert_eq!(epoch.checked_add(Duration::from_secs(u64::MAX)), None);
+        assert_eq!(epoch.checked_sub(Duration::from_secs(u64::MAX)), None);
+    }
+
+    #[test]
+    fn system_time_conversions() {
+        let system_time = UNIX_EPOCH + Duration::new(1_600_101_180, 211_914_212);
+        let time = Time::from(system_time);
+        assert_eq!(time.to_string(), "2020-09-14T16:33:00.211914212Z");
+        assert_eq!(SystemTime::from(time), system_time);
+        assert_eq!(Time::from(DateTime::<Utc>::from(time)), time);
+    }
+
     proptest! {
         #[test]
         fn can_parse_rfc3339_timestamps(stamp in pbt::time::arb_rfc3339_timestamp()) {
@@ -188,7 +304,7 @@ mod tests {
             // If `from_value` is the inverse of `to_value`, then it will always
             // map the JSON `encoded_time` to back to the inital `time`.
             let time: Time = datetime.into();
-            let json_encoded_time = serde_json::to_value(&time).unwrap();
+            let json_encoded_time = serde_json::to_value(time).unwrap();
             let decoded_time: Time = serde_json::from_value(json_encoded_time).unwrap();
             prop_assert_eq!(time, decoded_time);
         }
@@ -205,7 +321,7 @@ mod tests {
             // arbitrarily generated textual timestamps, rather than times in a
             // range. Tho we do incidentally test the inversion as well.
             let time: Time = stamp.parse().unwrap();
-            let json_encoded_time = serde_json::to_value(&time).unwrap();
+            let json_encoded_time = serde_json::to_value(time).unwrap();
             let decoded_time: Time = serde_json::from_value(json_encoded_time).unwrap();
             prop_assert_eq!(time, decoded_time);
         }
@@ -97,7 +97,9 @@ impl From<TrustThresholdFraction> for RawTrustThresholdFraction {
 
 impl TrustThreshold for TrustThresholdFraction {
     fn is_enough_power(&self, signed_voting_power: u64, total_voting_power: u64) -> bool {
-        signed_voting_power * self.denominator > total_voting_power * self.numerator
+        // Compare the fractions without rounding, nor overflowing
+        u128::from(signed_voting_power) * u128::from(self.denominator)
+            > u128::from(total_voting_power) * u128::from(self.numerator)
     }
 }
 
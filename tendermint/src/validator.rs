@@ -5,18 +5,67 @@ use subtle_encoding::base64;
 
 use crate::{account, hash::Hash, merkle, vote, Error, PublicKey, Signature};
 
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+use std::fmt;
 use tendermint_proto::types::SimpleValidator as RawSimpleValidator;
 use tendermint_proto::types::Validator as RawValidator;
 use tendermint_proto::types::ValidatorSet as RawValidatorSet;
 use tendermint_proto::Protobuf;
 
+/// Maximum total voting power of a validator set
+pub const MAX_TOTAL_VOTING_POWER: u64 = i64::MAX as u64 / 8;
+
 /// Validator set contains a vector of validators
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "SetFields")]
 pub struct Set {
     validators: Vec<Info>,
     proposer: Option<Info>,
     total_voting_power: vote::Power,
+    /// Indices of the validators by address, to look them up in constant time
+    #[serde(skip)]
+    indices: HashMap<account::Id, usize>,
+}
+
+/// The serialized fields of a validator set, from which its indices are built
+#[derive(Deserialize)]
+struct SetFields {
+    validators: Vec<Info>,
+    proposer: Option<Info>,
+    // Validator sets are serialized without it in consensus states
+    #[serde(default)]
+    total_voting_power: Option<vote::Power>,
+}
+
+impl TryFrom<SetFields> for Set {
+    type Error = Error;
+
+    fn try_from(fields: SetFields) -> Result<Self, Error> {
+        let total_voting_power = match fields.total_voting_power {
+            Some(total_voting_power) => total_voting_power,
+            None => Set::checked_total_voting_power(&fields.validators)?,
+        };
+
+        let mut set = Set {
+            validators: fields.validators,
+            proposer: fields.proposer,
+            total_voting_power,
+            indices: HashMap::new(),
+        };
+        set.index_validators();
+        Ok(set)
+    }
+}
+
+impl fmt::Debug for Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Set")
+            .field("validators", &self.validators)
+            .field("proposer", &self.proposer)
+            .field("total_voting_power", &self.total_voting_power)
+            .finish()
+    }
 }
 
 impl Protobuf<RawValidatorSet> for Set {}
@@ -28,14 +77,26 @@ impl TryFrom<RawValidatorSet> for Set {
         let validators = value
             .validators
             .into_iter()
-            .map(TryInto::try_into)
+            .enumerate()
+            .map(|(index, validator)| {
+                validator
+                    .try_into()
+                    .map_err(|e| Error::field(&format!("validators[{}]", index), e))
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let proposer = value.proposer.map(TryInto::try_into).transpose()?;
-        let validator_set = Self::new(validators, proposer);
+        let proposer = value
+            .proposer
+            .map(TryInto::try_into)
+            .transpose()
+            .map_err(|e| Error::field("proposer", e))?;
+        let validator_set = Self::new(validators, proposer)?;
 
         // Ensure that the raw voting power matches the computed one
-        let raw_voting_power = value.total_voting_power.try_into()?;
+        let raw_voting_power = value
+            .total_voting_power
+            .try_into()
+            .map_err(|e| Error::field("total_voting_power", e))?;
         if raw_voting_power != validator_set.total_voting_power() {
             return Err(Error::raw_voting_power_mismatch(
                 raw_voting_power,
@@ -58,27 +119,25 @@ impl From<Set> for RawValidatorSet {
 }
 
 impl Set {
-    /// Constructor
-    pub fn new(mut validators: Vec<Info>, proposer: Option<Info>) -> Set {
+    /// Constructor, failing if the total voting power of the validators
+    /// exceeds [`MAX_TOTAL_VOTING_POWER`]
+    pub fn new(mut validators: Vec<Info>, proposer: Option<Info>) -> Result<Set, Error> {
         Self::sort_validators(&mut validators);
 
-        // Compute the total voting power
-        let total_voting_power = validators
-            .iter()
-            .map(|v| v.power.value())
-            .sum::<u64>()
-            .try_into()
-            .unwrap();
+        let total_voting_power = Self::checked_total_voting_power(&validators)?;
 
-        Set {
+        let mut set = Set {
             validators,
             proposer,
             total_voting_power,
-        }
+            indices: HashMap::new(),
+        };
+        set.index_validators();
+        Ok(set)
     }
 
     /// Convenience constructor for cases where there is no proposer
-    pub fn without_proposer(validators: Vec<Info>) -> Set {
+    pub fn without_proposer(validators: Vec<Info>) -> Result<Set, Error> {
         Self::new(validators, None)
     }
 
@@ -96,7 +155,7 @@ impl Set {
 
         // Create the validator set with the given proposer.
         // This is required by IBC on-chain validation.
-        Ok(Self::new(validators, Some(proposer)))
+        Self::new(validators, Some(proposer))
     }
 
     /// Get Info of the underlying validators.
@@ -114,18 +173,38 @@ impl Set {
         self.total_voting_power
     }
 
+    /// The total voting power of the given validators, failing if it exceeds
+    /// [`MAX_TOTAL_VOTING_POWER`]
+    fn checked_total_voting_power(validators: &[Info]) -> Result<vote::Power, Error> {
+        vote::Power::checked_total(validators.iter().map(|v| v.power))
+    }
+
     /// Sort the validators according to the current Tendermint requirements
     /// (v. 0.34 -> first by validator power, descending, then by address, ascending)
-    fn sort_validators(vals: &mut Vec<Info>) {
+    fn sort_validators(vals: &mut [Info]) {
         vals.sort_by_key(|v| (std::cmp::Reverse(v.power), v.address));
     }
 
+    /// Index the validators by address, once they are sorted
+    fn index_validators(&mut self) {
+        self.indices = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| (validator.address, index))
+            .collect();
+    }
+
+    /// Returns the index in the Set of the validator with the given Id, if
+    /// it is in the Set.
+    pub fn validator_index(&self, val_id: account::Id) -> Option<usize> {
+        self.indices.get(&val_id).copied()
+    }
+
     /// Returns the validator with the given Id if its in the Set.
     pub fn validator(&self, val_id: account::Id) -> Option<Info> {
-        self.validators
-            .iter()
-            .find(|val| val.address == val_id)
-            .cloned()
+        self.validator_index(val_id)
+            .map(|index| self.validators[index].clone())
     }
 
     /// Compute the hash of this validator set
@@ -138,6 +217,242 @@ impl Set {
 
         Hash::Sha256(merkle::simple_hash_from_byte_vectors(validator_bytes))
     }
+
+    /// Find the validator with the highest proposer priority, ties being broken
+    /// in favor of the lowest address, as Tendermint does to find the proposer
+    /// of a validator set.
+    ///
+    /// See <https://github.com/tendermint/tendermint/blob/v0.34.11/types/validator_set.go#L259>
+    pub fn find_proposer(&self) -> Option<&Info> {
+        self.validators
+            .iter()
+            .fold(None, |proposer, validator| match proposer {
+                Some(proposer) if Self::has_priority_over(proposer, validator) => Some(proposer),
+                _ => Some(validator),
+            })
+    }
+
+    /// Increment the proposer priorities of the validators `times` times, ie. advance
+    /// the proposer selection by as many rounds, and set the proposer of the set to the
+    /// proposer of the last round. Does nothing if `times` is zero or the set is empty.
+    ///
+    /// This matches the proposer selection performed by Tendermint, including the
+    /// rescaling of the priorities and their centering around zero beforehand.
+    ///
+    /// See <https://github.com/tendermint/tendermint/blob/v0.34.11/types/validator_set.go#L118>
+    pub fn increment_proposer_priority(&mut self, times: u32) {
+        if times == 0 || self.validators.is_empty() {
+            return;
+        }
+
+        // Cap the difference between priorities to be proportional
+        // to twice the total voting power, by re-normalizing them.
+        let total_voting_power = self.total_voting_power_i64();
+        self.rescale_priorities(total_voting_power.saturating_mul(2));
+        self.shift_by_average_priority();
+
+        let mut proposer = 0;
+        for _ in 0..times {
+            for validator in self.validators.iter_mut() {
+                let priority = validator.proposer_priority.value();
+                validator.proposer_priority = priority
+                    .saturating_add(validator.power.value() as i64)
+                    .into();
+            }
+
+            proposer = (1..self.validators.len()).fold(0, |proposer, i| {
+                if Self::has_priority_over(&self.validators[proposer], &self.validators[i]) {
+                    proposer
+                } else {
+                    i
+                }
+            });
+
+            let priority = self.validators[proposer].proposer_priority.value();
+            self.validators[proposer].proposer_priority =
+                priority.saturating_sub(total_voting_power).into();
+        }
+
+        self.proposer = Some(self.validators[proposer].clone());
+    }
+
+    /// Apply the given updates to the validator set, as done by Tendermint
+    /// when processing the validator updates returned by `EndBlock`.
+    ///
+    /// Updates with a voting power of zero remove the corresponding validator,
+    /// the other ones either change the voting power of a validator of the set
+    /// or add a new validator. A new validator starts with a proposer priority
+    /// of about `-1.125` times the total voting power, so that validators cannot
+    /// reset a negative priority by unbonding and bonding again. The priorities
+    /// are then rescaled and centered around zero, and the validators sorted.
+    ///
+    /// The set is left untouched if an update is duplicated, removes a validator
+    /// not in the set, or if the resulting set would be empty or have a total
+    /// voting power greater than [`MAX_TOTAL_VOTING_POWER`].
+    ///
+    /// See <https://github.com/tendermint/tendermint/blob/v0.34.11/types/validator_set.go#L586>
+    pub fn apply_updates(&mut self, updates: Vec<Update>) -> Result<(), Error> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let invalid = |detail: String| Err(Error::invalid_validator_set_update(detail));
+
+        let mut changes: Vec<(account::Id, Update)> = updates
+            .into_iter()
+            .map(|update| (account::Id::from(update.pub_key), update))
+            .collect();
+        changes.sort_by_key(|(address, _)| *address);
+
+        if let Some(pair) = changes.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return invalid(format!("duplicate update for validator {}", pair[0].0));
+        }
+
+        if let Some((address, _)) = changes
+            .iter()
+            .find(|(_, update)| update.power.value() > MAX_TOTAL_VOTING_POWER)
+        {
+            return invalid(format!(
+                "voting power of validator {} exceeds the maximum of {}",
+                address, MAX_TOTAL_VOTING_POWER
+            ));
+        }
+
+        let (removals, updates): (Vec<_>, Vec<_>) = changes
+            .into_iter()
+            .partition(|(_, update)| update.power.value() == 0);
+
+        let mut removed_power: i128 = 0;
+        for (address, _) in &removals {
+            match self.validator_index(*address) {
+                Some(index) => removed_power += self.validators[index].power() as i128,
+                None => return invalid(format!("cannot remove unknown validator {}", address)),
+            }
+        }
+
+        let added = updates
+            .iter()
+            .filter(|(address, _)| self.validator_index(*address).is_none())
+            .count();
+        if added == 0 && removals.len() == self.validators.len() {
+            return invalid("applying the updates would result in an empty set".to_string());
+        }
+
+        let power_change: i128 = updates
+            .iter()
+            .map(|(address, update)| {
+                let current = self
+                    .validator_index(*address)
+                    .map_or(0, |index| self.validators[index].power());
+                update.power.value() as i128 - current as i128
+            })
+            .sum();
+
+        let total_after_updates = self.total_voting_power.value() as i128 + power_change;
+        if total_after_updates - removed_power > MAX_TOTAL_VOTING_POWER as i128 {
+            return invalid(format!(
+                "total voting power would exceed the maximum of {}",
+                MAX_TOTAL_VOTING_POWER
+            ));
+        }
+
+        // Tendermint computes the priority of new validators with the total
+        // voting power after the updates, but before the removals.
+        let total_after_updates = total_after_updates as i64;
+        let new_priority =
+            ProposerPriority::from(-(total_after_updates + (total_after_updates >> 3)));
+
+        for (address, update) in updates {
+            match self.validator_index(address) {
+                Some(index) => {
+                    let validator = &mut self.validators[index];
+                    validator.pub_key = update.pub_key;
+                    validator.power = update.power;
+                }
+                None => self.validators.push(Info {
+                    proposer_priority: new_priority,
+                    ..Info::new(update.pub_key, update.power)
+                }),
+            }
+        }
+
+        self.validators
+            .retain(|v| removals.iter().all(|(address, _)| v.address != *address));
+
+        self.total_voting_power = Self::checked_total_voting_power(&self.validators)?;
+
+        let total_voting_power = self.total_voting_power_i64();
+        self.rescale_priorities(total_voting_power.saturating_mul(2));
+        self.shift_by_average_priority();
+        Self::sort_validators(&mut self.validators);
+        self.index_validators();
+
+        // Keep the proposer in sync with its entry in the updated set.
+        self.proposer = self
+            .proposer
+            .take()
+            .and_then(|proposer| self.validator(proposer.address));
+
+        Ok(())
+    }
+
+    /// Whether the given validator is to propose rather than the other one.
+    fn has_priority_over(validator: &Info, other: &Info) -> bool {
+        (
+            validator.proposer_priority,
+            std::cmp::Reverse(validator.address),
+        ) > (other.proposer_priority, std::cmp::Reverse(other.address))
+    }
+
+    fn total_voting_power_i64(&self) -> i64 {
+        // The total voting power is bounded by `i64::MAX / 8` in Tendermint.
+        self.total_voting_power
+            .value()
+            .try_into()
+            .unwrap_or(i64::MAX)
+    }
+
+    /// Divide the priorities by the smallest factor which brings the difference
+    /// between the highest and the lowest priority within `max_diff`.
+    fn rescale_priorities(&mut self, max_diff: i64) {
+        if max_diff <= 0 {
+            return;
+        }
+
+        let priorities = self.validators.iter().map(|v| v.proposer_priority.value());
+        let (min, max) = match (priorities.clone().min(), priorities.max()) {
+            (Some(min), Some(max)) => (min as i128, max as i128),
+            _ => return,
+        };
+
+        let diff = max - min;
+        let max_diff = max_diff as i128;
+
+        if diff > max_diff {
+            let ratio = (diff + max_diff - 1) / max_diff;
+            for validator in self.validators.iter_mut() {
+                let priority = validator.proposer_priority.value() as i128 / ratio;
+                validator.proposer_priority = (priority as i64).into();
+            }
+        }
+    }
+
+    /// Subtract the average priority from all priorities, to center them around zero.
+    fn shift_by_average_priority(&mut self) {
+        let sum: i128 = self
+            .validators
+            .iter()
+            .map(|v| v.proposer_priority.value() as i128)
+            .sum();
+
+        // Tendermint computes the average with an Euclidean division.
+        let average = sum.div_euclid(self.validators.len() as i128) as i64;
+
+        for validator in self.validators.iter_mut() {
+            let priority = validator.proposer_priority.value();
+            validator.proposer_priority = priority.saturating_sub(average).into();
+        }
+    }
 }
 
 /// Validator information
@@ -163,17 +478,26 @@ pub struct Info {
     pub proposer_priority: ProposerPriority,
 }
 
+impl Protobuf<RawValidator> for Info {}
+
 impl TryFrom<RawValidator> for Info {
     type Error = Error;
 
     fn try_from(value: RawValidator) -> Result<Self, Self::Error> {
         Ok(Info {
-            address: value.address.try_into()?,
+            address: value
+                .address
+                .try_into()
+                .map_err(|e| Error::field("address", e))?,
             pub_key: value
                 .pub_key
                 .ok_or_else(Error::missing_public_key)?
-                .try_into()?,
-            power: value.voting_power.try_into()?,
+                .try_into()
+                .map_err(|e| Error::field("pub_key", e))?,
+            power: value
+                .voting_power
+                .try_into()
+                .map_err(|e| Error::field("voting_power", e))?,
             name: None,
             proposer_priority: value.proposer_priority.into(),
         })
@@ -204,16 +528,6 @@ impl Info {
     }
 }
 
-impl From<PublicKey> for account::Id {
-    fn from(pub_key: PublicKey) -> account::Id {
-        match pub_key {
-            PublicKey::Ed25519(pk) => account::Id::from(pk),
-            #[cfg(feature = "secp256k1")]
-            PublicKey::Secp256k1(pk) => account::Id::from(pk),
-        }
-    }
-}
-
 impl Info {
     /// Create a new validator.
     pub fn new(pk: PublicKey, vp: vote::Power) -> Info {
@@ -248,7 +562,10 @@ impl TryFrom<RawSimpleValidator> for SimpleValidator {
     fn try_from(value: RawSimpleValidator) -> Result<Self, Self::Error> {
         Ok(SimpleValidator {
             pub_key: value.pub_key,
-            voting_power: value.voting_power.try_into()?,
+            voting_power: value
+                .voting_power
+                .try_into()
+                .map_err(|e| Error::field("voting_power", e))?,
         })
     }
 }
@@ -273,6 +590,9 @@ impl From<&Info> for SimpleValidator {
             PublicKey::Secp256k1(pk) => Some(tendermint_proto::crypto::public_key::Sum::Secp256k1(
                 pk.to_bytes().to_vec(),
             )),
+            // Encoded by `Info::hash_bytes` instead
+            #[cfg(feature = "sr25519")]
+            PublicKey::Sr25519(_) => None,
         };
         SimpleValidator {
             pub_key: Some(tendermint_proto::crypto::PublicKey { sum }),
@@ -285,10 +605,36 @@ impl Info {
     /// Returns the bytes to be hashed into the Merkle tree -
     /// the leaves of the tree.
     pub fn hash_bytes(&self) -> Vec<u8> {
+        #[cfg(feature = "sr25519")]
+        if let PublicKey::Sr25519(pk) = &self.pub_key {
+            return sr25519_hash_bytes(pk, self.power);
+        }
         SimpleValidator::from(self).encode_vec().unwrap()
     }
 }
 
+/// The `SimpleValidator` encoding of a validator with the given Sr25519 key,
+/// which is the field 3 of the `PublicKey` message in Tendermint v0.35 and in
+/// the forks using such keys, but is missing from the Protobuf definitions of
+/// Tendermint v0.34 used by this crate
+#[cfg(feature = "sr25519")]
+fn sr25519_hash_bytes(pk: &crate::public_key::Sr25519, power: vote::Power) -> Vec<u8> {
+    let mut pub_key = Vec::new();
+    prost::encoding::bytes::encode(3, &pk.as_bytes().to_vec(), &mut pub_key);
+
+    let mut bytes = Vec::new();
+    prost::encoding::bytes::encode(1, &pub_key, &mut bytes);
+    bytes.extend(
+        SimpleValidator {
+            pub_key: None,
+            voting_power: power,
+        }
+        .encode_vec()
+        .unwrap(),
+    );
+    bytes
+}
+
 // Todo: Is there more knowledge/restrictions about proposerPriority?
 /// Proposer priority
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Default)]
@@ -336,6 +682,16 @@ enum Pk {
     /// Ed25519 keys
     #[serde(rename = "ed25519")]
     Ed25519(String),
+
+    /// Secp256k1 keys
+    #[cfg(feature = "secp256k1")]
+    #[serde(rename = "secp256k1")]
+    Secp256k1(String),
+
+    /// Sr25519 keys
+    #[cfg(feature = "sr25519")]
+    #[serde(rename = "sr25519")]
+    Sr25519(String),
 }
 
 fn deserialize_public_key<'de, D>(deserializer: D) -> Result<PublicKey, D::Error>
@@ -350,6 +706,22 @@ where
             PublicKey::from_raw_ed25519(&bytes)
                 .ok_or_else(|| D::Error::custom("error parsing Ed25519 key"))
         }
+        #[cfg(feature = "sr25519")]
+        Pk::Sr25519(base64_value) => {
+            let bytes =
+                base64::decode(base64_value).map_err(|e| D::Error::custom(format!("{}", e)))?;
+
+            PublicKey::from_raw_sr25519(&bytes)
+                .ok_or_else(|| D::Error::custom("error parsing sr25519 key"))
+        }
+        #[cfg(feature = "secp256k1")]
+        Pk::Secp256k1(base64_value) => {
+            let bytes =
+                base64::decode(base64_value).map_err(|e| D::Error::custom(format!("{}", e)))?;
+
+            PublicKey::from_raw_secp256k1(&bytes)
+                .ok_or_else(|| D::Error::custom("error parsing secp256k1 key"))
+        }
     }
 }
 
@@ -416,7 +788,7 @@ mod tests {
             22, 57, 84, 71, 122, 200, 169, 192, 252, 41, 148, 223, 180,
         ];
 
-        let val_set = Set::without_proposer(vec![v1.clone(), v2.clone(), v3.clone()]);
+        let val_set = Set::without_proposer(vec![v1.clone(), v2.clone(), v3.clone()]).unwrap();
         let hash = val_set.hash();
         assert_eq!(hash_expect, hash.as_bytes().to_vec());
 
@@ -437,4 +809,238 @@ mod tests {
             148_151_478_422_287_875 + 158_095_448_483_785_107 + 770_561_664_770_006_272
         );
     }
+
+    // make a validator with the given name as address
+    fn make_named_validator(name: &str, vp: u64) -> Info {
+        let mut address = [0; 20];
+        address[..name.len()].copy_from_slice(name.as_bytes());
+
+        let mut validator = make_validator(vec![name.len() as u8; 32], vp);
+        validator.address = account::Id::new(address);
+        validator
+    }
+
+    fn names(val_set: &Set, proposers: impl Iterator<Item = account::Id>) -> String {
+        proposers
+            .map(|address| {
+                let name = val_set
+                    .validators()
+                    .iter()
+                    .find(|v| v.address == address)
+                    .map(|v| {
+                        v.address
+                            .as_bytes()
+                            .iter()
+                            .take_while(|&&b| b != 0)
+                            .copied()
+                    })
+                    .unwrap()
+                    .collect::<Vec<u8>>();
+                String::from_utf8(name).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_proposer_selection() {
+        // test vector from `TestProposerSelection1` in the Go code
+        let mut val_set = Set::without_proposer(vec![
+            make_named_validator("foo", 1000),
+            make_named_validator("bar", 300),
+            make_named_validator("baz", 330),
+        ])
+        .unwrap();
+
+        // as done by `NewValidatorSet` in the Go code
+        val_set.increment_proposer_priority(1);
+
+        let mut proposers = Vec::new();
+        for _ in 0..99 {
+            proposers.push(val_set.proposer().as_ref().unwrap().address);
+            val_set.increment_proposer_priority(1);
+        }
+
+        let expected = "foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo bar \
+                        foo foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo bar \
+                        foo baz foo foo bar foo baz foo foo bar foo baz foo foo foo baz bar foo foo foo baz \
+                        foo bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo \
+                        foo bar foo baz foo foo bar foo baz foo foo bar foo baz foo foo";
+
+        assert_eq!(names(&val_set, proposers.into_iter()), expected);
+    }
+
+    #[test]
+    fn test_find_proposer() {
+        let mut val_set = Set::without_proposer(vec![
+            make_named_validator("foo", 10),
+            make_named_validator("bar", 10),
+        ])
+        .unwrap();
+
+        // ties are broken in favor of the lowest address
+        let proposer = val_set.find_proposer().unwrap().address;
+        assert_eq!(names(&val_set, std::iter::once(proposer)), "bar");
+
+        val_set.increment_proposer_priority(1);
+        assert_eq!(
+            val_set.proposer().as_ref().map(|v| v.address),
+            Some(proposer)
+        );
+
+        // the former proposer now has the lowest priority
+        let proposer = val_set.find_proposer().unwrap().address;
+        assert_eq!(names(&val_set, std::iter::once(proposer)), "foo");
+    }
+
+    // make a validator with a key derived from the given seed
+    fn make_seeded_validator(seed: u8, vp: u64) -> Info {
+        let public = crate::test::keypair(seed).public;
+        make_validator(public.as_bytes().to_vec(), vp)
+    }
+
+    fn update(validator: &Info, vp: u64) -> Update {
+        Update {
+            pub_key: validator.pub_key,
+            power: vote::Power::try_from(vp).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_validator_index() {
+        let foo = make_seeded_validator(1, 10);
+        let bar = make_seeded_validator(2, 20);
+        let baz = make_seeded_validator(3, 30);
+
+        let assert_indexed = |val_set: &Set| {
+            for (index, validator) in val_set.validators().iter().enumerate() {
+                assert_eq!(val_set.validator_index(validator.address), Some(index));
+            }
+        };
+
+        let mut val_set = Set::without_proposer(vec![foo.clone(), bar.clone()]).unwrap();
+        assert_indexed(&val_set);
+        assert_eq!(val_set.validator_index(baz.address), None);
+
+        // The validators are sorted again once updated
+        val_set
+            .apply_updates(vec![update(&baz, 30), update(&foo, 0)])
+            .unwrap();
+        assert_indexed(&val_set);
+        assert_eq!(val_set.validator_index(baz.address), Some(0));
+        assert_eq!(val_set.validator_index(foo.address), None);
+
+        // The indices are rebuilt when deserializing
+        let deserialized: Set =
+            serde_json::from_str(&serde_json::to_string(&val_set).unwrap()).unwrap();
+        assert_eq!(deserialized.validator_index(bar.address), Some(1));
+        assert_eq!(deserialized.hash(), val_set.hash());
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let foo = make_seeded_validator(1, 10);
+        let bar = make_seeded_validator(2, 20);
+        let baz = make_seeded_validator(3, 30);
+
+        let mut val_set = Set::without_proposer(vec![foo.clone(), bar.clone()]).unwrap();
+        val_set
+            .apply_updates(vec![update(&baz, 30), update(&foo, 0)])
+            .unwrap();
+
+        // baz starts with a priority of -(60 + 60 / 8), then the priorities
+        // are shifted by their average, ie. -34.
+        let priorities: Vec<_> = val_set
+            .validators()
+            .iter()
+            .map(|v| (v.address, v.power(), v.proposer_priority.value()))
+            .collect();
+        assert_eq!(
+            priorities,
+            vec![(baz.address, 30, -33), (bar.address, 20, 34)]
+        );
+        assert_eq!(val_set.total_voting_power().value(), 50);
+    }
+
+    #[test]
+    fn test_apply_updates_keeps_priorities() {
+        let foo = make_seeded_validator(1, 10);
+        let bar = make_seeded_validator(2, 20);
+
+        let mut val_set = Set::without_proposer(vec![foo.clone(), bar.clone()]).unwrap();
+        val_set.increment_proposer_priority(1);
+        assert_eq!(val_set.proposer().as_ref().unwrap().address, bar.address);
+
+        val_set.apply_updates(vec![update(&foo, 40)]).unwrap();
+
+        let priorities: Vec<_> = val_set
+            .validators()
+            .iter()
+            .map(|v| (v.address, v.power(), v.proposer_priority.value()))
+            .collect();
+        assert_eq!(
+            priorities,
+            vec![(foo.address, 40, 10), (bar.address, 20, -10)]
+        );
+        assert_eq!(
+            val_set.proposer().as_ref(),
+            val_set.validator(bar.address).as_ref()
+        );
+    }
+
+    #[test]
+    fn test_apply_invalid_updates() {
+        let foo = make_seeded_validator(1, 10);
+        let bar = make_seeded_validator(2, 20);
+        let baz = make_seeded_validator(3, 30);
+
+        let val_set = Set::without_proposer(vec![foo.clone(), bar.clone()]).unwrap();
+
+        let invalid_updates = vec![
+            vec![update(&baz, 30), update(&baz, 40)],
+            vec![update(&baz, 0)],
+            vec![update(&foo, 0), update(&bar, 0)],
+            vec![update(&baz, MAX_TOTAL_VOTING_POWER - 20)],
+            vec![update(&baz, MAX_TOTAL_VOTING_POWER + 1)],
+        ];
+
+        for updates in invalid_updates {
+            let mut updated = val_set.clone();
+            assert!(updated.apply_updates(updates).is_err());
+            assert_eq!(updated, val_set);
+        }
+    }
+
+    #[test]
+    fn test_total_voting_power_overflow() {
+        let foo = make_seeded_validator(1, MAX_TOTAL_VOTING_POWER);
+        let bar = make_seeded_validator(2, 1);
+
+        assert!(Set::without_proposer(vec![foo.clone()]).is_ok());
+        assert!(Set::without_proposer(vec![foo.clone(), bar.clone()]).is_err());
+
+        // Validator sets serialized without their total voting power
+        let json = serde_json::json!({
+            "validators": [foo, bar],
+            "proposer": null,
+        });
+        assert!(serde_json::from_value::<Set>(json).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "sr25519")]
+    fn sr25519_validator_hash_bytes() {
+        // The compressed Ristretto basepoint
+        let key = base64::decode("4vKuCmq8TnGohKlhxQBRX1jjC2qlgt2NtqZZReCNLXY=").unwrap();
+        let info = Info::new(
+            PublicKey::from_raw_sr25519(&key).unwrap(),
+            vote::Power::from(10_u32),
+        );
+
+        // SimpleValidator { pub_key: PublicKey { sr25519: key }, voting_power: 10 }
+        let mut expected = vec![0x0a, 34, 0x1a, 32];
+        expected.extend(&key);
+        expected.extend(&[0x10, 10]);
+        assert_eq!(info.hash_bytes(), expected);
+    }
 }
@@ -6,14 +6,15 @@ mod sign_vote;
 mod validator_index;
 
 pub use self::canonical_vote::CanonicalVote;
-pub use self::power::Power;
+pub use self::power::{Power, VotingPowerTally};
 pub use self::sign_vote::*;
 pub use self::validator_index::ValidatorIndex;
 use crate::chain::Id as ChainId;
+use crate::compat::CompatMode;
 use crate::consensus::State;
 use crate::error::Error;
 use crate::hash;
-use crate::{account, block, Signature, Time};
+use crate::{account, block, PublicKey, Signature, Time};
 use bytes::BufMut;
 use ed25519::Signature as ed25519Signature;
 use ed25519::SIGNATURE_LENGTH as ed25519SignatureLength;
@@ -68,19 +69,34 @@ impl TryFrom<RawVote> for Vote {
             return Err(Error::missing_timestamp());
         }
         Ok(Vote {
-            vote_type: value.r#type.try_into()?,
-            height: value.height.try_into()?,
-            round: value.round.try_into()?,
+            vote_type: value
+                .r#type
+                .try_into()
+                .map_err(|e| Error::field("type", e))?,
+            height: value
+                .height
+                .try_into()
+                .map_err(|e| Error::field("height", e))?,
+            round: value
+                .round
+                .try_into()
+                .map_err(|e| Error::field("round", e))?,
             // block_id can be nil in the Go implementation
-            block_id: value
-                .block_id
-                .map(TryInto::try_into)
-                .transpose()?
-                .filter(|i| i != &block::Id::default()),
+            block_id: block::Id::try_from_optional(value.block_id)
+                .map_err(|e| Error::field("block_id", e))?,
             timestamp: value.timestamp.map(|t| t.into()),
-            validator_address: value.validator_address.try_into()?,
-            validator_index: value.validator_index.try_into()?,
-            signature: value.signature.try_into()?,
+            validator_address: value
+                .validator_address
+                .try_into()
+                .map_err(|e| Error::field("validator_address", e))?,
+            validator_index: value
+                .validator_index
+                .try_into()
+                .map_err(|e| Error::field("validator_index", e))?,
+            signature: value
+                .signature
+                .try_into()
+                .map_err(|e| Error::field("signature", e))?,
         })
     }
 }
@@ -140,6 +156,54 @@ impl Vote {
         CanonicalVote::new(self.clone(), chain_id).encode_length_delimited_vec()
     }
 
+    /// Create the signable vector of this vote for the given version of the
+    /// protocol, ie. [`Vote::to_signable_vec`] since v0.34
+    ///
+    /// Fails for Tendermint 0.33 without the `amino` feature.
+    pub fn to_signable_vec_with_compat(
+        &self,
+        chain_id: ChainId,
+        compat: CompatMode,
+    ) -> Result<Vec<u8>, Error> {
+        match compat {
+            #[cfg(feature = "amino")]
+            CompatMode::V0_33 => Ok(self.to_amino_sign_bytes(chain_id)),
+            #[cfg(not(feature = "amino"))]
+            CompatMode::V0_33 => Err(Error::unsupported_compat_mode(compat.to_string())),
+            CompatMode::V0_34 => self
+                .to_signable_vec(chain_id)
+                .map_err(|e| Error::protocol(e.to_string())),
+        }
+    }
+
+    /// Verify that this vote was signed for the given chain by the validator
+    /// with the given public key, as done by `Vote.Verify` in Go.
+    ///
+    /// Fails if the address of the public key is not the validator address of
+    /// the vote, or if the signature is not valid for the canonical sign bytes
+    /// of the vote.
+    pub fn verify_signature(&self, chain_id: ChainId, pub_key: &PublicKey) -> Result<(), Error> {
+        self.verify_signature_with_compat(chain_id, pub_key, CompatMode::LATEST)
+    }
+
+    /// Verify that this vote was signed for the given chain by the validator
+    /// with the given public key, with the sign bytes of the given version of
+    /// the protocol (see [`Vote::to_signable_vec_with_compat`])
+    pub fn verify_signature_with_compat(
+        &self,
+        chain_id: ChainId,
+        pub_key: &PublicKey,
+        compat: CompatMode,
+    ) -> Result<(), Error> {
+        if account::Id::from(*pub_key) != self.validator_address {
+            return Err(Error::invalid_validator_address());
+        }
+
+        let sign_bytes = self.to_signable_vec_with_compat(chain_id, compat)?;
+
+        pub_key.verify(&sign_bytes, &self.signature)
+    }
+
     /// Consensus state from this vote - This doesn't seem to be used anywhere.
     #[deprecated(
         since = "0.17.0",
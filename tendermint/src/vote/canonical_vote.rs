@@ -57,14 +57,13 @@ impl TryFrom<RawCanonicalVote> for CanonicalVote {
 
 impl From<CanonicalVote> for RawCanonicalVote {
     fn from(value: CanonicalVote) -> Self {
-        // If the Hash is empty in BlockId, the BlockId should be empty.
-        // See: https://github.com/informalsystems/tendermint-rs/issues/663
-        let block_id = value.block_id.filter(|i| i != &block::Id::default());
         RawCanonicalVote {
             r#type: value.vote_type.into(),
             height: value.height.into(),
             round: value.round.value().into(),
-            block_id: block_id.map(Into::into),
+            // The nil block ID is not encoded
+            // See: https://github.com/informalsystems/tendermint-rs/issues/663
+            block_id: value.block_id.and_then(block::Id::canonicalize),
             timestamp: value.timestamp.map(Into::into),
             chain_id: value.chain_id.to_string(),
         }
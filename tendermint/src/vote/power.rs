@@ -6,6 +6,8 @@ use std::fmt;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::error::Error;
+use crate::trust_threshold::{TrustThreshold, TrustThresholdFraction};
+use crate::validator::MAX_TOTAL_VOTING_POWER;
 
 /// Voting power
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Default)]
@@ -75,6 +77,100 @@ impl Power {
     pub fn is_zero(self) -> bool {
         self.0 == 0
     }
+
+    /// Add the given voting power, or `None` if the sum is out of the range
+    /// of voting powers, ie. greater than `i64::MAX`
+    pub fn checked_add(self, other: Power) -> Option<Power> {
+        self.0
+            .checked_add(other.0)
+            .filter(|sum| *sum <= i64::MAX as u64)
+            .map(Power)
+    }
+
+    /// Subtract the given voting power, or `None` if it is greater than this
+    /// one
+    pub fn checked_sub(self, other: Power) -> Option<Power> {
+        self.0.checked_sub(other.0).map(Power)
+    }
+
+    /// Sum the given voting powers, failing if their total exceeds
+    /// [`MAX_TOTAL_VOTING_POWER`], as Tendermint does for validator sets
+    pub fn checked_total<I>(powers: I) -> Result<Power, Error>
+    where
+        I: IntoIterator<Item = Power>,
+    {
+        powers
+            .into_iter()
+            .try_fold(Power(0), |total, power| total.checked_add(power))
+            .filter(|total| total.0 <= MAX_TOTAL_VOTING_POWER)
+            .ok_or_else(Error::total_voting_power_overflow)
+    }
+}
+
+/// Tally of the voting power of the validators of a set which signed a
+/// commit, against a trust threshold
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize, Eq)]
+pub struct VotingPowerTally {
+    /// Total voting power
+    pub total: u64,
+    /// Tallied voting power
+    pub tallied: u64,
+    /// Trust threshold for voting power
+    pub trust_threshold: TrustThresholdFraction,
+}
+
+impl VotingPowerTally {
+    /// Tally of no voting power yet, out of the given total
+    pub fn new(total: Power, trust_threshold: TrustThresholdFraction) -> Self {
+        Self {
+            total: total.value(),
+            tallied: 0,
+            trust_threshold,
+        }
+    }
+
+    /// Tally the given voting power, failing if the tallied voting power
+    /// would exceed the total one
+    pub fn tally(&mut self, power: Power) -> Result<(), Error> {
+        self.tallied = self
+            .tallied
+            .checked_add(power.value())
+            .filter(|tallied| *tallied <= self.total)
+            .ok_or_else(Error::total_voting_power_overflow)?;
+        Ok(())
+    }
+
+    /// Whether the tallied voting power exceeds the trust threshold
+    pub fn is_enough(&self) -> bool {
+        self.trust_threshold
+            .is_enough_power(self.tallied, self.total)
+    }
+
+    /// The minimum voting power which must be tallied to exceed the trust threshold.
+    pub fn required(&self) -> u64 {
+        let threshold = u128::from(self.total) * u128::from(self.trust_threshold.numerator())
+            / u128::from(self.trust_threshold.denominator());
+
+        threshold as u64 + 1
+    }
+
+    /// The voting power missing to exceed the trust threshold, if any.
+    pub fn missing(&self) -> u64 {
+        self.required().saturating_sub(self.tallied)
+    }
+}
+
+impl fmt::Display for VotingPowerTally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "VotingPower(total={} tallied={} required={} trust_threshold={})",
+            self.total,
+            self.tallied,
+            self.required(),
+            self.trust_threshold
+        )
+    }
 }
 
 impl<'de> Deserialize<'de> for Power {
@@ -95,3 +191,44 @@ impl Serialize for Power {
         proto_int.to_string().serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_arithmetic() {
+        let max = Power::try_from(i64::MAX).unwrap();
+        assert_eq!(max.checked_add(Power::from(1_u8)), None);
+        assert_eq!(
+            Power::from(2_u8).checked_sub(Power::from(1_u8)),
+            Some(Power::from(1_u8))
+        );
+        assert_eq!(Power::from(1_u8).checked_sub(Power::from(2_u8)), None);
+
+        let quarter = Power::try_from(MAX_TOTAL_VOTING_POWER / 4).unwrap();
+        assert!(Power::checked_total(vec![quarter; 4]).is_ok());
+        assert!(Power::checked_total(vec![quarter; 5]).is_err());
+        assert!(Power::checked_total(vec![max, max]).is_err());
+    }
+
+    #[test]
+    fn tally_voting_power() {
+        // The comparison with the threshold must not overflow
+        let total = Power::try_from(MAX_TOTAL_VOTING_POWER).unwrap();
+        let mut tally = VotingPowerTally::new(total, TrustThresholdFraction::TWO_THIRDS);
+
+        tally
+            .tally(Power::try_from(tally.required() - 1).unwrap())
+            .unwrap();
+        assert!(!tally.is_enough());
+        assert_eq!(tally.missing(), 1);
+
+        tally.tally(Power::from(1_u8)).unwrap();
+        assert!(tally.is_enough());
+        assert_eq!(tally.missing(), 0);
+
+        // More voting power than the total cannot be tallied
+        assert!(tally.tally(total).is_err());
+    }
+}
@@ -269,6 +269,56 @@ mod tests {
         assert_eq!(got, want);
     }
 
+    #[test]
+    fn test_vote_signature_verification() {
+        use crate::test::keypair;
+        use crate::PublicKey;
+        use ed25519_dalek::Signer;
+
+        let keypair = keypair(7);
+        let pub_key = PublicKey::from(keypair.public);
+
+        let dt = "2017-12-25T03:00:01.234Z".parse::<DateTime<Utc>>().unwrap();
+        let mut vote = Vote {
+            vote_type: Type::Precommit,
+            height: Height::from(12345_u32),
+            round: Round::from(2_u16),
+            timestamp: Some(dt.into()),
+            block_id: Some(BlockId {
+                hash: Hash::try_from(b"DEADBEEFDEADBEEFBAFBAFBAFBAFBAFA".to_vec()).unwrap(),
+                part_set_header: Header::new(
+                    1_000_000,
+                    Hash::try_from(b"0022446688AACCEE1133557799BBDDFF".to_vec()).unwrap(),
+                )
+                .unwrap(),
+            }),
+            validator_address: AccountId::from(pub_key),
+            validator_index: ValidatorIndex::try_from(0).unwrap(),
+            signature: Signature::default(),
+        };
+        let chain_id = ChainId::from_str("test_chain_id").unwrap();
+
+        let sign_bytes = vote.to_signable_vec(chain_id.clone()).unwrap();
+        vote.signature = Signature::Ed25519(keypair.sign(&sign_bytes));
+
+        vote.verify_signature(chain_id.clone(), &pub_key).unwrap();
+
+        // Signed for another chain
+        assert!(vote
+            .verify_signature(ChainId::from_str("other_chain_id").unwrap(), &pub_key)
+            .is_err());
+
+        // Signed by another validator
+        let other_pub_key = PublicKey::from(crate::test::keypair(8).public);
+        assert!(vote
+            .verify_signature(chain_id.clone(), &other_pub_key)
+            .is_err());
+
+        // Tampered with after signing
+        vote.round = Round::from(3_u16);
+        assert!(vote.verify_signature(chain_id, &pub_key).is_err());
+    }
+
     #[test]
     fn test_sign_bytes_compatibility() {
         let cv = CanonicalVote::new(Vote::default(), ChainId::try_from("A").unwrap());
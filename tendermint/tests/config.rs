@@ -197,6 +197,138 @@ mod files {
         assert_eq!(instrumentation.namespace, "tendermint");
     }
 
+    /// Settings missing from older `config.toml` files take the defaults of
+    /// Tendermint Core
+    #[test]
+    fn config_toml_defaults() {
+        let config = TendermintConfig::parse_toml(read_fixture("config.toml")).unwrap();
+        assert_eq!(config.rpc.experimental_subscription_buffer_size, 200);
+        assert_eq!(config.rpc.experimental_websocket_write_buffer_size, 200);
+        assert!(!config.rpc.experimental_close_on_slow_client);
+        assert_eq!(config.mempool.version, "v0");
+        assert_eq!(*config.mempool.ttl_duration, Duration::from_secs(0));
+        assert_eq!(config.mempool.ttl_num_blocks, 0);
+        assert_eq!(
+            *config.statesync.chunk_request_timeout,
+            Duration::from_secs(10)
+        );
+        assert_eq!(config.statesync.chunk_fetchers, 4);
+        assert_eq!(config.tx_index.psql_conn, None);
+        assert!(!config.storage.discard_abci_responses);
+
+        let empty = TendermintConfig::parse_toml("").unwrap();
+        assert_eq!(empty, TendermintConfig::default());
+        assert_eq!(
+            empty.rpc.laddr,
+            "tcp://127.0.0.1:26657".parse::<net::Address>().unwrap()
+        );
+        assert_eq!(*empty.consensus.timeout_commit, Duration::from_secs(1));
+
+        let written = toml::to_string(&empty).unwrap();
+        assert_eq!(TendermintConfig::parse_toml(written).unwrap(), empty);
+    }
+
+    /// Parse the settings introduced by Tendermint v0.34
+    #[test]
+    fn config_toml_v0_34_settings() {
+        let config = TendermintConfig::parse_toml(
+            r#"
+            [rpc]
+            experimental_subscription_buffer_size = 300
+            experimental_websocket_write_buffer_size = 400
+            experimental_close_on_slow_client = true
+
+            [mempool]
+            version = "v1"
+            ttl-duration = "30s"
+            ttl-num-blocks = 10
+
+            [statesync]
+            chunk_request_timeout = "15s"
+            chunk_fetchers = "8"
+
+            [tx_index]
+            indexer = "psql"
+            psql-conn = "postgresql://tendermint@localhost:5432/tendermint"
+
+            [storage]
+            discard_abci_responses = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.rpc.experimental_subscription_buffer_size, 300);
+        assert_eq!(config.rpc.experimental_websocket_write_buffer_size, 400);
+        assert!(config.rpc.experimental_close_on_slow_client);
+        assert_eq!(config.mempool.version, "v1");
+        assert_eq!(*config.mempool.ttl_duration, Duration::from_secs(30));
+        assert_eq!(config.mempool.ttl_num_blocks, 10);
+        assert_eq!(
+            *config.statesync.chunk_request_timeout,
+            Duration::from_secs(15)
+        );
+        assert_eq!(config.statesync.chunk_fetchers, 8);
+        assert_eq!(config.tx_index.indexer, TxIndexer::Psql);
+        assert_eq!(
+            config.tx_index.psql_conn.as_deref(),
+            Some("postgresql://tendermint@localhost:5432/tendermint")
+        );
+        assert!(config.storage.discard_abci_responses);
+        config.validate().unwrap();
+
+        let written = toml::to_string(&config).unwrap();
+        assert_eq!(TendermintConfig::parse_toml(written).unwrap(), config);
+
+        let integer = TendermintConfig::parse_toml("[statesync]\nchunk_fetchers = 2").unwrap();
+        assert_eq!(integer.statesync.chunk_fetchers, 2);
+    }
+
+    /// Validate configurations as Tendermint Core does
+    #[test]
+    fn config_toml_validation() {
+        TendermintConfig::default().validate().unwrap();
+        TendermintConfig::parse_toml(read_fixture("config.toml"))
+            .unwrap()
+            .validate()
+            .unwrap();
+
+        let mut config = TendermintConfig::default();
+        config.statesync.enable = true;
+        assert!(config.validate().is_err());
+        config.statesync.rpc_servers = vec![
+            "tcp://rpc-1.example.com:26657".to_string(),
+            "tcp://rpc-2.example.com:26657".to_string(),
+        ];
+        config.statesync.trust_height = 1000;
+        config.statesync.trust_hash =
+            "C6C4FEB72FFD4E1B6E4D5F5DD68E2B2D0A4C0E8B3E1C0F6A6B0C2D0F4D3B2A1C".to_string();
+        config.validate().unwrap();
+        config.statesync.discovery_time = Duration::from_secs(1).into();
+        assert!(config.validate().is_err());
+        config.statesync.discovery_time = Duration::from_secs(0).into();
+        config.validate().unwrap();
+        config.statesync.chunk_fetchers = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = TendermintConfig::default();
+        config.fastsync.version = "v3".to_string();
+        assert!(config.validate().is_err());
+
+        let mut config = TendermintConfig::default();
+        config.mempool.version = "v2".to_string();
+        assert!(config.validate().is_err());
+
+        let mut config = TendermintConfig::default();
+        config.rpc.experimental_subscription_buffer_size = 50;
+        assert!(config.validate().is_err());
+        config.rpc.experimental_subscription_buffer_size = 300;
+        assert!(config.validate().is_err());
+
+        let mut config = TendermintConfig::default();
+        config.tx_index.indexer = TxIndexer::Psql;
+        assert!(config.validate().is_err());
+    }
+
     /// Parse an example `node_key.json` file to a `NodeKey` struct
     #[test]
     fn node_key_parser() {
@@ -217,6 +349,41 @@ mod files {
             priv_validator_key.consensus_pubkey().to_hex(),
             "F26BF4B2A2E84CEB7A53C3F1AE77408779B20064782FBADBDF0E365959EE4534"
         );
+
+        // Only the public key may be shown
+        let secret = priv_validator_key
+            .priv_key
+            .ed25519_keypair()
+            .unwrap()
+            .secret
+            .to_bytes();
+        let debug = format!("{:?}", priv_validator_key);
+        assert!(debug.contains("PrivateKey"));
+        let secret_prefix = format!("{:?}", &secret[..4]);
+        assert!(!debug.contains(secret_prefix.trim_end_matches(']')));
+    }
+
+    /// Parse an example `priv_validator_state.json` to a `PrivValidatorState` struct,
+    /// then serialize it and parse again
+    #[test]
+    fn priv_validator_state_json_parser() {
+        let raw_state = read_fixture("priv_validator_state.json");
+        let state = PrivValidatorState::parse_json(raw_state).unwrap();
+        assert_eq!(state.height.value(), 1608);
+        assert_eq!(state.round.value(), 0);
+        assert_eq!(state.step, 3);
+        assert_eq!(state.signature.len(), 64);
+        assert_eq!(state.sign_bytes.len(), 112);
+
+        let written_state = PrivValidatorState::parse_json(state.to_json().unwrap()).unwrap();
+        assert_eq!(state, written_state);
+
+        let empty = PrivValidatorState::parse_json(r#"{"height":"0","round":0,"step":0}"#).unwrap();
+        assert_eq!(empty, PrivValidatorState::default());
+        assert_eq!(
+            empty.to_json().unwrap(),
+            "{\n  \"height\": \"0\",\n  \"round\": 0,\n  \"step\": 0\n}"
+        );
     }
 
     /// Parse an example `config.toml` file to a `TendermintConfig` struct, then
@@ -0,0 +1,237 @@
+//! Golden tests of the JSON and Protobuf encodings of domain types.
+//!
+//! Each domain type has fixtures in the `tests/support/golden` subdirectory:
+//!
+//! - `<name>.json`: its JSON encoding
+//! - `<name>.go.pb.hex` or `<name>.pb.hex`: its Protobuf encoding, in
+//!   hexadecimal
+//!
+//! The values parsed from the JSON fixtures must encode back into both
+//! fixtures, and the Protobuf fixtures must decode into values which encode
+//! back into them, so that any change to the encodings shows up in these
+//! tests.
+//!
+//! The `.go.pb.hex` fixtures are encoded by Go nodes: they are the block
+//! served by a Go node (see `tests/support/serialization/block`), whose part
+//! set hash the node computed, and messages embedded in this block, which
+//! `go_fixtures_are_encoded_by_go` checks. Their JSON fixtures come from the
+//! same responses of the node. These fixtures are never regenerated, as an
+//! encoding that no longer matches them is no longer compatible with Go.
+//!
+//! The `.pb.hex` fixtures are encoded by tendermint-rs. When a change to
+//! their encoding is intended, regenerate them by running the tests with the
+//! `TENDERMINT_REGENERATE_FIXTURES` environment variable set:
+//!
+//! ```text
+//! TENDERMINT_REGENERATE_FIXTURES=1 cargo test -p tendermint --test golden
+//! ```
+
+mod golden {
+    use pretty_assertions::assert_eq;
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::{
+        convert::TryFrom,
+        env,
+        fmt::Debug,
+        fs,
+        path::{Path, PathBuf},
+    };
+    use subtle_encoding::hex;
+    use tendermint::{
+        block, consensus, evidence, merkle, validator, vote::Vote, Block, PublicKey, Time,
+    };
+    use tendermint_proto::Protobuf;
+
+    /// Environment variable requesting the fixtures to be regenerated
+    const REGENERATE: &str = "TENDERMINT_REGENERATE_FIXTURES";
+
+    fn fixture_path(file_name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/support/golden")
+            .join(file_name)
+    }
+
+    fn regenerate() -> bool {
+        env::var_os(REGENERATE).is_some()
+    }
+
+    fn read_hex(path: &Path) -> Vec<u8> {
+        let encoded = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+        hex::decode(encoded.trim()).unwrap()
+    }
+
+    /// Check the encodings of the domain type `T` against its fixtures, or
+    /// regenerate them
+    fn check_fixtures<T, R>(name: &str)
+    where
+        T: Debug + PartialEq + Serialize + DeserializeOwned + Protobuf<R>,
+        R: prost::Message + Default + From<T>,
+        T: TryFrom<R>,
+        <T as TryFrom<R>>::Error: std::fmt::Display,
+    {
+        let json_path = fixture_path(&format!("{}.json", name));
+        let go_proto_path = fixture_path(&format!("{}.go.pb.hex", name));
+        let from_go = go_proto_path.exists();
+        let proto_path = if from_go {
+            go_proto_path
+        } else {
+            fixture_path(&format!("{}.pb.hex", name))
+        };
+
+        let json = fs::read_to_string(&json_path).unwrap();
+        let value: T = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("cannot parse {}: {}", json_path.display(), e));
+
+        let encoded_json = serde_json::to_string_pretty(&value).unwrap() + "\n";
+        let encoded_proto =
+            String::from_utf8(hex::encode(value.encode_vec().unwrap())).unwrap() + "\n";
+
+        if regenerate() && !from_go {
+            fs::write(&json_path, &encoded_json).unwrap();
+            fs::write(&proto_path, &encoded_proto).unwrap();
+            return;
+        }
+
+        let expected_json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let actual_json: serde_json::Value = serde_json::from_str(&encoded_json).unwrap();
+        assert_eq!(actual_json, expected_json, "JSON encoding of {}", name);
+
+        let expected_proto = fs::read_to_string(&proto_path).unwrap_or_else(|e| {
+            panic!(
+                "cannot read {}: {} (set {} to generate it)",
+                proto_path.display(),
+                e,
+                REGENERATE
+            )
+        });
+        assert_eq!(
+            encoded_proto,
+            expected_proto,
+            "Protobuf encoding of {}{}",
+            name,
+            if from_go {
+                " (encoded by Go, which must not be regenerated)"
+            } else {
+                ""
+            }
+        );
+
+        let bytes = hex::decode(expected_proto.trim()).unwrap();
+        let decoded = T::decode_vec(&bytes)
+            .unwrap_or_else(|e| panic!("cannot decode {}: {}", proto_path.display(), e));
+        // Not all of the fields of the JSON encoding are in the Protobuf
+        // encoding, eg. the names of validators
+        assert_eq!(
+            decoded.encode_vec().unwrap(),
+            bytes,
+            "Protobuf decoding of {}",
+            name
+        );
+    }
+
+    /// The fixtures encoded by Go are the block served by a Go node, whose
+    /// part set hash matches the one of the node, and messages embedded in it
+    #[test]
+    fn go_fixtures_are_encoded_by_go() {
+        let response = fs::read_to_string(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/support/serialization/block/block_from_go_node.json"),
+        )
+        .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        let block_id: block::Id = serde_json::from_value(response["block_id"].clone()).unwrap();
+
+        let block = read_hex(&fixture_path("block.go.pb.hex"));
+        let parts = block
+            .chunks(block::parts::BLOCK_PART_SIZE_BYTES)
+            .map(<[u8]>::to_vec)
+            .collect();
+        assert_eq!(
+            merkle::simple_hash_from_byte_vectors(parts),
+            block_id.part_set_header.hash.as_bytes(),
+            "part set hash of block.go.pb.hex"
+        );
+
+        for entry in fs::read_dir(fixture_path("")).unwrap() {
+            let path = entry.unwrap().path();
+            let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+            if !file_name.ends_with(".go.pb.hex") || file_name == "block.go.pb.hex" {
+                continue;
+            }
+            let message = read_hex(&path);
+            assert!(
+                block
+                    .windows(message.len())
+                    .any(|window| window == message.as_slice()),
+                "{} is not embedded in the block encoded by Go",
+                file_name
+            );
+        }
+    }
+
+    #[test]
+    fn block() {
+        check_fixtures::<Block, _>("block");
+    }
+
+    #[test]
+    fn block_id() {
+        check_fixtures::<block::Id, _>("block_id");
+    }
+
+    #[test]
+    fn header() {
+        check_fixtures::<block::Header, _>("header");
+    }
+
+    #[test]
+    fn commit() {
+        check_fixtures::<block::Commit, _>("commit");
+    }
+
+    #[test]
+    fn signed_header() {
+        check_fixtures::<block::signed_header::SignedHeader, _>("signed_header");
+    }
+
+    #[test]
+    fn validator() {
+        check_fixtures::<validator::Info, _>("validator");
+    }
+
+    #[test]
+    fn consensus_params() {
+        check_fixtures::<consensus::Params, _>("consensus_params");
+    }
+
+    #[test]
+    fn part_set_header() {
+        check_fixtures::<block::parts::Header, _>("part_set_header");
+    }
+
+    #[test]
+    fn time() {
+        check_fixtures::<Time, _>("time");
+    }
+
+    #[test]
+    fn validator_set() {
+        check_fixtures::<validator::Set, _>("validator_set");
+    }
+
+    #[test]
+    fn public_key() {
+        check_fixtures::<PublicKey, _>("public_key");
+    }
+
+    #[test]
+    fn vote() {
+        check_fixtures::<Vote, _>("vote");
+    }
+
+    #[test]
+    fn evidence() {
+        check_fixtures::<evidence::Evidence, _>("evidence");
+    }
+}
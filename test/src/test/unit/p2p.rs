@@ -1 +1,2 @@
+mod privval;
 mod secret_connection;
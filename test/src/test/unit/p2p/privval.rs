@@ -0,0 +1,250 @@
+use std::convert::TryFrom;
+use std::net::TcpListener;
+use std::thread;
+
+use ed25519_dalek::{self as ed25519};
+use rand_core::OsRng;
+
+use tendermint::config::{PrivValidatorKey, PrivValidatorState};
+use tendermint::privval::FilePrivValidator;
+use tendermint::signer::Signer;
+use tendermint::{account, block, chain, proposal, vote, Hash, PrivateKey, Signature, Time};
+use tendermint_p2p::error::ErrorDetail;
+use tendermint_p2p::privval::{read_message, SignerClient, SignerServer, MAX_MESSAGE_SIZE};
+
+fn chain_id() -> chain::Id {
+    chain::Id::try_from("test-chain").unwrap()
+}
+
+fn file_priv_validator(name: &str) -> FilePrivValidator {
+    let state_file = std::env::temp_dir().join(format!(
+        "tendermint-p2p-privval-{}-{}.json",
+        name,
+        std::process::id()
+    ));
+    FilePrivValidator::new(
+        PrivValidatorKey::generate(),
+        PrivValidatorState::default(),
+        state_file,
+    )
+}
+
+fn block_id(byte: u8) -> block::Id {
+    let hash = Hash::from_bytes(tendermint::hash::Algorithm::Sha256, &[byte; 32]).unwrap();
+    block::Id {
+        hash,
+        part_set_header: block::parts::Header::new(1, hash).unwrap(),
+    }
+}
+
+fn prevote(validator: &FilePrivValidator, block_id: block::Id) -> vote::Vote {
+    vote::Vote {
+        vote_type: vote::Type::Prevote,
+        height: block::Height::from(10_u32),
+        round: block::Round::from(1_u16),
+        block_id: Some(block_id),
+        timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+        validator_address: validator.address(),
+        validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+        signature: Signature::default(),
+    }
+}
+
+#[test]
+fn test_sign_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    let validator = file_priv_validator("tcp");
+    let pub_key = validator.public_key();
+    let vote = prevote(&validator, block_id(1));
+    let conflicting_vote = prevote(&validator, block_id(2));
+    let state_file = std::env::temp_dir().join(format!(
+        "tendermint-p2p-privval-tcp-{}.json",
+        std::process::id()
+    ));
+
+    let signer = thread::spawn(move || {
+        let key = ed25519::Keypair::generate(&mut OsRng {});
+        let mut server =
+            SignerServer::connect_tcp(addr, key, validator).expect("to connect to the node");
+
+        // serve until the node closes the connection
+        assert!(server.serve().is_err());
+    });
+
+    let node_key = ed25519::Keypair::generate(&mut OsRng {});
+    let mut client = SignerClient::accept_tcp(&listener, node_key).expect("handshake to succeed");
+
+    client.ping().unwrap();
+    assert_eq!(client.public_key(&chain_id()).unwrap(), pub_key);
+
+    let signed_vote = client.sign_vote(&chain_id(), vote.clone()).unwrap();
+    signed_vote.verify_signature(chain_id(), &pub_key).unwrap();
+    assert_eq!(client.sign_vote(&chain_id(), vote).unwrap(), signed_vote);
+
+    // the remote signer refuses to double sign, and keeps answering requests
+    assert!(client.sign_vote(&chain_id(), conflicting_vote).is_err());
+
+    let proposal = proposal::Proposal {
+        msg_type: proposal::Type::Proposal,
+        height: block::Height::from(11_u32),
+        round: block::Round::from(0_u16),
+        pol_round: None,
+        block_id: Some(block_id(3)),
+        timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:30Z").unwrap()),
+        signature: Signature::default(),
+    };
+    let signed_proposal = client.sign_proposal(&chain_id(), proposal).unwrap();
+    signed_proposal.verify(chain_id(), &pub_key).unwrap();
+
+    drop(client);
+    signer.join().expect("signer thread has panicked");
+    std::fs::remove_file(state_file).unwrap();
+}
+
+#[test]
+fn test_remote_signer() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    let validator = file_priv_validator("remote");
+    let pub_key = validator.public_key();
+
+    let signer = thread::spawn(move || {
+        let secret_key = ed25519::Keypair::generate(&mut OsRng {});
+        let mut server =
+            SignerServer::connect_tcp(addr, secret_key, validator).expect("to connect to the node");
+        for _ in 0..3 {
+            server.handle_request().unwrap();
+        }
+    });
+
+    let node_key = ed25519::Keypair::generate(&mut OsRng {});
+    let mut client = SignerClient::accept_tcp(&listener, node_key).expect("handshake to succeed");
+
+    // the node only uses the client as a signer
+    let remote: &mut dyn Signer = &mut client;
+    assert_eq!(remote.public_key(&chain_id()).unwrap(), pub_key);
+
+    let mut vote = vote::Vote {
+        vote_type: vote::Type::Prevote,
+        height: block::Height::from(10_u32),
+        round: block::Round::from(1_u16),
+        block_id: Some(block_id(1)),
+        timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+        validator_address: account::Id::from(pub_key),
+        validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+        signature: Signature::default(),
+    };
+    remote.sign_vote(&chain_id(), &mut vote).unwrap();
+    vote.verify_signature(chain_id(), &pub_key).unwrap();
+
+    let mut proposal = proposal::Proposal {
+        msg_type: proposal::Type::Proposal,
+        height: block::Height::from(11_u32),
+        round: block::Round::from(0_u16),
+        pol_round: None,
+        block_id: Some(block_id(2)),
+        timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:30Z").unwrap()),
+        signature: Signature::default(),
+    };
+    remote.sign_proposal(&chain_id(), &mut proposal).unwrap();
+    proposal.verify(chain_id(), &pub_key).unwrap();
+
+    signer.join().expect("signer thread has panicked");
+}
+
+#[test]
+fn test_serve_private_key() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("to be able to bind to 127.0.0.1");
+    let addr = listener.local_addr().unwrap();
+
+    // any signer can be served, here one without double signing protection
+    let private_key = PrivateKey::Ed25519(ed25519::Keypair::generate(&mut OsRng {}));
+    let pub_key = private_key.public_key();
+
+    let signer = thread::spawn(move || {
+        let key = ed25519::Keypair::generate(&mut OsRng {});
+        let mut server =
+            SignerServer::connect_tcp(addr, key, private_key).expect("to connect to the node");
+        for _ in 0..3 {
+            server.handle_request().unwrap();
+        }
+    });
+
+    let node_key = ed25519::Keypair::generate(&mut OsRng {});
+    let mut client = SignerClient::accept_tcp(&listener, node_key).expect("handshake to succeed");
+    assert_eq!(client.public_key(&chain_id()).unwrap(), pub_key);
+
+    let vote = vote::Vote {
+        vote_type: vote::Type::Prevote,
+        height: block::Height::from(10_u32),
+        round: block::Round::from(1_u16),
+        block_id: Some(block_id(1)),
+        timestamp: Some(Time::parse_from_rfc3339("2021-07-16T12:16:29Z").unwrap()),
+        validator_address: account::Id::from(pub_key),
+        validator_index: vote::ValidatorIndex::try_from(0_u32).unwrap(),
+        signature: Signature::default(),
+    };
+    let signed_vote = client.sign_vote(&chain_id(), vote.clone()).unwrap();
+    signed_vote.verify_signature(chain_id(), &pub_key).unwrap();
+
+    let conflicting_vote = vote::Vote {
+        block_id: Some(block_id(2)),
+        ..vote
+    };
+    let signed_vote = client.sign_vote(&chain_id(), conflicting_vote).unwrap();
+    signed_vote.verify_signature(chain_id(), &pub_key).unwrap();
+
+    signer.join().expect("signer thread has panicked");
+}
+
+#[test]
+fn test_read_message_rejects_malformed_length_prefix() {
+    // The 10th byte of the varint still has its continuation bit set
+    let mut bytes: &[u8] = &[0x80; 11];
+    match read_message(&mut bytes) {
+        Err(e) => assert!(matches!(e.detail(), ErrorDetail::MalformedMessageLength(_))),
+        Ok(message) => panic!("expected an error, got {:?}", message),
+    }
+
+    // A length of 10241 bytes, as a varint
+    assert_eq!(MAX_MESSAGE_SIZE, 10240);
+    let mut bytes: &[u8] = &[0x81, 0x50];
+    match read_message(&mut bytes) {
+        Err(e) => assert!(matches!(e.detail(), ErrorDetail::MessageTooLarge(_))),
+        Ok(message) => panic!("expected an error, got {:?}", message),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sign_over_unix_socket() {
+    use std::os::unix::net::UnixListener;
+
+    let socket = std::env::temp_dir().join(format!(
+        "tendermint-p2p-privval-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&socket);
+    let listener = UnixListener::bind(&socket).expect("to be able to bind the socket");
+
+    let validator = file_priv_validator("unix");
+    let pub_key = validator.public_key();
+
+    let path = socket.clone();
+    let signer = thread::spawn(move || {
+        let mut server =
+            SignerServer::connect_unix(path, validator).expect("to connect to the node");
+        server.handle_request().unwrap();
+        server.handle_request().unwrap();
+    });
+
+    let mut client = SignerClient::accept_unix(&listener).expect("to accept the signer");
+    client.ping().unwrap();
+    assert_eq!(client.public_key(&chain_id()).unwrap(), pub_key);
+
+    signer.join().expect("signer thread has panicked");
+    std::fs::remove_file(socket).unwrap();
+}
@@ -61,6 +61,38 @@ fn test_read_write_single_message() {
     receiver.join().expect("receiver thread has panicked");
 }
 
+#[test]
+fn test_read_write_large_message_in_small_reads() {
+    // Spans several frames, which are read a few bytes at a time.
+    let message: Vec<u8> = (0..5000_u32).map(|i| i as u8).collect();
+    let expected = message.clone();
+
+    let (pipe1, pipe2) = pipe::async_bipipe_buffered();
+
+    let sender = thread::spawn(move || {
+        let mut conn1 = new_peer_conn(pipe2).expect("handshake to succeed");
+
+        conn1
+            .write_all(&message)
+            .expect("expected to write message");
+    });
+
+    let receiver = thread::spawn(move || {
+        let mut conn2 = new_peer_conn(pipe1).expect("handshake to succeed");
+
+        let mut received = Vec::new();
+        while received.len() < expected.len() {
+            let mut buf = [0; 3];
+            let n = conn2.read(&mut buf).expect("expected to read message");
+            received.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(received, expected);
+    });
+
+    sender.join().expect("sender thread has panicked");
+    receiver.join().expect("receiver thread has panicked");
+}
+
 #[test]
 fn test_evil_peer_shares_invalid_eph_key() {
     let mut csprng = OsRng {};
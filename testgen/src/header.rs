@@ -111,9 +111,15 @@ impl Generator<block::Header> for Header {
         } else {
             Validator::new("a").generate().unwrap().address
         };
-        let valset = validator::Set::without_proposer(vals);
+        let valset = try_with!(
+            validator::Set::without_proposer(vals),
+            "invalid validator set"
+        );
         let next_valset = match &self.next_validators {
-            Some(next_vals) => validator::Set::without_proposer(generate_validators(next_vals)?),
+            Some(next_vals) => try_with!(
+                validator::Set::without_proposer(generate_validators(next_vals)?),
+                "invalid next validator set"
+            ),
             None => valset.clone(),
         };
         let chain_id = match chain::Id::from_str(
@@ -208,17 +208,24 @@ impl Generator<TmLightBlock> for LightBlock {
             generate_signed_header(header, commit).expect("Could not generate signed header");
 
         let validators = match &self.validators {
-            None => validator::Set::without_proposer(generate_validators(
+            None => generate_validators(
                 header
                     .validators
                     .as_ref()
                     .expect("missing validators in header"),
-            )?),
-            Some(vals) => validator::Set::without_proposer(generate_validators(vals)?),
+            )?,
+            Some(vals) => generate_validators(vals)?,
         };
+        let validators = try_with!(
+            validator::Set::without_proposer(validators),
+            "invalid validator set"
+        );
 
         let next_validators = match &self.next_validators {
-            Some(next_vals) => validator::Set::without_proposer(generate_validators(next_vals)?),
+            Some(next_vals) => try_with!(
+                validator::Set::without_proposer(generate_validators(next_vals)?),
+                "invalid next validator set"
+            ),
             None => validators.clone(),
         };
 
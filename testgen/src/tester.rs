@@ -29,7 +29,7 @@ impl TestEnv {
     pub fn push(&self, child: &str) -> Option<Self> {
         let mut path = PathBuf::from(&self.current_dir);
         path.push(child);
-        path.to_str().and_then(|path| TestEnv::new(path))
+        path.to_str().and_then(TestEnv::new)
     }
 
     pub fn current_dir(&self) -> &str {
@@ -256,7 +256,7 @@ impl Tester {
                 *result = Failure { message, location };
             })
         });
-        let result = panic::catch_unwind(|| test());
+        let result = panic::catch_unwind(test);
         panic::set_hook(old_hook);
         match result {
             Ok(_) => Success,
@@ -296,7 +296,7 @@ impl Tester {
                 let output_dir = output_env.full_path(path);
                 let output_env = TestEnv::new(output_dir.to_str().unwrap()).unwrap();
                 test(test_case, &env, &test_env, &output_env);
-                fs::remove_dir_all(&env.current_dir()).unwrap();
+                fs::remove_dir_all(env.current_dir()).unwrap();
             }),
             Err(e) => ParseError(e),
         };
@@ -319,9 +319,7 @@ impl Tester {
     }
 
     fn results_for(&mut self, name: &str) -> &mut Vec<(String, TestResult)> {
-        self.results
-            .entry(name.to_string())
-            .or_insert_with(Vec::new)
+        self.results.entry(name.to_string()).or_default()
     }
 
     fn add_result(&mut self, name: &str, path: &str, result: TestResult) {
@@ -437,7 +435,7 @@ impl Tester {
     pub fn run_foreach_in_dir(&mut self, dir: &str) {
         let full_dir = PathBuf::from(&self.root_dir).join(dir);
         let starts_with_underscore = |entry: &DirEntry| {
-            if let Some(last) = entry.path().iter().rev().next() {
+            if let Some(last) = entry.path().iter().next_back() {
                 if let Some(last) = last.to_str() {
                     if last.starts_with('_') {
                         return true;
@@ -460,9 +458,7 @@ impl Tester {
                             let path = format!("{}", entry.path().display());
                             let rel_path = self.env().unwrap().rel_path(&path).unwrap();
                             if kind.is_file() || kind.is_symlink() {
-                                if !rel_path.ends_with(".json") {
-                                    return;
-                                } else {
+                                if rel_path.ends_with(".json") {
                                     self.run_for_file(&rel_path);
                                 }
                             } else if kind.is_dir() {
@@ -76,6 +76,9 @@ fn make_supervisor() -> Supervisor {
         trust_threshold: TrustThreshold::new(1, 3).unwrap(),
         trusting_period: Duration::from_secs(60 * 60), // 60 minutes
         clock_drift: Duration::from_secs(5 * 60),      // 5 minutes
+        max_bisection_steps: None,
+        trust_threshold_overrides: Vec::new(),
+        forward_clock_drift: None,
     };
 
     let primary_instance = make_instance(primary, options, node_address.clone());
@@ -100,8 +103,11 @@ fn forward() {
         println!("[info ] - iteration {}/{}", i, max_iterations);
 
         match handle.verify_to_highest() {
-            Ok(light_block) => {
-                println!("[info ] synced to block {}", light_block.height());
+            Ok(verified) => {
+                println!(
+                    "[info ] synced to block {}",
+                    verified.light_block().height()
+                );
             }
             Err(err) => {
                 println!("[error] sync failed: {}", err);
@@ -129,14 +135,14 @@ fn backward() -> Result<(), Error> {
         println!("[info ] - iteration {}/{}", i, max_iterations);
 
         // First we sync to the highest block to have a high enough trusted state
-        let trusted_state = handle.verify_to_highest()?;
+        let trusted_state = handle.verify_to_highest()?.into_light_block();
         println!("[info ] synced to highest block {}", trusted_state.height());
 
         // Then we pick a height below the trusted state
         let target_height = Height::try_from(trusted_state.height().value() / 2).unwrap();
 
         // We now try to verify a block at this height
-        let light_block = handle.verify_to_target(target_height)?;
+        let light_block = handle.verify_to_target(target_height)?.into_light_block();
         println!("[info ] verified lower block {}", light_block.height());
 
         std::thread::sleep(Duration::from_millis(800));
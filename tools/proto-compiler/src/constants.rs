@@ -26,6 +26,7 @@ const NULLABLEVECARRAY: &str = r#"#[serde(with = "crate::serializers::txs")]"#;
 const NULLABLE: &str = r#"#[serde(with = "crate::serializers::nullable")]"#;
 const ALIAS_POWER_QUOTED: &str =
     r#"#[serde(alias = "power", with = "crate::serializers::from_str")]"#;
+const INT_OR_STRING: &str = r#"#[serde(with = "crate::serializers::int_or_string")]"#;
 const PART_SET_HEADER_TOTAL: &str =
     r#"#[serde(with = "crate::serializers::part_set_header_total")]"#;
 const RENAME_EDPUBKEY: &str = r#"#[serde(rename = "tendermint/PubKeyEd25519", with = "crate::serializers::bytes::base64string")]"#;
@@ -118,6 +119,7 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.types.Data.txs", NULLABLEVECARRAY),
     (".tendermint.types.EvidenceList.evidence", NULLABLE),
     (".tendermint.types.Commit.height", QUOTED),
+    (".tendermint.types.Commit.round", INT_OR_STRING),
     (".tendermint.types.Commit.signatures", NULLABLE),
     (".tendermint.types.CommitSig.validator_address", HEXSTRING),
     (".tendermint.types.CommitSig.timestamp", OPTIONAL),
@@ -159,3 +161,13 @@ pub static CUSTOM_FIELD_ATTRIBUTES: &[(&str, &str)] = &[
     (".tendermint.crypto.Proof.aunts", VEC_BASE64STRING),
     (".tendermint.crypto.Proof.leaf_hash", BASE64STRING),
 ];
+
+/// Bytes fields generated as reference-counted `bytes::Bytes` rather than `Vec<u8>`,
+/// so that transactions are shared instead of copied once decoded.
+/// The paths are as defined in the prost_build::Config::bytes here:
+/// https://docs.rs/prost-build/0.7.0/prost_build/struct.Config.html#method.bytes
+pub static BYTES_FIELDS: &[&str] = &[
+    ".tendermint.abci.RequestCheckTx.tx",
+    ".tendermint.abci.RequestDeliverTx.tx",
+    ".tendermint.types.Data.txs",
+];
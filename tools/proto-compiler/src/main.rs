@@ -7,7 +7,8 @@ use functions::{copy_files, find_proto_files, generate_tendermint_lib, get_commi
 
 mod constants;
 use constants::{
-    CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_COMMITISH, TENDERMINT_REPO,
+    BYTES_FIELDS, CUSTOM_FIELD_ATTRIBUTES, CUSTOM_TYPE_ATTRIBUTES, TENDERMINT_COMMITISH,
+    TENDERMINT_REPO,
 };
 
 fn main() {
@@ -64,6 +65,7 @@ fn main() {
     for field_attribute in CUSTOM_FIELD_ATTRIBUTES {
         pb.field_attribute(field_attribute.0, field_attribute.1);
     }
+    pb.bytes(BYTES_FIELDS);
     pb.compile_well_known_types();
     // The below in-place path redirection removes the Duration and Timestamp structs from
     // google.protobuf.rs. We replace them with our own versions that have valid doctest comments.